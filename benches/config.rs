@@ -2,8 +2,13 @@
 //! Benchmarks for configuration serialization and deserialization
 
 use criterion::{Criterion, criterion_group, criterion_main};
-use easyhdr::config::models::Win32App;
-use easyhdr::config::{AppConfig, MonitoredApp, UserPreferences, WindowState};
+use easyhdr::config::models::{AppSource, Win32App};
+use easyhdr::config::{
+    AppConfig, AutoStartMethod, BatteryHdrPolicy, MonitoredApp, ShutdownPolicy, UpdateChannel,
+    UserPreferences, WindowState,
+};
+use easyhdr::hdr::AutoHdrMode;
+use easyhdr::utils::{LogFormat, LogLevel, UpdateCheckCache};
 use std::hint::black_box;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -11,17 +16,50 @@ use uuid::Uuid;
 fn create_large_config() -> AppConfig {
     let mut config = AppConfig {
         monitored_apps: Vec::with_capacity(100),
+        excluded_processes: Vec::new(),
         preferences: UserPreferences {
             auto_start: true,
+            autostart_method: AutoStartMethod::Registry,
+            autostart_elevated: false,
+            autostart_delay_secs: 0,
             monitoring_interval_ms: 1000,
             show_tray_notifications: true,
             show_update_notifications: true,
             auto_open_release_page: false,
+            update_channel: UpdateChannel::Stable,
             minimize_to_tray_on_minimize: true,
             minimize_to_tray_on_close: false,
             start_minimized_to_tray: false,
             last_update_check_time: 0,
             cached_latest_version: String::new(),
+            update_check_cache: UpdateCheckCache::default(),
+            uwp_enumerate_all_users: false,
+            track_child_processes: true,
+            wait_for_window: false,
+            hdr_disable_debounce_ms: 500,
+            weekly_summary_notification: false,
+            last_weekly_summary_time: 0,
+            log_level: LogLevel::Info,
+            log_format: LogFormat::Text,
+            shutdown_policy: ShutdownPolicy::LeaveAsIs,
+            max_hdr_session_minutes: 0,
+            external_change_hold_minutes: 0,
+            battery_hdr_policy: BatteryHdrPolicy::Allow,
+            battery_hdr_threshold_percent: 20,
+            icon_cache_max_mb: 16,
+            adaptive_polling: false,
+            max_idle_polling_interval_ms: 5000,
+            suspend_night_light: false,
+            onboarding_completed: true,
+            language: String::new(),
+            allow_unsupported_windows: false,
+            dry_run: false,
+            write_event_log: false,
+            metrics_enabled: false,
+            trace_hdr_api: false,
+            discord_presence: false,
+            discord_client_id: String::new(),
+            run_in_background: false,
         },
         window_state: WindowState {
             x: 100,
@@ -29,6 +67,8 @@ fn create_large_config() -> AppConfig {
             width: 800,
             height: 600,
         },
+        known_displays: Vec::new(),
+        display_groups: Vec::new(),
     };
 
     // Add 100 monitored apps to simulate a large configuration
@@ -39,6 +79,16 @@ fn create_large_config() -> AppConfig {
             exe_path: PathBuf::from(format!("C:\\Games\\Game{i}\\game.exe")),
             process_name: format!("game{i}"),
             enabled: i % 2 == 0,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
     }
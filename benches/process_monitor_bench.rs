@@ -5,8 +5,15 @@
 //! See `docs/performance_plan.md` for profiling instructions.
 
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
-use easyhdr::config::models::Win32App;
-use easyhdr::config::{AppConfig, MonitoredApp, UserPreferences, WindowState};
+use easyhdr::config::models::{AppSource, Win32App};
+use easyhdr::config::{
+    AppConfig, AutoStartMethod, BatteryHdrPolicy, MonitoredApp, ShutdownPolicy, UpdateChannel,
+    UserPreferences, WindowState,
+};
+use easyhdr::hdr::AutoHdrMode;
+use easyhdr::monitor::{AppIdentifier, WatchState};
+use easyhdr::utils::{LogFormat, LogLevel, UpdateCheckCache, normalize_process_name};
+use std::collections::HashMap;
 use std::hint::black_box;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -16,17 +23,50 @@ use uuid::Uuid;
 fn create_mock_config(num_monitored: usize) -> Arc<parking_lot::RwLock<AppConfig>> {
     let mut config = AppConfig {
         monitored_apps: Vec::with_capacity(num_monitored),
+        excluded_processes: Vec::new(),
         preferences: UserPreferences {
             auto_start: false,
+            autostart_method: AutoStartMethod::Registry,
+            autostart_elevated: false,
+            autostart_delay_secs: 0,
             monitoring_interval_ms: 1000,
             show_tray_notifications: false,
             show_update_notifications: false,
             auto_open_release_page: false,
+            update_channel: UpdateChannel::Stable,
             minimize_to_tray_on_minimize: false,
             minimize_to_tray_on_close: false,
             start_minimized_to_tray: false,
             last_update_check_time: 0,
             cached_latest_version: String::new(),
+            update_check_cache: UpdateCheckCache::default(),
+            uwp_enumerate_all_users: false,
+            track_child_processes: true,
+            wait_for_window: false,
+            hdr_disable_debounce_ms: 500,
+            weekly_summary_notification: false,
+            last_weekly_summary_time: 0,
+            log_level: LogLevel::Info,
+            log_format: LogFormat::Text,
+            shutdown_policy: ShutdownPolicy::LeaveAsIs,
+            max_hdr_session_minutes: 0,
+            external_change_hold_minutes: 0,
+            battery_hdr_policy: BatteryHdrPolicy::Allow,
+            battery_hdr_threshold_percent: 20,
+            icon_cache_max_mb: 16,
+            adaptive_polling: false,
+            max_idle_polling_interval_ms: 5000,
+            suspend_night_light: false,
+            onboarding_completed: true,
+            language: String::new(),
+            allow_unsupported_windows: false,
+            dry_run: false,
+            write_event_log: false,
+            metrics_enabled: false,
+            trace_hdr_api: false,
+            discord_presence: false,
+            discord_client_id: String::new(),
+            run_in_background: false,
         },
         window_state: WindowState {
             x: 100,
@@ -34,6 +74,8 @@ fn create_mock_config(num_monitored: usize) -> Arc<parking_lot::RwLock<AppConfig
             width: 800,
             height: 600,
         },
+        known_displays: Vec::new(),
+        display_groups: Vec::new(),
     };
 
     // Add monitored apps with realistic process names
@@ -58,6 +100,16 @@ fn create_mock_config(num_monitored: usize) -> Arc<parking_lot::RwLock<AppConfig
             exe_path: PathBuf::from(format!("C:\\Program Files\\{display_name}\\{process_name}")),
             process_name: process_name.to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
     }
@@ -68,7 +120,8 @@ fn create_mock_config(num_monitored: usize) -> Arc<parking_lot::RwLock<AppConfig
 /// Benchmark monitored app lookups
 ///
 /// This simulates the event handling pattern where we check if a process
-/// is in the monitored list. Currently uses O(n) iteration, target is O(1) `HashSet`.
+/// is in the monitored list with O(n) iteration. The process monitor itself uses the
+/// hashed index measured by `bench_watch_state_matching`.
 fn bench_monitored_app_lookup(c: &mut Criterion) {
     let mut group = c.benchmark_group("monitored_app_lookup");
 
@@ -331,12 +384,145 @@ fn bench_poll_processes_simulation(c: &mut Criterion) {
     group.finish();
 }
 
+/// Snapshot of `num_processes` processes, three of which are on the mock watch list
+fn create_mock_snapshot(num_processes: u32) -> Vec<(u32, String, u32)> {
+    (0..num_processes)
+        .map(|i| {
+            let exe_file = match i {
+                0 => "chrome.exe".to_string(),
+                1 => "obs64.exe".to_string(),
+                2 => "steam.exe".to_string(),
+                _ => format!("process_{i}.exe"),
+            };
+            (1000 + i * 4, exe_file, 4)
+        })
+        .collect()
+}
+
+/// Benchmark matching a process snapshot against the watch list
+///
+/// Compares walking the watch list for every process with the hashed `WatchState`
+/// index the process monitor uses.
+fn bench_watch_state_matching(c: &mut Criterion) {
+    let mut group = c.benchmark_group("watch_state_matching");
+    let processes: Vec<AppIdentifier> = create_mock_snapshot(500)
+        .into_iter()
+        .map(|(_, exe_file, _)| AppIdentifier::Win32(normalize_process_name(&exe_file)))
+        .collect();
+
+    for num_apps in [10, 50, 200] {
+        let apps = create_mock_config(num_apps).read().monitored_apps.clone();
+        let state = WatchState::new().with_apps(apps.clone(), true);
+
+        group.bench_with_input(
+            BenchmarkId::new("linear_scan", num_apps),
+            &num_apps,
+            |b, _| {
+                b.iter(|| {
+                    black_box(
+                        processes
+                            .iter()
+                            .filter(|app_id| {
+                                apps.iter()
+                                    .any(|app| AppIdentifier::from_monitored_app(app) == **app_id)
+                            })
+                            .count(),
+                    )
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("hashed_index", num_apps),
+            &num_apps,
+            |b, _| {
+                b.iter(|| {
+                    black_box(
+                        processes
+                            .iter()
+                            .filter(|app_id| state.is_monitored(app_id))
+                            .count(),
+                    )
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Benchmark identifying the processes of consecutive, unchanged snapshots
+///
+/// `full_rescan` normalizes every process name on every poll; `incremental` reuses the
+/// identifier of processes seen by the previous poll with the same executable and parent,
+/// as the process monitor does. Opening processes is not measured, which makes the real
+/// difference larger on Windows.
+fn bench_incremental_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("incremental_scan");
+    let state =
+        WatchState::new().with_apps(create_mock_config(50).read().monitored_apps.clone(), true);
+
+    for num_processes in [100, 250, 500] {
+        let snapshot = create_mock_snapshot(num_processes);
+
+        group.bench_with_input(
+            BenchmarkId::new("full_rescan", num_processes),
+            &snapshot,
+            |b, snapshot| {
+                b.iter(|| {
+                    black_box(
+                        snapshot
+                            .iter()
+                            .filter(|(_, exe_file, _)| {
+                                state.is_monitored(&AppIdentifier::Win32(normalize_process_name(
+                                    exe_file,
+                                )))
+                            })
+                            .count(),
+                    )
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("incremental", num_processes),
+            &snapshot,
+            |b, snapshot| {
+                let mut known: HashMap<u32, (String, u32, AppIdentifier)> = HashMap::new();
+                b.iter(|| {
+                    let mut previous = std::mem::take(&mut known);
+                    let mut matched = 0usize;
+                    for (pid, exe_file, parent_pid) in snapshot {
+                        let app_id = match previous.remove(pid) {
+                            Some((known_exe, known_parent, app_id))
+                                if known_exe == *exe_file && known_parent == *parent_pid =>
+                            {
+                                app_id
+                            }
+                            _ => AppIdentifier::Win32(normalize_process_name(exe_file)),
+                        };
+                        if state.is_monitored(&app_id) {
+                            matched += 1;
+                        }
+                        known.insert(*pid, (exe_file.clone(), *parent_pid, app_id));
+                    }
+                    black_box(matched)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_monitored_app_lookup,
     bench_watch_list_clone,
     bench_config_read_contention,
     bench_string_allocations,
-    bench_poll_processes_simulation
+    bench_poll_processes_simulation,
+    bench_watch_state_matching,
+    bench_incremental_scan
 );
 criterion_main!(benches);
@@ -1,7 +1,7 @@
 //! Build script for `EasyHDR`
 //!
 //! This build script performs three main tasks:
-//! 1. Compiles Slint UI files (`ui/main.slint`) into Rust code
+//! 1. Compiles Slint UI files (`ui/main.slint`) into Rust code (only with the `gui` feature)
 //! 2. Embeds version and build metadata (commit SHA) for runtime access
 //! 3. On Windows, embeds application resources (icon, version info, manifest) into the executable
 //!
@@ -15,6 +15,7 @@ use std::process::Command;
 
 fn main() {
     // Compile Slint UI files
+    #[cfg(feature = "gui")]
     slint_build::compile("ui/main.slint")
         .expect("Failed to compile Slint UI files. Check ui/main.slint for syntax errors.");
 
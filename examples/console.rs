@@ -0,0 +1,137 @@
+//! Run the EasyHDR engine behind a console front-end
+//!
+//! Shows how another program embeds the library without the GUI. It loads the EasyHDR
+//! configuration, wires the process monitor, HDR state monitor and application
+//! controller together the way `easyhdr.exe` does, prints every state update and reads
+//! commands from standard input.
+//!
+//! ```text
+//! cargo run --example console                # switch HDR on the real displays
+//! cargo run --example console -- --simulate  # simulated HDR display, any platform
+//! ```
+//!
+//! Commands: `status`, `on`, `off`, `quit`. Exit EasyHDR first: like `easyhdr.exe` the
+//! example holds the single-instance lock and uses the same data directory, which
+//! `EASYHDR_CONFIG_DIR` can point elsewhere.
+
+use easyhdr::config::ConfigManager;
+use easyhdr::controller::{AppController, AppState, HdrOverride};
+use easyhdr::hdr::{HdrControl, HdrController, MockHdrControl};
+use easyhdr::monitor::{HdrStateEvent, HdrStateMonitor, ProcessEvent, ProcessMonitor};
+use easyhdr::utils::{SingleInstanceGuard, data_dir};
+use parking_lot::Mutex;
+use std::io::BufRead;
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+
+/// Capacity of the channels between the components, as in `easyhdr.exe`
+const CHANNEL_CAPACITY: usize = 32;
+
+fn main() -> easyhdr::Result<()> {
+    data_dir::init(false);
+    let _instance = SingleInstanceGuard::new()?;
+
+    let config = ConfigManager::load()?;
+    println!("Watching {} applications", config.monitored_apps.len());
+
+    let (process_tx, process_rx) = mpsc::sync_channel::<ProcessEvent>(CHANNEL_CAPACITY);
+    let (hdr_state_tx, hdr_state_rx) = mpsc::sync_channel::<HdrStateEvent>(CHANNEL_CAPACITY);
+    let (state_tx, state_rx) = mpsc::sync_channel::<AppState>(CHANNEL_CAPACITY);
+
+    let process_monitor = ProcessMonitor::new(
+        Duration::from_millis(config.preferences.monitoring_interval_ms),
+        process_tx,
+    );
+    let watch_state = process_monitor.get_watch_state_ref();
+
+    if std::env::args().any(|arg| arg == "--simulate") {
+        println!("Simulating one HDR display, the real displays are left alone");
+        let controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            process_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        // Nothing changes the simulated display behind the controller's back
+        let _hdr_state_tx = hdr_state_tx;
+        run(controller, process_monitor, state_rx)
+    } else {
+        let hdr_state_monitor = HdrStateMonitor::new(HdrController::new()?, hdr_state_tx)?;
+        let controller =
+            AppController::new(config, process_rx, hdr_state_rx, state_tx, watch_state)?;
+        let _hdr_state_monitor = hdr_state_monitor.start();
+        run(controller, process_monitor, state_rx)
+    }
+}
+
+/// Start the engine and answer commands until `quit` or the end of input
+fn run<H: HdrControl + 'static>(
+    controller: AppController<H>,
+    process_monitor: ProcessMonitor,
+    state_rx: mpsc::Receiver<AppState>,
+) -> easyhdr::Result<()> {
+    let controller = Arc::new(Mutex::new(controller));
+    let _event_loop = AppController::spawn_event_loop(Arc::clone(&controller));
+    let _process_monitor = process_monitor.start();
+
+    std::thread::spawn(move || {
+        for state in state_rx {
+            print_state(&state);
+        }
+    });
+    controller.lock().send_initial_state();
+
+    println!("Commands: status, on, off, quit");
+    for line in std::io::stdin().lock().lines() {
+        match line?.trim() {
+            "status" => {
+                for display in controller.lock().display_hdr_states() {
+                    println!(
+                        "{}: HDR {}",
+                        display.target.display_name(),
+                        on_off(display.hdr_enabled)
+                    );
+                }
+            }
+            "on" => report(controller.lock().apply_hdr_override(HdrOverride::KeepOn)),
+            "off" => report(controller.lock().apply_hdr_override(HdrOverride::TurnOff)),
+            "quit" => break,
+            "" => {}
+            other => println!("Unknown command {other:?}, use status, on, off or quit"),
+        }
+    }
+
+    // Applies the shutdown policy of the configuration, like exiting EasyHDR
+    controller.lock().shutdown();
+    Ok(())
+}
+
+/// Print a state update from the controller
+fn print_state(state: &AppState) {
+    let running = if state.active_apps.is_empty() {
+        "nothing running".to_string()
+    } else {
+        state.active_apps.join(", ")
+    };
+    println!("HDR {} ({running})", on_off(state.hdr_enabled));
+    if !state.last_event.is_empty() {
+        println!("  {}", state.last_event);
+    }
+    if let Some(error) = &state.hdr_toggle_error {
+        println!("  HDR toggle failed: {error}");
+    }
+}
+
+/// Print the error of a failed command
+fn report(result: easyhdr::Result<()>) {
+    if let Err(e) = result {
+        println!("Failed: {e}");
+    }
+}
+
+/// `on` or `off`
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
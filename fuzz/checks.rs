@@ -0,0 +1,47 @@
+//! Checks shared by the fuzz targets and `tests/fuzz_regressions.rs`
+//!
+//! Each check takes the raw input of a fuzz target and panics when the code under test
+//! crashes or breaks an invariant, so replaying a saved input runs exactly what the
+//! fuzzer ran.
+
+use easyhdr::config::{AppConfig, MonitoredApp};
+use easyhdr::controller::agent::{AgentRequest, AgentResponse, Endpoint};
+
+/// `fuzz_config_json`: parsing arbitrary text as a configuration must not panic
+pub fn config_json(data: &[u8]) {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _result: Result<AppConfig, _> = serde_json::from_str(s);
+    }
+}
+
+/// `fuzz_monitored_app_json`: whatever parses as a monitored application must serialize
+/// and parse back to the same application
+pub fn monitored_app_json(data: &[u8]) {
+    if let Ok(s) = std::str::from_utf8(data)
+        && let Ok(app) = serde_json::from_str::<MonitoredApp>(s)
+    {
+        let json = serde_json::to_string(&app).expect("parsed application must serialize");
+        let reparsed: MonitoredApp =
+            serde_json::from_str(&json).expect("serialized application must parse");
+        assert_eq!(reparsed.id(), app.id());
+    }
+}
+
+/// `fuzz_agent_protocol`: request lines and endpoint files must round-trip, and no line
+/// may panic the response parser
+pub fn agent_protocol(data: &[u8]) {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(request) = AgentRequest::parse(s) {
+        let line = serde_json::to_string(&request).expect("parsed request must serialize");
+        assert_eq!(AgentRequest::parse(&line).ok(), Some(request));
+    }
+
+    let _response: Result<AgentResponse, _> = serde_json::from_str(s);
+
+    if let Some(endpoint) = Endpoint::parse(s) {
+        assert_eq!(Endpoint::parse(&endpoint.to_string()), Some(endpoint));
+    }
+}
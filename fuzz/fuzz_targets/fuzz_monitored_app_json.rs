@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../checks.rs"]
+#[expect(dead_code, reason = "Each fuzz target runs one of the shared checks")]
+mod checks;
+
+fuzz_target!(|data: &[u8]| checks::monitored_app_json(data));
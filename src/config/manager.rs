@@ -1,18 +1,50 @@
 //! Configuration manager for loading and saving application configuration.
-
+//!
+//! Before each save the previous configuration is copied to a timestamped file in the
+//! `backups` directory next to `config.json`; the newest [`MAX_BACKUPS`] are kept. A
+//! configuration that no longer parses is replaced by the newest backup on load.
+//!
+//! Configurations from older schema versions are migrated on load; the original file is
+//! kept as `backups/pre-migration-v<version>.json`.
+
+use crate::config::migration::{self, CURRENT_SCHEMA_VERSION};
 use crate::config::models::AppConfig;
-use crate::error::{EasyHdrError, Result};
-use std::path::PathBuf;
+use crate::error::{EasyHdrError, Result, StringError};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Number of configuration backups kept
+pub const MAX_BACKUPS: usize = 10;
+
+/// A configuration as written to disk, tagged with the schema version
+#[derive(Serialize)]
+struct VersionedConfig<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    config: &'a AppConfig,
+}
+
+impl<'a> VersionedConfig<'a> {
+    fn new(config: &'a AppConfig) -> Self {
+        Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            config,
+        }
+    }
+}
 
 /// Configuration manager
 pub struct ConfigManager;
 
 impl ConfigManager {
     /// Get the path to the configuration file.
+    ///
+    /// The file lives in the data directory, `%APPDATA%\EasyHDR` unless portable mode or
+    /// `EASYHDR_CONFIG_DIR` selects another one.
     pub fn get_config_path() -> PathBuf {
-        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
-        PathBuf::from(appdata).join("EasyHDR").join("config.json")
+        crate::utils::data_dir::get_data_dir().join("config.json")
     }
 
     /// Ensure the configuration directory exists.
@@ -55,30 +87,179 @@ impl ConfigManager {
             e
         })?;
 
-        let mut config = match serde_json::from_str(&json) {
-            Ok(config) => {
+        let mut config = match Self::parse(&json) {
+            Ok((config, version)) => {
                 info!("Configuration loaded successfully from {:?}", config_path);
+                if version < CURRENT_SCHEMA_VERSION {
+                    Self::finish_migration(&json, version, &config);
+                }
                 config
             }
             Err(e) => {
                 warn!(
-                    "Failed to parse configuration from {:?}, using defaults: {}",
+                    "Failed to parse configuration from {:?}: {}",
                     config_path, e
                 );
-                AppConfig::default()
+                Self::load_newest_backup().unwrap_or_else(|| {
+                    warn!("No usable configuration backup, using defaults");
+                    AppConfig::default()
+                })
             }
         };
 
+        Self::validate_match_rules(&mut config);
+
+        // Icons are only needed by the main window, which loads them in the background
+        // once it is shown (see `AppController::load_icons_in_background`)
+        Ok(config)
+    }
+
+    /// Parse a configuration, migrating it from an older schema version
+    ///
+    /// Returns the configuration and the schema version it was stored with.
+    fn parse(json: &str) -> Result<(AppConfig, u32)> {
+        let mut raw: serde_json::Value = serde_json::from_str(json)?;
+        let version = migration::migrate(&mut raw)?;
+        if version > CURRENT_SCHEMA_VERSION {
+            warn!(
+                "Configuration was written by a newer EasyHDR (schema {}), settings this \
+                 version doesn't know are dropped on the next save",
+                version
+            );
+        }
+        Ok((serde_json::from_value(raw)?, version))
+    }
+
+    /// Keep the pre-migration file and save the migrated configuration
+    ///
+    /// The migrated configuration is already in memory, so failures are only logged.
+    fn finish_migration(original: &str, version: u32, config: &AppConfig) {
+        let backup_path = Self::get_backup_dir().join(format!("pre-migration-v{version}.json"));
+        let backed_up = std::fs::create_dir_all(Self::get_backup_dir())
+            .and_then(|()| std::fs::write(&backup_path, original));
+        if let Err(e) = backed_up {
+            warn!(
+                "Failed to back up the configuration before migrating it, keeping the old file: {}",
+                e
+            );
+            return;
+        }
+
+        match Self::save(config) {
+            Ok(()) => info!(
+                "Configuration migrated from schema {} to {}, previous file kept at {:?}",
+                version, CURRENT_SCHEMA_VERSION, backup_path
+            ),
+            Err(e) => warn!("Failed to save the migrated configuration: {}", e),
+        }
+    }
+
+    /// Load the newest backup that still parses
+    fn load_newest_backup() -> Option<AppConfig> {
+        Self::list_backups().into_iter().find_map(|path| {
+            let config = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|json| Self::parse(&json).ok())
+                .map(|(config, _)| config);
+            if config.is_some() {
+                warn!("Restored configuration from backup {:?}", path);
+            }
+            config
+        })
+    }
+
+    /// Drop match rules that do not compile
+    ///
+    /// An application with an invalid rule keeps being matched by its exact process name,
+    /// so one bad pattern does not stop the rest of the watch list from loading.
+    fn validate_match_rules(config: &mut AppConfig) {
+        use crate::config::models::MonitoredApp;
+
+        for app in &mut config.monitored_apps {
+            if let MonitoredApp::Win32(app) = app
+                && let Some(rule) = &app.match_rule
+                && let Err(e) = rule.validate()
+            {
+                warn!(
+                    "Ignoring match rule of '{}', matching '{}' exactly: {}",
+                    app.display_name, app.process_name, e
+                );
+                app.match_rule = None;
+            }
+        }
+    }
+
+    /// Load the icons of apps that have none, from the disk cache or their source
+    ///
+    /// Returns the number of apps that have an icon afterwards.
+    pub fn load_icons(config: &mut AppConfig) -> usize {
+        if let Err(e) = Self::restore_icons_from_cache(config) {
+            warn!(
+                "Failed to restore icons from cache: {}. Continuing without cached icons.",
+                e
+            );
+        }
+
+        Self::regenerate_missing_icons(config);
+
+        config
+            .monitored_apps
+            .iter()
+            .filter(|app| app.icon_data().is_some())
+            .count()
+    }
+
+    /// Load the icons of the given apps that have none, reporting them in batches
+    ///
+    /// Icons restored from the disk cache are passed to `on_loaded` first and icons
+    /// extracted from their source afterwards, so a caller on another thread can show most
+    /// icons before the slow extraction finishes. Empty batches are not reported. Returns
+    /// the number of icons loaded.
+    pub fn load_icons_incrementally(
+        monitored_apps: Vec<crate::config::models::MonitoredApp>,
+        mut on_loaded: impl FnMut(Vec<(Uuid, Vec<u8>)>),
+    ) -> usize {
+        use crate::config::models::MonitoredApp;
+
+        fn icons_of(apps: Vec<MonitoredApp>) -> Vec<(Uuid, Vec<u8>)> {
+            apps.into_iter()
+                .filter_map(|mut app| Some((*app.id(), app.icon_data_mut().take()?)))
+                .collect()
+        }
+
+        let mut config = AppConfig {
+            monitored_apps,
+            ..AppConfig::default()
+        };
+        config
+            .monitored_apps
+            .retain(|app| app.icon_data().is_none());
+
         if let Err(e) = Self::restore_icons_from_cache(&mut config) {
             warn!(
                 "Failed to restore icons from cache: {}. Continuing without cached icons.",
                 e
             );
         }
+        let (restored, missing): (Vec<_>, Vec<_>) = config
+            .monitored_apps
+            .into_iter()
+            .partition(|app| app.icon_data().is_some());
+        let restored = icons_of(restored);
+        let mut loaded = restored.len();
+        if !restored.is_empty() {
+            on_loaded(restored);
+        }
 
+        config.monitored_apps = missing;
         Self::regenerate_missing_icons(&mut config);
+        let extracted = icons_of(config.monitored_apps);
+        loaded += extracted.len();
+        if !extracted.is_empty() {
+            on_loaded(extracted);
+        }
 
-        Ok(config)
+        loaded
     }
 
     /// Restore icons from disk cache in parallel.
@@ -88,6 +269,7 @@ impl ConfigManager {
     )]
     fn restore_icons_from_cache(config: &mut AppConfig) -> Result<()> {
         use crate::config::models::MonitoredApp;
+        use crate::utils::volume::volume_kind;
         use rayon::prelude::*;
 
         if config.monitored_apps.is_empty() {
@@ -116,10 +298,14 @@ impl ConfigManager {
         let icons: Vec<(uuid::Uuid, Vec<u8>)> = config
             .monitored_apps
             .par_iter()
+            .filter(|app| app.icon_data().is_none())
             .filter_map(|app| {
+                // Freshness checks would stat executables on network shares and removable
+                // drives, which can block startup; their cached icons are used as they are
                 let source_path = match app {
-                    MonitoredApp::Win32(win32) => Some(win32.exe_path.as_path()),
-                    MonitoredApp::Uwp(_) => None,
+                    MonitoredApp::Win32(win32) => Some(win32.exe_path.as_path())
+                        .filter(|path| !volume_kind(path).may_be_offline()),
+                    MonitoredApp::Uwp(_) | MonitoredApp::Folder(_) => None,
                 };
 
                 match cache.load_icon(*app.id(), source_path) {
@@ -177,6 +363,7 @@ impl ConfigManager {
         use crate::config::models::MonitoredApp;
         #[cfg(windows)]
         use crate::utils::memory_profiler;
+        use crate::utils::volume::volume_kind;
         #[cfg(windows)]
         use crate::uwp;
 
@@ -188,10 +375,7 @@ impl ConfigManager {
         let apps_needing_icons_count = config
             .monitored_apps
             .iter()
-            .filter(|app| match app {
-                MonitoredApp::Win32(win32) => win32.icon_data.is_none(),
-                MonitoredApp::Uwp(uwp) => uwp.icon_data.is_none(),
-            })
+            .filter(|app| app.icon_data().is_none())
             .count();
 
         if apps_needing_icons_count == 0 {
@@ -243,6 +427,18 @@ impl ConfigManager {
                 continue;
             }
 
+            // Executables on network shares and removable drives may be offline or slow
+            // to read; the controller extracts their icons in the background instead
+            if let MonitoredApp::Win32(win32_app) = app
+                && volume_kind(&win32_app.exe_path).may_be_offline()
+            {
+                tracing::debug!(
+                    "Deferring icon extraction for '{}' on a network or removable volume",
+                    win32_app.display_name
+                );
+                continue;
+            }
+
             match app {
                 MonitoredApp::Win32(win32_app) => {
                     if win32_app.ensure_icon_loaded().is_some() {
@@ -273,9 +469,12 @@ impl ConfigManager {
                 MonitoredApp::Uwp(uwp_app) => {
                     #[cfg(windows)]
                     if let Some(packages) = &uwp_packages
-                        && let Some(pkg) = packages
-                            .iter()
-                            .find(|p| p.package_family_name == uwp_app.package_family_name)
+                        && let Some(pkg) = packages.iter().find(|p| {
+                            crate::utils::unicode::eq_ignore_case(
+                                &p.package_family_name,
+                                &uwp_app.package_family_name,
+                            ) && crate::utils::unicode::eq_ignore_case(&p.app_id, &uwp_app.app_id)
+                        })
                         && let Some(logo_stream) = &pkg.logo_stream
                     {
                         match uwp::extract_icon_from_stream(logo_stream) {
@@ -321,6 +520,30 @@ impl ConfigManager {
                         let _ = uwp_app; // Suppress unused variable warning
                     }
                 }
+                // Folders have no icon of their own and always get a letter tile
+                MonitoredApp::Folder(_) => {}
+            }
+
+            // Fall back to a letter tile when the source has no usable icon. UWP apps are
+            // skipped when enumeration failed, since their logo may be available next time.
+            #[cfg(windows)]
+            let uwp_source_checked = uwp_packages.is_some();
+            #[cfg(not(windows))]
+            let uwp_source_checked = true;
+
+            let source_checked = !matches!(app, MonitoredApp::Uwp(_)) || uwp_source_checked;
+            if source_checked && app.ensure_fallback_icon() {
+                if let (Some(cache), Some(icon_data)) = (&cache, app.icon_data())
+                    && let Err(e) = cache.save_icon(*app.id(), icon_data)
+                {
+                    tracing::warn!(
+                        "Failed to cache letter-tile icon for '{}': {}",
+                        app.display_name(),
+                        e
+                    );
+                }
+
+                regenerated_count += 1;
             }
         }
 
@@ -350,7 +573,7 @@ impl ConfigManager {
         let temp_path = config_dir.join("config.json.tmp");
 
         debug!("Serializing configuration to JSON");
-        let json = serde_json::to_string_pretty(config).map_err(|e| {
+        let json = serde_json::to_string_pretty(&VersionedConfig::new(config)).map_err(|e| {
             error!("Failed to serialize configuration to JSON: {}", e);
             e
         })?;
@@ -364,6 +587,11 @@ impl ConfigManager {
             e
         })?;
 
+        // A failed backup must not prevent saving
+        if let Err(e) = Self::backup_current() {
+            warn!("Failed to back up the previous configuration: {}", e);
+        }
+
         debug!("Renaming temp file to config file: {:?}", config_path);
         std::fs::rename(&temp_path, &config_path).map_err(|e| {
             error!(
@@ -376,13 +604,152 @@ impl ConfigManager {
         info!("Configuration saved successfully to {:?}", config_path);
         Ok(())
     }
+
+    /// Directory holding the configuration backups
+    pub fn get_backup_dir() -> PathBuf {
+        Self::get_config_path().with_file_name("backups")
+    }
+
+    /// Configuration backups, newest first
+    pub fn list_backups() -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(Self::get_backup_dir()) else {
+            return Vec::new();
+        };
+
+        let mut backups: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with("config-")
+                            && Path::new(name)
+                                .extension()
+                                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+                    })
+            })
+            .collect();
+        // The timestamps all have the same number of digits, so names sort by age
+        backups.sort_unstable_by(|a, b| b.cmp(a));
+        backups
+    }
+
+    /// Copy the current configuration to the backup directory and drop old backups
+    ///
+    /// Skipped when there is no configuration yet, when it doesn't parse (a corrupt
+    /// file would push the good backups out) or when it matches the newest backup.
+    fn backup_current() -> Result<()> {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        use tracing::debug;
+
+        let Ok(current) = std::fs::read_to_string(Self::get_config_path()) else {
+            return Ok(());
+        };
+        if Self::parse(&current).is_err() {
+            debug!("Not backing up a configuration that doesn't parse");
+            return Ok(());
+        }
+
+        let backups = Self::list_backups();
+        if backups
+            .first()
+            .is_some_and(|newest| std::fs::read_to_string(newest).is_ok_and(|json| json == current))
+        {
+            return Ok(());
+        }
+
+        let backup_dir = Self::get_backup_dir();
+        std::fs::create_dir_all(&backup_dir)?;
+        let mut timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut backup_path = backup_dir.join(format!("config-{timestamp}.json"));
+        // Saves within the same millisecond must not overwrite each other's backup
+        while backup_path.exists() {
+            timestamp += 1;
+            backup_path = backup_dir.join(format!("config-{timestamp}.json"));
+        }
+        std::fs::write(&backup_path, current)?;
+        debug!("Backed up configuration to {:?}", backup_path);
+
+        for old in Self::list_backups().iter().skip(MAX_BACKUPS) {
+            if let Err(e) = std::fs::remove_file(old) {
+                warn!("Failed to remove old configuration backup {:?}: {}", old, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Write `config` to `path`, e.g. to move it to another PC
+    ///
+    /// Icons are not included; they are extracted again after importing.
+    pub fn export(config: &AppConfig, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(&VersionedConfig::new(config))?;
+        std::fs::write(path, json)?;
+        info!("Configuration exported to {:?}", path);
+        Ok(())
+    }
+
+    /// Read and validate a configuration exported with [`ConfigManager::export`]
+    ///
+    /// Unlike [`ConfigManager::load`], nothing is repaired: a file that isn't an `EasyHDR`
+    /// configuration, lists an application twice or has an invalid match rule is
+    /// rejected. The configuration on disk is not changed.
+    pub fn import(path: &Path) -> Result<AppConfig> {
+        let json = std::fs::read_to_string(path)?;
+        let (config, _) = Self::parse(&json).map_err(|e| {
+            EasyHdrError::ConfigError(StringError::new(format!(
+                "{} is not an EasyHDR configuration: {e}",
+                path.display()
+            )))
+        })?;
+        Self::validate(&config)?;
+
+        info!(
+            "Imported configuration with {} applications from {:?}",
+            config.monitored_apps.len(),
+            path
+        );
+        Ok(config)
+    }
+
+    /// Check the parts of a configuration that deserializing doesn't
+    fn validate(config: &AppConfig) -> Result<()> {
+        use crate::config::models::MonitoredApp;
+        use std::collections::HashSet;
+
+        if !(500..=2000).contains(&config.preferences.monitoring_interval_ms) {
+            return Err(EasyHdrError::ConfigError(StringError::new(format!(
+                "monitoring interval of {}ms is outside 500-2000ms",
+                config.preferences.monitoring_interval_ms
+            ))));
+        }
+
+        let mut ids = HashSet::new();
+        for app in &config.monitored_apps {
+            if !ids.insert(*app.id()) {
+                return Err(EasyHdrError::ConfigError(StringError::new(format!(
+                    "application '{}' is listed twice",
+                    app.display_name()
+                ))));
+            }
+            if let MonitoredApp::Win32(app) = app
+                && let Some(rule) = &app.match_rule
+            {
+                rule.validate()?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 #[expect(clippy::unwrap_used)]
 mod tests {
     use super::*;
-    use crate::config::models::{MonitoredApp, Win32App};
+    use crate::config::models::{AppSource, MonitoredApp, Win32App};
+    use crate::hdr::AutoHdrMode;
     use crate::test_utils::{AppdataGuard, create_test_dir};
     use std::fs;
     use std::path::PathBuf;
@@ -435,6 +802,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\Games\\test.exe"),
             process_name: "test".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.preferences.auto_start = true;
@@ -767,7 +1144,7 @@ mod tests {
     /// Test `ConfigManager` save/load with mixed Win32 and UWP apps
     #[test]
     fn test_save_and_load_mixed_win32_and_uwp_apps() {
-        use crate::config::models::UwpApp;
+        use crate::config::models::{AppSource, UwpApp};
 
         let test_dir = create_test_dir();
         let _guard = AppdataGuard::new(&test_dir);
@@ -782,6 +1159,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\Games\\Cyberpunk 2077\\bin\\x64\\Cyberpunk2077.exe"),
             process_name: "cyberpunk2077".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
@@ -792,6 +1179,14 @@ mod tests {
             package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
             app_id: "App".to_string(),
             enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: None,
         }));
 
@@ -802,6 +1197,16 @@ mod tests {
             exe_path: PathBuf::from("D:\\Games\\RDR2\\RDR2.exe"),
             process_name: "rdr2".to_string(),
             enabled: false,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
@@ -985,7 +1390,7 @@ mod tests {
     /// correctly with the new `MonitoredApp` enum structure.
     #[test]
     fn test_atomic_write_with_monitored_app_enum() {
-        use crate::config::models::UwpApp;
+        use crate::config::models::{AppSource, UwpApp};
 
         let test_dir = create_test_dir();
         let _guard = AppdataGuard::new(&test_dir);
@@ -998,6 +1403,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\Games\\test.exe"),
             process_name: "test".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.monitored_apps.push(MonitoredApp::Uwp(UwpApp {
@@ -1006,6 +1421,14 @@ mod tests {
             package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
             app_id: "App".to_string(),
             enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: None,
         }));
 
@@ -1034,4 +1457,282 @@ mod tests {
 
         // TempDir and AppdataGuard automatically clean up when dropped
     }
+
+    #[test]
+    fn test_start_in_tray_defers_icon_loading() {
+        use crate::config::models::UwpApp;
+        use crate::utils::IconCache;
+
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let app = UwpApp::from_package_info(
+            "Calculator".to_string(),
+            "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
+            "App".to_string(),
+            None,
+        );
+        let app_id = app.id;
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Uwp(app));
+        config.preferences.start_minimized_to_tray = true;
+        ConfigManager::save(&config).unwrap();
+
+        IconCache::new(IconCache::default_cache_dir())
+            .unwrap()
+            .save_icon(app_id, &[0x80; 32 * 32 * 4])
+            .unwrap();
+
+        // Starting in the tray leaves the cached icon on disk
+        let mut loaded = ConfigManager::load().unwrap();
+        assert!(loaded.monitored_apps[0].icon_data().is_none());
+
+        // Showing the window loads it
+        assert_eq!(ConfigManager::load_icons(&mut loaded), 1);
+        assert!(loaded.monitored_apps[0].icon_data().is_some());
+    }
+
+    #[test]
+    fn test_icons_load_incrementally() {
+        use crate::config::models::UwpApp;
+        use crate::utils::IconCache;
+
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let uwp_app = |name: &str| {
+            UwpApp::from_package_info(
+                name.to_string(),
+                format!("Contoso.{name}_8wekyb3d8bbwe"),
+                "App".to_string(),
+                None,
+            )
+        };
+        let cached = uwp_app("Cached");
+        let uncached = uwp_app("Uncached");
+        let (cached_id, uncached_id) = (cached.id, uncached.id);
+        IconCache::new(IconCache::default_cache_dir())
+            .unwrap()
+            .save_icon(cached_id, &[0x80; 32 * 32 * 4])
+            .unwrap();
+
+        let mut batches = Vec::new();
+        let loaded = ConfigManager::load_icons_incrementally(
+            vec![MonitoredApp::Uwp(cached), MonitoredApp::Uwp(uncached)],
+            |icons| batches.push(icons.into_iter().map(|(id, _)| id).collect::<Vec<_>>()),
+        );
+
+        // Cached icons first, then the icon extracted or drawn as a letter tile
+        assert_eq!(loaded, 2);
+        assert_eq!(batches, [vec![cached_id], vec![uncached_id]]);
+    }
+
+    #[test]
+    fn test_invalid_match_rules_are_dropped_on_load() {
+        use crate::config::models::MatchRule;
+
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let rule_app = |name: &str, rule: MatchRule| {
+            MonitoredApp::Win32(Win32App {
+                id: Uuid::new_v4(),
+                display_name: name.to_string(),
+                exe_path: PathBuf::from(format!("C:\\Games\\{name}.exe")),
+                process_name: name.to_string(),
+                enabled: true,
+                source: AppSource::Manual,
+                auto_hdr: AutoHdrMode::Default,
+                display_mode: None,
+                hdr_disable_debounce_ms: None,
+                max_hdr_session_minutes: None,
+                color_mode: None,
+                capture_tone_mapping: None,
+                display_group: None,
+                match_rule: Some(rule),
+                aliases: Vec::new(),
+                icon_data: None,
+            })
+        };
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(rule_app(
+            "ue4game",
+            MatchRule::Glob("ue4game*.exe".to_string()),
+        ));
+        config
+            .monitored_apps
+            .push(rule_app("broken", MatchRule::Regex("broken(".to_string())));
+        ConfigManager::save(&config).unwrap();
+
+        let loaded = ConfigManager::load().unwrap();
+        let rules: Vec<_> = loaded
+            .monitored_apps
+            .iter()
+            .map(|app| match app {
+                MonitoredApp::Win32(app) => app.match_rule.clone(),
+                MonitoredApp::Uwp(_) | MonitoredApp::Folder(_) => None,
+            })
+            .collect();
+
+        // The broken entry stays on the watch list, matched by its exact process name
+        assert_eq!(
+            rules,
+            vec![Some(MatchRule::Glob("ue4game*.exe".to_string())), None]
+        );
+    }
+
+    #[test]
+    fn test_save_keeps_rotating_backups() {
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let mut config = AppConfig::default();
+        for interval in 0..=MAX_BACKUPS as u64 + 2 {
+            config.preferences.monitoring_interval_ms = 500 + interval;
+            ConfigManager::save(&config).unwrap();
+        }
+        // Saving the same configuration again backs it up only once
+        ConfigManager::save(&config).unwrap();
+        ConfigManager::save(&config).unwrap();
+
+        let backups = ConfigManager::list_backups();
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        let newest: AppConfig =
+            serde_json::from_str(&fs::read_to_string(&backups[0]).unwrap()).unwrap();
+        assert_eq!(
+            newest.preferences.monitoring_interval_ms,
+            500 + MAX_BACKUPS as u64 + 2,
+            "newest backup is the last saved configuration"
+        );
+    }
+
+    #[test]
+    fn test_corrupt_config_is_restored_from_backup() {
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let mut config = AppConfig::default();
+        config.preferences.monitoring_interval_ms = 1500;
+        ConfigManager::save(&config).unwrap();
+        config.preferences.monitoring_interval_ms = 2000;
+        ConfigManager::save(&config).unwrap();
+
+        fs::write(ConfigManager::get_config_path(), "{ truncated").unwrap();
+        let loaded = ConfigManager::load().unwrap();
+        assert_eq!(loaded.preferences.monitoring_interval_ms, 1500);
+
+        // The corrupt file is not backed up over the good ones
+        ConfigManager::save(&loaded).unwrap();
+        assert_eq!(ConfigManager::list_backups().len(), 1);
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from(r"C:\Games\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        config.excluded_processes = vec!["obs64".to_string()];
+
+        let path = test_dir.path().join("easyhdr-config.json");
+        ConfigManager::export(&config, &path).unwrap();
+        let imported = ConfigManager::import(&path).unwrap();
+        assert_eq!(imported.monitored_apps, config.monitored_apps);
+        assert_eq!(imported.excluded_processes, config.excluded_processes);
+        assert!(
+            !ConfigManager::get_config_path().exists(),
+            "importing does not touch the configuration on disk"
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_files() {
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+        let path = test_dir.path().join("easyhdr-config.json");
+
+        fs::write(&path, r#"{"monitored_apps": "not an array"}"#).unwrap();
+        assert!(ConfigManager::import(&path).is_err());
+
+        let mut config = AppConfig::default();
+        let app = MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from(r"C:\Games\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        });
+        config.monitored_apps = vec![app.clone(), app];
+        ConfigManager::export(&config, &path).unwrap();
+        assert!(ConfigManager::import(&path).is_err());
+
+        assert!(ConfigManager::import(&test_dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_unversioned_config_is_migrated_and_backed_up() {
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let config_path = ConfigManager::get_config_path();
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        let original = r#"{
+            "monitored_apps": [{
+                "id": "550e8400-e29b-41d4-a716-446655440000",
+                "display_name": "Game",
+                "exe_path": "C:\\Games\\game.exe",
+                "process_name": "game",
+                "enabled": true
+            }]
+        }"#;
+        fs::write(&config_path, original).unwrap();
+
+        let config = ConfigManager::load().unwrap();
+        assert_eq!(config.monitored_apps.len(), 1);
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved["schema_version"], CURRENT_SCHEMA_VERSION);
+        assert_eq!(saved["monitored_apps"][0]["app_type"], "win32");
+        assert_eq!(
+            fs::read_to_string(ConfigManager::get_backup_dir().join("pre-migration-v1.json"))
+                .unwrap(),
+            original
+        );
+
+        // Loading the migrated file again changes nothing
+        let written = fs::read_to_string(&config_path).unwrap();
+        ConfigManager::load().unwrap();
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), written);
+    }
 }
@@ -0,0 +1,159 @@
+//! Configuration schema versions and migrations
+//!
+//! `config.json` carries a top-level `schema_version`. Files written before it was
+//! introduced have none and count as version 1. When the schema changes, bump
+//! [`CURRENT_SCHEMA_VERSION`] and append a [`Migration`] that rewrites the raw JSON of
+//! the previous version; [`migrate`] runs the steps in order. The result is checked by
+//! deserializing it before `ConfigManager` backs up the old file and saves the new one,
+//! so a failed migration never touches the configuration on disk.
+
+use crate::error::{EasyHdrError, Result, StringError};
+use serde_json::Value;
+
+/// Schema version written by this build
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Version of configurations written before `schema_version` existed
+pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// One step upgrading the raw configuration from `from` to `from + 1`
+pub struct Migration {
+    /// Schema version the step upgrades from
+    pub from: u32,
+    /// What the step changes, for the log
+    pub description: &'static str,
+    /// Rewrite the raw JSON in place
+    pub apply: fn(&mut Value) -> Result<()>,
+}
+
+/// Migrations in order, one per schema version before [`CURRENT_SCHEMA_VERSION`]
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    description: "tag monitored applications without app_type as Win32 applications",
+    apply: tag_untyped_apps,
+}];
+
+/// Schema version of a raw configuration
+pub fn schema_version(config: &Value) -> u32 {
+    config
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+/// Upgrade a raw configuration to [`CURRENT_SCHEMA_VERSION`]
+///
+/// Returns the version the configuration had. A configuration from a newer build is
+/// left as it is.
+pub fn migrate(config: &mut Value) -> Result<u32> {
+    use tracing::info;
+
+    let from = schema_version(config);
+    let Some(object) = config.as_object_mut() else {
+        return Err(EasyHdrError::ConfigError(StringError::new(
+            "configuration is not a JSON object",
+        )));
+    };
+    if from >= CURRENT_SCHEMA_VERSION {
+        return Ok(from);
+    }
+    object.insert("schema_version".to_string(), from.into());
+
+    for migration in MIGRATIONS.iter().filter(|migration| migration.from >= from) {
+        info!(
+            "Migrating configuration from schema {} to {}: {}",
+            migration.from,
+            migration.from + 1,
+            migration.description
+        );
+        (migration.apply)(config).map_err(|e| {
+            EasyHdrError::ConfigError(StringError::new(format!(
+                "migration from schema {} failed: {e}",
+                migration.from
+            )))
+        })?;
+        config["schema_version"] = (migration.from + 1).into();
+    }
+
+    if schema_version(config) != CURRENT_SCHEMA_VERSION {
+        return Err(EasyHdrError::ConfigError(StringError::new(format!(
+            "no migration path from schema {from} to {CURRENT_SCHEMA_VERSION}"
+        ))));
+    }
+    Ok(from)
+}
+
+/// Schema 1 → 2: the first configurations stored Win32 applications without the
+/// `app_type` tag
+#[expect(
+    clippy::unnecessary_wraps,
+    reason = "Every migration step has the signature of `Migration::apply`"
+)]
+fn tag_untyped_apps(config: &mut Value) -> Result<()> {
+    let Some(apps) = config
+        .get_mut("monitored_apps")
+        .and_then(Value::as_array_mut)
+    else {
+        return Ok(());
+    };
+
+    for app in apps.iter_mut().filter_map(Value::as_object_mut) {
+        if !app.contains_key("app_type") && app.contains_key("exe_path") {
+            app.insert("app_type".to_string(), "win32".into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn test_migrations_cover_every_version() {
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|migration| migration.from).collect();
+        let expected: Vec<u32> = (UNVERSIONED_SCHEMA_VERSION..CURRENT_SCHEMA_VERSION).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn test_unversioned_config_is_migrated() {
+        let mut config: Value = serde_json::from_str(
+            r#"{
+                "monitored_apps": [{
+                    "id": "550e8400-e29b-41d4-a716-446655440000",
+                    "display_name": "Game",
+                    "exe_path": "C:\\Games\\game.exe",
+                    "process_name": "game",
+                    "enabled": true
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(migrate(&mut config).unwrap(), UNVERSIONED_SCHEMA_VERSION);
+        assert_eq!(schema_version(&config), CURRENT_SCHEMA_VERSION);
+        assert_eq!(config["monitored_apps"][0]["app_type"], "win32");
+        let migrated: AppConfig = serde_json::from_value(config).unwrap();
+        assert_eq!(migrated.monitored_apps.len(), 1);
+    }
+
+    #[test]
+    fn test_current_and_newer_configs_are_unchanged() {
+        let mut current = serde_json::json!({ "schema_version": CURRENT_SCHEMA_VERSION });
+        let before = current.clone();
+        assert_eq!(migrate(&mut current).unwrap(), CURRENT_SCHEMA_VERSION);
+        assert_eq!(current, before);
+
+        let mut newer = serde_json::json!({ "schema_version": CURRENT_SCHEMA_VERSION + 1 });
+        assert_eq!(migrate(&mut newer).unwrap(), CURRENT_SCHEMA_VERSION + 1);
+    }
+
+    #[test]
+    fn test_non_object_is_rejected() {
+        assert!(migrate(&mut serde_json::json!([])).is_err());
+    }
+}
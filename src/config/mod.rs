@@ -1,10 +1,18 @@
 //! Configuration management module
 //!
 //! Handles loading, saving, and managing application configuration.
-//! Provides persistent storage with atomic writes to prevent corruption.
+//! Provides persistent storage with atomic writes to prevent corruption, and
+//! versioned schema migrations for configurations written by older builds.
 
 pub mod manager;
+pub mod migration;
 pub mod models;
 
 pub use manager::ConfigManager;
-pub use models::{AppConfig, MonitoredApp, UserPreferences, UwpApp, Win32App, WindowState};
+pub use migration::CURRENT_SCHEMA_VERSION;
+pub use models::{
+    AppConfig, AppSource, AutoStartMethod, BatteryHdrPolicy, DEFAULT_HDR_DISABLE_DEBOUNCE_MS,
+    DEFAULT_OLED_CARE_AFTER_MINUTES, DisplayGroup, FolderApp, KnownDisplay,
+    MAX_HDR_DISABLE_DEBOUNCE_MS, MatchRule, MonitoredApp, OledCare, ShutdownPolicy, UpdateChannel,
+    UserPreferences, UwpApp, Win32App, WindowState, hdr_disable_debounce, max_hdr_session,
+};
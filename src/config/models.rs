@@ -2,12 +2,255 @@
 //!
 //! This module defines the data structures used for application configuration.
 
-use crate::error::Result;
-use crate::utils::{extract_display_name_from_exe, extract_icon_from_exe};
+use crate::error::{EasyHdrError, Result, StringError};
+use crate::hdr::{
+    AutoHdrMode, ColorMode, DisplayIdentity, DisplayModeOverride, DisplayTarget, VcpCommand,
+};
+use crate::utils::unicode::eq_ignore_case;
+use crate::utils::{
+    LogFormat, LogLevel, PowerStatus, UpdateCheckCache, clean_display_name,
+    extract_display_name_from_exe, extract_icon_from_exe, normalize_process_name,
+};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 use uuid::Uuid;
 
+/// Where a monitored application entry came from
+///
+/// Persisted so the GUI can badge and filter entries by origin. Entries written before
+/// this field existed deserialize as [`AppSource::Manual`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AppSource {
+    /// Added by the user through the file or UWP picker
+    #[default]
+    Manual,
+    /// Imported from a game launcher library (Steam, Epic, GOG)
+    Imported,
+}
+
+impl AppSource {
+    /// Short label used for GUI badges and filters
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Imported => "imported",
+        }
+    }
+}
+
+/// What happens to HDR when the user exits `EasyHDR`
+///
+/// Applied by `AppController::shutdown` before the process exits. Crashes are handled
+/// separately by the crash guard, which always turns HDR off if `EasyHDR` turned it on.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ShutdownPolicy {
+    /// Leave HDR as it is
+    #[default]
+    LeaveAsIs,
+    /// Turn HDR off if `EasyHDR` turned it on
+    RestorePrevious,
+    /// Turn HDR off on every display
+    ForceOff,
+}
+
+impl ShutdownPolicy {
+    /// Name used in the GUI and the configuration file
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::LeaveAsIs => "leave_as_is",
+            Self::RestorePrevious => "restore_previous",
+            Self::ForceOff => "force_off",
+        }
+    }
+
+    /// Parse a name returned by [`ShutdownPolicy::as_str`]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "leave_as_is" => Some(Self::LeaveAsIs),
+            "restore_previous" => Some(Self::RestorePrevious),
+            "force_off" => Some(Self::ForceOff),
+            _ => None,
+        }
+    }
+}
+
+/// How `EasyHDR` is started when the user logs in
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoStartMethod {
+    /// Entry in the `Run` registry key, started without elevation
+    #[default]
+    Registry,
+    /// Task Scheduler logon task, which can start elevated and after a delay
+    TaskScheduler,
+}
+
+impl AutoStartMethod {
+    /// Name used in the GUI and the configuration file
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Registry => "registry",
+            Self::TaskScheduler => "task_scheduler",
+        }
+    }
+
+    /// Parse a name returned by [`AutoStartMethod::as_str`]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "registry" => Some(Self::Registry),
+            "task_scheduler" => Some(Self::TaskScheduler),
+            _ => None,
+        }
+    }
+}
+
+/// Releases the update checker offers
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    /// Full releases only
+    #[default]
+    Stable,
+    /// Prereleases as well as full releases
+    Beta,
+}
+
+impl UpdateChannel {
+    /// Name used in the GUI and the configuration file
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+        }
+    }
+
+    /// Parse a name returned by [`UpdateChannel::as_str`]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "stable" => Some(Self::Stable),
+            "beta" => Some(Self::Beta),
+            _ => None,
+        }
+    }
+}
+
+/// Whether HDR is turned on while the system runs on battery power
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BatteryHdrPolicy {
+    /// Turn HDR on regardless of the power source
+    #[default]
+    Allow,
+    /// Keep HDR off on battery power
+    SkipOnBattery,
+    /// Keep HDR off on battery power once the charge drops below
+    /// [`UserPreferences::battery_hdr_threshold_percent`]
+    SkipBelowThreshold,
+}
+
+impl BatteryHdrPolicy {
+    /// Name used in the GUI and the configuration file
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::SkipOnBattery => "skip_on_battery",
+            Self::SkipBelowThreshold => "skip_below_threshold",
+        }
+    }
+
+    /// Parse a name returned by [`BatteryHdrPolicy::as_str`]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "allow" => Some(Self::Allow),
+            "skip_on_battery" => Some(Self::SkipOnBattery),
+            "skip_below_threshold" => Some(Self::SkipBelowThreshold),
+            _ => None,
+        }
+    }
+
+    /// Whether HDR stays off with the given power status
+    ///
+    /// An unknown charge never counts as below the threshold.
+    pub fn skips_hdr(self, status: PowerStatus, threshold_percent: u8) -> bool {
+        match self {
+            Self::Allow => false,
+            Self::SkipOnBattery => status.on_battery,
+            Self::SkipBelowThreshold => {
+                status.on_battery
+                    && status
+                        .battery_percent
+                        .is_some_and(|percent| percent < threshold_percent)
+            }
+        }
+    }
+}
+
+/// Pattern matching the process names of a Win32 application
+///
+/// Lets one entry watch a family of executables, such as the `ue4game-*` builds of an
+/// Unreal Engine game, instead of one exact process name. Patterns match the whole
+/// process name and ignore case. Process names carry no extension, so a trailing `.exe`
+/// in a glob is ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchRule {
+    /// Glob where `*` matches any run of characters and `?` any single character
+    Glob(String),
+    /// Regular expression
+    Regex(String),
+}
+
+impl MatchRule {
+    /// Anchored regular expression for the rule, for matching normalized process names
+    pub fn regex_source(&self) -> String {
+        match self {
+            Self::Glob(pattern) => {
+                let pattern = normalize_process_name(pattern);
+                let pattern = pattern.strip_suffix(".exe").unwrap_or(&pattern);
+
+                let mut source = String::from("^");
+                for c in pattern.chars() {
+                    match c {
+                        '*' => source.push_str(".*"),
+                        '?' => source.push('.'),
+                        c => source.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                    }
+                }
+                source.push('$');
+                source
+            }
+            Self::Regex(pattern) => format!("^(?:{})$", pattern.trim()),
+        }
+    }
+
+    /// Check that the rule compiles
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the pattern is empty or not a valid regular expression.
+    pub fn validate(&self) -> Result<()> {
+        let (Self::Glob(pattern) | Self::Regex(pattern)) = self;
+        if pattern.trim().is_empty() {
+            return Err(EasyHdrError::ConfigError(StringError::new(
+                "match rule pattern is empty",
+            )));
+        }
+
+        regex::RegexBuilder::new(&self.regex_source())
+            .case_insensitive(true)
+            .build()
+            .map(|_| ())
+            .map_err(|e| {
+                EasyHdrError::ConfigError(StringError::new(format!(
+                    "invalid match rule '{pattern}': {e}"
+                )))
+            })
+    }
+}
+
 /// Win32 desktop application
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Win32App {
@@ -21,6 +264,41 @@ pub struct Win32App {
     pub process_name: String,
     /// Whether monitoring is enabled for this application
     pub enabled: bool,
+    /// Where this entry came from
+    #[serde(default)]
+    pub source: AppSource,
+    /// Windows 11 Auto HDR override for this executable
+    #[serde(default)]
+    pub auto_hdr: AutoHdrMode,
+    /// Display mode applied together with HDR while this application runs
+    #[serde(default)]
+    pub display_mode: Option<DisplayModeOverride>,
+    /// Per-application override of [`UserPreferences::hdr_disable_debounce_ms`]
+    #[serde(default)]
+    pub hdr_disable_debounce_ms: Option<u64>,
+    /// Per-application override of [`UserPreferences::max_hdr_session_minutes`]
+    #[serde(default)]
+    pub max_hdr_session_minutes: Option<u32>,
+    /// Color mode used instead of HDR while this application runs (`None` means HDR)
+    #[serde(default)]
+    pub color_mode: Option<ColorMode>,
+    /// Tone mapping of Game Bar HDR captures while HDR is on for this application
+    /// (`None` leaves the Windows setting alone)
+    #[serde(default)]
+    pub capture_tone_mapping: Option<bool>,
+    /// Name of the display group HDR is turned on for while this application runs
+    /// (`None` means every HDR display)
+    #[serde(default)]
+    pub display_group: Option<String>,
+    /// Pattern matching further process names (`None` matches `process_name` only)
+    #[serde(default)]
+    pub match_rule: Option<MatchRule>,
+    /// Alternative executables treated as this application, such as the differently
+    /// named builds of a localized game
+    ///
+    /// Entries are process names or executable paths; only the file name is matched.
+    #[serde(default)]
+    pub aliases: Vec<String>,
     /// Cached icon data (not persisted to config file)
     #[serde(skip)]
     pub icon_data: Option<Vec<u8>>,
@@ -39,18 +317,96 @@ pub struct UwpApp {
     pub app_id: String,
     /// Whether monitoring is enabled for this application
     pub enabled: bool,
+    /// Whether detection matches only this application (AUMID) instead of any process
+    /// from the package
+    ///
+    /// Set for packages that contain more than one application. Processes whose AUMID
+    /// cannot be determined still match at the package level.
+    #[serde(default)]
+    pub match_app_id: bool,
+    /// Where this entry came from
+    #[serde(default)]
+    pub source: AppSource,
+    /// Display mode applied together with HDR while this application runs
+    #[serde(default)]
+    pub display_mode: Option<DisplayModeOverride>,
+    /// Per-application override of [`UserPreferences::hdr_disable_debounce_ms`]
+    #[serde(default)]
+    pub hdr_disable_debounce_ms: Option<u64>,
+    /// Per-application override of [`UserPreferences::max_hdr_session_minutes`]
+    #[serde(default)]
+    pub max_hdr_session_minutes: Option<u32>,
+    /// Color mode used instead of HDR while this application runs (`None` means HDR)
+    #[serde(default)]
+    pub color_mode: Option<ColorMode>,
+    /// Tone mapping of Game Bar HDR captures while HDR is on for this application
+    /// (`None` leaves the Windows setting alone)
+    #[serde(default)]
+    pub capture_tone_mapping: Option<bool>,
+    /// Name of the display group HDR is turned on for while this application runs
+    /// (`None` means every HDR display)
+    #[serde(default)]
+    pub display_group: Option<String>,
+    /// Cached icon data (not persisted to config file)
+    #[serde(skip)]
+    pub icon_data: Option<Vec<u8>>,
+}
+
+/// Folder whose executables are all monitored
+///
+/// Any process whose image lies inside the folder or one of its subfolders counts as
+/// this application, so a whole game library can be watched with one entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct FolderApp {
+    /// Unique identifier for this application entry
+    pub id: Uuid,
+    /// Display name shown in the UI
+    pub display_name: String,
+    /// Full path to the folder
+    pub path: PathBuf,
+    /// Whether monitoring is enabled for this folder
+    pub enabled: bool,
+    /// Where this entry came from
+    #[serde(default)]
+    pub source: AppSource,
+    /// Display mode applied together with HDR while an executable from the folder runs
+    #[serde(default)]
+    pub display_mode: Option<DisplayModeOverride>,
+    /// Per-application override of [`UserPreferences::hdr_disable_debounce_ms`]
+    #[serde(default)]
+    pub hdr_disable_debounce_ms: Option<u64>,
+    /// Per-application override of [`UserPreferences::max_hdr_session_minutes`]
+    #[serde(default)]
+    pub max_hdr_session_minutes: Option<u32>,
+    /// Color mode used instead of HDR while an executable from the folder runs
+    #[serde(default)]
+    pub color_mode: Option<ColorMode>,
+    /// Tone mapping of Game Bar HDR captures while HDR is on for the folder
+    /// (`None` leaves the Windows setting alone)
+    #[serde(default)]
+    pub capture_tone_mapping: Option<bool>,
+    /// Name of the display group HDR is turned on for while an executable from the
+    /// folder runs (`None` means every HDR display)
+    #[serde(default)]
+    pub display_group: Option<String>,
     /// Cached icon data (not persisted to config file)
     #[serde(skip)]
     pub icon_data: Option<Vec<u8>>,
 }
 
-/// Represents a monitored application (Win32 or UWP)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Represents a monitored application (Win32, UWP or folder)
+///
+/// Stored with an `app_type` tag. Entries written before the tag existed are tagged by
+/// the configuration migration before they are deserialized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(tag = "app_type", rename_all = "lowercase")]
 pub enum MonitoredApp {
     /// Traditional Win32 desktop application
     Win32(Win32App),
     /// Universal Windows Platform application
     Uwp(UwpApp),
+    /// Every executable inside a folder
+    Folder(FolderApp),
 }
 
 impl std::fmt::Display for MonitoredApp {
@@ -58,6 +414,7 @@ impl std::fmt::Display for MonitoredApp {
         match self {
             Self::Win32(app) => write!(f, "Win32: {} ({})", app.display_name, app.process_name),
             Self::Uwp(app) => write!(f, "UWP: {} ({})", app.display_name, app.package_family_name),
+            Self::Folder(app) => write!(f, "Folder: {} ({})", app.display_name, app.path.display()),
         }
     }
 }
@@ -66,13 +423,16 @@ impl Win32App {
     /// Create a Win32 app from an executable path
     ///
     /// Extracts display name from file metadata, icon from resources, and generates
-    /// a unique UUID. Process name is derived from filename (lowercase, no extension).
+    /// a unique UUID. Process name is derived from filename (normalized with
+    /// [`normalize_process_name`], no extension). A `\\?\` prefix is removed so the
+    /// configuration always stores the plain path.
     ///
     /// Accepts any type that can be converted into a `PathBuf` for better ergonomics.
     pub fn from_exe_path(exe_path: impl Into<PathBuf>) -> Result<Self> {
         use crate::error::EasyHdrError;
+        use crate::utils::long_path;
 
-        let exe_path = exe_path.into();
+        let exe_path = long_path::to_plain(&exe_path.into());
 
         // Validate that the path exists and is a file
         if !exe_path.exists() {
@@ -88,19 +448,19 @@ impl Win32App {
         }
 
         // Extract display name from metadata (with fallback to filename)
-        let display_name = extract_display_name_from_exe(&exe_path)?;
+        let display_name = clean_display_name(&extract_display_name_from_exe(&exe_path)?);
 
-        // Extract process name from filename (lowercase, without extension)
+        // Extract process name from filename (normalized, without extension)
         let process_name = exe_path
             .file_stem()
             .and_then(|s| s.to_str())
+            .map(normalize_process_name)
             .ok_or_else(|| {
                 EasyHdrError::ConfigError(crate::error::StringError::new(format!(
                     "Failed to extract filename from path: {}",
                     exe_path.display()
                 )))
-            })?
-            .to_lowercase();
+            })?;
 
         // Generate unique UUID for this app
         // Thread safety: Each call generates a unique UUID, preventing file path conflicts
@@ -133,6 +493,16 @@ impl Win32App {
             exe_path,
             process_name,
             enabled: true, // Default to enabled
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data,
         })
     }
@@ -173,6 +543,30 @@ impl Win32App {
             tracing::debug!("Released icon data for {}", self.display_name);
         }
     }
+
+    /// Split a list of aliases typed by the user
+    ///
+    /// Entries are separated by commas, semicolons or line breaks. Surrounding
+    /// whitespace, empty entries and duplicates (ignoring case) are dropped.
+    pub fn parse_aliases(text: &str) -> Vec<String> {
+        let mut aliases: Vec<String> = Vec::new();
+        for alias in text.split([',', ';', '\n']).map(str::trim) {
+            if !alias.is_empty() && !aliases.iter().any(|known| eq_ignore_case(known, alias)) {
+                aliases.push(alias.to_string());
+            }
+        }
+        aliases
+    }
+
+    /// Process names the aliases match, normalized like `process_name`
+    pub fn alias_process_names(&self) -> impl Iterator<Item = String> + '_ {
+        self.aliases.iter().filter_map(|alias| {
+            let file_name = alias.rsplit(['\\', '/']).next().unwrap_or(alias);
+            let name = normalize_process_name(file_name);
+            let name = name.strip_suffix(".exe").unwrap_or(&name);
+            (!name.is_empty()).then(|| name.to_string())
+        })
+    }
 }
 
 impl UwpApp {
@@ -265,10 +659,23 @@ impl UwpApp {
             package_family_name,
             app_id,
             enabled: true, // Default to enabled
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data,
         }
     }
 
+    /// Application User Model ID (`PackageFamilyName!AppId`) of this application
+    pub fn aumid(&self) -> String {
+        format!("{}!{}", self.package_family_name, self.app_id)
+    }
+
     /// Release icon data from memory
     ///
     /// Removes the icon data from memory and updates the memory profiler.
@@ -282,12 +689,60 @@ impl UwpApp {
     }
 }
 
+impl FolderApp {
+    /// Create a folder entry from a directory path
+    ///
+    /// The display name is the folder name. A `\\?\` prefix is removed so the
+    /// configuration always stores the plain path.
+    pub fn from_path(path: impl Into<PathBuf>) -> Result<Self> {
+        use crate::utils::long_path;
+
+        let path = long_path::to_plain(&path.into());
+        if !path.is_dir() {
+            return Err(EasyHdrError::ConfigError(StringError::new(format!(
+                "Path is not a folder: {}",
+                path.display()
+            ))));
+        }
+
+        let display_name = path
+            .file_name()
+            .map(|name| clean_display_name(&name.to_string_lossy()))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| path.display().to_string());
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            display_name,
+            path,
+            enabled: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        })
+    }
+
+    /// Release icon data from memory
+    pub fn release_icon(&mut self) {
+        if let Some(icon_data) = self.icon_data.take() {
+            crate::utils::memory_profiler::record_icon_removed_safe(icon_data.len());
+            tracing::debug!("Released icon data for {}", self.display_name);
+        }
+    }
+}
+
 impl MonitoredApp {
     /// Get the unique ID regardless of app type
     pub fn id(&self) -> &Uuid {
         match self {
             Self::Win32(app) => &app.id,
             Self::Uwp(app) => &app.id,
+            Self::Folder(app) => &app.id,
         }
     }
 
@@ -296,6 +751,16 @@ impl MonitoredApp {
         match self {
             Self::Win32(app) => &app.display_name,
             Self::Uwp(app) => &app.display_name,
+            Self::Folder(app) => &app.display_name,
+        }
+    }
+
+    /// Set the display name shown in the UI
+    pub fn set_display_name(&mut self, display_name: String) {
+        match self {
+            Self::Win32(app) => app.display_name = display_name,
+            Self::Uwp(app) => app.display_name = display_name,
+            Self::Folder(app) => app.display_name = display_name,
         }
     }
 
@@ -304,6 +769,140 @@ impl MonitoredApp {
         match self {
             Self::Win32(app) => app.enabled,
             Self::Uwp(app) => app.enabled,
+            Self::Folder(app) => app.enabled,
+        }
+    }
+
+    /// Get where this entry came from
+    pub fn source(&self) -> AppSource {
+        match self {
+            Self::Win32(app) => app.source,
+            Self::Uwp(app) => app.source,
+            Self::Folder(app) => app.source,
+        }
+    }
+
+    /// Get the display mode applied together with HDR while this application runs
+    pub fn display_mode(&self) -> Option<DisplayModeOverride> {
+        match self {
+            Self::Win32(app) => app.display_mode,
+            Self::Uwp(app) => app.display_mode,
+            Self::Folder(app) => app.display_mode,
+        }
+    }
+
+    /// Get the per-application override of the HDR disable delay in milliseconds
+    pub fn hdr_disable_debounce_ms(&self) -> Option<u64> {
+        match self {
+            Self::Win32(app) => app.hdr_disable_debounce_ms,
+            Self::Uwp(app) => app.hdr_disable_debounce_ms,
+            Self::Folder(app) => app.hdr_disable_debounce_ms,
+        }
+    }
+
+    /// Set the per-application override of the HDR disable delay in milliseconds
+    pub fn set_hdr_disable_debounce_ms(&mut self, hdr_disable_debounce_ms: Option<u64>) {
+        match self {
+            Self::Win32(app) => app.hdr_disable_debounce_ms = hdr_disable_debounce_ms,
+            Self::Uwp(app) => app.hdr_disable_debounce_ms = hdr_disable_debounce_ms,
+            Self::Folder(app) => app.hdr_disable_debounce_ms = hdr_disable_debounce_ms,
+        }
+    }
+
+    /// Get the per-application override of the HDR session limit in minutes
+    pub fn max_hdr_session_minutes(&self) -> Option<u32> {
+        match self {
+            Self::Win32(app) => app.max_hdr_session_minutes,
+            Self::Uwp(app) => app.max_hdr_session_minutes,
+            Self::Folder(app) => app.max_hdr_session_minutes,
+        }
+    }
+
+    /// Set the per-application override of the HDR session limit in minutes
+    pub fn set_max_hdr_session_minutes(&mut self, max_hdr_session_minutes: Option<u32>) {
+        match self {
+            Self::Win32(app) => app.max_hdr_session_minutes = max_hdr_session_minutes,
+            Self::Uwp(app) => app.max_hdr_session_minutes = max_hdr_session_minutes,
+            Self::Folder(app) => app.max_hdr_session_minutes = max_hdr_session_minutes,
+        }
+    }
+
+    /// Get the color mode used instead of HDR while this application runs
+    pub fn color_mode(&self) -> Option<ColorMode> {
+        match self {
+            Self::Win32(app) => app.color_mode,
+            Self::Uwp(app) => app.color_mode,
+            Self::Folder(app) => app.color_mode,
+        }
+    }
+
+    /// Set the color mode used instead of HDR while this application runs
+    pub fn set_color_mode(&mut self, color_mode: Option<ColorMode>) {
+        match self {
+            Self::Win32(app) => app.color_mode = color_mode,
+            Self::Uwp(app) => app.color_mode = color_mode,
+            Self::Folder(app) => app.color_mode = color_mode,
+        }
+    }
+
+    /// Get the tone mapping of HDR captures applied while this application runs
+    pub fn capture_tone_mapping(&self) -> Option<bool> {
+        match self {
+            Self::Win32(app) => app.capture_tone_mapping,
+            Self::Uwp(app) => app.capture_tone_mapping,
+            Self::Folder(app) => app.capture_tone_mapping,
+        }
+    }
+
+    /// Set the tone mapping of HDR captures applied while this application runs
+    pub fn set_capture_tone_mapping(&mut self, capture_tone_mapping: Option<bool>) {
+        match self {
+            Self::Win32(app) => app.capture_tone_mapping = capture_tone_mapping,
+            Self::Uwp(app) => app.capture_tone_mapping = capture_tone_mapping,
+            Self::Folder(app) => app.capture_tone_mapping = capture_tone_mapping,
+        }
+    }
+
+    /// Get the name of the display group HDR is turned on for while this application runs
+    pub fn display_group(&self) -> Option<&str> {
+        match self {
+            Self::Win32(app) => app.display_group.as_deref(),
+            Self::Uwp(app) => app.display_group.as_deref(),
+            Self::Folder(app) => app.display_group.as_deref(),
+        }
+    }
+
+    /// Set the name of the display group HDR is turned on for while this application runs
+    pub fn set_display_group(&mut self, display_group: Option<String>) {
+        match self {
+            Self::Win32(app) => app.display_group = display_group,
+            Self::Uwp(app) => app.display_group = display_group,
+            Self::Folder(app) => app.display_group = display_group,
+        }
+    }
+
+    /// Alternative executables treated as this application (Win32 applications only)
+    pub fn aliases(&self) -> &[String] {
+        match self {
+            Self::Win32(app) => &app.aliases,
+            Self::Uwp(_) | Self::Folder(_) => &[],
+        }
+    }
+
+    /// Pattern matching further process names (Win32 applications only)
+    pub fn match_rule(&self) -> Option<&MatchRule> {
+        match self {
+            Self::Win32(app) => app.match_rule.as_ref(),
+            Self::Uwp(_) | Self::Folder(_) => None,
+        }
+    }
+
+    /// Set the display mode applied together with HDR while this application runs
+    pub fn set_display_mode(&mut self, display_mode: Option<DisplayModeOverride>) {
+        match self {
+            Self::Win32(app) => app.display_mode = display_mode,
+            Self::Uwp(app) => app.display_mode = display_mode,
+            Self::Folder(app) => app.display_mode = display_mode,
         }
     }
 
@@ -312,6 +911,7 @@ impl MonitoredApp {
         match self {
             Self::Win32(app) => &mut app.icon_data,
             Self::Uwp(app) => &mut app.icon_data,
+            Self::Folder(app) => &mut app.icon_data,
         }
     }
 
@@ -323,7 +923,25 @@ impl MonitoredApp {
         match self {
             Self::Win32(app) => app.ensure_icon_loaded(),
             Self::Uwp(app) => app.icon_data.as_ref(),
+            Self::Folder(app) => app.icon_data.as_ref(),
+        }
+    }
+
+    /// Fill in a letter-tile icon if no icon is loaded
+    ///
+    /// Generates a deterministic tile from the display name so the GUI never shows a
+    /// blank square. Returns `true` if a tile was generated; the caller is responsible
+    /// for persisting it to the icon cache.
+    pub fn ensure_fallback_icon(&mut self) -> bool {
+        if self.icon_data().is_some() {
+            return false;
         }
+
+        let tile = crate::utils::generate_letter_tile(self.display_name());
+        crate::utils::memory_profiler::record_icon_cached_safe(tile.len());
+        tracing::debug!("Generated letter-tile icon for {}", self.display_name());
+        *self.icon_data_mut() = Some(tile);
+        true
     }
 
     /// Release icon data to free memory
@@ -333,6 +951,7 @@ impl MonitoredApp {
         match self {
             Self::Win32(app) => app.release_icon(),
             Self::Uwp(app) => app.release_icon(),
+            Self::Folder(app) => app.release_icon(),
         }
     }
 
@@ -353,6 +972,7 @@ impl MonitoredApp {
         match self {
             Self::Win32(app) => app.enabled = enabled,
             Self::Uwp(app) => app.enabled = enabled,
+            Self::Folder(app) => app.enabled = enabled,
         }
     }
 
@@ -372,57 +992,7 @@ impl MonitoredApp {
         match self {
             Self::Win32(app) => &app.icon_data,
             Self::Uwp(app) => &app.icon_data,
-        }
-    }
-}
-
-/// Backward-compatible deserialization for `MonitoredApp` enum
-///
-/// Supports both:
-/// - Legacy format: entries without `app_type` field (migrated to Win32)
-/// - New format: entries with `app_type` field ("win32" or "uwp")
-impl<'de> Deserialize<'de> for MonitoredApp {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        #[derive(Deserialize)]
-        #[serde(tag = "app_type", rename_all = "lowercase")]
-        enum Tagged {
-            Win32(Win32App),
-            Uwp(UwpApp),
-        }
-
-        #[derive(Deserialize)]
-        struct Legacy {
-            id: Uuid,
-            display_name: String,
-            exe_path: PathBuf,
-            process_name: String,
-            enabled: bool,
-        }
-
-        #[derive(Deserialize)]
-        #[serde(untagged)]
-        enum Helper {
-            Tagged(Tagged),
-            Legacy(Legacy),
-        }
-
-        match Helper::deserialize(deserializer)? {
-            Helper::Tagged(Tagged::Win32(app)) => Ok(Self::Win32(app)),
-            Helper::Tagged(Tagged::Uwp(app)) => Ok(Self::Uwp(app)),
-            Helper::Legacy(legacy) => {
-                // Migrate legacy format to Win32App
-                Ok(Self::Win32(Win32App {
-                    id: legacy.id,
-                    display_name: legacy.display_name,
-                    exe_path: legacy.exe_path,
-                    process_name: legacy.process_name,
-                    enabled: legacy.enabled,
-                    icon_data: None,
-                }))
-            }
+            Self::Folder(app) => &app.icon_data,
         }
     }
 }
@@ -437,29 +1007,119 @@ impl Serialize for MonitoredApp {
 
         match self {
             Self::Win32(app) => {
-                let mut state = serializer.serialize_struct("MonitoredApp", 6)?;
+                let mut state = serializer.serialize_struct("MonitoredApp", 10)?;
                 state.serialize_field("app_type", "win32")?;
                 state.serialize_field("id", &app.id)?;
                 state.serialize_field("display_name", &app.display_name)?;
                 state.serialize_field("exe_path", &app.exe_path)?;
                 state.serialize_field("process_name", &app.process_name)?;
                 state.serialize_field("enabled", &app.enabled)?;
+                state.serialize_field("source", &app.source)?;
+                state.serialize_field("auto_hdr", &app.auto_hdr)?;
+                serialize_optional_field(&mut state, "display_mode", app.display_mode.as_ref())?;
+                serialize_optional_field(
+                    &mut state,
+                    "hdr_disable_debounce_ms",
+                    app.hdr_disable_debounce_ms.as_ref(),
+                )?;
+                serialize_optional_field(
+                    &mut state,
+                    "max_hdr_session_minutes",
+                    app.max_hdr_session_minutes.as_ref(),
+                )?;
+                serialize_optional_field(&mut state, "color_mode", app.color_mode.as_ref())?;
+                serialize_optional_field(
+                    &mut state,
+                    "capture_tone_mapping",
+                    app.capture_tone_mapping.as_ref(),
+                )?;
+                serialize_optional_field(&mut state, "display_group", app.display_group.as_ref())?;
+                serialize_optional_field(&mut state, "match_rule", app.match_rule.as_ref())?;
+                serialize_optional_field(
+                    &mut state,
+                    "aliases",
+                    (!app.aliases.is_empty()).then_some(&app.aliases),
+                )?;
                 state.end()
             }
             Self::Uwp(app) => {
-                let mut state = serializer.serialize_struct("MonitoredApp", 6)?;
+                let mut state = serializer.serialize_struct("MonitoredApp", 10)?;
                 state.serialize_field("app_type", "uwp")?;
                 state.serialize_field("id", &app.id)?;
                 state.serialize_field("display_name", &app.display_name)?;
                 state.serialize_field("package_family_name", &app.package_family_name)?;
                 state.serialize_field("app_id", &app.app_id)?;
                 state.serialize_field("enabled", &app.enabled)?;
+                state.serialize_field("match_app_id", &app.match_app_id)?;
+                state.serialize_field("source", &app.source)?;
+                serialize_optional_field(&mut state, "display_mode", app.display_mode.as_ref())?;
+                serialize_optional_field(
+                    &mut state,
+                    "hdr_disable_debounce_ms",
+                    app.hdr_disable_debounce_ms.as_ref(),
+                )?;
+                serialize_optional_field(
+                    &mut state,
+                    "max_hdr_session_minutes",
+                    app.max_hdr_session_minutes.as_ref(),
+                )?;
+                serialize_optional_field(&mut state, "color_mode", app.color_mode.as_ref())?;
+                serialize_optional_field(
+                    &mut state,
+                    "capture_tone_mapping",
+                    app.capture_tone_mapping.as_ref(),
+                )?;
+                serialize_optional_field(&mut state, "display_group", app.display_group.as_ref())?;
+                state.end()
+            }
+            Self::Folder(app) => {
+                let mut state = serializer.serialize_struct("MonitoredApp", 10)?;
+                state.serialize_field("app_type", "folder")?;
+                state.serialize_field("id", &app.id)?;
+                state.serialize_field("display_name", &app.display_name)?;
+                state.serialize_field("path", &app.path)?;
+                state.serialize_field("enabled", &app.enabled)?;
+                state.serialize_field("source", &app.source)?;
+                serialize_optional_field(&mut state, "display_mode", app.display_mode.as_ref())?;
+                serialize_optional_field(
+                    &mut state,
+                    "hdr_disable_debounce_ms",
+                    app.hdr_disable_debounce_ms.as_ref(),
+                )?;
+                serialize_optional_field(
+                    &mut state,
+                    "max_hdr_session_minutes",
+                    app.max_hdr_session_minutes.as_ref(),
+                )?;
+                serialize_optional_field(&mut state, "color_mode", app.color_mode.as_ref())?;
+                serialize_optional_field(
+                    &mut state,
+                    "capture_tone_mapping",
+                    app.capture_tone_mapping.as_ref(),
+                )?;
+                serialize_optional_field(&mut state, "display_group", app.display_group.as_ref())?;
                 state.end()
             }
         }
     }
 }
 
+/// Serialize an optional per-application setting, leaving it out of the entry when unset
+fn serialize_optional_field<S, T>(
+    state: &mut S,
+    key: &'static str,
+    value: Option<&T>,
+) -> std::result::Result<(), S::Error>
+where
+    S: serde::ser::SerializeStruct,
+    T: Serialize + ?Sized,
+{
+    match value {
+        Some(value) => state.serialize_field(key, value),
+        None => state.skip_field(key),
+    }
+}
+
 impl AsRef<std::path::Path> for Win32App {
     fn as_ref(&self) -> &std::path::Path {
         &self.exe_path
@@ -492,30 +1152,175 @@ impl std::convert::TryFrom<PathBuf> for Win32App {
 pub struct AppConfig {
     /// List of monitored applications
     pub monitored_apps: Vec<MonitoredApp>,
+    /// Process names that keep HDR off while they run, even if a monitored application
+    /// is running too
+    ///
+    /// Meant for tools that misbehave in HDR, such as some screen recorders. Names are
+    /// matched like the process names of Win32 applications.
+    pub excluded_processes: Vec<String>,
     /// User preferences
     pub preferences: UserPreferences,
     /// Window state for persistence
     pub window_state: WindowState,
+    /// Displays seen so far, keyed by stable identity
+    pub known_displays: Vec<KnownDisplay>,
+    /// Named groups of displays that applications can be bound to, such as "TV" or
+    /// "Desk monitors"
+    ///
+    /// Only settable in the config file.
+    pub display_groups: Vec<DisplayGroup>,
 }
 
-/// Custom deserializer for `AppConfig` that handles partial failures in `monitored_apps`
-///
-/// When deserialization fails for an individual app entry, it logs the error and
-/// continues loading other valid entries.
-impl<'de> Deserialize<'de> for AppConfig {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        use serde::de::{MapAccess, Visitor};
-        use std::fmt;
-
+impl AppConfig {
+    /// Replace the exclusion list with the process names listed in `text`
+    ///
+    /// Names are separated by commas, semicolons or line breaks. Each is normalized
+    /// with [`normalize_process_name`] and loses a trailing `.exe`; empty entries and
+    /// duplicates are dropped.
+    pub fn set_excluded_processes(&mut self, text: &str) {
+        let mut names: Vec<String> = Vec::new();
+        for name in text.split([',', ';', '\n']) {
+            let name = normalize_process_name(name);
+            let name = name.strip_suffix(".exe").unwrap_or(&name);
+            if !name.is_empty() && !names.iter().any(|known| known == name) {
+                names.push(name.to_string());
+            }
+        }
+        self.excluded_processes = names;
+    }
+
+    /// Record the identities of the given displays
+    ///
+    /// Adds displays not seen before and refreshes the stored name of known ones.
+    /// Displays without a stable identity are skipped. Returns `true` if anything
+    /// changed and the configuration should be saved.
+    pub fn remember_displays(&mut self, displays: &[DisplayTarget]) -> bool {
+        let mut changed = false;
+
+        for display in displays {
+            let Some(identity) = &display.identity else {
+                continue;
+            };
+            let name = display.display_name();
+
+            match self
+                .known_displays
+                .iter_mut()
+                .find(|known| &known.identity == identity)
+            {
+                Some(known) if known.name == name => {}
+                Some(known) => {
+                    known.name = name;
+                    changed = true;
+                }
+                None => {
+                    self.known_displays.push(KnownDisplay {
+                        identity: identity.clone(),
+                        name,
+                        oled_care: None,
+                        hdr_color_profile: None,
+                    });
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Look up a known display by its stable identity
+    pub fn known_display(&self, identity: &DisplayIdentity) -> Option<&KnownDisplay> {
+        self.known_displays
+            .iter()
+            .find(|known| &known.identity == identity)
+    }
+
+    /// OLED care settings of a display, `None` unless it is tagged as OLED
+    pub fn oled_care(&self, display: &DisplayTarget) -> Option<&OledCare> {
+        display
+            .identity
+            .as_ref()
+            .and_then(|identity| self.known_display(identity))
+            .and_then(|known| known.oled_care.as_ref())
+    }
+
+    /// Tag a known display as OLED with the given care settings, or untag it with `None`
+    ///
+    /// Returns `false` if no known display has the identity.
+    pub fn set_oled_care(
+        &mut self,
+        identity: &DisplayIdentity,
+        oled_care: Option<OledCare>,
+    ) -> bool {
+        self.known_display_mut(identity)
+            .map(|known| known.oled_care = oled_care)
+            .is_some()
+    }
+
+    /// HDR color profile of a display, `None` to leave the Windows default alone
+    pub fn hdr_color_profile(&self, display: &DisplayTarget) -> Option<&str> {
+        display
+            .identity
+            .as_ref()
+            .and_then(|identity| self.known_display(identity))
+            .and_then(|known| known.hdr_color_profile.as_deref())
+    }
+
+    /// Set the HDR color profile of a known display, or clear it with `None`
+    ///
+    /// Returns `false` if no known display has the identity.
+    pub fn set_hdr_color_profile(
+        &mut self,
+        identity: &DisplayIdentity,
+        profile: Option<String>,
+    ) -> bool {
+        self.known_display_mut(identity)
+            .map(|known| known.hdr_color_profile = profile)
+            .is_some()
+    }
+
+    /// Look up a display group by name, ignoring case
+    pub fn display_group(&self, name: &str) -> Option<&DisplayGroup> {
+        self.display_groups
+            .iter()
+            .find(|group| eq_ignore_case(&group.name, name))
+    }
+
+    /// Look up a known display for modification
+    fn known_display_mut(&mut self, identity: &DisplayIdentity) -> Option<&mut KnownDisplay> {
+        self.known_displays
+            .iter_mut()
+            .find(|known| &known.identity == identity)
+    }
+}
+
+/// Custom deserializer for `AppConfig` that handles partial failures in `monitored_apps`
+///
+/// When deserialization fails for an individual app entry, it logs the error and
+/// continues loading other valid entries.
+impl<'de> Deserialize<'de> for AppConfig {
+    #[expect(
+        clippy::too_many_lines,
+        reason = "Lenient per-entry deserialization of every config section"
+    )]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{MapAccess, Visitor};
+        use std::fmt;
+
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "snake_case")]
         enum Field {
             MonitoredApps,
+            ExcludedProcesses,
             Preferences,
             WindowState,
+            KnownDisplays,
+            DisplayGroups,
+            // Handled by `ConfigManager` before deserializing, see `config::migration`
+            SchemaVersion,
         }
 
         struct AppConfigVisitor;
@@ -532,8 +1337,11 @@ impl<'de> Deserialize<'de> for AppConfig {
                 V: MapAccess<'de>,
             {
                 let mut monitored_apps: Option<Vec<MonitoredApp>> = None;
+                let mut excluded_processes: Option<Vec<String>> = None;
                 let mut preferences: Option<UserPreferences> = None;
                 let mut window_state: Option<WindowState> = None;
+                let mut known_displays: Option<Vec<KnownDisplay>> = None;
+                let mut display_groups: Option<Vec<DisplayGroup>> = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -565,6 +1373,14 @@ impl<'de> Deserialize<'de> for AppConfig {
 
                             monitored_apps = Some(valid_apps);
                         }
+                        Field::ExcludedProcesses => {
+                            if excluded_processes.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "excluded_processes",
+                                ));
+                            }
+                            excluded_processes = Some(map.next_value()?);
+                        }
                         Field::Preferences => {
                             if preferences.is_some() {
                                 return Err(serde::de::Error::duplicate_field("preferences"));
@@ -577,18 +1393,44 @@ impl<'de> Deserialize<'de> for AppConfig {
                             }
                             window_state = Some(map.next_value()?);
                         }
+                        Field::KnownDisplays => {
+                            if known_displays.is_some() {
+                                return Err(serde::de::Error::duplicate_field("known_displays"));
+                            }
+                            known_displays = Some(map.next_value()?);
+                        }
+                        Field::DisplayGroups => {
+                            if display_groups.is_some() {
+                                return Err(serde::de::Error::duplicate_field("display_groups"));
+                            }
+                            display_groups = Some(map.next_value()?);
+                        }
+                        Field::SchemaVersion => {
+                            map.next_value::<serde::de::IgnoredAny>()?;
+                        }
                     }
                 }
 
                 Ok(AppConfig {
                     monitored_apps: monitored_apps.unwrap_or_default(),
+                    excluded_processes: excluded_processes.unwrap_or_default(),
                     preferences: preferences.unwrap_or_default(),
                     window_state: window_state.unwrap_or_default(),
+                    known_displays: known_displays.unwrap_or_default(),
+                    display_groups: display_groups.unwrap_or_default(),
                 })
             }
         }
 
-        const FIELDS: &[&str] = &["monitored_apps", "preferences", "window_state"];
+        const FIELDS: &[&str] = &[
+            "monitored_apps",
+            "excluded_processes",
+            "preferences",
+            "window_state",
+            "known_displays",
+            "display_groups",
+            "schema_version",
+        ];
         deserializer.deserialize_struct("AppConfig", FIELDS, AppConfigVisitor)
     }
 }
@@ -602,6 +1444,15 @@ impl<'de> Deserialize<'de> for AppConfig {
 pub struct UserPreferences {
     /// Whether to auto-start on Windows login
     pub auto_start: bool,
+    /// How auto-start is registered
+    #[serde(default)]
+    pub autostart_method: AutoStartMethod,
+    /// Whether the logon task starts `EasyHDR` with highest privileges
+    #[serde(default)]
+    pub autostart_elevated: bool,
+    /// Seconds the logon task waits after login before starting `EasyHDR`
+    #[serde(default)]
+    pub autostart_delay_secs: u32,
     /// Process monitoring interval in milliseconds (500-2000)
     pub monitoring_interval_ms: u64,
     /// Whether to show tray notifications on HDR changes
@@ -612,6 +1463,9 @@ pub struct UserPreferences {
     /// Whether to automatically open the GitHub release page when updates are detected
     #[serde(default)]
     pub auto_open_release_page: bool,
+    /// Releases offered by the update checker
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
     /// Whether to minimize to tray when minimize button is clicked (true) or minimize to taskbar (false)
     pub minimize_to_tray_on_minimize: bool,
     /// Whether to minimize to tray when close button is clicked (true) or close the application (false)
@@ -625,6 +1479,207 @@ pub struct UserPreferences {
     /// Cached latest version from the last update check (empty if never checked or failed)
     #[serde(default)]
     pub cached_latest_version: String,
+    /// Conditional request validators and rate limit backoff of the update checker
+    #[serde(default)]
+    pub update_check_cache: UpdateCheckCache,
+    /// Whether the UWP picker lists packages installed for all users (requires elevation)
+    #[serde(default)]
+    pub uwp_enumerate_all_users: bool,
+    /// Whether processes launched by a monitored application count as that application
+    ///
+    /// Off by default: launchers such as Steam outlive the games they start, so every
+    /// game they launch would count as the launcher.
+    #[serde(default)]
+    pub track_child_processes: bool,
+    /// Whether a monitored application only counts as started once one of its processes
+    /// shows a visible window
+    #[serde(default)]
+    pub wait_for_window: bool,
+    /// Whether the process monitor polls faster after activity, such as a foreground
+    /// window switch, and slower while nothing happens
+    ///
+    /// Only settable in the config file.
+    #[serde(default)]
+    pub adaptive_polling: bool,
+    /// Longest interval in milliseconds adaptive polling stretches to while idle, clamped
+    /// to the monitoring interval and [`MAX_IDLE_POLLING_INTERVAL_MS`]
+    ///
+    /// Only settable in the config file.
+    #[serde(default = "default_max_idle_polling_interval_ms")]
+    pub max_idle_polling_interval_ms: u64,
+    /// Delay in milliseconds before HDR is turned off after the last monitored application
+    /// exits (0-10000)
+    ///
+    /// The disable is cancelled if a monitored application starts again within the delay,
+    /// so games that relaunch themselves keep HDR on.
+    #[serde(default = "default_hdr_disable_debounce_ms")]
+    pub hdr_disable_debounce_ms: u64,
+    /// Whether to show a weekly notification summarizing HDR usage
+    #[serde(default)]
+    pub weekly_summary_notification: bool,
+    /// Timestamp of the last weekly summary (Unix timestamp in seconds, 0 if never shown)
+    #[serde(default)]
+    pub last_weekly_summary_time: u64,
+    /// Minimum severity of messages written to the log file
+    ///
+    /// Can be changed from the tray menu while the application is running.
+    #[serde(default)]
+    pub log_level: LogLevel,
+    /// Log file format (plain text or JSON lines)
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// What happens to HDR when the user exits `EasyHDR`
+    #[serde(default)]
+    pub shutdown_policy: ShutdownPolicy,
+    /// Longest continuous time in minutes HDR stays on before `EasyHDR` turns it off (0 for
+    /// no limit)
+    ///
+    /// Protects OLED displays from a bright HDR menu left on overnight. A notification
+    /// shortly before the limit offers to extend the session.
+    #[serde(default)]
+    pub max_hdr_session_minutes: u32,
+    /// Minutes `EasyHDR` leaves HDR alone after it was turned on or off outside `EasyHDR`
+    /// against the automatic decision (0 to always follow the monitored applications)
+    ///
+    /// The hold also ends as soon as a monitored application that was not running at
+    /// the time of the change starts.
+    #[serde(default = "default_external_change_hold_minutes")]
+    pub external_change_hold_minutes: u32,
+    /// Whether monitored applications turn HDR on while running on battery power
+    ///
+    /// HDR held off this way is turned on once AC power returns while a monitored
+    /// application still runs.
+    #[serde(default)]
+    pub battery_hdr_policy: BatteryHdrPolicy,
+    /// Battery charge in percent below which
+    /// [`BatteryHdrPolicy::SkipBelowThreshold`] keeps HDR off
+    #[serde(default = "default_battery_hdr_threshold_percent")]
+    pub battery_hdr_threshold_percent: u8,
+    /// Whether Night Light is turned off while a monitored application has HDR on
+    ///
+    /// Night Light is turned back on when HDR turns off, unless it was already off.
+    #[serde(default)]
+    pub suspend_night_light: bool,
+    /// Largest total size of the icon cache in MiB, 0 for no limit
+    ///
+    /// Only settable in the config file. The icons used least recently are deleted first
+    /// and extracted again when needed.
+    #[serde(default = "default_icon_cache_max_mb")]
+    pub icon_cache_max_mb: u32,
+    /// Whether to start on Windows builds older than the minimum supported build
+    ///
+    /// Only settable in the config file. Display capabilities are probed at startup, but
+    /// features may still fail on such builds.
+    #[serde(default)]
+    pub allow_unsupported_windows: bool,
+    /// Whether HDR toggles are only logged and notified instead of made
+    ///
+    /// Only settable in the config file, or for one run with `--dry-run`. Useful for
+    /// trying a new configuration without the screen flickering on every toggle.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Whether HDR toggles, failed toggles and stopped components are written to the
+    /// Windows Event Log under the `EasyHDR` source
+    #[serde(default)]
+    pub write_event_log: bool,
+    /// Whether local toggle metrics are collected and written to `metrics.json`
+    #[serde(default)]
+    pub metrics_enabled: bool,
+    /// Whether the raw packets of Display Configuration API calls are written to
+    /// `hdr_api_trace.log`
+    ///
+    /// A diagnostic for maintainers, only settable in the config file or for one run
+    /// with `--trace-hdr-api`.
+    #[serde(default)]
+    pub trace_hdr_api: bool,
+    /// Whether Discord shows the monitored application running in HDR
+    #[serde(default)]
+    pub discord_presence: bool,
+    /// Client ID of the Discord application the presence is shown for
+    #[serde(default)]
+    pub discord_client_id: String,
+    /// Whether monitoring continues in a background agent after the window is closed
+    ///
+    /// Auto-start then launches the agent instead of the window.
+    #[serde(default)]
+    pub run_in_background: bool,
+    /// Whether first-run setup was finished or skipped
+    ///
+    /// Configs written before first-run setup existed count as set up.
+    #[serde(default = "default_onboarding_completed")]
+    pub onboarding_completed: bool,
+    /// Language of dialogs, notifications and the tray menu as a tag such as `de` or
+    /// `pt-BR`, empty to follow the Windows user locale
+    ///
+    /// Only settable in the config file. Takes effect on the next start.
+    #[serde(default)]
+    pub language: String,
+}
+
+/// Default delay before HDR is turned off after the last monitored application exits
+pub const DEFAULT_HDR_DISABLE_DEBOUNCE_MS: u64 = 500;
+
+/// Longest accepted delay before HDR is turned off after the last monitored application exits
+pub const MAX_HDR_DISABLE_DEBOUNCE_MS: u64 = 10_000;
+
+/// Default minutes an HDR change made outside `EasyHDR` is respected
+pub const DEFAULT_EXTERNAL_CHANGE_HOLD_MINUTES: u32 = 30;
+
+/// Default battery charge in percent below which HDR can be kept off
+pub const DEFAULT_BATTERY_HDR_THRESHOLD_PERCENT: u8 = 20;
+
+/// Default size limit of the icon cache in MiB
+pub const DEFAULT_ICON_CACHE_MAX_MB: u32 = 16;
+
+/// Default longest interval adaptive polling stretches to while idle
+pub const DEFAULT_MAX_IDLE_POLLING_INTERVAL_MS: u64 = 5000;
+
+/// Longest accepted interval adaptive polling stretches to while idle
+pub const MAX_IDLE_POLLING_INTERVAL_MS: u64 = 10_000;
+
+impl UserPreferences {
+    /// Delay before HDR is turned off after the last monitored application exits
+    ///
+    /// Values above [`MAX_HDR_DISABLE_DEBOUNCE_MS`], e.g. from a hand-edited config, are
+    /// clamped.
+    pub fn hdr_disable_debounce(&self) -> Duration {
+        hdr_disable_debounce(self.hdr_disable_debounce_ms)
+    }
+
+    /// Longest continuous time HDR stays on, `None` for no limit
+    pub fn max_hdr_session(&self) -> Option<Duration> {
+        max_hdr_session(self.max_hdr_session_minutes)
+    }
+
+    /// Longest interval adaptive polling stretches to, `None` without adaptive polling
+    ///
+    /// Never shorter than the monitoring interval or longer than
+    /// [`MAX_IDLE_POLLING_INTERVAL_MS`].
+    pub fn max_idle_polling_interval(&self) -> Option<Duration> {
+        self.adaptive_polling.then(|| {
+            Duration::from_millis(
+                self.max_idle_polling_interval_ms
+                    .min(MAX_IDLE_POLLING_INTERVAL_MS)
+                    .max(self.monitoring_interval_ms),
+            )
+        })
+    }
+
+    /// How long an HDR change made outside `EasyHDR` is respected, `None` if it is not
+    pub fn external_change_hold(&self) -> Option<Duration> {
+        (self.external_change_hold_minutes > 0)
+            .then(|| Duration::from_secs(u64::from(self.external_change_hold_minutes) * 60))
+    }
+}
+
+/// Convert an HDR session limit in minutes to a `Duration`, `None` for no limit
+pub fn max_hdr_session(minutes: u32) -> Option<Duration> {
+    (minutes > 0).then(|| Duration::from_secs(u64::from(minutes) * 60))
+}
+
+/// Convert a debounce delay in milliseconds to a `Duration`, clamped to the accepted range
+pub fn hdr_disable_debounce(ms: u64) -> Duration {
+    Duration::from_millis(ms.min(MAX_HDR_DISABLE_DEBOUNCE_MS))
 }
 
 /// Default value for `show_update_notifications` field (true for backwards compatibility)
@@ -632,6 +1687,110 @@ fn default_show_update_notifications() -> bool {
     true
 }
 
+/// Default value for `external_change_hold_minutes` field
+fn default_external_change_hold_minutes() -> u32 {
+    DEFAULT_EXTERNAL_CHANGE_HOLD_MINUTES
+}
+
+/// Default value for `battery_hdr_threshold_percent` field
+fn default_battery_hdr_threshold_percent() -> u8 {
+    DEFAULT_BATTERY_HDR_THRESHOLD_PERCENT
+}
+
+/// Default value for `icon_cache_max_mb` field
+fn default_icon_cache_max_mb() -> u32 {
+    DEFAULT_ICON_CACHE_MAX_MB
+}
+
+/// Default value for `max_idle_polling_interval_ms` field
+fn default_max_idle_polling_interval_ms() -> u64 {
+    DEFAULT_MAX_IDLE_POLLING_INTERVAL_MS
+}
+
+/// Default value for `onboarding_completed` field (existing configs skip first-run setup)
+fn default_onboarding_completed() -> bool {
+    true
+}
+
+/// Default value for `hdr_disable_debounce_ms` field
+fn default_hdr_disable_debounce_ms() -> u64 {
+    DEFAULT_HDR_DISABLE_DEBOUNCE_MS
+}
+
+/// Display seen by `EasyHDR`
+///
+/// Persisted so per-display configuration can refer to a monitor by its stable
+/// [`DisplayIdentity`] and be resolved back to a live `DisplayTarget` after reboots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KnownDisplay {
+    /// Stable identity of the monitor
+    pub identity: DisplayIdentity,
+    /// Name the monitor was last seen with
+    pub name: String,
+    /// OLED care settings, `None` unless the display is tagged as OLED
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oled_care: Option<OledCare>,
+    /// Color profile made the default HDR profile each time `EasyHDR` turns HDR on
+    ///
+    /// File name of a profile in the Windows color store, such as the one the Windows
+    /// HDR Calibration app saves. `None` leaves the Windows default alone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hdr_color_profile: Option<String>,
+}
+
+/// Named group of displays HDR is turned on for together
+///
+/// Applications bound to a group by name turn HDR on for the group's displays only,
+/// for example a media player that should light up the TV but leave the desk monitors
+/// alone. Displays are listed by stable identity, as in `known_displays`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DisplayGroup {
+    /// Name applications refer to the group by
+    pub name: String,
+    /// Stable identities of the displays in the group
+    #[serde(default)]
+    pub displays: Vec<DisplayIdentity>,
+}
+
+/// Default HDR session length after which OLED panel maintenance is due (4 hours)
+pub const DEFAULT_OLED_CARE_AFTER_MINUTES: u32 = 240;
+
+/// Panel maintenance for a display tagged as OLED
+///
+/// After an HDR session of at least `after_minutes`, `EasyHDR` reminds the user to run the
+/// panel's pixel refresh, offering to start it over DDC/CI when the display has a
+/// command configured.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OledCare {
+    /// HDR session length in minutes after which maintenance is due
+    #[serde(default = "default_oled_care_after_minutes")]
+    pub after_minutes: u32,
+    /// Vendor DDC/CI command that starts the pixel refresh, if the panel has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pixel_refresh_command: Option<VcpCommand>,
+}
+
+impl Default for OledCare {
+    fn default() -> Self {
+        Self {
+            after_minutes: DEFAULT_OLED_CARE_AFTER_MINUTES,
+            pixel_refresh_command: None,
+        }
+    }
+}
+
+impl OledCare {
+    /// HDR session length after which maintenance is due
+    pub fn due_after(&self) -> Duration {
+        Duration::from_secs(u64::from(self.after_minutes.max(1)) * 60)
+    }
+}
+
+/// Default value for `OledCare::after_minutes`
+fn default_oled_care_after_minutes() -> u32 {
+    DEFAULT_OLED_CARE_AFTER_MINUTES
+}
+
 /// Window state for position and size persistence
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WindowState {
@@ -649,15 +1808,47 @@ impl Default for UserPreferences {
     fn default() -> Self {
         Self {
             auto_start: false,
+            autostart_method: AutoStartMethod::Registry,
+            autostart_elevated: false,
+            autostart_delay_secs: 0,
             monitoring_interval_ms: 1000,
             show_tray_notifications: true,
             show_update_notifications: true,
             auto_open_release_page: false,
+            update_channel: UpdateChannel::Stable,
             minimize_to_tray_on_minimize: true,
             minimize_to_tray_on_close: false,
             start_minimized_to_tray: false,
             last_update_check_time: 0,
             cached_latest_version: String::new(),
+            update_check_cache: UpdateCheckCache::default(),
+            uwp_enumerate_all_users: false,
+            track_child_processes: false,
+            wait_for_window: false,
+            hdr_disable_debounce_ms: DEFAULT_HDR_DISABLE_DEBOUNCE_MS,
+            weekly_summary_notification: false,
+            last_weekly_summary_time: 0,
+            log_level: LogLevel::default(),
+            log_format: LogFormat::default(),
+            shutdown_policy: ShutdownPolicy::default(),
+            max_hdr_session_minutes: 0,
+            external_change_hold_minutes: DEFAULT_EXTERNAL_CHANGE_HOLD_MINUTES,
+            battery_hdr_policy: BatteryHdrPolicy::Allow,
+            battery_hdr_threshold_percent: DEFAULT_BATTERY_HDR_THRESHOLD_PERCENT,
+            icon_cache_max_mb: DEFAULT_ICON_CACHE_MAX_MB,
+            adaptive_polling: false,
+            max_idle_polling_interval_ms: DEFAULT_MAX_IDLE_POLLING_INTERVAL_MS,
+            suspend_night_light: false,
+            allow_unsupported_windows: false,
+            dry_run: false,
+            write_event_log: false,
+            metrics_enabled: false,
+            trace_hdr_api: false,
+            discord_presence: false,
+            discord_client_id: String::new(),
+            run_in_background: false,
+            onboarding_completed: false,
+            language: String::new(),
         }
     }
 }
@@ -705,6 +1896,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\Program Files\\Test\\test.exe"),
             process_name: "test".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: Some(vec![1, 2, 3, 4]), // Should be skipped in serialization
         };
 
@@ -729,89 +1930,496 @@ mod tests {
     }
 
     #[test]
-    fn test_uwp_app_serialization_round_trip() {
-        // Create a UwpApp with all fields populated
-        let app = UwpApp {
-            id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
-            display_name: "Calculator".to_string(),
-            package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
-            app_id: "App".to_string(),
-            enabled: true,
-            icon_data: Some(vec![1, 2, 3, 4]), // Should be skipped in serialization
+    fn test_unicode_app_names_serialization_round_trip() {
+        let win32 = MonitoredApp::Win32(Win32App {
+            id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+            display_name: "原神 ゲーム \u{1F3AE}".to_string(),
+            exe_path: PathBuf::from("C:\\Games\\原神\\原神.exe"),
+            process_name: "原神".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        });
+        let uwp = MonitoredApp::Uwp(UwpApp {
+            id: Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap(),
+            display_name: "검은사막".to_string(),
+            package_family_name: "Pearl.BlackDesert_8wekyb3d8bbwe".to_string(),
+            app_id: "App".to_string(),
+            enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        });
+
+        for app in [win32, uwp] {
+            let json = serde_json::to_string_pretty(&app).unwrap();
+            let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(deserialized.display_name(), app.display_name());
+            assert_eq!(serde_json::to_string_pretty(&deserialized).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_uwp_app_serialization_round_trip() {
+        // Create a UwpApp with all fields populated
+        let app = UwpApp {
+            id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+            display_name: "Calculator".to_string(),
+            package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
+            app_id: "App".to_string(),
+            enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: Some(vec![1, 2, 3, 4]), // Should be skipped in serialization
+        };
+
+        // Serialize to JSON
+        let json = serde_json::to_string(&app).unwrap();
+
+        // Verify icon_data is not in JSON (due to #[serde(skip)])
+        assert!(!json.contains("icon_data"));
+
+        // Deserialize back
+        let deserialized: UwpApp = serde_json::from_str(&json).unwrap();
+
+        // Verify all fields except icon_data
+        assert_eq!(app.id, deserialized.id);
+        assert_eq!(app.display_name, deserialized.display_name);
+        assert_eq!(app.package_family_name, deserialized.package_family_name);
+        assert_eq!(app.app_id, deserialized.app_id);
+        assert_eq!(app.enabled, deserialized.enabled);
+
+        // icon_data should be None after deserialization
+        assert!(deserialized.icon_data.is_none());
+    }
+
+    #[test]
+    fn test_monitored_app_win32_serialization() {
+        // Create a Win32 MonitoredApp
+        let app = MonitoredApp::Win32(Win32App {
+            id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+            display_name: "Test Application".to_string(),
+            exe_path: PathBuf::from("C:\\Program Files\\Test\\test.exe"),
+            process_name: "test".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        });
+
+        // Serialize to JSON
+        let json = serde_json::to_string(&app).unwrap();
+
+        // Verify app_type field is present
+        assert!(json.contains("\"app_type\":\"win32\""));
+
+        // Deserialize back
+        let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+
+        // Verify it's a Win32 variant
+        assert!(matches!(deserialized, MonitoredApp::Win32(_)));
+        assert_eq!(app.id(), deserialized.id());
+        assert_eq!(app.display_name(), deserialized.display_name());
+    }
+
+    #[test]
+    fn test_monitored_app_uwp_serialization() {
+        // Create a UWP MonitoredApp
+        let app = MonitoredApp::Uwp(UwpApp {
+            id: Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap(),
+            display_name: "Calculator".to_string(),
+            package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
+            app_id: "App".to_string(),
+            enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        });
+
+        // Serialize to JSON
+        let json = serde_json::to_string(&app).unwrap();
+
+        // Verify app_type field is present
+        assert!(json.contains("\"app_type\":\"uwp\""));
+
+        // Deserialize back
+        let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+
+        // Verify it's a UWP variant
+        assert!(matches!(deserialized, MonitoredApp::Uwp(_)));
+        assert_eq!(app.id(), deserialized.id());
+        assert_eq!(app.display_name(), deserialized.display_name());
+    }
+
+    #[test]
+    fn test_uwp_app_match_app_id_round_trip_and_default() {
+        let app = MonitoredApp::Uwp(UwpApp {
+            id: Uuid::new_v4(),
+            display_name: "Xbox Game Bar".to_string(),
+            package_family_name: "Microsoft.XboxGamingOverlay_8wekyb3d8bbwe".to_string(),
+            app_id: "GameBar".to_string(),
+            enabled: true,
+            match_app_id: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        });
+
+        let json = serde_json::to_string(&app).unwrap();
+        let MonitoredApp::Uwp(deserialized) = serde_json::from_str(&json).unwrap() else {
+            panic!("Expected UWP variant");
+        };
+        assert!(deserialized.match_app_id);
+        assert_eq!(
+            deserialized.aumid(),
+            "Microsoft.XboxGamingOverlay_8wekyb3d8bbwe!GameBar"
+        );
+
+        // Entries saved before AUMID support match at the package level
+        let old_json = r#"{
+            "app_type": "uwp",
+            "id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "display_name": "Calculator",
+            "package_family_name": "Microsoft.WindowsCalculator_8wekyb3d8bbwe",
+            "app_id": "App",
+            "enabled": true
+        }"#;
+        let MonitoredApp::Uwp(old) = serde_json::from_str(old_json).unwrap() else {
+            panic!("Expected UWP variant");
+        };
+        assert!(!old.match_app_id);
+    }
+
+    #[test]
+    fn test_set_excluded_processes() {
+        let mut config = AppConfig::default();
+        config.set_excluded_processes("OBS64.exe, ShareX;\n  obs64 ,, ");
+        assert_eq!(config.excluded_processes, vec!["obs64", "sharex"]);
+
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.excluded_processes, config.excluded_processes);
+
+        // Configs written before the exclusion list existed load with an empty one
+        let old: AppConfig = serde_json::from_str(r#"{"monitored_apps": []}"#).unwrap();
+        assert!(old.excluded_processes.is_empty());
+
+        config.set_excluded_processes("");
+        assert!(config.excluded_processes.is_empty());
+    }
+
+    #[test]
+    fn test_remember_displays() {
+        let identity = DisplayIdentity::new("DEL", 0x4141, b"7MT0123ABCD");
+        let mut display = DisplayTarget {
+            target_id: 1,
+            friendly_name: Some("DELL U3223QE".to_string()),
+            identity: Some(identity.clone()),
+            ..Default::default()
+        };
+        let anonymous = DisplayTarget {
+            target_id: 2,
+            ..Default::default()
+        };
+
+        let mut config = AppConfig::default();
+        assert!(config.remember_displays(&[display.clone(), anonymous]));
+        assert_eq!(config.known_displays.len(), 1);
+        assert!(!config.remember_displays(std::slice::from_ref(&display)));
+
+        // Same monitor after a reboot: new target ID, same identity
+        display.target_id = 7;
+        display.friendly_name = Some("Dell Monitor".to_string());
+        assert!(config.remember_displays(std::slice::from_ref(&display)));
+        assert_eq!(config.known_displays.len(), 1);
+        assert_eq!(
+            config.known_display(&identity).unwrap().name,
+            "Dell Monitor"
+        );
+
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.known_displays, config.known_displays);
+
+        // Configs written before display identities existed
+        let legacy: AppConfig = serde_json::from_str(r#"{"monitored_apps": []}"#).unwrap();
+        assert!(legacy.known_displays.is_empty());
+    }
+
+    #[test]
+    fn test_oled_care_is_stored_per_known_display() {
+        let identity = DisplayIdentity::new("LGD", 0x0001, b"OLED-1");
+        let display = DisplayTarget {
+            target_id: 1,
+            identity: Some(identity.clone()),
+            ..Default::default()
         };
 
-        // Serialize to JSON
-        let json = serde_json::to_string(&app).unwrap();
-
-        // Verify icon_data is not in JSON (due to #[serde(skip)])
-        assert!(!json.contains("icon_data"));
+        let mut config = AppConfig::default();
+        assert!(!config.set_oled_care(&identity, Some(OledCare::default())));
+        config.remember_displays(std::slice::from_ref(&display));
+        assert_eq!(config.oled_care(&display), None);
 
-        // Deserialize back
-        let deserialized: UwpApp = serde_json::from_str(&json).unwrap();
+        let care = OledCare {
+            after_minutes: 120,
+            pixel_refresh_command: VcpCommand::parse("E1=01"),
+        };
+        assert!(config.set_oled_care(&identity, Some(care.clone())));
+        assert_eq!(config.oled_care(&display), Some(&care));
+        assert_eq!(care.due_after(), Duration::from_secs(2 * 3600));
 
-        // Verify all fields except icon_data
-        assert_eq!(app.id, deserialized.id);
-        assert_eq!(app.display_name, deserialized.display_name);
-        assert_eq!(app.package_family_name, deserialized.package_family_name);
-        assert_eq!(app.app_id, deserialized.app_id);
-        assert_eq!(app.enabled, deserialized.enabled);
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.oled_care(&display), Some(&care));
 
-        // icon_data should be None after deserialization
-        assert!(deserialized.icon_data.is_none());
+        // Tagging without settings uses the default session length
+        let partial: OledCare = serde_json::from_str("{}").unwrap();
+        assert_eq!(partial, OledCare::default());
     }
 
     #[test]
-    fn test_monitored_app_win32_serialization() {
-        // Create a Win32 MonitoredApp
+    fn test_app_source_round_trip_and_default() {
         let app = MonitoredApp::Win32(Win32App {
-            id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
-            display_name: "Test Application".to_string(),
-            exe_path: PathBuf::from("C:\\Program Files\\Test\\test.exe"),
-            process_name: "test".to_string(),
+            id: Uuid::new_v4(),
+            display_name: "Cyberpunk 2077".to_string(),
+            exe_path: PathBuf::from("C:\\Games\\Cyberpunk2077.exe"),
+            process_name: "cyberpunk2077".to_string(),
             enabled: true,
+            source: AppSource::Imported,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         });
 
-        // Serialize to JSON
         let json = serde_json::to_string(&app).unwrap();
-
-        // Verify app_type field is present
-        assert!(json.contains("\"app_type\":\"win32\""));
-
-        // Deserialize back
+        assert!(json.contains(r#""source":"imported""#));
         let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.source(), AppSource::Imported);
 
-        // Verify it's a Win32 variant
-        assert!(matches!(deserialized, MonitoredApp::Win32(_)));
-        assert_eq!(app.id(), deserialized.id());
-        assert_eq!(app.display_name(), deserialized.display_name());
+        // Entries saved before sources were tracked were added manually
+        let old_json = r#"{
+            "app_type": "win32",
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "display_name": "Game",
+            "exe_path": "C:\\Games\\game.exe",
+            "process_name": "game",
+            "enabled": true
+        }"#;
+        let old: MonitoredApp = serde_json::from_str(old_json).unwrap();
+        assert_eq!(old.source(), AppSource::Manual);
     }
 
     #[test]
-    fn test_monitored_app_uwp_serialization() {
-        // Create a UWP MonitoredApp
-        let app = MonitoredApp::Uwp(UwpApp {
-            id: Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap(),
-            display_name: "Calculator".to_string(),
-            package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
-            app_id: "App".to_string(),
+    fn test_display_mode_round_trip_and_default() {
+        use crate::hdr::Resolution;
+
+        let mut app = MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Elden Ring".to_string(),
+            exe_path: PathBuf::from("C:\\Games\\eldenring.exe"),
+            process_name: "eldenring".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         });
 
-        // Serialize to JSON
+        // Unset display modes are left out of the config entry
+        let json = serde_json::to_string(&app).unwrap();
+        assert!(!json.contains("display_mode"));
+        let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.display_mode(), None);
+
+        let display_mode = DisplayModeOverride {
+            refresh_rate_hz: Some(120),
+            resolution: Some(Resolution {
+                width: 2560,
+                height: 1440,
+            }),
+        };
+        app.set_display_mode(Some(display_mode));
         let json = serde_json::to_string(&app).unwrap();
+        let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.display_mode(), Some(display_mode));
+    }
 
-        // Verify app_type field is present
-        assert!(json.contains("\"app_type\":\"uwp\""));
+    #[test]
+    fn test_color_mode_round_trip_and_default() {
+        let mut app = MonitoredApp::Uwp(UwpApp::from_package_info(
+            "Photos".to_string(),
+            "Microsoft.Windows.Photos_8wekyb3d8bbwe".to_string(),
+            "App".to_string(),
+            None,
+        ));
 
-        // Deserialize back
+        let json = serde_json::to_string(&app).unwrap();
+        assert!(!json.contains("color_mode"));
         let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.color_mode(), None);
 
-        // Verify it's a UWP variant
-        assert!(matches!(deserialized, MonitoredApp::Uwp(_)));
-        assert_eq!(app.id(), deserialized.id());
-        assert_eq!(app.display_name(), deserialized.display_name());
+        app.set_color_mode(Some(ColorMode::Wcg));
+        let json = serde_json::to_string(&app).unwrap();
+        assert!(json.contains(r#""color_mode":"wcg""#));
+        let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.color_mode(), Some(ColorMode::Wcg));
+    }
+
+    #[test]
+    fn test_capture_tone_mapping_round_trip_and_default() {
+        let mut app = MonitoredApp::Uwp(UwpApp::from_package_info(
+            "Forza Horizon 5".to_string(),
+            "Microsoft.624F8B84B80_8wekyb3d8bbwe".to_string(),
+            "ForzaHorizon5".to_string(),
+            None,
+        ));
+
+        let json = serde_json::to_string(&app).unwrap();
+        assert!(!json.contains("capture_tone_mapping"));
+
+        app.set_capture_tone_mapping(Some(false));
+        let json = serde_json::to_string(&app).unwrap();
+        assert!(json.contains(r#""capture_tone_mapping":false"#));
+        let deserialized: MonitoredApp = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.capture_tone_mapping(), Some(false));
+    }
+
+    #[test]
+    fn test_display_groups_round_trip_and_default() {
+        let config: AppConfig = serde_json::from_str("{}").unwrap();
+        assert!(config.display_groups.is_empty());
+
+        let mut config = AppConfig::default();
+        config.display_groups.push(DisplayGroup {
+            name: "TV".to_string(),
+            displays: vec![DisplayIdentity::new("SAM", 0x7325, b"TV-1")],
+        });
+        let mut app = MonitoredApp::Uwp(UwpApp::from_package_info(
+            "Netflix".to_string(),
+            "4DF9E0F8.Netflix_mcm4njqhnhss8".to_string(),
+            "Netflix.App".to_string(),
+            None,
+        ));
+        assert!(
+            !serde_json::to_string(&app)
+                .unwrap()
+                .contains("display_group")
+        );
+        app.set_display_group(Some("TV".to_string()));
+        config.monitored_apps.push(app);
+
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.display_groups, config.display_groups);
+        assert_eq!(deserialized.monitored_apps[0].display_group(), Some("TV"));
+        assert_eq!(
+            deserialized
+                .display_group("tv")
+                .map(|group| group.displays.len()),
+            Some(1)
+        );
+        assert!(deserialized.display_group("Desk").is_none());
+    }
+
+    #[test]
+    fn test_auto_hdr_round_trip_and_default() {
+        let app = MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Elden Ring".to_string(),
+            exe_path: PathBuf::from("C:\\Games\\eldenring.exe"),
+            process_name: "eldenring".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Off,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        });
+
+        let json = serde_json::to_string(&app).unwrap();
+        assert!(json.contains(r#""auto_hdr":"off""#));
+        let MonitoredApp::Win32(deserialized) =
+            serde_json::from_str::<MonitoredApp>(&json).unwrap()
+        else {
+            panic!("Expected Win32 app");
+        };
+        assert_eq!(deserialized.auto_hdr, AutoHdrMode::Off);
+
+        // Entries saved before Auto HDR overrides existed leave the Windows setting alone
+        let old_json = r#"{
+            "app_type": "win32",
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "display_name": "Game",
+            "exe_path": "C:\\Games\\game.exe",
+            "process_name": "game",
+            "enabled": true
+        }"#;
+        let MonitoredApp::Win32(old) = serde_json::from_str::<MonitoredApp>(old_json).unwrap()
+        else {
+            panic!("Expected Win32 app");
+        };
+        assert_eq!(old.auto_hdr, AutoHdrMode::Default);
     }
 
     #[test]
@@ -825,8 +2433,16 @@ mod tests {
             "enabled": true
         }"#;
 
-        // Deserialize legacy format
-        let deserialized: MonitoredApp = serde_json::from_str(legacy_json).unwrap();
+        // Untagged entries are only read after the configuration migration tagged them
+        assert!(serde_json::from_str::<MonitoredApp>(legacy_json).is_err());
+        let mut config = serde_json::json!({
+            "monitored_apps": [serde_json::from_str::<serde_json::Value>(legacy_json).unwrap()]
+        });
+        crate::config::migration::migrate(&mut config).unwrap();
+        let deserialized = serde_json::from_value::<AppConfig>(config)
+            .unwrap()
+            .monitored_apps
+            .remove(0);
 
         // Should be migrated to Win32 variant
         assert!(matches!(deserialized, MonitoredApp::Win32(_)));
@@ -856,6 +2472,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\test.exe"),
             process_name: "test".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: Some(vec![1, 2, 3]),
         });
 
@@ -873,6 +2499,14 @@ mod tests {
             package_family_name: "Package_Publisher".to_string(),
             app_id: "App".to_string(),
             enabled: false,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: None,
         });
 
@@ -884,6 +2518,52 @@ mod tests {
         assert!(!uwp_app.is_enabled());
     }
 
+    #[test]
+    fn test_ensure_fallback_icon_only_fills_missing_icons() {
+        let mut with_icon = MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Win32 App".to_string(),
+            exe_path: PathBuf::from("C:\\test.exe"),
+            process_name: "test".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: Some(vec![1, 2, 3]),
+        });
+        assert!(!with_icon.ensure_fallback_icon());
+        assert_eq!(with_icon.icon_data().as_deref(), Some(&[1, 2, 3][..]));
+
+        let mut without_icon = MonitoredApp::Uwp(UwpApp {
+            id: Uuid::new_v4(),
+            display_name: "UWP App".to_string(),
+            package_family_name: "Package_Publisher".to_string(),
+            app_id: "App".to_string(),
+            enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        });
+        assert!(without_icon.ensure_fallback_icon());
+        assert_eq!(
+            without_icon.icon_data().as_deref(),
+            Some(crate::utils::generate_letter_tile("UWP App").as_slice())
+        );
+    }
+
     #[test]
     fn test_app_config_serialization_round_trip() {
         // Create a full AppConfig with mixed Win32 and UWP apps
@@ -894,6 +2574,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\Games\\Cyberpunk 2077\\bin\\x64\\Cyberpunk2077.exe"),
             process_name: "cyberpunk2077".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
@@ -902,6 +2592,16 @@ mod tests {
             exe_path: PathBuf::from("D:\\Games\\RDR2\\RDR2.exe"),
             process_name: "rdr2".to_string(),
             enabled: false,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.monitored_apps.push(MonitoredApp::Uwp(UwpApp {
@@ -910,6 +2610,14 @@ mod tests {
             package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
             app_id: "App".to_string(),
             enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: None,
         }));
         config.preferences.auto_start = true;
@@ -993,15 +2701,47 @@ mod tests {
     fn test_user_preferences_serialization_round_trip() {
         let prefs = UserPreferences {
             auto_start: true,
+            autostart_method: AutoStartMethod::Registry,
+            autostart_elevated: false,
+            autostart_delay_secs: 0,
             monitoring_interval_ms: 2000,
             show_tray_notifications: false,
             show_update_notifications: true,
             auto_open_release_page: false,
+            update_channel: UpdateChannel::Stable,
             minimize_to_tray_on_minimize: true,
             minimize_to_tray_on_close: false,
             start_minimized_to_tray: true,
             last_update_check_time: 1_234_567_890,
             cached_latest_version: "1.2.3".to_string(),
+            update_check_cache: UpdateCheckCache::default(),
+            uwp_enumerate_all_users: false,
+            track_child_processes: true,
+            wait_for_window: false,
+            hdr_disable_debounce_ms: 500,
+            weekly_summary_notification: false,
+            last_weekly_summary_time: 0,
+            log_level: LogLevel::Debug,
+            log_format: LogFormat::Json,
+            shutdown_policy: ShutdownPolicy::ForceOff,
+            max_hdr_session_minutes: 0,
+            external_change_hold_minutes: 0,
+            battery_hdr_policy: BatteryHdrPolicy::Allow,
+            battery_hdr_threshold_percent: 20,
+            icon_cache_max_mb: 16,
+            adaptive_polling: false,
+            max_idle_polling_interval_ms: 5000,
+            suspend_night_light: false,
+            onboarding_completed: true,
+            language: String::new(),
+            allow_unsupported_windows: false,
+            dry_run: false,
+            write_event_log: false,
+            metrics_enabled: false,
+            trace_hdr_api: false,
+            discord_presence: false,
+            discord_client_id: String::new(),
+            run_in_background: false,
         };
 
         let json = serde_json::to_string(&prefs).unwrap();
@@ -1020,6 +2760,82 @@ mod tests {
             prefs.start_minimized_to_tray,
             deserialized.start_minimized_to_tray
         );
+        assert_eq!(deserialized.log_level, LogLevel::Debug);
+        assert_eq!(deserialized.log_format, LogFormat::Json);
+        assert_eq!(deserialized.shutdown_policy, ShutdownPolicy::ForceOff);
+        assert!(json.contains(r#""shutdown_policy":"force_off""#));
+    }
+
+    #[test]
+    fn test_shutdown_policy_names() {
+        for policy in [
+            ShutdownPolicy::LeaveAsIs,
+            ShutdownPolicy::RestorePrevious,
+            ShutdownPolicy::ForceOff,
+        ] {
+            assert_eq!(ShutdownPolicy::parse(policy.as_str()), Some(policy));
+        }
+        assert_eq!(ShutdownPolicy::parse("off"), None);
+    }
+
+    #[test]
+    fn test_autostart_method_names_and_default() {
+        for method in [AutoStartMethod::Registry, AutoStartMethod::TaskScheduler] {
+            assert_eq!(AutoStartMethod::parse(method.as_str()), Some(method));
+        }
+        assert_eq!(AutoStartMethod::parse("run_key"), None);
+
+        let preferences: UserPreferences = serde_json::from_str(
+            &serde_json::to_string(&UserPreferences::default())
+                .unwrap()
+                .replace(r#""autostart_method":"registry","#, ""),
+        )
+        .unwrap();
+        assert_eq!(preferences.autostart_method, AutoStartMethod::Registry);
+    }
+
+    #[test]
+    fn test_update_channel_names_and_default() {
+        for channel in [UpdateChannel::Stable, UpdateChannel::Beta] {
+            assert_eq!(UpdateChannel::parse(channel.as_str()), Some(channel));
+        }
+        assert_eq!(UpdateChannel::parse("nightly"), None);
+
+        let preferences: UserPreferences = serde_json::from_str(
+            &serde_json::to_string(&UserPreferences::default())
+                .unwrap()
+                .replace(r#""update_channel":"stable","#, ""),
+        )
+        .unwrap();
+        assert_eq!(preferences.update_channel, UpdateChannel::Stable);
+    }
+
+    #[test]
+    fn test_battery_hdr_policy() {
+        for policy in [
+            BatteryHdrPolicy::Allow,
+            BatteryHdrPolicy::SkipOnBattery,
+            BatteryHdrPolicy::SkipBelowThreshold,
+        ] {
+            assert_eq!(BatteryHdrPolicy::parse(policy.as_str()), Some(policy));
+        }
+        assert_eq!(BatteryHdrPolicy::parse("never"), None);
+
+        let battery = |percent| PowerStatus {
+            on_battery: true,
+            battery_percent: percent,
+        };
+        let ac = PowerStatus {
+            on_battery: false,
+            battery_percent: Some(5),
+        };
+        assert!(!BatteryHdrPolicy::Allow.skips_hdr(battery(Some(5)), 20));
+        assert!(BatteryHdrPolicy::SkipOnBattery.skips_hdr(battery(Some(90)), 20));
+        assert!(!BatteryHdrPolicy::SkipOnBattery.skips_hdr(ac, 20));
+        assert!(BatteryHdrPolicy::SkipBelowThreshold.skips_hdr(battery(Some(19)), 20));
+        assert!(!BatteryHdrPolicy::SkipBelowThreshold.skips_hdr(battery(Some(20)), 20));
+        assert!(!BatteryHdrPolicy::SkipBelowThreshold.skips_hdr(battery(None), 20));
+        assert!(!BatteryHdrPolicy::SkipBelowThreshold.skips_hdr(ac, 20));
     }
 
     #[test]
@@ -1047,6 +2863,86 @@ mod tests {
         assert!(!prefs.auto_start);
         assert_eq!(prefs.monitoring_interval_ms, 1000);
         assert!(prefs.show_tray_notifications);
+        assert!(!prefs.track_child_processes);
+    }
+
+    #[test]
+    fn test_track_child_processes_defaults_to_false_when_missing() {
+        let json = r#"{
+            "auto_start": false,
+            "monitoring_interval_ms": 1000,
+            "show_tray_notifications": true,
+            "minimize_to_tray_on_minimize": true,
+            "minimize_to_tray_on_close": false
+        }"#;
+
+        let prefs: UserPreferences = serde_json::from_str(json).unwrap();
+        assert!(!prefs.track_child_processes);
+    }
+
+    #[test]
+    fn test_onboarding_skipped_for_existing_configs() {
+        let json = r#"{
+            "auto_start": false,
+            "monitoring_interval_ms": 1000,
+            "show_tray_notifications": true,
+            "minimize_to_tray_on_minimize": true,
+            "minimize_to_tray_on_close": false
+        }"#;
+
+        let prefs: UserPreferences = serde_json::from_str(json).unwrap();
+        assert!(prefs.onboarding_completed);
+        assert!(!UserPreferences::default().onboarding_completed);
+    }
+
+    #[test]
+    fn test_hdr_disable_debounce_default_and_clamp() {
+        let json = r#"{
+            "auto_start": false,
+            "monitoring_interval_ms": 1000,
+            "show_tray_notifications": true,
+            "minimize_to_tray_on_minimize": true,
+            "minimize_to_tray_on_close": false
+        }"#;
+
+        let mut prefs: UserPreferences = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            prefs.hdr_disable_debounce_ms,
+            DEFAULT_HDR_DISABLE_DEBOUNCE_MS
+        );
+        assert_eq!(prefs.hdr_disable_debounce(), Duration::from_millis(500));
+
+        prefs.hdr_disable_debounce_ms = 60_000;
+        assert_eq!(
+            prefs.hdr_disable_debounce(),
+            Duration::from_millis(MAX_HDR_DISABLE_DEBOUNCE_MS)
+        );
+    }
+
+    #[test]
+    fn test_max_idle_polling_interval_default_and_clamp() {
+        let mut prefs = UserPreferences::default();
+        assert!(!prefs.adaptive_polling);
+        assert_eq!(prefs.max_idle_polling_interval(), None);
+
+        prefs.adaptive_polling = true;
+        assert_eq!(
+            prefs.max_idle_polling_interval(),
+            Some(Duration::from_millis(DEFAULT_MAX_IDLE_POLLING_INTERVAL_MS))
+        );
+
+        prefs.max_idle_polling_interval_ms = 60_000;
+        assert_eq!(
+            prefs.max_idle_polling_interval(),
+            Some(Duration::from_millis(MAX_IDLE_POLLING_INTERVAL_MS))
+        );
+
+        // Never shorter than the monitoring interval
+        prefs.max_idle_polling_interval_ms = 100;
+        assert_eq!(
+            prefs.max_idle_polling_interval(),
+            Some(Duration::from_millis(prefs.monitoring_interval_ms))
+        );
     }
 
     #[test]
@@ -1135,6 +3031,32 @@ mod tests {
         assert_ne!(app1.id, app2.id);
     }
 
+    #[test]
+    fn test_from_exe_path_beyond_max_path() {
+        use crate::test_utils::create_test_dir;
+        use crate::utils::long_path::MAX_PATH;
+
+        // Game stores nest installs deeply enough to exceed MAX_PATH
+        let temp_dir = create_test_dir();
+        let mut install_dir = temp_dir.path().to_path_buf();
+        for _ in 0..10 {
+            install_dir.push("Deeply Nested Install Folder");
+        }
+        std::fs::create_dir_all(&install_dir).unwrap();
+        let exe_path = install_dir.join("Game-Win64-Shipping.exe");
+        std::fs::write(&exe_path, b"MZ").unwrap();
+        assert!(exe_path.as_os_str().len() > MAX_PATH);
+
+        let app = Win32App::from_exe_path(exe_path.clone()).unwrap();
+        assert_eq!(app.process_name, "game-win64-shipping");
+        assert_eq!(app.exe_path, exe_path);
+
+        // The long path survives a config round trip unchanged
+        let json = serde_json::to_string(&app).unwrap();
+        let restored: Win32App = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.exe_path, exe_path);
+    }
+
     #[test]
     fn test_uwp_app_from_package_info() {
         // Test UwpApp constructor without logo path
@@ -1518,7 +3440,9 @@ mod tests {
             }
         }"#;
 
-        let config: AppConfig = serde_json::from_str(json).unwrap();
+        let mut config: serde_json::Value = serde_json::from_str(json).unwrap();
+        crate::config::migration::migrate(&mut config).unwrap();
+        let config: AppConfig = serde_json::from_value(config).unwrap();
 
         // Legacy app and UWP app should be loaded (invalid one skipped)
         assert_eq!(config.monitored_apps.len(), 2);
@@ -1527,6 +3451,154 @@ mod tests {
         assert_eq!(config.monitored_apps[1].display_name(), "Calculator");
         assert!(matches!(config.monitored_apps[1], MonitoredApp::Uwp(_)));
     }
+
+    #[test]
+    fn test_folder_app_round_trip() {
+        let app = MonitoredApp::Folder(FolderApp {
+            id: Uuid::new_v4(),
+            display_name: "Games".to_string(),
+            path: PathBuf::from("D:\\Games"),
+            enabled: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: Some(5000),
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: Some(vec![1, 2, 3]),
+        });
+
+        let json = serde_json::to_string(&app).unwrap();
+        assert!(json.contains(r#""app_type":"folder""#));
+        assert!(!json.contains("icon_data"));
+
+        let MonitoredApp::Folder(deserialized) = serde_json::from_str(&json).unwrap() else {
+            panic!("expected a folder entry");
+        };
+        assert_eq!(deserialized.path, PathBuf::from("D:\\Games"));
+        assert_eq!(deserialized.hdr_disable_debounce_ms, Some(5000));
+        assert_eq!(deserialized.icon_data, None);
+    }
+
+    #[test]
+    fn test_folder_app_from_path() {
+        let temp_dir = crate::test_utils::create_test_dir();
+        let library = temp_dir.path().join("Game Library");
+        std::fs::create_dir_all(&library).unwrap();
+
+        let folder_app = FolderApp::from_path(&library).unwrap();
+        assert_eq!(folder_app.display_name, "Game Library");
+        assert_eq!(folder_app.path, library);
+        assert!(folder_app.enabled);
+
+        // Files and missing paths are not folders
+        let file = library.join("game.exe");
+        std::fs::write(&file, b"MZ").unwrap();
+        assert!(FolderApp::from_path(&file).is_err());
+        assert!(FolderApp::from_path(library.join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_match_rule_regex_source() {
+        // Globs ignore case and a trailing .exe, and escape everything but * and ?
+        assert_eq!(
+            MatchRule::Glob("UE4Game*.EXE".to_string()).regex_source(),
+            "^ue4game.*$"
+        );
+        assert_eq!(
+            MatchRule::Glob("game?.v1".to_string()).regex_source(),
+            r"^game.\.v1$"
+        );
+        assert_eq!(
+            MatchRule::Regex("ue4game-(win64|wingdk)-shipping".to_string()).regex_source(),
+            "^(?:ue4game-(win64|wingdk)-shipping)$"
+        );
+    }
+
+    #[test]
+    fn test_match_rule_validate() {
+        assert!(MatchRule::Glob("ue4game*".to_string()).validate().is_ok());
+        assert!(
+            MatchRule::Regex(r"ue4game-\w+".to_string())
+                .validate()
+                .is_ok()
+        );
+
+        assert!(MatchRule::Glob("  ".to_string()).validate().is_err());
+        assert!(MatchRule::Regex(String::new()).validate().is_err());
+        assert!(MatchRule::Regex("ue4game(".to_string()).validate().is_err());
+    }
+
+    #[test]
+    fn test_match_rule_round_trip() {
+        let json = r#"{
+            "app_type": "win32",
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "display_name": "Unreal Game",
+            "exe_path": "C:\\Games\\UE4Game-Win64-Shipping.exe",
+            "process_name": "ue4game-win64-shipping",
+            "enabled": true,
+            "match_rule": { "glob": "ue4game*.exe" }
+        }"#;
+
+        let app: MonitoredApp = serde_json::from_str(json).unwrap();
+        let MonitoredApp::Win32(win32_app) = &app else {
+            panic!("expected a Win32 app");
+        };
+        assert_eq!(
+            win32_app.match_rule,
+            Some(MatchRule::Glob("ue4game*.exe".to_string()))
+        );
+
+        let round_trip: MonitoredApp =
+            serde_json::from_str(&serde_json::to_string(&app).unwrap()).unwrap();
+        assert_eq!(round_trip, app);
+
+        // Entries without a rule leave it out of the file
+        let mut plain = win32_app.clone();
+        plain.match_rule = None;
+        let json = serde_json::to_string(&MonitoredApp::Win32(plain)).unwrap();
+        assert!(!json.contains("match_rule"));
+    }
+
+    #[test]
+    fn test_aliases() {
+        let json = r#"{
+            "app_type": "win32",
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "display_name": "Game",
+            "exe_path": "C:\\Games\\Game.exe",
+            "process_name": "game",
+            "enabled": true,
+            "aliases": ["Game_JP.exe", "D:\\Games\\Regional\\GAME-KR.EXE"]
+        }"#;
+
+        let app: MonitoredApp = serde_json::from_str(json).unwrap();
+        let MonitoredApp::Win32(win32_app) = &app else {
+            panic!("expected a Win32 app");
+        };
+        assert_eq!(
+            win32_app.alias_process_names().collect::<Vec<_>>(),
+            vec!["game_jp", "game-kr"]
+        );
+
+        let round_trip: MonitoredApp =
+            serde_json::from_str(&serde_json::to_string(&app).unwrap()).unwrap();
+        assert_eq!(round_trip, app);
+
+        // Entries without aliases leave them out of the file
+        let mut plain = win32_app.clone();
+        plain.aliases = Win32App::parse_aliases("  ,; ");
+        assert!(plain.aliases.is_empty());
+        let json = serde_json::to_string(&MonitoredApp::Win32(plain)).unwrap();
+        assert!(!json.contains("aliases"));
+
+        assert_eq!(
+            Win32App::parse_aliases("game_jp.exe, GAME_JP.exe;\nD:\\Games\\game-kr.exe"),
+            vec!["game_jp.exe", "D:\\Games\\game-kr.exe"]
+        );
+    }
 }
 
 // Property-based tests using proptest
@@ -1555,6 +3627,16 @@ mod proptests {
                     exe_path,
                     process_name,
                     enabled,
+                    source: AppSource::Manual,
+                    auto_hdr: AutoHdrMode::Default,
+                    display_mode: None,
+                    hdr_disable_debounce_ms: None,
+                    max_hdr_session_minutes: None,
+                    color_mode: None,
+                    capture_tone_mapping: None,
+                    display_group: None,
+                    match_rule: None,
+                    aliases: Vec::new(),
                     icon_data: None,
                 }
             })
@@ -1581,6 +3663,14 @@ mod proptests {
                         package_family_name,
                         app_id,
                         enabled,
+                        match_app_id: false,
+                        source: AppSource::Manual,
+                        display_mode: None,
+                        hdr_disable_debounce_ms: None,
+                        max_hdr_session_minutes: None,
+                        color_mode: None,
+                        capture_tone_mapping: None,
+                        display_group: None,
                         icon_data: None,
                     }
                 },
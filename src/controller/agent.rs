@@ -0,0 +1,1102 @@
+//! Background agent
+//!
+//! With the `run_in_background` preference, HDR automation does not depend on the window:
+//! `EasyHDR` runs a second process with [`AGENT_FLAG`] that keeps the controller, process
+//! monitor and HDR state monitor running without a window or tray icon. Auto-start
+//! launches the agent instead of the window at login.
+//!
+//! The window is then a front-end of the agent rather than a second controller. It starts
+//! an agent if none runs, keeps a controller of its own for editing the configuration and
+//! reading the displays, and forwards everything that changes HDR, such as manual
+//! sessions, overrides, snoozes and per-display toggles, over a control channel (see
+//! [`AgentLink`]). After saving the configuration it asks the agent to re-read it, and it
+//! shows the agent's state updates, which it polls with [`AgentCommand::Updates`]. Closing
+//! the window, or the window crashing, leaves monitoring running. Without the preference
+//! the window runs the controller itself, and starting it while an agent runs asks the
+//! agent to stop, so only one process ever toggles HDR.
+//!
+//! The terminal UI is a client of the agent in the same way: it starts an agent if none
+//! runs, reads its state and sends changes to the watch list and HDR over the same
+//! channel, and leaves the agent running when it exits.
+//!
+//! The agent is a per-user background task rather than a Windows service, because HDR
+//! and the other display settings belong to the interactive session, which services
+//! cannot reach. The control channel is a TCP socket on the loopback interface. The
+//! agent writes its port and a random token to [`ENDPOINT_FILE`] in the data directory,
+//! and requests without the token are rejected; tokens are compared in constant time. A
+//! client sends one JSON request line per connection and reads one JSON response line
+//! back.
+
+use crate::config::{ConfigManager, MonitoredApp, Win32App};
+use crate::controller::{AppController, AppState, DisplayHdrState, HdrOverride, PendingActionId};
+use crate::error::Result;
+use crate::hdr::{ColorMode, DisplayIdentity, DisplayModeOverride, HdrControl};
+use crate::utils::data_dir;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Command-line flag starting `EasyHDR` as a background agent
+pub const AGENT_FLAG: &str = "--agent";
+
+/// File in the data directory naming the port and token of the running agent
+pub const ENDPOINT_FILE: &str = "agent.endpoint";
+
+/// Timeout for connecting to the agent and for every read and write
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest request or response line accepted
+const MAX_LINE_LEN: u64 = 1024 * 1024;
+
+/// State updates kept for a front-end window; older ones are dropped
+const MAX_QUEUED_UPDATES: usize = 32;
+
+/// How often a front-end window asks the agent for state updates
+const UPDATE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Command sent to the agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentCommand {
+    /// Report the HDR state and the running monitored applications
+    Status,
+    /// Stop monitoring and exit, so another `EasyHDR` process can take over
+    Stop,
+    /// Stop monitoring and exit like [`AgentCommand::Stop`], but applying the shutdown
+    /// policy, for a front-end window closed after running in the background was turned
+    /// off
+    Shutdown,
+    /// Report the state shown by the terminal UI
+    State,
+    /// Add the executable at `exe_path` as a desktop application
+    AddApp {
+        /// Path of the executable
+        exe_path: String,
+    },
+    /// Remove an application from the watch list
+    RemoveApp {
+        /// ID of the application
+        id: Uuid,
+    },
+    /// Enable or disable an application
+    SetAppEnabled {
+        /// ID of the application
+        id: Uuid,
+        /// Whether the application is monitored
+        enabled: bool,
+    },
+    /// Turn HDR on with a manual session, or off by ending the session or overriding the
+    /// running applications
+    ToggleHdr,
+    /// Cancel a scheduled or held back action
+    CancelPending {
+        /// Action to cancel
+        id: PendingActionId,
+    },
+    /// Re-read the configuration file after a front-end window saved it
+    ReloadConfig,
+    /// Take the state updates queued since the last call, for a front-end window
+    Updates {
+        /// Whether to drop the queued updates and start from the current state, for a
+        /// window that just connected
+        resend: bool,
+    },
+    /// Start a manual HDR session, see [`AppController::start_manual_session`]
+    StartManualSession {
+        /// Length of the session, `None` until it is stopped
+        duration_secs: Option<u64>,
+        /// Display mode to switch to first
+        display_mode: Option<DisplayModeOverride>,
+    },
+    /// End the manual HDR session
+    StopManualSession,
+    /// Override automatic HDR management from a toggle notification
+    ApplyHdrOverride {
+        /// Requested override
+        hdr_override: HdrOverride,
+    },
+    /// Leave an application to itself for a while, see [`AppController::snooze_app`]
+    SnoozeApp {
+        /// ID of the application
+        id: Uuid,
+    },
+    /// Extend the HDR session about to reach its limit
+    ExtendHdrSession,
+    /// Start the pixel refresh offered after a long HDR session
+    StartPixelRefresh,
+    /// Turn HDR on or off on a single display
+    SetDisplayHdr {
+        /// The display
+        identity: DisplayIdentity,
+        /// Whether HDR is turned on
+        enabled: bool,
+    },
+    /// Switch a display to a color mode
+    SetDisplayColorMode {
+        /// The display
+        identity: DisplayIdentity,
+        /// Color mode to switch to
+        mode: ColorMode,
+    },
+}
+
+/// Monitored application as reported by [`AgentCommand::State`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentApp {
+    /// ID to pass to the commands changing the application
+    pub id: Uuid,
+    /// Name shown to users
+    pub name: String,
+    /// Whether the application is monitored
+    pub enabled: bool,
+}
+
+/// Pending action as reported by [`AgentCommand::State`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentPendingAction {
+    /// ID to pass to [`AgentCommand::CancelPending`]
+    pub id: PendingActionId,
+    /// Description with the time left when the state was read, e.g.
+    /// `HDR turning off · 8 s left`
+    pub description: String,
+}
+
+/// State of the agent's controller, the answer to [`AgentCommand::State`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentState {
+    /// Whether HDR is on
+    pub hdr_enabled: bool,
+    /// Whether HDR toggles are only simulated
+    pub dry_run: bool,
+    /// Description of the running manual HDR session, e.g. `HDR session · 1 h`
+    pub manual_session: Option<String>,
+    /// Displays the last HDR toggle failed on, `None` once a toggle succeeds
+    pub hdr_toggle_error: Option<String>,
+    /// Monitored applications, in the order of the configuration
+    pub apps: Vec<AgentApp>,
+    /// Actions the controller has scheduled or is holding back
+    pub pending_actions: Vec<AgentPendingAction>,
+}
+
+/// Answer of the agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+pub enum AgentResponse {
+    /// Answer to [`AgentCommand::Status`]
+    Status {
+        /// Whether HDR is on
+        hdr_enabled: bool,
+        /// Running monitored applications
+        active_apps: Vec<String>,
+    },
+    /// Answer to [`AgentCommand::Stop`] and [`AgentCommand::Shutdown`], sent before the
+    /// agent exits
+    Stopping,
+    /// Answer to [`AgentCommand::State`]
+    State(AgentState),
+    /// Answer to [`AgentCommand::Updates`]
+    Updates {
+        /// Queued state updates, oldest first
+        states: Vec<AppState>,
+    },
+    /// A command changing the controller succeeded
+    Done {
+        /// What was done, e.g. `Added Game`
+        message: String,
+    },
+    /// The request was rejected
+    Error {
+        /// Reason
+        message: String,
+    },
+}
+
+/// Request line sent to the agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentRequest {
+    /// Token from [`ENDPOINT_FILE`]
+    pub token: String,
+    /// Command to run
+    pub command: AgentCommand,
+}
+
+impl AgentRequest {
+    /// Parse a request line
+    pub fn parse(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line)
+    }
+}
+
+/// Port and token of a running agent, as stored in [`ENDPOINT_FILE`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// Loopback port the agent listens on
+    pub port: u16,
+    /// Token every request must carry
+    pub token: String,
+}
+
+impl Endpoint {
+    /// Parse the `<port> <token>` contents of [`ENDPOINT_FILE`]
+    pub fn parse(contents: &str) -> Option<Self> {
+        let (port, token) = contents.trim().split_once(' ')?;
+        let token = token.trim();
+        if token.is_empty() {
+            return None;
+        }
+        Some(Self {
+            port: port.parse().ok()?,
+            token: token.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.port, self.token)
+    }
+}
+
+/// Location of [`ENDPOINT_FILE`] in the data directory
+pub fn endpoint_path() -> PathBuf {
+    data_dir::get_data_dir().join(ENDPOINT_FILE)
+}
+
+/// Control channel of a running agent
+///
+/// Removes the endpoint file when dropped.
+#[derive(Debug)]
+pub struct AgentServer {
+    listener: TcpListener,
+    token: String,
+    endpoint_path: PathBuf,
+}
+
+impl AgentServer {
+    /// Listen on a free loopback port and publish it in the data directory
+    pub fn bind() -> io::Result<Self> {
+        Self::bind_at(endpoint_path())
+    }
+
+    /// Listen on a free loopback port and publish it in the given endpoint file
+    pub fn bind_at(endpoint_path: PathBuf) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let endpoint = Endpoint {
+            port: listener.local_addr()?.port(),
+            token: Uuid::new_v4().simple().to_string(),
+        };
+        if let Some(parent) = endpoint_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&endpoint_path, endpoint.to_string())?;
+        Ok(Self {
+            listener,
+            token: endpoint.token,
+            endpoint_path,
+        })
+    }
+
+    /// Answer requests until a client sends [`AgentCommand::Stop`] or
+    /// [`AgentCommand::Shutdown`], and return that command
+    ///
+    /// `execute` answers every other command, see [`execute`].
+    pub fn serve(
+        self,
+        execute: impl Fn(AgentCommand) -> AgentResponse,
+    ) -> io::Result<AgentCommand> {
+        use tracing::{debug, info};
+
+        for stream in self.listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    debug!("Failed to accept agent connection: {}", e);
+                    continue;
+                }
+            };
+            match self.handle(&mut stream, &execute) {
+                Ok(Some(command)) => {
+                    info!("Background agent asked to stop");
+                    return Ok(command);
+                }
+                Ok(None) => {}
+                Err(e) => debug!("Failed to answer agent request: {}", e),
+            }
+        }
+        Ok(AgentCommand::Stop)
+    }
+
+    /// Answer one request, returning the command if it asked the agent to stop
+    fn handle(
+        &self,
+        stream: &mut TcpStream,
+        execute: &impl Fn(AgentCommand) -> AgentResponse,
+    ) -> io::Result<Option<AgentCommand>> {
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+        let line = read_line(&*stream)?;
+        let (response, stop) = match AgentRequest::parse(&line) {
+            Ok(request) if tokens_match(&self.token, &request.token) => match request.command {
+                command @ (AgentCommand::Stop | AgentCommand::Shutdown) => {
+                    (AgentResponse::Stopping, Some(command))
+                }
+                command => (execute(command), None),
+            },
+            Ok(_) => (
+                AgentResponse::Error {
+                    message: "Invalid token".to_string(),
+                },
+                None,
+            ),
+            Err(e) => (
+                AgentResponse::Error {
+                    message: format!("Invalid request: {e}"),
+                },
+                None,
+            ),
+        };
+        write_line(stream, &response)?;
+        Ok(stop)
+    }
+}
+
+impl Drop for AgentServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.endpoint_path);
+    }
+}
+
+/// State updates of the agent's controller, queued until a front-end window takes them
+#[derive(Debug, Default)]
+pub struct StateUpdates {
+    queue: Mutex<VecDeque<AppState>>,
+}
+
+impl StateUpdates {
+    /// Queue a state update, dropping the oldest once [`MAX_QUEUED_UPDATES`] are queued
+    pub fn push(&self, state: AppState) {
+        let mut queue = self.queue.lock();
+        if queue.len() == MAX_QUEUED_UPDATES {
+            queue.pop_front();
+        }
+        queue.push_back(state);
+    }
+
+    /// Take the queued state updates, oldest first
+    fn take(&self) -> Vec<AppState> {
+        self.queue.lock().drain(..).collect()
+    }
+}
+
+/// Run a command other than [`AgentCommand::Stop`] and [`AgentCommand::Shutdown`] on the
+/// agent's controller
+///
+/// `updates` holds the controller's state updates for [`AgentCommand::Updates`].
+#[expect(
+    clippy::too_many_lines,
+    reason = "One arm per command keeps the protocol in one place"
+)]
+pub fn execute<H: HdrControl>(
+    controller: &Mutex<AppController<H>>,
+    updates: &StateUpdates,
+    command: AgentCommand,
+) -> AgentResponse {
+    let mut controller = controller.lock();
+    let result = match command {
+        AgentCommand::Status => {
+            let active_apps = controller
+                .config
+                .read()
+                .monitored_apps
+                .iter()
+                .filter(|app| app.is_enabled())
+                .map(|app| app.display_name().to_string())
+                .collect();
+            return AgentResponse::Status {
+                hdr_enabled: controller.is_hdr_enabled(),
+                active_apps,
+            };
+        }
+        AgentCommand::Stop | AgentCommand::Shutdown => {
+            return AgentResponse::Error {
+                message: "Stopping is handled by the agent channel".to_string(),
+            };
+        }
+        AgentCommand::State => return AgentResponse::State(state(&controller)),
+        AgentCommand::AddApp { exe_path } => Win32App::from_exe_path(exe_path).and_then(|app| {
+            let name = app.display_name.clone();
+            controller
+                .add_application(MonitoredApp::Win32(app))
+                .map(|()| format!("Added {name}"))
+        }),
+        AgentCommand::RemoveApp { id } => {
+            let name = app_name(&controller, id);
+            controller
+                .remove_application(id)
+                .map(|()| format!("Removed {name}"))
+        }
+        AgentCommand::SetAppEnabled { id, enabled } => {
+            let name = app_name(&controller, id);
+            controller.toggle_app_enabled(id, enabled).map(|()| {
+                if enabled {
+                    format!("Enabled {name}")
+                } else {
+                    format!("Disabled {name}")
+                }
+            })
+        }
+        AgentCommand::ToggleHdr => {
+            if !controller.is_hdr_enabled() {
+                controller
+                    .start_manual_session(None, None)
+                    .map(|()| "Started a manual HDR session".to_string())
+            } else if controller.is_manual_session_active() {
+                controller
+                    .stop_manual_session()
+                    .map(|()| "Ended the manual HDR session".to_string())
+            } else {
+                controller
+                    .apply_hdr_override(HdrOverride::TurnOff)
+                    .map(|()| "Turned HDR off until the applications exit".to_string())
+            }
+        }
+        AgentCommand::CancelPending { id } => Ok(if controller.cancel_pending(id) {
+            "Cancelled the pending action".to_string()
+        } else {
+            "The action was no longer pending".to_string()
+        }),
+        AgentCommand::ReloadConfig => ConfigManager::load().map(|config| {
+            controller.reload_config(config);
+            "Reloaded the configuration".to_string()
+        }),
+        AgentCommand::Updates { resend } => {
+            // Updates nobody showed are stale for a window that just connected
+            let mut states = updates.take();
+            if resend {
+                states = vec![controller.current_state()];
+            }
+            return AgentResponse::Updates { states };
+        }
+        AgentCommand::StartManualSession {
+            duration_secs,
+            display_mode,
+        } => controller
+            .start_manual_session(duration_secs.map(Duration::from_secs), display_mode)
+            .map(|()| "Started a manual HDR session".to_string()),
+        AgentCommand::StopManualSession => controller
+            .stop_manual_session()
+            .map(|()| "Ended the manual HDR session".to_string()),
+        AgentCommand::ApplyHdrOverride { hdr_override } => controller
+            .apply_hdr_override(hdr_override)
+            .map(|()| "Applied the HDR override".to_string()),
+        AgentCommand::SnoozeApp { id } => {
+            let name = app_name(&controller, id);
+            controller.snooze_app(id);
+            Ok(format!("Snoozed {name}"))
+        }
+        AgentCommand::ExtendHdrSession => {
+            controller.extend_hdr_session();
+            Ok("Extended the HDR session".to_string())
+        }
+        AgentCommand::StartPixelRefresh => controller
+            .start_pixel_refresh()
+            .map(|()| "Started the pixel refresh".to_string()),
+        AgentCommand::SetDisplayHdr { identity, enabled } => {
+            match controller
+                .display_hdr_states()
+                .into_iter()
+                .find(|display| display.target.identity.as_ref() == Some(&identity))
+            {
+                Some(display) => controller
+                    .set_display_hdr(&display.target, enabled)
+                    .map(|()| {
+                        format!(
+                            "Turned HDR {} on {}",
+                            if enabled { "on" } else { "off" },
+                            display.target.display_name()
+                        )
+                    }),
+                None => {
+                    Err(io::Error::other(format!("display {identity} is not connected")).into())
+                }
+            }
+        }
+        AgentCommand::SetDisplayColorMode { identity, mode } => controller
+            .set_display_color_mode(&identity, mode)
+            .map(|()| format!("Switched display {identity} to {mode}")),
+    };
+
+    match result {
+        Ok(message) => AgentResponse::Done { message },
+        Err(e) => AgentResponse::Error {
+            message: e.to_string(),
+        },
+    }
+}
+
+/// Answer to [`AgentCommand::State`]
+fn state<H: HdrControl>(controller: &AppController<H>) -> AgentState {
+    let now = Instant::now();
+    AgentState {
+        hdr_enabled: controller.is_hdr_enabled(),
+        dry_run: controller.is_dry_run(),
+        manual_session: controller.manual_session_description(),
+        hdr_toggle_error: controller.hdr_toggle_error().map(str::to_string),
+        apps: controller
+            .config
+            .read()
+            .monitored_apps
+            .iter()
+            .map(|app| AgentApp {
+                id: *app.id(),
+                name: app.display_name().to_string(),
+                enabled: app.is_enabled(),
+            })
+            .collect(),
+        pending_actions: controller
+            .pending_actions()
+            .iter()
+            .map(|action| AgentPendingAction {
+                id: action.id,
+                description: action.countdown(now),
+            })
+            .collect(),
+    }
+}
+
+/// Name of the monitored application with `id`, or the ID if there is none
+fn app_name<H: HdrControl>(controller: &AppController<H>, id: Uuid) -> String {
+    controller
+        .config
+        .read()
+        .monitored_apps
+        .iter()
+        .find(|app| app.id() == &id)
+        .map_or_else(|| id.to_string(), |app| app.display_name().to_string())
+}
+
+/// Connection of a front-end window's controller to the background agent
+#[derive(Debug, Clone)]
+pub struct AgentLink {
+    endpoint_path: PathBuf,
+}
+
+impl AgentLink {
+    /// Link to the agent published in the given endpoint file, usually
+    /// [`endpoint_path`]
+    pub fn at(endpoint_path: PathBuf) -> Self {
+        Self { endpoint_path }
+    }
+
+    /// Send a command to the agent and return its answer
+    pub fn request(&self, command: AgentCommand) -> io::Result<AgentResponse> {
+        send_at(&self.endpoint_path, command)
+    }
+
+    /// Run a command changing the agent's controller, returning what was done
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not answer or rejects the command.
+    pub fn run(&self, command: AgentCommand) -> Result<String> {
+        match self.request(command)? {
+            AgentResponse::Done { message } => Ok(message),
+            AgentResponse::Error { message } => Err(io::Error::other(message).into()),
+            response => Err(io::Error::other(format!(
+                "Unexpected answer from the background agent: {response:?}"
+            ))
+            .into()),
+        }
+    }
+}
+
+/// Pass the agent's state updates on to a front-end window until the window exits
+///
+/// Starts a thread polling the agent every [`UPDATE_POLL_INTERVAL`]. Every update gets
+/// the HDR state of the displays from `display_hdr`, read by the window itself. While
+/// the agent does not answer, the window keeps its last state, and it is sent the
+/// current state once an agent answers again.
+pub fn forward_state_updates(
+    link: AgentLink,
+    display_hdr: impl Fn() -> Vec<DisplayHdrState> + Send + 'static,
+    state_sender: mpsc::SyncSender<AppState>,
+) -> io::Result<std::thread::JoinHandle<()>> {
+    use tracing::{debug, info, warn};
+
+    std::thread::Builder::new()
+        .name("agent-link".to_string())
+        .spawn(move || {
+            let mut resend = true;
+            loop {
+                match link.request(AgentCommand::Updates { resend }) {
+                    Ok(AgentResponse::Updates { states }) => {
+                        if resend {
+                            info!("Showing the state of the background agent");
+                        }
+                        resend = false;
+                        let display_hdr = if states.is_empty() {
+                            Vec::new()
+                        } else {
+                            display_hdr()
+                        };
+                        for mut state in states {
+                            state.display_hdr.clone_from(&display_hdr);
+                            if state_sender.send(state).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Ok(response) => {
+                        debug!("Unexpected answer to a state request: {:?}", response);
+                    }
+                    Err(e) => {
+                        if !resend {
+                            warn!("The background agent stopped answering: {}", e);
+                        }
+                        resend = true;
+                    }
+                }
+                std::thread::sleep(UPDATE_POLL_INTERVAL);
+            }
+        })
+}
+
+/// Send a command to the agent published in the data directory
+pub fn send(command: AgentCommand) -> io::Result<AgentResponse> {
+    send_at(&endpoint_path(), command)
+}
+
+/// Send a command to the agent published in the given endpoint file
+pub fn send_at(endpoint_path: &Path, command: AgentCommand) -> io::Result<AgentResponse> {
+    let endpoint = Endpoint::parse(&std::fs::read_to_string(endpoint_path)?).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "Malformed agent endpoint file")
+    })?;
+
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, endpoint.port));
+    let mut stream = TcpStream::connect_timeout(&address, IO_TIMEOUT)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    write_line(
+        &mut stream,
+        &AgentRequest {
+            token: endpoint.token,
+            command,
+        },
+    )?;
+    Ok(serde_json::from_str(&read_line(&stream)?)?)
+}
+
+/// Ask a running agent to stop, returning whether one was running
+///
+/// An endpoint file left behind by an agent that no longer runs is removed.
+pub fn stop_running_agent() -> bool {
+    use tracing::{info, warn};
+
+    let path = endpoint_path();
+    match send_at(&path, AgentCommand::Stop) {
+        Ok(AgentResponse::Stopping) => {
+            info!("Taking over from the background agent");
+            true
+        }
+        Ok(response) => {
+            warn!("Background agent refused to stop: {:?}", response);
+            false
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => false,
+        Err(e) => {
+            info!(
+                "No background agent answered ({}), removing its endpoint file",
+                e
+            );
+            let _ = std::fs::remove_file(&path);
+            false
+        }
+    }
+}
+
+/// Start a background agent in a new process
+///
+/// The agent waits for this process to release the single-instance lock.
+pub fn spawn_process() -> io::Result<()> {
+    let mut command = std::process::Command::new(std::env::current_exe()?);
+    command.arg(AGENT_FLAG);
+    if data_dir::is_portable() {
+        command.arg("--portable");
+    }
+    command.spawn().map(drop)
+}
+
+/// Start a background agent unless one answers, and wait up to `timeout` for it
+///
+/// A new agent waits for the single-instance lock, so the timeout should be longer than
+/// its wait.
+pub fn ensure_running(timeout: Duration) -> io::Result<()> {
+    use tracing::info;
+
+    if matches!(send(AgentCommand::Status), Ok(AgentResponse::Status { .. })) {
+        return Ok(());
+    }
+
+    info!("No background agent answered, starting one");
+    spawn_process()?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        std::thread::sleep(Duration::from_millis(200));
+        match send(AgentCommand::Status) {
+            Ok(AgentResponse::Status { .. }) => return Ok(()),
+            _ if Instant::now() < deadline => {}
+            Ok(response) => {
+                return Err(io::Error::other(format!(
+                    "Unexpected answer from the background agent: {response:?}"
+                )));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Read one line of at most [`MAX_LINE_LEN`] bytes
+fn read_line(stream: impl Read) -> io::Result<String> {
+    let mut line = String::new();
+    BufReader::new(stream)
+        .take(MAX_LINE_LEN)
+        .read_line(&mut line)?;
+    Ok(line)
+}
+
+/// Compare a request token with the agent's in time independent of where they differ
+///
+/// Only the length can be told apart by timing, and every token has the same length.
+fn tokens_match(expected: &str, given: &str) -> bool {
+    let (expected, given) = (expected.as_bytes(), given.as_bytes());
+    let difference = expected
+        .iter()
+        .zip(given)
+        .fold(0, |difference, (a, b)| difference | (a ^ b));
+    expected.len() == given.len() && std::hint::black_box(difference) == 0
+}
+
+/// Write a value as one JSON line
+fn write_line(stream: &mut impl Write, value: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_string(value)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{AppdataGuard, create_test_dir};
+
+    #[test]
+    fn test_endpoint_round_trip() {
+        let endpoint = Endpoint {
+            port: 50123,
+            token: "abc123".to_string(),
+        };
+        assert_eq!(Endpoint::parse(&endpoint.to_string()), Some(endpoint));
+        assert_eq!(Endpoint::parse("50123"), None);
+        assert_eq!(Endpoint::parse("50123 "), None);
+        assert_eq!(Endpoint::parse("port abc123"), None);
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc123", "abc124"));
+        assert!(!tokens_match("abc123", "abc12"));
+        assert!(!tokens_match("abc123", ""));
+    }
+
+    #[test]
+    fn test_status_and_stop() {
+        let dir = create_test_dir();
+        let path = dir.path().join(ENDPOINT_FILE);
+        let server = AgentServer::bind_at(path.clone()).unwrap();
+        let agent = std::thread::spawn(move || {
+            server.serve(|_| AgentResponse::Status {
+                hdr_enabled: true,
+                active_apps: vec!["Game".to_string()],
+            })
+        });
+
+        assert_eq!(
+            send_at(&path, AgentCommand::Status).unwrap(),
+            AgentResponse::Status {
+                hdr_enabled: true,
+                active_apps: vec!["Game".to_string()],
+            }
+        );
+        assert_eq!(
+            send_at(&path, AgentCommand::Stop).unwrap(),
+            AgentResponse::Stopping
+        );
+        agent.join().unwrap().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_wrong_token_is_rejected() {
+        let dir = create_test_dir();
+        let path = dir.path().join(ENDPOINT_FILE);
+        let server = AgentServer::bind_at(path.clone()).unwrap();
+        let port = Endpoint::parse(&std::fs::read_to_string(&path).unwrap())
+            .unwrap()
+            .port;
+        let agent = std::thread::spawn(move || {
+            server.serve(|_| AgentResponse::Status {
+                hdr_enabled: false,
+                active_apps: Vec::new(),
+            })
+        });
+
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        write_line(
+            &mut stream,
+            &AgentRequest {
+                token: "guess".to_string(),
+                command: AgentCommand::Stop,
+            },
+        )
+        .unwrap();
+        let response: AgentResponse = serde_json::from_str(&read_line(&stream).unwrap()).unwrap();
+        assert!(matches!(response, AgentResponse::Error { .. }));
+
+        // Still running after the rejected stop
+        assert_eq!(
+            send_at(&path, AgentCommand::Stop).unwrap(),
+            AgentResponse::Stopping
+        );
+        agent.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_commands_drive_the_controller() {
+        use crate::config::{AppConfig, AppSource};
+        use crate::hdr::{AutoHdrMode, MockHdrControl};
+        use crate::monitor::SharedWatchState;
+        use std::path::PathBuf;
+        use std::sync::{Arc, mpsc};
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let id = Uuid::new_v4();
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id,
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let controller = Mutex::new(AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            Arc::new(SharedWatchState::default()),
+        ));
+        let drain = std::thread::spawn(move || state_rx.into_iter().count());
+
+        let path = temp_dir.path().join(ENDPOINT_FILE);
+        let server = AgentServer::bind_at(path.clone()).unwrap();
+        let updates = StateUpdates::default();
+        let agent = std::thread::scope(|scope| {
+            let agent =
+                scope.spawn(|| server.serve(|command| execute(&controller, &updates, command)));
+
+            let AgentResponse::State(state) = send_at(&path, AgentCommand::State).unwrap() else {
+                panic!("expected the state");
+            };
+            assert!(!state.hdr_enabled);
+            assert_eq!(
+                state.apps,
+                vec![AgentApp {
+                    id,
+                    name: "Game".to_string(),
+                    enabled: true,
+                }]
+            );
+
+            assert_eq!(
+                send_at(&path, AgentCommand::SetAppEnabled { id, enabled: false }).unwrap(),
+                AgentResponse::Done {
+                    message: "Disabled Game".to_string(),
+                }
+            );
+            assert!(matches!(
+                send_at(&path, AgentCommand::ToggleHdr).unwrap(),
+                AgentResponse::Done { .. }
+            ));
+            let AgentResponse::State(state) = send_at(&path, AgentCommand::State).unwrap() else {
+                panic!("expected the state");
+            };
+            assert!(state.hdr_enabled);
+            assert!(state.manual_session.is_some());
+            assert!(!state.apps[0].enabled);
+
+            assert!(matches!(
+                send_at(
+                    &path,
+                    AgentCommand::AddApp {
+                        exe_path: "C:\\missing\\game.exe".to_string(),
+                    },
+                )
+                .unwrap(),
+                AgentResponse::Error { .. }
+            ));
+            assert_eq!(
+                send_at(&path, AgentCommand::Stop).unwrap(),
+                AgentResponse::Stopping
+            );
+            agent.join().unwrap()
+        });
+        agent.unwrap();
+
+        drop(controller);
+        drain.join().unwrap();
+    }
+
+    #[test]
+    fn test_front_end_forwards_to_the_agent() {
+        use crate::config::AppConfig;
+        use crate::hdr::MockHdrControl;
+        use crate::monitor::SharedWatchState;
+        use std::sync::{Arc, mpsc};
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let controller = Mutex::new(AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            Arc::new(SharedWatchState::default()),
+        ));
+
+        let path = temp_dir.path().join(ENDPOINT_FILE);
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (front_end_state_tx, front_end_state_rx) = mpsc::sync_channel(32);
+        let mut front_end = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            front_end_state_tx,
+            Arc::new(SharedWatchState::default()),
+        )
+        .front_end_of(AgentLink::at(path.clone()));
+        assert!(front_end.is_front_end());
+
+        // Queue the agent's state updates like the agent's state thread does
+        let updates = Arc::new(StateUpdates::default());
+        let queue = std::thread::spawn({
+            let updates = Arc::clone(&updates);
+            move || {
+                for state in state_rx {
+                    updates.push(state);
+                }
+            }
+        });
+
+        let server = AgentServer::bind_at(path.clone()).unwrap();
+        let stopped_by = std::thread::scope(|scope| {
+            let agent =
+                scope.spawn(|| server.serve(|command| execute(&controller, &updates, command)));
+
+            // A window that just connected starts from the current state
+            let link = AgentLink::at(path.clone());
+            let AgentResponse::Updates { states } = link
+                .request(AgentCommand::Updates { resend: true })
+                .unwrap()
+            else {
+                panic!("expected state updates");
+            };
+            assert_eq!(states.len(), 1);
+            assert!(!states[0].hdr_enabled);
+
+            // Actions run on the agent's controller, not the window's
+            front_end.start_manual_session(None, None).unwrap();
+            assert!(controller.lock().is_manual_session_active());
+            assert!(!front_end.is_manual_session_active());
+
+            // Saved changes reach the agent
+            let mut preferences = front_end.config.read().preferences.clone();
+            preferences.hdr_disable_debounce_ms = 1234;
+            front_end.update_preferences(preferences).unwrap();
+            assert_eq!(
+                controller
+                    .lock()
+                    .config
+                    .read()
+                    .preferences
+                    .hdr_disable_debounce_ms,
+                1234
+            );
+
+            // The window shows the agent's state updates instead of sending its own
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let state = loop {
+                let AgentResponse::Updates { states } = link
+                    .request(AgentCommand::Updates { resend: false })
+                    .unwrap()
+                else {
+                    panic!("expected state updates");
+                };
+                if let Some(state) = states.into_iter().rev().find(|state| state.hdr_enabled) {
+                    break state;
+                }
+                assert!(Instant::now() < deadline, "no state update with HDR on");
+                std::thread::sleep(Duration::from_millis(10));
+            };
+            assert!(state.manual_session.is_some());
+            assert!(front_end_state_rx.try_recv().is_err());
+
+            front_end.stop_manual_session().unwrap();
+            assert!(!controller.lock().is_manual_session_active());
+
+            let stopped_by = link.request(AgentCommand::Shutdown).unwrap();
+            assert_eq!(stopped_by, AgentResponse::Stopping);
+            agent.join().unwrap().unwrap()
+        });
+        assert_eq!(stopped_by, AgentCommand::Shutdown);
+
+        drop(controller);
+        queue.join().unwrap();
+    }
+}
@@ -1,18 +1,81 @@
 //! Application controller implementation.
 
-use crate::config::{AppConfig, ConfigManager, MonitoredApp, UserPreferences};
+use crate::config::{
+    AppConfig, ConfigManager, DisplayGroup, KnownDisplay, MAX_HDR_DISABLE_DEBOUNCE_MS, MatchRule,
+    MonitoredApp, OledCare, ShutdownPolicy, UserPreferences, Win32App, hdr_disable_debounce,
+    max_hdr_session,
+};
+use crate::controller::agent::{AgentCommand, AgentLink};
+use crate::controller::clock::{Clock, SystemClock};
+use crate::controller::onboarding::{
+    HDR_TEST_DURATION, HdrTest, Onboarding, OnboardingState, OnboardingStep,
+};
+use crate::controller::session_history::{
+    AppStats, MANUAL_SESSION_ID, SessionHistory, SessionTracker, StatsFormat, WEEKLY_SUMMARY_PERIOD,
+};
+use crate::controller::toggle_history::{ToggleHistory, ToggleRecord, ToggleTrigger};
 use crate::error::{EasyHdrError, Result};
-use crate::hdr::HdrController;
-use crate::monitor::{AppIdentifier, HdrStateEvent, ProcessEvent, WatchState};
+use crate::hdr::{
+    AutoHdrController, AutoHdrMode, BandwidthAdvisory, Capabilities, CaptureToneMappingController,
+    ColorMode, DisplayIdentity, DisplayModeController, DisplayModeOverride, DisplayTarget,
+    HDR_TOGGLE_ATTEMPTS, HdrControl, HdrController, NightLightController, VcpCommand,
+    color_profile, crash_guard, link_bandwidth,
+};
+use crate::integrations::discord::{Activity, DiscordPresence};
+use crate::monitor::running_candidates::RunningCandidate;
+use crate::monitor::{
+    AdaptivePolling, AppIdentifier, HdrStateEvent, PollTimings, ProcessEvent, SharedWatchState,
+};
+use crate::utils::error_center;
+use crate::utils::event_log::{self, EventKind};
+use crate::utils::metrics;
+use crate::utils::volume::OfflineVolumes;
+use crate::utils::watchdog;
+use crate::utils::{LogLevel, PowerStatus, normalize_process_name};
 use parking_lot::{Mutex, RwLock};
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, mpsc};
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
+/// How often the believed HDR state and active sessions are checked against the system
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long before the HDR session limit the user is warned
+const SESSION_LIMIT_WARNING: Duration = Duration::from_mins(5);
+
+/// How much longer HDR stays on when the user extends a session from the warning
+const SESSION_LIMIT_EXTENSION: Duration = Duration::from_hours(1);
+
+/// How long after a resume or a display driver reset HDR turning off is put down to
+/// Windows reverting it rather than the user
+const DISPLAY_RESET_GRACE: Duration = Duration::from_mins(1);
+
+/// How long automatic HDR management ignores an application snoozed from a notification
+const APP_SNOOZE_DURATION: Duration = Duration::from_hours(2);
+
+/// Period in which HDR changes made outside `EasyHDR` are counted to detect a conflict
+const HDR_CONFLICT_WINDOW: Duration = Duration::from_mins(2);
+
+/// HDR changes made outside `EasyHDR` within [`HDR_CONFLICT_WINDOW`] tolerated before
+/// automatic HDR management pauses
+const HDR_CONFLICT_MAX_CHANGES: usize = 3;
+
+/// Icons delivered by the background icon loader, as (application id, icon data)
+type IconBatch = Vec<(Uuid, Vec<u8>)>;
+
 /// Application state for GUI updates
-#[derive(Debug, Clone)]
+///
+/// Serialized for a window that is a front-end of the background agent, see
+/// [`crate::controller::agent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Independent status flags shown in the GUI"
+)]
 pub struct AppState {
     /// Whether HDR is currently enabled
     pub hdr_enabled: bool,
@@ -30,15 +93,379 @@ pub struct AppState {
     /// Set to true on first state update if no HDR displays were detected at startup.
     /// GUI should show notification and then clear this flag.
     pub show_no_hdr_warning: bool,
+    /// Flag indicating this update follows an automatic HDR toggle
+    ///
+    /// Set when HDR was toggled because a monitored application started or stopped,
+    /// so the GUI can offer override actions in its notification.
+    pub hdr_toggled_automatically: bool,
+    /// Application whose start or stop caused the automatic toggle, so the notification
+    /// can offer to snooze it
+    pub hdr_toggle_app: Option<Uuid>,
+    /// Weekly HDR usage summary to show as a notification
+    ///
+    /// Set on the first state update after the summary is due. GUI should show the
+    /// notification and then clear this field.
+    pub weekly_summary: Option<String>,
+    /// Displays that kept failing the last HDR toggle, `None` once a toggle succeeds
+    pub hdr_toggle_error: Option<String>,
+    /// Each display the last HDR toggle failed on, empty once a toggle succeeds
+    pub hdr_toggle_failures: Vec<DisplayToggleFailure>,
+    /// Why automatic HDR management is paused after conflicting HDR changes, `None`
+    /// while it runs
+    pub hdr_conflict: Option<String>,
+    /// Flag to show a notification that automatic HDR management paused
+    ///
+    /// Set when a conflict is detected. GUI should show notification and then clear
+    /// this flag.
+    pub show_hdr_conflict_notification: bool,
+    /// Flag to show a notification for a new HDR toggle failure
+    ///
+    /// Set when a toggle still failed after all retries. GUI should show notification
+    /// and then clear this flag.
+    pub show_hdr_toggle_error_notification: bool,
+    /// Current log level, mirrored by the tray menu
+    pub log_level: LogLevel,
+    /// Description of the running manual HDR session, `None` when none is running
+    pub manual_session: Option<String>,
+    /// Warning that the HDR session limit is about to turn HDR off
+    ///
+    /// Set once per deadline. GUI should show a notification offering to extend the
+    /// session and then clear this field.
+    pub session_limit_warning: Option<String>,
+    /// Reminder to run OLED panel maintenance after a long HDR session
+    ///
+    /// Set once per HDR session. GUI should show a notification and then clear this field.
+    pub oled_care_reminder: Option<OledCareReminder>,
+    /// Actions the controller has scheduled or is holding back, cancellable with
+    /// [`AppController::cancel_pending`]
+    pub pending_actions: Vec<PendingAction>,
+    /// Whether HDR toggles are only simulated, so `hdr_enabled` is the simulated state
+    pub dry_run: bool,
+    /// HDR state of each connected HDR-capable display, for the tray menu
+    ///
+    /// Not sent by the background agent; the window reads its own displays.
+    #[serde(skip)]
+    pub display_hdr: Vec<DisplayHdrState>,
+    /// First-run setup to show, `None` once it was finished or skipped
+    pub onboarding: Option<OnboardingState>,
+}
+
+/// Identifies an action the controller has scheduled or is holding back
+///
+/// Each kind is pending at most once, or once per application, so the kind doubles as
+/// the identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingActionId {
+    /// HDR turns off when the debounce window after the last monitored application ends
+    HdrDisable,
+    /// HDR toggle held back until the displays turn back on
+    DeferredHdrToggle,
+    /// Manual override suspending the next automatic toggle
+    HdrOverride,
+    /// HDR held off while excluded processes run, restored once they exit
+    ExclusionHold,
+    /// HDR left as it was changed outside `EasyHDR` until the hold expires
+    ExternalHdrHold,
+    /// Application ignored by automatic HDR management until the snooze expires
+    AppSnooze(Uuid),
+    /// Automatic HDR management paused after conflicting HDR changes, until resumed
+    HdrConflictPause,
+}
+
+/// Action the controller has scheduled or is holding back, for the GUI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingAction {
+    /// Identifier to pass to [`AppController::cancel_pending`]
+    pub id: PendingActionId,
+    /// Short description, e.g. `HDR turning off`
+    pub description: String,
+    /// When the action runs, `None` if it waits for an event rather than a deadline
+    #[serde(with = "millis_left")]
+    pub due_at: Option<Instant>,
+}
+
+/// Serializes a deadline as the milliseconds left, since an `Instant` only has a meaning
+/// within the process that took it
+mod millis_left {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::{Duration, Instant};
+
+    #[expect(
+        clippy::ref_option,
+        reason = "Signature required by serde's `with` attribute"
+    )]
+    pub fn serialize<S: Serializer>(
+        due_at: &Option<Instant>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        due_at
+            .map(|due_at| {
+                u64::try_from(due_at.saturating_duration_since(Instant::now()).as_millis())
+                    .unwrap_or(u64::MAX)
+            })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Instant>, D::Error> {
+        Ok(Option::<u64>::deserialize(deserializer)?
+            .and_then(|millis| Instant::now().checked_add(Duration::from_millis(millis))))
+    }
+}
+
+impl PendingAction {
+    /// Description with the time left until the action runs, e.g. `HDR turning off · 8 s left`
+    ///
+    /// Seconds are shown for the last minute and rounded up, so the countdown never shows
+    /// 0 before the action runs. Actions without a deadline show their description only.
+    pub fn countdown(&self, now: Instant) -> String {
+        let Some(due_at) = self.due_at else {
+            return self.description.clone();
+        };
+        let secs = due_at
+            .saturating_duration_since(now)
+            .as_millis()
+            .div_ceil(1000)
+            .max(1);
+        if secs < 60 {
+            format!("{} · {secs} s left", self.description)
+        } else {
+            format!("{} · {} min left", self.description, secs.div_ceil(60))
+        }
+    }
+}
+
+/// HDR state of a connected HDR-capable display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayHdrState {
+    /// The display, to pass to [`AppController::set_display_hdr`]
+    pub target: DisplayTarget,
+    /// Whether HDR is on
+    pub hdr_enabled: bool,
+}
+
+/// A display an HDR toggle still failed on after all retries
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayToggleFailure {
+    /// Name of the display to show users
+    pub display: String,
+    /// Windows error code of the failing call, `None` if the error carried none
+    pub error_code: Option<i32>,
+    /// Full error message
+    pub message: String,
+}
+
+impl DisplayToggleFailure {
+    /// Record why HDR could not be toggled on a display
+    pub fn new(target: &DisplayTarget, error: &EasyHdrError) -> Self {
+        Self {
+            display: target.display_name(),
+            error_code: error.error_code(),
+            message: error.to_string(),
+        }
+    }
+
+    /// What the user can try for a known Windows error code
+    pub fn hint(&self) -> Option<&'static str> {
+        /// Windows refuses display changes on the secure desktop and in remote sessions
+        const ERROR_ACCESS_DENIED: i32 = 5;
+        /// The graphics driver rejected the request
+        const ERROR_GEN_FAILURE: i32 = 31;
+        /// The display or driver cannot switch HDR
+        const ERROR_NOT_SUPPORTED: i32 = 50;
+        /// The display path no longer exists, e.g. after it was reconnected
+        const ERROR_INVALID_PARAMETER: i32 = 87;
+
+        match self.error_code? {
+            ERROR_ACCESS_DENIED => Some(
+                "Windows denied the change, which happens while the screen is locked or in a remote desktop session.",
+            ),
+            ERROR_GEN_FAILURE => Some(
+                "The graphics driver rejected the change. Updating the driver or reconnecting the display usually helps.",
+            ),
+            ERROR_NOT_SUPPORTED => {
+                Some("The display or its graphics driver does not support switching HDR.")
+            }
+            ERROR_INVALID_PARAMETER => Some(
+                "The display was reconnected or its configuration changed. Try again, or restart EasyHDR if it keeps failing.",
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DisplayToggleFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.error_code {
+            Some(code) => write!(f, "HDR failed on {}: error {code}", self.display),
+            None => write!(f, "HDR failed on {}: {}", self.display, self.message),
+        }
+    }
+}
+
+/// Reminder to run OLED panel maintenance, sent when a long HDR session ends
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OledCareReminder {
+    /// Notification text naming the displays and the session length
+    pub message: String,
+    /// Whether a display has a DDC/CI command to start the pixel refresh
+    pub can_start_pixel_refresh: bool,
+}
+
+/// Per-application settings edited together, for [`AppController::update_application`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppSettings {
+    /// Display name shown in the UI
+    pub display_name: String,
+    /// Color mode used instead of HDR (`None` means HDR)
+    pub color_mode: Option<ColorMode>,
+    /// Override of [`UserPreferences::hdr_disable_debounce_ms`]
+    pub hdr_disable_debounce_ms: Option<u64>,
+    /// Display mode applied together with HDR
+    pub display_mode: Option<DisplayModeOverride>,
+    /// Pattern matching further process names (Win32 applications only)
+    pub match_rule: Option<MatchRule>,
+    /// Alternative executables treated as the application (Win32 applications only)
+    pub aliases: Vec<String>,
+    /// Name of the display group HDR is limited to (`None` means every display)
+    pub display_group: Option<String>,
+}
+
+impl AppSettings {
+    /// Current settings of an application
+    pub fn of(app: &MonitoredApp) -> Self {
+        Self {
+            display_name: app.display_name().to_string(),
+            color_mode: app.color_mode(),
+            hdr_disable_debounce_ms: app.hdr_disable_debounce_ms(),
+            display_mode: app.display_mode(),
+            match_rule: app.match_rule().cloned(),
+            aliases: app.aliases().to_vec(),
+            display_group: app.display_group().map(str::to_string),
+        }
+    }
+}
+
+/// Format a number of minutes for notifications, e.g. `2 h` or `90 min`
+fn describe_minutes(minutes: u64) -> String {
+    if minutes >= 60 && minutes.is_multiple_of(60) {
+        format!("{} h", minutes / 60)
+    } else {
+        format!("{minutes} min")
+    }
+}
+
+/// Whether two targets are the same display, matched by adapter and target ID
+fn is_same_display(a: &DisplayTarget, b: &DisplayTarget) -> bool {
+    a.adapter_id == b.adapter_id && a.target_id == b.target_id
+}
+
+/// HDR state chosen outside `EasyHDR` against the automatic decision
+///
+/// Automatic toggles leave HDR alone until `until`, or until a monitored application
+/// that was not running at the time of the change starts.
+#[derive(Debug, Clone)]
+struct ExternalHdrHold {
+    /// HDR state chosen outside `EasyHDR`
+    enabled: bool,
+    /// When HDR was changed
+    since: Instant,
+    /// When automatic HDR management resumes
+    until: Instant,
+    /// Monitored applications running when HDR was changed
+    running_apps: HashSet<Uuid>,
+}
+
+/// Manual HDR session started from the GUI without a monitored application
+#[derive(Debug, Clone, Copy)]
+struct ManualSession {
+    /// When the session ends by itself, `None` to keep it until stopped
+    ends_at: Option<Instant>,
+    /// Requested length of the session
+    duration: Option<Duration>,
+}
+
+impl ManualSession {
+    /// Short description for the GUI and logs, e.g. `HDR session · 1 h`
+    fn describe(&self) -> String {
+        match self.duration {
+            None => "HDR session · until stopped".to_string(),
+            Some(duration) => format!(
+                "HDR session · {}",
+                describe_minutes(duration.as_secs() / 60)
+            ),
+        }
+    }
+}
+
+/// Continuous time with HDR on, counted against the session limit
+#[derive(Debug, Clone, Copy)]
+struct HdrSessionLimit {
+    /// When the session limit started counting
+    since: Instant,
+    /// Time added by extending the session from the warning notification
+    extended_by: Duration,
+    /// Whether the warning for the current deadline was sent
+    warned: bool,
+}
+
+/// Result of a background volume availability check
+struct VolumeCheck {
+    /// Volumes that were unreachable
+    offline: OfflineVolumes,
+    /// Icons extracted for applications whose volume is available again
+    icons: Vec<(Uuid, Vec<u8>)>,
+}
+
+/// Manual override of automatic HDR management, requested from a toggle notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HdrOverride {
+    /// Turn HDR on and keep it on when the last monitored application stops
+    KeepOn,
+    /// Turn HDR off and keep it off until all monitored applications have stopped
+    TurnOff,
+}
+
+/// Whether an HDR toggle took effect or waits for the displays to turn on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToggleOutcome {
+    /// The displays were switched
+    Applied,
+    /// The displays are off, the toggle runs once they turn back on
+    Deferred,
+    /// Automatic HDR management is paused, nothing changed
+    Skipped,
 }
 
 /// Application logic controller
-pub struct AppController {
+///
+/// Generic over the HDR backend so embedding crates and tests can inject their own;
+/// defaults to the Windows [`HdrController`]. Time is read from a [`Clock`], see
+/// [`AppController::with_clock`].
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Independent runtime state flags of the controller"
+)]
+pub struct AppController<H = HdrController> {
     /// Application configuration (public for GUI access)
     pub config: Arc<RwLock<AppConfig>>,
-    hdr_controller: HdrController,
+    hdr_controller: H,
     active_process_count: AtomicUsize,
     current_hdr_state: AtomicBool,
+    /// HDR-capable displays with HDR on, as last reported by the HDR state monitor
+    ///
+    /// `current_hdr_state` is on while any of them is, so HDR turned off on one display
+    /// while another keeps it on does not end the HDR session.
+    hdr_on_displays: Vec<DisplayTarget>,
+    /// HDR change made outside `EasyHDR` that automatic toggles leave alone
+    external_hold: Option<ExternalHdrHold>,
+    /// Power source and battery charge, as last reported by the HDR state monitor
+    power_status: PowerStatus,
+    /// Whether HDR is on because `EasyHDR` turned it on, for [`ShutdownPolicy::RestorePrevious`]
+    hdr_enabled_by_app: bool,
     /// Taken when event loop starts
     event_receiver: Option<mpsc::Receiver<ProcessEvent>>,
     /// Taken when event loop starts
@@ -49,7 +476,7 @@ pub struct AppController {
     /// Nanoseconds elapsed since `startup_time` for debouncing
     last_toggle_time_nanos: Arc<AtomicU64>,
     /// Shared watch state with `ProcessMonitor` for atomic updates
-    watch_state: Arc<RwLock<WatchState>>,
+    watch_state: Arc<SharedWatchState>,
     /// Tracks whether HDR displays are currently available
     ///
     /// Used to detect when HDR displays become available after being unavailable,
@@ -63,6 +490,147 @@ pub struct AppController {
     ///
     /// Set if no HDR displays were detected at startup, cleared after notification is sent.
     pending_no_hdr_warning: AtomicBool,
+    /// Flag to mark the next state update as following an automatic HDR toggle
+    pending_auto_toggle_notification: AtomicBool,
+    /// Application whose start or stop last toggled HDR or scheduled it to turn off
+    auto_toggle_app: Option<Uuid>,
+    /// Applications snoozed from a toggle notification, with when the snooze ends
+    ///
+    /// Snoozed applications are left out of the watch list, as if they were disabled.
+    snoozed_apps: HashMap<Uuid, Instant>,
+    /// Active manual override, if any
+    ///
+    /// `KeepOn` is consumed by the next automatic disable it suppresses; `TurnOff` is
+    /// cleared once no monitored applications are running.
+    hdr_override: Option<HdrOverride>,
+    /// Whether the displays are powered on, as reported by the HDR state monitor
+    displays_on: bool,
+    /// HDR toggle requested while the displays were off
+    ///
+    /// Some drivers fail to change the HDR state of a sleeping display, so the toggle is
+    /// held back and applied once the displays turn back on.
+    deferred_hdr_toggle: Option<bool>,
+    /// Application whose start requested the deferred HDR toggle, becomes
+    /// `auto_toggle_app` once the toggle runs
+    deferred_toggle_app: Option<Uuid>,
+    /// When the system last resumed from sleep or HDR displays came back, which can
+    /// revert HDR behind `EasyHDR`'s back
+    display_reset_at: Option<Instant>,
+    /// Whether the system resumed while the displays were off, so HDR is rechecked once
+    /// they turn on
+    resume_pending: bool,
+    /// HDR state seen by the last reconciliation that disagreed with `current_hdr_state`
+    ///
+    /// Drift is only corrected when two consecutive checks agree, so events still in
+    /// flight are not mistaken for missed ones.
+    suspected_hdr_drift: Option<bool>,
+    /// Running monitored application count seen by the last reconciliation that
+    /// disagreed with `active_process_count`
+    suspected_session_drift: Option<usize>,
+    /// Watched folders reported as blocked by an elevated process, by executable name
+    reported_elevated_apps: HashMap<AppIdentifier, String>,
+    /// When HDR is turned off after the last monitored application stopped
+    ///
+    /// Cancelled if a monitored application starts again before the deadline, so
+    /// applications that relaunch themselves keep HDR on.
+    pending_hdr_disable: Option<Instant>,
+    /// Applies and restores per-application display modes alongside HDR
+    display_mode: DisplayModeController,
+    /// Display mode requested by the monitored application that started the session
+    session_display_mode: Option<DisplayModeOverride>,
+    /// Applies and restores per-application tone mapping of HDR captures alongside HDR
+    capture_tone_mapping: CaptureToneMappingController,
+    /// Capture tone mapping requested by the monitored application that started the
+    /// session
+    session_capture_tone_mapping: Option<bool>,
+    /// Display group of each running monitored application, in start order (`None` for
+    /// applications that turn HDR on for every display)
+    session_display_groups: Vec<(AppIdentifier, Option<DisplayGroup>)>,
+    /// Displays of display groups HDR was turned on for, turned back off at the end of
+    /// the session (`None` while HDR is on for every display)
+    group_hdr_displays: Option<Vec<DisplayTarget>>,
+    /// Turns Night Light off while a monitored application has HDR on
+    night_light: NightLightController,
+    /// Whether the running session switched the displays to wide color gamut instead of
+    /// turning HDR on
+    wcg_session: bool,
+    /// Displays the running session switched to wide color gamut, with the color mode
+    /// each had before
+    wcg_displays: Vec<(DisplayTarget, ColorMode)>,
+    /// Discord Rich Presence, `None` unless enabled in the preferences
+    discord: Option<DiscordPresence>,
+    /// Running sessions of monitored applications, for the session history
+    sessions: SessionTracker,
+    /// Finished sessions of monitored applications
+    session_history: SessionHistory,
+    /// Whether `session_history` has changes not yet saved to disk
+    session_history_dirty: bool,
+    /// Recent HDR toggles and what triggered them
+    toggle_history: ToggleHistory,
+    /// Weekly summary to include in the next state update
+    pending_weekly_summary: Mutex<Option<String>>,
+    /// Displays that kept failing the last HDR toggle after all retries
+    hdr_toggle_error: Option<String>,
+    /// Each display behind `hdr_toggle_error`, with its error code
+    hdr_toggle_failures: Vec<DisplayToggleFailure>,
+    /// Flag to show the HDR toggle failure notification on next state update
+    pending_hdr_toggle_error_notification: AtomicBool,
+    /// Recent HDR changes made outside `EasyHDR`, within [`HDR_CONFLICT_WINDOW`]
+    external_hdr_changes: Vec<Instant>,
+    /// When automatic HDR management paused because HDR kept changing outside `EasyHDR`
+    ///
+    /// Another HDR tool or a game switching HDR itself fights the automatic toggles,
+    /// which makes the displays flicker. Only manual toggles are made until the user
+    /// resumes automatic management.
+    hdr_conflict_since: Option<Instant>,
+    /// Flag to show the HDR conflict notification on next state update
+    pending_hdr_conflict_notification: AtomicBool,
+    /// Network and removable volumes found unreachable by the last availability check
+    ///
+    /// Applications on these volumes are shown as offline rather than missing.
+    offline_volumes: OfflineVolumes,
+    /// Whether a volume availability check has finished since startup
+    volumes_checked: bool,
+    /// Volume availability check running in the background
+    volume_check: Option<mpsc::Receiver<VolumeCheck>>,
+    /// Icon loading running in the background, delivering batches of loaded icons
+    icon_loader: Option<mpsc::Receiver<IconBatch>>,
+    /// Running manual HDR session, if any
+    ///
+    /// Keeps HDR on like a running monitored application, so stopping the last
+    /// monitored application doesn't turn HDR off while it runs.
+    manual_session: Option<ManualSession>,
+    /// Time HDR has been on since `EasyHDR` turned it on, for the session limit
+    ///
+    /// `None` while HDR is off or was turned on outside `EasyHDR`.
+    hdr_session: Option<HdrSessionLimit>,
+    /// Session limit warning to include in the next state update
+    pending_session_limit_warning: Mutex<Option<String>>,
+    /// When HDR was last seen turning on, for OLED care reminders
+    ///
+    /// Unlike `hdr_session`, counts HDR turned on outside `EasyHDR` as well.
+    oled_hdr_since: Option<Instant>,
+    /// Pixel refresh commands offered by the last OLED care reminder
+    pixel_refresh_due: Vec<(DisplayTarget, VcpCommand)>,
+    /// OLED care reminder to include in the next state update
+    pending_oled_care_reminder: Mutex<Option<OledCareReminder>>,
+    /// Excluded processes currently running
+    ///
+    /// While any of them runs, HDR stays off even if monitored applications start.
+    running_exclusions: HashSet<AppIdentifier>,
+    /// Color mode to switch back to once no excluded process runs
+    ///
+    /// Set when an excluded process turned HDR or wide color gamut off, or kept a
+    /// monitored application from turning it on.
+    exclusion_hold: Option<ColorMode>,
+    /// Whether HDR toggles are simulated and logged instead of made
+    dry_run: bool,
+    /// First-run setup in progress, `None` once it was finished or skipped
+    onboarding: Option<Onboarding>,
+    /// Time source for debounce windows, holds, snoozes and session limits
+    clock: Arc<dyn Clock>,
+    /// Background agent this controller is a front-end of, see [`Self::front_end_of`]
+    agent: Option<AgentLink>,
 }
 
 impl AppController {
@@ -72,49 +640,22 @@ impl AppController {
         event_receiver: mpsc::Receiver<ProcessEvent>,
         hdr_state_receiver: mpsc::Receiver<HdrStateEvent>,
         gui_state_sender: mpsc::SyncSender<AppState>,
-        watch_state: Arc<RwLock<WatchState>>,
+        watch_state: Arc<SharedWatchState>,
     ) -> Result<Self> {
-        use tracing::info;
-
         let hdr_controller = HdrController::new().map_err(|e| {
             use tracing::error;
             error!("Failed to initialize HDR controller: {e}");
             EasyHdrError::HdrControlFailed(Box::new(e))
         })?;
 
-        let initial_hdr_state = Self::detect_current_hdr_state(&hdr_controller);
-        info!("Detected initial HDR state: {}", initial_hdr_state);
-
-        let startup_time = Instant::now();
-
-        // Check if HDR displays are available at startup
-        let hdr_displays_available = hdr_controller
-            .get_display_cache()
-            .iter()
-            .any(|d| d.supports_hdr);
-
-        // If no HDR displays found at startup, schedule a warning notification
-        let show_startup_warning = !hdr_displays_available;
-
-        let controller = Self {
-            config: Arc::new(RwLock::new(config)),
+        Ok(Self::with_hdr_control(
             hdr_controller,
-            active_process_count: AtomicUsize::new(0),
-            current_hdr_state: AtomicBool::new(initial_hdr_state),
-            event_receiver: Some(event_receiver),
-            hdr_state_receiver: Some(hdr_state_receiver),
+            config,
+            event_receiver,
+            hdr_state_receiver,
             gui_state_sender,
-            startup_time,
-            last_toggle_time_nanos: Arc::new(AtomicU64::new(0)),
             watch_state,
-            hdr_displays_available: AtomicBool::new(hdr_displays_available),
-            pending_hdr_available_notification: AtomicBool::new(false),
-            pending_no_hdr_warning: AtomicBool::new(show_startup_warning),
-        };
-
-        controller.update_process_monitor_watch_list();
-
-        Ok(controller)
+        ))
     }
 
     /// Create a new application controller with mock HDR controller
@@ -126,7 +667,7 @@ impl AppController {
         event_receiver: mpsc::Receiver<ProcessEvent>,
         hdr_state_receiver: mpsc::Receiver<HdrStateEvent>,
         gui_state_sender: mpsc::SyncSender<AppState>,
-        watch_state: Arc<RwLock<WatchState>>,
+        watch_state: Arc<SharedWatchState>,
     ) -> Result<Self> {
         use tracing::info;
 
@@ -136,22 +677,101 @@ impl AppController {
             EasyHdrError::HdrControlFailed(Box::new(e))
         })?;
 
-        let initial_hdr_state = Self::detect_current_hdr_state(&hdr_controller);
+        // Don't show warning for mock controller (test mode)
+        let controller = Self::from_parts(
+            hdr_controller,
+            config,
+            event_receiver,
+            hdr_state_receiver,
+            gui_state_sender,
+            watch_state,
+            false,
+        );
         info!("AppController initialized with mock HDR controller (test mode)");
 
+        controller.update_process_monitor_watch_list();
+
+        Ok(controller)
+    }
+}
+
+impl<H: HdrControl> AppController<H> {
+    /// Create an application controller that drives HDR through the given backend
+    ///
+    /// Detects the initial HDR state from the backend. Use [`MockHdrControl`] to run the
+    /// controller without touching real displays.
+    ///
+    /// [`MockHdrControl`]: crate::hdr::MockHdrControl
+    pub fn with_hdr_control(
+        hdr_control: H,
+        config: AppConfig,
+        event_receiver: mpsc::Receiver<ProcessEvent>,
+        hdr_state_receiver: mpsc::Receiver<HdrStateEvent>,
+        gui_state_sender: mpsc::SyncSender<AppState>,
+        watch_state: Arc<SharedWatchState>,
+    ) -> Self {
+        let controller = Self::from_parts(
+            hdr_control,
+            config,
+            event_receiver,
+            hdr_state_receiver,
+            gui_state_sender,
+            watch_state,
+            true,
+        );
+
+        controller.update_process_monitor_watch_list();
+        controller.remember_displays();
+        controller.reapply_auto_hdr_modes();
+
+        controller
+    }
+
+    /// Build the controller state without touching configuration or displays
+    ///
+    /// `warn_without_hdr` schedules the startup warning when no HDR display is found.
+    fn from_parts(
+        mut hdr_controller: H,
+        config: AppConfig,
+        event_receiver: mpsc::Receiver<ProcessEvent>,
+        hdr_state_receiver: mpsc::Receiver<HdrStateEvent>,
+        gui_state_sender: mpsc::SyncSender<AppState>,
+        watch_state: Arc<SharedWatchState>,
+        warn_without_hdr: bool,
+    ) -> Self {
+        use tracing::info;
+
+        let initial_hdr_state = hdr_controller.detect_current_hdr_state();
+        info!("Detected initial HDR state: {}", initial_hdr_state);
+        let hdr_on_displays = Self::read_hdr_on_displays(&hdr_controller);
+
         let startup_time = Instant::now();
 
-        // Mock controller has empty display cache
-        let hdr_displays_available = hdr_controller
-            .get_display_cache()
-            .iter()
-            .any(|d| d.supports_hdr);
+        // Check if HDR displays are available at startup
+        let hdr_displays_available = hdr_controller.displays().iter().any(|d| d.supports_hdr);
+
+        // If no HDR displays found at startup, schedule a warning notification
+        let show_startup_warning = warn_without_hdr && !hdr_displays_available;
+
+        let dry_run = config.preferences.dry_run;
+        if dry_run {
+            info!("Dry run: HDR changes are logged, not made");
+        }
+        hdr_controller.set_dry_run(dry_run);
+        let onboarding = (!config.preferences.onboarding_completed).then(Onboarding::new);
+        let capture_tone_mapping =
+            CaptureToneMappingController::new(hdr_controller.windows_version());
+        let discord = Self::discord_presence_for(&config.preferences, None);
 
-        let controller = Self {
+        Self {
             config: Arc::new(RwLock::new(config)),
             hdr_controller,
             active_process_count: AtomicUsize::new(0),
             current_hdr_state: AtomicBool::new(initial_hdr_state),
+            hdr_on_displays,
+            external_hold: None,
+            power_status: PowerStatus::current(),
+            hdr_enabled_by_app: false,
             event_receiver: Some(event_receiver),
             hdr_state_receiver: Some(hdr_state_receiver),
             gui_state_sender,
@@ -160,18 +780,119 @@ impl AppController {
             watch_state,
             hdr_displays_available: AtomicBool::new(hdr_displays_available),
             pending_hdr_available_notification: AtomicBool::new(false),
-            // Don't show warning for mock controller (test mode)
-            pending_no_hdr_warning: AtomicBool::new(false),
-        };
+            pending_no_hdr_warning: AtomicBool::new(show_startup_warning),
+            pending_auto_toggle_notification: AtomicBool::new(false),
+            auto_toggle_app: None,
+            snoozed_apps: HashMap::new(),
+            hdr_override: None,
+            displays_on: true,
+            deferred_hdr_toggle: None,
+            deferred_toggle_app: None,
+            display_reset_at: None,
+            resume_pending: false,
+            suspected_hdr_drift: None,
+            suspected_session_drift: None,
+            reported_elevated_apps: HashMap::new(),
+            pending_hdr_disable: None,
+            display_mode: DisplayModeController::new(),
+            session_display_mode: None,
+            capture_tone_mapping,
+            session_capture_tone_mapping: None,
+            session_display_groups: Vec::new(),
+            group_hdr_displays: None,
+            night_light: NightLightController::new(),
+            wcg_session: false,
+            wcg_displays: Vec::new(),
+            discord,
+            sessions: SessionTracker::new(),
+            session_history: SessionHistory::load(),
+            session_history_dirty: false,
+            toggle_history: ToggleHistory::load(),
+            pending_weekly_summary: Mutex::new(None),
+            hdr_toggle_error: None,
+            hdr_toggle_failures: Vec::new(),
+            pending_hdr_toggle_error_notification: AtomicBool::new(false),
+            external_hdr_changes: Vec::new(),
+            hdr_conflict_since: None,
+            pending_hdr_conflict_notification: AtomicBool::new(false),
+            offline_volumes: OfflineVolumes::default(),
+            volumes_checked: false,
+            volume_check: None,
+            icon_loader: None,
+            manual_session: None,
+            hdr_session: None,
+            pending_session_limit_warning: Mutex::new(None),
+            oled_hdr_since: None,
+            pixel_refresh_due: Vec::new(),
+            pending_oled_care_reminder: Mutex::new(None),
+            running_exclusions: HashSet::new(),
+            exclusion_hold: None,
+            dry_run,
+            onboarding,
+            clock: Arc::new(SystemClock),
+            agent: None,
+        }
+    }
 
-        controller.update_process_monitor_watch_list();
+    /// Measure time against the given clock instead of the system clock
+    ///
+    /// Lets tests step through debounce windows and holds with a [`ManualClock`] instead of
+    /// sleeping.
+    ///
+    /// [`ManualClock`]: crate::controller::clock::ManualClock
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.startup_time = clock.now();
+        self.clock = Arc::new(clock);
+        self
+    }
 
-        Ok(controller)
+    /// Make this controller a front-end of the background agent behind `link`
+    ///
+    /// The controller keeps editing the configuration and reading the displays, but
+    /// forwards actions that change HDR to the agent, asks it to re-read the
+    /// configuration after every save and sends no state updates of its own; the window
+    /// shows the agent's, see
+    /// [`crate::controller::agent::forward_state_updates`]. Its event loop is not
+    /// started, and first-run setup and the Discord Rich Presence are left to a window
+    /// running the controller itself and to the agent.
+    #[must_use]
+    pub fn front_end_of(mut self, link: AgentLink) -> Self {
+        self.onboarding = None;
+        self.discord = None;
+        self.agent = Some(link);
+        self
+    }
+
+    /// Whether this controller is a front-end of the background agent
+    pub fn is_front_end(&self) -> bool {
+        self.agent.is_some()
+    }
+
+    /// Run `command` on the background agent if this controller is its front-end
+    ///
+    /// Returns `None` if the controller runs the action itself.
+    fn forward(&self, command: AgentCommand) -> Option<Result<()>> {
+        use tracing::debug;
+
+        let agent = self.agent.as_ref()?;
+        Some(agent.run(command).map(|message| {
+            debug!("Background agent: {}", message);
+        }))
     }
 
-    /// Detect the current HDR state from the system by checking all HDR-capable displays.
-    fn detect_current_hdr_state(hdr_controller: &HdrController) -> bool {
-        hdr_controller.detect_current_hdr_state()
+    /// [`Self::forward`] for actions that cannot fail, logging an error instead
+    fn forward_logged(&self, command: AgentCommand) -> bool {
+        use tracing::error;
+
+        match self.forward(command) {
+            Some(Err(e)) => {
+                error!("Background agent failed to run the action: {}", e);
+                true
+            }
+            Some(Ok(())) => true,
+            None => false,
+        }
     }
 
     /// Take ownership of the event receiver if it hasn't been taken yet.
@@ -200,7 +921,6 @@ impl AppController {
         G: FnMut(HdrStateEvent),
     {
         use std::sync::mpsc::{RecvTimeoutError, TryRecvError};
-        use std::time::Duration;
         use tracing::warn;
 
         match event_receiver.recv_timeout(Duration::from_millis(100)) {
@@ -229,7 +949,6 @@ impl AppController {
     /// Run the main event loop to receive process and HDR state events.
     pub fn run(&mut self) {
         use std::sync::mpsc::{RecvTimeoutError, TryRecvError};
-        use std::time::Duration;
         use tracing::{info, warn};
 
         let Some(event_receiver) = self.take_event_receiver() else {
@@ -243,6 +962,8 @@ impl AppController {
         };
 
         info!("Entering main event loop (process events + HDR state events)");
+        self.refresh_volume_availability();
+        let mut next_reconcile = Instant::now() + RECONCILE_INTERVAL;
         loop {
             match event_receiver.recv_timeout(Duration::from_millis(100)) {
                 Ok(event) => {
@@ -267,14 +988,43 @@ impl AppController {
                     }
                 }
             }
+
+            self.process_tick(&mut next_reconcile);
         }
 
         info!("Main event loop exited");
     }
 
+    /// Timed work of one event loop iteration, after its events were handled
+    ///
+    /// Runs deadlines and background checks on every iteration, and reconciles state and
+    /// saves metrics once `next_reconcile` has passed.
+    fn process_tick(&mut self, next_reconcile: &mut Instant) {
+        self.process_pending_hdr_disable();
+        self.process_manual_session_deadline();
+        self.process_onboarding();
+        self.process_session_limit();
+        self.process_oled_care();
+        self.process_app_snoozes();
+        self.process_volume_check();
+        self.process_icon_loads();
+        self.update_session_history();
+
+        if Instant::now() >= *next_reconcile {
+            self.reconcile_state();
+            self.check_weekly_summary();
+            self.recheck_offline_volumes();
+            Self::save_metrics();
+            *next_reconcile = Instant::now() + RECONCILE_INTERVAL;
+        }
+    }
+
     /// Spawn the event loop in a background thread. Only locks controller while handling individual events,
     /// preventing GUI callbacks from being blocked.
-    pub fn spawn_event_loop(controller: Arc<Mutex<AppController>>) -> std::thread::JoinHandle<()> {
+    pub fn spawn_event_loop(controller: Arc<Mutex<Self>>) -> std::thread::JoinHandle<()>
+    where
+        H: 'static,
+    {
         let (event_receiver, hdr_state_receiver) = {
             let mut controller_guard = controller.lock();
             (
@@ -286,44 +1036,79 @@ impl AppController {
                     .expect("AppController HDR state receiver already taken"),
             )
         };
+        controller.lock().refresh_volume_availability();
+
+        std::thread::Builder::new()
+            .name("app-controller".to_string())
+            .spawn(move || {
+                use tracing::info;
+
+                info!("Entering main event loop (process events + HDR state events)");
+                // Restarted by the watchdog after a panic; the controller keeps its state
+                watchdog::supervise("app-controller", || {
+                    let mut next_reconcile = Instant::now() + RECONCILE_INTERVAL;
+                    while Self::process_event_loop_iteration(
+                        &event_receiver,
+                        &hdr_state_receiver,
+                        &mut |event| {
+                            let mut controller_guard = controller.lock();
+                            controller_guard.handle_process_event(event);
+                        },
+                        &mut |event| {
+                            let mut controller_guard = controller.lock();
+                            controller_guard.handle_hdr_state_event(event);
+                        },
+                    ) {
+                        controller.lock().process_tick(&mut next_reconcile);
+                    }
+                });
+                info!("Main event loop exited");
+            })
+            .expect("Failed to spawn the AppController event loop thread")
+    }
 
-        std::thread::spawn(move || {
-            use tracing::info;
-
-            info!("Entering main event loop (process events + HDR state events)");
-            while Self::process_event_loop_iteration(
-                &event_receiver,
-                &hdr_state_receiver,
-                &mut |event| {
-                    let mut controller_guard = controller.lock();
-                    controller_guard.handle_process_event(event);
-                },
-                &mut |event| {
-                    let mut controller_guard = controller.lock();
-                    controller_guard.handle_hdr_state_event(event);
-                },
-            ) {}
-            info!("Main event loop exited");
-        })
+    /// When a started application was detected, for its activation latency
+    ///
+    /// An application found by a process scan counts from the start of that scan, as the
+    /// scan itself delays the event. Others, such as UWP apps reported by the app watcher,
+    /// count from now.
+    fn detection_time(&self, app_id: &AppIdentifier) -> Instant {
+        let results = self.watch_state.results();
+        results
+            .scan_started
+            .filter(|_| {
+                results
+                    .running_apps
+                    .as_ref()
+                    .is_some_and(|running| running.contains(app_id))
+            })
+            .unwrap_or_else(Instant::now)
     }
 
     /// Handle a process event to automatically toggle HDR.
     ///
-    /// Enables HDR when first monitored app starts, disables when last one stops.
-    /// Uses 500ms debouncing to prevent rapid toggling during app restarts.
+    /// Enables HDR when first monitored app starts. When the last one stops, the disable
+    /// is scheduled after the debounce window and cancelled if a monitored app returns.
+    #[expect(
+        clippy::too_many_lines,
+        reason = "Start and stop handling share session bookkeeping that would be scattered if split"
+    )]
     fn handle_process_event(&mut self, event: ProcessEvent) {
         use tracing::{debug, error, info};
 
         match event {
             ProcessEvent::Started(app_id) => {
                 debug!("Process started event: {:?}", app_id);
+                let detected_at = self.detection_time(&app_id);
 
-                let normalized_id = Self::normalize_app_identifier(&app_id);
-                let state = self.watch_state.read();
-                let is_monitored = state.identifiers.contains(&normalized_id);
-                drop(state);
+                let normalized = Self::normalize_app_identifier(&app_id);
+                if self.watch_state.load().is_excluded(&normalized) {
+                    self.excluded_process_started(normalized);
+                    return;
+                }
+                let watched_id = self.watch_state.load().watched_identifier(&normalized);
 
-                if is_monitored {
+                if let Some(normalized_id) = watched_id {
                     match &app_id {
                         AppIdentifier::Win32(process_name) => {
                             info!("Monitored Win32 application started: {}", process_name);
@@ -331,18 +1116,99 @@ impl AppController {
                         AppIdentifier::Uwp(package_family_name) => {
                             info!("Monitored UWP application started: {}", package_family_name);
                         }
+                        AppIdentifier::UwpApplication(aumid) => {
+                            info!("Monitored UWP application started: {}", aumid);
+                        }
+                        AppIdentifier::Folder(path) => {
+                            info!("Application from monitored folder started: {}", path);
+                        }
                     }
 
                     let prev_count = self.active_process_count.fetch_add(1, Ordering::SeqCst);
                     debug!("Active process count: {} -> {}", prev_count, prev_count + 1);
 
-                    if prev_count == 0 && !self.current_hdr_state.load(Ordering::SeqCst) {
-                        info!("First monitored application started, enabling HDR");
-                        if let Err(e) = self.toggle_hdr(true) {
-                            error!("Failed to enable HDR: {}", e);
+                    if prev_count == 0 {
+                        self.session_display_mode = self.display_mode_for(&normalized_id);
+                        self.session_capture_tone_mapping =
+                            self.capture_tone_mapping_for(&normalized_id);
+                    }
+                    let display_group = self.display_group_for(&normalized_id);
+                    self.session_display_groups
+                        .push((normalized_id.clone(), display_group));
+
+                    if self.pending_hdr_disable.take().is_some() {
+                        info!(
+                            "Monitored application returned within the debounce window, keeping HDR on"
+                        );
+                    }
+
+                    let app_id = self.app_id_for(&normalized_id);
+                    if let Some(hold) = &self.external_hold
+                        && app_id.is_none_or(|id| !hold.running_apps.contains(&id))
+                    {
+                        info!(
+                            "Another monitored application started, resuming automatic HDR management"
+                        );
+                        self.external_hold = None;
+                    }
+
+                    // A wide color gamut session belongs to the application that started it
+                    let wants_wcg = self.color_modes_supported()
+                        && self.color_mode_for(&normalized_id) == Some(ColorMode::Wcg);
+                    if (prev_count == 0 || !(self.wcg_session || wants_wcg))
+                        && self.session_lacks_hdr()
+                    {
+                        if self.hdr_override == Some(HdrOverride::TurnOff) {
+                            info!("Monitored application started, but HDR was turned off manually");
+                        } else if self.held_hdr_state() == Some(false) {
+                            info!(
+                                "Monitored application started, but HDR was turned off outside EasyHDR"
+                            );
+                        } else if self.battery_blocks_hdr() && !wants_wcg {
+                            info!(
+                                "Monitored application started, but HDR is kept off on battery power"
+                            );
+                        } else if !self.running_exclusions.is_empty() {
+                            info!(
+                                "Monitored application started, but an excluded process keeps HDR off"
+                            );
+                            self.exclusion_hold.get_or_insert(if wants_wcg {
+                                ColorMode::Wcg
+                            } else {
+                                ColorMode::Hdr
+                            });
+                        } else if wants_wcg {
+                            info!("Monitored application started, switching to wide color gamut");
+                            self.set_wcg_session(true);
+                        } else {
+                            info!("Monitored application started, enabling HDR");
+                            let name = self
+                                .app_name_for(&normalized_id)
+                                .unwrap_or_else(|| normalized_id.to_string());
+                            match self.toggle_hdr(true, ToggleTrigger::AppStarted(name.clone())) {
+                                Ok(ToggleOutcome::Applied) => {
+                                    if !self.dry_run {
+                                        metrics::get_metrics()
+                                            .record_activation(&name, detected_at.elapsed());
+                                    }
+                                    self.pending_auto_toggle_notification
+                                        .store(true, Ordering::SeqCst);
+                                    self.auto_toggle_app = app_id;
+                                }
+                                // Notified once the displays wake; the activation time
+                                // would measure their sleep, so it isn't recorded
+                                Ok(ToggleOutcome::Deferred) => self.deferred_toggle_app = app_id,
+                                Ok(ToggleOutcome::Skipped) => {}
+                                Err(e) => error!("Failed to enable HDR: {}", e),
+                            }
                         }
                     } else {
-                        debug!("HDR already enabled or other processes running, skipping toggle");
+                        debug!("HDR already on for the running applications, skipping toggle");
+                    }
+
+                    if let Some(id) = app_id {
+                        self.sessions
+                            .start(id, self.current_hdr_state.load(Ordering::SeqCst));
                     }
 
                     self.send_state_update();
@@ -352,12 +1218,14 @@ impl AppController {
             ProcessEvent::Stopped(app_id) => {
                 debug!("Process stopped event: {:?}", app_id);
 
-                let normalized_id = Self::normalize_app_identifier(&app_id);
-                let state = self.watch_state.read();
-                let is_monitored = state.identifiers.contains(&normalized_id);
-                drop(state);
+                let normalized = Self::normalize_app_identifier(&app_id);
+                if self.running_exclusions.contains(&normalized) {
+                    self.excluded_process_stopped(&normalized);
+                    return;
+                }
+                let watched_id = self.watch_state.load().watched_identifier(&normalized);
 
-                if is_monitored {
+                if let Some(normalized_id) = watched_id {
                     match &app_id {
                         AppIdentifier::Win32(process_name) => {
                             info!("Monitored Win32 application stopped: {}", process_name);
@@ -365,6 +1233,12 @@ impl AppController {
                         AppIdentifier::Uwp(package_family_name) => {
                             info!("Monitored UWP application stopped: {}", package_family_name);
                         }
+                        AppIdentifier::UwpApplication(aumid) => {
+                            info!("Monitored UWP application stopped: {}", aumid);
+                        }
+                        AppIdentifier::Folder(path) => {
+                            info!("Last application from monitored folder stopped: {}", path);
+                        }
                     }
 
                     // Rust 1.95 renamed this to `try_update`, but EasyHDR's MSRV is 1.93.
@@ -381,20 +1255,64 @@ impl AppController {
                         prev_count.saturating_sub(1)
                     );
 
-                    let last_toggle_nanos = self.last_toggle_time_nanos.load(Ordering::Relaxed);
-                    let last_toggle =
-                        self.startup_time + std::time::Duration::from_nanos(last_toggle_nanos);
-                    if last_toggle.elapsed() < std::time::Duration::from_millis(500) {
+                    if prev_count <= 1 && self.hdr_override == Some(HdrOverride::TurnOff) {
+                        debug!("No monitored applications running, clearing manual HDR off");
+                        self.hdr_override = None;
+                    }
+                    if let Some(index) = self
+                        .session_display_groups
+                        .iter()
+                        .position(|(id, _)| *id == normalized_id)
+                    {
+                        self.session_display_groups.remove(index);
+                    }
+                    if prev_count <= 1 {
+                        self.session_display_mode = None;
+                        self.session_capture_tone_mapping = None;
+                        self.session_display_groups.clear();
+                    }
+                    if prev_count <= 1 && self.wcg_session {
+                        info!("Last monitored application stopped, leaving wide color gamut");
+                        self.set_wcg_session(false);
+                    }
+                    if prev_count <= 1
+                        && self.manual_session.is_none()
+                        && self.exclusion_hold.take().is_some()
+                    {
+                        debug!(
+                            "Last monitored application stopped, HDR stays off after excluded processes exit"
+                        );
+                    }
+
+                    if let Some(id) = self.app_id_for(&normalized_id)
+                        && let Some(session) = self.sessions.stop(id)
+                    {
                         debug!(
-                            "Debouncing: last toggle was less than 500ms ago, skipping HDR disable"
+                            "Session of {} ended after {}s ({}s with HDR)",
+                            id, session.duration_secs, session.hdr_secs
                         );
-                        return;
+                        self.session_history.record(session);
+                        self.session_history_dirty = true;
                     }
 
-                    if prev_count == 1 && self.current_hdr_state.load(Ordering::SeqCst) {
-                        info!("Last monitored application stopped, disabling HDR");
-                        if let Err(e) = self.toggle_hdr(false) {
-                            error!("Failed to disable HDR: {}", e);
+                    if prev_count == 1 && self.manual_session.is_some() {
+                        info!(
+                            "Last monitored application stopped, keeping HDR on for the manual session"
+                        );
+                    } else if prev_count == 1 && self.current_hdr_state.load(Ordering::SeqCst) {
+                        if self.hdr_override == Some(HdrOverride::KeepOn) {
+                            info!(
+                                "Last monitored application stopped, keeping HDR on as requested"
+                            );
+                            self.hdr_override = None;
+                        } else {
+                            let debounce = self.hdr_disable_debounce_for(&normalized_id);
+                            info!(
+                                "Last monitored application stopped, disabling HDR in {:?} unless it returns",
+                                debounce
+                            );
+                            self.pending_hdr_disable = Some(self.clock.now() + debounce);
+                            self.auto_toggle_app = self.app_id_for(&normalized_id);
                         }
                     } else {
                         debug!("Other processes still running or HDR already off, skipping toggle");
@@ -406,22 +1324,154 @@ impl AppController {
         }
     }
 
+    /// Turn HDR or wide color gamut off when the first excluded process starts.
+    ///
+    /// Exclusions take precedence over monitored applications: the mode that was on is
+    /// remembered in `exclusion_hold` and restored by [`Self::excluded_process_stopped`].
+    fn excluded_process_started(&mut self, app_id: AppIdentifier) {
+        use tracing::{error, info};
+
+        info!("Excluded process started: {}", app_id);
+        let first = self.running_exclusions.is_empty();
+        if !self.running_exclusions.insert(app_id) || !first {
+            self.send_state_update();
+            return;
+        }
+
+        if self.pending_hdr_disable.take().is_some() {
+            info!("Excluded process started during the debounce window, disabling HDR now");
+        }
+        if self.wcg_session {
+            info!("Excluded process started, leaving wide color gamut");
+            self.set_wcg_session(false);
+            self.exclusion_hold = Some(ColorMode::Wcg);
+        } else if self.current_hdr_state.load(Ordering::SeqCst) {
+            info!("Excluded process started, disabling HDR");
+            match self.toggle_hdr(false, ToggleTrigger::ExclusionStarted) {
+                Ok(_) => self.exclusion_hold = Some(ColorMode::Hdr),
+                Err(e) => error!("Failed to disable HDR: {}", e),
+            }
+        }
+
+        self.send_state_update();
+    }
+
+    /// Restore the mode held off by excluded processes once the last of them stops.
+    ///
+    /// Only restored while a monitored application or a manual session still wants HDR,
+    /// and not if HDR was turned off manually in the meantime.
+    fn excluded_process_stopped(&mut self, app_id: &AppIdentifier) {
+        use tracing::{debug, error, info};
+
+        info!("Excluded process stopped: {}", app_id);
+        if !self.running_exclusions.remove(app_id) || !self.running_exclusions.is_empty() {
+            self.send_state_update();
+            return;
+        }
+
+        let wanted =
+            self.active_process_count.load(Ordering::SeqCst) > 0 || self.manual_session.is_some();
+        match self.exclusion_hold.take() {
+            Some(_) if !wanted => {
+                debug!("No excluded process running, nothing keeps HDR on");
+            }
+            Some(_) if self.hdr_override == Some(HdrOverride::TurnOff) => {
+                info!("No excluded process running, but HDR was turned off manually");
+            }
+            Some(_) if self.held_hdr_state() == Some(false) => {
+                info!("No excluded process running, but HDR was turned off outside EasyHDR");
+            }
+            Some(ColorMode::Hdr) if self.battery_blocks_hdr() => {
+                info!("No excluded process running, but HDR is kept off on battery power");
+            }
+            Some(ColorMode::Wcg) => {
+                info!("No excluded process running, switching back to wide color gamut");
+                self.set_wcg_session(true);
+            }
+            Some(_) if !self.current_hdr_state.load(Ordering::SeqCst) => {
+                info!("No excluded process running, enabling HDR again");
+                match self.toggle_hdr(true, ToggleTrigger::ExclusionsExited) {
+                    Ok(ToggleOutcome::Applied) => self
+                        .pending_auto_toggle_notification
+                        .store(true, Ordering::SeqCst),
+                    Ok(ToggleOutcome::Deferred | ToggleOutcome::Skipped) => {}
+                    Err(e) => error!("Failed to enable HDR: {}", e),
+                }
+            }
+            Some(_) | None => {}
+        }
+
+        self.send_state_update();
+    }
+
     /// Handle an HDR state event from external Windows settings changes.
     ///
     /// Updates internal state and GUI without calling `toggle_hdr()` since the change already occurred.
+    #[expect(
+        clippy::too_many_lines,
+        reason = "One match arm per HDR state event, each updating sessions and the GUI"
+    )]
     fn handle_hdr_state_event(&mut self, event: HdrStateEvent) {
         use tracing::{debug, info, warn};
 
         match event {
-            HdrStateEvent::Enabled => {
-                info!("HDR was enabled externally (via Windows settings)");
-                self.current_hdr_state.store(true, Ordering::SeqCst);
-                debug!("Updated internal HDR state to: true");
-            }
-            HdrStateEvent::Disabled => {
-                info!("HDR was disabled externally (via Windows settings)");
-                self.current_hdr_state.store(false, Ordering::SeqCst);
-                debug!("Updated internal HDR state to: false");
+            HdrStateEvent::DisplayHdrChanged {
+                display: changed,
+                previous,
+                enabled,
+            } => {
+                self.note_display_hdr(&changed, enabled);
+                // Also changed outside EasyHDR or on a single display
+                self.hdr_controller.refresh_capabilities();
+                let any_enabled = !self.hdr_on_displays.is_empty();
+                let was_enabled = self.current_hdr_state.load(Ordering::SeqCst);
+
+                if any_enabled && !was_enabled {
+                    info!(
+                        "HDR was enabled externally on {} (via Windows settings)",
+                        changed
+                    );
+                    self.current_hdr_state.store(true, Ordering::SeqCst);
+                    debug!("Updated internal HDR state to: true");
+                    self.hold_external_hdr_change(true);
+                    self.note_external_hdr_change();
+                } else if !any_enabled && was_enabled && self.recently_reset() {
+                    info!(
+                        "HDR was turned off on {} right after a resume or display reset",
+                        changed
+                    );
+                    self.current_hdr_state.store(false, Ordering::SeqCst);
+                    self.hdr_enabled_by_app = false;
+                    crash_guard::disarm();
+                    self.reassert_hdr_state();
+                } else if !any_enabled && was_enabled {
+                    info!(
+                        "HDR was disabled externally on {} (via Windows settings)",
+                        changed
+                    );
+                    self.current_hdr_state.store(false, Ordering::SeqCst);
+                    self.hdr_enabled_by_app = false;
+                    crash_guard::disarm();
+                    if self.end_manual_session_tracking() {
+                        info!("HDR was turned off outside EasyHDR, ended the manual HDR session");
+                    }
+                    debug!("Updated internal HDR state to: false");
+                    self.hold_external_hdr_change(false);
+                    self.note_external_hdr_change();
+                } else if !enabled && any_enabled {
+                    info!(
+                        "HDR was turned off on {} only, still on for {} other display(s)",
+                        changed,
+                        self.hdr_on_displays.len()
+                    );
+                } else {
+                    debug!(
+                        "HDR turned {} on {} (was {}), matching the internal HDR state",
+                        if enabled { "on" } else { "off" },
+                        changed,
+                        if previous { "on" } else { "off" }
+                    );
+                }
             }
             HdrStateEvent::DisplayConfigurationChanged { hdr_capable_count } => {
                 info!(
@@ -433,6 +1483,13 @@ impl AppController {
                 if let Err(e) = self.refresh_displays() {
                     warn!("Failed to refresh display cache: {}", e);
                 }
+                let connected = self.hdr_controller.displays();
+                self.hdr_on_displays.retain(|known| {
+                    connected.iter().any(|display| {
+                        display.adapter_id == known.adapter_id
+                            && display.target_id == known.target_id
+                    })
+                });
 
                 // Track state transition for notification
                 let was_unavailable = !self.hdr_displays_available.load(Ordering::SeqCst);
@@ -445,6 +1502,11 @@ impl AppController {
                     info!("HDR displays now available - scheduling notification");
                     self.pending_hdr_available_notification
                         .store(true, Ordering::SeqCst);
+
+                    // Displays vanishing and coming back, e.g. after a driver reset, can
+                    // turn HDR off without the user asking
+                    self.display_reset_at = Some(self.clock.now());
+                    self.drop_hold_from_display_reset();
                 }
 
                 // If HDR displays are now available and we have active monitored processes,
@@ -452,12 +1514,19 @@ impl AppController {
                 let active_count = self.active_process_count.load(Ordering::SeqCst);
                 let current_hdr = self.current_hdr_state.load(Ordering::SeqCst);
 
-                if now_available && active_count > 0 && !current_hdr {
+                if now_available
+                    && active_count > 0
+                    && !current_hdr
+                    && !self.wcg_session
+                    && self.hdr_override != Some(HdrOverride::TurnOff)
+                    && self.held_hdr_state().is_none()
+                    && !self.battery_blocks_hdr()
+                {
                     info!(
                         "HDR displays now available with {} active monitored process(es), enabling HDR",
                         active_count
                     );
-                    if let Err(e) = self.toggle_hdr(true) {
+                    if let Err(e) = self.toggle_hdr(true, ToggleTrigger::DisplaysChanged) {
                         warn!(
                             "Failed to enable HDR after display configuration change: {}",
                             e
@@ -465,942 +1534,7179 @@ impl AppController {
                     }
                 }
             }
-        }
-
-        self.send_state_update();
-    }
-
-    /// Toggle HDR state globally on all displays and update debouncing timestamp.
-    fn toggle_hdr(&mut self, enable: bool) -> Result<()> {
-        use tracing::{info, warn};
-
-        info!("Toggling HDR: {}", if enable { "ON" } else { "OFF" });
-
-        let results = self.hdr_controller.set_hdr_global(enable).map_err(|e| {
-            use tracing::error;
-            error!("Failed to set HDR state globally: {e}");
-            EasyHdrError::HdrControlFailed(Box::new(e))
-        })?;
-
-        for (target, result) in results {
-            match result {
-                Ok(()) => {
+            HdrStateEvent::DisplayPowerChanged { displays_on } => {
+                self.displays_on = displays_on;
+
+                // Nothing to manage while the displays are off, so stop polling processes
+                // until they come back on instead of reacting to stale state
+                if self
+                    .watch_state
+                    .load()
+                    .suspension
+                    .set_suspended(!displays_on)
+                {
                     info!(
-                        "HDR {} for display (adapter={:#x}:{:#x}, target={})",
-                        if enable { "enabled" } else { "disabled" },
-                        target.adapter_id.LowPart,
-                        target.adapter_id.HighPart,
-                        target.target_id
+                        "Displays turned {}, {} process polling",
+                        if displays_on { "on" } else { "off" },
+                        if displays_on {
+                            "resuming"
+                        } else {
+                            "suspending"
+                        }
                     );
                 }
-                Err(e) => {
-                    warn!(
-                        "Failed to toggle HDR for display (adapter={:#x}:{:#x}, target={}): {}",
-                        target.adapter_id.LowPart, target.adapter_id.HighPart, target.target_id, e
-                    );
+
+                if displays_on {
+                    self.apply_deferred_hdr_toggle();
+                    if std::mem::take(&mut self.resume_pending) {
+                        self.display_reset_at = Some(self.clock.now());
+                        self.reassert_hdr_state();
+                    }
+                }
+            }
+            HdrStateEvent::VolumesChanged => {
+                debug!("Volumes changed, rechecking network and removable volumes");
+                self.refresh_volume_availability();
+            }
+            HdrStateEvent::SystemResumed | HdrStateEvent::DisplayDriverReset => {
+                self.display_reset_at = Some(self.clock.now());
+                if self.displays_on {
+                    self.reassert_hdr_state();
+                } else {
+                    debug!("Displays are off, rechecking HDR once they turn on");
+                    self.resume_pending = true;
+                }
+            }
+            HdrStateEvent::PowerStatusChanged { status } => {
+                let was_blocked = self.battery_blocks_hdr();
+                self.power_status = status;
+                if was_blocked && !self.battery_blocks_hdr() {
+                    self.enable_hdr_after_battery_hold();
                 }
             }
         }
 
-        self.current_hdr_state.store(enable, Ordering::SeqCst);
-
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "Elapsed nanos will not exceed u64::MAX within application lifetime"
-        )]
-        let elapsed_nanos = self.startup_time.elapsed().as_nanos() as u64;
-        self.last_toggle_time_nanos
-            .store(elapsed_nanos, Ordering::Relaxed);
-
-        Ok(())
+        self.send_state_update();
     }
 
-    /// Send current state update to GUI.
-    fn send_state_update(&self) {
-        use tracing::{debug, warn};
-
-        let config = self.config.read();
-        let active_apps: Vec<String> = config
-            .monitored_apps
-            .iter()
-            .filter(|app| app.is_enabled())
-            .map(|app| app.display_name().to_string())
-            .collect();
-        drop(config);
-
-        let hdr_enabled = self.current_hdr_state.load(Ordering::SeqCst);
-
-        // Atomically get and clear the pending notification flags
-        let show_hdr_available_notification = self
-            .pending_hdr_available_notification
-            .swap(false, Ordering::SeqCst);
-        let show_no_hdr_warning = self.pending_no_hdr_warning.swap(false, Ordering::SeqCst);
+    /// Leave HDR as it was changed outside `EasyHDR` if the change contradicts the
+    /// monitored applications
+    ///
+    /// HDR turned off while a monitored application or manual session wants it on, or
+    /// turned on while nothing does, is held for
+    /// [`UserPreferences::external_change_hold_minutes`].
+    fn hold_external_hdr_change(&mut self, enabled: bool) {
+        use tracing::info;
 
-        let state = AppState {
-            hdr_enabled,
-            active_apps,
-            last_event: format!(
-                "Active processes: {}",
-                self.active_process_count.load(Ordering::SeqCst)
-            ),
-            show_hdr_available_notification,
-            show_no_hdr_warning,
+        let wanted =
+            self.active_process_count.load(Ordering::SeqCst) > 0 || self.manual_session.is_some();
+        if enabled == wanted {
+            self.external_hold = None;
+            return;
+        }
+        let Some(hold) = self.config.read().preferences.external_change_hold() else {
+            return;
         };
 
-        debug!(
-            "Sending state update to GUI: HDR enabled = {}, show HDR available notification = {}, show no HDR warning = {}",
-            hdr_enabled, show_hdr_available_notification, show_no_hdr_warning
+        info!(
+            "HDR was turned {} outside EasyHDR, leaving it alone for {} minute(s) or until another monitored application starts",
+            if enabled { "on" } else { "off" },
+            hold.as_secs() / 60
         );
+        let now = self.clock.now();
+        self.external_hold = Some(ExternalHdrHold {
+            enabled,
+            since: now,
+            until: now + hold,
+            running_apps: self.sessions.running_apps().collect(),
+        });
+    }
 
-        if let Err(e) = self.gui_state_sender.send(state) {
-            warn!("Failed to send state update to GUI: {}", e);
-        } else {
-            debug!("State update sent successfully to GUI");
+    /// Count an HDR change made outside `EasyHDR` and pause automatic HDR management when
+    /// they keep coming
+    ///
+    /// More than [`HDR_CONFLICT_MAX_CHANGES`] changes within [`HDR_CONFLICT_WINDOW`] mean
+    /// another program is switching HDR too. Pausing stops the two from flipping HDR back
+    /// and forth until the user resumes automatic management.
+    fn note_external_hdr_change(&mut self) {
+        use tracing::warn;
+
+        let now = self.clock.now();
+        self.external_hdr_changes
+            .retain(|&at| now.duration_since(at) < HDR_CONFLICT_WINDOW);
+        self.external_hdr_changes.push(now);
+
+        if self.hdr_conflict_since.is_none()
+            && self.external_hdr_changes.len() > HDR_CONFLICT_MAX_CHANGES
+        {
+            warn!(
+                "HDR changed {} times outside EasyHDR within {} minute(s), pausing automatic HDR management",
+                self.external_hdr_changes.len(),
+                HDR_CONFLICT_WINDOW.as_secs() / 60
+            );
+            self.hdr_conflict_since = Some(now);
+            self.pending_hdr_conflict_notification
+                .store(true, Ordering::SeqCst);
         }
     }
 
-    /// Send initial state to GUI and populate `ProcessMonitor` watch list.
-    pub fn send_initial_state(&self) {
+    /// HDR state chosen outside `EasyHDR` that automatic toggles must leave alone, `None`
+    /// if there is none or its hold has expired
+    fn held_hdr_state(&mut self) -> Option<bool> {
         use tracing::info;
 
-        info!("Sending initial state update to populate GUI");
-        self.update_process_monitor_watch_list();
+        let hold = self.external_hold.as_ref()?;
+        if self.clock.now() < hold.until {
+            return Some(hold.enabled);
+        }
+        info!(
+            "Hold on the HDR change made outside EasyHDR expired, resuming automatic HDR management"
+        );
+        self.external_hold = None;
+        None
+    }
 
-        self.send_state_update();
+    /// Whether the system resumed or HDR displays came back within [`DISPLAY_RESET_GRACE`]
+    fn recently_reset(&self) -> bool {
+        self.display_reset_at
+            .is_some_and(|at| self.clock.now().saturating_duration_since(at) < DISPLAY_RESET_GRACE)
     }
 
-    /// Add application to config, save to disk, and update `ProcessMonitor` watch list.
-    /// Logs warning and continues with in-memory config if save fails.
-    pub fn add_application(&mut self, app: MonitoredApp) -> Result<()> {
+    /// Forget an HDR off hold that started around the last resume or display reset
+    ///
+    /// The HDR off event can arrive shortly before the displays come back, so it was
+    /// probably Windows reverting HDR rather than the user.
+    fn drop_hold_from_display_reset(&mut self) {
         use tracing::info;
 
-        match &app {
-            MonitoredApp::Win32(win32_app) => {
-                info!(
-                    "Adding Win32 application: {} ({})",
-                    win32_app.display_name, win32_app.process_name
-                );
-            }
-            MonitoredApp::Uwp(uwp_app) => {
-                info!(
-                    "Adding UWP application: {} ({})",
-                    uwp_app.display_name, uwp_app.package_family_name
-                );
-            }
+        let Some(reset_at) = self.display_reset_at else {
+            return;
+        };
+        if let Some(hold) = &self.external_hold
+            && !hold.enabled
+            && hold.since.max(reset_at) - hold.since.min(reset_at) < DISPLAY_RESET_GRACE
+        {
+            info!("HDR was turned off by a display reset, not outside EasyHDR");
+            self.external_hold = None;
         }
+    }
 
-        // Add to config
+    /// Turn HDR back on if a resume or display reset turned it off
+    ///
+    /// Windows sometimes reverts HDR across a sleep or a display driver reset while a
+    /// monitored application keeps running. The actual HDR state is read back and HDR
+    /// turned on again if the running applications still want it. HDR turned on by
+    /// Windows is left alone.
+    fn reassert_hdr_state(&mut self) {
+        use tracing::{debug, info, warn};
+
+        if !self.displays_on || self.dry_run || !self.hdr_displays_available.load(Ordering::SeqCst)
         {
-            let mut config = self.config.write();
-            config.monitored_apps.push(app);
+            return;
         }
+        self.drop_hold_from_display_reset();
 
-        // Save configuration - if this fails, we continue with in-memory config
-        self.save_config_gracefully();
-
-        // Update ProcessMonitor watch list
-        self.update_process_monitor_watch_list();
+        let actual = self.hdr_controller.detect_current_hdr_state();
+        if self.current_hdr_state.swap(actual, Ordering::SeqCst) != actual {
+            debug!(
+                "HDR is {} after the resume or display reset",
+                if actual { "on" } else { "off" }
+            );
+        }
+        self.hdr_on_displays = Self::read_hdr_on_displays(&self.hdr_controller);
+
+        let wanted =
+            self.active_process_count.load(Ordering::SeqCst) > 0 || self.manual_session.is_some();
+        if !self.session_lacks_hdr()
+            || !wanted
+            || self.wcg_session
+            || self.hdr_override == Some(HdrOverride::TurnOff)
+            || self.held_hdr_state() == Some(false)
+            || !self.running_exclusions.is_empty()
+            || self.battery_blocks_hdr()
+        {
+            return;
+        }
 
-        // Send state update to GUI
-        self.send_state_update();
+        info!("HDR was reverted while monitored applications run, turning it back on");
+        if let Err(e) = self.toggle_hdr(true, ToggleTrigger::DisplaysReset) {
+            warn!("Failed to turn HDR back on: {}", e);
+        }
+    }
 
-        info!("Application added successfully");
-        Ok(())
+    /// Whether the battery preference keeps HDR off with the current power status
+    fn battery_blocks_hdr(&self) -> bool {
+        let config = self.config.read();
+        config.preferences.battery_hdr_policy.skips_hdr(
+            self.power_status,
+            config.preferences.battery_hdr_threshold_percent,
+        )
     }
 
-    /// Remove application by UUID, save to disk, and update `ProcessMonitor` watch list.
-    /// Logs warning and continues with in-memory config if save fails.
-    pub fn remove_application(&mut self, id: Uuid) -> Result<()> {
+    /// Turn HDR on for the running monitored applications once the battery no longer
+    /// keeps it off
+    ///
+    /// Nothing changes if HDR was turned off manually or outside `EasyHDR`, or an excluded
+    /// process keeps it off.
+    fn enable_hdr_after_battery_hold(&mut self) {
         use tracing::{info, warn};
 
-        info!("Removing application with ID: {}", id);
-
+        if self.active_process_count.load(Ordering::SeqCst) == 0
+            || self.current_hdr_state.load(Ordering::SeqCst)
+            || self.wcg_session
+            || self.hdr_override == Some(HdrOverride::TurnOff)
+            || self.held_hdr_state().is_some()
+            || !self.hdr_displays_available.load(Ordering::SeqCst)
         {
-            let mut config = self.config.write();
-            config.monitored_apps.retain(|app| app.id() != &id);
+            return;
+        }
+        if !self.running_exclusions.is_empty() {
+            self.exclusion_hold.get_or_insert(ColorMode::Hdr);
+            return;
         }
 
-        if let Ok(cache) = crate::utils::icon_cache::IconCache::new(
-            crate::utils::icon_cache::IconCache::default_cache_dir(),
-        ) {
-            if let Err(e) = cache.remove_icon(id) {
-                warn!("Failed to remove cached icon for app {}: {}", id, e);
-            }
-        } else {
-            warn!("Failed to initialize icon cache for cleanup of app {}", id);
+        info!("Power connected with monitored applications running, enabling HDR");
+        match self.toggle_hdr(true, ToggleTrigger::PowerConnected) {
+            Ok(ToggleOutcome::Applied) => self
+                .pending_auto_toggle_notification
+                .store(true, Ordering::SeqCst),
+            Ok(ToggleOutcome::Deferred | ToggleOutcome::Skipped) => {}
+            Err(e) => warn!("Failed to enable HDR after power was connected: {}", e),
         }
+    }
 
-        self.save_config_gracefully();
-        self.update_process_monitor_watch_list();
-        self.send_state_update();
+    /// Record the HDR state of one display in `hdr_on_displays`
+    ///
+    /// Displays are matched by adapter and target ID.
+    fn note_display_hdr(&mut self, display: &DisplayTarget, enabled: bool) {
+        self.hdr_on_displays
+            .retain(|known| !is_same_display(known, display));
+        if enabled {
+            self.hdr_on_displays.push(display.clone());
+        }
+    }
 
-        info!("Application removed successfully");
-        Ok(())
+    /// HDR-capable displays that have HDR on, read from the displays
+    fn read_hdr_on_displays(hdr_controller: &H) -> Vec<DisplayTarget> {
+        hdr_controller
+            .displays()
+            .iter()
+            .filter(|d| d.supports_hdr && hdr_controller.is_hdr_enabled(d).unwrap_or(false))
+            .cloned()
+            .collect()
     }
 
-    /// Toggle application enabled state by UUID, save to disk, and update `ProcessMonitor` watch list.
-    /// Logs warning and continues with in-memory config if save fails.
-    pub fn toggle_app_enabled(&mut self, id: Uuid, enabled: bool) -> Result<()> {
-        use tracing::info;
+    /// Whether HDR is on for a display, as far as the controller knows
+    ///
+    /// A dry run never switches the displays, so those a toggle would have switched
+    /// count as on.
+    fn display_has_hdr(&self, display: &DisplayTarget) -> bool {
+        if self.dry_run && self.current_hdr_state.load(Ordering::SeqCst) {
+            return self.group_hdr_displays.as_ref().is_none_or(|displays| {
+                displays.iter().any(|known| is_same_display(known, display))
+            });
+        }
+        self.hdr_on_displays
+            .iter()
+            .any(|known| is_same_display(known, display))
+    }
 
-        info!("Toggling application {} to enabled={}", id, enabled);
+    /// Apply an HDR toggle that was held back while the displays were off.
+    ///
+    /// The toggle is dropped if it no longer matches the monitored applications, e.g. the
+    /// game that requested HDR exited while the displays were asleep.
+    fn apply_deferred_hdr_toggle(&mut self) {
+        use tracing::{debug, info, warn};
 
-        {
-            let mut config = self.config.write();
-            if let Some(app) = config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
-                app.set_enabled(enabled);
-            }
+        let app = self.deferred_toggle_app.take();
+        let Some(enable) = self.deferred_hdr_toggle.take() else {
+            return;
+        };
+
+        let apps_running =
+            self.active_process_count.load(Ordering::SeqCst) > 0 || self.manual_session.is_some();
+        if enable != apps_running && self.hdr_override.is_none() {
+            debug!(
+                "Dropping deferred HDR toggle: monitored applications changed while the displays were off"
+            );
+            return;
         }
 
-        self.save_config_gracefully();
-        self.update_process_monitor_watch_list();
-        self.send_state_update();
+        if enable == self.current_hdr_state.load(Ordering::SeqCst) {
+            return;
+        }
+        if self.held_hdr_state().is_some_and(|held| held != enable) {
+            debug!("Dropping deferred HDR toggle: HDR was changed outside EasyHDR");
+            return;
+        }
+        if enable && self.battery_blocks_hdr() {
+            debug!("Dropping deferred HDR toggle: HDR is kept off on battery power");
+            return;
+        }
 
-        info!("Application enabled state updated successfully");
-        Ok(())
+        info!(
+            "Displays turned on, applying deferred HDR toggle: {}",
+            if enable { "ON" } else { "OFF" }
+        );
+        match self.toggle_hdr(enable, ToggleTrigger::DisplaysWoke) {
+            Ok(ToggleOutcome::Applied) => {
+                self.pending_auto_toggle_notification
+                    .store(true, Ordering::SeqCst);
+                if enable && app.is_some() {
+                    self.auto_toggle_app = app;
+                }
+            }
+            Ok(ToggleOutcome::Deferred | ToggleOutcome::Skipped) => {}
+            Err(e) => warn!("Failed to apply deferred HDR toggle: {}", e),
+        }
     }
 
-    /// Update user preferences and save to disk.
-    /// Logs warning and continues with in-memory config if save fails.
-    pub fn update_preferences(&mut self, prefs: UserPreferences) -> Result<()> {
-        use tracing::info;
+    /// Compare the believed HDR state and active sessions against the system and
+    /// correct any drift.
+    ///
+    /// Safety net for missed events, called periodically from the event loop. The HDR
+    /// state is re-read from the displays and the active-session count is compared with
+    /// the monitored applications found by the latest process scan. A mismatch is only
+    /// corrected once it has been seen by two consecutive checks. Skipped while the
+    /// displays are off, since process polling is suspended then.
+    pub fn reconcile_state(&mut self) {
+        use tracing::{debug, info, warn};
 
-        info!("Updating user preferences");
+        if !self.displays_on {
+            self.suspected_hdr_drift = None;
+            self.suspected_session_drift = None;
+            return;
+        }
 
+        let mut corrected = false;
+
+        // Without HDR displays the detected state is always off, so there is nothing to
+        // compare against; in a dry run the displays never follow the believed state
+        if self.hdr_displays_available.load(Ordering::SeqCst)
+            && self.deferred_hdr_toggle.is_none()
+            && !self.dry_run
         {
-            let mut config = self.config.write();
-            config.preferences = prefs;
+            let believed = self.current_hdr_state.load(Ordering::SeqCst);
+            let actual = self.hdr_controller.detect_current_hdr_state();
+            if believed == actual {
+                self.suspected_hdr_drift = None;
+            } else if self.suspected_hdr_drift == Some(actual) {
+                warn!(
+                    "Reconciliation: HDR is {} but was believed to be {}, correcting internal state",
+                    if actual { "on" } else { "off" },
+                    if believed { "on" } else { "off" }
+                );
+                self.current_hdr_state.store(actual, Ordering::SeqCst);
+                self.hdr_on_displays = Self::read_hdr_on_displays(&self.hdr_controller);
+                self.suspected_hdr_drift = None;
+                corrected = true;
+            } else {
+                debug!("Reconciliation: HDR state differs from believed state, rechecking");
+                self.suspected_hdr_drift = Some(actual);
+            }
         }
 
-        self.save_config_gracefully();
+        let running = self
+            .watch_state
+            .results()
+            .running_apps
+            .as_ref()
+            .map(HashSet::len);
+        if let Some(actual) = running {
+            let believed = self.active_process_count.load(Ordering::SeqCst);
+            if believed == actual {
+                self.suspected_session_drift = None;
+            } else if self.suspected_session_drift == Some(actual) {
+                warn!(
+                    "Reconciliation: {} monitored application(s) running but {} were tracked, correcting active sessions",
+                    actual, believed
+                );
+                self.active_process_count.store(actual, Ordering::SeqCst);
+                self.suspected_session_drift = None;
+                corrected = true;
+                self.apply_session_correction(believed, actual);
+            } else {
+                debug!("Reconciliation: active sessions differ from latest scan, rechecking");
+                self.suspected_session_drift = Some(actual);
+            }
+        }
 
-        info!("User preferences updated successfully");
-        Ok(())
+        // Processes removed from the exclusion list while running never report stopping
+        let stale: Vec<AppIdentifier> = {
+            let state = self.watch_state.load();
+            self.running_exclusions
+                .iter()
+                .filter(|app_id| !state.is_excluded(app_id))
+                .cloned()
+                .collect()
+        };
+        for app_id in stale {
+            info!("Reconciliation: {} is no longer excluded", app_id);
+            self.excluded_process_stopped(&app_id);
+        }
+
+        // The app list shows which watched folders an elevated process keeps from matching
+        let elevated = self.watch_state.results().elevated_apps.clone();
+        if elevated != self.reported_elevated_apps {
+            for (app_id, exe_file) in &elevated {
+                if !self.reported_elevated_apps.contains_key(app_id) {
+                    warn!(
+                        "{} in monitored folder {} cannot be opened, probably because it runs as administrator; run EasyHDR as administrator to match it",
+                        exe_file, app_id
+                    );
+                }
+            }
+            self.reported_elevated_apps = elevated;
+            self.send_state_update();
+        }
+
+        if corrected {
+            info!("Reconciliation corrected internal state");
+            self.send_state_update();
+        }
     }
 
-    /// Re-enumerate displays and update HDR controller's display cache.
-    /// Call when display configuration changes (e.g., monitor connected/disconnected).
-    pub fn refresh_displays(&mut self) -> Result<()> {
-        use tracing::info;
+    /// Apply the HDR toggle that a missed first start or last stop event would have caused.
+    fn apply_session_correction(&mut self, believed: usize, actual: usize) {
+        use tracing::{error, info};
 
-        info!("Refreshing display list due to potential display configuration change");
-        let displays = self.hdr_controller.refresh_displays().map_err(|e| {
-            use tracing::error;
-            error!("Failed to refresh display list: {e}");
-            EasyHdrError::HdrControlFailed(Box::new(e))
-        })?;
-        info!(
-            "Display list refreshed: {} display(s) found ({} HDR-capable)",
-            displays.len(),
-            displays.iter().filter(|d| d.supports_hdr).count()
-        );
-        Ok(())
+        let hdr_enabled = self.current_hdr_state.load(Ordering::SeqCst);
+
+        if believed == 0 && actual > 0 && !hdr_enabled && !self.wcg_session {
+            if self.hdr_override == Some(HdrOverride::TurnOff)
+                || self.held_hdr_state() == Some(false)
+                || self.battery_blocks_hdr()
+            {
+                return;
+            }
+            if !self.running_exclusions.is_empty() {
+                self.exclusion_hold.get_or_insert(ColorMode::Hdr);
+                return;
+            }
+            info!("Reconciliation: monitored application running, enabling HDR");
+            if let Err(e) = self.toggle_hdr(true, ToggleTrigger::Reconciliation) {
+                error!("Failed to enable HDR: {}", e);
+            }
+        } else if believed > 0 && actual == 0 {
+            self.session_display_groups.clear();
+            if self.wcg_session {
+                info!(
+                    "Reconciliation: no monitored applications running, leaving wide color gamut"
+                );
+                self.set_wcg_session(false);
+            }
+            if self.hdr_override == Some(HdrOverride::TurnOff) {
+                self.hdr_override = None;
+            }
+            if !hdr_enabled {
+                return;
+            }
+            if self.hdr_override == Some(HdrOverride::KeepOn) {
+                self.hdr_override = None;
+                return;
+            }
+            if self.manual_session.is_some() {
+                return;
+            }
+            info!("Reconciliation: no monitored applications running, disabling HDR");
+            if let Err(e) = self.toggle_hdr(false, ToggleTrigger::Reconciliation) {
+                error!("Failed to disable HDR: {}", e);
+            }
+        }
     }
 
-    /// Save configuration with graceful error handling
-    ///
-    /// Attempts to save the current configuration to disk. Failures are logged
-    /// but do not propagate errors, allowing the application to continue with
-    /// in-memory configuration. This implements graceful degradation for config
-    /// persistence.
-    ///
-    /// # Design
+    /// Toggle HDR state globally on all displays and update debouncing timestamp.
     ///
-    /// This helper consolidates the config save pattern used across multiple
-    /// methods in `AppController`, eliminating ~20 lines of duplication.
-    fn save_config_gracefully(&self) {
-        use tracing::warn;
+    /// While the displays are off the toggle is deferred until they turn back on, and
+    /// automatic toggles are skipped while conflicting HDR changes pause automatic
+    /// management; the outcome tells callers whether the displays were switched. Each
+    /// display's state is verified and retried; displays that keep failing are reported
+    /// to the GUI, and the believed state only changes if at least one display changed.
+    #[expect(
+        clippy::too_many_lines,
+        reason = "Toggling and recording the toggle form one sequence"
+    )]
+    fn toggle_hdr(&mut self, enable: bool, trigger: ToggleTrigger) -> Result<ToggleOutcome> {
+        use tracing::{info, warn};
 
-        let config = self.config.read();
-        if let Err(e) = ConfigManager::save(&config) {
-            warn!(
-                "Failed to save configuration to disk: {}. Continuing with in-memory config. \
-                 Changes will be lost on application restart.",
-                e
+        if self.hdr_conflict_since.is_some() && trigger.is_automatic() {
+            info!(
+                "Automatic HDR management is paused after conflicting HDR changes, not turning HDR {} ({})",
+                if enable { "ON" } else { "OFF" },
+                trigger
             );
+            return Ok(ToggleOutcome::Skipped);
+        }
+
+        if !self.displays_on {
+            info!(
+                "Displays are off, deferring HDR {} until they turn back on",
+                if enable { "ON" } else { "OFF" }
+            );
+            self.deferred_hdr_toggle = Some(enable);
+            return Ok(ToggleOutcome::Deferred);
+        }
+
+        info!("Toggling HDR: {}", if enable { "ON" } else { "OFF" });
+        let group_targets = self.group_toggle_targets(enable);
+
+        // Only the believed state changes, as if every targeted HDR display had followed
+        if self.dry_run {
+            let targets: Vec<DisplayTarget> = group_targets
+                .clone()
+                .unwrap_or_else(|| self.hdr_controller.displays().to_vec())
+                .into_iter()
+                .filter(|t| t.supports_hdr)
+                .collect();
+            for target in &targets {
+                info!(
+                    "Dry run: would turn HDR {} for {}",
+                    if enable { "ON" } else { "OFF" },
+                    target
+                );
+            }
+            let group_displays = if enable {
+                self.group_displays_after_enable(group_targets.clone())
+            } else {
+                None
+            };
+            // Turning display groups off leaves HDR on for the displays outside them
+            let hdr_on = enable
+                || (group_targets.is_some()
+                    && self
+                        .hdr_on_displays
+                        .iter()
+                        .any(|known| !targets.iter().any(|target| is_same_display(target, known))));
+            self.current_hdr_state.store(hdr_on, Ordering::SeqCst);
+            self.hdr_enabled_by_app = enable;
+            self.group_hdr_displays = group_displays;
+            self.record_toggle_time();
+
+            let displays = targets.iter().map(ToString::to_string).collect();
+            let mut record = ToggleRecord::now(trigger, enable, displays, None);
+            record.dry_run = true;
+            self.toggle_history.record(record);
+            return Ok(ToggleOutcome::Applied);
+        }
+
+        // Switch the display mode first so HDR is enabled on the final mode
+        if enable
+            && let Some(display_mode) = self.session_display_mode
+            && let Err(e) = self.display_mode.apply(display_mode)
+        {
+            warn!("Failed to apply display mode {}: {}", display_mode, e);
+        }
+
+        let results = match &group_targets {
+            Some(targets) => Ok(self.hdr_controller.set_hdr_verified_on(targets, enable)),
+            None => self.hdr_controller.set_hdr_global_verified(enable),
+        };
+        let results = match results {
+            Ok(results) => results,
+            Err(e) => {
+                use tracing::error;
+                error!("Failed to set HDR state globally: {e}");
+                metrics::get_metrics().record_failure();
+                let message = format!(
+                    "HDR could not be turned {}: {e}",
+                    if enable { "on" } else { "off" }
+                );
+                event_log::report(EventKind::HdrToggleFailed, &message);
+                error_center::report("HDR control", &message);
+                self.toggle_history.record(ToggleRecord::now(
+                    trigger,
+                    enable,
+                    Vec::new(),
+                    Some(e.to_string()),
+                ));
+                return Err(EasyHdrError::HdrControlFailed(Box::new(e)));
+            }
+        };
+
+        let mut failed = Vec::new();
+        let mut failures = Vec::new();
+        for (target, result) in &results {
+            match result {
+                Ok(()) => {
+                    info!(
+                        "HDR {} for {}",
+                        if enable { "enabled" } else { "disabled" },
+                        target
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to toggle HDR for {} after {} attempts: {}",
+                        target, HDR_TOGGLE_ATTEMPTS, e
+                    );
+                    failed.push(target.to_string());
+                    failures.push(DisplayToggleFailure::new(target, e));
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            self.hdr_toggle_error = None;
+            self.hdr_toggle_failures.clear();
+        } else {
+            self.hdr_toggle_error = Some(format!(
+                "HDR could not be turned {} for {}",
+                if enable { "on" } else { "off" },
+                failures
+                    .iter()
+                    .map(|failure| match failure.error_code {
+                        Some(code) => format!("{} (error {code})", failure.display),
+                        None => failure.display.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            self.hdr_toggle_failures = failures;
+            self.pending_hdr_toggle_error_notification
+                .store(true, Ordering::SeqCst);
+
+            // Nothing changed, so keep the believed state and let the caller report it
+            if failed.len() == results.len() {
+                metrics::get_metrics().record_failure();
+                if let Some(error) = &self.hdr_toggle_error {
+                    event_log::report(EventKind::HdrToggleFailed, error);
+                    error_center::report("HDR control", error);
+                }
+                self.toggle_history.record(ToggleRecord::now(
+                    trigger,
+                    enable,
+                    failed,
+                    self.hdr_toggle_error.clone(),
+                ));
+                if enable && let Err(e) = self.display_mode.restore() {
+                    warn!("Failed to restore display mode: {}", e);
+                }
+                return Err(EasyHdrError::HdrControlFailed(
+                    crate::error::StringError::new(format!(
+                        "HDR toggle failed on every display after {HDR_TOGGLE_ATTEMPTS} attempts"
+                    )),
+                ));
+            }
+        }
+
+        if enable {
+            self.apply_hdr_color_profiles(&results);
+            if let Some(tone_mapping) = self.session_capture_tone_mapping
+                && let Err(e) = self.capture_tone_mapping.apply(tone_mapping)
+            {
+                warn!("Failed to set tone mapping of HDR captures: {}", e);
+            }
+            if trigger.is_automatic()
+                && self.config.read().preferences.suspend_night_light
+                && let Err(e) = self.night_light.suspend()
+            {
+                warn!("Failed to turn Night Light off: {}", e);
+            }
+        } else {
+            if let Err(e) = self.display_mode.restore() {
+                warn!("Failed to restore display mode: {}", e);
+            }
+            if let Err(e) = self.capture_tone_mapping.restore() {
+                warn!("Failed to restore tone mapping of HDR captures: {}", e);
+            }
+            if let Err(e) = self.night_light.restore() {
+                warn!("Failed to turn Night Light back on: {}", e);
+            }
+        }
+
+        let group_displays = if enable {
+            self.group_displays_after_enable(group_targets.clone())
+        } else {
+            None
+        };
+        // EasyHDR changed HDR itself, so an earlier change outside it no longer applies
+        self.external_hold = None;
+        let hdr_on = if group_targets.is_some() {
+            // Displays outside the display groups keep their HDR state, which decides
+            // whether HDR is still on after turning the groups off
+            self.hdr_on_displays = Self::read_hdr_on_displays(&self.hdr_controller);
+            enable || !self.hdr_on_displays.is_empty()
+        } else {
+            for (target, _) in results.iter().filter(|(_, result)| result.is_ok()) {
+                self.note_display_hdr(target, enable);
+            }
+            enable
+        };
+        self.current_hdr_state.store(hdr_on, Ordering::SeqCst);
+        self.hdr_enabled_by_app = enable;
+        self.group_hdr_displays = group_displays;
+        self.hdr_controller.refresh_capabilities();
+
+        // Remember that HDR must be turned back off if EasyHDR dies while it is on
+        if enable {
+            crash_guard::arm();
+        } else {
+            crash_guard::disarm();
+        }
+
+        self.record_toggle_time();
+
+        let mut message = format!("HDR turned {}", if enable { "on" } else { "off" });
+        if let Some(error) = &self.hdr_toggle_error {
+            message = format!("{message}. {error}");
         }
+        event_log::report(EventKind::HdrToggled, &message);
+        metrics::get_metrics().record_toggle(enable);
+
+        let displays = results
+            .iter()
+            .filter(|(_, result)| result.is_ok())
+            .map(|(target, _)| target.to_string())
+            .collect();
+        self.toggle_history.record(ToggleRecord::now(
+            trigger,
+            enable,
+            displays,
+            self.hdr_toggle_error.clone(),
+        ));
+
+        Ok(ToggleOutcome::Applied)
     }
 
-    /// Normalize `AppIdentifier` for case-insensitive matching.
+    /// Remember when HDR was last toggled, for debouncing.
+    fn record_toggle_time(&self) {
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Elapsed nanos will not exceed u64::MAX within application lifetime"
+        )]
+        let elapsed_nanos = self
+            .clock
+            .now()
+            .saturating_duration_since(self.startup_time)
+            .as_nanos() as u64;
+        self.last_toggle_time_nanos
+            .store(elapsed_nanos, Ordering::Relaxed);
+    }
+
+    /// Set the configured HDR color profiles on the displays HDR was just turned on for.
     ///
-    /// Win32 process names are normalized to lowercase. UWP package family names are case-sensitive.
-    fn normalize_app_identifier(app_id: &AppIdentifier) -> AppIdentifier {
-        match app_id {
-            AppIdentifier::Win32(process_name) => AppIdentifier::Win32(process_name.to_lowercase()),
-            AppIdentifier::Uwp(package_family_name) => {
-                AppIdentifier::Uwp(package_family_name.clone())
+    /// Windows doesn't always reapply a calibration profile when HDR is turned on
+    /// programmatically. Failures are logged; HDR stays on either way.
+    fn apply_hdr_color_profiles(&self, results: &[(DisplayTarget, Result<()>)]) {
+        use tracing::warn;
+
+        let config = self.config.read();
+        for (target, _) in results.iter().filter(|(_, result)| result.is_ok()) {
+            if let Some(profile) = config.hdr_color_profile(target)
+                && let Err(e) = self.hdr_controller.set_color_profile(target, profile)
+            {
+                warn!("Failed to apply HDR color profile to {}: {}", target, e);
             }
         }
     }
 
-    /// Update `ProcessMonitor` watch list with enabled monitored applications from config.
-    fn update_process_monitor_watch_list(&self) {
-        use tracing::debug;
+    /// Send current state update to GUI.
+    ///
+    /// A front-end of the background agent sends none; the window shows the agent's.
+    fn send_state_update(&self) {
+        use tracing::{debug, warn};
+
+        if self.is_front_end() {
+            return;
+        }
+
+        let state = self.current_state();
+        debug!(
+            "Sending state update to GUI: HDR enabled = {}, show HDR available notification = {}, show no HDR warning = {}",
+            state.hdr_enabled, state.show_hdr_available_notification, state.show_no_hdr_warning
+        );
+
+        if let Err(e) = self.gui_state_sender.send(state) {
+            warn!("Failed to send state update to GUI: {}", e);
+        } else {
+            debug!("State update sent successfully to GUI");
+        }
+
+        if let Some(discord) = &self.discord {
+            discord.update(self.discord_activity());
+        }
+    }
 
+    /// Current state for the GUI, taking the notifications waiting to be shown
+    pub fn current_state(&self) -> AppState {
         let config = self.config.read();
-        let monitored_apps: Vec<MonitoredApp> = config
+        let active_apps: Vec<String> = config
             .monitored_apps
             .iter()
             .filter(|app| app.is_enabled())
-            .cloned()
+            .map(|app| app.display_name().to_string())
             .collect();
+        let log_level = config.preferences.log_level;
         drop(config);
 
-        debug!(
-            "Updating ProcessMonitor watch list with {} monitored applications",
-            monitored_apps.len()
-        );
-
-        let identifiers: HashSet<AppIdentifier> = monitored_apps
-            .iter()
-            .map(|app| match app {
-                MonitoredApp::Win32(win32_app) => {
-                    AppIdentifier::Win32(win32_app.process_name.to_lowercase())
-                }
-                MonitoredApp::Uwp(uwp_app) => {
-                    AppIdentifier::Uwp(uwp_app.package_family_name.clone())
-                }
-            })
-            .collect();
+        let hdr_enabled = self.current_hdr_state.load(Ordering::SeqCst);
 
-        let mut state = self.watch_state.write();
-        *state = WatchState {
-            apps: Arc::new(monitored_apps),
-            identifiers,
+        // Atomically get and clear the pending notification flags
+        let show_hdr_available_notification = self
+            .pending_hdr_available_notification
+            .swap(false, Ordering::SeqCst);
+        let show_no_hdr_warning = self.pending_no_hdr_warning.swap(false, Ordering::SeqCst);
+        let hdr_toggled_automatically = self
+            .pending_auto_toggle_notification
+            .swap(false, Ordering::SeqCst);
+        let hdr_toggle_app = if hdr_toggled_automatically {
+            self.auto_toggle_app
+        } else {
+            None
         };
+        let weekly_summary = self.pending_weekly_summary.lock().take();
+        let show_hdr_toggle_error_notification = self
+            .pending_hdr_toggle_error_notification
+            .swap(false, Ordering::SeqCst);
+        let show_hdr_conflict_notification = self
+            .pending_hdr_conflict_notification
+            .swap(false, Ordering::SeqCst);
 
-        debug!("ProcessMonitor watch state updated atomically");
+        AppState {
+            hdr_enabled,
+            active_apps,
+            last_event: format!(
+                "Active processes: {}",
+                self.active_process_count.load(Ordering::SeqCst)
+            ),
+            show_hdr_available_notification,
+            show_no_hdr_warning,
+            hdr_toggled_automatically,
+            hdr_toggle_app,
+            weekly_summary,
+            hdr_toggle_error: self.hdr_toggle_error.clone(),
+            hdr_toggle_failures: self.hdr_toggle_failures.clone(),
+            show_hdr_toggle_error_notification,
+            hdr_conflict: self.hdr_conflict_since.map(|_| {
+                "HDR keeps changing outside EasyHDR, probably another HDR tool or a game switching it too. Automatic HDR is paused until you resume it.".to_string()
+            }),
+            show_hdr_conflict_notification,
+            log_level,
+            manual_session: self.manual_session.as_ref().map(ManualSession::describe),
+            session_limit_warning: self.pending_session_limit_warning.lock().take(),
+            oled_care_reminder: self.pending_oled_care_reminder.lock().take(),
+            pending_actions: self.pending_actions(),
+            dry_run: self.dry_run,
+            display_hdr: self.display_hdr_states(),
+            onboarding: self.onboarding_state(),
+        }
     }
-}
 
-#[cfg(test)]
-#[expect(clippy::unwrap_used)]
-mod tests {
-    use super::*;
-    use crate::config::models::Win32App;
-    use crate::config::{AppConfig, MonitoredApp};
-    use crate::test_utils::{AppdataGuard, create_test_dir};
-    use std::path::PathBuf;
-    use uuid::Uuid;
+    /// Start, restart or stop the Discord Rich Presence to match the preferences.
+    ///
+    /// `current` is kept if it was started for the configured client ID.
+    fn discord_presence_for(
+        preferences: &UserPreferences,
+        current: Option<DiscordPresence>,
+    ) -> Option<DiscordPresence> {
+        use tracing::{info, warn};
 
-    #[test]
-    fn test_app_controller_creation() {
-        let config = AppConfig::default();
-        let (_event_tx, event_rx) = mpsc::sync_channel(32);
-        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let client_id = preferences.discord_client_id.trim();
+        if !preferences.discord_presence || client_id.is_empty() {
+            if current.is_some() {
+                info!("Discord Rich Presence turned off");
+            }
+            return None;
+        }
+        if let Some(current) = current.filter(|current| current.client_id() == client_id) {
+            return Some(current);
+        }
 
-        let controller = AppController::new(
-            config,
-            event_rx,
-            hdr_state_rx,
-            state_tx,
-            watch_state.clone(),
-        );
-        assert!(controller.is_ok());
+        match DiscordPresence::start(client_id) {
+            Ok(discord) => {
+                info!("Discord Rich Presence turned on");
+                Some(discord)
+            }
+            Err(e) => {
+                warn!("Failed to start Discord Rich Presence: {}", e);
+                None
+            }
+        }
     }
 
-    #[test]
-    fn test_handle_process_started_increments_count() {
-        // Create a config with one monitored app
+    /// Apply changed Discord Rich Presence preferences.
+    ///
+    /// A front-end of the background agent leaves the presence to the agent.
+    pub fn apply_discord_preferences(&mut self) {
+        if self.is_front_end() {
+            return;
+        }
+        let preferences = self.config.read().preferences.clone();
+        self.discord = Self::discord_presence_for(&preferences, self.discord.take());
+        if let Some(discord) = &self.discord {
+            discord.update(self.discord_activity());
+        }
+    }
+
+    /// Activity shown on Discord: the first running monitored application while HDR is on.
+    fn discord_activity(&self) -> Option<Activity> {
+        if !self.current_hdr_state.load(Ordering::SeqCst) {
+            return None;
+        }
+        let config = self.config.read();
+        config.monitored_apps.iter().find_map(|app| {
+            let started_at = self.sessions.started_at(*app.id())?;
+            Some(Activity::playing_in_hdr(
+                app.display_name(),
+                started_at
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            ))
+        })
+    }
+
+    /// Send initial state to GUI and populate `ProcessMonitor` watch list.
+    pub fn send_initial_state(&self) {
+        use tracing::info;
+
+        info!("Sending initial state update to populate GUI");
+        self.update_process_monitor_watch_list();
+
+        self.send_state_update();
+    }
+
+    /// Leave out the picker candidates an application on the watch list already covers.
+    ///
+    /// Only reads the config, so callers take the slow process snapshot or Start Menu
+    /// scan before locking the controller.
+    pub fn retain_unmonitored(&self, candidates: &mut Vec<RunningCandidate>) {
+        let config = self.config.read();
+        candidates.retain(|candidate| !candidate.kind.is_monitored_by(&config.monitored_apps));
+    }
+
+    /// Add application to config, save to disk, and update `ProcessMonitor` watch list.
+    /// Logs warning and continues with in-memory config if save fails.
+    pub fn add_application(&mut self, app: MonitoredApp) -> Result<()> {
+        use tracing::info;
+
+        match &app {
+            MonitoredApp::Win32(win32_app) => {
+                info!(
+                    "Adding Win32 application: {} ({})",
+                    win32_app.display_name, win32_app.process_name
+                );
+            }
+            MonitoredApp::Uwp(uwp_app) => {
+                info!(
+                    "Adding UWP application: {} ({})",
+                    uwp_app.display_name, uwp_app.package_family_name
+                );
+            }
+            MonitoredApp::Folder(folder_app) => {
+                info!(
+                    "Adding folder: {} ({})",
+                    folder_app.display_name,
+                    folder_app.path.display()
+                );
+            }
+        }
+
+        // Add to config
+        {
+            let mut config = self.config.write();
+            config.monitored_apps.push(app);
+        }
+
+        // Save configuration - if this fails, we continue with in-memory config
+        self.save_config_gracefully();
+
+        // Update ProcessMonitor watch list
+        self.update_process_monitor_watch_list();
+
+        // Send state update to GUI
+        self.send_state_update();
+
+        info!("Application added successfully");
+        Ok(())
+    }
+
+    /// Find the monitored Win32 application for an executable path.
+    ///
+    /// Paths are compared through their resolved final path, so an executable reached
+    /// through a junction, symlink or redirected folder matches the existing entry.
+    pub fn find_win32_app_by_path(&self, exe_path: &Path) -> Option<Uuid> {
+        use crate::utils::canonical_path;
+
+        let key = canonical_path::path_key(exe_path);
+        self.config
+            .read()
+            .monitored_apps
+            .iter()
+            .find_map(|app| match app {
+                MonitoredApp::Win32(win32_app)
+                    if canonical_path::path_key(&win32_app.exe_path) == key =>
+                {
+                    Some(win32_app.id)
+                }
+                _ => None,
+            })
+    }
+
+    /// Whether an application lives on a network or removable volume that is offline
+    ///
+    /// Offline applications are kept and keep their settings; they simply cannot be
+    /// launched until the volume is back.
+    pub fn is_app_offline(&self, app: &MonitoredApp) -> bool {
+        match app {
+            MonitoredApp::Win32(win32_app) => self.offline_volumes.contains(&win32_app.exe_path),
+            MonitoredApp::Folder(folder_app) => self.offline_volumes.contains(&folder_app.path),
+            MonitoredApp::Uwp(_) => false,
+        }
+    }
+
+    /// Warning for a watched application that a process running elevated keeps from
+    /// matching, `None` if there is nothing to report
+    pub fn elevated_app_warning(&self, app: &MonitoredApp) -> Option<String> {
+        let exe_file = self
+            .reported_elevated_apps
+            .get(&AppIdentifier::from_monitored_app(app))?;
+        Some(format!(
+            "{exe_file} seems to run as administrator, so EasyHDR cannot see which folder it \
+             is in. Run EasyHDR as administrator or add {exe_file} directly."
+        ))
+    }
+
+    /// Re-check the volumes of monitored applications on network shares and removable drives
+    ///
+    /// The check runs on a background thread so slow or disconnected volumes never block
+    /// the event loop or the GUI; [`Self::process_volume_check`] applies its result. Icons
+    /// skipped while loading the configuration, or while their volume was offline, are
+    /// extracted on the same thread once the volume answers.
+    pub fn refresh_volume_availability(&mut self) {
+        use crate::utils::volume::{AVAILABILITY_TIMEOUT, volume_kind};
+        use tracing::warn;
+
+        if self.volume_check.is_some() {
+            return;
+        }
+
+        let apps: Vec<Win32App> = self
+            .config
+            .read()
+            .monitored_apps
+            .iter()
+            .filter_map(|app| match app {
+                MonitoredApp::Win32(win32_app)
+                    if volume_kind(&win32_app.exe_path).may_be_offline() =>
+                {
+                    Some(win32_app.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        if apps.is_empty() {
+            self.volumes_checked = true;
+            if !self.offline_volumes.is_empty() {
+                self.offline_volumes = OfflineVolumes::default();
+                self.send_state_update();
+            }
+            return;
+        }
+
+        let previous = self.volumes_checked.then(|| self.offline_volumes.clone());
+        let (sender, receiver) = mpsc::channel();
+        let spawned = std::thread::Builder::new()
+            .name("volume-check".to_string())
+            .spawn(move || {
+                let offline = OfflineVolumes::check(
+                    apps.iter().map(|app| app.exe_path.as_path()),
+                    AVAILABILITY_TIMEOUT,
+                );
+                let icons = apps
+                    .into_iter()
+                    .filter(|app| {
+                        app.icon_data.is_none()
+                            && !offline.contains(&app.exe_path)
+                            && previous
+                                .as_ref()
+                                .is_none_or(|previous| previous.contains(&app.exe_path))
+                    })
+                    .filter_map(|mut app| {
+                        let icon = app.ensure_icon_loaded()?.clone();
+                        Some((app.id, icon))
+                    })
+                    .collect();
+                // The receiver is gone if the controller was dropped meanwhile
+                let _ = sender.send(VolumeCheck { offline, icons });
+            });
+
+        match spawned {
+            Ok(_) => self.volume_check = Some(receiver),
+            Err(e) => warn!("Failed to spawn volume availability check: {}", e),
+        }
+    }
+
+    /// Re-check volumes periodically while some are offline
+    ///
+    /// Reconnecting a network share does not broadcast a volume arrival, so offline
+    /// volumes are also polled from the event loop.
+    fn recheck_offline_volumes(&mut self) {
+        if !self.offline_volumes.is_empty() {
+            self.refresh_volume_availability();
+        }
+    }
+
+    /// Apply the result of a finished volume availability check.
+    ///
+    /// Called from the event loop on every iteration; does nothing while no check has
+    /// finished.
+    fn process_volume_check(&mut self) {
+        use tracing::{info, warn};
+
+        let Some(receiver) = &self.volume_check else {
+            return;
+        };
+        let check = match receiver.try_recv() {
+            Ok(check) => check,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                warn!("Volume availability check ended without a result");
+                self.volume_check = None;
+                return;
+            }
+        };
+        self.volume_check = None;
+        self.volumes_checked = true;
+
+        let mut changed = check.offline != self.offline_volumes;
+        if changed {
+            if check.offline.is_empty() {
+                info!("All network and removable volumes are available");
+            } else {
+                info!(
+                    "{} network or removable volume(s) offline",
+                    check.offline.len()
+                );
+            }
+        }
+        self.offline_volumes = check.offline;
+
+        if !check.icons.is_empty() {
+            let cache = crate::utils::icon_cache::IconCache::new(
+                crate::utils::icon_cache::IconCache::default_cache_dir(),
+            )
+            .ok();
+            let mut config = self.config.write();
+            for (id, icon) in check.icons {
+                let Some(app) = config
+                    .monitored_apps
+                    .iter_mut()
+                    .find(|app| *app.id() == id && app.icon_data().is_none())
+                else {
+                    continue;
+                };
+
+                if let Some(cache) = &cache
+                    && let Err(e) = cache.save_icon(id, &icon)
+                {
+                    warn!("Failed to cache icon for '{}': {}", app.display_name(), e);
+                }
+                *app.icon_data_mut() = Some(icon);
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.send_state_update();
+        }
+    }
+
+    /// Load missing icons on a background thread
+    ///
+    /// Called when the main window is shown, so icons never delay its first paint. Icons
+    /// restored from the disk cache arrive first and extracted ones later; each batch is
+    /// applied by [`Self::process_icon_loads`] and sent to the GUI as a state update. Does
+    /// nothing while a load is still running.
+    pub fn load_icons_in_background(&mut self) {
+        use tracing::{info, warn};
+
+        if self.icon_loader.is_some() {
+            return;
+        }
+
+        let apps: Vec<MonitoredApp> = self
+            .config
+            .read()
+            .monitored_apps
+            .iter()
+            .filter(|app| app.icon_data().is_none())
+            .cloned()
+            .collect();
+        if apps.is_empty() {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let spawned = std::thread::Builder::new()
+            .name("icon-loader".to_string())
+            .spawn(move || {
+                let loaded = ConfigManager::load_icons_incrementally(apps, |icons| {
+                    // The receiver is gone if the controller was dropped meanwhile
+                    let _ = sender.send(icons);
+                });
+                info!("Loaded {} icon(s) in the background", loaded);
+            });
+
+        match spawned {
+            Ok(_) => self.icon_loader = Some(receiver),
+            Err(e) => {
+                warn!("Failed to spawn icon loader, loading icons inline: {}", e);
+                ConfigManager::load_icons(&mut self.config.write());
+                self.send_state_update();
+            }
+        }
+    }
+
+    /// Apply the icons delivered by the background icon loader.
+    ///
+    /// Called from the event loop on every iteration; does nothing while no icons have
+    /// arrived. The first load to finish is recorded by the startup profiler.
+    fn process_icon_loads(&mut self) {
+        use crate::utils::startup_profiler::{self, StartupPhase};
+
+        let Some(receiver) = &self.icon_loader else {
+            return;
+        };
+
+        let mut changed = false;
+        let mut finished = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(icons) => {
+                    let mut config = self.config.write();
+                    for (id, icon) in icons {
+                        if let Some(app) = config
+                            .monitored_apps
+                            .iter_mut()
+                            .find(|app| *app.id() == id && app.icon_data().is_none())
+                        {
+                            *app.icon_data_mut() = Some(icon);
+                            changed = true;
+                        }
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        if finished {
+            self.icon_loader = None;
+            let profiler = startup_profiler::get_profiler();
+            if !profiler
+                .get_timings()
+                .iter()
+                .any(|timing| timing.phase == StartupPhase::IconLoad)
+            {
+                profiler.record_phase(StartupPhase::IconLoad);
+            }
+        }
+        if changed {
+            self.send_state_update();
+        }
+    }
+
+    /// Remove application by UUID, save to disk, and update `ProcessMonitor` watch list.
+    /// Logs warning and continues with in-memory config if save fails.
+    pub fn remove_application(&mut self, id: Uuid) -> Result<()> {
+        use tracing::{info, warn};
+
+        info!("Removing application with ID: {}", id);
+
+        let removed = {
+            let mut config = self.config.write();
+            let index = config.monitored_apps.iter().position(|app| app.id() == &id);
+            index.map(|index| config.monitored_apps.remove(index))
+        };
+
+        // Don't leave an Auto HDR override behind for an app EasyHDR no longer manages
+        if let Some(MonitoredApp::Win32(app)) = removed
+            && app.auto_hdr != AutoHdrMode::Default
+            && let Err(e) = self
+                .auto_hdr()
+                .set_mode(&app.exe_path, AutoHdrMode::Default)
+        {
+            warn!(
+                "Failed to clear Auto HDR override for {}: {}",
+                app.display_name, e
+            );
+        }
+
+        self.session_history.remove_app(id);
+        self.snoozed_apps.remove(&id);
+        self.session_history_dirty = true;
+
+        if let Ok(cache) = crate::utils::icon_cache::IconCache::new(
+            crate::utils::icon_cache::IconCache::default_cache_dir(),
+        ) {
+            if let Err(e) = cache.remove_icon(id) {
+                warn!("Failed to remove cached icon for app {}: {}", id, e);
+            }
+        } else {
+            warn!("Failed to initialize icon cache for cleanup of app {}", id);
+        }
+
+        self.save_config_gracefully();
+        self.update_process_monitor_watch_list();
+        self.send_state_update();
+
+        info!("Application removed successfully");
+        Ok(())
+    }
+
+    /// Toggle application enabled state by UUID, save to disk, and update `ProcessMonitor` watch list.
+    /// Logs warning and continues with in-memory config if save fails.
+    pub fn toggle_app_enabled(&mut self, id: Uuid, enabled: bool) -> Result<()> {
+        use tracing::info;
+
+        info!("Toggling application {} to enabled={}", id, enabled);
+
+        {
+            let mut config = self.config.write();
+            if let Some(app) = config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
+                app.set_enabled(enabled);
+            }
+        }
+
+        self.save_config_gracefully();
+        self.update_process_monitor_watch_list();
+        self.send_state_update();
+
+        info!("Application enabled state updated successfully");
+        Ok(())
+    }
+
+    /// Leave an application to itself for a while.
+    ///
+    /// Called from an HDR toggle notification. For [`APP_SNOOZE_DURATION`] the
+    /// application is left out of the watch list as if it were disabled, without changing
+    /// the saved configuration. The snooze is listed in [`Self::pending_actions`] and can
+    /// be cancelled there.
+    pub fn snooze_app(&mut self, id: Uuid) {
+        use tracing::info;
+
+        info!(
+            "Snoozing automatic HDR management for application {} for {}",
+            id,
+            describe_minutes(APP_SNOOZE_DURATION.as_secs() / 60)
+        );
+        // Kept by a front-end too, for the application list
+        self.snoozed_apps
+            .insert(id, self.clock.now() + APP_SNOOZE_DURATION);
+        if self.forward_logged(AgentCommand::SnoozeApp { id }) {
+            return;
+        }
+
+        // Left out of the watch list, the app's exit is no longer reported, so a running
+        // session ends now and HDR is turned off as if the app had exited
+        let running = self
+            .sessions
+            .running_apps()
+            .any(|running| running == id)
+            .then(|| {
+                self.config
+                    .read()
+                    .monitored_apps
+                    .iter()
+                    .find(|app| *app.id() == id)
+                    .map(AppIdentifier::from_monitored_app)
+            })
+            .flatten();
+        if let Some(app_id) = running {
+            self.handle_process_event(ProcessEvent::Stopped(app_id));
+        }
+        self.update_process_monitor_watch_list();
+        self.send_state_update();
+    }
+
+    /// Whether an application is snoozed
+    pub fn is_app_snoozed(&self, id: Uuid) -> bool {
+        self.snoozed_apps
+            .get(&id)
+            .is_some_and(|&until| until > self.clock.now())
+    }
+
+    /// Watch snoozed applications again once their snooze has ended.
+    ///
+    /// Called from the event loop on every iteration.
+    fn process_app_snoozes(&mut self) {
+        use tracing::info;
+
+        let now = self.clock.now();
+        let snoozed = self.snoozed_apps.len();
+        self.snoozed_apps.retain(|id, until| {
+            let active = now < *until;
+            if !active {
+                info!("Snooze of application {} ended", id);
+            }
+            active
+        });
+
+        if self.snoozed_apps.len() != snoozed {
+            self.update_process_monitor_watch_list();
+            self.send_state_update();
+        }
+    }
+
+    /// Set the Auto HDR override for a Win32 application by UUID and save to disk.
+    ///
+    /// The override is written to the Windows graphics settings immediately; DirectX
+    /// picks it up the next time the application starts. UWP applications are ignored.
+    pub fn set_auto_hdr_mode(&mut self, id: Uuid, mode: AutoHdrMode) -> Result<()> {
+        use tracing::info;
+
+        let exe_path = {
+            let config = self.config.read();
+            config.monitored_apps.iter().find_map(|app| match app {
+                MonitoredApp::Win32(app) if app.id == id => Some(app.exe_path.clone()),
+                _ => None,
+            })
+        };
+
+        let Some(exe_path) = exe_path else {
+            return Ok(());
+        };
+
+        info!(
+            "Setting Auto HDR for application {} to {}",
+            id,
+            mode.as_str()
+        );
+        self.auto_hdr().set_mode(&exe_path, mode)?;
+
+        {
+            let mut config = self.config.write();
+            if let Some(MonitoredApp::Win32(app)) =
+                config.monitored_apps.iter_mut().find(|app| app.id() == &id)
+            {
+                app.auto_hdr = mode;
+            }
+        }
+
+        self.save_config_gracefully();
+        self.send_state_update();
+
+        Ok(())
+    }
+
+    /// Whether per-application Auto HDR overrides are available (Windows 11 only)
+    pub fn auto_hdr_supported(&self) -> bool {
+        self.auto_hdr().is_supported()
+    }
+
+    fn auto_hdr(&self) -> AutoHdrController {
+        AutoHdrController::new(self.hdr_controller.windows_version())
+    }
+
+    /// Re-apply configured Auto HDR overrides at startup.
+    ///
+    /// Keeps the Windows graphics settings in line with the config if they were reset
+    /// or edited elsewhere while `EasyHDR` was not running.
+    fn reapply_auto_hdr_modes(&self) {
+        use tracing::warn;
+
+        let auto_hdr = self.auto_hdr();
+        if !auto_hdr.is_supported() {
+            return;
+        }
+
+        let config = self.config.read();
+        for app in &config.monitored_apps {
+            if let MonitoredApp::Win32(app) = app
+                && app.auto_hdr != AutoHdrMode::Default
+                && let Err(e) = auto_hdr.set_mode(&app.exe_path, app.auto_hdr)
+            {
+                warn!(
+                    "Failed to apply Auto HDR override for {}: {}",
+                    app.display_name, e
+                );
+            }
+        }
+    }
+
+    /// Update user preferences and save to disk.
+    /// Logs warning and continues with in-memory config if save fails.
+    pub fn update_preferences(&mut self, prefs: UserPreferences) -> Result<()> {
+        use tracing::info;
+
+        info!("Updating user preferences");
+
+        {
+            let mut config = self.config.write();
+            config.preferences = prefs;
+        }
+
+        self.save_config_gracefully();
+        self.update_process_monitor_watch_list();
+        self.apply_discord_preferences();
+
+        info!("User preferences updated successfully");
+        Ok(())
+    }
+
+    /// Export the configuration to `path`.
+    pub fn export_config(&self, path: &Path) -> Result<()> {
+        ConfigManager::export(&self.config.read(), path)
+    }
+
+    /// Replace the watch list, exclusions and preferences with an exported configuration.
+    ///
+    /// Window placement, known displays, the auto-start setting and update check
+    /// metadata belong to this PC and are kept. The previous configuration is backed
+    /// up when the imported one is saved.
+    pub fn import_config(&mut self, path: &Path) -> Result<()> {
+        use tracing::info;
+
+        let imported = ConfigManager::import(path)?;
+        {
+            let mut config = self.config.write();
+            let local = config.preferences.clone();
+            config.monitored_apps = imported.monitored_apps;
+            config.excluded_processes = imported.excluded_processes;
+            config.preferences = UserPreferences {
+                auto_start: local.auto_start,
+                autostart_method: local.autostart_method,
+                autostart_elevated: local.autostart_elevated,
+                autostart_delay_secs: local.autostart_delay_secs,
+                last_update_check_time: local.last_update_check_time,
+                cached_latest_version: local.cached_latest_version,
+                update_check_cache: local.update_check_cache,
+                discord_presence: local.discord_presence,
+                discord_client_id: local.discord_client_id,
+                run_in_background: local.run_in_background,
+                ..imported.preferences
+            };
+            let app_count = config.monitored_apps.len();
+            ConfigManager::load_icons(&mut config);
+            info!("Imported {} applications from {:?}", app_count, path);
+        }
+
+        self.save_config_gracefully();
+        self.update_process_monitor_watch_list();
+        self.reapply_auto_hdr_modes();
+        let preferences = self.config.read().preferences.clone();
+        event_log::set_enabled(preferences.write_event_log);
+        metrics::get_metrics().set_enabled(preferences.metrics_enabled);
+        self.send_state_update();
+        Ok(())
+    }
+
+    /// Apply a manual override from an HDR toggle notification.
+    ///
+    /// Sets HDR to the requested state immediately and suspends the matching
+    /// automatic toggle until the override is consumed or cleared.
+    pub fn apply_hdr_override(&mut self, hdr_override: HdrOverride) -> Result<()> {
+        use tracing::info;
+
+        if let Some(result) = self.forward(AgentCommand::ApplyHdrOverride { hdr_override }) {
+            return result;
+        }
+
+        info!("Applying manual HDR override: {:?}", hdr_override);
+
+        let enable = hdr_override == HdrOverride::KeepOn;
+        self.hdr_override = Some(hdr_override);
+        self.external_hold = None;
+
+        // Turning HDR off by hand also ends a manual session
+        if !enable && self.end_manual_session_tracking() {
+            info!("Manual override ends the manual HDR session");
+        }
+
+        // "Keep HDR on" during the debounce window suppresses the pending disable itself
+        if self.pending_hdr_disable.take().is_some() {
+            info!("Manual override replaces the pending HDR disable");
+            if hdr_override == HdrOverride::KeepOn {
+                self.hdr_override = None;
+            }
+        }
+
+        if self.current_hdr_state.load(Ordering::SeqCst) != enable
+            && let Err(e) = self.toggle_hdr(enable, ToggleTrigger::ManualOverride)
+        {
+            self.send_state_update();
+            return Err(e);
+        }
+
+        // Turning off with nothing running needs no suppression
+        if hdr_override == HdrOverride::TurnOff
+            && self.active_process_count.load(Ordering::SeqCst) == 0
+        {
+            self.hdr_override = None;
+        }
+
+        self.send_state_update();
+        Ok(())
+    }
+
+    /// List the actions that are scheduled or held back.
+    ///
+    /// Lets the GUI show e.g. "HDR turning off in 8 s" with a way to cancel it.
+    pub fn pending_actions(&self) -> Vec<PendingAction> {
+        let mut actions = Vec::new();
+
+        if let Some(deadline) = self.pending_hdr_disable {
+            actions.push(PendingAction {
+                id: PendingActionId::HdrDisable,
+                description: "HDR turning off".to_string(),
+                due_at: Some(deadline),
+            });
+        }
+        if let Some(enable) = self.deferred_hdr_toggle {
+            actions.push(PendingAction {
+                id: PendingActionId::DeferredHdrToggle,
+                description: format!(
+                    "HDR turning {} when the displays wake up",
+                    if enable { "on" } else { "off" }
+                ),
+                due_at: None,
+            });
+        }
+        if let Some(hdr_override) = self.hdr_override {
+            actions.push(PendingAction {
+                id: PendingActionId::HdrOverride,
+                description: match hdr_override {
+                    HdrOverride::KeepOn => "Keeping HDR on after the last application stops",
+                    HdrOverride::TurnOff => "Keeping HDR off while applications run",
+                }
+                .to_string(),
+                due_at: None,
+            });
+        }
+        if self.exclusion_hold.is_some() {
+            let mut names: Vec<String> = self
+                .running_exclusions
+                .iter()
+                .filter_map(|app_id| {
+                    let AppIdentifier::Win32(process_name) = app_id else {
+                        return None;
+                    };
+                    Some(format!("{process_name}.exe"))
+                })
+                .collect();
+            names.sort();
+            actions.push(PendingAction {
+                id: PendingActionId::ExclusionHold,
+                description: format!("HDR held off while {} runs", names.join(", ")),
+                due_at: None,
+            });
+        }
+        if let Some(hold) = &self.external_hold
+            && self.clock.now() < hold.until
+        {
+            actions.push(PendingAction {
+                id: PendingActionId::ExternalHdrHold,
+                description: format!(
+                    "Leaving HDR {} as changed outside EasyHDR",
+                    if hold.enabled { "on" } else { "off" }
+                ),
+                due_at: Some(hold.until),
+            });
+        }
+        let mut snoozes: Vec<_> = self.snoozed_apps.iter().collect();
+        snoozes.sort_by_key(|&(_, until)| *until);
+        for (&id, &until) in snoozes {
+            let name = self
+                .config
+                .read()
+                .monitored_apps
+                .iter()
+                .find(|app| app.id() == &id)
+                .map_or_else(|| id.to_string(), |app| app.display_name().to_string());
+            actions.push(PendingAction {
+                id: PendingActionId::AppSnooze(id),
+                description: format!("{name} snoozed"),
+                due_at: Some(until),
+            });
+        }
+        if self.hdr_conflict_since.is_some() {
+            actions.push(PendingAction {
+                id: PendingActionId::HdrConflictPause,
+                description: "Automatic HDR paused after conflicting HDR changes".to_string(),
+                due_at: None,
+            });
+        }
+
+        actions
+    }
+
+    /// Cancel a scheduled or held back action.
+    ///
+    /// Cancelling the scheduled disable leaves HDR on; cancelling an override hands HDR
+    /// back to automatic management without toggling it now. Cancelling an exclusion
+    /// hold leaves HDR off when the excluded processes exit, cancelling a snooze watches
+    /// the application again and cancelling a conflict pause resumes automatic HDR
+    /// management from the next event. Returns `false` if the
+    /// action was no longer pending, e.g. because it ran in the meantime.
+    pub fn cancel_pending(&mut self, id: PendingActionId) -> bool {
+        use tracing::info;
+
+        if self.is_front_end() {
+            if let PendingActionId::AppSnooze(app_id) = id {
+                self.snoozed_apps.remove(&app_id);
+            }
+            return self.forward_logged(AgentCommand::CancelPending { id });
+        }
+
+        let cancelled = match id {
+            PendingActionId::HdrDisable => self.pending_hdr_disable.take().is_some(),
+            PendingActionId::DeferredHdrToggle => {
+                self.deferred_toggle_app = None;
+                self.deferred_hdr_toggle.take().is_some()
+            }
+            PendingActionId::HdrOverride => self.hdr_override.take().is_some(),
+            PendingActionId::ExclusionHold => self.exclusion_hold.take().is_some(),
+            PendingActionId::ExternalHdrHold => self.external_hold.take().is_some(),
+            PendingActionId::AppSnooze(app_id) => {
+                let resumed = self.snoozed_apps.remove(&app_id).is_some();
+                if resumed {
+                    self.update_process_monitor_watch_list();
+                }
+                resumed
+            }
+            PendingActionId::HdrConflictPause => {
+                self.external_hdr_changes.clear();
+                self.hdr_conflict_since.take().is_some()
+            }
+        };
+
+        if cancelled {
+            info!("Cancelled pending action: {:?}", id);
+            self.send_state_update();
+        }
+        cancelled
+    }
+
+    /// Display modes of the monitored applications, offered for manual HDR sessions.
+    ///
+    /// Each distinct mode is listed once, in the order of the application list.
+    pub fn manual_session_display_modes(&self) -> Vec<DisplayModeOverride> {
+        let mut display_modes: Vec<DisplayModeOverride> = Vec::new();
+        for display_mode in self
+            .config
+            .read()
+            .monitored_apps
+            .iter()
+            .filter_map(MonitoredApp::display_mode)
+            .filter(|display_mode| !display_mode.is_empty())
+        {
+            if !display_modes.contains(&display_mode) {
+                display_modes.push(display_mode);
+            }
+        }
+        display_modes
+    }
+
+    /// Start a manual HDR session without a monitored application.
+    ///
+    /// Turns HDR on, switching to `display_mode` first unless a monitored application
+    /// already chose one, and keeps it on until [`Self::stop_manual_session`] is called or
+    /// `duration` has passed. The session is recorded in the history under
+    /// [`MANUAL_SESSION_ID`]. Starting a session while one is running only replaces its
+    /// duration.
+    pub fn start_manual_session(
+        &mut self,
+        duration: Option<Duration>,
+        display_mode: Option<DisplayModeOverride>,
+    ) -> Result<()> {
+        use tracing::info;
+
+        if let Some(result) = self.forward(AgentCommand::StartManualSession {
+            duration_secs: duration.map(|duration| duration.as_secs()),
+            display_mode,
+        }) {
+            return result;
+        }
+
+        let session = ManualSession {
+            ends_at: duration.map(|duration| self.clock.now() + duration),
+            duration,
+        };
+        if self.manual_session.replace(session).is_some() {
+            info!("Manual HDR session changed to {}", session.describe());
+            self.send_state_update();
+            return Ok(());
+        }
+
+        info!("Starting manual {}", session.describe());
+
+        // An explicit request replaces "keep HDR off" and any pending disable
+        self.hdr_override = None;
+        self.pending_hdr_disable = None;
+
+        let apps_running = self.active_process_count.load(Ordering::SeqCst) > 0;
+        if !apps_running {
+            self.session_display_mode =
+                display_mode.filter(|display_mode| !display_mode.is_empty());
+        }
+
+        if !self.current_hdr_state.load(Ordering::SeqCst)
+            && let Err(e) = self.toggle_hdr(true, ToggleTrigger::ManualSession)
+        {
+            self.manual_session = None;
+            if !apps_running {
+                self.session_display_mode = None;
+            }
+            self.send_state_update();
+            return Err(e);
+        }
+
+        self.sessions.start(
+            MANUAL_SESSION_ID,
+            self.current_hdr_state.load(Ordering::SeqCst),
+        );
+        self.send_state_update();
+        Ok(())
+    }
+
+    /// End the running manual HDR session.
+    ///
+    /// HDR is turned off right away unless a monitored application is still running, in
+    /// which case it stays on for that application. Does nothing without a session.
+    pub fn stop_manual_session(&mut self) -> Result<()> {
+        use tracing::info;
+
+        if let Some(result) = self.forward(AgentCommand::StopManualSession) {
+            return result;
+        }
+
+        if !self.end_manual_session_tracking() {
+            return Ok(());
+        }
+        info!("Manual HDR session ended");
+
+        let mut result = Ok(());
+        if self.active_process_count.load(Ordering::SeqCst) == 0 {
+            self.session_display_mode = None;
+            if self.current_hdr_state.load(Ordering::SeqCst) || self.deferred_hdr_toggle.is_some() {
+                result = self
+                    .toggle_hdr(false, ToggleTrigger::ManualSession)
+                    .map(drop);
+            }
+        }
+
+        self.send_state_update();
+        result
+    }
+
+    /// Simulate HDR toggles instead of making them, or go back to making them.
+    ///
+    /// Used for the `--dry-run` command-line flag; the `dry_run` preference sets it at
+    /// startup. The displays and the believed HDR state are left as they are.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        use tracing::info;
+
+        if dry_run != self.dry_run {
+            info!("Dry run {}", if dry_run { "started" } else { "ended" });
+        }
+        self.dry_run = dry_run;
+        self.hdr_controller.set_dry_run(dry_run);
+        self.send_state_update();
+    }
+
+    /// Whether HDR toggles are only simulated
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Whether HDR is on, as last set or observed by the controller
+    pub fn is_hdr_enabled(&self) -> bool {
+        self.current_hdr_state.load(Ordering::SeqCst)
+    }
+
+    /// Whether a manual HDR session is running
+    pub fn is_manual_session_active(&self) -> bool {
+        self.manual_session.is_some()
+    }
+
+    /// Description of the running manual HDR session, e.g. `HDR session · 1 h`
+    pub fn manual_session_description(&self) -> Option<String> {
+        self.manual_session.as_ref().map(ManualSession::describe)
+    }
+
+    /// Displays the last HDR toggle failed on, `None` once a toggle succeeds
+    pub fn hdr_toggle_error(&self) -> Option<&str> {
+        self.hdr_toggle_error.as_deref()
+    }
+
+    /// Forget the manual HDR session and record it in the history.
+    ///
+    /// Returns `false` if no session was running. Leaves the HDR state alone.
+    fn end_manual_session_tracking(&mut self) -> bool {
+        use tracing::debug;
+
+        if self.manual_session.take().is_none() {
+            return false;
+        }
+
+        if let Some(session) = self.sessions.stop(MANUAL_SESSION_ID) {
+            debug!(
+                "Manual HDR session lasted {}s ({}s with HDR)",
+                session.duration_secs, session.hdr_secs
+            );
+            self.session_history.record(session);
+            self.session_history_dirty = true;
+        }
+        true
+    }
+
+    /// End a timed manual HDR session once its duration has passed.
+    ///
+    /// Called from the event loop on every iteration.
+    fn process_manual_session_deadline(&mut self) {
+        use tracing::{error, info};
+
+        let Some(ends_at) = self.manual_session.and_then(|session| session.ends_at) else {
+            return;
+        };
+        if self.clock.now() < ends_at {
+            return;
+        }
+
+        info!("Manual HDR session time is up");
+        if let Err(e) = self.stop_manual_session() {
+            error!("Failed to turn HDR off after the manual session: {}", e);
+        }
+    }
+
+    /// Current step of first-run setup, `None` once it was finished or skipped
+    pub fn onboarding_step(&self) -> Option<OnboardingStep> {
+        self.onboarding.as_ref().map(|onboarding| onboarding.step)
+    }
+
+    /// First-run setup for the GUI, `None` once it was finished or skipped
+    fn onboarding_state(&self) -> Option<OnboardingState> {
+        let onboarding = self.onboarding.as_ref()?;
+        let hdr_displays = self
+            .hdr_controller
+            .displays()
+            .iter()
+            .filter(|target| target.supports_hdr)
+            .map(DisplayTarget::display_name)
+            .collect();
+        Some(onboarding.state(self.clock.now(), hdr_displays))
+    }
+
+    /// Move first-run setup on to its next step.
+    ///
+    /// Ends a running HDR test first. Moving on from the last step finishes setup, see
+    /// [`Self::finish_onboarding`].
+    pub fn advance_onboarding(&mut self) {
+        use tracing::error;
+
+        let Some(step) = self.onboarding_step() else {
+            return;
+        };
+        if let Err(e) = self.end_hdr_test(None) {
+            error!("Failed to switch HDR back after the HDR test: {}", e);
+        }
+
+        match step.next() {
+            Some(next) => {
+                if let Some(onboarding) = &mut self.onboarding {
+                    onboarding.step = next;
+                }
+                self.send_state_update();
+            }
+            None => self.finish_onboarding(),
+        }
+    }
+
+    /// Finish or skip first-run setup.
+    ///
+    /// Ends a running HDR test and records in the preferences that setup is done, so it
+    /// isn't shown again.
+    pub fn finish_onboarding(&mut self) {
+        use tracing::{error, info};
+
+        if self.onboarding.is_none() {
+            return;
+        }
+        if let Err(e) = self.end_hdr_test(None) {
+            error!("Failed to switch HDR back after the HDR test: {}", e);
+        }
+        self.onboarding = None;
+        info!("First-run setup finished");
+
+        self.config.write().preferences.onboarding_completed = true;
+        self.save_config_gracefully();
+        self.send_state_update();
+    }
+
+    /// Turn HDR on so the user can check it works during first-run setup.
+    ///
+    /// HDR switches back after [`HDR_TEST_DURATION`] unless the user answers earlier with
+    /// [`Self::end_hdr_test`]. If HDR is already on it is left on. Does nothing outside
+    /// the HDR test step or while a test runs.
+    pub fn start_hdr_test(&mut self) -> Result<()> {
+        use tracing::info;
+
+        let hdr_was_on = self.current_hdr_state.load(Ordering::SeqCst);
+        let reverts_at = self.clock.now() + HDR_TEST_DURATION;
+        let Some(onboarding) = self.onboarding.as_mut().filter(|onboarding| {
+            onboarding.step == OnboardingStep::HdrTest && onboarding.hdr_test.is_none()
+        }) else {
+            return Ok(());
+        };
+        onboarding.hdr_test = Some(HdrTest {
+            hdr_was_on,
+            reverts_at,
+        });
+        onboarding.hdr_test_passed = None;
+        info!("Testing HDR for {}s", HDR_TEST_DURATION.as_secs());
+
+        if !hdr_was_on && let Err(e) = self.toggle_hdr(true, ToggleTrigger::HdrTest) {
+            if let Some(onboarding) = &mut self.onboarding {
+                onboarding.hdr_test = None;
+                onboarding.hdr_test_passed = Some(false);
+            }
+            self.send_state_update();
+            return Err(e);
+        }
+
+        self.send_state_update();
+        Ok(())
+    }
+
+    /// End the running HDR test and switch HDR back to how it was.
+    ///
+    /// `worked` is the user's answer whether HDR looked right, `None` if the test ended
+    /// without one. HDR stays on if a monitored application or a manual session needs it
+    /// by now. Does nothing without a running test.
+    pub fn end_hdr_test(&mut self, worked: Option<bool>) -> Result<()> {
+        use tracing::info;
+
+        let Some(onboarding) = &mut self.onboarding else {
+            return Ok(());
+        };
+        let Some(test) = onboarding.hdr_test.take() else {
+            return Ok(());
+        };
+        if worked.is_some() {
+            onboarding.hdr_test_passed = worked;
+        }
+
+        let hdr_needed =
+            self.active_process_count.load(Ordering::SeqCst) > 0 || self.manual_session.is_some();
+        let mut result = Ok(());
+        if !test.hdr_was_on && !hdr_needed && self.current_hdr_state.load(Ordering::SeqCst) {
+            info!("HDR test ended, turning HDR back off");
+            result = self.toggle_hdr(false, ToggleTrigger::HdrTest).map(drop);
+        }
+
+        self.send_state_update();
+        result
+    }
+
+    /// Switch HDR back once the first-run HDR test has run for [`HDR_TEST_DURATION`].
+    ///
+    /// Called from the event loop on every iteration. Until then, sends a state update
+    /// whenever the countdown shown in the GUI changes.
+    fn process_onboarding(&mut self) {
+        use tracing::{error, info};
+
+        let now = self.clock.now();
+        let Some(onboarding) = &mut self.onboarding else {
+            return;
+        };
+        let Some(test) = onboarding.hdr_test else {
+            return;
+        };
+
+        if now >= test.reverts_at {
+            info!("HDR test time is up");
+            if let Err(e) = self.end_hdr_test(None) {
+                error!("Failed to switch HDR back after the HDR test: {}", e);
+            }
+        } else if onboarding.countdown_changed(now) {
+            self.send_state_update();
+        }
+    }
+
+    /// Longest continuous time HDR may stay on for the running applications, `None` for no
+    /// limit
+    ///
+    /// Each running application uses its own limit if it has one, otherwise the global
+    /// preference. The longest limit wins, and an application without a limit lifts it.
+    fn max_hdr_session(&self) -> Option<Duration> {
+        let config = self.config.read();
+        let global = config.preferences.max_hdr_session_minutes;
+
+        let mut longest = None;
+        for id in self.sessions.running_apps() {
+            let minutes = config
+                .monitored_apps
+                .iter()
+                .find(|app| app.id() == &id)
+                .and_then(MonitoredApp::max_hdr_session_minutes)
+                .unwrap_or(global);
+            let limit = max_hdr_session(minutes)?;
+            longest = Some(longest.map_or(limit, |longest: Duration| longest.max(limit)));
+        }
+
+        longest.or_else(|| max_hdr_session(global))
+    }
+
+    /// Turn HDR off once it has been on longer than the session limit.
+    ///
+    /// Called from the event loop on every iteration. Only time since `EasyHDR` turned HDR
+    /// on counts. A warning is sent [`SESSION_LIMIT_WARNING`] before the limit so the user
+    /// can extend the session; at the limit HDR is turned off as if "Turn off now" had been
+    /// picked, so it stays off until the running applications exit.
+    fn process_session_limit(&mut self) {
+        use tracing::{error, info, warn};
+
+        if !self.hdr_enabled_by_app || !self.current_hdr_state.load(Ordering::SeqCst) {
+            self.hdr_session = None;
+            return;
+        }
+
+        let now = self.clock.now();
+        let session = *self.hdr_session.get_or_insert(HdrSessionLimit {
+            since: now,
+            extended_by: Duration::ZERO,
+            warned: false,
+        });
+        let Some(limit) = self.max_hdr_session() else {
+            return;
+        };
+        let ends_at = session.since + limit + session.extended_by;
+        let on_for = describe_minutes(now.saturating_duration_since(session.since).as_secs() / 60);
+
+        if now >= ends_at {
+            warn!(
+                "HDR has been on for {}, turning it off to protect the display",
+                on_for
+            );
+            self.hdr_session = None;
+            if let Err(e) = self.apply_hdr_override(HdrOverride::TurnOff) {
+                error!("Failed to turn HDR off at the session limit: {}", e);
+            }
+        } else if !session.warned && now + SESSION_LIMIT_WARNING >= ends_at {
+            let remaining = ends_at
+                .saturating_duration_since(now)
+                .as_secs()
+                .div_ceil(60);
+            info!("HDR session limit reached in {} min", remaining);
+            self.hdr_session = Some(HdrSessionLimit {
+                warned: true,
+                ..session
+            });
+            *self.pending_session_limit_warning.lock() = Some(format!(
+                "HDR has been on for {on_for} and turns off in {} to protect your display.",
+                describe_minutes(remaining)
+            ));
+            self.send_state_update();
+        }
+    }
+
+    /// Keep HDR on longer than the session limit.
+    ///
+    /// Called from the session limit warning notification; adds
+    /// [`SESSION_LIMIT_EXTENSION`] to the running session. Does nothing while HDR is off.
+    pub fn extend_hdr_session(&mut self) {
+        use tracing::info;
+
+        if self.forward_logged(AgentCommand::ExtendHdrSession) {
+            return;
+        }
+
+        if let Some(session) = &mut self.hdr_session {
+            session.extended_by += SESSION_LIMIT_EXTENSION;
+            session.warned = false;
+            info!(
+                "HDR session extended by {}",
+                describe_minutes(SESSION_LIMIT_EXTENSION.as_secs() / 60)
+            );
+        }
+    }
+
+    /// Remind the user to run OLED panel maintenance after a long HDR session.
+    ///
+    /// Called from the event loop on every iteration. When HDR turns off after being on
+    /// at least as long as a connected OLED display asks for, a reminder naming those
+    /// displays is sent. The pixel refresh itself blanks the screen for several minutes,
+    /// so it only starts when the user picks it from the notification.
+    fn process_oled_care(&mut self) {
+        use tracing::info;
+
+        let hdr_on = self.current_hdr_state.load(Ordering::SeqCst);
+        let since = match (hdr_on, self.oled_hdr_since) {
+            (true, None) => {
+                self.oled_hdr_since = Some(self.clock.now());
+                return;
+            }
+            (false, Some(since)) => since,
+            _ => return,
+        };
+        self.oled_hdr_since = None;
+
+        let session = self.clock.now().saturating_duration_since(since);
+        let due: Vec<(DisplayTarget, Option<VcpCommand>)> = {
+            let config = self.config.read();
+            self.hdr_controller
+                .displays()
+                .iter()
+                .filter(|display| display.supports_hdr)
+                .filter_map(|display| {
+                    let care = config.oled_care(display)?;
+                    (session >= care.due_after())
+                        .then(|| (display.clone(), care.pixel_refresh_command))
+                })
+                .collect()
+        };
+        if due.is_empty() {
+            return;
+        }
+
+        let names: Vec<String> = due
+            .iter()
+            .map(|(display, _)| display.display_name())
+            .collect();
+        let on_for = describe_minutes(session.as_secs() / 60);
+        info!(
+            "HDR was on for {} on OLED display(s) {}, reminding to run panel maintenance",
+            on_for,
+            names.join(", ")
+        );
+
+        self.pixel_refresh_due = due
+            .into_iter()
+            .filter_map(|(display, command)| Some((display, command?)))
+            .collect();
+        *self.pending_oled_care_reminder.lock() = Some(OledCareReminder {
+            message: format!(
+                "HDR was on for {on_for} on {}. Run the panel's pixel refresh to keep the OLED \
+                 panel even; it turns the screen off for a few minutes.",
+                names.join(", ")
+            ),
+            can_start_pixel_refresh: !self.pixel_refresh_due.is_empty(),
+        });
+        self.send_state_update();
+    }
+
+    /// Start the pixel refresh offered by the last OLED care reminder.
+    ///
+    /// Sends the configured DDC/CI command to each display the reminder named. Does
+    /// nothing if no reminder offered a pixel refresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error if a display rejects the command; the remaining displays
+    /// are still tried.
+    pub fn start_pixel_refresh(&mut self) -> Result<()> {
+        use tracing::{info, warn};
+
+        if let Some(result) = self.forward(AgentCommand::StartPixelRefresh) {
+            return result;
+        }
+
+        let mut result = Ok(());
+        for (target, command) in std::mem::take(&mut self.pixel_refresh_due) {
+            info!("Starting pixel refresh on {}", target.display_name());
+            if let Err(e) = self.hdr_controller.send_vcp_command(&target, command) {
+                warn!("Failed to start pixel refresh on {}: {}", target, e);
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Connected displays that can have per-display settings, with their stored settings
+    ///
+    /// Only displays with a stable identity are listed, since settings are stored by it.
+    pub fn display_settings(&self) -> Vec<(DisplayTarget, KnownDisplay)> {
+        let config = self.config.read();
+        self.hdr_controller
+            .displays()
+            .iter()
+            .filter_map(|display| {
+                let known = config.known_display(display.identity.as_ref()?)?;
+                Some((display.clone(), known.clone()))
+            })
+            .collect()
+    }
+
+    /// Current color mode of a display, `None` if it cannot be read
+    pub fn display_color_mode(&self, target: &DisplayTarget) -> Option<ColorMode> {
+        self.hdr_controller.color_mode(target).ok()
+    }
+
+    /// Warning when HDR on a display falls back to subsampled chroma because its link is
+    /// too slow at the current mode, `None` if HDR is off or fits, or the signal cannot
+    /// be read
+    pub fn display_link_advisory(&self, target: &DisplayTarget) -> Option<BandwidthAdvisory> {
+        if !target.supports_hdr {
+            return None;
+        }
+        let signal = self.hdr_controller.link_signal(target).ok()?;
+        let hdr_enabled = self.hdr_controller.is_hdr_enabled(target).ok()?;
+        link_bandwidth::hdr_advisory(target.connector, signal, hdr_enabled)
+    }
+
+    /// HDR state of each connected HDR-capable display
+    ///
+    /// Displays whose state cannot be read are reported with HDR off.
+    pub fn display_hdr_states(&self) -> Vec<DisplayHdrState> {
+        self.hdr_controller
+            .displays()
+            .iter()
+            .filter(|display| display.supports_hdr)
+            .map(|display| DisplayHdrState {
+                target: display.clone(),
+                hdr_enabled: self.hdr_controller.is_hdr_enabled(display).unwrap_or(false),
+            })
+            .collect()
+    }
+
+    /// Turn HDR on or off on a single connected display right away, leaving the other
+    /// displays as they are.
+    ///
+    /// Like [`Self::set_display_color_mode`], the HDR state monitor picks up the
+    /// resulting HDR state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the display is no longer connected or HDR cannot be set on it.
+    pub fn set_display_hdr(&self, target: &DisplayTarget, enable: bool) -> Result<()> {
+        use tracing::info;
+
+        if self.is_front_end() {
+            let Some(identity) = target.identity.clone() else {
+                return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                    format!("display {} cannot be told apart", target.display_name()),
+                )));
+            };
+            return self
+                .forward(AgentCommand::SetDisplayHdr {
+                    identity,
+                    enabled: enable,
+                })
+                .unwrap_or(Ok(()));
+        }
+
+        let Some(connected) = self.hdr_controller.displays().iter().find(|display| {
+            display.adapter_id == target.adapter_id && display.target_id == target.target_id
+        }) else {
+            return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                format!("display {} is not connected", target.display_name()),
+            )));
+        };
+
+        let result = self.hdr_controller.set_hdr_state(connected, enable);
+        // Sent on failure too, so the tray unchecks what the click checked
+        self.send_state_update();
+        result?;
+        info!(
+            "Turned HDR {} on {}",
+            if enable { "on" } else { "off" },
+            connected
+        );
+        Ok(())
+    }
+
+    /// Switch a connected display to a color mode right away.
+    ///
+    /// Works like changing the mode in the Windows display settings: the HDR state
+    /// monitor picks up the resulting HDR state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the display is not connected or the mode cannot be set, e.g.
+    /// WCG before Windows 11 24H2.
+    pub fn set_display_color_mode(
+        &self,
+        identity: &DisplayIdentity,
+        mode: ColorMode,
+    ) -> Result<()> {
+        use tracing::info;
+
+        if let Some(result) = self.forward(AgentCommand::SetDisplayColorMode {
+            identity: identity.clone(),
+            mode,
+        }) {
+            return result;
+        }
+
+        let Some(target) = self
+            .hdr_controller
+            .displays()
+            .iter()
+            .find(|target| target.identity.as_ref() == Some(identity))
+        else {
+            return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                format!(
+                    "display {} {:04X} is not connected",
+                    identity.manufacturer, identity.product_code
+                ),
+            )));
+        };
+
+        self.hdr_controller.set_color_mode(target, mode)?;
+        info!("Switched {} to {}", target, mode);
+        Ok(())
+    }
+
+    /// Set the color profile applied when HDR turns on for a display, or clear it with
+    /// `None`, and save to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile is not a file name or the display is not among
+    /// the known displays.
+    pub fn set_hdr_color_profile(
+        &mut self,
+        identity: &DisplayIdentity,
+        profile: Option<&str>,
+    ) -> Result<()> {
+        use tracing::info;
+
+        let profile = profile
+            .map(|profile| color_profile::validate_profile_name(profile).map(str::to_string))
+            .transpose()?;
+        if !self
+            .config
+            .write()
+            .set_hdr_color_profile(identity, profile.clone())
+        {
+            return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                format!(
+                    "display {} {:04X} is not a known display",
+                    identity.manufacturer, identity.product_code
+                ),
+            )));
+        }
+        if let Some(profile) = profile {
+            info!(
+                "Display {} {:04X} uses HDR color profile '{}'",
+                identity.manufacturer, identity.product_code, profile
+            );
+        } else {
+            info!(
+                "Display {} {:04X} keeps the Windows default HDR color profile",
+                identity.manufacturer, identity.product_code
+            );
+        }
+
+        self.save_config_gracefully();
+
+        Ok(())
+    }
+
+    /// Tag a display as OLED with the given care settings, or untag it with `None`, and
+    /// save to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the display is not among the known displays.
+    pub fn set_oled_care(
+        &mut self,
+        identity: &DisplayIdentity,
+        oled_care: Option<OledCare>,
+    ) -> Result<()> {
+        use tracing::info;
+
+        if !self
+            .config
+            .write()
+            .set_oled_care(identity, oled_care.clone())
+        {
+            return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                format!(
+                    "display {} {:04X} is not a known display",
+                    identity.manufacturer, identity.product_code
+                ),
+            )));
+        }
+        if let Some(care) = oled_care {
+            info!(
+                "Display {} {:04X} tagged as OLED, maintenance due after {} min",
+                identity.manufacturer, identity.product_code, care.after_minutes
+            );
+        } else {
+            info!(
+                "Display {} {:04X} no longer tagged as OLED",
+                identity.manufacturer, identity.product_code
+            );
+        }
+
+        self.save_config_gracefully();
+
+        Ok(())
+    }
+
+    /// Re-enumerate displays and update HDR controller's display cache.
+    /// Call when display configuration changes (e.g., monitor connected/disconnected).
+    pub fn refresh_displays(&mut self) -> Result<()> {
+        use tracing::info;
+
+        info!("Refreshing display list due to potential display configuration change");
+        let displays = self.hdr_controller.enumerate_displays().map_err(|e| {
+            use tracing::error;
+            error!("Failed to refresh display list: {e}");
+            EasyHdrError::HdrControlFailed(Box::new(e))
+        })?;
+        info!(
+            "Display list refreshed: {} display(s) found ({} HDR-capable)",
+            displays.len(),
+            displays.iter().filter(|d| d.supports_hdr).count()
+        );
+        self.remember_displays();
+        Ok(())
+    }
+
+    /// Persist the stable identities of the currently connected displays
+    ///
+    /// Keeps `known_displays` in the config up to date so per-display settings can be
+    /// resolved back to live targets after reboots, when adapter LUIDs change.
+    fn remember_displays(&self) {
+        use tracing::info;
+
+        let changed = self
+            .config
+            .write()
+            .remember_displays(self.hdr_controller.displays());
+        if changed {
+            info!("Known displays updated, saving configuration");
+            self.save_config_gracefully();
+        }
+    }
+
+    /// Save configuration with graceful error handling
+    ///
+    /// Attempts to save the current configuration to disk. Failures are logged
+    /// but do not propagate errors, allowing the application to continue with
+    /// in-memory configuration. This implements graceful degradation for config
+    /// persistence.
+    ///
+    /// # Design
+    ///
+    /// This helper consolidates the config save pattern used across multiple
+    /// methods in `AppController`, eliminating ~20 lines of duplication.
+    fn save_config_gracefully(&self) {
+        use tracing::warn;
+
+        let config = self.config.read();
+        if let Err(e) = ConfigManager::save(&config) {
+            warn!(
+                "Failed to save configuration to disk: {}. Continuing with in-memory config. \
+                 Changes will be lost on application restart.",
+                e
+            );
+            return;
+        }
+        drop(config);
+        self.notify_config_saved();
+    }
+
+    /// Ask the background agent to re-read the configuration file if this controller is
+    /// its front-end
+    ///
+    /// Called after every save, including those the GUI makes itself.
+    pub fn notify_config_saved(&self) {
+        self.forward_logged(AgentCommand::ReloadConfig);
+    }
+
+    /// Replace the configuration with the one a front-end window saved and apply it
+    ///
+    /// Called by the background agent. Applies the watch list, the log level and the
+    /// other process-wide preferences, like saving the settings dialog does in a window
+    /// running the controller itself.
+    pub fn reload_config(&mut self, config: AppConfig) {
+        use tracing::info;
+
+        info!(
+            "Reloading configuration with {} monitored apps",
+            config.monitored_apps.len()
+        );
+        crate::utils::set_log_level(config.preferences.log_level);
+        event_log::set_enabled(config.preferences.write_event_log);
+        metrics::get_metrics().set_enabled(config.preferences.metrics_enabled);
+        *self.config.write() = config;
+        self.update_process_monitor_watch_list();
+        self.apply_discord_preferences();
+        self.send_state_update();
+    }
+
+    /// Turn HDR off once a scheduled disable is due.
+    ///
+    /// Called from the event loop on every iteration; does nothing until the debounce
+    /// window of the last stopped application has passed.
+    fn process_pending_hdr_disable(&mut self) {
+        use tracing::{error, info};
+
+        let Some(deadline) = self.pending_hdr_disable else {
+            return;
+        };
+        if self.clock.now() < deadline {
+            return;
+        }
+        self.pending_hdr_disable = None;
+
+        if self.active_process_count.load(Ordering::SeqCst) > 0
+            || self.manual_session.is_some()
+            || !self.current_hdr_state.load(Ordering::SeqCst)
+        {
+            return;
+        }
+
+        info!("No monitored application returned within the debounce window, disabling HDR");
+        match self.toggle_hdr(false, ToggleTrigger::AppsExited) {
+            Ok(ToggleOutcome::Applied) => self
+                .pending_auto_toggle_notification
+                .store(true, Ordering::SeqCst),
+            Ok(ToggleOutcome::Deferred | ToggleOutcome::Skipped) => {}
+            Err(e) => error!("Failed to disable HDR: {}", e),
+        }
+        self.send_state_update();
+    }
+
+    /// Delay before HDR is turned off after the application matching an identifier stops.
+    ///
+    /// Uses the application's own override if it has one, otherwise the global preference.
+    fn hdr_disable_debounce_for(&self, normalized_id: &AppIdentifier) -> Duration {
+        let config = self.config.read();
+        config
+            .monitored_apps
+            .iter()
+            .find(|app| {
+                Self::normalize_app_identifier(&AppIdentifier::from_monitored_app(app))
+                    == *normalized_id
+            })
+            .and_then(MonitoredApp::hdr_disable_debounce_ms)
+            .map_or_else(
+                || config.preferences.hdr_disable_debounce(),
+                hdr_disable_debounce,
+            )
+    }
+
+    /// Set the per-application HDR disable delay by UUID and save to disk.
+    ///
+    /// `None` falls back to the global preference.
+    pub fn set_hdr_disable_debounce(&mut self, id: Uuid, debounce_ms: Option<u64>) -> Result<()> {
+        use tracing::info;
+
+        let debounce_ms = debounce_ms.map(|ms| ms.min(MAX_HDR_DISABLE_DEBOUNCE_MS));
+        if let Some(ms) = debounce_ms {
+            info!("Setting HDR disable delay for {} to {}ms", id, ms);
+        } else {
+            info!("Clearing HDR disable delay override for {}", id);
+        }
+
+        {
+            let mut config = self.config.write();
+            if let Some(app) = config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
+                app.set_hdr_disable_debounce_ms(debounce_ms);
+            }
+        }
+
+        self.save_config_gracefully();
+
+        Ok(())
+    }
+
+    /// Set the per-application HDR session limit in minutes by UUID and save to disk.
+    ///
+    /// `None` falls back to the global preference; `Some(0)` lifts the limit while the
+    /// application runs.
+    pub fn set_max_hdr_session(&mut self, id: Uuid, minutes: Option<u32>) -> Result<()> {
+        use tracing::info;
+
+        match minutes {
+            Some(0) => info!("Removing the HDR session limit for {}", id),
+            Some(minutes) => info!("Setting HDR session limit for {} to {} min", id, minutes),
+            None => info!("Clearing HDR session limit override for {}", id),
+        }
+
+        {
+            let mut config = self.config.write();
+            if let Some(app) = config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
+                app.set_max_hdr_session_minutes(minutes);
+            }
+        }
+
+        self.save_config_gracefully();
+
+        Ok(())
+    }
+
+    /// Change the log level, apply it to the running logger and save to disk.
+    pub fn set_log_level(&self, level: LogLevel) {
+        self.config.write().preferences.log_level = level;
+        crate::utils::set_log_level(level);
+
+        self.save_config_gracefully();
+        self.send_state_update();
+    }
+
+    /// Apply the shutdown policy before `EasyHDR` exits.
+    ///
+    /// Turns HDR off as the [`ShutdownPolicy`] preference asks, saves pending session
+    /// history and disarms the crash guard so the next launch leaves HDR alone. Called
+    /// from the GUI right before the process exits.
+    pub fn shutdown(&mut self) {
+        use tracing::{error, info};
+
+        let policy = self.config.read().preferences.shutdown_policy;
+        info!("Shutting down with policy {}", policy.as_str());
+
+        if self.should_turn_off_hdr_on_shutdown(policy) {
+            // A toggle deferred until the displays wake up would never run
+            self.displays_on = true;
+            if let Err(e) = self.toggle_hdr(false, ToggleTrigger::Shutdown) {
+                error!("Failed to turn HDR off on shutdown: {}", e);
+            }
+        }
+
+        // Night Light comes back even when HDR is left on
+        if let Err(e) = self.night_light.restore() {
+            error!("Failed to turn Night Light back on on shutdown: {}", e);
+        }
+
+        self.end_manual_session_tracking();
+        self.end_running_sessions();
+        self.update_session_history();
+        Self::save_metrics();
+        crash_guard::disarm();
+    }
+
+    /// Save state for another `EasyHDR` process to take over, without applying the
+    /// shutdown policy
+    ///
+    /// HDR stays as it is and the next process picks up the running applications from
+    /// its first scan. Display settings applied for a running application stay applied.
+    pub fn hand_off(&mut self) {
+        use tracing::info;
+
+        info!("Handing off to another EasyHDR process");
+        self.end_manual_session_tracking();
+        self.end_running_sessions();
+        self.update_session_history();
+        Self::save_metrics();
+        crash_guard::disarm();
+    }
+
+    /// Record the sessions of applications still running when `EasyHDR` exits.
+    ///
+    /// The time played so far counts as a finished session; the next process starts a
+    /// new one from its first scan.
+    fn end_running_sessions(&mut self) {
+        use tracing::debug;
+
+        // Account HDR time up to now before the sessions end
+        self.sessions
+            .set_hdr(self.current_hdr_state.load(Ordering::SeqCst) && !self.dry_run);
+        for session in self.sessions.stop_all() {
+            debug!(
+                "Session of {} still running on exit, recorded after {}s ({}s with HDR)",
+                session.app_id, session.duration_secs, session.hdr_secs
+            );
+            self.session_history.record(session);
+            self.session_history_dirty = true;
+        }
+    }
+
+    /// Write the local metrics file if metrics are enabled and changed.
+    fn save_metrics() {
+        use tracing::warn;
+
+        if let Err(e) = metrics::get_metrics().save_if_changed() {
+            warn!("Failed to save metrics: {}", e);
+        }
+    }
+
+    /// Whether the shutdown policy requires turning HDR off
+    fn should_turn_off_hdr_on_shutdown(&self, policy: ShutdownPolicy) -> bool {
+        match policy {
+            ShutdownPolicy::LeaveAsIs => false,
+            ShutdownPolicy::RestorePrevious => self.hdr_enabled_by_app,
+            ShutdownPolicy::ForceOff => {
+                self.current_hdr_state.load(Ordering::SeqCst)
+                    || self.hdr_controller.detect_current_hdr_state()
+            }
+        }
+    }
+
+    /// Look up the UUID of the monitored application matching an identifier.
+    fn app_id_for(&self, normalized_id: &AppIdentifier) -> Option<Uuid> {
+        self.config
+            .read()
+            .monitored_apps
+            .iter()
+            .find(|app| {
+                Self::normalize_app_identifier(&AppIdentifier::from_monitored_app(app))
+                    == *normalized_id
+            })
+            .map(|app| *app.id())
+    }
+
+    /// Account HDR time to running sessions and save finished ones.
+    ///
+    /// Called from the event loop on every iteration, so HDR time is tracked no matter
+    /// what toggled HDR and the history file is written from the controller thread.
+    fn update_session_history(&mut self) {
+        use tracing::warn;
+
+        // Simulated HDR time is not usage
+        self.sessions
+            .set_hdr(self.current_hdr_state.load(Ordering::SeqCst) && !self.dry_run);
+
+        if self.session_history_dirty {
+            self.session_history_dirty = false;
+            if let Err(e) = self.session_history.save() {
+                warn!(
+                    "Failed to save session history: {}. Continuing with in-memory history.",
+                    e
+                );
+            }
+        }
+    }
+
+    /// Get the session statistics of an application by UUID.
+    ///
+    /// Only finished sessions are counted; a session still running is included once
+    /// the application stops or `EasyHDR` exits.
+    pub fn get_app_stats(&self, id: Uuid) -> AppStats {
+        self.session_history.app_stats(id)
+    }
+
+    /// Statistics of every monitored application and of manual HDR sessions, most HDR
+    /// time first.
+    ///
+    /// Applications that never ran are included with empty statistics, which shows the
+    /// ones `EasyHDR` hasn't seen start yet.
+    pub fn all_app_stats(&self) -> Vec<(String, AppStats)> {
+        let config = self.config.read();
+        self.session_history
+            .app_rows(&config.monitored_apps)
+            .into_iter()
+            .map(|(name, stats)| (name.to_string(), stats))
+            .collect()
+    }
+
+    /// Recent HDR toggles with what triggered them, newest first.
+    pub fn get_history(&self) -> Vec<ToggleRecord> {
+        self.toggle_history.records().cloned().collect()
+    }
+
+    /// Export statistics covering all monitored applications.
+    pub fn export_stats(&self, format: StatsFormat) -> Result<String> {
+        let config = self.config.read();
+        self.session_history
+            .export(&config.monitored_apps, format, SystemTime::now())
+    }
+
+    /// Queue the weekly HDR usage summary once a week has passed since the last one.
+    ///
+    /// Called from the event loop alongside reconciliation. The first check after the
+    /// preference is enabled only starts the week.
+    pub fn check_weekly_summary(&mut self) {
+        use tracing::info;
+
+        let now = SystemTime::now();
+        let now_secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        let (enabled, last) = {
+            let preferences = &self.config.read().preferences;
+            (
+                preferences.weekly_summary_notification,
+                preferences.last_weekly_summary_time,
+            )
+        };
+        if !enabled
+            || (last != 0 && now_secs.saturating_sub(last) < WEEKLY_SUMMARY_PERIOD.as_secs())
+        {
+            return;
+        }
+
+        self.config.write().preferences.last_weekly_summary_time = now_secs;
+        self.save_config_gracefully();
+        if last == 0 {
+            return;
+        }
+
+        if let Some(summary) = self.session_history.weekly_summary(now) {
+            info!("Weekly HDR summary: {}", summary);
+            *self.pending_weekly_summary.lock() = Some(summary);
+            self.send_state_update();
+        }
+    }
+
+    /// Look up the display mode of the enabled monitored application matching an identifier.
+    fn display_mode_for(&self, normalized_id: &AppIdentifier) -> Option<DisplayModeOverride> {
+        let config = self.config.read();
+        config
+            .monitored_apps
+            .iter()
+            .filter(|app| app.is_enabled())
+            .find(|app| {
+                Self::normalize_app_identifier(&AppIdentifier::from_monitored_app(app))
+                    == *normalized_id
+            })
+            .and_then(MonitoredApp::display_mode)
+    }
+
+    /// Look up the capture tone mapping of the enabled monitored application matching an
+    /// identifier.
+    fn capture_tone_mapping_for(&self, normalized_id: &AppIdentifier) -> Option<bool> {
+        let config = self.config.read();
+        config
+            .monitored_apps
+            .iter()
+            .filter(|app| app.is_enabled())
+            .find(|app| {
+                Self::normalize_app_identifier(&AppIdentifier::from_monitored_app(app))
+                    == *normalized_id
+            })
+            .and_then(MonitoredApp::capture_tone_mapping)
+    }
+
+    /// Look up the display group of the enabled monitored application matching an
+    /// identifier.
+    ///
+    /// Groups the configuration doesn't define are ignored with a warning.
+    fn display_group_for(&self, normalized_id: &AppIdentifier) -> Option<DisplayGroup> {
+        use tracing::warn;
+
+        let config = self.config.read();
+        let name = config
+            .monitored_apps
+            .iter()
+            .filter(|app| app.is_enabled())
+            .find(|app| {
+                Self::normalize_app_identifier(&AppIdentifier::from_monitored_app(app))
+                    == *normalized_id
+            })
+            .and_then(MonitoredApp::display_group)?;
+        let group = config.display_group(name).cloned();
+        if group.is_none() {
+            warn!(
+                "Display group '{}' is not defined, turning HDR on for every display",
+                name
+            );
+        }
+        group
+    }
+
+    /// Displays an HDR toggle is limited to, `None` for every HDR display
+    ///
+    /// Turning HDR on uses the union of the display groups of the running applications,
+    /// falling back to every display when one of them has no group or none of a group's
+    /// displays with HDR support is connected. Turning it off uses the displays the
+    /// groups turned on.
+    fn group_toggle_targets(&self, enable: bool) -> Option<Vec<DisplayTarget>> {
+        use tracing::{debug, warn};
+
+        if !enable {
+            return self.group_hdr_displays.clone();
+        }
+        let mut targets: Vec<DisplayTarget> = Vec::new();
+        let mut names = Vec::new();
+        for (_, group) in &self.session_display_groups {
+            let group = group.as_ref()?;
+            let resolved: Vec<DisplayTarget> = self
+                .hdr_controller
+                .resolve_displays(&group.displays)
+                .into_iter()
+                .filter(|target| target.supports_hdr)
+                .collect();
+            if resolved.is_empty() {
+                warn!(
+                    "No HDR display of group '{}' is connected, turning HDR on for every display",
+                    group.name
+                );
+                return None;
+            }
+            for target in resolved {
+                if !targets.iter().any(|known| is_same_display(known, &target)) {
+                    targets.push(target);
+                }
+            }
+            if !names.contains(&group.name.as_str()) {
+                names.push(group.name.as_str());
+            }
+        }
+        if targets.is_empty() {
+            return None;
+        }
+        debug!(
+            "Limiting HDR to display group(s) '{}' ({} display(s))",
+            names.join("', '"),
+            targets.len()
+        );
+        Some(targets)
+    }
+
+    /// Whether the running applications need HDR on a display that has it off
+    ///
+    /// Applications bound to display groups need it on each display of their groups,
+    /// even while a display outside them, such as a desk monitor, already has HDR on.
+    /// Other applications need it on every display, which is the case once HDR is on
+    /// and not limited to display groups.
+    fn session_lacks_hdr(&self) -> bool {
+        match self.group_toggle_targets(true) {
+            Some(targets) => targets.iter().any(|target| !self.display_has_hdr(target)),
+            None => {
+                !self.current_hdr_state.load(Ordering::SeqCst) || self.group_hdr_displays.is_some()
+            }
+        }
+    }
+
+    /// Displays to turn HDR back off for at the end of the session, once HDR was turned
+    /// on for `targets`
+    ///
+    /// Displays turned on earlier for other display groups are kept. `None` stands for
+    /// every display.
+    fn group_displays_after_enable(
+        &self,
+        targets: Option<Vec<DisplayTarget>>,
+    ) -> Option<Vec<DisplayTarget>> {
+        let mut displays = targets?;
+        match &self.group_hdr_displays {
+            // The session already turned HDR on for every display
+            None if self.hdr_enabled_by_app && self.current_hdr_state.load(Ordering::SeqCst) => {
+                return None;
+            }
+            None => {}
+            Some(earlier) => {
+                for known in earlier {
+                    if !displays
+                        .iter()
+                        .any(|display| is_same_display(display, known))
+                    {
+                        displays.push(known.clone());
+                    }
+                }
+            }
+        }
+        Some(displays)
+    }
+
+    /// Look up the display name of the monitored application matching an identifier.
+    fn app_name_for(&self, normalized_id: &AppIdentifier) -> Option<String> {
+        self.config
+            .read()
+            .monitored_apps
+            .iter()
+            .find(|app| {
+                Self::normalize_app_identifier(&AppIdentifier::from_monitored_app(app))
+                    == *normalized_id
+            })
+            .map(|app| app.display_name().to_string())
+    }
+
+    /// Look up the color mode of the enabled monitored application matching an identifier.
+    fn color_mode_for(&self, normalized_id: &AppIdentifier) -> Option<ColorMode> {
+        let config = self.config.read();
+        config
+            .monitored_apps
+            .iter()
+            .filter(|app| app.is_enabled())
+            .find(|app| {
+                Self::normalize_app_identifier(&AppIdentifier::from_monitored_app(app))
+                    == *normalized_id
+            })
+            .and_then(MonitoredApp::color_mode)
+    }
+
+    /// Switch the session's HDR-capable displays to wide color gamut, or back to the
+    /// color mode they had before.
+    ///
+    /// Used instead of `toggle_hdr()` for applications bound to WCG, so the believed HDR
+    /// state is left alone. Like HDR, WCG is limited to the display group of the session
+    /// and registered with the crash guard. Failures are logged and leave the display as
+    /// it was.
+    fn set_wcg_session(&mut self, enable: bool) {
+        use tracing::warn;
+
+        if enable {
+            let targets = self.group_toggle_targets(true).unwrap_or_else(|| {
+                self.hdr_controller
+                    .displays()
+                    .iter()
+                    .filter(|target| target.supports_hdr)
+                    .cloned()
+                    .collect()
+            });
+            for target in targets {
+                let previous = match self.hdr_controller.color_mode(&target) {
+                    Ok(ColorMode::Wcg) => continue,
+                    Ok(previous) => previous,
+                    Err(e) => {
+                        warn!("Failed to read the color mode of {}: {}", target, e);
+                        continue;
+                    }
+                };
+                match self.hdr_controller.set_color_mode(&target, ColorMode::Wcg) {
+                    Ok(()) => self.wcg_displays.push((target, previous)),
+                    Err(e) => warn!("Failed to switch {} to {}: {}", target, ColorMode::Wcg, e),
+                }
+            }
+            // Remember the color modes to restore if EasyHDR dies during the session
+            if !self.wcg_displays.is_empty() {
+                crash_guard::arm_color_modes(&self.wcg_displays);
+            }
+        } else {
+            for (target, previous) in std::mem::take(&mut self.wcg_displays) {
+                if let Err(e) = self.hdr_controller.set_color_mode(&target, previous) {
+                    warn!("Failed to switch {} back to {}: {}", target, previous, e);
+                }
+            }
+            crash_guard::disarm();
+        }
+        self.hdr_controller.refresh_capabilities();
+        self.wcg_session = enable;
+    }
+
+    /// Whether displays can be switched to wide color gamut (Windows 11 24H2 or later)
+    pub fn color_modes_supported(&self) -> bool {
+        Capabilities::for_version(self.hdr_controller.windows_version()).hdr_state_api
+    }
+
+    /// Set the color mode used instead of HDR for an application by UUID and save to disk.
+    ///
+    /// Takes effect the next time the application starts a session. `None` and
+    /// [`ColorMode::Hdr`] both keep the default of turning HDR on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for [`ColorMode::Sdr`]; an application that should not change the
+    /// displays is disabled instead.
+    pub fn set_app_color_mode(&mut self, id: Uuid, color_mode: Option<ColorMode>) -> Result<()> {
+        use tracing::info;
+
+        let color_mode = color_mode.filter(|color_mode| *color_mode != ColorMode::Hdr);
+        if color_mode == Some(ColorMode::Sdr) {
+            return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                "an application can use HDR or WCG, not SDR",
+            )));
+        }
+        info!(
+            "Application {} uses {}",
+            id,
+            color_mode.unwrap_or(ColorMode::Hdr)
+        );
+
+        {
+            let mut config = self.config.write();
+            if let Some(app) = config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
+                app.set_color_mode(color_mode);
+            }
+        }
+
+        self.save_config_gracefully();
+        self.send_state_update();
+
+        Ok(())
+    }
+
+    /// Set the display mode applied together with HDR for an application by UUID and
+    /// save to disk.
+    ///
+    /// Takes effect the next time the application turns HDR on. `None` clears the mode.
+    pub fn set_display_mode(
+        &mut self,
+        id: Uuid,
+        display_mode: Option<DisplayModeOverride>,
+    ) -> Result<()> {
+        use tracing::info;
+
+        let display_mode = display_mode.filter(|display_mode| !display_mode.is_empty());
+        if let Some(display_mode) = display_mode {
+            info!("Setting display mode for {} to {}", id, display_mode);
+        } else {
+            info!("Clearing display mode for {}", id);
+        }
+
+        {
+            let mut config = self.config.write();
+            if let Some(app) = config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
+                app.set_display_mode(display_mode);
+            }
+        }
+
+        self.save_config_gracefully();
+        self.send_state_update();
+
+        Ok(())
+    }
+
+    /// Whether tone mapping of HDR captures can be switched on this system
+    pub fn capture_tone_mapping_supported(&self) -> bool {
+        self.capture_tone_mapping.is_supported()
+    }
+
+    /// Set the tone mapping of HDR captures applied together with HDR for an application
+    /// by UUID and save to disk.
+    ///
+    /// Takes effect the next time the application turns HDR on. `None` leaves the Windows
+    /// setting alone.
+    pub fn set_capture_tone_mapping(
+        &mut self,
+        id: Uuid,
+        capture_tone_mapping: Option<bool>,
+    ) -> Result<()> {
+        use tracing::info;
+
+        if let Some(enable) = capture_tone_mapping {
+            info!(
+                "Setting tone mapping of HDR captures for {} to {}",
+                id,
+                if enable { "on" } else { "off" }
+            );
+        } else {
+            info!("Clearing tone mapping of HDR captures for {}", id);
+        }
+
+        {
+            let mut config = self.config.write();
+            if let Some(app) = config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
+                app.set_capture_tone_mapping(capture_tone_mapping);
+            }
+        }
+
+        self.save_config_gracefully();
+        self.send_state_update();
+
+        Ok(())
+    }
+
+    /// Replace the settings of an application by UUID, save to disk and update the watch
+    /// list.
+    ///
+    /// Everything is checked before anything changes, so an invalid field leaves the
+    /// application as it was. Changes take effect the next time the application starts a
+    /// session.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no application has the UUID, e.g. because it was removed in the
+    /// meantime, the display name is empty, the color mode is SDR, the match rule does not
+    /// compile, the display group is not defined, or a UWP or folder application is given
+    /// a match rule or aliases.
+    pub fn update_application(&mut self, id: Uuid, settings: AppSettings) -> Result<()> {
+        use tracing::info;
+
+        let invalid = |message: &str| {
+            Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                message,
+            )))
+        };
+        let display_name = settings.display_name.trim();
+        if display_name.is_empty() {
+            return invalid("the display name is empty");
+        }
+        let color_mode = settings
+            .color_mode
+            .filter(|color_mode| *color_mode != ColorMode::Hdr);
+        if color_mode == Some(ColorMode::Sdr) {
+            return invalid("an application can use HDR or WCG, not SDR");
+        }
+        if let Some(match_rule) = &settings.match_rule {
+            match_rule.validate()?;
+        }
+
+        {
+            let mut config = self.config.write();
+            let Some(index) = config.monitored_apps.iter().position(|app| app.id() == &id) else {
+                return invalid("the application is no longer in the watch list");
+            };
+            // Groups are stored with their configured spelling; a group that is no longer
+            // defined may stay as it was
+            let display_group = match settings
+                .display_group
+                .as_deref()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+            {
+                None => None,
+                Some(name) => match config.display_group(name) {
+                    Some(group) => Some(group.name.clone()),
+                    None if config.monitored_apps[index].display_group() == Some(name) => {
+                        Some(name.to_string())
+                    }
+                    None => return invalid("the display group is not defined"),
+                },
+            };
+            let app = &mut config.monitored_apps[index];
+            match app {
+                MonitoredApp::Win32(app) => {
+                    app.match_rule = settings.match_rule;
+                    app.aliases = settings.aliases;
+                }
+                MonitoredApp::Uwp(_) | MonitoredApp::Folder(_) => {
+                    if settings.match_rule.is_some() || !settings.aliases.is_empty() {
+                        return invalid(
+                            "only applications added from an executable can have a match rule or aliases",
+                        );
+                    }
+                }
+            }
+            info!("Updating settings of {} ({})", id, display_name);
+            app.set_display_name(display_name.to_string());
+            app.set_color_mode(color_mode);
+            app.set_hdr_disable_debounce_ms(
+                settings
+                    .hdr_disable_debounce_ms
+                    .map(|ms| ms.min(MAX_HDR_DISABLE_DEBOUNCE_MS)),
+            );
+            app.set_display_mode(
+                settings
+                    .display_mode
+                    .filter(|display_mode| !display_mode.is_empty()),
+            );
+            app.set_display_group(display_group);
+        }
+
+        self.save_config_gracefully();
+        self.update_process_monitor_watch_list();
+        self.send_state_update();
+
+        Ok(())
+    }
+
+    /// Set the alternative executables of a Win32 application by UUID, save to disk and
+    /// update the watch list.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the application is not a Win32 application.
+    pub fn set_app_aliases(&mut self, id: Uuid, aliases: Vec<String>) -> Result<()> {
+        use tracing::info;
+
+        {
+            let mut config = self.config.write();
+            match config.monitored_apps.iter_mut().find(|app| app.id() == &id) {
+                Some(MonitoredApp::Win32(app)) => {
+                    info!("Setting aliases for {} to {:?}", id, aliases);
+                    app.aliases = aliases;
+                }
+                Some(MonitoredApp::Uwp(_) | MonitoredApp::Folder(_)) => {
+                    return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                        "only applications added from an executable can have aliases",
+                    )));
+                }
+                None => return Ok(()),
+            }
+        }
+
+        self.save_config_gracefully();
+        self.update_process_monitor_watch_list();
+        self.send_state_update();
+
+        Ok(())
+    }
+
+    /// Normalize `AppIdentifier` for case-insensitive matching.
+    ///
+    /// Win32 process names are normalized with [`normalize_process_name`]. UWP and folder
+    /// identifiers keep their spelling; `AppIdentifier` already compares them
+    /// case-insensitively.
+    fn normalize_app_identifier(app_id: &AppIdentifier) -> AppIdentifier {
+        match app_id {
+            AppIdentifier::Win32(process_name) => {
+                AppIdentifier::Win32(normalize_process_name(process_name))
+            }
+            AppIdentifier::Uwp(package_family_name) => {
+                AppIdentifier::Uwp(package_family_name.clone())
+            }
+            AppIdentifier::UwpApplication(aumid) => AppIdentifier::UwpApplication(aumid.clone()),
+            AppIdentifier::Folder(path) => AppIdentifier::Folder(path.clone()),
+        }
+    }
+
+    /// Update `ProcessMonitor` watch list with enabled monitored applications that are not
+    /// snoozed, excluded processes and monitoring preferences from config.
+    pub fn update_process_monitor_watch_list(&self) {
+        use tracing::debug;
+
+        let config = self.config.read();
+        let monitored_apps: Vec<MonitoredApp> = config
+            .monitored_apps
+            .iter()
+            .filter(|app| app.is_enabled() && !self.snoozed_apps.contains_key(app.id()))
+            .cloned()
+            .collect();
+        let track_child_processes = config.preferences.track_child_processes;
+        let wait_for_window = config.preferences.wait_for_window;
+        let adaptive_polling = config
+            .preferences
+            .max_idle_polling_interval()
+            .map(|max_idle_interval| AdaptivePolling { max_idle_interval });
+        let excluded_processes = config.excluded_processes.clone();
+        drop(config);
+
+        debug!(
+            "Updating ProcessMonitor watch list with {} monitored applications",
+            monitored_apps.len()
+        );
+
+        self.watch_state.update(|state| {
+            state
+                .with_apps(monitored_apps, track_child_processes)
+                .with_exclusions(&excluded_processes)
+                .with_wait_for_window(wait_for_window)
+                .with_adaptive_polling(adaptive_polling)
+        });
+
+        debug!(
+            "ProcessMonitor watch state version {} published",
+            self.watch_state.version()
+        );
+    }
+
+    /// Start logging every process the monitor considers for `duration`
+    ///
+    /// Each poll logs why every running process did or didn't match the watch list.
+    pub fn start_process_trace(&self, duration: std::time::Duration) {
+        use tracing::info;
+
+        info!("Starting process trace for {:?}", duration);
+        self.watch_state.load().trace.start(duration);
+    }
+
+    /// Snapshot of the process monitor's scan duration statistics
+    pub fn poll_timings(&self) -> PollTimings {
+        self.watch_state.results().poll_timings.clone()
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::config::models::{AppSource, FolderApp, Win32App};
+    use crate::config::{
+        AppConfig, AutoStartMethod, BatteryHdrPolicy, MonitoredApp, ShutdownPolicy, UpdateChannel,
+    };
+    use crate::controller::clock::ManualClock;
+    use crate::hdr::MockHdrControl;
+    use crate::test_utils::{AppdataGuard, create_test_dir};
+    use crate::utils::{LogFormat, UpdateCheckCache};
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    impl<H: HdrControl> AppController<H> {
+        /// Expire a scheduled HDR disable as if the debounce window had passed
+        fn finish_hdr_disable_debounce(&mut self) {
+            if self.pending_hdr_disable.is_some() {
+                self.pending_hdr_disable = Some(self.clock.now());
+            }
+            self.process_pending_hdr_disable();
+        }
+    }
+
+    /// Test that an injected backend receives the HDR toggles
+    #[test]
+    fn test_controller_drives_injected_hdr_backend() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        assert!(controller.hdr_displays_available.load(Ordering::SeqCst));
+        assert!(!controller.pending_no_hdr_warning.load(Ordering::SeqCst));
+
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "game".to_string(),
+        )));
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "game".to_string(),
+        )));
+        controller.finish_hdr_disable_debounce();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_display_group_limits_hdr_to_its_displays() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let tv = DisplayIdentity::new("SAM", 0x7325, b"TV-1");
+        let mut config = AppConfig::default();
+        config.display_groups.push(DisplayGroup {
+            name: "TV".to_string(),
+            displays: vec![tv.clone()],
+        });
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Media Player".to_string(),
+            exe_path: PathBuf::from("C:\\test\\player.exe"),
+            process_name: "player".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: Some("tv".to_string()),
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: true,
+                identity: Some(tv),
+                ..DisplayTarget::default()
+            },
+        ]);
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        // Only the TV turns on, and only the TV turns back off
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "player".to_string(),
+        )));
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert_eq!(backend.hdr_state(2), Some(true));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        backend.set_external_hdr_state(1, true);
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "player".to_string(),
+        )));
+        controller.finish_hdr_disable_debounce();
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert_eq!(backend.hdr_state(2), Some(false));
+        assert_eq!(controller.group_hdr_displays, None);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Without a connected display of the group every HDR display follows
+        backend.set_external_hdr_state(1, false);
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+            display: controller.hdr_controller.displays()[0].clone(),
+            previous: true,
+            enabled: false,
+        });
+        controller.config.write().display_groups[0].displays =
+            vec![DisplayIdentity::new("SAM", 0x7325, b"other")];
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "player".to_string(),
+        )));
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert_eq!(backend.hdr_state(2), Some(true));
+    }
+
+    /// Win32 application `process_name` bound to `display_group`
+    fn group_app(process_name: &str, display_group: Option<&str>) -> MonitoredApp {
+        MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: process_name.to_string(),
+            exe_path: PathBuf::from(format!("C:\\test\\{process_name}.exe")),
+            process_name: process_name.to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: display_group.map(str::to_string),
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        })
+    }
+
+    /// Test that a display group turns on while a display outside it already has HDR on,
+    /// and that HDR stays on for that display after the group turns off
+    #[test]
+    fn test_display_group_turns_on_beside_hdr_on_another_display() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let tv = DisplayIdentity::new("SAM", 0x7325, b"TV-1");
+        let mut config = AppConfig::default();
+        config.display_groups.push(DisplayGroup {
+            name: "TV".to_string(),
+            displays: vec![tv.clone()],
+        });
+        config.monitored_apps.push(group_app("player", Some("TV")));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        // The desk monitor already has HDR on
+        let backend = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: true,
+                identity: Some(tv),
+                ..DisplayTarget::default()
+            },
+        ]);
+        backend.set_external_hdr_state(1, true);
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        )
+        .with_clock(ManualClock::new());
+
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "player".to_string(),
+        )));
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert_eq!(backend.hdr_state(2), Some(true));
+
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "player".to_string(),
+        )));
+        controller.finish_hdr_disable_debounce();
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert_eq!(backend.hdr_state(2), Some(false));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert!(!controller.hdr_enabled_by_app);
+
+        // The believed state matches the displays, so reconciliation changes nothing
+        controller.reconcile_state();
+        controller.reconcile_state();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(controller.suspected_hdr_drift, None);
+        assert_eq!(backend.hdr_state(2), Some(false));
+
+        // Starting again turns the TV back on although HDR is still on elsewhere
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "player".to_string(),
+        )));
+        assert_eq!(backend.hdr_state(2), Some(true));
+    }
+
+    /// Test that applications starting during a session add the displays of their
+    /// display groups, or every display when they have none
+    #[test]
+    fn test_display_groups_of_running_apps_are_combined() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let desk = DisplayIdentity::new("DEL", 0x4321, b"DESK-1");
+        let tv = DisplayIdentity::new("SAM", 0x7325, b"TV-1");
+        let mut config = AppConfig::default();
+        config.display_groups.push(DisplayGroup {
+            name: "Desk".to_string(),
+            displays: vec![desk.clone()],
+        });
+        config.display_groups.push(DisplayGroup {
+            name: "TV".to_string(),
+            displays: vec![tv.clone()],
+        });
+        config.monitored_apps.push(group_app("player", Some("TV")));
+        config
+            .monitored_apps
+            .push(group_app("editor", Some("Desk")));
+        config.monitored_apps.push(group_app("game", None));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                identity: Some(desk),
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: true,
+                identity: Some(tv),
+                ..DisplayTarget::default()
+            },
+        ]);
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        )
+        .with_clock(ManualClock::new());
+        let start = |controller: &mut AppController<MockHdrControl>, name: &str| {
+            controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+                name.to_string(),
+            )));
+        };
+        let stop = |controller: &mut AppController<MockHdrControl>, name: &str| {
+            controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+                name.to_string(),
+            )));
+        };
+
+        // A second group joins the session, and both stay on until the last app exits
+        start(&mut controller, "player");
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert_eq!(backend.hdr_state(2), Some(true));
+        start(&mut controller, "editor");
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert_eq!(backend.hdr_state(2), Some(true));
+        stop(&mut controller, "editor");
+        assert_eq!(backend.hdr_state(1), Some(true));
+        stop(&mut controller, "player");
+        controller.finish_hdr_disable_debounce();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert_eq!(backend.hdr_state(2), Some(false));
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // An application without a group turns HDR on for every display
+        start(&mut controller, "player");
+        assert_eq!(backend.hdr_state(1), Some(false));
+        start(&mut controller, "game");
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert_eq!(backend.hdr_state(2), Some(true));
+        assert_eq!(controller.group_hdr_displays, None);
+        stop(&mut controller, "player");
+        stop(&mut controller, "game");
+        controller.finish_hdr_disable_debounce();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert_eq!(backend.hdr_state(2), Some(false));
+    }
+
+    #[test]
+    fn test_manual_session_keeps_hdr_on_and_is_recorded() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        controller
+            .start_manual_session(Some(Duration::from_secs(3600)), None)
+            .unwrap();
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert!(controller.is_manual_session_active());
+        let state = state_rx.try_iter().last().unwrap();
+        assert_eq!(state.manual_session.as_deref(), Some("HDR session · 1 h"));
+
+        // A monitored application stopping doesn't end the manual session
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "game".to_string(),
+        )));
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "game".to_string(),
+        )));
+        assert!(controller.pending_hdr_disable.is_none());
+        assert_eq!(backend.hdr_state(1), Some(true));
+
+        controller.stop_manual_session().unwrap();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert!(!controller.is_manual_session_active());
+        assert_eq!(controller.get_app_stats(MANUAL_SESSION_ID).sessions, 1);
+        assert!(state_rx.try_iter().last().unwrap().manual_session.is_none());
+
+        // Timed sessions end by themselves
+        controller
+            .start_manual_session(Some(Duration::ZERO), None)
+            .unwrap();
+        assert_eq!(backend.hdr_state(1), Some(true));
+        controller.process_manual_session_deadline();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert_eq!(controller.get_app_stats(MANUAL_SESSION_ID).sessions, 2);
+    }
+
+    /// Test that a manual session switches to the display-mode profile picked from the
+    /// monitored applications
+    #[test]
+    fn test_manual_session_uses_app_display_mode() {
+        use crate::hdr::display_mode::Resolution;
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let display_mode = DisplayModeOverride {
+            refresh_rate_hz: Some(120),
+            resolution: Some(Resolution {
+                width: 2560,
+                height: 1440,
+            }),
+        };
+        let mut config = AppConfig::default();
+        for (name, mode) in [
+            ("game", Some(display_mode)),
+            ("other", Some(display_mode)),
+            ("player", None),
+        ] {
+            config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+                id: Uuid::new_v4(),
+                display_name: name.to_string(),
+                exe_path: PathBuf::from(format!("C:\\test\\{name}.exe")),
+                process_name: name.to_string(),
+                enabled: true,
+                source: AppSource::Manual,
+                auto_hdr: AutoHdrMode::Default,
+                display_mode: mode,
+                hdr_disable_debounce_ms: None,
+                max_hdr_session_minutes: None,
+                color_mode: None,
+                capture_tone_mapping: None,
+                display_group: None,
+                match_rule: None,
+                aliases: Vec::new(),
+                icon_data: None,
+            }));
+        }
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        // The profile shared by two applications is offered once
+        let display_modes = controller.manual_session_display_modes();
+        assert_eq!(display_modes, vec![display_mode]);
+
+        controller
+            .start_manual_session(None, display_modes.first().copied())
+            .unwrap();
+        assert_eq!(controller.session_display_mode, Some(display_mode));
+
+        controller.stop_manual_session().unwrap();
+        assert!(controller.session_display_mode.is_none());
+    }
+
+    #[test]
+    fn test_onboarding_hdr_test_reverts_and_completes() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(64);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let clock = ManualClock::new();
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        )
+        .with_clock(clock.clone());
+        assert_eq!(controller.onboarding_step(), Some(OnboardingStep::Displays));
+
+        // The HDR test only runs on its own step
+        controller.start_hdr_test().unwrap();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        controller.advance_onboarding();
+        let onboarding = state_rx.try_iter().last().unwrap().onboarding.unwrap();
+        assert_eq!(onboarding.step, OnboardingStep::HdrTest);
+        assert_eq!(onboarding.hdr_displays.len(), 1);
+
+        // Without an answer HDR switches back once the test time is up
+        controller.start_hdr_test().unwrap();
+        assert_eq!(backend.hdr_state(1), Some(true));
+        clock.advance(HDR_TEST_DURATION / 3);
+        controller.process_onboarding();
+        let onboarding = state_rx.try_iter().last().unwrap().onboarding.unwrap();
+        assert_eq!(onboarding.hdr_test_seconds_left, Some(10));
+        clock.advance(HDR_TEST_DURATION);
+        controller.process_onboarding();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        let onboarding = state_rx.try_iter().last().unwrap().onboarding.unwrap();
+        assert_eq!(onboarding.hdr_test_seconds_left, None);
+        assert_eq!(onboarding.hdr_test_passed, None);
+
+        // An answer ends the test early
+        controller.start_hdr_test().unwrap();
+        controller.end_hdr_test(Some(true)).unwrap();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        let onboarding = state_rx.try_iter().last().unwrap().onboarding.unwrap();
+        assert_eq!(onboarding.hdr_test_passed, Some(true));
+        assert_eq!(
+            controller.get_history().first().unwrap().trigger,
+            ToggleTrigger::HdrTest
+        );
+
+        // Moving on from the last step finishes setup for good
+        controller.advance_onboarding();
+        controller.advance_onboarding();
+        controller.advance_onboarding();
+        assert_eq!(controller.onboarding_step(), None);
+        assert!(state_rx.try_iter().last().unwrap().onboarding.is_none());
+        assert!(controller.config.read().preferences.onboarding_completed);
+    }
+
+    #[test]
+    fn test_session_limit_warns_then_turns_hdr_off() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let app_id = Uuid::new_v4();
+        let mut config = AppConfig::default();
+        config.preferences.max_hdr_session_minutes = 60;
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "game".to_string(),
+        )));
+        controller.process_session_limit();
+        assert_eq!(
+            controller.max_hdr_session(),
+            Some(Duration::from_secs(3600))
+        );
+        assert!(
+            state_rx
+                .try_iter()
+                .all(|state| state.session_limit_warning.is_none())
+        );
+
+        // Shortly before the limit the user is warned once
+        let started = Instant::now()
+            .checked_sub(Duration::from_secs(56 * 60))
+            .unwrap();
+        controller.hdr_session.as_mut().unwrap().since = started;
+        controller.process_session_limit();
+        controller.process_session_limit();
+        let warnings: Vec<String> = state_rx
+            .try_iter()
+            .filter_map(|state| state.session_limit_warning)
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("turns off in"));
+
+        // Extending keeps HDR on past the original limit
+        controller.extend_hdr_session();
+        let started = Instant::now()
+            .checked_sub(Duration::from_secs(61 * 60))
+            .unwrap();
+        controller.hdr_session.as_mut().unwrap().since = started;
+        controller.process_session_limit();
+        assert_eq!(backend.hdr_state(1), Some(true));
+
+        // At the limit HDR is turned off and stays off while the game runs
+        let started = Instant::now()
+            .checked_sub(Duration::from_secs(121 * 60))
+            .unwrap();
+        controller.hdr_session.as_mut().unwrap().since = started;
+        controller.process_session_limit();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert_eq!(controller.hdr_override, Some(HdrOverride::TurnOff));
+        assert!(controller.hdr_session.is_none());
+
+        // Applications without a limit lift it while they run
+        controller.set_max_hdr_session(app_id, Some(0)).unwrap();
+        assert_eq!(controller.max_hdr_session(), None);
+        controller.set_max_hdr_session(app_id, Some(180)).unwrap();
+        assert_eq!(
+            controller.max_hdr_session(),
+            Some(Duration::from_secs(3 * 3600))
+        );
+    }
+
+    #[test]
+    fn test_oled_care_reminds_after_long_hdr_session() {
+        use crate::hdr::{DisplayIdentity, MockHdrControl};
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let identity = DisplayIdentity::new("LGD", 0x0001, b"OLED-1");
+        let backend = MockHdrControl::new(vec![DisplayTarget {
+            target_id: 1,
+            supports_hdr: true,
+            friendly_name: Some("LG OLED".to_string()),
+            identity: Some(identity.clone()),
+            ..DisplayTarget::default()
+        }]);
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        let command = VcpCommand {
+            code: 0xE1,
+            value: 0x01,
+        };
+        controller
+            .set_oled_care(
+                &identity,
+                Some(OledCare {
+                    after_minutes: 120,
+                    pixel_refresh_command: Some(command),
+                }),
+            )
+            .unwrap();
+        assert_eq!(controller.display_settings().len(), 1);
+
+        // A short session does not need maintenance
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "game".to_string(),
+        )));
+        controller.process_oled_care();
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        controller.process_oled_care();
+        assert!(
+            state_rx
+                .try_iter()
+                .all(|state| state.oled_care_reminder.is_none())
+        );
+
+        // After a long session the user is reminded once and can start the pixel refresh
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        controller.process_oled_care();
+        controller.oled_hdr_since = Instant::now().checked_sub(Duration::from_secs(3 * 3600));
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        controller.process_oled_care();
+        controller.process_oled_care();
+        let reminders: Vec<OledCareReminder> = state_rx
+            .try_iter()
+            .filter_map(|state| state.oled_care_reminder)
+            .collect();
+        assert_eq!(reminders.len(), 1);
+        assert!(reminders[0].message.contains("3 h on LG OLED"));
+        assert!(reminders[0].can_start_pixel_refresh);
+
+        controller.start_pixel_refresh().unwrap();
+        controller.start_pixel_refresh().unwrap();
+        assert_eq!(backend.vcp_commands(), vec![(1, command)]);
+
+        // Untagged displays are left alone
+        controller.set_oled_care(&identity, None).unwrap();
+        assert_eq!(controller.display_settings()[0].1.oled_care, None);
+    }
+
+    #[test]
+    fn test_hdr_color_profile_is_applied_when_hdr_turns_on() {
+        use crate::hdr::{DisplayIdentity, MockHdrControl};
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let identity = DisplayIdentity::new("DEL", 0x4141, b"7MT0123ABCD");
+        let backend = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                identity: Some(identity.clone()),
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+        ]);
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        // Paths are rejected, file names in the color store are accepted
+        assert!(
+            controller
+                .set_hdr_color_profile(&identity, Some(r"C:\hdr.icc"))
+                .is_err()
+        );
+        controller
+            .set_hdr_color_profile(&identity, Some(" Calibration.icc "))
+            .unwrap();
+        assert_eq!(
+            controller.display_settings()[0]
+                .1
+                .hdr_color_profile
+                .as_deref(),
+            Some("Calibration.icc")
+        );
+
+        // Only the configured display gets its profile, and only when HDR turns on
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        assert_eq!(
+            backend.color_profiles(),
+            vec![(1, "Calibration.icc".to_string())]
+        );
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        assert_eq!(backend.color_profiles().len(), 1);
+
+        controller.set_hdr_color_profile(&identity, None).unwrap();
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        assert_eq!(backend.color_profiles().len(), 1);
+    }
+
+    #[test]
+    fn test_hdr_toggle_failures_name_each_display() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                friendly_name: Some("LG C2".to_string()),
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+        ]);
+        backend.set_failing(1, true);
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        let state = state_rx.try_iter().last().unwrap();
+        assert_eq!(state.hdr_toggle_failures.len(), 1);
+        let failure = &state.hdr_toggle_failures[0];
+        assert_eq!(failure.to_string(), "HDR failed on LG C2: error 31");
+        assert!(failure.hint().is_some());
+        assert_eq!(
+            state.hdr_toggle_error.as_deref(),
+            Some("HDR could not be turned on for LG C2 (error 31)")
+        );
+        assert!(state.show_hdr_toggle_error_notification);
+
+        backend.set_failing(1, false);
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        let state = state_rx.try_iter().last().unwrap();
+        assert!(state.hdr_toggle_failures.is_empty());
+        assert_eq!(state.hdr_toggle_error, None);
+    }
+
+    #[test]
+    fn test_detection_time_counts_from_scan_start() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+        let controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        );
+
+        let game = AppIdentifier::Win32("game".to_string());
+        let scan_started = Instant::now().checked_sub(Duration::from_secs(5)).unwrap();
+        watch_state.update_results(|results| {
+            results.running_apps = Some(HashSet::from([game.clone()]));
+            results.scan_started = Some(scan_started);
+        });
+        assert_eq!(controller.detection_time(&game), scan_started);
+
+        // Not found by the scan, e.g. reported by the UWP app watcher
+        let editor = AppIdentifier::Win32("editor".to_string());
+        assert!(controller.detection_time(&editor) > scan_started + Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_hdr_toggles_are_reported_to_event_log() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        event_log::record();
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        assert_eq!(
+            event_log::take_recorded(),
+            vec![
+                (EventKind::HdrToggled, "HDR turned on".to_string()),
+                (EventKind::HdrToggled, "HDR turned off".to_string()),
+            ]
+        );
+
+        // A toggle that fails on every display is reported as a failure instead
+        backend.set_failing(1, true);
+        event_log::record();
+        assert!(controller.apply_hdr_override(HdrOverride::KeepOn).is_err());
+        let recorded = event_log::take_recorded();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].0, EventKind::HdrToggleFailed);
+        assert!(recorded[0].1.starts_with("HDR could not be turned on"));
+    }
+
+    #[test]
+    fn test_set_display_hdr_only_changes_one_display() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 3,
+                ..DisplayTarget::default()
+            },
+        ]);
+        let controller = AppController::with_hdr_control(
+            backend.clone(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        // SDR-only displays are not listed
+        let states = controller.display_hdr_states();
+        assert_eq!(states.len(), 2);
+        assert!(states.iter().all(|state| !state.hdr_enabled));
+
+        controller.set_display_hdr(&states[1].target, true).unwrap();
+        assert_eq!(backend.hdr_state(1), Some(false));
+        assert_eq!(backend.hdr_state(2), Some(true));
+
+        // The tray learns about the change right away
+        let state = state_rx.try_iter().last().unwrap();
+        assert_eq!(
+            state
+                .display_hdr
+                .iter()
+                .map(|display| display.hdr_enabled)
+                .collect::<Vec<_>>(),
+            vec![false, true]
+        );
+
+        let disconnected = DisplayTarget {
+            target_id: 9,
+            supports_hdr: true,
+            ..DisplayTarget::default()
+        };
+        assert!(controller.set_display_hdr(&disconnected, true).is_err());
+    }
+
+    #[test]
+    fn test_hdr_turned_off_on_one_display_keeps_hdr_on() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let display = |target_id| DisplayTarget {
+            target_id,
+            supports_hdr: true,
+            ..DisplayTarget::default()
+        };
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::new(vec![display(1), display(2)]),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        for target_id in [1, 2] {
+            controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+                display: display(target_id),
+                previous: false,
+                enabled: true,
+            });
+        }
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Turning HDR off on the secondary display leaves the primary one on
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+            display: display(2),
+            previous: true,
+            enabled: false,
+        });
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(controller.hdr_on_displays, vec![display(1)]);
+
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+            display: display(1),
+            previous: true,
+            enabled: false,
+        });
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+        assert!(controller.hdr_on_displays.is_empty());
+    }
+
+    #[test]
+    fn test_hdr_reverted_after_resume_is_restored() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let app = Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        };
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(app));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let display = backend.displays()[0].clone();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(backend.hdr_state(1), Some(true));
+
+        // Reverted while asleep
+        backend.set_external_hdr_state(1, false);
+        controller.handle_hdr_state_event(HdrStateEvent::SystemResumed);
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Reverted again shortly after the resume: not held as a change by the user
+        backend.set_external_hdr_state(1, false);
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+            display,
+            previous: true,
+            enabled: false,
+        });
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert!(controller.external_hold.is_none());
+
+        // A driver reset that keeps the display configuration
+        backend.set_external_hdr_state(1, false);
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayDriverReset);
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_hdr_kept_off_on_low_battery() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let app = Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        };
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(app));
+        config.preferences.battery_hdr_policy = BatteryHdrPolicy::SkipBelowThreshold;
+        config.preferences.battery_hdr_threshold_percent = 20;
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        let power = |on_battery, percent| HdrStateEvent::PowerStatusChanged {
+            status: PowerStatus {
+                on_battery,
+                battery_percent: Some(percent),
+            },
+        };
+
+        controller.handle_hdr_state_event(power(true, 15));
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Still on battery, but above the threshold
+        controller.handle_hdr_state_event(power(true, 25));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // A running session is not cut short when the charge drops again
+        controller.handle_hdr_state_event(power(true, 10));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_hdr_turned_off_outside_easyhdr_is_held() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let app = Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        };
+        let other = Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Other App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\other.exe"),
+            process_name: "other".to_string(),
+            ..app.clone()
+        };
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(app));
+        config.monitored_apps.push(MonitoredApp::Win32(other));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let display = backend.displays()[0].clone();
+        let mut controller = AppController::with_hdr_control(
+            backend,
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        let started = |name: &str| ProcessEvent::Started(AppIdentifier::Win32(name.to_string()));
+        let stopped = |name: &str| ProcessEvent::Stopped(AppIdentifier::Win32(name.to_string()));
+
+        controller.handle_process_event(started("app"));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Turned off in the Windows settings while the game runs
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+            display,
+            previous: true,
+            enabled: false,
+        });
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(
+            controller
+                .pending_actions()
+                .iter()
+                .map(|action| action.id)
+                .collect::<Vec<_>>(),
+            vec![PendingActionId::ExternalHdrHold]
+        );
+
+        // The same game restarting leaves HDR off
+        controller.handle_process_event(stopped("app"));
+        controller.finish_hdr_disable_debounce();
+        controller.handle_process_event(started("app"));
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Another game is a new decision
+        controller.handle_process_event(stopped("app"));
+        controller.finish_hdr_disable_debounce();
+        controller.handle_process_event(started("other"));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert!(controller.external_hold.is_none());
+    }
+
+    #[test]
+    fn test_conflicting_hdr_changes_pause_automation() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let display = backend.displays()[0].clone();
+        let mut controller = AppController::with_hdr_control(
+            backend,
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        let app = || AppIdentifier::Win32("app".to_string());
+
+        controller.handle_process_event(ProcessEvent::Started(app()));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Another program keeps flipping HDR
+        for enabled in [false, true, false] {
+            controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+                display: display.clone(),
+                previous: !enabled,
+                enabled,
+            });
+        }
+        assert!(controller.hdr_conflict_since.is_none());
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayHdrChanged {
+            display,
+            previous: false,
+            enabled: true,
+        });
+        assert!(controller.hdr_conflict_since.is_some());
+        let conflict_state = state_rx.try_iter().last().unwrap();
+        assert!(conflict_state.show_hdr_conflict_notification);
+        assert!(conflict_state.hdr_conflict.is_some());
+        assert!(
+            conflict_state
+                .pending_actions
+                .iter()
+                .any(|action| action.id == PendingActionId::HdrConflictPause)
+        );
+
+        // While paused, automatic toggles are left out
+        controller.handle_process_event(ProcessEvent::Stopped(app()));
+        controller.finish_hdr_disable_debounce();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Resuming hands HDR back to the monitored applications
+        assert!(controller.cancel_pending(PendingActionId::HdrConflictPause));
+        assert!(state_rx.try_iter().last().unwrap().hdr_conflict.is_none());
+        controller.handle_process_event(ProcessEvent::Started(app()));
+        controller.handle_process_event(ProcessEvent::Stopped(app()));
+        controller.finish_hdr_disable_debounce();
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_dry_run_simulates_hdr_toggles() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut config = AppConfig::default();
+        config.preferences.dry_run = true;
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        assert!(controller.is_dry_run());
+
+        // The believed state follows the toggle, the display does not
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(backend.set_calls(), 0);
+        assert_eq!(backend.hdr_state(1), Some(false));
+
+        // Reconciliation must not mistake the simulated state for drift
+        controller.reconcile_state();
+        controller.reconcile_state();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        controller.set_dry_run(false);
+        let state = state_rx.try_iter().last().unwrap();
+        assert!(!state.dry_run);
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        assert_eq!(backend.hdr_state(1), Some(true));
+    }
+
+    #[test]
+    fn test_toggles_are_recorded_in_history() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        assert!(controller.get_history().is_empty());
+
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+
+        let history = controller.get_history();
+        assert_eq!(history.len(), 2);
+        assert!(!history[0].enable, "newest record comes first");
+        assert!(history[1].enable);
+        assert!(history.iter().all(|record| {
+            record.trigger == ToggleTrigger::ManualOverride
+                && record.succeeded()
+                && !record.dry_run
+                && !record.displays.is_empty()
+        }));
+
+        // The history survives a restart
+        assert_eq!(ToggleHistory::load().records().count(), 2);
+    }
+
+    #[test]
+    fn test_wcg_bound_app_switches_to_wcg_instead_of_hdr() {
+        use crate::hdr::{MockHdrControl, WindowsVersion};
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let app_id = Uuid::new_v4();
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Photo Editor".to_string(),
+            exe_path: PathBuf::from("C:\\test\\editor.exe"),
+            process_name: "editor".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend =
+            MockHdrControl::with_hdr_display().with_windows_version(WindowsVersion::Windows11_24H2);
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        assert!(controller.color_modes_supported());
+
+        // SDR is not a session mode
+        assert!(
+            controller
+                .set_app_color_mode(app_id, Some(ColorMode::Sdr))
+                .is_err()
+        );
+        controller
+            .set_app_color_mode(app_id, Some(ColorMode::Wcg))
+            .unwrap();
+
+        let target = backend.displays()[0].clone();
+        let editor = || AppIdentifier::Win32("editor".to_string());
+        controller.handle_process_event(ProcessEvent::Started(editor()));
+        assert_eq!(backend.color_mode(&target).unwrap(), ColorMode::Wcg);
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Leaving WCG does not wait for the HDR disable delay
+        controller.handle_process_event(ProcessEvent::Stopped(editor()));
+        assert_eq!(backend.color_mode(&target).unwrap(), ColorMode::Sdr);
+
+        // Cleared bindings turn HDR on again
+        controller.set_app_color_mode(app_id, None).unwrap();
+        controller.handle_process_event(ProcessEvent::Started(editor()));
+        assert_eq!(backend.color_mode(&target).unwrap(), ColorMode::Hdr);
+    }
+
+    /// Test that WCG is limited to the session's display group and that leaving it
+    /// restores the mode each display had, not SDR
+    #[test]
+    fn test_wcg_session_uses_display_group_and_restores_previous_mode() {
+        use crate::hdr::{MockHdrControl, WindowsVersion};
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let tv = DisplayIdentity::new("SAM", 0x7325, b"TV-1");
+        let mut config = AppConfig::default();
+        config.display_groups.push(DisplayGroup {
+            name: "TV".to_string(),
+            displays: vec![tv.clone()],
+        });
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Photo Editor".to_string(),
+            exe_path: PathBuf::from("C:\\test\\editor.exe"),
+            process_name: "editor".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: Some(ColorMode::Wcg),
+            capture_tone_mapping: None,
+            display_group: Some("TV".to_string()),
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: true,
+                identity: Some(tv),
+                ..DisplayTarget::default()
+            },
+        ])
+        .with_windows_version(WindowsVersion::Windows11_24H2);
+        let desk = backend.displays()[0].clone();
+        let tv = backend.displays()[1].clone();
+        // The desk monitor was in WCG before, the TV in SDR
+        backend.set_color_mode(&desk, ColorMode::Wcg).unwrap();
+
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+
+        let editor = || AppIdentifier::Win32("editor".to_string());
+        controller.handle_process_event(ProcessEvent::Started(editor()));
+        assert_eq!(backend.color_mode(&tv).unwrap(), ColorMode::Wcg);
+        assert_eq!(controller.wcg_displays, vec![(tv.clone(), ColorMode::Sdr)]);
+
+        controller.handle_process_event(ProcessEvent::Stopped(editor()));
+        assert_eq!(backend.color_mode(&tv).unwrap(), ColorMode::Sdr);
+        assert_eq!(backend.color_mode(&desk).unwrap(), ColorMode::Wcg);
+        assert!(controller.wcg_displays.is_empty());
+    }
+
+    #[test]
+    fn test_wcg_binding_falls_back_to_hdr_before_24h2() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Photo Editor".to_string(),
+            exe_path: PathBuf::from("C:\\test\\editor.exe"),
+            process_name: "editor".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: Some(ColorMode::Wcg),
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        assert!(!controller.color_modes_supported());
+
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "editor".to_string(),
+        )));
+        assert_eq!(backend.hdr_state(1), Some(true));
+    }
+
+    #[test]
+    fn test_app_controller_creation() {
+        let config = AppConfig::default();
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_handle_process_started_increments_count() {
+        // Create a config with one monitored app
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Initial count should be 0
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+
+        // Handle a started event for the monitored app
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+
+        // Count should be incremented to 1
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+
+        // Should have sent a state update
+        let state = state_rx.try_recv().unwrap();
+        assert!(state.hdr_enabled);
+    }
+
+    #[test]
+    fn test_handle_process_started_case_insensitive() {
+        // Create a config with one monitored app (lowercase)
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Handle a started event with different case
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "APP".to_string(),
+        )));
+
+        // Count should be incremented (case-insensitive match)
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_handle_process_started_disabled_app_ignored() {
+        // Create a config with one disabled app
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: false, // Disabled
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Handle a started event for the disabled app
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+
+        // Count should remain 0 (disabled apps are ignored)
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_handle_process_stopped_decrements_count() {
+        // Create a config with one monitored app
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Start the app first
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+
+        // Clear the state updates from start
+        state_rx.try_iter().for_each(drop);
+
+        // Stop the app
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+
+        // Count should be decremented to 0
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+
+        // Should have sent a state update, with HDR kept on for the debounce window
+        let state = state_rx.try_recv().unwrap();
+        assert!(state.hdr_enabled);
+
+        controller.finish_hdr_disable_debounce();
+        let state = state_rx.try_iter().last().unwrap();
+        assert!(!state.hdr_enabled);
+    }
+
+    #[test]
+    fn test_handle_process_stopped_when_count_is_zero() {
+        // Create a config with one monitored app
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Initial count should be 0
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+
+        // Send a spurious ProcessEvent::Stopped when count is already 0
+        // This should NOT cause underflow (wrapping to usize::MAX)
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+
+        // Count should remain 0 (saturating_sub prevents underflow)
+        assert_eq!(
+            controller.active_process_count.load(Ordering::SeqCst),
+            0,
+            "Count should remain 0 and not wrap to usize::MAX"
+        );
+
+        // Send another spurious stop event to verify idempotency
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+
+        // Count should still be 0
+        assert_eq!(
+            controller.active_process_count.load(Ordering::SeqCst),
+            0,
+            "Multiple spurious stops should not corrupt state"
+        );
+
+        // Verify that normal operation still works after spurious events
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(
+            controller.active_process_count.load(Ordering::SeqCst),
+            1,
+            "Normal increment should work after spurious stops"
+        );
+    }
+
+    #[test]
+    fn test_handle_multiple_processes() {
+        // Create a config with two monitored apps
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "App 1".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app1.exe"),
+            process_name: "app1".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "App 2".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app2.exe"),
+            process_name: "app2".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Start first app
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app1".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Start second app
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app2".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 2);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Stop first app - HDR should remain on
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app1".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Stop second app - HDR should turn off
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app2".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.finish_hdr_disable_debounce();
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_add_application() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let config = AppConfig::default();
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Create a new app to add
+        let app = MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "New App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\newapp.exe"),
+            process_name: "newapp".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        });
+
+        // Add the application
+        let result = controller.add_application(app.clone());
+        assert!(result.is_ok());
+
+        // Verify it was added to config
+        let config = controller.config.read();
+        assert_eq!(config.monitored_apps.len(), 1);
+        assert_eq!(config.monitored_apps[0].display_name(), "New App");
+        drop(config);
+
+        // Verify watch list was updated
+        let watch_state_guard = watch_state.load();
+        assert_eq!(watch_state_guard.apps.len(), 1);
+        assert!(watch_state_guard.apps.iter().any(|app| {
+            if let MonitoredApp::Win32(win32_app) = app {
+                win32_app.process_name == "newapp"
+            } else {
+                false
+            }
+        }));
+    }
+
+    #[test]
+    fn test_remove_application() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        let app_id = Uuid::new_v4();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Remove the application
+        let result = controller.remove_application(app_id);
+        assert!(result.is_ok());
+
+        // Verify it was removed from config
+        let config = controller.config.read();
+        assert_eq!(config.monitored_apps.len(), 0);
+        drop(config);
+
+        // Verify watch list was updated
+        let watch_state_guard = watch_state.load();
+        assert_eq!(watch_state_guard.apps.len(), 0);
+    }
+
+    #[test]
+    fn test_toggle_app_enabled() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        let app_id = Uuid::new_v4();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Initially populate watch list
+        controller.update_process_monitor_watch_list();
+        {
+            let watch_state_guard = watch_state.load();
+            assert_eq!(watch_state_guard.apps.len(), 1);
+            assert!(watch_state_guard.apps.iter().any(|app| {
+                if let MonitoredApp::Win32(win32_app) = app {
+                    win32_app.process_name == "app"
+                } else {
+                    false
+                }
+            }));
+        }
+
+        // Disable the application
+        let result = controller.toggle_app_enabled(app_id, false);
+        assert!(result.is_ok());
+
+        // Verify enabled flag was updated
+        let config = controller.config.read();
+        assert!(!config.monitored_apps[0].is_enabled());
+        drop(config);
+
+        // Verify watch list was updated (app should be removed)
+        let watch_state_guard = watch_state.load();
+        assert_eq!(watch_state_guard.apps.len(), 0);
+        drop(watch_state_guard);
+
+        // Re-enable the application
+        let result = controller.toggle_app_enabled(app_id, true);
+        assert!(result.is_ok());
+
+        // Verify enabled flag was updated
+        let config = controller.config.read();
+        assert!(config.monitored_apps[0].is_enabled());
+        drop(config);
+
+        // Verify watch list was updated (app should be added back)
+        let watch_state_guard = watch_state.load();
+        assert_eq!(watch_state_guard.apps.len(), 1);
+        assert!(watch_state_guard.apps.iter().any(|app| {
+            if let MonitoredApp::Win32(win32_app) = app {
+                win32_app.process_name == "app"
+            } else {
+                false
+            }
+        }));
+    }
+
+    #[test]
+    fn test_update_preferences() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let config = AppConfig::default();
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Create new preferences
+        let new_prefs = UserPreferences {
+            auto_start: true,
+            autostart_method: AutoStartMethod::Registry,
+            autostart_elevated: false,
+            autostart_delay_secs: 0,
+            monitoring_interval_ms: 2000,
+            show_tray_notifications: false,
+            show_update_notifications: true,
+            auto_open_release_page: false,
+            update_channel: UpdateChannel::Stable,
+            minimize_to_tray_on_minimize: true,
+            minimize_to_tray_on_close: false,
+            start_minimized_to_tray: false,
+            last_update_check_time: 0,
+            cached_latest_version: String::new(),
+            update_check_cache: UpdateCheckCache::default(),
+            uwp_enumerate_all_users: false,
+            track_child_processes: true,
+            wait_for_window: false,
+            hdr_disable_debounce_ms: 500,
+            weekly_summary_notification: false,
+            last_weekly_summary_time: 0,
+            log_level: LogLevel::Info,
+            log_format: LogFormat::Text,
+            shutdown_policy: ShutdownPolicy::LeaveAsIs,
+            max_hdr_session_minutes: 0,
+            external_change_hold_minutes: 0,
+            battery_hdr_policy: BatteryHdrPolicy::Allow,
+            battery_hdr_threshold_percent: 20,
+            icon_cache_max_mb: 16,
+            adaptive_polling: false,
+            max_idle_polling_interval_ms: 5000,
+            suspend_night_light: false,
+            onboarding_completed: true,
+            language: String::new(),
+            allow_unsupported_windows: false,
+            dry_run: false,
+            write_event_log: false,
+            metrics_enabled: false,
+            trace_hdr_api: false,
+            discord_presence: false,
+            discord_client_id: String::new(),
+            run_in_background: false,
+        };
+
+        // Update preferences
+        let result = controller.update_preferences(new_prefs.clone());
+        assert!(result.is_ok());
+
+        // Verify preferences were updated
+        let config = controller.config.read();
+        assert!(config.preferences.auto_start);
+        assert_eq!(config.preferences.monitoring_interval_ms, 2000);
+        assert!(!config.preferences.show_tray_notifications);
+    }
+
+    #[test]
+    fn test_update_process_monitor_watch_list() {
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "App 1".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app1.exe"),
+            process_name: "app1".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "App 2".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app2.exe"),
+            process_name: "app2".to_string(),
+            enabled: false, // Disabled
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "App 3".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app3.exe"),
+            process_name: "app3".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Update watch list
+        controller.update_process_monitor_watch_list();
+
+        // Verify only enabled apps are in watch list
+        let watch_state_guard = watch_state.load();
+        assert_eq!(watch_state_guard.apps.len(), 2);
+        assert!(watch_state_guard.apps.iter().any(|app| {
+            if let MonitoredApp::Win32(win32_app) = app {
+                win32_app.process_name == "app1"
+            } else {
+                false
+            }
+        }));
+        assert!(!watch_state_guard.apps.iter().any(|app| {
+            if let MonitoredApp::Win32(win32_app) = app {
+                win32_app.process_name == "app2"
+            } else {
+                false
+            }
+        })); // Disabled
+        assert!(watch_state_guard.apps.iter().any(|app| {
+            if let MonitoredApp::Win32(win32_app) = app {
+                win32_app.process_name == "app3"
+            } else {
+                false
+            }
+        }));
+    }
+
+    #[test]
+    fn test_import_config_replaces_watch_list() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut exported = AppConfig::default();
+        exported.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\Games\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        exported.preferences.auto_start = true;
+        exported.preferences.monitoring_interval_ms = 2000;
+        let path = temp_dir.path().join("easyhdr-config.json");
+        ConfigManager::export(&exported, &path).unwrap();
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        );
+
+        controller.import_config(&path).unwrap();
+
+        let config = controller.config.read();
+        assert_eq!(config.monitored_apps.len(), 1);
+        assert_eq!(config.preferences.monitoring_interval_ms, 2000);
+        assert!(
+            !config.preferences.auto_start,
+            "auto-start belongs to this PC and is kept"
+        );
+        drop(config);
+        assert_eq!(watch_state.load().apps.len(), 1);
+        assert_eq!(ConfigManager::load().unwrap().monitored_apps.len(), 1);
+    }
+
+    #[test]
+    fn test_set_app_aliases() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let app_id = Uuid::new_v4();
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        let folder = FolderApp {
+            id: Uuid::new_v4(),
+            display_name: "Games".to_string(),
+            path: PathBuf::from("C:\\Games"),
+            enabled: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        };
+        let folder_id = folder.id;
+        config.monitored_apps.push(MonitoredApp::Folder(folder));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        controller
+            .set_app_aliases(app_id, vec!["Game_JP.exe".to_string()])
+            .unwrap();
+        assert_eq!(
+            controller.config.read().monitored_apps[0].aliases(),
+            ["Game_JP.exe".to_string()]
+        );
+        // The watch list picks the alias up right away
+        assert_eq!(
+            watch_state
+                .load()
+                .watched_identifier(&AppIdentifier::Win32("game_jp".to_string())),
+            Some(AppIdentifier::Win32("game".to_string()))
+        );
+
+        assert!(
+            controller
+                .set_app_aliases(folder_id, vec!["game.exe".to_string()])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_update_application() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let app_id = Uuid::new_v4();
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        let folder = FolderApp {
+            id: Uuid::new_v4(),
+            display_name: "Games".to_string(),
+            path: PathBuf::from("C:\\Games"),
+            enabled: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        };
+        let folder_id = folder.id;
+        config.monitored_apps.push(MonitoredApp::Folder(folder));
+        config.display_groups.push(DisplayGroup {
+            name: "TV".to_string(),
+            displays: Vec::new(),
+        });
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        let settings = AppSettings {
+            display_name: "  Game (Steam)  ".to_string(),
+            color_mode: Some(ColorMode::Wcg),
+            hdr_disable_debounce_ms: Some(3000),
+            display_mode: None,
+            match_rule: Some(MatchRule::Glob("game-*".to_string())),
+            aliases: Vec::new(),
+            display_group: Some("tv".to_string()),
+        };
+        controller
+            .update_application(app_id, settings.clone())
+            .unwrap();
+        let app = controller.config.read().monitored_apps[0].clone();
+        assert_eq!(
+            AppSettings::of(&app),
+            AppSettings {
+                display_name: "Game (Steam)".to_string(),
+                display_group: Some("TV".to_string()),
+                ..settings.clone()
+            }
+        );
+        // The watch list picks the match rule up right away
+        assert_eq!(
+            watch_state
+                .load()
+                .watched_identifier(&AppIdentifier::Win32("game-win64".to_string())),
+            Some(AppIdentifier::Win32("game".to_string()))
+        );
+
+        // An invalid field leaves the application as it was
+        assert!(
+            controller
+                .update_application(
+                    app_id,
+                    AppSettings {
+                        display_name: "Renamed".to_string(),
+                        match_rule: Some(MatchRule::Regex("game(".to_string())),
+                        ..settings.clone()
+                    }
+                )
+                .is_err()
+        );
+        assert_eq!(
+            controller.config.read().monitored_apps[0].display_name(),
+            "Game (Steam)"
+        );
+
+        // Only defined display groups can be chosen
+        assert!(
+            controller
+                .update_application(
+                    app_id,
+                    AppSettings {
+                        display_group: Some("Desk".to_string()),
+                        ..settings.clone()
+                    }
+                )
+                .is_err()
+        );
+        assert_eq!(
+            controller.config.read().monitored_apps[0].display_group(),
+            Some("TV")
+        );
+
+        assert!(
+            controller
+                .update_application(
+                    folder_id,
+                    AppSettings {
+                        display_name: "Games".to_string(),
+                        ..settings.clone()
+                    }
+                )
+                .is_err()
+        );
+
+        // Removed in the meantime
+        assert!(
+            controller
+                .update_application(Uuid::new_v4(), settings)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_run_processes_events() {
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Spawn thread to run the event loop
+        let handle = std::thread::spawn(move || {
+            controller.run();
+        });
+
+        // Send a process started event
+        event_tx
+            .send(ProcessEvent::Started(AppIdentifier::Win32(
+                "app".to_string(),
+            )))
+            .unwrap();
+
+        // Wait a bit for the event to be processed
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        // Verify state update was sent
+        let state = state_rx
+            .recv_timeout(std::time::Duration::from_millis(100))
+            .unwrap();
+        assert!(state.hdr_enabled);
+
+        // Close the channel to exit the event loop
+        drop(event_tx);
+
+        // Wait for the thread to complete
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_run_handles_channel_disconnection_gracefully() {
+        let config = AppConfig::default();
+
+        let (event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Spawn thread to run the event loop
+        let handle = std::thread::spawn(move || {
+            controller.run();
+        });
+
+        // Immediately close the channel
+        drop(event_tx);
+
+        // Wait for the thread to complete - should exit gracefully
+        let result = handle.join();
+        assert!(
+            result.is_ok(),
+            "Event loop should exit gracefully when channel disconnects"
+        );
+    }
+
+    #[test]
+    fn test_run_processes_multiple_events() {
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "App 1".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app1.exe"),
+            process_name: "app1".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "App 2".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app2.exe"),
+            process_name: "app2".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(ManualClock::new());
+
+        // Spawn thread to run the event loop
+        let handle = std::thread::spawn(move || {
+            controller.run();
+        });
+
+        // Send multiple events
+        event_tx
+            .send(ProcessEvent::Started(AppIdentifier::Win32(
+                "app1".to_string(),
+            )))
+            .unwrap();
+        event_tx
+            .send(ProcessEvent::Started(AppIdentifier::Win32(
+                "app2".to_string(),
+            )))
+            .unwrap();
+
+        // Wait for events to be processed
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        // Verify state updates were sent
+        let state1 = state_rx
+            .recv_timeout(std::time::Duration::from_millis(100))
+            .unwrap();
+        assert!(state1.hdr_enabled);
+
+        let state2 = state_rx
+            .recv_timeout(std::time::Duration::from_millis(100))
+            .unwrap();
+        assert!(state2.hdr_enabled);
+
+        // Close the channel to exit the event loop
+        drop(event_tx);
+
+        // Wait for the thread to complete
+        handle.join().unwrap();
+    }
+
+    /// Test rapid process start/stop with debouncing.
+    /// Verifies the pending disable prevents unnecessary HDR toggling during app restarts.
+    #[test]
+    fn test_rapid_process_restart_debouncing() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        // Create a config with one monitored app
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let clock = ManualClock::new();
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        )
+        .with_clock(clock.clone());
+        let debounce =
+            Duration::from_millis(controller.config.read().preferences.hdr_disable_debounce_ms);
+
+        // Start the app - HDR should turn on
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        let first_toggle_nanos = controller.last_toggle_time_nanos.load(Ordering::Relaxed);
+
+        // Stop the app - HDR disable is scheduled, not applied
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        assert!(controller.pending_hdr_disable.is_some());
+        // HDR should still be on because we're within the debounce window
+        clock.advance(debounce / 2);
+        controller.process_pending_hdr_disable();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Restart the app within the window - the pending disable is cancelled
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(controller.pending_hdr_disable.is_none());
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // Verify that the last toggle time hasn't changed (no toggle occurred)
+        let second_toggle_nanos = controller.last_toggle_time_nanos.load(Ordering::Relaxed);
+        assert_eq!(
+            first_toggle_nanos, second_toggle_nanos,
+            "Toggle time should not change during debounce"
+        );
+
+        // Stop the app again and let the debounce window pass - HDR turns off
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        clock.advance(debounce / 2);
+        controller.process_pending_hdr_disable();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        clock.advance(debounce);
+        controller.process_pending_hdr_disable();
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+        assert!(controller.pending_hdr_disable.is_none());
+    }
+
+    /// Test that a per-app debounce of zero disables HDR on the next loop iteration
+    /// and a longer one keeps it on.
+    #[test]
+    fn test_per_app_hdr_disable_debounce() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
         let mut config = AppConfig::default();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
             id: Uuid::new_v4(),
-            display_name: "Test App".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app.exe"),
-            process_name: "app".to_string(),
+            display_name: "Instant App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\instant.exe"),
+            process_name: "instant".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: Some(0),
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
+        config.preferences.hdr_disable_debounce_ms = 10_000;
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
-
-        // Initial count should be 0
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        .with_clock(ManualClock::new());
 
-        // Handle a started event for the monitored app
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
-            "app".to_string(),
+            "instant".to_string(),
         )));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Count should be incremented to 1
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "instant".to_string(),
+        )));
+        controller.process_pending_hdr_disable();
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Should have sent a state update
-        let state = state_rx.try_recv().unwrap();
-        assert!(state.hdr_enabled);
+        // Without the override the 10s global preference applies
+        let id = *controller.config.read().monitored_apps[0].id();
+        controller.set_hdr_disable_debounce(id, None).unwrap();
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "instant".to_string(),
+        )));
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "instant".to_string(),
+        )));
+        controller.process_pending_hdr_disable();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
     }
 
+    /// Test that a finished session shows up in the per-app statistics and report.
     #[test]
-    fn test_handle_process_started_case_insensitive() {
-        // Create a config with one monitored app (lowercase)
+    fn test_finished_session_recorded_in_app_stats() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
         let mut config = AppConfig::default();
+        let id = Uuid::new_v4();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "Test App".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app.exe"),
-            process_name: "app".to_string(),
+            id,
+            display_name: "Test Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
+        let sessions_before = controller.get_app_stats(id).sessions;
 
-        // Handle a started event with different case
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
-            "APP".to_string(),
+            "game".to_string(),
         )));
+        assert_eq!(controller.get_app_stats(id).sessions, sessions_before);
 
-        // Count should be incremented (case-insensitive match)
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "game".to_string(),
+        )));
+        let stats = controller.get_app_stats(id);
+        assert_eq!(stats.sessions, sessions_before + 1);
+        assert!(stats.last_played.is_some());
+        assert!(controller.session_history_dirty);
+        assert!(
+            controller
+                .export_stats(StatsFormat::Text)
+                .unwrap()
+                .contains("Test Game")
+        );
     }
 
+    /// Test that a session still running on exit is recorded instead of dropped
     #[test]
-    fn test_handle_process_started_disabled_app_ignored() {
-        // Create a config with one disabled app
+    fn test_running_session_recorded_on_shutdown() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
         let mut config = AppConfig::default();
+        let id = Uuid::new_v4();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "Test App".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app.exe"),
-            process_name: "app".to_string(),
-            enabled: false, // Disabled
+            id,
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
-            watch_state.clone(),
-        )
-        .unwrap();
+            watch_state,
+        );
 
-        // Handle a started event for the disabled app
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
-            "app".to_string(),
+            "game".to_string(),
         )));
+        assert_eq!(controller.get_app_stats(id).sessions, 0);
 
-        // Count should remain 0 (disabled apps are ignored)
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.shutdown();
+        assert_eq!(controller.get_app_stats(id).sessions, 1);
+        assert_eq!(controller.sessions.running_apps().count(), 0);
+        assert!(!controller.session_history_dirty);
+        assert!(SessionHistory::get_history_path().exists());
     }
 
+    /// Test that the weekly summary is sent once a week has passed, only when enabled.
     #[test]
-    fn test_handle_process_stopped_decrements_count() {
-        // Create a config with one monitored app
+    fn test_discord_activity_follows_running_app() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
         let mut config = AppConfig::default();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
             id: Uuid::new_v4(),
-            display_name: "Test App".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app.exe"),
-            process_name: "app".to_string(),
+            display_name: "Cyberpunk 2077".to_string(),
+            exe_path: PathBuf::from("C:\\test\\cyberpunk2077.exe"),
+            process_name: "cyberpunk2077".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
-            watch_state.clone(),
-        )
-        .unwrap();
+            watch_state,
+        );
+        // Off by default
+        assert!(controller.discord.is_none());
+        assert_eq!(controller.discord_activity(), None);
 
-        // Start the app first
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
-            "app".to_string(),
+            "cyberpunk2077".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
-
-        // Clear the state update from start
-        let _ = state_rx.try_recv();
-
-        // Wait for debounce period to pass
-        std::thread::sleep(std::time::Duration::from_millis(600));
+        let activity = controller.discord_activity().unwrap();
+        assert_eq!(activity.details, "Playing Cyberpunk 2077 in HDR");
+        assert!(activity.started_at > 0);
 
-        // Stop the app
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
-            "app".to_string(),
+            "cyberpunk2077".to_string(),
         )));
-
-        // Count should be decremented to 0
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
-
-        // Should have sent a state update
-        let state = state_rx.try_recv().unwrap();
-        assert!(!state.hdr_enabled);
+        controller.finish_hdr_disable_debounce();
+        assert_eq!(controller.discord_activity(), None);
     }
 
     #[test]
-    fn test_handle_process_stopped_when_count_is_zero() {
-        // Create a config with one monitored app
+    fn test_weekly_summary_sent_when_due() {
+        use crate::controller::SessionRecord;
+
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let now_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
         let mut config = AppConfig::default();
-        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "Test App".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app.exe"),
-            process_name: "app".to_string(),
-            enabled: true,
-            icon_data: None,
-        }));
+        config.preferences.last_weekly_summary_time = now_secs - 8 * 86_400;
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
-
-        // Initial count should be 0
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
-
-        // Send a spurious ProcessEvent::Stopped when count is already 0
-        // This should NOT cause underflow (wrapping to usize::MAX)
-        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
-            "app".to_string(),
-        )));
-
-        // Count should remain 0 (saturating_sub prevents underflow)
-        assert_eq!(
-            controller.active_process_count.load(Ordering::SeqCst),
-            0,
-            "Count should remain 0 and not wrap to usize::MAX"
-        );
+        .with_clock(ManualClock::new());
+        controller.session_history = SessionHistory::default();
+        controller.session_history.record(SessionRecord {
+            app_id: Uuid::new_v4(),
+            started_at: now_secs - 86_400,
+            duration_secs: 7_200,
+            hdr_secs: 7_200,
+        });
 
-        // Send another spurious stop event to verify idempotency
-        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
-            "app".to_string(),
-        )));
+        // Disabled by default
+        controller.check_weekly_summary();
+        assert!(state_rx.try_recv().is_err());
 
-        // Count should still be 0
+        controller
+            .config
+            .write()
+            .preferences
+            .weekly_summary_notification = true;
+        controller.check_weekly_summary();
+        let state = state_rx.try_recv().unwrap();
         assert_eq!(
-            controller.active_process_count.load(Ordering::SeqCst),
-            0,
-            "Multiple spurious stops should not corrupt state"
+            state.weekly_summary.as_deref(),
+            Some("2.0 h of HDR across 1 app this week")
         );
 
-        // Verify that normal operation still works after spurious events
-        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
-            "app".to_string(),
-        )));
-        assert_eq!(
-            controller.active_process_count.load(Ordering::SeqCst),
-            1,
-            "Normal increment should work after spurious stops"
-        );
+        // Not due again until next week
+        controller.check_weekly_summary();
+        assert!(state_rx.try_recv().is_err());
     }
 
+    /// Test that monitored executables are found through other spellings of their path
     #[test]
-    fn test_handle_multiple_processes() {
-        // Create a config with two monitored apps
+    fn test_find_win32_app_by_path() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let game_dir = temp_dir.path().join("Games").join("Game");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        let exe_path = game_dir.join("game.exe");
+        std::fs::write(&exe_path, b"MZ").unwrap();
+
+        let id = Uuid::new_v4();
         let mut config = AppConfig::default();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "App 1".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app1.exe"),
-            process_name: "app1".to_string(),
-            enabled: true,
-            icon_data: None,
-        }));
-        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "App 2".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app2.exe"),
-            process_name: "app2".to_string(),
+            id,
+            display_name: "Game".to_string(),
+            exe_path: exe_path.clone(),
+            process_name: "game".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
-            watch_state.clone(),
+            watch_state,
         )
-        .unwrap();
-
-        // Start first app
-        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
-            "app1".to_string(),
-        )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
-        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
-
-        // Start second app
-        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
-            "app2".to_string(),
-        )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 2);
-        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
-
-        // Wait for debounce period
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
-        // Stop first app - HDR should remain on
-        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
-            "app1".to_string(),
-        )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
-        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
-
-        // Wait for debounce period
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
-        // Stop second app - HDR should turn off
-        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
-            "app2".to_string(),
-        )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
-        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+        .with_clock(ManualClock::new());
+
+        assert_eq!(controller.find_win32_app_by_path(&exe_path), Some(id));
+        let indirect = temp_dir
+            .path()
+            .join("Games")
+            .join("..")
+            .join("Games")
+            .join("Game")
+            .join("game.exe");
+        assert_eq!(controller.find_win32_app_by_path(&indirect), Some(id));
+        assert_eq!(
+            controller.find_win32_app_by_path(&game_dir.join("other.exe")),
+            None
+        );
     }
 
     #[test]
-    fn test_add_application() {
+    fn test_apps_on_unreachable_shares_are_offline() {
         // Isolate test environment to prevent writing to real config directory
         let temp_dir = create_test_dir();
         let _guard = AppdataGuard::new(&temp_dir);
 
-        let config = AppConfig::default();
+        let share_app = Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Share Game".to_string(),
+            exe_path: PathBuf::from(r"\\easyhdr-offline.invalid\games\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        };
+        let local_app = Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Local Game".to_string(),
+            exe_path: temp_dir.path().join("local.exe"),
+            process_name: "local".to_string(),
+            ..share_app.clone()
+        };
+        let mut config = AppConfig::default();
+        config
+            .monitored_apps
+            .push(MonitoredApp::Win32(share_app.clone()));
+        config
+            .monitored_apps
+            .push(MonitoredApp::Win32(local_app.clone()));
+
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
-            watch_state.clone(),
-        )
-        .unwrap();
+            watch_state,
+        );
+        assert!(!controller.is_app_offline(&MonitoredApp::Win32(share_app.clone())));
 
-        // Create a new app to add
-        let app = MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "New App".to_string(),
-            exe_path: PathBuf::from("C:\\test\\newapp.exe"),
-            process_name: "newapp".to_string(),
-            enabled: true,
-            icon_data: None,
-        });
+        controller.handle_hdr_state_event(HdrStateEvent::VolumesChanged);
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while controller.volume_check.is_some() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+            controller.process_volume_check();
+        }
 
-        // Add the application
-        let result = controller.add_application(app.clone());
-        assert!(result.is_ok());
+        // Unreachable shares are offline, local apps never are, and both stay configured
+        assert!(controller.is_app_offline(&MonitoredApp::Win32(share_app)));
+        assert!(!controller.is_app_offline(&MonitoredApp::Win32(local_app)));
+        assert_eq!(controller.config.read().monitored_apps.len(), 2);
+    }
 
-        // Verify it was added to config
-        let config = controller.config.read();
-        assert_eq!(config.monitored_apps.len(), 1);
-        assert_eq!(config.monitored_apps[0].display_name(), "New App");
-        drop(config);
+    #[test]
+    fn test_set_log_level_updates_preferences_and_state() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
 
-        // Verify watch list was updated
-        let watch_state_guard = watch_state.read();
-        assert_eq!(watch_state_guard.apps.len(), 1);
-        assert!(watch_state_guard.apps.iter().any(|app| {
-            if let MonitoredApp::Win32(win32_app) = app {
-                win32_app.process_name == "newapp"
-            } else {
-                false
-            }
-        }));
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            AppConfig::default(),
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        )
+        .with_clock(ManualClock::new());
+
+        controller.set_log_level(LogLevel::Debug);
+
+        assert_eq!(
+            controller.config.read().preferences.log_level,
+            LogLevel::Debug
+        );
+        assert_eq!(state_rx.try_recv().unwrap().log_level, LogLevel::Debug);
     }
 
+    /// Test that the shutdown policy decides whether HDR is turned off on exit
     #[test]
-    fn test_remove_application() {
+    fn test_shutdown_policy() {
         // Isolate test environment to prevent writing to real config directory
         let temp_dir = create_test_dir();
         let _guard = AppdataGuard::new(&temp_dir);
 
-        let mut config = AppConfig::default();
-        let app_id = Uuid::new_v4();
-        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: app_id,
-            display_name: "Test App".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app.exe"),
-            process_name: "app".to_string(),
-            enabled: true,
-            icon_data: None,
-        }));
-
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
-            config,
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            AppConfig::default(),
             event_rx,
             hdr_state_rx,
             state_tx,
-            watch_state.clone(),
+            watch_state,
         )
-        .unwrap();
-
-        // Remove the application
-        let result = controller.remove_application(app_id);
-        assert!(result.is_ok());
+        .with_clock(ManualClock::new());
 
-        // Verify it was removed from config
-        let config = controller.config.read();
-        assert_eq!(config.monitored_apps.len(), 0);
-        drop(config);
+        // HDR turned on by EasyHDR
+        controller
+            .toggle_hdr(true, ToggleTrigger::ManualOverride)
+            .unwrap();
+        assert!(controller.hdr_enabled_by_app);
+        assert!(!controller.should_turn_off_hdr_on_shutdown(ShutdownPolicy::LeaveAsIs));
+        assert!(controller.should_turn_off_hdr_on_shutdown(ShutdownPolicy::RestorePrevious));
+        assert!(controller.should_turn_off_hdr_on_shutdown(ShutdownPolicy::ForceOff));
+
+        // HDR turned on by the user outside EasyHDR
+        controller.hdr_enabled_by_app = false;
+        assert!(!controller.should_turn_off_hdr_on_shutdown(ShutdownPolicy::RestorePrevious));
+        assert!(controller.should_turn_off_hdr_on_shutdown(ShutdownPolicy::ForceOff));
+
+        controller.config.write().preferences.shutdown_policy = ShutdownPolicy::ForceOff;
+        controller.shutdown();
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Verify watch list was updated
-        let watch_state_guard = watch_state.read();
-        assert_eq!(watch_state_guard.apps.len(), 0);
+        // Leaving HDR as it is keeps the state EasyHDR set
+        controller
+            .toggle_hdr(true, ToggleTrigger::ManualOverride)
+            .unwrap();
+        controller.config.write().preferences.shutdown_policy = ShutdownPolicy::LeaveAsIs;
+        controller.shutdown();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
     }
 
+    /// Test that debouncing only affects HDR disable, not enable.
+    /// HDR should always turn on immediately when a monitored app starts.
     #[test]
-    fn test_toggle_app_enabled() {
-        // Isolate test environment to prevent writing to real config directory
-        let temp_dir = create_test_dir();
-        let _guard = AppdataGuard::new(&temp_dir);
-
+    fn test_debouncing_does_not_affect_hdr_enable() {
+        // Create a config with one monitored app
         let mut config = AppConfig::default();
-        let app_id = Uuid::new_v4();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: app_id,
+            id: Uuid::new_v4(),
             display_name: "Test App".to_string(),
             exe_path: PathBuf::from("C:\\test\\app.exe"),
             process_name: "app".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
-
-        // Initially populate watch list
-        controller.update_process_monitor_watch_list();
-        {
-            let watch_state_guard = watch_state.read();
-            assert_eq!(watch_state_guard.apps.len(), 1);
-            assert!(watch_state_guard.apps.iter().any(|app| {
-                if let MonitoredApp::Win32(win32_app) = app {
-                    win32_app.process_name == "app"
-                } else {
-                    false
-                }
-            }));
-        }
-
-        // Disable the application
-        let result = controller.toggle_app_enabled(app_id, false);
-        assert!(result.is_ok());
-
-        // Verify enabled flag was updated
-        let config = controller.config.read();
-        assert!(!config.monitored_apps[0].is_enabled());
-        drop(config);
-
-        // Verify watch list was updated (app should be removed)
-        let watch_state_guard = watch_state.read();
-        assert_eq!(watch_state_guard.apps.len(), 0);
-        drop(watch_state_guard);
+        .with_clock(ManualClock::new());
 
-        // Re-enable the application
-        let result = controller.toggle_app_enabled(app_id, true);
-        assert!(result.is_ok());
+        // Start the app - HDR should turn on immediately
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Verify enabled flag was updated
-        let config = controller.config.read();
-        assert!(config.monitored_apps[0].is_enabled());
-        drop(config);
+        // Stop the app - HDR should turn off
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.finish_hdr_disable_debounce();
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Verify watch list was updated (app should be added back)
-        let watch_state_guard = watch_state.read();
-        assert_eq!(watch_state_guard.apps.len(), 1);
-        assert!(watch_state_guard.apps.iter().any(|app| {
-            if let MonitoredApp::Win32(win32_app) = app {
-                win32_app.process_name == "app"
-            } else {
-                false
-            }
-        }));
+        // Immediately start the app again (within what would be a debounce window if it applied to enable)
+        // HDR should turn on immediately regardless of timing
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
     }
 
     #[test]
-    fn test_update_preferences() {
-        // Isolate test environment to prevent writing to real config directory
-        let temp_dir = create_test_dir();
-        let _guard = AppdataGuard::new(&temp_dir);
+    fn test_automatic_toggle_flags_state_update() {
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
 
-        let config = AppConfig::default();
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
-
-        // Create new preferences
-        let new_prefs = UserPreferences {
-            auto_start: true,
-            monitoring_interval_ms: 2000,
-            show_tray_notifications: false,
-            show_update_notifications: true,
-            auto_open_release_page: false,
-            minimize_to_tray_on_minimize: true,
-            minimize_to_tray_on_close: false,
-            start_minimized_to_tray: false,
-            last_update_check_time: 0,
-            cached_latest_version: String::new(),
-        };
+        .with_clock(ManualClock::new());
 
-        // Update preferences
-        let result = controller.update_preferences(new_prefs.clone());
-        assert!(result.is_ok());
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        let state = state_rx.try_recv().unwrap();
+        assert!(state.hdr_enabled);
+        assert!(state.hdr_toggled_automatically);
 
-        // Verify preferences were updated
-        let config = controller.config.read();
-        assert!(config.preferences.auto_start);
-        assert_eq!(config.preferences.monitoring_interval_ms, 2000);
-        assert!(!config.preferences.show_tray_notifications);
+        // Manual overrides are not reported as automatic toggles
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        let state = state_rx.try_recv().unwrap();
+        assert!(!state.hdr_enabled);
+        assert!(!state.hdr_toggled_automatically);
     }
 
     #[test]
-    fn test_update_process_monitor_watch_list() {
+    fn test_hdr_toggle_deferred_while_displays_off() {
         let mut config = AppConfig::default();
+        let app_id = Uuid::new_v4();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "App 1".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app1.exe"),
-            process_name: "app1".to_string(),
-            enabled: true,
-            icon_data: None,
-        }));
-        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "App 2".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app2.exe"),
-            process_name: "app2".to_string(),
-            enabled: false, // Disabled
-            icon_data: None,
-        }));
-        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
-            id: Uuid::new_v4(),
-            display_name: "App 3".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app3.exe"),
-            process_name: "app3".to_string(),
+            id: app_id,
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
-        // Update watch list
-        controller.update_process_monitor_watch_list();
+        controller
+            .handle_hdr_state_event(HdrStateEvent::DisplayPowerChanged { displays_on: false });
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(controller.deferred_hdr_toggle, Some(true));
+        // Nothing is reported as toggled until the deferred toggle runs
+        assert_eq!(controller.auto_toggle_app, None);
 
-        // Verify only enabled apps are in watch list
-        let watch_state_guard = watch_state.read();
-        assert_eq!(watch_state_guard.apps.len(), 2);
-        assert!(watch_state_guard.apps.iter().any(|app| {
-            if let MonitoredApp::Win32(win32_app) = app {
-                win32_app.process_name == "app1"
-            } else {
-                false
-            }
-        }));
-        assert!(!watch_state_guard.apps.iter().any(|app| {
-            if let MonitoredApp::Win32(win32_app) = app {
-                win32_app.process_name == "app2"
-            } else {
-                false
-            }
-        })); // Disabled
-        assert!(watch_state_guard.apps.iter().any(|app| {
-            if let MonitoredApp::Win32(win32_app) = app {
-                win32_app.process_name == "app3"
-            } else {
-                false
-            }
-        }));
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayPowerChanged { displays_on: true });
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(controller.deferred_hdr_toggle, None);
+        assert_eq!(controller.auto_toggle_app, Some(app_id));
+
+        // A toggle that is stale by the time the displays wake up is dropped
+        controller
+            .handle_hdr_state_event(HdrStateEvent::DisplayPowerChanged { displays_on: false });
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        controller.handle_hdr_state_event(HdrStateEvent::DisplayPowerChanged { displays_on: true });
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(controller.deferred_hdr_toggle, None);
     }
 
     #[test]
-    fn test_run_processes_events() {
+    fn test_reconcile_state_corrects_missed_process_events() {
         let mut config = AppConfig::default();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
             id: Uuid::new_v4(),
@@ -1408,162 +8714,279 @@ mod tests {
             exe_path: PathBuf::from("C:\\test\\app.exe"),
             process_name: "app".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
-        let (event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
-        // Spawn thread to run the event loop
-        let handle = std::thread::spawn(move || {
-            controller.run();
+        // No scan yet, nothing to compare against
+        controller.reconcile_state();
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+
+        // The Started event was missed; drift is only corrected on the second check
+        watch_state.update_results(|results| {
+            results.running_apps = Some(HashSet::from([AppIdentifier::Win32("app".to_string())]));
         });
+        controller.reconcile_state();
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.reconcile_state();
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Send a process started event
-        event_tx
-            .send(ProcessEvent::Started(AppIdentifier::Win32(
-                "app".to_string(),
-            )))
-            .unwrap();
+        // The Stopped event was missed
+        watch_state.update_results(|results| results.running_apps = Some(HashSet::new()));
+        controller.reconcile_state();
+        controller.reconcile_state();
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+    }
 
-        // Wait a bit for the event to be processed
-        std::thread::sleep(std::time::Duration::from_millis(50));
+    #[test]
+    fn test_elevated_folder_process_is_reported() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let folder = MonitoredApp::Folder(FolderApp {
+            id: Uuid::new_v4(),
+            display_name: "Games".to_string(),
+            path: PathBuf::from("D:\\Games"),
+            enabled: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        });
+        let folder_id = AppIdentifier::from_monitored_app(&folder);
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(folder.clone());
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        );
+        while state_rx.try_recv().is_ok() {}
 
-        // Verify state update was sent
-        let state = state_rx
-            .recv_timeout(std::time::Duration::from_millis(100))
-            .unwrap();
-        assert!(state.hdr_enabled);
+        controller.reconcile_state();
+        assert_eq!(controller.elevated_app_warning(&folder), None);
 
-        // Close the channel to exit the event loop
-        drop(event_tx);
+        watch_state.update_results(|results| {
+            results
+                .elevated_apps
+                .insert(folder_id, "game.exe".to_string());
+        });
+        controller.reconcile_state();
+        assert!(
+            controller
+                .elevated_app_warning(&folder)
+                .unwrap()
+                .starts_with("game.exe seems to run as administrator")
+        );
+        assert!(state_rx.try_recv().is_ok(), "app list is refreshed");
 
-        // Wait for the thread to complete
-        handle.join().unwrap();
+        watch_state.update_results(|results| results.elevated_apps.clear());
+        controller.reconcile_state();
+        assert_eq!(controller.elevated_app_warning(&folder), None);
     }
 
     #[test]
-    fn test_run_handles_channel_disconnection_gracefully() {
-        let config = AppConfig::default();
+    fn test_session_display_mode_follows_starting_app() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
 
-        let (event_tx, event_rx) = mpsc::sync_channel(32);
+        let app_id = Uuid::new_v4();
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
-        // Spawn thread to run the event loop
-        let handle = std::thread::spawn(move || {
-            controller.run();
-        });
+        let display_mode = DisplayModeOverride {
+            refresh_rate_hz: Some(120),
+            resolution: None,
+        };
+        controller
+            .set_display_mode(app_id, Some(display_mode))
+            .unwrap();
+        assert_eq!(
+            controller.config.read().monitored_apps[0].display_mode(),
+            Some(display_mode)
+        );
 
-        // Immediately close the channel
-        drop(event_tx);
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "App".to_string(),
+        )));
+        assert_eq!(controller.session_display_mode, Some(display_mode));
 
-        // Wait for the thread to complete - should exit gracefully
-        let result = handle.join();
-        assert!(
-            result.is_ok(),
-            "Event loop should exit gracefully when channel disconnects"
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.session_display_mode, None);
+
+        // An empty override clears the display mode
+        controller
+            .set_display_mode(app_id, Some(DisplayModeOverride::default()))
+            .unwrap();
+        assert_eq!(
+            controller.config.read().monitored_apps[0].display_mode(),
+            None
         );
     }
 
     #[test]
-    fn test_run_processes_multiple_events() {
+    fn test_turn_off_override_suppresses_enable_until_apps_stop() {
         let mut config = AppConfig::default();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
             id: Uuid::new_v4(),
-            display_name: "App 1".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app1.exe"),
-            process_name: "app1".to_string(),
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
             id: Uuid::new_v4(),
-            display_name: "App 2".to_string(),
-            exe_path: PathBuf::from("C:\\test\\app2.exe"),
-            process_name: "app2".to_string(),
+            display_name: "Other App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\other.exe"),
+            process_name: "other".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
-        let (event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
-
-        // Spawn thread to run the event loop
-        let handle = std::thread::spawn(move || {
-            controller.run();
-        });
-
-        // Send multiple events
-        event_tx
-            .send(ProcessEvent::Started(AppIdentifier::Win32(
-                "app1".to_string(),
-            )))
-            .unwrap();
-        event_tx
-            .send(ProcessEvent::Started(AppIdentifier::Win32(
-                "app2".to_string(),
-            )))
-            .unwrap();
+        .with_clock(ManualClock::new());
 
-        // Wait for events to be processed
-        std::thread::sleep(std::time::Duration::from_millis(100));
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Verify state updates were sent
-        let state1 = state_rx
-            .recv_timeout(std::time::Duration::from_millis(100))
-            .unwrap();
-        assert!(state1.hdr_enabled);
+        // "Turn off now" while the app is still running
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
 
-        let state2 = state_rx
-            .recv_timeout(std::time::Duration::from_millis(100))
-            .unwrap();
-        assert!(state2.hdr_enabled);
+        // Stopping and restarting within the running session must not re-enable HDR
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "other".to_string(),
+        )));
+        assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Close the channel to exit the event loop
-        drop(event_tx);
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "other".to_string(),
+        )));
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        assert_eq!(controller.hdr_override, None);
 
-        // Wait for the thread to complete
-        handle.join().unwrap();
+        // Automatic management resumes once all monitored apps have stopped
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
     }
 
-    /// Test rapid process start/stop with debouncing.
-    /// Verifies 500ms debouncing prevents unnecessary HDR toggling during app restarts.
     #[test]
-    fn test_rapid_process_restart_debouncing() {
-        // Create a config with one monitored app
+    fn test_keep_on_override_suppresses_next_disable() {
         let mut config = AppConfig::default();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
             id: Uuid::new_v4(),
@@ -1571,75 +8994,96 @@ mod tests {
             exe_path: PathBuf::from("C:\\test\\app.exe"),
             process_name: "app".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
-        // Start the app - HDR should turn on
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
             "app".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
-        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
-
-        let first_toggle_nanos = controller.last_toggle_time_nanos.load(Ordering::Relaxed);
-
-        // Wait a short time (less than 500ms)
-        std::thread::sleep(std::time::Duration::from_millis(100));
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
 
-        // Stop the app - HDR should NOT turn off due to debouncing
+        // "Keep HDR on" survives the last app stopping and is then consumed
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
             "app".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
-        // HDR should still be on because we're within the debounce window
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(controller.hdr_override, None);
 
-        // Verify that the last toggle time hasn't changed (no toggle occurred)
-        let second_toggle_nanos = controller.last_toggle_time_nanos.load(Ordering::Relaxed);
-        assert_eq!(
-            first_toggle_nanos, second_toggle_nanos,
-            "Toggle time should not change during debounce"
-        );
-
-        // Now test that after the debounce period expires, HDR can be toggled again
-        // First, restart the app to get the count back to 1
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
             "app".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
-        // HDR should still be on (it never turned off)
-        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
-
-        // Wait for debounce period to expire (600ms to be safe)
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
-        // Stop the app - HDR should turn off now (debounce period has passed)
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
             "app".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.finish_hdr_disable_debounce();
         assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+
+        // "Keep HDR on" while a disable is pending cancels it
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
+        controller.apply_hdr_override(HdrOverride::KeepOn).unwrap();
+        controller.finish_hdr_disable_debounce();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert_eq!(controller.pending_hdr_disable, None);
+        assert_eq!(controller.hdr_override, None);
     }
 
-    /// Test that debouncing only affects HDR disable, not enable.
-    /// HDR should always turn on immediately when a monitored app starts.
     #[test]
-    fn test_debouncing_does_not_affect_hdr_enable() {
-        // Create a config with one monitored app
+    fn test_pending_action_countdown() {
+        let now = Instant::now();
+        let mut action = PendingAction {
+            id: PendingActionId::HdrDisable,
+            description: "HDR turning off".to_string(),
+            due_at: Some(now + Duration::from_millis(7200)),
+        };
+        assert_eq!(action.countdown(now), "HDR turning off · 8 s left");
+        assert_eq!(
+            action.countdown(now + Duration::from_secs(10)),
+            "HDR turning off · 1 s left"
+        );
+
+        action.due_at = Some(now + Duration::from_secs(90));
+        assert_eq!(action.countdown(now), "HDR turning off · 2 min left");
+
+        action.due_at = None;
+        assert_eq!(action.countdown(now), "HDR turning off");
+    }
+
+    #[test]
+    fn test_pending_actions_can_be_listed_and_cancelled() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
         let mut config = AppConfig::default();
         config.monitored_apps.push(MonitoredApp::Win32(Win32App {
             id: Uuid::new_v4(),
@@ -1647,47 +9091,237 @@ mod tests {
             exe_path: PathBuf::from("C:\\test\\app.exe"),
             process_name: "app".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
 
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
-        let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
-        )
-        .unwrap();
+        );
+        assert!(controller.pending_actions().is_empty());
 
-        // Start the app - HDR should turn on immediately
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
             "app".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
-        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+            "app".to_string(),
+        )));
 
-        // Wait for debounce period
-        std::thread::sleep(std::time::Duration::from_millis(600));
+        // The scheduled disable is listed with its deadline and sent to the GUI
+        let actions = controller.pending_actions();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].id, PendingActionId::HdrDisable);
+        assert_eq!(actions[0].due_at, controller.pending_hdr_disable);
+        let last_state = state_rx.try_iter().last().unwrap();
+        assert_eq!(last_state.pending_actions, actions);
+
+        // Cancelling it keeps HDR on past the debounce window
+        assert!(controller.cancel_pending(PendingActionId::HdrDisable));
+        assert!(!controller.cancel_pending(PendingActionId::HdrDisable));
+        controller.finish_hdr_disable_debounce();
+        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        assert!(
+            state_rx
+                .try_iter()
+                .last()
+                .unwrap()
+                .pending_actions
+                .is_empty()
+        );
 
-        // Stop the app - HDR should turn off
-        controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
+        // Overrides are listed until cancelled, handing HDR back to automatic control
+        controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
             "app".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.apply_hdr_override(HdrOverride::TurnOff).unwrap();
+        assert_eq!(
+            controller
+                .pending_actions()
+                .iter()
+                .map(|action| action.id)
+                .collect::<Vec<_>>(),
+            vec![PendingActionId::HdrOverride]
+        );
+        assert!(controller.cancel_pending(PendingActionId::HdrOverride));
+        assert_eq!(controller.hdr_override, None);
         assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
+    }
 
-        // Immediately start the app again (within what would be a debounce window if it applied to enable)
-        // HDR should turn on immediately regardless of timing
+    #[test]
+    fn test_snoozed_app_is_ignored_until_resumed() {
+        let app_id = Uuid::new_v4();
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: app_id,
+            display_name: "Test App".to_string(),
+            exe_path: PathBuf::from("C:\\test\\app.exe"),
+            process_name: "app".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let clock = ManualClock::new();
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state.clone(),
+        )
+        .with_clock(clock.clone());
+        controller.update_process_monitor_watch_list();
+
+        // The notification for the automatic toggle names the application
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
             "app".to_string(),
         )));
-        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
-        assert!(controller.current_hdr_state.load(Ordering::SeqCst));
+        let toggle_state = state_rx
+            .try_iter()
+            .find(|state| state.hdr_toggled_automatically)
+            .unwrap();
+        assert_eq!(toggle_state.hdr_toggle_app, Some(app_id));
+
+        // Snoozing leaves it out of the watch list, ends its session as if it had exited
+        // and lists the snooze
+        controller.snooze_app(app_id);
+        assert!(controller.is_app_snoozed(app_id));
+        assert!(watch_state.load().apps.is_empty());
+        assert_eq!(controller.sessions.running_apps().count(), 0);
+        assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        assert!(controller.pending_hdr_disable.is_some());
+        controller.pending_hdr_disable = None;
+        let actions = controller.pending_actions();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].id, PendingActionId::AppSnooze(app_id));
+        assert_eq!(actions[0].description, "Test App snoozed");
+        assert!(actions[0].due_at.is_some());
+
+        // Cancelling the snooze watches the application again
+        assert!(controller.cancel_pending(PendingActionId::AppSnooze(app_id)));
+        assert!(!controller.is_app_snoozed(app_id));
+        assert_eq!(watch_state.load().apps.len(), 1);
+
+        // So does the snooze running out
+        controller.snooze_app(app_id);
+        clock.advance(APP_SNOOZE_DURATION);
+        controller.process_app_snoozes();
+        assert!(!controller.is_app_snoozed(app_id));
+        assert_eq!(watch_state.load().apps.len(), 1);
+        assert!(controller.pending_actions().is_empty());
+    }
+
+    #[test]
+    fn test_excluded_process_keeps_hdr_off() {
+        // Isolate test environment to prevent writing to real config directory
+        let temp_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&temp_dir);
+
+        let mut config = AppConfig::default();
+        config.monitored_apps.push(MonitoredApp::Win32(Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from("C:\\test\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        }));
+        config.set_excluded_processes("OBS64.exe");
+
+        let (_event_tx, event_rx) = mpsc::sync_channel(32);
+        let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
+        let (state_tx, _state_rx) = mpsc::sync_channel(32);
+        let watch_state = Arc::new(SharedWatchState::default());
+
+        let backend = MockHdrControl::with_hdr_display();
+        let mut controller = AppController::with_hdr_control(
+            backend.clone(),
+            config,
+            event_rx,
+            hdr_state_rx,
+            state_tx,
+            watch_state,
+        );
+        let game = || AppIdentifier::Win32("game".to_string());
+        let recorder = || AppIdentifier::Win32("OBS64".to_string());
+
+        // An excluded process starting turns HDR off while the game keeps running
+        controller.handle_process_event(ProcessEvent::Started(game()));
+        assert_eq!(backend.hdr_state(1), Some(true));
+        controller.handle_process_event(ProcessEvent::Started(recorder()));
+        assert_eq!(backend.hdr_state(1), Some(false));
+        let actions = controller.pending_actions();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].id, PendingActionId::ExclusionHold);
+        assert_eq!(actions[0].description, "HDR held off while obs64.exe runs");
+
+        // HDR returns once it exits
+        controller.handle_process_event(ProcessEvent::Stopped(recorder()));
+        assert_eq!(backend.hdr_state(1), Some(true));
+        assert!(controller.pending_actions().is_empty());
+
+        // A monitored application starting while it runs doesn't turn HDR on
+        controller.handle_process_event(ProcessEvent::Stopped(game()));
+        controller.finish_hdr_disable_debounce();
+        controller.handle_process_event(ProcessEvent::Started(recorder()));
+        controller.handle_process_event(ProcessEvent::Started(game()));
+        assert_eq!(backend.hdr_state(1), Some(false));
+
+        // Without the monitored application there is nothing to restore
+        controller.handle_process_event(ProcessEvent::Stopped(game()));
+        controller.handle_process_event(ProcessEvent::Stopped(recorder()));
+        assert_eq!(backend.hdr_state(1), Some(false));
+
+        // Cancelling the hold leaves HDR off after the excluded process exits
+        controller.handle_process_event(ProcessEvent::Started(recorder()));
+        controller.handle_process_event(ProcessEvent::Started(game()));
+        assert!(controller.cancel_pending(PendingActionId::ExclusionHold));
+        controller.handle_process_event(ProcessEvent::Stopped(recorder()));
+        assert_eq!(backend.hdr_state(1), Some(false));
     }
 
     // ========================================================================================
@@ -1707,6 +9341,14 @@ mod tests {
             package_family_name: package_family_name.to_string(),
             app_id: app_id.to_string(),
             enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: None,
         })
     }
@@ -1724,16 +9366,17 @@ mod tests {
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
         // Initial count should be 0
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
@@ -1767,16 +9410,17 @@ mod tests {
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
         // Start the UWP app first
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Uwp(
@@ -1788,9 +9432,6 @@ mod tests {
         // Clear the state update from start
         let _ = state_rx.try_recv();
 
-        // Wait for debounce period to pass
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Stop the UWP app
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Uwp(
             "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
@@ -1799,11 +9440,12 @@ mod tests {
         // Count should be decremented to 0
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
 
-        // HDR should be disabled
+        // HDR should be disabled once the debounce window has passed
+        controller.finish_hdr_disable_debounce();
         assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
 
         // Should have sent a state update
-        let state = state_rx.try_recv().unwrap();
+        let state = state_rx.try_iter().last().unwrap();
         assert!(!state.hdr_enabled);
     }
 
@@ -1817,6 +9459,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\Windows\\notepad.exe"),
             process_name: "notepad".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.monitored_apps.push(create_test_uwp_app(
@@ -1828,16 +9480,17 @@ mod tests {
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
         // Start Win32 app
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
@@ -1853,9 +9506,6 @@ mod tests {
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 2);
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Wait for debounce period
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Stop Win32 app - HDR should remain on because UWP app is still running
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
             "notepad".to_string(),
@@ -1866,14 +9516,12 @@ mod tests {
             "HDR should remain enabled when UWP app is still running"
         );
 
-        // Wait for debounce period
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Stop UWP app - HDR should turn off
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Uwp(
             "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
         )));
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.finish_hdr_disable_debounce();
         assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
     }
 
@@ -1887,6 +9535,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\Windows\\notepad.exe"),
             process_name: "notepad".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.monitored_apps.push(create_test_uwp_app(
@@ -1898,16 +9556,17 @@ mod tests {
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
         // Scenario 1: Start UWP app, then Win32 app, then stop UWP app (Win32 is last)
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Uwp(
@@ -1919,8 +9578,6 @@ mod tests {
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 2);
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Stop UWP app - HDR should remain on
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Uwp(
             "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
@@ -1928,18 +9585,14 @@ mod tests {
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Stop Win32 app (last app) - HDR should turn off
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
             "notepad".to_string(),
         )));
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.finish_hdr_disable_debounce();
         assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
 
-        // Wait to reset state
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Scenario 2: Start Win32 app, then UWP app, then stop Win32 app (UWP is last)
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
             "notepad".to_string(),
@@ -1950,8 +9603,6 @@ mod tests {
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 2);
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Stop Win32 app - HDR should remain on
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
             "notepad".to_string(),
@@ -1959,13 +9610,12 @@ mod tests {
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 1);
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Stop UWP app (last app) - HDR should turn off
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Uwp(
             "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
         )));
         assert_eq!(controller.active_process_count.load(Ordering::SeqCst), 0);
+        controller.finish_hdr_disable_debounce();
         assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
     }
 
@@ -1979,6 +9629,16 @@ mod tests {
             exe_path: PathBuf::from("C:\\test\\app1.exe"),
             process_name: "app1".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
         config.monitored_apps.push(create_test_uwp_app(
@@ -1990,16 +9650,17 @@ mod tests {
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
         // Test that both app types increment the counter
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Win32(
@@ -2023,8 +9684,6 @@ mod tests {
         // Verify HDR is on with both apps running
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         // Test that both app types decrement the counter
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Win32(
             "app1".to_string(),
@@ -2038,8 +9697,6 @@ mod tests {
         // HDR should still be on
         assert!(controller.current_hdr_state.load(Ordering::SeqCst));
 
-        std::thread::sleep(std::time::Duration::from_millis(600));
-
         controller.handle_process_event(ProcessEvent::Stopped(AppIdentifier::Uwp(
             "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
         )));
@@ -2050,6 +9707,7 @@ mod tests {
         );
 
         // HDR should be off now
+        controller.finish_hdr_disable_debounce();
         assert!(!controller.current_hdr_state.load(Ordering::SeqCst));
     }
 
@@ -2071,16 +9729,17 @@ mod tests {
         let (_event_tx, event_rx) = mpsc::sync_channel(32);
         let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel(32);
         let (state_tx, _state_rx) = mpsc::sync_channel(32);
-        let watch_state = Arc::new(RwLock::new(WatchState::new()));
+        let watch_state = Arc::new(SharedWatchState::default());
 
-        let mut controller = AppController::new(
+        let mut controller = AppController::with_hdr_control(
+            MockHdrControl::with_hdr_display(),
             config,
             event_rx,
             hdr_state_rx,
             state_tx,
             watch_state.clone(),
         )
-        .unwrap();
+        .with_clock(ManualClock::new());
 
         // Handle a started event for the disabled UWP app
         controller.handle_process_event(ProcessEvent::Started(AppIdentifier::Uwp(
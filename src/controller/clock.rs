@@ -0,0 +1,76 @@
+//! Time source of the application controller
+//!
+//! Debounce windows, holds, snoozes and session limits are measured against
+//! [`Clock::now`]. The controller runs on [`SystemClock`]; tests hand it a
+//! [`ManualClock`] and advance that instead of sleeping through the windows.
+
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Source of the current instant
+pub trait Clock: Send + Sync {
+    /// Current instant
+    fn now(&self) -> Instant;
+}
+
+/// Monotonic system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock that only moves when advanced
+///
+/// Clones share the same time, so a test can keep a handle to the clock it passed to
+/// the controller.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Create a clock stopped at the current instant
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock() += by;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::new();
+        let handle = clock.clone();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        handle.advance(Duration::from_millis(600));
+        assert_eq!(clock.now(), start + Duration::from_millis(600));
+    }
+}
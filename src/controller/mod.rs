@@ -1,8 +1,26 @@
 //! Application logic controller module
 //!
 //! Coordinates between process monitoring, HDR control, and GUI.
-//! Manages HDR state with debouncing to prevent rapid toggling.
+//! Manages HDR state, deferring the disable so restarting applications keep HDR on, and
+//! records every HDR toggle with what triggered it. Time is read through a [`Clock`] so
+//! tests can step through debounce windows. The controller can keep running in a
+//! background agent after the window is closed, and guides first-time users through
+//! setup.
 
+pub mod agent;
 pub mod app_controller;
+pub mod clock;
+pub mod onboarding;
+pub mod session_history;
+pub mod toggle_history;
 
-pub use app_controller::{AppController, AppState};
+pub use app_controller::{
+    AppController, AppSettings, AppState, DisplayHdrState, DisplayToggleFailure, HdrOverride,
+    OledCareReminder, PendingAction, PendingActionId,
+};
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use onboarding::{OnboardingState, OnboardingStep};
+pub use session_history::{
+    AppStats, MANUAL_SESSION_ID, SessionHistory, SessionRecord, StatsFormat,
+};
+pub use toggle_history::{ToggleHistory, ToggleRecord, ToggleTrigger};
@@ -0,0 +1,164 @@
+//! First-run setup
+//!
+//! On the first launch the GUI walks the user through setting `EasyHDR` up: it lists the
+//! displays that support HDR, lets the user try HDR and switches it back by itself after
+//! [`HDR_TEST_DURATION`] in case the screen stays dark, asks whether `EasyHDR` should
+//! start with Windows and offers to add a first application. [`Onboarding`] tracks the
+//! progress; `AppController` drives it and marks it done in the `onboarding_completed`
+//! preference once the user finishes or skips it.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How long HDR stays on during the test before it switches back by itself
+pub const HDR_TEST_DURATION: Duration = Duration::from_secs(15);
+
+/// Step of first-run setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    /// Displays that support HDR
+    Displays,
+    /// Trying HDR with an automatic switch back
+    HdrTest,
+    /// Whether `EasyHDR` starts with Windows
+    AutoStart,
+    /// Adding the first application
+    FirstApp,
+}
+
+impl OnboardingStep {
+    /// Step after this one, `None` after the last
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Displays => Some(Self::HdrTest),
+            Self::HdrTest => Some(Self::AutoStart),
+            Self::AutoStart => Some(Self::FirstApp),
+            Self::FirstApp => None,
+        }
+    }
+
+    /// Name of the step for the GUI
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Displays => "displays",
+            Self::HdrTest => "hdr_test",
+            Self::AutoStart => "auto_start",
+            Self::FirstApp => "first_app",
+        }
+    }
+}
+
+/// Running HDR test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HdrTest {
+    /// HDR state before the test, restored when it ends
+    pub hdr_was_on: bool,
+    /// When HDR switches back by itself
+    pub reverts_at: Instant,
+}
+
+/// Progress through first-run setup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Onboarding {
+    /// Current step
+    pub step: OnboardingStep,
+    /// Running HDR test, `None` while none runs
+    pub hdr_test: Option<HdrTest>,
+    /// Whether the user saw HDR working, `None` until they answered
+    pub hdr_test_passed: Option<bool>,
+    /// Seconds left in the HDR test at the last state update
+    reported_seconds_left: Option<u64>,
+}
+
+impl Default for Onboarding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Onboarding {
+    /// Start first-run setup at the first step
+    pub fn new() -> Self {
+        Self {
+            step: OnboardingStep::Displays,
+            hdr_test: None,
+            hdr_test_passed: None,
+            reported_seconds_left: None,
+        }
+    }
+
+    /// Whole seconds until the running HDR test switches back, rounded up
+    pub fn seconds_left(&self, now: Instant) -> Option<u64> {
+        self.hdr_test.map(|test| {
+            let left = test.reverts_at.saturating_duration_since(now);
+            left.as_secs() + u64::from(left.subsec_nanos() > 0)
+        })
+    }
+
+    /// Whether the countdown shown in the GUI changed since the last state update
+    ///
+    /// Remembers the current countdown, so each second is reported once.
+    pub fn countdown_changed(&mut self, now: Instant) -> bool {
+        let seconds_left = self.seconds_left(now);
+        seconds_left != std::mem::replace(&mut self.reported_seconds_left, seconds_left)
+    }
+
+    /// State of first-run setup for the GUI
+    pub fn state(&self, now: Instant, hdr_displays: Vec<String>) -> OnboardingState {
+        OnboardingState {
+            step: self.step,
+            hdr_displays,
+            hdr_test_seconds_left: self.seconds_left(now),
+            hdr_test_passed: self.hdr_test_passed,
+        }
+    }
+}
+
+/// State of first-run setup, for the GUI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnboardingState {
+    /// Current step
+    pub step: OnboardingStep,
+    /// Names of the connected displays that support HDR
+    pub hdr_displays: Vec<String>,
+    /// Seconds until the running HDR test switches back, `None` while none runs
+    pub hdr_test_seconds_left: Option<u64>,
+    /// Whether the user saw HDR working, `None` until they answered
+    pub hdr_test_passed: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_and_countdown() {
+        let mut step = OnboardingStep::Displays;
+        let mut steps = vec![step.as_str()];
+        while let Some(next) = step.next() {
+            steps.push(next.as_str());
+            step = next;
+        }
+        assert_eq!(steps, ["displays", "hdr_test", "auto_start", "first_app"]);
+
+        let start = Instant::now();
+        let mut onboarding = Onboarding::new();
+        assert_eq!(onboarding.seconds_left(start), None);
+        assert!(!onboarding.countdown_changed(start));
+
+        onboarding.hdr_test = Some(HdrTest {
+            hdr_was_on: false,
+            reverts_at: start + HDR_TEST_DURATION,
+        });
+        assert!(onboarding.countdown_changed(start));
+        assert_eq!(onboarding.seconds_left(start), Some(15));
+        assert!(!onboarding.countdown_changed(start + Duration::from_millis(500)));
+        assert_eq!(
+            onboarding.seconds_left(start + Duration::from_millis(1500)),
+            Some(14)
+        );
+        assert!(onboarding.countdown_changed(start + Duration::from_millis(1500)));
+        assert_eq!(onboarding.seconds_left(start + HDR_TEST_DURATION), Some(0));
+    }
+}
@@ -0,0 +1,832 @@
+//! Per-application session history and statistics
+//!
+//! Every time a monitored application runs, a session is recorded with its start time,
+//! how long it ran and how much of that time HDR was on. The history is kept in
+//! `%APPDATA%\EasyHDR\session_history.json` and feeds the per-app statistics shown in
+//! the GUI, the exported statistics (text, CSV or JSON) and the weekly summary.
+//!
+//! Manual HDR sessions started from the GUI are recorded the same way under
+//! [`MANUAL_SESSION_ID`], so they appear in the statistics as their own row.
+//!
+//! Only the most recent [`MAX_SESSIONS`] sessions are kept one by one; older ones are
+//! folded into per-app totals in the same file, so the statistics still cover every
+//! session ever recorded.
+
+use crate::config::{ConfigManager, MonitoredApp};
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// Oldest sessions are dropped once the history holds this many
+const MAX_SESSIONS: usize = 5000;
+
+/// Period covered by the weekly summary
+pub const WEEKLY_SUMMARY_PERIOD: Duration = Duration::from_hours(7 * 24);
+
+/// Application ID that manual HDR sessions are recorded under
+pub const MANUAL_SESSION_ID: Uuid = Uuid::nil();
+
+/// Name of manual HDR sessions in reports and exports
+const MANUAL_SESSION_NAME: &str = "Manual HDR sessions";
+
+/// File format of exported statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    /// Plain-text table of per-app totals
+    Text,
+    /// One row per recorded session
+    Csv,
+    /// Per-app totals and all recorded sessions
+    Json,
+}
+
+impl StatsFormat {
+    /// Pick the format from a file extension, defaulting to plain text
+    pub fn from_extension(extension: &str) -> Self {
+        if extension.eq_ignore_ascii_case("csv") {
+            Self::Csv
+        } else if extension.eq_ignore_ascii_case("json") {
+            Self::Json
+        } else {
+            Self::Text
+        }
+    }
+}
+
+/// A finished session of a monitored application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Monitored application the session belongs to
+    pub app_id: Uuid,
+    /// When the session started, in seconds since the Unix epoch
+    pub started_at: u64,
+    /// How long the application ran, in seconds
+    pub duration_secs: u64,
+    /// How long HDR was on while the application ran, in seconds
+    pub hdr_secs: u64,
+}
+
+/// Totals over all recorded sessions of one application
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AppStats {
+    /// Number of recorded sessions
+    pub sessions: usize,
+    /// Number of recorded sessions in which HDR was on, to tell whether the automation
+    /// triggers for the application
+    pub hdr_sessions: usize,
+    /// Total time the application ran
+    pub total_time: Duration,
+    /// Total time HDR was on while the application ran
+    pub hdr_time: Duration,
+    /// Start of the most recent session
+    pub last_played: Option<SystemTime>,
+}
+
+impl AppStats {
+    /// Hours with HDR on, for display
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Hours are displayed with one decimal; precision loss only occurs beyond 2^52 seconds"
+    )]
+    pub fn hdr_hours(&self) -> f64 {
+        self.hdr_time.as_secs() as f64 / 3600.0
+    }
+
+    /// One-line summary, e.g. `12 sessions · 3.4 h with HDR · last played 2 days ago`
+    ///
+    /// Empty when the application has never been played.
+    pub fn summary(&self, now: SystemTime) -> String {
+        let Some(last_played) = self.last_played else {
+            return String::new();
+        };
+
+        format!(
+            "{} session{} · {:.1} h with HDR · last played {}",
+            self.sessions,
+            if self.sessions == 1 { "" } else { "s" },
+            self.hdr_hours(),
+            format_days_ago(last_played, now)
+        )
+    }
+
+    /// Hours the application ran, for display
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Hours are displayed with one decimal; precision loss only occurs beyond 2^52 seconds"
+    )]
+    pub fn total_hours(&self) -> f64 {
+        self.total_time.as_secs() as f64 / 3600.0
+    }
+
+    /// Longer summary for the statistics dialog, e.g.
+    /// `12 sessions, 11 with HDR · 14.2 h played, 3.4 h with HDR · last played today`
+    ///
+    /// Empty when the application has never been played.
+    pub fn details(&self, now: SystemTime) -> String {
+        let Some(last_played) = self.last_played else {
+            return String::new();
+        };
+
+        format!(
+            "{} session{}, {} with HDR · {:.1} h played, {:.1} h with HDR · last played {}",
+            self.sessions,
+            if self.sessions == 1 { "" } else { "s" },
+            self.hdr_sessions,
+            self.total_hours(),
+            self.hdr_hours(),
+            format_days_ago(last_played, now)
+        )
+    }
+}
+
+/// Per-app totals in the JSON export
+#[derive(Serialize)]
+struct AppStatsExport<'a> {
+    id: Uuid,
+    name: &'a str,
+    sessions: usize,
+    hdr_sessions: usize,
+    total_secs: u64,
+    hdr_secs: u64,
+    /// Start of the most recent session, in seconds since the Unix epoch
+    last_played: Option<u64>,
+}
+
+/// Root of the JSON export
+#[derive(Serialize)]
+struct StatsExport<'a> {
+    apps: Vec<AppStatsExport<'a>>,
+    sessions: Vec<&'a SessionRecord>,
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+/// Describe how long ago a point in time was, in whole days
+fn format_days_ago(time: SystemTime, now: SystemTime) -> String {
+    let days = now.duration_since(time).unwrap_or_default().as_secs() / 86_400;
+
+    match days {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{days} days ago"),
+    }
+}
+
+/// Totals of the sessions of one application dropped from the history
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct ArchivedTotals {
+    /// Number of dropped sessions
+    sessions: usize,
+    /// Number of dropped sessions in which HDR was on
+    hdr_sessions: usize,
+    /// How long the application ran in them, in seconds
+    duration_secs: u64,
+    /// How long HDR was on in them, in seconds
+    hdr_secs: u64,
+    /// Start of the most recent dropped session, in seconds since the Unix epoch
+    last_started_at: u64,
+}
+
+impl ArchivedTotals {
+    /// Count a dropped session
+    fn add(&mut self, session: &SessionRecord) {
+        self.sessions += 1;
+        self.hdr_sessions += usize::from(session.hdr_secs > 0);
+        self.duration_secs += session.duration_secs;
+        self.hdr_secs += session.hdr_secs;
+        self.last_started_at = self.last_started_at.max(session.started_at);
+    }
+}
+
+/// Recorded sessions of all monitored applications
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionHistory {
+    /// Finished sessions, oldest first
+    #[serde(default)]
+    sessions: Vec<SessionRecord>,
+    /// Totals of the sessions dropped beyond [`MAX_SESSIONS`], by application
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    archived: HashMap<Uuid, ArchivedTotals>,
+}
+
+impl SessionHistory {
+    /// Path of the session history file, next to the configuration file
+    pub fn get_history_path() -> PathBuf {
+        ConfigManager::get_config_path().with_file_name("session_history.json")
+    }
+
+    /// Load the session history from disk
+    ///
+    /// Starts with an empty history if the file doesn't exist or is corrupt, so a lost
+    /// history never prevents startup.
+    pub fn load() -> Self {
+        use tracing::{debug, warn};
+
+        let path = Self::get_history_path();
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            debug!("No session history at {:?}, starting empty", path);
+            return Self::default();
+        };
+
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            warn!(
+                "Failed to parse session history from {:?}, starting empty: {}",
+                path, e
+            );
+            Self::default()
+        })
+    }
+
+    /// Save the session history to disk using a temp file and rename
+    pub fn save(&self) -> Result<()> {
+        use tracing::debug;
+
+        let path = Self::get_history_path();
+        ConfigManager::ensure_config_dir()?;
+
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_string(self)?)?;
+        std::fs::rename(&temp_path, &path)?;
+
+        debug!("Session history saved to {:?}", path);
+        Ok(())
+    }
+
+    /// Add a finished session, folding the oldest ones beyond the size limit into the
+    /// per-app totals
+    pub fn record(&mut self, session: SessionRecord) {
+        self.sessions.push(session);
+        if self.sessions.len() > MAX_SESSIONS {
+            let excess = self.sessions.len() - MAX_SESSIONS;
+            for session in self.sessions.drain(..excess) {
+                self.archived
+                    .entry(session.app_id)
+                    .or_default()
+                    .add(&session);
+            }
+        }
+    }
+
+    /// Forget all sessions of an application
+    pub fn remove_app(&mut self, app_id: Uuid) {
+        self.sessions.retain(|session| session.app_id != app_id);
+        self.archived.remove(&app_id);
+    }
+
+    /// Totals over all recorded sessions of an application
+    pub fn app_stats(&self, app_id: Uuid) -> AppStats {
+        let archived = self
+            .archived
+            .get(&app_id)
+            .map_or_else(AppStats::default, |totals| AppStats {
+                sessions: totals.sessions,
+                hdr_sessions: totals.hdr_sessions,
+                total_time: Duration::from_secs(totals.duration_secs),
+                hdr_time: Duration::from_secs(totals.hdr_secs),
+                last_played: Some(UNIX_EPOCH + Duration::from_secs(totals.last_started_at)),
+            });
+
+        self.sessions
+            .iter()
+            .filter(|session| session.app_id == app_id)
+            .fold(archived, |mut stats, session| {
+                let started_at = UNIX_EPOCH + Duration::from_secs(session.started_at);
+                stats.sessions += 1;
+                stats.hdr_sessions += usize::from(session.hdr_secs > 0);
+                stats.total_time += Duration::from_secs(session.duration_secs);
+                stats.hdr_time += Duration::from_secs(session.hdr_secs);
+                stats.last_played = stats.last_played.max(Some(started_at));
+                stats
+            })
+    }
+
+    /// Totals of the given applications and manual HDR sessions, most HDR time first
+    pub fn app_rows<'a>(&self, apps: &'a [MonitoredApp]) -> Vec<(&'a str, AppStats)> {
+        let mut rows: Vec<_> = apps
+            .iter()
+            .map(|app| (*app.id(), app.display_name()))
+            .chain(self.manual_sessions_entry())
+            .map(|(id, name)| (name, self.app_stats(id)))
+            .collect();
+        rows.sort_by(|a, b| b.1.hdr_time.cmp(&a.1.hdr_time).then(a.0.cmp(b.0)));
+        rows
+    }
+
+    /// Export statistics for the given applications in a file format
+    pub fn export(
+        &self,
+        apps: &[MonitoredApp],
+        format: StatsFormat,
+        now: SystemTime,
+    ) -> Result<String> {
+        match format {
+            StatsFormat::Text => Ok(self.report(apps, now)),
+            StatsFormat::Csv => Ok(self.export_csv(apps)),
+            StatsFormat::Json => self.export_json(apps),
+        }
+    }
+
+    /// CSV with one row per recorded session of the given applications and manual
+    /// HDR sessions
+    ///
+    /// Sessions of applications no longer monitored are left out.
+    pub fn export_csv(&self, apps: &[MonitoredApp]) -> String {
+        let names: HashMap<Uuid, &str> = apps
+            .iter()
+            .map(|app| (*app.id(), app.display_name()))
+            .chain(std::iter::once((MANUAL_SESSION_ID, MANUAL_SESSION_NAME)))
+            .collect();
+
+        let mut csv = String::from("app_id,app_name,started_at,duration_secs,hdr_secs\n");
+        for session in &self.sessions {
+            let Some(name) = names.get(&session.app_id) else {
+                continue;
+            };
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                session.app_id,
+                csv_field(name),
+                session.started_at,
+                session.duration_secs,
+                session.hdr_secs
+            );
+        }
+        csv
+    }
+
+    /// JSON with per-app totals and the recorded sessions of the given applications
+    ///
+    /// Manual HDR sessions are included as an extra entry once any were recorded.
+    pub fn export_json(&self, apps: &[MonitoredApp]) -> Result<String> {
+        let apps_export = apps
+            .iter()
+            .map(|app| (*app.id(), app.display_name()))
+            .chain(self.manual_sessions_entry())
+            .map(|(id, name)| {
+                let stats = self.app_stats(id);
+                AppStatsExport {
+                    id,
+                    name,
+                    sessions: stats.sessions,
+                    hdr_sessions: stats.hdr_sessions,
+                    total_secs: stats.total_time.as_secs(),
+                    hdr_secs: stats.hdr_time.as_secs(),
+                    last_played: stats.last_played.map(|last_played| {
+                        last_played
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs()
+                    }),
+                }
+            })
+            .collect();
+
+        let export = StatsExport {
+            apps: apps_export,
+            sessions: self
+                .sessions
+                .iter()
+                .filter(|session| {
+                    session.app_id == MANUAL_SESSION_ID
+                        || apps.iter().any(|app| *app.id() == session.app_id)
+                })
+                .collect(),
+        };
+        Ok(serde_json::to_string_pretty(&export)?)
+    }
+
+    /// Summary of HDR usage over the past week, e.g.
+    /// `14.0 h of HDR across 5 apps this week`
+    ///
+    /// `None` when HDR wasn't used in the past week.
+    pub fn weekly_summary(&self, now: SystemTime) -> Option<String> {
+        let since = now
+            .checked_sub(WEEKLY_SUMMARY_PERIOD)
+            .unwrap_or(UNIX_EPOCH)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut apps = std::collections::HashSet::new();
+        let mut hdr_secs = 0;
+        for session in self
+            .sessions
+            .iter()
+            .filter(|session| session.started_at >= since && session.hdr_secs > 0)
+        {
+            if session.app_id != MANUAL_SESSION_ID {
+                apps.insert(session.app_id);
+            }
+            hdr_secs += session.hdr_secs;
+        }
+
+        if hdr_secs == 0 {
+            return None;
+        }
+
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "Hours are displayed with one decimal; precision loss only occurs beyond 2^52 seconds"
+        )]
+        let hours = hdr_secs as f64 / 3600.0;
+        if apps.is_empty() {
+            return Some(format!("{hours:.1} h of HDR this week"));
+        }
+        Some(format!(
+            "{hours:.1} h of HDR across {} app{} this week",
+            apps.len(),
+            if apps.len() == 1 { "" } else { "s" }
+        ))
+    }
+
+    /// Plain-text statistics report for the given applications and manual HDR
+    /// sessions, most HDR time first
+    pub fn report(&self, apps: &[MonitoredApp], now: SystemTime) -> String {
+        let rows = self.app_rows(apps);
+
+        let mut report = String::from("EasyHDR usage statistics\n\n");
+        let _ = writeln!(
+            report,
+            "{:<40} {:>8} {:>10} {:>10}  Last played",
+            "Application", "Sessions", "Hours", "HDR hours"
+        );
+        for (name, stats) in rows {
+            let last_played = stats.last_played.map_or_else(
+                || "never".to_string(),
+                |last_played| format_days_ago(last_played, now),
+            );
+            let _ = writeln!(
+                report,
+                "{:<40} {:>8} {:>10.1} {:>10.1}  {}",
+                name,
+                stats.sessions,
+                stats.total_hours(),
+                stats.hdr_hours(),
+                last_played
+            );
+        }
+        report
+    }
+
+    /// ID and name of manual HDR sessions, if any were recorded
+    fn manual_sessions_entry(&self) -> Option<(Uuid, &'static str)> {
+        (self.archived.contains_key(&MANUAL_SESSION_ID)
+            || self
+                .sessions
+                .iter()
+                .any(|session| session.app_id == MANUAL_SESSION_ID))
+        .then_some((MANUAL_SESSION_ID, MANUAL_SESSION_NAME))
+    }
+}
+
+/// Session of a monitored application that is still running
+#[derive(Debug, Clone, Copy)]
+struct ActiveSession {
+    /// Running processes of the application
+    processes: usize,
+    started: Instant,
+    started_at: SystemTime,
+    /// HDR time accumulated up to `hdr_since`
+    hdr_time: Duration,
+    /// Since when HDR has been on, `None` while it is off
+    hdr_since: Option<Instant>,
+}
+
+impl ActiveSession {
+    fn set_hdr(&mut self, enabled: bool, now: Instant) {
+        match (enabled, self.hdr_since) {
+            (true, None) => self.hdr_since = Some(now),
+            (false, Some(since)) => {
+                self.hdr_time += now.saturating_duration_since(since);
+                self.hdr_since = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Tracks running sessions of monitored applications
+///
+/// Sessions are counted per application, so an application running several processes
+/// (or restarting one while another is still running) stays a single session.
+#[derive(Debug, Default)]
+pub struct SessionTracker {
+    active: HashMap<Uuid, ActiveSession>,
+}
+
+impl SessionTracker {
+    /// Create a tracker with no running sessions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note that a process of an application started
+    pub fn start(&mut self, app_id: Uuid, hdr_enabled: bool) {
+        let now = Instant::now();
+        let session = self.active.entry(app_id).or_insert_with(|| ActiveSession {
+            processes: 0,
+            started: now,
+            started_at: SystemTime::now(),
+            hdr_time: Duration::ZERO,
+            hdr_since: None,
+        });
+        session.processes += 1;
+        session.set_hdr(hdr_enabled, now);
+    }
+
+    /// Note that a process of an application stopped
+    ///
+    /// Returns the finished session once the application's last process has stopped.
+    pub fn stop(&mut self, app_id: Uuid) -> Option<SessionRecord> {
+        let session = self.active.get_mut(&app_id)?;
+        session.processes = session.processes.saturating_sub(1);
+        if session.processes > 0 {
+            return None;
+        }
+
+        let session = self.active.remove(&app_id)?;
+        Some(Self::finish(app_id, session, Instant::now()))
+    }
+
+    /// End every running session, however many processes are still running
+    ///
+    /// Used on exit so the time played so far is recorded instead of dropped.
+    pub fn stop_all(&mut self) -> Vec<SessionRecord> {
+        let now = Instant::now();
+        self.active
+            .drain()
+            .map(|(app_id, session)| Self::finish(app_id, session, now))
+            .collect()
+    }
+
+    /// Turn a finished session into its history record
+    fn finish(app_id: Uuid, mut session: ActiveSession, now: Instant) -> SessionRecord {
+        session.set_hdr(false, now);
+
+        SessionRecord {
+            app_id,
+            started_at: session
+                .started_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            duration_secs: now.saturating_duration_since(session.started).as_secs(),
+            hdr_secs: session.hdr_time.as_secs(),
+        }
+    }
+
+    /// Applications with a running session
+    pub fn running_apps(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.active.keys().copied()
+    }
+
+    /// When the running session of an application started
+    pub fn started_at(&self, app_id: Uuid) -> Option<SystemTime> {
+        self.active.get(&app_id).map(|session| session.started_at)
+    }
+
+    /// Note the current HDR state so running sessions only count time with HDR on
+    pub fn set_hdr(&mut self, enabled: bool) {
+        let now = Instant::now();
+        for session in self.active.values_mut() {
+            session.set_hdr(enabled, now);
+        }
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn record(app_id: Uuid, started_at: u64, duration_secs: u64, hdr_secs: u64) -> SessionRecord {
+        SessionRecord {
+            app_id,
+            started_at,
+            duration_secs,
+            hdr_secs,
+        }
+    }
+
+    #[test]
+    fn test_app_stats_totals() {
+        let game = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let mut history = SessionHistory::default();
+        history.record(record(game, 1_000, 3_600, 3_600));
+        history.record(record(other, 2_000, 60, 0));
+        history.record(record(game, 90_000, 1_800, 1_800));
+
+        let stats = history.app_stats(game);
+        assert_eq!(stats.sessions, 2);
+        assert_eq!(stats.hdr_sessions, 2);
+        assert_eq!(history.app_stats(other).hdr_sessions, 0);
+        assert_eq!(stats.total_time, Duration::from_secs(5_400));
+        assert_eq!(stats.hdr_time, Duration::from_secs(5_400));
+        assert_eq!(
+            stats.last_played,
+            Some(UNIX_EPOCH + Duration::from_secs(90_000))
+        );
+        assert!((stats.hdr_hours() - 1.5).abs() < f64::EPSILON);
+
+        let now = UNIX_EPOCH + Duration::from_secs(90_000 + 2 * 86_400);
+        assert_eq!(
+            stats.summary(now),
+            "2 sessions · 1.5 h with HDR · last played 2 days ago"
+        );
+        assert_eq!(
+            stats.details(now),
+            "2 sessions, 2 with HDR · 1.5 h played, 1.5 h with HDR · last played 2 days ago"
+        );
+
+        history.remove_app(game);
+        assert_eq!(history.app_stats(game), AppStats::default());
+        assert_eq!(history.app_stats(game).summary(now), "");
+    }
+
+    #[test]
+    fn test_export_csv_and_json() {
+        use crate::config::{AppSource, Win32App};
+        use crate::hdr::AutoHdrMode;
+
+        let game = Uuid::new_v4();
+        let apps = vec![MonitoredApp::Win32(Win32App {
+            id: game,
+            display_name: "Game, \"Deluxe\"".to_string(),
+            exe_path: PathBuf::from("C:\\Games\\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
+            icon_data: None,
+        })];
+
+        let mut history = SessionHistory::default();
+        history.record(record(Uuid::new_v4(), 2_000, 60, 0));
+        history.record(record(game, 3_000, 600, 600));
+
+        let csv = history.export_csv(&apps);
+        assert_eq!(
+            csv,
+            format!(
+                "app_id,app_name,started_at,duration_secs,hdr_secs\n{game},\"Game, \"\"Deluxe\"\"\",3000,600,600\n"
+            )
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&history.export_json(&apps).unwrap()).unwrap();
+        assert_eq!(json["apps"][0]["sessions"], 1);
+        assert_eq!(json["apps"][0]["hdr_secs"], 600);
+        assert_eq!(json["apps"][0]["last_played"], 3_000);
+        assert_eq!(json["sessions"].as_array().unwrap().len(), 1);
+
+        assert_eq!(StatsFormat::from_extension("CSV"), StatsFormat::Csv);
+        assert_eq!(StatsFormat::from_extension("json"), StatsFormat::Json);
+        assert_eq!(StatsFormat::from_extension("txt"), StatsFormat::Text);
+    }
+
+    #[test]
+    fn test_weekly_summary_covers_past_week() {
+        let now_secs = 100 * 86_400;
+        let now = UNIX_EPOCH + Duration::from_secs(now_secs);
+        let mut history = SessionHistory::default();
+        assert_eq!(history.weekly_summary(now), None);
+
+        history.record(record(
+            Uuid::new_v4(),
+            now_secs - 8 * 86_400,
+            36_000,
+            36_000,
+        ));
+        assert_eq!(history.weekly_summary(now), None);
+
+        let game = Uuid::new_v4();
+        history.record(record(game, now_secs - 86_400, 36_000, 36_000));
+        history.record(record(game, now_secs - 3_600, 3_600, 3_600));
+        history.record(record(Uuid::new_v4(), now_secs - 10_800, 10_800, 10_800));
+        history.record(record(Uuid::new_v4(), now_secs - 3_600, 3_600, 0));
+        assert_eq!(
+            history.weekly_summary(now).as_deref(),
+            Some("14.0 h of HDR across 2 apps this week")
+        );
+    }
+
+    #[test]
+    fn test_manual_sessions_in_reports() {
+        let now_secs = 100 * 86_400;
+        let now = UNIX_EPOCH + Duration::from_secs(now_secs);
+        let mut history = SessionHistory::default();
+        assert!(!history.report(&[], now).contains(MANUAL_SESSION_NAME));
+
+        history.record(record(MANUAL_SESSION_ID, now_secs - 7_200, 3_600, 3_600));
+        assert_eq!(
+            history.weekly_summary(now).as_deref(),
+            Some("1.0 h of HDR this week")
+        );
+        assert!(history.report(&[], now).contains(MANUAL_SESSION_NAME));
+        assert!(
+            history
+                .export_csv(&[])
+                .contains(&format!("{MANUAL_SESSION_ID},{MANUAL_SESSION_NAME},"))
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&history.export_json(&[]).unwrap()).unwrap();
+        assert_eq!(json["apps"][0]["name"], MANUAL_SESSION_NAME);
+        assert_eq!(json["apps"][0]["hdr_secs"], 3_600);
+        assert_eq!(json["sessions"].as_array().unwrap().len(), 1);
+
+        // Manual sessions add HDR time but don't count as an app
+        history.record(record(Uuid::new_v4(), now_secs - 3_600, 3_600, 3_600));
+        assert_eq!(
+            history.weekly_summary(now).as_deref(),
+            Some("2.0 h of HDR across 1 app this week")
+        );
+    }
+
+    #[test]
+    fn test_history_drops_oldest_sessions() {
+        let app_id = Uuid::new_v4();
+        let mut history = SessionHistory::default();
+        for started_at in 0..=MAX_SESSIONS as u64 {
+            history.record(record(app_id, started_at, 1, 1));
+        }
+
+        assert_eq!(history.sessions.len(), MAX_SESSIONS);
+        assert_eq!(history.sessions[0].started_at, 1);
+
+        // Dropped sessions still count, also after a reload
+        let history: SessionHistory =
+            serde_json::from_str(&serde_json::to_string(&history).unwrap()).unwrap();
+        let stats = history.app_stats(app_id);
+        assert_eq!(stats.sessions, MAX_SESSIONS + 1);
+        assert_eq!(stats.hdr_sessions, MAX_SESSIONS + 1);
+        assert_eq!(
+            stats.total_time,
+            Duration::from_secs(MAX_SESSIONS as u64 + 1)
+        );
+        assert_eq!(
+            stats.last_played,
+            Some(UNIX_EPOCH + Duration::from_secs(MAX_SESSIONS as u64))
+        );
+    }
+
+    #[test]
+    fn test_tracker_counts_processes_per_app() {
+        let app_id = Uuid::new_v4();
+        let mut tracker = SessionTracker::new();
+
+        tracker.start(app_id, true);
+        tracker.start(app_id, true);
+        assert!(tracker.stop(app_id).is_none());
+
+        let session = tracker.stop(app_id).unwrap();
+        assert_eq!(session.app_id, app_id);
+        assert!(session.hdr_secs <= session.duration_secs);
+
+        // Stopping an application without a running session is ignored
+        assert!(tracker.stop(app_id).is_none());
+    }
+
+    #[test]
+    fn test_tracker_stop_all_ends_running_sessions() {
+        let game = Uuid::new_v4();
+        let launcher = Uuid::new_v4();
+        let mut tracker = SessionTracker::new();
+
+        tracker.start(game, true);
+        tracker.start(game, true);
+        tracker.start(launcher, false);
+
+        let mut ended: Vec<Uuid> = tracker
+            .stop_all()
+            .into_iter()
+            .map(|session| session.app_id)
+            .collect();
+        ended.sort();
+        let mut expected = vec![game, launcher];
+        expected.sort();
+        assert_eq!(ended, expected);
+        assert_eq!(tracker.running_apps().count(), 0);
+    }
+}
@@ -0,0 +1,326 @@
+//! History of HDR toggles and why they happened
+//!
+//! Every HDR toggle made by the controller is recorded with its time, what triggered it,
+//! the displays it affected and whether it worked, so the GUI can answer "why did HDR
+//! turn on at 9:14 PM?". The most recent records are kept in memory; all of them are
+//! appended to `%APPDATA%\EasyHDR\toggle_history.jsonl` (one JSON record per line), which
+//! is rotated to `toggle_history.1.jsonl` once it grows past [`MAX_LOG_BYTES`].
+
+use crate::config::ConfigManager;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of records kept in memory and shown in the GUI
+pub const MAX_RECENT: usize = 200;
+
+/// Size after which the log file is rotated
+pub const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// What made the controller toggle HDR
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "app", rename_all = "snake_case")]
+pub enum ToggleTrigger {
+    /// A monitored application started
+    AppStarted(String),
+    /// The last monitored application exited and the debounce window passed
+    AppsExited,
+    /// An excluded process started
+    ExclusionStarted,
+    /// The last excluded process exited while monitored applications run
+    ExclusionsExited,
+    /// HDR displays appeared while monitored applications run
+    DisplaysChanged,
+    /// The displays woke up and a toggle deferred while they were off was applied
+    DisplaysWoke,
+    /// AC power returned while monitored applications run
+    PowerConnected,
+    /// A resume from sleep or a display driver reset turned HDR off while monitored
+    /// applications run
+    DisplaysReset,
+    /// The periodic check found HDR out of step with the running applications
+    Reconciliation,
+    /// HDR was turned on or off by hand for the running applications
+    ManualOverride,
+    /// A manual HDR session started or ended
+    ManualSession,
+    /// HDR was tried during first-run setup
+    HdrTest,
+    /// `EasyHDR` shut down
+    Shutdown,
+}
+
+impl ToggleTrigger {
+    /// Whether `EasyHDR` toggled HDR on its own rather than at the user's request
+    pub fn is_automatic(&self) -> bool {
+        !matches!(
+            self,
+            Self::ManualOverride | Self::ManualSession | Self::HdrTest | Self::Shutdown
+        )
+    }
+}
+
+impl fmt::Display for ToggleTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AppStarted(name) => write!(f, "{name} started"),
+            Self::AppsExited => f.write_str("last monitored application exited"),
+            Self::ExclusionStarted => f.write_str("excluded process started"),
+            Self::ExclusionsExited => f.write_str("excluded processes exited"),
+            Self::DisplaysChanged => f.write_str("HDR display connected"),
+            Self::DisplaysWoke => f.write_str("displays woke up"),
+            Self::PowerConnected => f.write_str("power connected"),
+            Self::DisplaysReset => f.write_str("HDR reverted by Windows"),
+            Self::Reconciliation => f.write_str("state check"),
+            Self::ManualOverride => f.write_str("manual override"),
+            Self::ManualSession => f.write_str("manual session"),
+            Self::HdrTest => f.write_str("HDR test"),
+            Self::Shutdown => f.write_str("EasyHDR shut down"),
+        }
+    }
+}
+
+/// One HDR toggle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToggleRecord {
+    /// When HDR was toggled, in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// What made the controller toggle HDR
+    pub trigger: ToggleTrigger,
+    /// Whether HDR was turned on (`true`) or off
+    pub enable: bool,
+    /// Displays the toggle was applied to
+    #[serde(default)]
+    pub displays: Vec<String>,
+    /// Why the toggle failed on some or all displays, `None` if it worked everywhere
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Whether the toggle was only simulated
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl ToggleRecord {
+    /// Record a toggle made now
+    pub fn now(
+        trigger: ToggleTrigger,
+        enable: bool,
+        displays: Vec<String>,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            trigger,
+            enable,
+            displays,
+            error,
+            dry_run: false,
+        }
+    }
+
+    /// Describe how long before `now` the toggle happened
+    pub fn age(&self, now: SystemTime) -> String {
+        let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        match now.saturating_sub(self.timestamp) {
+            0..60 => "just now".to_string(),
+            secs @ 60..3_600 => format!("{} min ago", secs / 60),
+            secs @ 3_600..86_400 => format!("{} h ago", secs / 3_600),
+            secs => format!("{} days ago", secs / 86_400),
+        }
+    }
+
+    /// Whether the toggle worked on every display
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// One-line description without the time, e.g. `HDR on (Game started) · DELL U2723QE`
+    pub fn description(&self) -> String {
+        let mut description = format!(
+            "HDR {} ({})",
+            if self.enable { "on" } else { "off" },
+            self.trigger
+        );
+        if self.dry_run {
+            description.push_str(" [dry run]");
+        }
+        if !self.displays.is_empty() {
+            description.push_str(" · ");
+            description.push_str(&self.displays.join(", "));
+        }
+        if let Some(error) = &self.error {
+            description.push_str(" · failed: ");
+            description.push_str(error);
+        }
+        description
+    }
+}
+
+/// Recent HDR toggles, backed by a rotating log file
+#[derive(Debug, Clone, Default)]
+pub struct ToggleHistory {
+    /// Most recent records, oldest first
+    recent: VecDeque<ToggleRecord>,
+}
+
+impl ToggleHistory {
+    /// Path of the toggle log, next to the configuration file
+    pub fn get_log_path() -> PathBuf {
+        ConfigManager::get_config_path().with_file_name("toggle_history.jsonl")
+    }
+
+    /// Load the most recent records from the log
+    ///
+    /// Lines that don't parse are skipped, so a damaged log never prevents startup.
+    pub fn load() -> Self {
+        use tracing::debug;
+
+        let path = Self::get_log_path();
+        let Ok(log) = std::fs::read_to_string(&path) else {
+            debug!("No toggle history at {:?}, starting empty", path);
+            return Self::default();
+        };
+
+        let mut history = Self::default();
+        for record in log
+            .lines()
+            .filter_map(|line| serde_json::from_str::<ToggleRecord>(line).ok())
+        {
+            history.push(record);
+        }
+        history
+    }
+
+    /// Add a record and append it to the log
+    pub fn record(&mut self, record: ToggleRecord) {
+        use tracing::warn;
+
+        if let Err(e) = Self::append(&record) {
+            warn!("Failed to write toggle history: {}", e);
+        }
+        self.push(record);
+    }
+
+    /// Recent records, newest first
+    pub fn records(&self) -> impl Iterator<Item = &ToggleRecord> {
+        self.recent.iter().rev()
+    }
+
+    fn push(&mut self, record: ToggleRecord) {
+        if self.recent.len() == MAX_RECENT {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(record);
+    }
+
+    /// Append a record to the log, rotating it first if it grew too large
+    fn append(record: &ToggleRecord) -> Result<()> {
+        let path = Self::get_log_path();
+        ConfigManager::ensure_config_dir()?;
+
+        if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() >= MAX_LOG_BYTES) {
+            std::fs::rename(&path, path.with_extension("1.jsonl"))?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{AppdataGuard, create_test_dir};
+
+    fn record(enable: bool) -> ToggleRecord {
+        ToggleRecord::now(
+            ToggleTrigger::AppStarted("Game".to_string()),
+            enable,
+            vec!["DELL U2723QE".to_string()],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_description() {
+        let mut on = record(true);
+        assert_eq!(on.description(), "HDR on (Game started) · DELL U2723QE");
+
+        on.trigger = ToggleTrigger::Reconciliation;
+        on.error = Some("HDR could not be turned on for DELL U2723QE".to_string());
+        assert_eq!(
+            on.description(),
+            "HDR on (state check) · DELL U2723QE · failed: HDR could not be turned on for DELL U2723QE"
+        );
+        assert!(!on.succeeded());
+    }
+
+    #[test]
+    fn test_age() {
+        let mut toggle = record(true);
+        toggle.timestamp = 1_000_000;
+        let at = |secs: u64| UNIX_EPOCH + std::time::Duration::from_secs(secs);
+
+        assert_eq!(toggle.age(at(1_000_030)), "just now");
+        assert_eq!(toggle.age(at(1_000_000 + 14 * 60)), "14 min ago");
+        assert_eq!(toggle.age(at(1_000_000 + 3 * 3_600)), "3 h ago");
+        assert_eq!(toggle.age(at(1_000_000 + 2 * 86_400)), "2 days ago");
+        // Clock changes never make a record lie in the future
+        assert_eq!(toggle.age(UNIX_EPOCH), "just now");
+    }
+
+    #[test]
+    fn test_records_survive_reload() {
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let mut history = ToggleHistory::default();
+        history.record(record(true));
+        history.record(record(false));
+
+        let loaded = ToggleHistory::load();
+        let records: Vec<_> = loaded.records().collect();
+        assert_eq!(records.len(), 2);
+        assert!(!records[0].enable, "newest record comes first");
+        assert!(records[1].enable);
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_most_recent() {
+        let mut history = ToggleHistory::default();
+        for _ in 0..MAX_RECENT {
+            history.push(record(true));
+        }
+        history.push(record(false));
+
+        assert_eq!(history.records().count(), MAX_RECENT);
+        assert!(!history.records().next().unwrap().enable);
+    }
+
+    #[test]
+    fn test_log_rotates_when_too_large() {
+        let test_dir = create_test_dir();
+        let _guard = AppdataGuard::new(&test_dir);
+
+        let path = ToggleHistory::get_log_path();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, vec![b'\n'; usize::try_from(MAX_LOG_BYTES).unwrap()]).unwrap();
+
+        ToggleHistory::default().record(record(true));
+
+        assert!(path.with_extension("1.jsonl").exists());
+        assert_eq!(ToggleHistory::load().records().count(), 1);
+    }
+}
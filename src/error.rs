@@ -22,6 +22,26 @@ impl StringError {
     }
 }
 
+/// Failed Windows API call that returned a Win32 error code
+#[derive(Debug, Error)]
+#[error("{message}: error code {code}")]
+pub struct ApiError {
+    /// What failed, e.g. "Failed to set HDR state"
+    pub message: String,
+    /// Win32 error code the call returned
+    pub code: i32,
+}
+
+impl ApiError {
+    /// Create a new `ApiError` from a message and the returned error code
+    pub fn new(message: impl Into<String>, code: i32) -> Box<Self> {
+        Box::new(Self {
+            message: message.into(),
+            code,
+        })
+    }
+}
+
 /// Icon cache error types
 ///
 /// Structured error types for icon cache operations following thiserror pattern.
@@ -34,8 +54,8 @@ impl StringError {
 )]
 pub enum IconCacheError {
     /// Invalid icon data size (must be exactly 4096 bytes for 32x32 RGBA)
-    #[error("Invalid icon data size: expected 4096 bytes, got {actual}")]
-    InvalidIconSize { actual: usize },
+    #[error("Invalid icon data size: expected {expected} bytes, got {actual}")]
+    InvalidIconSize { expected: usize, actual: usize },
 
     /// Failed to create icon cache directory
     /// Preserves the underlying I/O error source
@@ -126,6 +146,22 @@ pub enum IconCacheError {
         source: std::io::Error,
     },
 
+    /// Failed to scan the cache directory for corrupt icons
+    #[error("Failed to verify icon cache at {path}")]
+    CacheVerificationFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Failed to scan the cache directory for orphaned or excess icons
+    #[error("Failed to clean up icon cache at {path}")]
+    CacheMaintenanceFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     /// Failed to get file metadata for cache validation
     #[error("Failed to get metadata for {path}")]
     MetadataError {
@@ -185,6 +221,10 @@ pub enum EasyHdrError {
     #[error("Failed to enumerate UWP packages: {0}")]
     UwpEnumerationError(#[source] Box<dyn std::error::Error + Send + Sync>),
 
+    /// Enumerating packages for all users requires an elevated process
+    #[error("Enumerating UWP packages for all users requires administrator privileges")]
+    UwpElevationRequired,
+
     /// Invalid package family name
     #[error("Invalid package family name: {0}")]
     InvalidPackageFamilyName(String),
@@ -208,6 +248,40 @@ pub enum EasyHdrError {
     IconCache(#[from] IconCacheError),
 }
 
+impl EasyHdrError {
+    /// Windows error code behind the error, if it came from a failed API call
+    pub fn error_code(&self) -> Option<i32> {
+        match self {
+            Self::HdrControlFailed(source) | Self::DriverError(source) => {
+                if let Some(api_error) = source.downcast_ref::<ApiError>() {
+                    Some(api_error.code)
+                } else {
+                    source.downcast_ref::<Self>().and_then(Self::error_code)
+                }
+            }
+            #[cfg(windows)]
+            Self::WindowsApiError(e) => Some(win32_code(e.code().0)),
+            _ => None,
+        }
+    }
+}
+
+/// Win32 error code wrapped in an HRESULT, or the HRESULT itself for other facilities
+///
+/// Failed Win32 calls surface through the `windows` crate as `HRESULT_FROM_WIN32`
+/// values, e.g. `0x80070005` for `ERROR_ACCESS_DENIED` (5).
+pub fn win32_code(hresult: i32) -> i32 {
+    /// Severity bit and `FACILITY_WIN32` of an HRESULT that wraps a Win32 error code
+    const FACILITY_WIN32_FAILURE: u32 = 0x8007_0000;
+
+    let bits = hresult.cast_unsigned();
+    if bits & 0xFFFF_0000 == FACILITY_WIN32_FAILURE {
+        (bits & 0xFFFF).cast_signed()
+    } else {
+        hresult
+    }
+}
+
 /// Result type alias for `EasyHDR` operations
 pub type Result<T> = std::result::Result<T, EasyHdrError>;
 
@@ -218,6 +292,7 @@ pub type Result<T> = std::result::Result<T, EasyHdrError>;
 ///
 /// The messages include detailed troubleshooting hints to help users
 /// resolve common issues.
+#[expect(clippy::too_many_lines, reason = "One message per error variant")]
 pub fn get_user_friendly_error(error: &EasyHdrError) -> String {
     match error {
         EasyHdrError::HdrNotSupported => "Your display doesn't support HDR.\n\n\
@@ -238,11 +313,13 @@ pub fn get_user_friendly_error(error: &EasyHdrError) -> String {
              The application may not function correctly.\n\
              Try restarting the application."
             .to_string(),
-        EasyHdrError::ConfigError(_) => "Failed to load or save configuration.\n\n\
+        EasyHdrError::ConfigError(_) => format!(
+            "Failed to load or save configuration.\n\n\
              Your settings may not persist.\n\
              Check that you have write permissions to:\n\
-             %APPDATA%\\EasyHDR"
-            .to_string(),
+             {}",
+            crate::utils::data_dir::get_data_dir().display()
+        ),
         #[cfg(windows)]
         EasyHdrError::WindowsApiError(e) => {
             format!(
@@ -275,6 +352,13 @@ pub fn get_user_friendly_error(error: &EasyHdrError) -> String {
              - Windows Store services are running\n\
              - Your Windows installation is not corrupted"
             .to_string(),
+        EasyHdrError::UwpElevationRequired => {
+            "Listing applications installed for all users requires administrator privileges.\n\n\
+             Please either:\n\
+             - Restart EasyHDR as administrator, or\n\
+             - Uncheck 'Show apps installed for all users' to list only your applications"
+                .to_string()
+        }
         EasyHdrError::InvalidPackageFamilyName(name) => {
             format!(
                 "Invalid package family name: {name}\n\n\
@@ -302,12 +386,14 @@ pub fn get_user_friendly_error(error: &EasyHdrError) -> String {
                  This does not affect functionality."
             )
         }
-        EasyHdrError::IconCache(_) => "Icon cache error occurred.\n\n\
+        EasyHdrError::IconCache(_) => format!(
+            "Icon cache error occurred.\n\n\
              Icon caching may not work correctly, but the application\n\
              will continue to function normally with icons in memory.\n\
              Check that you have write permissions to:\n\
-             %APPDATA%\\EasyHDR\\icon_cache"
-            .to_string(),
+             {}",
+            crate::utils::IconCache::default_cache_dir().display()
+        ),
     }
 }
 
@@ -334,4 +420,33 @@ mod tests {
         let error: EasyHdrError = io_error.into();
         assert!(matches!(error, EasyHdrError::IoError(_)));
     }
+
+    #[test]
+    fn test_error_code() {
+        let error = EasyHdrError::HdrControlFailed(ApiError::new("Failed to set HDR state", 31));
+        assert_eq!(error.error_code(), Some(31));
+        assert_eq!(
+            error.to_string(),
+            "Failed to control HDR: Failed to set HDR state: error code 31"
+        );
+
+        let wrapped = EasyHdrError::HdrControlFailed(Box::new(error));
+        assert_eq!(wrapped.error_code(), Some(31));
+        assert_eq!(
+            EasyHdrError::DriverError(StringError::new("no code")).error_code(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_win32_code() {
+        // HRESULT_FROM_WIN32(ERROR_ACCESS_DENIED)
+        assert_eq!(win32_code(0x8007_0005_u32.cast_signed()), 5);
+        // E_FAIL is not a Win32 error and is kept
+        assert_eq!(
+            win32_code(0x8000_4005_u32.cast_signed()),
+            0x8000_4005_u32.cast_signed()
+        );
+        assert_eq!(win32_code(31), 31);
+    }
 }
@@ -0,0 +1,233 @@
+//! Screen reader support for the application list
+//!
+//! Rows of the application list are read out as their name followed by a description of
+//! their state, built by [`row_description`]. Changes to monitored applications that
+//! don't happen where the keyboard focus is, such as an application snoozed from a
+//! notification or one whose drive went offline, are found by comparing
+//! [`AppSnapshot`]s of consecutive list updates and announced through UI Automation
+//! notifications by [`announce`], so screen readers speak them without moving the focus.
+
+use crate::{AppListItem, MainWindow};
+
+/// Activity ID of EasyHDR's UI Automation notifications, lets screen readers group them
+#[cfg(windows)]
+const ACTIVITY_ID: &str = "EasyHDR.AppListChanged";
+
+/// Spoken state of an application list row, e.g. "Win32 application, monitored, snoozed"
+pub fn row_description(item: &AppListItem) -> String {
+    let mut parts = vec![
+        match item.app_type.as_str() {
+            "uwp" => "UWP application".to_string(),
+            "folder" => "Folder".to_string(),
+            _ => "Win32 application".to_string(),
+        },
+        if item.enabled {
+            "monitored".to_string()
+        } else {
+            "not monitored".to_string()
+        },
+    ];
+    if item.source != "manual" {
+        parts.push(item.source.to_string());
+    }
+    if item.offline {
+        parts.push("drive not connected".to_string());
+    }
+    if item.snoozed {
+        parts.push("snoozed".to_string());
+    }
+    if !item.display_mode.is_empty() {
+        parts.push(format!("switches to {}", item.display_mode));
+    }
+    if !item.stats.is_empty() {
+        parts.push(item.stats.replace(" · ", ", "));
+    }
+    if !item.warning.is_empty() {
+        parts.push(item.warning.to_string());
+    }
+    parts.join(", ")
+}
+
+/// State of a monitored application whose changes are announced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppSnapshot {
+    /// Application ID
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Whether the application is monitored
+    pub enabled: bool,
+    /// Whether automatic HDR management ignores it for a while
+    pub snoozed: bool,
+    /// Whether its drive is unreachable
+    pub offline: bool,
+}
+
+impl AppSnapshot {
+    /// State of an application list row
+    pub fn of(item: &AppListItem) -> Self {
+        Self {
+            id: item.id.to_string(),
+            name: item.display_name.to_string(),
+            enabled: item.enabled,
+            snoozed: item.snoozed,
+            offline: item.offline,
+        }
+    }
+}
+
+/// Announcements for the changes between two application lists, in list order
+pub fn changes(previous: &[AppSnapshot], current: &[AppSnapshot]) -> Vec<String> {
+    let mut announcements = Vec::new();
+    for app in current {
+        let Some(before) = previous.iter().find(|before| before.id == app.id) else {
+            announcements.push(format!("{} added", app.name));
+            continue;
+        };
+        if before.enabled != app.enabled {
+            announcements.push(if app.enabled {
+                format!("{} is monitored", app.name)
+            } else {
+                format!("{} is no longer monitored", app.name)
+            });
+        }
+        if before.snoozed != app.snoozed {
+            announcements.push(if app.snoozed {
+                format!("{} snoozed", app.name)
+            } else {
+                format!("{} resumed", app.name)
+            });
+        }
+        if before.offline != app.offline {
+            announcements.push(if app.offline {
+                format!("{} is offline, its drive is not connected", app.name)
+            } else {
+                format!("{} is back online", app.name)
+            });
+        }
+    }
+    announcements.extend(
+        previous
+            .iter()
+            .filter(|before| current.iter().all(|app| app.id != before.id))
+            .map(|before| format!("{} removed", before.name)),
+    );
+    announcements
+}
+
+/// Have screen readers speak `message` without moving the focus
+///
+/// Raises a UI Automation notification on the main window. Does nothing while no UI
+/// Automation client, such as Narrator or NVDA, is running.
+///
+/// # Safety
+///
+/// The window handle comes from the live main window on the GUI thread, and the
+/// provider and strings outlive the calls using them.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for raising UI Automation notifications via UiaRaiseNotificationEvent"
+)]
+pub fn announce(window: &MainWindow, message: &str) {
+    use slint::ComponentHandle;
+    use slint::winit_030::WinitWindowAccessor;
+    use slint::winit_030::winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use tracing::{debug, warn};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Accessibility::{
+        NotificationKind_ActionCompleted, NotificationProcessing_ImportantAll,
+        UiaClientsAreListening, UiaHostProviderFromHwnd, UiaRaiseNotificationEvent,
+    };
+    use windows::core::BSTR;
+
+    // SAFETY: has no preconditions
+    if !unsafe { UiaClientsAreListening() }.as_bool() {
+        return;
+    }
+    let Some(hwnd) = window
+        .window()
+        .with_winit_window(|winit_window| match winit_window.window_handle() {
+            Ok(handle) => match handle.as_raw() {
+                RawWindowHandle::Win32(handle) => Some(HWND(handle.hwnd.get() as *mut _)),
+                _ => None,
+            },
+            Err(_) => None,
+        })
+        .flatten()
+    else {
+        return;
+    };
+
+    // SAFETY: `hwnd` is the handle of the main window, which outlives the call
+    let result = unsafe { UiaHostProviderFromHwnd(hwnd) }.and_then(|provider| {
+        // SAFETY: the provider and both strings are valid for the duration of the call
+        unsafe {
+            UiaRaiseNotificationEvent(
+                &provider,
+                NotificationKind_ActionCompleted,
+                NotificationProcessing_ImportantAll,
+                &BSTR::from(message),
+                &BSTR::from(ACTIVITY_ID),
+            )
+        }
+    });
+    match result {
+        Ok(()) => debug!("Announced to screen readers: {}", message),
+        Err(e) => warn!("Failed to announce {:?} to screen readers: {}", message, e),
+    }
+}
+
+/// UI Automation only exists on Windows
+#[cfg(not(windows))]
+pub fn announce(_window: &MainWindow, _message: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(id: &str, enabled: bool, snoozed: bool, offline: bool) -> AppSnapshot {
+        AppSnapshot {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            enabled,
+            snoozed,
+            offline,
+        }
+    }
+
+    #[test]
+    fn test_changes() {
+        let previous = [app("a", true, false, false), app("b", true, false, false)];
+        assert!(changes(&previous, &previous).is_empty());
+
+        let current = [app("a", false, true, true), app("c", true, false, false)];
+        assert_eq!(
+            changes(&previous, &current),
+            [
+                "A is no longer monitored",
+                "A snoozed",
+                "A is offline, its drive is not connected",
+                "C added",
+                "B removed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_row_description() {
+        let item = AppListItem {
+            display_name: "Game".into(),
+            app_type: "win32".into(),
+            source: "imported".into(),
+            enabled: true,
+            snoozed: true,
+            stats: "2 sessions · 1.5 h with HDR".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            row_description(&item),
+            "Win32 application, monitored, imported, snoozed, 2 sessions, 1.5 h with HDR"
+        );
+    }
+}
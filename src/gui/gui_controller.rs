@@ -11,9 +11,14 @@
 //! - Handles file picker dialogs and error messages
 
 use easyhdr::config::models::MonitoredApp;
-use easyhdr::controller::{AppController, AppState};
+use easyhdr::controller::{
+    AppController, AppState, OnboardingState, PendingAction, PendingActionId,
+};
 use easyhdr::error::Result;
-use easyhdr::utils::UpdateCheckResult;
+use easyhdr::hdr::{AutoHdrMode, ColorMode};
+use easyhdr::monitor::RunningCandidate;
+use easyhdr::utils::icon_cache::{ICON_SIZE, IconCache};
+use easyhdr::utils::{UpdateCheckResult, tr, tr_args};
 use parking_lot::Mutex;
 #[cfg(windows)]
 use slint::Model;
@@ -33,7 +38,21 @@ use easyhdr::config::models::Win32App;
 use crate::MainWindow;
 
 // Import TrayIcon for system tray integration
-use super::tray::TrayIcon;
+use super::accessibility::{self, AppSnapshot};
+use super::theme::{self, SystemTheme};
+use super::tray::{TrayHdrState, TrayIcon};
+
+/// High-DPI icons by app and size in pixels, `None` while loading or when the cache has no
+/// icon for the app
+type HighDpiIcons = std::collections::HashMap<(uuid::Uuid, u32), Option<slint::Image>>;
+
+/// Candidates listed by the running-app picker, shared with the thread listing them
+type PickerCandidates = Arc<Mutex<Vec<RunningCandidate>>>;
+
+thread_local! {
+    /// High-DPI icons shown in the application list, cleared when the window is hidden
+    static HIGH_DPI_ICONS: RefCell<HighDpiIcons> = RefCell::default();
+}
 
 /// Messages sent from background threads to the GUI event loop
 enum UiMessage {
@@ -53,6 +72,8 @@ pub struct GuiController {
     state_receiver: mpsc::Receiver<AppState>,
     /// System tray icon for notifications and status display
     tray_icon: TrayIcon,
+    /// Pending actions shown with a countdown in the main window, in display order
+    pending_actions: Rc<RefCell<Vec<PendingAction>>>,
 }
 
 impl GuiController {
@@ -83,43 +104,21 @@ impl GuiController {
 
         info!("Main window created successfully");
 
+        // Look the labels of the window up in the string tables of the active language
+        let strings = main_window.global::<crate::Strings>();
+        strings.on_tr(|key| tr(&key).into());
+        strings.on_tr_arg(|key, value| tr_args(&key, &[("value", &value)]).into());
+
+        // Follow the Windows app mode and accent color, now and whenever they change
+        let system_theme = SystemTheme::current();
+        system_theme.apply(&main_window);
+        theme::watch(main_window.as_weak(), system_theme);
+
         // Restore window position and size from config
         Self::restore_window_state(&main_window, &controller);
 
         // Initialize settings properties from config
-        {
-            let controller_guard = controller.lock();
-            let config = controller_guard.config.read();
-
-            main_window.set_settings_auto_start(config.preferences.auto_start);
-            // Safe cast: monitoring_interval_ms is constrained to 500-2000ms range
-            #[expect(
-                clippy::cast_possible_truncation,
-                reason = "monitoring_interval_ms is constrained to 500-2000ms range by validation, well within i32 range"
-            )]
-            {
-                main_window.set_settings_monitoring_interval_ms(
-                    config.preferences.monitoring_interval_ms as i32,
-                );
-            }
-            main_window
-                .set_settings_show_tray_notifications(config.preferences.show_tray_notifications);
-            main_window.set_settings_show_update_notifications(
-                config.preferences.show_update_notifications,
-            );
-            main_window
-                .set_settings_auto_open_release_page(config.preferences.auto_open_release_page);
-            main_window.set_settings_minimize_to_tray_on_minimize(
-                config.preferences.minimize_to_tray_on_minimize,
-            );
-            main_window.set_settings_minimize_to_tray_on_close(
-                config.preferences.minimize_to_tray_on_close,
-            );
-            main_window
-                .set_settings_start_minimized_to_tray(config.preferences.start_minimized_to_tray);
-
-            info!("Settings properties initialized from config");
-        }
+        Self::load_settings(&main_window, &controller.lock());
 
         // Initialize cache info from icon cache
         Self::update_cache_info(&main_window);
@@ -146,6 +145,36 @@ impl GuiController {
             Self::show_uwp_picker(&controller_clone, &window_weak);
         });
 
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_add_folder(move || {
+            Self::show_folder_picker(&controller_clone, &window_weak);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_import_games(move || {
+            Self::show_game_import(&controller_clone, &window_weak);
+        });
+
+        // Candidates listed by the running-app picker (running or Start Menu applications),
+        // in the order of its rows
+        let running_candidates: PickerCandidates = Arc::default();
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        let candidates = running_candidates.clone();
+        main_window.on_add_running_application(move || {
+            Self::show_running_app_picker(&controller_clone, &window_weak, &candidates);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        let candidates = running_candidates.clone();
+        main_window.on_add_start_menu_application(move || {
+            Self::show_start_menu_picker(&controller_clone, &window_weak, &candidates);
+        });
+
         let controller_clone = controller.clone();
         main_window.on_remove_application(move |index| {
             Self::remove_app_at_index(&controller_clone, index);
@@ -157,33 +186,109 @@ impl GuiController {
         });
 
         let controller_clone = controller.clone();
-        main_window.on_save_settings(
-            move |auto_start,
-                  monitoring_interval_ms,
-                  show_tray_notifications,
-                  show_update_notifications,
-                  auto_open_release_page,
-                  minimize_to_tray_on_minimize,
-                  minimize_to_tray_on_close,
-                  start_minimized_to_tray| {
-                Self::save_settings(
+        main_window.on_resume_automatic_hdr(move || {
+            Self::resume_automatic_hdr(&controller_clone);
+        });
+
+        let pending_actions = Rc::new(RefCell::new(Vec::new()));
+        let controller_clone = controller.clone();
+        let pending_actions_clone = pending_actions.clone();
+        main_window.on_cancel_pending_action(move |index| {
+            Self::cancel_pending_action(&controller_clone, &pending_actions_clone, index);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_resume_application(move |index| {
+            Self::resume_app(&controller_clone, index);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_cycle_auto_hdr(move |index| {
+            Self::cycle_auto_hdr(&controller_clone, index);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_cycle_color_mode(move |index| {
+            Self::cycle_color_mode(&controller_clone, index);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_cycle_capture_tone_mapping(move |index| {
+            Self::cycle_capture_tone_mapping(&controller_clone, index);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_open_app_settings(move |index| {
+            Self::open_app_settings(&controller_clone, &window_weak, index);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_save_app_settings(
+            move |index,
+                  display_name,
+                  color_mode,
+                  delay,
+                  refresh_rate,
+                  resolution,
+                  match_kind,
+                  match_text,
+                  aliases,
+                  display_group| {
+                Self::save_app_settings(
                     &controller_clone,
-                    auto_start,
-                    monitoring_interval_ms,
-                    show_tray_notifications,
-                    show_update_notifications,
-                    auto_open_release_page,
-                    minimize_to_tray_on_minimize,
-                    minimize_to_tray_on_close,
-                    start_minimized_to_tray,
+                    index,
+                    &display_name,
+                    &color_mode,
+                    &delay,
+                    &refresh_rate,
+                    &resolution,
+                    &match_kind,
+                    &match_text,
+                    &aliases,
+                    &display_group,
                 );
             },
         );
 
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_open_displays(move || {
+            Self::open_displays(&controller_clone, &window_weak);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_save_displays(move || {
+            Self::save_displays(&controller_clone, &window_weak);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_open_statistics(move || {
+            Self::open_statistics(&controller_clone, &window_weak);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_save_settings(move |settings| {
+            Self::save_settings(&controller_clone, &settings);
+        });
+
         let controller_clone = controller.clone();
         let window_weak = main_window.as_weak();
         main_window.on_check_for_updates(move || {
-            Self::check_for_updates(&controller_clone, &window_weak);
+            Self::check_for_updates(&controller_clone, &window_weak, false);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_install_update(move || {
+            Self::install_update(&controller_clone, &window_weak);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_restart_to_update(move || {
+            Self::restart_to_update(&controller_clone);
         });
 
         let window_weak = main_window.as_weak();
@@ -191,6 +296,68 @@ impl GuiController {
             Self::clear_icon_cache(&window_weak);
         });
 
+        let controller_clone = controller.clone();
+        main_window.on_export_stats(move || {
+            Self::export_stats(&controller_clone);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_export_config(move || {
+            Self::export_config(&controller_clone);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_import_config(move || {
+            Self::import_config(&controller_clone, &window_weak);
+        });
+
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_start_process_trace(move || {
+            Self::start_process_trace(&controller_clone, &window_weak);
+        });
+
+        let window_weak = main_window.as_weak();
+        main_window.on_clear_recent_errors(move || {
+            easyhdr::utils::error_center::clear();
+            if let Some(window) = window_weak.upgrade() {
+                Self::update_recent_errors(&window);
+            }
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_start_manual_session(move |minutes, display_mode| {
+            Self::start_manual_session(&controller_clone, minutes, &display_mode);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_stop_manual_session(move || {
+            Self::stop_manual_session(&controller_clone);
+        });
+
+        // First-run setup callbacks
+        let controller_clone = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.on_onboarding_next(move || {
+            Self::onboarding_next(&controller_clone, &window_weak);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_onboarding_skip(move || {
+            controller_clone.lock().finish_onboarding();
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_onboarding_start_hdr_test(move || {
+            Self::start_hdr_test(&controller_clone);
+        });
+
+        let controller_clone = controller.clone();
+        main_window.on_onboarding_end_hdr_test(move |worked| {
+            Self::end_hdr_test(&controller_clone, worked);
+        });
+
         // UWP picker callbacks
         #[cfg(windows)]
         {
@@ -219,6 +386,75 @@ impl GuiController {
             main_window.on_uwp_picker_deselect_all(move || {
                 Self::uwp_picker_deselect_all(&window_weak);
             });
+
+            let controller_clone = controller.clone();
+            let window_weak = main_window.as_weak();
+            main_window.on_uwp_picker_set_all_users(move |all_users| {
+                Self::uwp_picker_set_all_users(&controller_clone, &window_weak, all_users);
+            });
+
+            let controller_clone = controller.clone();
+            let window_weak = main_window.as_weak();
+            main_window.on_uwp_picker_set_games_only(move |_games_only| {
+                Self::show_uwp_picker(&controller_clone, &window_weak);
+            });
+        }
+
+        // Running application picker callbacks
+        #[cfg(windows)]
+        {
+            let controller_clone = controller.clone();
+            let window_weak = main_window.as_weak();
+            let candidates = running_candidates.clone();
+            main_window.on_running_app_add_selected(move || {
+                Self::running_app_add_selected(&controller_clone, &window_weak, &candidates);
+            });
+
+            let window_weak = main_window.as_weak();
+            let candidates = running_candidates.clone();
+            main_window.on_running_app_cancel(move || {
+                Self::running_app_cancel(&window_weak, &candidates);
+            });
+
+            let window_weak = main_window.as_weak();
+            main_window.on_running_app_toggle_selection(move |index, selected| {
+                Self::running_app_toggle_selection(&window_weak, index, selected);
+            });
+
+            let window_weak = main_window.as_weak();
+            main_window.on_running_app_search_changed(move |text| {
+                Self::running_app_search(&window_weak, &text);
+            });
+        }
+
+        // Game library import callbacks
+        #[cfg(windows)]
+        {
+            let controller_clone = controller.clone();
+            let window_weak = main_window.as_weak();
+            main_window.on_game_import_add_selected(move || {
+                Self::game_import_add_selected(&controller_clone, &window_weak);
+            });
+
+            let window_weak = main_window.as_weak();
+            main_window.on_game_import_cancel(move || {
+                Self::game_import_cancel(&window_weak);
+            });
+
+            let window_weak = main_window.as_weak();
+            main_window.on_game_import_toggle_selection(move |index, selected| {
+                Self::game_import_toggle_selection(&window_weak, index, selected);
+            });
+
+            let window_weak = main_window.as_weak();
+            main_window.on_game_import_select_all(move || {
+                Self::game_import_set_all_selected(&window_weak, true);
+            });
+
+            let window_weak = main_window.as_weak();
+            main_window.on_game_import_deselect_all(move || {
+                Self::game_import_set_all_selected(&window_weak, false);
+            });
         }
 
         info!("GUI callbacks connected");
@@ -250,32 +486,71 @@ impl GuiController {
                     Self::save_window_state(&window, &controller_for_close);
                 }
 
-                // Exit the application immediately
-                // Note: We use std::process::exit(0) instead of slint::quit_event_loop() because:
-                // 1. quit_event_loop() is asynchronous and doesn't guarantee immediate termination
-                // 2. Background threads (ProcessMonitor, AppController) run infinite loops with no shutdown signal
-                // 3. Returning KeepWindowShown after quit_event_loop() causes the window to hang
-                // 4. The OS will clean up all resources (memory, handles, threads) on process exit
-                // 5. Configuration has already been saved above, so no data loss occurs
-                info!("Exiting application");
-                std::process::exit(0);
+                Self::exit_application(&controller_for_close)
             }
         });
 
         info!("Close request handler configured (behavior controlled by user preference)");
 
+        #[cfg(windows)]
+        Self::connect_file_drop(&main_window, &controller);
+
         // Create the system tray icon
-        let tray_icon = TrayIcon::new(&main_window)?;
+        let log_level = controller.lock().config.read().preferences.log_level;
+        let controller_for_display_hdr = controller.clone();
+        let controller_for_log_level = controller.clone();
+        let controller_for_exit = controller.clone();
+        let tray_icon = TrayIcon::new(
+            &main_window,
+            log_level,
+            move |target, enable| {
+                let result = controller_for_display_hdr
+                    .lock()
+                    .set_display_hdr(&target, enable);
+                if let Err(e) = result {
+                    Self::show_error_dialog_from_error(&e);
+                }
+            },
+            move |level| {
+                controller_for_log_level.lock().set_log_level(level);
+            },
+            move || Self::exit_application(&controller_for_exit),
+        )?;
         info!("System tray icon created");
 
+        // Warm the UWP package cache so the picker opens instantly
+        #[cfg(windows)]
+        {
+            use easyhdr::uwp;
+            use tracing::warn;
+
+            let all_users = {
+                let controller_guard = controller.lock();
+                let config = controller_guard.config.read();
+                config.preferences.uwp_enumerate_all_users
+            };
+
+            let cache = uwp::get_package_cache();
+            cache.set_scope(Self::uwp_enumeration_scope(all_users));
+            if let Err(e) = cache.watch_package_changes() {
+                warn!("Failed to watch UWP package changes: {}", e);
+            }
+            cache.refresh_in_background();
+        }
+
         // Perform automatic update check on startup (in background)
         let controller_clone = controller.clone();
         let window_weak = main_window.as_weak();
         std::thread::spawn(move || {
-            // Wait a bit to let the UI fully initialize
-            std::thread::sleep(std::time::Duration::from_secs(2));
+            use easyhdr::utils::update_checker::{STARTUP_CHECK_JITTER_SECS, jitter_secs};
+
+            // Wait a bit to let the UI fully initialize, and spread out machines that
+            // start together
+            std::thread::sleep(std::time::Duration::from_secs(
+                2 + jitter_secs(STARTUP_CHECK_JITTER_SECS),
+            ));
             info!("Performing automatic update check on startup");
-            Self::check_for_updates(&controller_clone, &window_weak);
+            Self::check_for_updates(&controller_clone, &window_weak, true);
         });
 
         Ok(Self {
@@ -283,18 +558,17 @@ impl GuiController {
             controller_handle: controller,
             state_receiver,
             tray_icon,
+            pending_actions,
         })
     }
 
     /// Show file picker dialog for adding applications
     ///
-    /// Opens a native file picker dialog filtered to .exe files with multi-select support.
-    /// Extracts metadata and icon for each selected file, then adds them to the application list.
-    /// After adding applications, explicitly triggers a GUI update to ensure the new applications
-    /// appear immediately, even if the state sync thread failed to update due to the modal dialog.
+    /// Opens a native file picker dialog filtered to .exe files with multi-select support
+    /// and adds the selected files with [`Self::add_executables`].
     #[cfg(windows)]
     fn show_file_picker(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
-        use tracing::{info, warn};
+        use tracing::info;
 
         info!("Opening file picker dialog with multi-select support");
 
@@ -305,167 +579,350 @@ impl GuiController {
             .pick_files();
 
         if let Some(paths) = file_paths {
-            let file_count = paths.len();
-            info!("User selected {} file(s)", file_count);
-
-            let mut success_count = 0;
-            let mut error_count = 0;
-            // Pre-allocate with exact capacity since we know the maximum number of errors
-            let mut error_messages = Vec::with_capacity(file_count);
-
-            // Process each selected file
-            for path in paths {
-                info!("Processing file: {:?}", path);
-
-                // Extract metadata and create Win32App, then wrap in MonitoredApp
-                match Win32App::from_exe_path(path.clone()) {
-                    Ok(win32_app) => {
-                        info!(
-                            "Successfully extracted metadata for: {}",
-                            win32_app.display_name
-                        );
-
-                        // Wrap in MonitoredApp enum
-                        let app = MonitoredApp::Win32(win32_app);
-
-                        // Add application to controller
-                        let mut controller_guard = controller.lock();
-                        match controller_guard.add_application(app) {
-                            Ok(()) => {
-                                info!("Application added successfully");
-                                success_count += 1;
-                            }
-                            Err(e) => {
-                                warn!("Failed to add application: {}", e);
-                                error_count += 1;
-                                error_messages.push(format!(
-                                    "{}: {}",
-                                    path.file_name().unwrap_or_default().to_string_lossy(),
-                                    e
-                                ));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        warn!("Failed to extract metadata from {:?}: {}", path, e);
-                        error_count += 1;
-                        error_messages.push(format!(
-                            "{}: {}",
-                            path.file_name().unwrap_or_default().to_string_lossy(),
-                            e
-                        ));
-                    }
-                }
-            }
-
-            // Show summary if there were any errors
-            if error_count > 0 {
-                let summary = if success_count > 0 {
-                    format!(
-                        "Added {} application(s) successfully.\n\nFailed to add {} application(s):\n{}",
-                        success_count,
-                        error_count,
-                        error_messages.join("\n")
-                    )
-                } else {
-                    format!(
-                        "Failed to add all {} application(s):\n{}",
-                        error_count,
-                        error_messages.join("\n")
-                    )
-                };
-
-                Self::show_error_dialog(&summary);
-            } else if success_count > 0 {
-                info!("Successfully added {} application(s)", success_count);
-            }
-
-            // Manually trigger GUI update after file picker closes
-            // This ensures the new applications appear immediately, even if the state sync
-            // thread's update was skipped due to the modal dialog.
-            if success_count > 0 {
-                info!("Triggering manual GUI update after file picker operation");
-                Self::update_app_list_ui(controller, window);
-            }
+            info!("User selected {} file(s)", paths.len());
+            Self::add_executables(controller, window, paths);
         } else {
             info!("User cancelled file picker");
         }
     }
 
-    /// Stub implementation for non-Windows platforms
-    #[cfg(not(windows))]
-    fn show_file_picker(
-        _controller: &Arc<Mutex<AppController>>,
-        _window: &slint::Weak<MainWindow>,
-    ) {
-        Self::show_error_dialog("File picker is only supported on Windows");
-    }
-
-    /// Show UWP package picker dialog for adding UWP applications
+    /// Add executables or shortcuts to them as applications
     ///
-    /// Opens a dialog listing all installed UWP applications with their display names
-    /// and publisher information. Users can select one or more UWP apps to add to the
-    /// monitored app list.
+    /// Shortcuts are resolved to their target. Extracts metadata and icon for each
+    /// executable, then adds them to the application list. After adding applications,
+    /// explicitly triggers a GUI update to ensure the new applications appear
+    /// immediately, even if the state sync thread failed to update due to a modal dialog.
     #[cfg(windows)]
-    fn show_uwp_picker(_controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
-        use easyhdr::uwp;
+    fn add_executables(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+        paths: Vec<std::path::PathBuf>,
+    ) {
+        use easyhdr::utils::shortcut_resolver;
         use tracing::{info, warn};
 
-        info!("UWP picker button clicked - enumerating packages");
+        let file_count = paths.len();
 
-        // Get window handle
-        let Some(window) = window.upgrade() else {
-            warn!("Failed to upgrade window weak reference");
-            return;
-        };
+        let mut success_count = 0;
+        let mut error_count = 0;
+        // Pre-allocate with exact capacity since we know the maximum number of errors
+        let mut error_messages = Vec::with_capacity(file_count);
 
-        // Set loading state and clear previous data
-        window.set_uwp_picker_loading(true);
-        window.set_uwp_picker_error(slint::SharedString::from(""));
-        window.set_uwp_package_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+        // Process each selected file
+        for dropped in paths {
+            info!("Processing file: {:?}", dropped);
 
-        // Enumerate packages (blocking operation)
-        match uwp::enumerate_packages() {
-            Ok(mut packages) => {
-                info!("Successfully enumerated {} UWP packages", packages.len());
+            let path = match shortcut_resolver::executable_for(&dropped) {
+                Ok(path) => path,
+                Err(e) => {
+                    warn!("Cannot add {:?}: {}", dropped, e);
+                    error_count += 1;
+                    error_messages.push(format!(
+                        "{}: {}",
+                        dropped.file_name().unwrap_or_default().to_string_lossy(),
+                        e
+                    ));
+                    continue;
+                }
+            };
 
-                // Sort packages alphabetically by display name (case-insensitive)
-                packages.sort_by(|a, b| {
-                    a.display_name
-                        .to_lowercase()
-                        .cmp(&b.display_name.to_lowercase())
-                });
+            // The same executable may be picked through a junction or redirected folder
+            if controller.lock().find_win32_app_by_path(&path).is_some() {
+                info!("Skipping {:?}: already monitored", path);
+                error_count += 1;
+                error_messages.push(format!(
+                    "{}: already in the application list",
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                ));
+                continue;
+            }
 
-                // Convert to Slint model
-                let package_items: Vec<_> = packages
-                    .into_iter()
-                    .map(|pkg| {
-                        // Load icon if available (returns 32x32 RGBA bytes)
-                        let icon_data = if let Some(logo_stream) = &pkg.logo_stream {
-                            uwp::extract_icon_from_stream(logo_stream).ok()
-                        } else {
-                            None
-                        };
+            // Extract metadata and create Win32App, then wrap in MonitoredApp
+            match Win32App::from_exe_path(path.clone()) {
+                Ok(win32_app) => {
+                    info!(
+                        "Successfully extracted metadata for: {}",
+                        win32_app.display_name
+                    );
 
-                        // Convert RGBA bytes to Slint image
-                        let icon = if let Some(data) = icon_data {
-                            // Validate icon data size (32x32 RGBA = 4096 bytes)
-                            if data.len() == 32 * 32 * 4 {
-                                let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(
-                                    &data, 32, // width
-                                    32, // height
-                                );
-                                slint::Image::from_rgba8(buffer)
-                            } else {
-                                tracing::warn!(
-                                    "Icon data for UWP package '{}' has unexpected size: {} bytes (expected 4096)",
+                    // Wrap in MonitoredApp enum
+                    let mut app = MonitoredApp::Win32(win32_app);
+
+                    // No extractable icon: use a letter tile, cached like a normal icon
+                    if app.ensure_fallback_icon()
+                        && let Some(icon_data) = app.icon_data()
+                    {
+                        easyhdr::utils::IconCache::cache_icon_gracefully(
+                            *app.id(),
+                            icon_data,
+                            app.display_name(),
+                        );
+                    }
+
+                    // Add application to controller
+                    let mut controller_guard = controller.lock();
+                    match controller_guard.add_application(app) {
+                        Ok(()) => {
+                            info!("Application added successfully");
+                            success_count += 1;
+                        }
+                        Err(e) => {
+                            warn!("Failed to add application: {}", e);
+                            error_count += 1;
+                            error_messages.push(format!(
+                                "{}: {}",
+                                path.file_name().unwrap_or_default().to_string_lossy(),
+                                e
+                            ));
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to extract metadata from {:?}: {}", path, e);
+                    error_count += 1;
+                    error_messages.push(format!(
+                        "{}: {}",
+                        path.file_name().unwrap_or_default().to_string_lossy(),
+                        e
+                    ));
+                }
+            }
+        }
+
+        // Show summary if there were any errors
+        if error_count > 0 {
+            let summary = if success_count > 0 {
+                format!(
+                    "Added {} application(s) successfully.\n\nFailed to add {} application(s):\n{}",
+                    success_count,
+                    error_count,
+                    error_messages.join("\n")
+                )
+            } else {
+                format!(
+                    "Failed to add all {} application(s):\n{}",
+                    error_count,
+                    error_messages.join("\n")
+                )
+            };
+
+            Self::show_error_dialog(&summary);
+        } else if success_count > 0 {
+            info!("Successfully added {} application(s)", success_count);
+        }
+
+        // Manually trigger GUI update so the new applications appear immediately, even if
+        // the state sync thread's update was skipped due to a modal dialog.
+        if success_count > 0 {
+            info!("Triggering manual GUI update after adding applications");
+            Self::update_app_list_ui(controller, window);
+        }
+    }
+
+    /// Add executables and shortcuts dropped onto the main window
+    ///
+    /// Dropping several files delivers one event per file, so the files are collected and
+    /// added together once the event loop is idle, with a single error summary.
+    #[cfg(windows)]
+    fn connect_file_drop(main_window: &MainWindow, controller: &Arc<Mutex<AppController>>) {
+        use slint::winit_030::winit::event::WindowEvent;
+        use slint::winit_030::{EventResult, WinitWindowAccessor};
+
+        let dropped: Rc<RefCell<Vec<std::path::PathBuf>>> = Rc::default();
+        let controller = controller.clone();
+        let window_weak = main_window.as_weak();
+        main_window.window().on_winit_window_event(move |_, event| {
+            if let WindowEvent::DroppedFile(path) = event {
+                tracing::info!("File dropped onto the main window: {:?}", path);
+                let first = {
+                    let mut dropped = dropped.borrow_mut();
+                    dropped.push(path.clone());
+                    dropped.len() == 1
+                };
+                if first {
+                    let dropped = dropped.clone();
+                    let controller = controller.clone();
+                    let window_weak = window_weak.clone();
+                    Timer::single_shot(Duration::ZERO, move || {
+                        let paths = std::mem::take(&mut *dropped.borrow_mut());
+                        Self::add_executables(&controller, &window_weak, paths);
+                    });
+                }
+            }
+            EventResult::Propagate
+        });
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn show_file_picker(
+        _controller: &Arc<Mutex<AppController>>,
+        _window: &slint::Weak<MainWindow>,
+    ) {
+        Self::show_error_dialog("File picker is only supported on Windows");
+    }
+
+    /// Show folder picker dialog for monitoring every executable inside a folder
+    ///
+    /// Folders already on the list (compared through junctions and case) are not added
+    /// again.
+    #[cfg(windows)]
+    fn show_folder_picker(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+    ) {
+        use easyhdr::config::FolderApp;
+        use easyhdr::utils::canonical_path;
+        use tracing::{info, warn};
+
+        info!("Opening folder picker dialog");
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Select a Folder to Monitor")
+            .pick_folder()
+        else {
+            info!("User cancelled folder picker");
+            return;
+        };
+
+        let already_monitored = {
+            let controller_guard = controller.lock();
+            let config = controller_guard.config.read();
+            config.monitored_apps.iter().any(|app| {
+                matches!(app, MonitoredApp::Folder(folder_app)
+                    if canonical_path::same_path(&folder_app.path, &path))
+            })
+        };
+        if already_monitored {
+            info!("Skipping {:?}: already monitored", path);
+            Self::show_error_dialog(&format!(
+                "{} is already in the application list",
+                path.display()
+            ));
+            return;
+        }
+
+        let mut app = match FolderApp::from_path(path.clone()) {
+            Ok(folder_app) => MonitoredApp::Folder(folder_app),
+            Err(e) => {
+                warn!("Failed to add folder {:?}: {}", path, e);
+                Self::show_error_dialog_from_error(&e);
+                return;
+            }
+        };
+
+        // Folders have no icon of their own: use a letter tile, cached like a normal icon
+        if app.ensure_fallback_icon()
+            && let Some(icon_data) = app.icon_data()
+        {
+            easyhdr::utils::IconCache::cache_icon_gracefully(
+                *app.id(),
+                icon_data,
+                app.display_name(),
+            );
+        }
+
+        if let Err(e) = controller.lock().add_application(app) {
+            warn!("Failed to add folder: {}", e);
+            Self::show_error_dialog_from_error(&e);
+            return;
+        }
+
+        Self::update_app_list_ui(controller, window);
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn show_folder_picker(
+        _controller: &Arc<Mutex<AppController>>,
+        _window: &slint::Weak<MainWindow>,
+    ) {
+        Self::show_error_dialog("Folder picker is only supported on Windows");
+    }
+
+    /// Show UWP package picker dialog for adding UWP applications
+    ///
+    /// Opens a dialog listing all installed UWP applications with their display names
+    /// and publisher information. Users can select one or more UWP apps to add to the
+    /// monitored app list.
+    #[cfg(windows)]
+    fn show_uwp_picker(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
+        use easyhdr::uwp;
+        use tracing::{info, warn};
+
+        info!("UWP picker button clicked - loading packages from cache");
+
+        // Get window handle
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        // Apply the persisted enumeration scope before loading
+        let all_users = {
+            let controller_guard = controller.lock();
+            let config = controller_guard.config.read();
+            config.preferences.uwp_enumerate_all_users
+        };
+        window.set_uwp_picker_all_users(all_users);
+        uwp::get_package_cache().set_scope(Self::uwp_enumeration_scope(all_users));
+
+        // Set loading state and clear previous data
+        window.set_uwp_picker_loading(true);
+        window.set_uwp_picker_error(slint::SharedString::from(""));
+        window.set_uwp_package_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+
+        // Served from the package cache; only the very first open blocks on enumeration
+        match uwp::get_package_cache().packages() {
+            Ok(cached) => {
+                info!("Loaded {} UWP packages", cached.len());
+                let mut packages = cached.as_ref().clone();
+
+                // Manifest-based game classification hides system and utility apps
+                if window.get_uwp_picker_games_only() {
+                    packages.retain(|pkg| pkg.kind == uwp::PackageKind::Game);
+                }
+
+                // Sort packages alphabetically by display name (case-insensitive)
+                packages.sort_by(|a, b| {
+                    a.display_name
+                        .to_lowercase()
+                        .cmp(&b.display_name.to_lowercase())
+                });
+
+                // Convert to Slint model
+                let package_items: Vec<_> = packages
+                    .into_iter()
+                    .map(|pkg| {
+                        // Load icon if available (returns 32x32 RGBA bytes), falling back
+                        // to a letter tile so the list never shows blank squares
+                        let icon_data = pkg
+                            .logo_stream
+                            .as_ref()
+                            .and_then(|logo_stream| uwp::extract_icon_from_stream(logo_stream).ok())
+                            .filter(|data| !data.is_empty())
+                            .unwrap_or_else(|| {
+                                easyhdr::utils::generate_letter_tile(&pkg.display_name)
+                            });
+
+                        // Convert RGBA bytes to Slint image
+                        let icon = {
+                            // Validate icon data size (32x32 RGBA = 4096 bytes)
+                            let data = if icon_data.len() == 32 * 32 * 4 {
+                                icon_data
+                            } else {
+                                tracing::warn!(
+                                    "Icon data for UWP package '{}' has unexpected size: {} bytes (expected 4096)",
                                     pkg.display_name,
-                                    data.len()
+                                    icon_data.len()
                                 );
-                                slint::Image::default()
-                            }
-                        } else {
-                            slint::Image::default()
+                                easyhdr::utils::generate_letter_tile(&pkg.display_name)
+                            };
+                            let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(
+                                &data, 32, // width
+                                32, // height
+                            );
+                            slint::Image::from_rgba8(buffer)
                         };
 
                         crate::UwpPackageListItem {
@@ -475,6 +932,8 @@ impl GuiController {
                             app_id: slint::SharedString::from(pkg.app_id),
                             icon,
                             selected: false,
+                            installed_for_current_user: pkg.installed_for_current_user,
+                            multi_app_package: pkg.multi_app_package,
                         }
                     })
                     .collect();
@@ -486,328 +945,1822 @@ impl GuiController {
             }
             Err(e) => {
                 warn!("Failed to enumerate UWP packages: {}", e);
-                window.set_uwp_picker_error(slint::SharedString::from(format!(
-                    "Failed to load UWP applications: {e}"
-                )));
+                let message = if matches!(e, easyhdr::error::EasyHdrError::UwpElevationRequired) {
+                    easyhdr::error::get_user_friendly_error(&e)
+                } else {
+                    format!("Failed to load UWP applications: {e}")
+                };
+                window.set_uwp_picker_error(slint::SharedString::from(message));
                 window.set_uwp_picker_loading(false);
             }
         }
     }
 
+    /// Map the all-users preference to a package enumeration scope
+    #[cfg(windows)]
+    fn uwp_enumeration_scope(all_users: bool) -> easyhdr::uwp::PackageEnumerationScope {
+        use easyhdr::uwp::PackageEnumerationScope;
+
+        if all_users {
+            PackageEnumerationScope::AllUsers
+        } else {
+            PackageEnumerationScope::CurrentUser
+        }
+    }
+
+    /// Handle the UWP picker "all users" checkbox
+    ///
+    /// Persists the preference and reloads the package list for the new scope.
+    #[cfg(windows)]
+    fn uwp_picker_set_all_users(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+        all_users: bool,
+    ) {
+        use tracing::{info, warn};
+
+        info!("UWP picker: all-users enumeration set to {}", all_users);
+
+        {
+            let controller_guard = controller.lock();
+            controller_guard
+                .config
+                .write()
+                .preferences
+                .uwp_enumerate_all_users = all_users;
+
+            let config = controller_guard.config.read();
+            if let Err(e) = easyhdr::config::ConfigManager::save(&config) {
+                warn!("Failed to save UWP enumeration scope preference: {}", e);
+            } else {
+                drop(config);
+                controller_guard.notify_config_saved();
+            }
+        }
+
+        Self::show_uwp_picker(controller, window);
+    }
+
     /// Stub implementation for non-Windows platforms
     #[cfg(not(windows))]
     fn show_uwp_picker(_controller: &Arc<Mutex<AppController>>, _window: &slint::Weak<MainWindow>) {
         Self::show_error_dialog("UWP application picker is only supported on Windows");
     }
 
-    /// Collect application list items for UI consumption
+    /// Show the running application picker
     ///
-    /// Converts the monitored applications from the controller configuration
-    /// into `AppListItem` instances that can be displayed by the Slint UI.
-    /// The returned list is sorted alphabetically by display name (case-insensitive).
-    fn collect_app_list_items(controller: &Arc<Mutex<AppController>>) -> Vec<crate::AppListItem> {
-        let controller_guard = controller.lock();
-        // Phase 3.1: Use read lock for concurrent access
-        let config = controller_guard.config.read();
-
-        let mut items: Vec<_> = config
-            .monitored_apps
-            .iter()
-            .map(|app| {
-                // Get icon data using helper method
-                let icon_data_ref = app.icon_data();
+    /// Lists the applications running now that are not monitored yet, one row per
+    /// executable or UWP application, with a search field to narrow the list. The process
+    /// snapshot and icon extraction run on a background thread without holding the
+    /// controller, which is only locked to leave out monitored applications.
+    #[cfg(windows)]
+    fn show_running_app_picker(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+        candidates: &PickerCandidates,
+    ) {
+        use easyhdr::monitor::running_candidates;
+        use tracing::{info, warn};
 
-                let icon = if let Some(icon_data) = icon_data_ref {
-                    // Validate icon data size (32x32 RGBA = 4096 bytes)
-                    if icon_data.len() == 32 * 32 * 4 {
-                        let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(
-                            icon_data, 32, // width
-                            32, // height
-                        );
-                        slint::Image::from_rgba8(buffer)
-                    } else {
-                        tracing::warn!(
-                            "Icon data for {} has unexpected size: {} bytes (expected 4096)",
-                            app.display_name(),
-                            icon_data.len()
-                        );
-                        slint::Image::default()
-                    }
-                } else {
-                    slint::Image::default()
-                };
+        info!("Add running app button clicked - listing running applications");
 
-                // Get exe_path and app_type based on app variant
-                let (exe_path, app_type) = match app {
-                    MonitoredApp::Win32(win32_app) => {
-                        (win32_app.exe_path.to_string_lossy().to_string(), "win32")
-                    }
-                    MonitoredApp::Uwp(uwp_app) => {
-                        (format!("Package: {}", uwp_app.package_family_name), "uwp")
-                    }
-                };
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
 
-                crate::AppListItem {
-                    id: app.id().to_string().into(),
-                    display_name: app.display_name().to_string().into(),
-                    exe_path: exe_path.into(),
-                    enabled: app.is_enabled(),
-                    icon,
-                    app_type: app_type.into(),
-                }
-            })
-            .collect();
+        window.set_running_app_source("running".into());
+        window.set_running_app_loading(true);
+        window.set_running_app_error(slint::SharedString::new());
+        window.set_running_app_search(slint::SharedString::new());
+        window.set_running_app_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+        candidates.lock().clear();
+
+        let controller = controller.clone();
+        let window_weak = window.as_weak();
+        let candidates = candidates.clone();
+        let spawned = std::thread::Builder::new()
+            .name("running-app-picker".to_string())
+            .spawn(move || {
+                let listed = running_candidates::list_running_candidates().map(|mut listed| {
+                    controller.lock().retain_unmonitored(&mut listed);
+                    listed
+                });
 
-        drop(config);
-        drop(controller_guard);
+                let _ = window_weak.upgrade_in_event_loop(move |window| {
+                    // The picker was closed or switched to the Start Menu meanwhile
+                    if !window.get_running_app_loading()
+                        || window.get_running_app_source() != "running"
+                    {
+                        return;
+                    }
 
-        // Sort alphabetically by display name (case-insensitive)
-        items.sort_by(|a, b| {
-            a.display_name
-                .to_lowercase()
-                .cmp(&b.display_name.to_lowercase())
-        });
+                    match listed {
+                        Ok(listed) => {
+                            info!("Found {} running application(s) to offer", listed.len());
+                            Self::set_running_app_candidates(&window, &candidates, listed);
+                        }
+                        Err(e) => {
+                            warn!("Failed to list running applications: {}", e);
+                            window.set_running_app_error(
+                                format!("Failed to list running applications: {e}").into(),
+                            );
+                            window.set_running_app_loading(false);
+                        }
+                    }
+                });
+            });
 
-        items
+        if let Err(e) = spawned {
+            warn!("Failed to spawn running application listing: {}", e);
+            window
+                .set_running_app_error(format!("Failed to list running applications: {e}").into());
+            window.set_running_app_loading(false);
+        }
     }
 
-    /// Apply a new `AppState` to the GUI on the main thread
+    /// Show the application picker listing the Start Menu
     ///
-    /// Updates the status indicator, application list, tray icon, and optional
-    /// notifications to reflect the latest HDR state.
-    fn apply_ui_state(
-        window_weak: &slint::Weak<MainWindow>,
+    /// Lists the Start Menu shortcuts to executables that are not monitored yet, named
+    /// after the shortcut, in the same picker as the running applications. Like the
+    /// running application picker, the scan resolves shortcuts and extracts icons on a
+    /// background thread and only locks the controller to leave out monitored
+    /// applications.
+    #[cfg(windows)]
+    fn show_start_menu_picker(
         controller: &Arc<Mutex<AppController>>,
-        tray_icon: &Rc<RefCell<TrayIcon>>,
-        window_visibility: &Cell<bool>,
-        previous_hdr_state: &Cell<Option<bool>>,
-        state: &AppState,
+        window: &slint::Weak<MainWindow>,
+        candidates: &PickerCandidates,
     ) {
-        use tracing::{debug, info, warn};
+        use easyhdr::monitor::running_candidates;
+        use tracing::{info, warn};
 
-        let window_opt = window_weak.upgrade();
-        if let Some(window) = window_opt {
-            let window_visible = window.window().is_visible();
+        info!("Add from Start Menu button clicked - scanning the Start Menu");
 
-            // Detect when window becomes visible again (e.g., restored from tray)
-            // and reload GUI resources that were released during minimize
-            if window_visible && !window_visibility.get() {
-                info!("Window shown, reloading GUI resources");
-                Self::reload_gui_resources(controller);
-            }
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
 
-            window_visibility.set(window_visible);
+        window.set_running_app_source("start-menu".into());
+        window.set_running_app_loading(true);
+        window.set_running_app_error(slint::SharedString::new());
+        window.set_running_app_search(slint::SharedString::new());
+        window.set_running_app_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+        candidates.lock().clear();
+
+        let controller = controller.clone();
+        let window_weak = window.as_weak();
+        let candidates = candidates.clone();
+        let spawned = std::thread::Builder::new()
+            .name("start-menu-picker".to_string())
+            .spawn(move || {
+                let mut listed = running_candidates::list_start_menu_candidates();
+                controller.lock().retain_unmonitored(&mut listed);
+
+                let _ = window_weak.upgrade_in_event_loop(move |window| {
+                    // The picker was closed or switched to running applications meanwhile
+                    if !window.get_running_app_loading()
+                        || window.get_running_app_source() != "start-menu"
+                    {
+                        return;
+                    }
 
-            window.set_hdr_enabled(state.hdr_enabled);
-            debug!("Updated HDR enabled state to: {}", state.hdr_enabled);
+                    info!("Found {} Start Menu application(s) to offer", listed.len());
+                    Self::set_running_app_candidates(&window, &candidates, listed);
+                });
+            });
 
-            let app_list = Self::collect_app_list_items(controller);
-            let app_list_model = Rc::new(slint::VecModel::from(app_list));
-            window.set_app_list(app_list_model.into());
-            debug!("Updated application list in UI");
-        } else {
-            warn!("Window no longer exists, skipping UI update");
+        if let Err(e) = spawned {
+            warn!("Failed to spawn the Start Menu scan: {}", e);
+            window.set_running_app_error(format!("Failed to scan the Start Menu: {e}").into());
+            window.set_running_app_loading(false);
         }
+    }
 
-        let previous_state = previous_hdr_state.replace(Some(state.hdr_enabled));
-        let state_changed = previous_state != Some(state.hdr_enabled);
-        let had_previous = previous_state.is_some();
+    /// Fill the application picker with candidates, one row each in the same order
+    #[cfg(windows)]
+    fn set_running_app_candidates(
+        window: &MainWindow,
+        candidates: &PickerCandidates,
+        listed: Vec<RunningCandidate>,
+    ) {
+        use easyhdr::monitor::CandidateKind;
 
-        if !state_changed {
+        let items: Vec<_> = listed
+            .iter()
+            .map(|candidate| {
+                let icon_data = candidate
+                    .icon_data
+                    .clone()
+                    .filter(|data| data.len() == 32 * 32 * 4)
+                    .unwrap_or_else(|| {
+                        easyhdr::utils::generate_letter_tile(&candidate.display_name)
+                    });
+                let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(
+                    &icon_data, 32, // width
+                    32, // height
+                );
+
+                let (app_type, details) = match &candidate.kind {
+                    CandidateKind::Win32 { exe_path } => {
+                        ("win32", exe_path.to_string_lossy().to_string())
+                    }
+                    CandidateKind::Uwp {
+                        package_family_name,
+                        ..
+                    } => ("uwp", package_family_name.clone()),
+                };
+                crate::RunningAppListItem {
+                    display_name: slint::SharedString::from(candidate.display_name.as_str()),
+                    details: slint::SharedString::from(details),
+                    app_type: slint::SharedString::from(app_type),
+                    icon: slint::Image::from_rgba8(buffer),
+                    selected: false,
+                    hidden: false,
+                }
+            })
+            .collect();
+
+        *candidates.lock() = listed;
+        window.set_running_app_list(slint::ModelRc::new(slint::VecModel::from(items)));
+        window.set_running_app_loading(false);
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn show_running_app_picker(
+        _controller: &Arc<Mutex<AppController>>,
+        _window: &slint::Weak<MainWindow>,
+        _candidates: &PickerCandidates,
+    ) {
+        Self::show_error_dialog("Adding running applications is only supported on Windows");
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn show_start_menu_picker(
+        _controller: &Arc<Mutex<AppController>>,
+        _window: &slint::Weak<MainWindow>,
+        _candidates: &PickerCandidates,
+    ) {
+        Self::show_error_dialog("Adding Start Menu applications is only supported on Windows");
+    }
+
+    /// Handle running app picker "Add Selected" button click
+    ///
+    /// Adds desktop applications by executable path and UWP applications by package,
+    /// matching multi-app packages per application like the UWP picker.
+    #[cfg(windows)]
+    fn running_app_add_selected(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+        candidates: &PickerCandidates,
+    ) {
+        use easyhdr::config::models::UwpApp;
+        use easyhdr::monitor::CandidateKind;
+        use tracing::{info, warn};
+
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        let app_list = window.get_running_app_list();
+        let candidates = std::mem::take(&mut *candidates.lock());
+        let selected: Vec<_> = candidates
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| app_list.row_data(*index).is_some_and(|item| item.selected))
+            .map(|(_, candidate)| candidate)
+            .collect();
+        window.set_running_app_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+
+        if selected.is_empty() {
+            info!("No running applications selected");
             return;
         }
 
-        if had_previous {
-            info!(
-                "HDR state changed to: {}",
-                if state.hdr_enabled {
-                    "enabled"
-                } else {
-                    "disabled"
+        info!("Adding {} running application(s)", selected.len());
+
+        let mut success_count = 0;
+        let mut error_messages = Vec::new();
+
+        for candidate in selected {
+            let result = match candidate.kind {
+                CandidateKind::Win32 { exe_path } => {
+                    Win32App::from_exe_path(exe_path).map(|mut win32_app| {
+                        // Keep the name shown in the picker, such as a Start Menu shortcut's
+                        win32_app.display_name = candidate.display_name.clone();
+                        MonitoredApp::Win32(win32_app)
+                    })
                 }
-            );
-        } else {
-            debug!(
-                "Initial HDR state applied: {}",
-                if state.hdr_enabled {
-                    "enabled"
-                } else {
-                    "disabled"
+                CandidateKind::Uwp {
+                    package_family_name,
+                    app_id,
+                    multi_app_package,
+                } => {
+                    let mut uwp_app = UwpApp::from_package_info(
+                        candidate.display_name.clone(),
+                        package_family_name,
+                        app_id,
+                        None,
+                    );
+                    // Multi-app packages are matched per application so each entry is independent
+                    uwp_app.match_app_id = multi_app_package;
+                    uwp_app.icon_data = candidate.icon_data;
+                    Ok(MonitoredApp::Uwp(uwp_app))
                 }
-            );
+            }
+            .and_then(|mut app| {
+                if (app.ensure_fallback_icon() || matches!(app, MonitoredApp::Uwp(_)))
+                    && let Some(icon_data) = app.icon_data()
+                {
+                    easyhdr::utils::IconCache::cache_icon_gracefully(
+                        *app.id(),
+                        icon_data,
+                        app.display_name(),
+                    );
+                }
+
+                controller.lock().add_application(app)
+            });
+
+            match result {
+                Ok(()) => {
+                    info!("Added running application: {}", candidate.display_name);
+                    success_count += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to add running application {}: {}",
+                        candidate.display_name, e
+                    );
+                    error_messages.push(format!("{}: {}", candidate.display_name, e));
+                }
+            }
         }
 
-        let show_notifications = {
+        if !error_messages.is_empty() {
+            let summary = if success_count > 0 {
+                format!(
+                    "Added {} application(s) successfully.\n\nFailed to add {} application(s):\n{}",
+                    success_count,
+                    error_messages.len(),
+                    error_messages.join("\n")
+                )
+            } else {
+                format!(
+                    "Failed to add all {} application(s):\n{}",
+                    error_messages.len(),
+                    error_messages.join("\n")
+                )
+            };
+
+            Self::show_error_dialog(&summary);
+        }
+
+        if success_count > 0 {
+            info!("Triggering manual GUI update after adding running applications");
+            Self::update_app_list_ui(controller, &window.as_weak());
+        }
+    }
+
+    /// Handle running app picker "Cancel" button click
+    #[cfg(windows)]
+    fn running_app_cancel(window: &slint::Weak<MainWindow>, candidates: &PickerCandidates) {
+        use tracing::info;
+
+        info!("Running app picker: Cancelled");
+
+        candidates.lock().clear();
+        if let Some(window) = window.upgrade() {
+            // A listing still running is discarded when it finishes
+            window.set_running_app_loading(false);
+            window.set_running_app_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+        }
+    }
+
+    /// Handle running app picker selection toggle for a single application
+    #[cfg(windows)]
+    fn running_app_toggle_selection(window: &slint::Weak<MainWindow>, index: i32, selected: bool) {
+        let Some(window) = window.upgrade() else {
+            return;
+        };
+
+        let Ok(index_usize) = usize::try_from(index) else {
+            return;
+        };
+
+        let app_list = window.get_running_app_list();
+        if let Some(mut item) = app_list.row_data(index_usize) {
+            item.selected = selected;
+            app_list.set_row_data(index_usize, item);
+        }
+    }
+
+    /// Handle running app picker search text changes
+    ///
+    /// Rows whose name and path or package do not contain the text are collapsed; their
+    /// selection is kept.
+    #[cfg(windows)]
+    fn running_app_search(window: &slint::Weak<MainWindow>, text: &str) {
+        use easyhdr::utils::unicode::fold_case;
+
+        let Some(window) = window.upgrade() else {
+            return;
+        };
+
+        let query = fold_case(text.trim());
+        let app_list = window.get_running_app_list();
+        for i in 0..app_list.row_count() {
+            if let Some(mut item) = app_list.row_data(i) {
+                let hidden = !query.is_empty()
+                    && !fold_case(&item.display_name).contains(&query)
+                    && !fold_case(&item.details).contains(&query);
+                if item.hidden != hidden {
+                    item.hidden = hidden;
+                    app_list.set_row_data(i, item);
+                }
+            }
+        }
+    }
+
+    /// Show the game library import dialog
+    ///
+    /// Scans Steam, Epic Games and GOG libraries for installed games and lists them
+    /// with checkboxes. Games whose executable is already monitored are shown but
+    /// cannot be selected. The libraries are scanned and the icons extracted on a
+    /// background thread, so the window stays responsive while the dialog loads.
+    #[cfg(windows)]
+    fn show_game_import(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
+        use easyhdr::utils::canonical_path;
+        use tracing::{info, warn};
+
+        info!("Import games button clicked - scanning game libraries");
+
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        window.set_game_import_loading(true);
+        window.set_game_import_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+
+        // Executables that are already monitored (compared through junctions and case)
+        let monitored_exes: std::collections::HashSet<String> = {
             let controller_guard = controller.lock();
             let config = controller_guard.config.read();
-            config.preferences.show_tray_notifications
+            config
+                .monitored_apps
+                .iter()
+                .filter_map(|app| match app {
+                    MonitoredApp::Win32(win32_app) => {
+                        Some(canonical_path::path_key(&win32_app.exe_path))
+                    }
+                    MonitoredApp::Uwp(_) | MonitoredApp::Folder(_) => None,
+                })
+                .collect()
         };
 
-        match tray_icon.try_borrow_mut() {
-            Ok(mut tray_icon_mut) => {
-                tray_icon_mut.update_icon(state.hdr_enabled);
-                if had_previous && show_notifications {
-                    let message = if state.hdr_enabled {
-                        "HDR Enabled"
-                    } else {
-                        "HDR Disabled"
-                    };
-                    tray_icon_mut.show_notification(message);
-                }
+        let window_weak = window.as_weak();
+        let spawned = std::thread::Builder::new()
+            .name("game-import".to_string())
+            .spawn(move || {
+                let games: Vec<_> = easyhdr::integrations::enumerate_installed_games()
+                    .into_iter()
+                    .map(|game| {
+                        let icon_data = easyhdr::utils::extract_icon_from_exe(&game.exe_path)
+                            .ok()
+                            .filter(|data| data.len() == 32 * 32 * 4)
+                            .unwrap_or_else(|| easyhdr::utils::generate_letter_tile(&game.name));
+                        (game, icon_data)
+                    })
+                    .collect();
+                info!("Found {} installed game(s)", games.len());
+
+                let _ = window_weak.upgrade_in_event_loop(move |window| {
+                    let game_items: Vec<_> = games
+                        .into_iter()
+                        .map(|(game, icon_data)| {
+                            let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(
+                                &icon_data, 32, // width
+                                32, // height
+                            );
 
-                // Show startup warning notification (no HDR displays detected)
-                if state.show_no_hdr_warning {
-                    info!("Showing no HDR displays warning notification");
-                    tray_icon_mut.show_notification(
-                        "No HDR-capable displays detected. HDR toggling will not work until an HDR display is connected."
-                    );
-                }
+                            let already_added =
+                                monitored_exes.contains(&canonical_path::path_key(&game.exe_path));
+                            let exe_path = game.exe_path.to_string_lossy().to_string();
+                            crate::LibraryGameListItem {
+                                already_added,
+                                display_name: slint::SharedString::from(game.name),
+                                exe_path: slint::SharedString::from(exe_path),
+                                library: slint::SharedString::from(game.library.name()),
+                                icon: slint::Image::from_rgba8(buffer),
+                                selected: false,
+                            }
+                        })
+                        .collect();
 
-                // Show HDR displays available notification
-                if state.show_hdr_available_notification {
-                    info!("Showing HDR displays available notification");
-                    tray_icon_mut
-                        .show_notification("HDR displays detected! HDR toggling is now available.");
+                    window.set_game_import_list(slint::ModelRc::new(slint::VecModel::from(
+                        game_items,
+                    )));
+                    window.set_game_import_loading(false);
+                });
+            });
+
+        if let Err(e) = spawned {
+            warn!("Failed to spawn game library scan: {}", e);
+            window.set_game_import_loading(false);
+        }
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn show_game_import(
+        _controller: &Arc<Mutex<AppController>>,
+        _window: &slint::Weak<MainWindow>,
+    ) {
+        Self::show_error_dialog("Game library import is only supported on Windows");
+    }
+
+    /// Handle game import "Import Selected" button click
+    ///
+    /// Adds every selected game as a Win32 application marked as imported. The
+    /// launcher's game title is used as display name instead of the executable's
+    /// version resource, which is often generic (e.g. "Unreal Engine").
+    #[cfg(windows)]
+    fn game_import_add_selected(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+    ) {
+        use easyhdr::config::models::AppSource;
+        use tracing::{info, warn};
+
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        let game_list = window.get_game_import_list();
+        let selected_games: Vec<_> = game_list
+            .iter()
+            .filter(|game| game.selected && !game.already_added)
+            .collect();
+
+        if selected_games.is_empty() {
+            info!("No games selected for import");
+            return;
+        }
+
+        info!("Importing {} selected game(s)", selected_games.len());
+
+        let mut success_count = 0;
+        let mut error_messages = Vec::new();
+
+        for game in selected_games {
+            let result =
+                Win32App::from_exe_path(game.exe_path.as_str()).and_then(|mut win32_app| {
+                    win32_app.display_name = game.display_name.to_string();
+                    win32_app.source = AppSource::Imported;
+
+                    let mut app = MonitoredApp::Win32(win32_app);
+                    if app.ensure_fallback_icon()
+                        && let Some(icon_data) = app.icon_data()
+                    {
+                        easyhdr::utils::IconCache::cache_icon_gracefully(
+                            *app.id(),
+                            icon_data,
+                            app.display_name(),
+                        );
+                    }
+
+                    controller.lock().add_application(app)
+                });
+
+            match result {
+                Ok(()) => {
+                    info!("Imported {} game: {}", game.library, game.display_name);
+                    success_count += 1;
+                }
+                Err(e) => {
+                    warn!("Failed to import game {}: {}", game.display_name, e);
+                    error_messages.push(format!("{}: {}", game.display_name, e));
                 }
-            }
-            Err(_) => {
-                warn!("Tray icon borrow failed, skipping tray update");
             }
         }
+
+        window.set_game_import_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+
+        if !error_messages.is_empty() {
+            let summary = if success_count > 0 {
+                format!(
+                    "Imported {} game(s) successfully.\n\nFailed to import {} game(s):\n{}",
+                    success_count,
+                    error_messages.len(),
+                    error_messages.join("\n")
+                )
+            } else {
+                format!(
+                    "Failed to import all {} game(s):\n{}",
+                    error_messages.len(),
+                    error_messages.join("\n")
+                )
+            };
+
+            Self::show_error_dialog(&summary);
+        }
+
+        if success_count > 0 {
+            info!("Triggering manual GUI update after game import");
+            Self::update_app_list_ui(controller, &window.as_weak());
+        }
+    }
+
+    /// Handle game import "Cancel" button click
+    #[cfg(windows)]
+    fn game_import_cancel(window: &slint::Weak<MainWindow>) {
+        use tracing::info;
+
+        info!("Game import: Cancelled");
+
+        if let Some(window) = window.upgrade() {
+            window.set_game_import_list(slint::ModelRc::new(slint::VecModel::from(Vec::new())));
+        }
+    }
+
+    /// Handle game import selection toggle for a single game
+    #[cfg(windows)]
+    fn game_import_toggle_selection(window: &slint::Weak<MainWindow>, index: i32, selected: bool) {
+        let Some(window) = window.upgrade() else {
+            return;
+        };
+
+        let Ok(index_usize) = usize::try_from(index) else {
+            return;
+        };
+
+        let game_list = window.get_game_import_list();
+        if let Some(mut game) = game_list.row_data(index_usize)
+            && !game.already_added
+        {
+            game.selected = selected;
+            game_list.set_row_data(index_usize, game);
+        }
+    }
+
+    /// Handle game import "Select All" / "Deselect All" button clicks
+    ///
+    /// Games that are already monitored are never selected.
+    #[cfg(windows)]
+    fn game_import_set_all_selected(window: &slint::Weak<MainWindow>, selected: bool) {
+        let Some(window) = window.upgrade() else {
+            return;
+        };
+
+        let game_list = window.get_game_import_list();
+        for i in 0..game_list.row_count() {
+            if let Some(mut game) = game_list.row_data(i)
+                && !game.already_added
+            {
+                game.selected = selected;
+                game_list.set_row_data(i, game);
+            }
+        }
+    }
+
+    /// High-DPI icon of an app, `None` while it is loading or when the cache has none
+    ///
+    /// An icon not asked for yet is added to `requests` and loaded by
+    /// [`Self::load_high_dpi_icons`].
+    fn high_dpi_icon(
+        app: &MonitoredApp,
+        size: u32,
+        requests: &mut Vec<(uuid::Uuid, Option<std::path::PathBuf>)>,
+    ) -> Option<slint::Image> {
+        use easyhdr::utils::volume::volume_kind;
+
+        HIGH_DPI_ICONS.with_borrow_mut(|icons| {
+            icons
+                .entry((*app.id(), size))
+                .or_insert_with(|| {
+                    // Executables on network or removable volumes are not read, so an
+                    // offline share never delays the other icons
+                    let source = match app {
+                        MonitoredApp::Win32(win32_app)
+                            if !volume_kind(&win32_app.exe_path).may_be_offline() =>
+                        {
+                            Some(win32_app.exe_path.clone())
+                        }
+                        _ => None,
+                    };
+                    requests.push((*app.id(), source));
+                    None
+                })
+                .clone()
+        })
+    }
+
+    /// Load high-DPI icons on a background thread and show them in the application list
+    ///
+    /// Win32 icons are extracted at `size` pixels the first time, other apps get their
+    /// largest cached icon resized. Rows keep their 32x32 icon until then.
+    fn load_high_dpi_icons(
+        window: &MainWindow,
+        size: u32,
+        requests: Vec<(uuid::Uuid, Option<std::path::PathBuf>)>,
+    ) {
+        use tracing::{debug, warn};
+
+        if requests.is_empty() {
+            return;
+        }
+
+        let window_weak = window.as_weak();
+        let spawned = std::thread::Builder::new()
+            .name("high-dpi-icon-loader".to_string())
+            .spawn(move || {
+                let Ok(cache) = IconCache::new(IconCache::default_cache_dir()) else {
+                    return;
+                };
+                let loaded: Vec<_> = requests
+                    .into_iter()
+                    .filter_map(
+                        |(id, source)| match cache.get_icon(id, size, source.as_deref()) {
+                            Ok(icon_data) => Some((id, icon_data?)),
+                            Err(e) => {
+                                debug!("No {}px icon for app {}: {}", size, id, e);
+                                None
+                            }
+                        },
+                    )
+                    .collect();
+                if loaded.is_empty() {
+                    return;
+                }
+
+                let _ = window_weak.upgrade_in_event_loop(move |window| {
+                    // Icons are released while the window is hidden
+                    if !window.window().is_visible() {
+                        return;
+                    }
+
+                    let app_list = window.get_app_list();
+                    let shown_size = IconCache::icon_size_for_scale(window.window().scale_factor());
+                    for (id, icon_data) in loaded {
+                        let icon = slint::Image::from_rgba8(
+                            SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(
+                                &icon_data, size, size,
+                            ),
+                        );
+                        HIGH_DPI_ICONS.with_borrow_mut(|icons| {
+                            icons.insert((id, size), Some(icon.clone()));
+                        });
+                        if shown_size != size {
+                            continue;
+                        }
+
+                        let id = id.to_string();
+                        for index in 0..app_list.row_count() {
+                            if let Some(mut item) = app_list.row_data(index)
+                                && item.id == id.as_str()
+                            {
+                                item.icon = icon.clone();
+                                app_list.set_row_data(index, item);
+                            }
+                        }
+                    }
+                });
+            });
+        if let Err(e) = spawned {
+            warn!("Failed to spawn high-DPI icon loader: {}", e);
+        }
+    }
+
+    /// Collect application list items for UI consumption
+    ///
+    /// Converts the monitored applications from the controller configuration
+    /// into `AppListItem` instances that can be displayed by the Slint UI.
+    /// The returned list is sorted alphabetically by display name (case-insensitive).
+    /// Icons are shown at the size for the window's scale factor (see
+    /// [`IconCache::icon_size_for_scale`]) once loaded in the background.
+    fn collect_app_list_items(
+        controller: &Arc<Mutex<AppController>>,
+        window: &MainWindow,
+    ) -> Vec<crate::AppListItem> {
+        let icon_size = IconCache::icon_size_for_scale(window.window().scale_factor());
+        let mut high_dpi_requests = Vec::new();
+        let controller_guard = controller.lock();
+        // Phase 3.1: Use read lock for concurrent access
+        let config = controller_guard.config.read();
+        let now = std::time::SystemTime::now();
+
+        let mut items: Vec<_> = config
+            .monitored_apps
+            .iter()
+            .map(|app| {
+                // Get icon data using helper method
+                let icon_data_ref = app.icon_data();
+
+                // Validate icon data size (32x32 RGBA = 4096 bytes); missing or invalid
+                // icons are rendered as a letter tile until the icon is regenerated
+                let icon_data: std::borrow::Cow<'_, [u8]> = match icon_data_ref {
+                    Some(icon_data) if icon_data.len() == 32 * 32 * 4 => icon_data.into(),
+                    Some(icon_data) => {
+                        tracing::warn!(
+                            "Icon data for {} has unexpected size: {} bytes (expected 4096)",
+                            app.display_name(),
+                            icon_data.len()
+                        );
+                        easyhdr::utils::generate_letter_tile(app.display_name()).into()
+                    }
+                    None => easyhdr::utils::generate_letter_tile(app.display_name()).into(),
+                };
+                let high_dpi_icon = (icon_size != ICON_SIZE && icon_data_ref.is_some())
+                    .then(|| Self::high_dpi_icon(app, icon_size, &mut high_dpi_requests))
+                    .flatten();
+                let icon = high_dpi_icon.unwrap_or_else(|| {
+                    let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(
+                        &icon_data, 32, // width
+                        32, // height
+                    );
+                    slint::Image::from_rgba8(buffer)
+                });
+
+                // Get exe_path, app_type and Auto HDR mode based on app variant
+                let (exe_path, app_type, auto_hdr) = match app {
+                    MonitoredApp::Win32(win32_app) => (
+                        win32_app.exe_path.to_string_lossy().to_string(),
+                        "win32",
+                        win32_app.auto_hdr,
+                    ),
+                    MonitoredApp::Uwp(uwp_app) => (
+                        format!("Package: {}", uwp_app.package_family_name),
+                        "uwp",
+                        AutoHdrMode::Default,
+                    ),
+                    MonitoredApp::Folder(folder_app) => (
+                        format!("Folder: {}", folder_app.path.display()),
+                        "folder",
+                        AutoHdrMode::Default,
+                    ),
+                };
+
+                crate::AppListItem {
+                    id: app.id().to_string().into(),
+                    display_name: app.display_name().to_string().into(),
+                    exe_path: exe_path.into(),
+                    enabled: app.is_enabled(),
+                    icon,
+                    app_type: app_type.into(),
+                    source: app.source().as_str().into(),
+                    auto_hdr: auto_hdr.as_str().into(),
+                    display_mode: app
+                        .display_mode()
+                        .map(|display_mode| display_mode.to_string())
+                        .unwrap_or_default()
+                        .into(),
+                    color_mode: app.color_mode().unwrap_or(ColorMode::Hdr).as_str().into(),
+                    capture_tone_mapping: match app.capture_tone_mapping() {
+                        Some(true) => "on",
+                        Some(false) => "off",
+                        None => "default",
+                    }
+                    .into(),
+                    stats: controller_guard
+                        .get_app_stats(*app.id())
+                        .summary(now)
+                        .into(),
+                    offline: controller_guard.is_app_offline(app),
+                    snoozed: controller_guard.is_app_snoozed(*app.id()),
+                    warning: controller_guard
+                        .elevated_app_warning(app)
+                        .unwrap_or_default()
+                        .into(),
+                    accessible_description: slint::SharedString::default(),
+                }
+            })
+            .collect();
+
+        drop(config);
+        drop(controller_guard);
+        Self::load_high_dpi_icons(window, icon_size, high_dpi_requests);
+
+        for item in &mut items {
+            item.accessible_description = accessibility::row_description(item).into();
+        }
+
+        // Sort alphabetically by display name (case-insensitive)
+        items.sort_by(|a, b| {
+            a.display_name
+                .to_lowercase()
+                .cmp(&b.display_name.to_lowercase())
+        });
+
+        items
+    }
+
+    /// Apply a new `AppState` to the GUI on the main thread
+    ///
+    /// Updates the status indicator, application list, tray icon, and optional
+    /// notifications to reflect the latest HDR state.
+    fn apply_ui_state(
+        window_weak: &slint::Weak<MainWindow>,
+        controller: &Arc<Mutex<AppController>>,
+        tray_icon: &Rc<RefCell<TrayIcon>>,
+        window_visibility: &Cell<bool>,
+        previous_hdr_state: &Cell<Option<bool>>,
+        previous_apps: &RefCell<Option<Vec<AppSnapshot>>>,
+        state: &AppState,
+    ) {
+        use tracing::{debug, info, warn};
+
+        let window_opt = window_weak.upgrade();
+        if let Some(window) = window_opt {
+            let window_visible = window.window().is_visible();
+
+            // Detect when window becomes visible again (e.g., restored from tray)
+            // and reload GUI resources that were released during minimize
+            if window_visible && !window_visibility.get() {
+                info!("Window shown, reloading GUI resources");
+                Self::reload_gui_resources(controller);
+            }
+
+            window_visibility.set(window_visible);
+
+            window.set_hdr_enabled(state.hdr_enabled);
+            debug!("Updated HDR enabled state to: {}", state.hdr_enabled);
+            window.set_hdr_error_text(Self::hdr_error_text(&state).into());
+            window.set_hdr_conflict_text(state.hdr_conflict.clone().unwrap_or_default().into());
+            window.set_manual_session_text(state.manual_session.clone().unwrap_or_default().into());
+            Self::update_onboarding(&window, state.onboarding.as_ref());
+
+            let app_list = Self::collect_app_list_items(controller, &window);
+            let snapshots: Vec<_> = app_list.iter().map(AppSnapshot::of).collect();
+            let app_list_model = Rc::new(slint::VecModel::from(app_list));
+            window.set_app_list(app_list_model.into());
+            debug!("Updated application list in UI");
+
+            // Profiles come from the app list, so a removed one is no longer selected
+            let display_modes: Vec<slint::SharedString> = controller
+                .lock()
+                .manual_session_display_modes()
+                .iter()
+                .map(|display_mode| display_mode.to_string().into())
+                .collect();
+            if !display_modes.contains(&window.get_manual_session_display_mode()) {
+                window.set_manual_session_display_mode(slint::SharedString::new());
+            }
+            window.set_manual_session_display_modes(slint::ModelRc::new(slint::VecModel::from(
+                display_modes,
+            )));
+
+            // The first list is only remembered, later ones announce what changed
+            if let Some(previous) = previous_apps.replace(Some(snapshots.clone())) {
+                let changes = accessibility::changes(&previous, &snapshots);
+                if !changes.is_empty() {
+                    accessibility::announce(&window, &changes.join(". "));
+                }
+            }
+
+            Self::update_scan_diagnostics(&window, controller);
+            window.set_settings_metrics_text(
+                easyhdr::utils::metrics::get_metrics()
+                    .snapshot()
+                    .summary()
+                    .into(),
+            );
+            // Only a toggle adds to the history, which is read under the controller lock
+            if previous_hdr_state.get() != Some(state.hdr_enabled)
+                || state.show_hdr_toggle_error_notification
+            {
+                Self::update_hdr_history(&window, controller);
+            }
+            Self::update_recent_errors(&window);
+        } else {
+            warn!("Window no longer exists, skipping UI update");
+        }
+
+        match tray_icon.try_borrow_mut() {
+            Ok(mut tray_icon_mut) => {
+                tray_icon_mut.update_log_level(state.log_level);
+                tray_icon_mut.update_displays(&state.display_hdr);
+                // Also reflects per-display changes that leave the overall state as it is
+                tray_icon_mut.update_icon(TrayHdrState::from_displays(
+                    state.hdr_enabled,
+                    &state.display_hdr,
+                ));
+            }
+            Err(_) => warn!("Tray icon borrow failed, skipping tray menu update"),
+        }
+
+        // Show weekly HDR usage summary (opt-in via preferences), independent of HDR changes
+        if let Some(summary) = &state.weekly_summary {
+            info!("Showing weekly HDR summary notification");
+            match tray_icon.try_borrow() {
+                Ok(tray_icon_ref) => tray_icon_ref.show_notification(summary),
+                Err(_) => warn!("Tray icon borrow failed, skipping weekly summary"),
+            }
+        }
+
+        // Warn before the session limit turns HDR off, offering to extend the session
+        if let Some(warning) = &state.session_limit_warning {
+            info!("Showing HDR session limit notification");
+            match tray_icon.try_borrow() {
+                Ok(tray_icon_ref) => {
+                    let controller = Arc::clone(controller);
+                    tray_icon_ref.show_session_limit_notification(warning, move || {
+                        controller.lock().extend_hdr_session();
+                    });
+                }
+                Err(_) => warn!("Tray icon borrow failed, skipping session limit warning"),
+            }
+        }
+
+        // Another program keeps switching HDR, offer to resume automatic management
+        if state.show_hdr_conflict_notification
+            && let Some(conflict) = &state.hdr_conflict
+        {
+            info!("Showing HDR conflict notification");
+            match tray_icon.try_borrow() {
+                Ok(tray_icon_ref) => {
+                    let controller = Arc::clone(controller);
+                    tray_icon_ref.show_hdr_conflict_notification(conflict, move || {
+                        Self::resume_automatic_hdr(&controller);
+                    });
+                }
+                Err(_) => warn!("Tray icon borrow failed, skipping HDR conflict notification"),
+            }
+        }
+
+        // Remind to run OLED panel maintenance, offering to start it over DDC/CI
+        if let Some(reminder) = &state.oled_care_reminder {
+            info!("Showing OLED care notification");
+            match tray_icon.try_borrow() {
+                Ok(tray_icon_ref) => {
+                    let controller = Arc::clone(controller);
+                    tray_icon_ref.show_oled_care_notification(
+                        &reminder.message,
+                        reminder.can_start_pixel_refresh,
+                        move || {
+                            let result = controller.lock().start_pixel_refresh();
+                            if let Err(e) = result {
+                                Self::show_error_dialog_from_error(&e);
+                            }
+                        },
+                    );
+                }
+                Err(_) => warn!("Tray icon borrow failed, skipping OLED care reminder"),
+            }
+        }
+
+        // A failed toggle usually leaves the HDR state unchanged, so report it here
+        if state.show_hdr_toggle_error_notification
+            && let Some(error) = &state.hdr_toggle_error
+        {
+            warn!("Showing HDR toggle failure notification: {}", error);
+            match tray_icon.try_borrow() {
+                Ok(tray_icon_ref) => tray_icon_ref.show_notification(error),
+                Err(_) => warn!("Tray icon borrow failed, skipping HDR toggle failure"),
+            }
+        }
+
+        let previous_state = previous_hdr_state.replace(Some(state.hdr_enabled));
+        let state_changed = previous_state != Some(state.hdr_enabled);
+        let had_previous = previous_state.is_some();
+
+        if !state_changed {
+            return;
+        }
+
+        if had_previous {
+            info!(
+                "HDR state changed to: {}",
+                if state.hdr_enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+        } else {
+            debug!(
+                "Initial HDR state applied: {}",
+                if state.hdr_enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+
+        let show_notifications = {
+            let controller_guard = controller.lock();
+            let config = controller_guard.config.read();
+            config.preferences.show_tray_notifications
+        };
+
+        match tray_icon.try_borrow() {
+            Ok(tray_icon_ref) => {
+                if had_previous && show_notifications {
+                    let message = tr(match (state.dry_run, state.hdr_enabled) {
+                        (false, true) => "hdr-enabled",
+                        (false, false) => "hdr-disabled",
+                        (true, true) => "dry-run-enabled",
+                        (true, false) => "dry-run-disabled",
+                    });
+
+                    // Automatic toggles can be overridden from the notification itself
+                    if state.hdr_toggled_automatically {
+                        let on_snooze = state.hdr_toggle_app.map(|app_id| {
+                            let controller = Arc::clone(controller);
+                            move || controller.lock().snooze_app(app_id)
+                        });
+                        let controller = Arc::clone(controller);
+                        tray_icon_ref.show_hdr_toggle_notification(
+                            &message,
+                            move |hdr_override| {
+                                if let Err(e) = controller.lock().apply_hdr_override(hdr_override) {
+                                    tracing::error!("Failed to apply HDR override: {}", e);
+                                }
+                            },
+                            on_snooze,
+                        );
+                    } else {
+                        tray_icon_ref.show_notification(&message);
+                    }
+                }
+
+                // Show startup warning notification (no HDR displays detected)
+                if state.show_no_hdr_warning {
+                    info!("Showing no HDR displays warning notification");
+                    tray_icon_ref.show_notification(&tr("no-hdr-displays"));
+                }
+
+                // Show HDR displays available notification
+                if state.show_hdr_available_notification {
+                    info!("Showing HDR displays available notification");
+                    tray_icon_ref.show_notification(&tr("hdr-displays-available"));
+                }
+            }
+            Err(_) => {
+                warn!("Tray icon borrow failed, skipping tray update");
+            }
+        }
+    }
+
+    /// Update the application list in the UI
+    ///
+    /// This helper method reads the current application list from the controller
+    /// and updates the GUI display. Used to manually refresh the UI after
+    /// operations that might be missed by the state sync thread (like after
+    /// modal dialogs close).
+    #[cfg(windows)]
+    fn update_app_list_ui(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+    ) {
+        use tracing::{debug, warn};
+
+        // Upgrade weak reference to strong reference
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window reference in update_app_list_ui");
+            return;
+        };
+
+        // Read the application list from config
+        let app_list = Self::collect_app_list_items(controller, &window);
+
+        // Update the app list in the UI
+        let count = app_list.len();
+        let app_list_model = std::rc::Rc::new(slint::VecModel::from(app_list));
+        window.set_app_list(app_list_model.into());
+        debug!("Manually updated application list in UI ({} apps)", count);
+    }
+
+    /// Remove application at the specified index
+    ///
+    /// Gets the application UUID from the config at the specified index,
+    /// then calls `controller.remove_application()` to remove it.
+    #[cfg(windows)]
+    fn remove_app_at_index(controller: &Arc<Mutex<AppController>>, index: i32) {
+        use tracing::{info, warn};
+
+        info!("Removing application at index: {}", index);
+
+        // Get the app UUID at the specified index
+        let mut controller_guard = controller.lock();
+
+        // Access the config to get the app UUID
+        let app_id = {
+            let config = controller_guard.config.read();
+            // Validate index is non-negative and within bounds
+            // We check index < 0 first, then cast is safe
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "index is validated to be non-negative before casting"
+            )]
+            if index < 0 || (index as usize) >= config.monitored_apps.len() {
+                warn!("Invalid index: {}", index);
+                drop(config);
+                drop(controller_guard);
+                Self::show_error_dialog("Invalid application index");
+                return;
+            }
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "index is validated to be non-negative"
+            )]
+            let idx = index as usize;
+            *config.monitored_apps[idx].id()
+        };
+
+        // Remove the application
+        match controller_guard.remove_application(app_id) {
+            Ok(()) => {
+                info!("Application removed successfully");
+            }
+            Err(e) => {
+                warn!("Failed to remove application: {}", e);
+                drop(controller_guard);
+                Self::show_error_dialog_from_error(&e);
+            }
+        }
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn remove_app_at_index(_controller: &Arc<Mutex<AppController>>, _index: i32) {
+        Self::show_error_dialog("Application management is only supported on Windows");
+    }
+
+    /// Toggle the enabled state of an application at the specified index
+    ///
+    /// Gets the application UUID from the config at the specified index,
+    /// then calls `controller.toggle_app_enabled()` to update the enabled state.
+    #[cfg(windows)]
+    fn toggle_app_enabled(controller: &Arc<Mutex<AppController>>, index: i32, enabled: bool) {
+        use tracing::{info, warn};
+
+        info!(
+            "Toggling application at index {} to enabled={}",
+            index, enabled
+        );
+
+        // Get the app UUID at the specified index
+        let mut controller_guard = controller.lock();
+
+        // Access the config to get the app UUID
+        let app_id = {
+            let config = controller_guard.config.read();
+            // Validate index is non-negative and within bounds
+            // We check index < 0 first, then cast is safe
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "index is validated to be non-negative before casting"
+            )]
+            if index < 0 || (index as usize) >= config.monitored_apps.len() {
+                warn!("Invalid index: {}", index);
+                drop(config);
+                drop(controller_guard);
+                Self::show_error_dialog("Invalid application index");
+                return;
+            }
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "index is validated to be non-negative"
+            )]
+            let idx = index as usize;
+            *config.monitored_apps[idx].id()
+        };
+
+        // Toggle the enabled state
+        match controller_guard.toggle_app_enabled(app_id, enabled) {
+            Ok(()) => {
+                info!("Application enabled state updated successfully");
+            }
+            Err(e) => {
+                warn!("Failed to toggle application enabled state: {}", e);
+                drop(controller_guard);
+                Self::show_error_dialog_from_error(&e);
+            }
+        }
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn toggle_app_enabled(_controller: &Arc<Mutex<AppController>>, _index: i32, _enabled: bool) {
+        Self::show_error_dialog("Application management is only supported on Windows");
+    }
+
+    /// Start a manual HDR session lasting `minutes`, or until stopped when `minutes` is 0
+    ///
+    /// `display_mode` is one of the labels of
+    /// [`AppController::manual_session_display_modes`], empty to keep the current mode.
+    fn start_manual_session(
+        controller: &Arc<Mutex<AppController>>,
+        minutes: i32,
+        display_mode: &str,
+    ) {
+        use tracing::warn;
+
+        let duration = u64::try_from(minutes)
+            .ok()
+            .filter(|&minutes| minutes > 0)
+            .map(|minutes| std::time::Duration::from_secs(minutes * 60));
+
+        let mut controller_guard = controller.lock();
+        let display_mode = controller_guard
+            .manual_session_display_modes()
+            .into_iter()
+            .find(|mode| mode.to_string() == display_mode);
+        if let Err(e) = controller_guard.start_manual_session(duration, display_mode) {
+            warn!("Failed to start manual HDR session: {}", e);
+            drop(controller_guard);
+            Self::show_error_dialog_from_error(&e);
+        }
+    }
+
+    /// End the running manual HDR session
+    fn stop_manual_session(controller: &Arc<Mutex<AppController>>) {
+        use tracing::warn;
+
+        let mut controller_guard = controller.lock();
+        if let Err(e) = controller_guard.stop_manual_session() {
+            warn!("Failed to end manual HDR session: {}", e);
+            drop(controller_guard);
+            Self::show_error_dialog_from_error(&e);
+        }
+    }
+
+    /// Move first-run setup on to its next step
+    ///
+    /// Leaving the auto-start step saves the choice to the preferences and registers or
+    /// removes the auto-start entry, like saving the settings dialog.
+    fn onboarding_next(
+        controller: &Arc<Mutex<AppController>>,
+        window_weak: &slint::Weak<MainWindow>,
+    ) {
+        use easyhdr::controller::OnboardingStep;
+        use easyhdr::utils::AutoStartManager;
+        use tracing::{info, warn};
+
+        let mut controller_guard = controller.lock();
+        if controller_guard.onboarding_step() == Some(OnboardingStep::AutoStart)
+            && let Some(window) = window_weak.upgrade()
+        {
+            let auto_start = window.get_onboarding_auto_start();
+            window.set_settings_auto_start(auto_start);
+
+            let mut preferences = controller_guard.config.read().preferences.clone();
+            preferences.auto_start = auto_start;
+            info!(
+                "{} auto-start from first-run setup",
+                if auto_start { "Enabling" } else { "Disabling" }
+            );
+            if let Err(e) = controller_guard
+                .update_preferences(preferences.clone())
+                .and_then(|()| AutoStartManager::apply(&preferences))
+            {
+                warn!("Failed to update auto-start: {}", e);
+                drop(controller_guard);
+                Self::show_error_dialog(&format!("Failed to update auto-start:\n\n{e}"));
+                controller_guard = controller.lock();
+            }
+        }
+        controller_guard.advance_onboarding();
+    }
+
+    /// Turn HDR on for the first-run HDR test
+    fn start_hdr_test(controller: &Arc<Mutex<AppController>>) {
+        use tracing::warn;
+
+        let mut controller_guard = controller.lock();
+        if let Err(e) = controller_guard.start_hdr_test() {
+            warn!("Failed to start the HDR test: {}", e);
+            drop(controller_guard);
+            Self::show_error_dialog_from_error(&e);
+        }
+    }
+
+    /// End the first-run HDR test with the user's answer whether HDR looked right
+    fn end_hdr_test(controller: &Arc<Mutex<AppController>>, worked: bool) {
+        use tracing::warn;
+
+        let mut controller_guard = controller.lock();
+        if let Err(e) = controller_guard.end_hdr_test(Some(worked)) {
+            warn!("Failed to switch HDR back after the HDR test: {}", e);
+            drop(controller_guard);
+            Self::show_error_dialog_from_error(&e);
+        }
+    }
+
+    /// Cycle the Auto HDR override of the application at the specified index
+    ///
+    /// Steps through Default, On and Off, then calls `controller.set_auto_hdr_mode()`
+    /// to write the new mode to the Windows graphics settings and the config.
+    #[cfg(windows)]
+    fn cycle_auto_hdr(controller: &Arc<Mutex<AppController>>, index: i32) {
+        use tracing::{info, warn};
+
+        let mut controller_guard = controller.lock();
+
+        let (app_id, mode) = {
+            let config = controller_guard.config.read();
+            // Validate index is non-negative and within bounds
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "index is validated to be non-negative before casting"
+            )]
+            let app = (index >= 0)
+                .then(|| config.monitored_apps.get(index as usize))
+                .flatten();
+            match app {
+                Some(MonitoredApp::Win32(app)) => (app.id, app.auto_hdr.next()),
+                _ => {
+                    warn!("Invalid index for Auto HDR override: {}", index);
+                    return;
+                }
+            }
+        };
+
+        match controller_guard.set_auto_hdr_mode(app_id, mode) {
+            Ok(()) => {
+                info!("Auto HDR override updated successfully");
+            }
+            Err(e) => {
+                warn!("Failed to set Auto HDR override: {}", e);
+                drop(controller_guard);
+                Self::show_error_dialog_from_error(&e);
+            }
+        }
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn cycle_auto_hdr(_controller: &Arc<Mutex<AppController>>, _index: i32) {
+        Self::show_error_dialog("Auto HDR is only supported on Windows");
+    }
+
+    /// Switch the application at the specified index between HDR and wide color gamut
+    ///
+    /// Calls `controller.set_app_color_mode()`, which saves the binding to the config.
+    fn cycle_color_mode(controller: &Arc<Mutex<AppController>>, index: i32) {
+        use tracing::warn;
+
+        let mut controller_guard = controller.lock();
+
+        let (app_id, color_mode) = {
+            let config = controller_guard.config.read();
+            // Validate index is non-negative and within bounds
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "index is validated to be non-negative before casting"
+            )]
+            let app = (index >= 0)
+                .then(|| config.monitored_apps.get(index as usize))
+                .flatten();
+            let Some(app) = app else {
+                warn!("Invalid index for color mode: {}", index);
+                return;
+            };
+            let color_mode = match app.color_mode() {
+                Some(ColorMode::Wcg) => None,
+                _ => Some(ColorMode::Wcg),
+            };
+            (*app.id(), color_mode)
+        };
+
+        if let Err(e) = controller_guard.set_app_color_mode(app_id, color_mode) {
+            warn!("Failed to set color mode: {}", e);
+            drop(controller_guard);
+            Self::show_error_dialog_from_error(&e);
+        }
+    }
+
+    /// Resume automatic HDR management after it paused on conflicting HDR changes
+    fn resume_automatic_hdr(controller: &Arc<Mutex<AppController>>) {
+        use easyhdr::controller::PendingActionId;
+
+        controller
+            .lock()
+            .cancel_pending(PendingActionId::HdrConflictPause);
     }
 
-    /// Update the application list in the UI
+    /// Cancel the pending action at the specified index of the main window's list
     ///
-    /// This helper method reads the current application list from the controller
-    /// and updates the GUI display. Used to manually refresh the UI after
-    /// operations that might be missed by the state sync thread (like after
-    /// modal dialogs close).
-    #[cfg(windows)]
-    fn update_app_list_ui(
+    /// Does nothing if the action ran in the meantime; the next state update removes it.
+    fn cancel_pending_action(
         controller: &Arc<Mutex<AppController>>,
-        window: &slint::Weak<MainWindow>,
+        pending_actions: &RefCell<Vec<PendingAction>>,
+        index: i32,
     ) {
-        use tracing::{debug, warn};
+        use tracing::warn;
 
-        // Upgrade weak reference to strong reference
-        let Some(window) = window.upgrade() else {
-            warn!("Failed to upgrade window reference in update_app_list_ui");
+        let id = usize::try_from(index)
+            .ok()
+            .and_then(|index| pending_actions.borrow().get(index).map(|action| action.id));
+        let Some(id) = id else {
+            warn!("Invalid index for cancelling a pending action: {}", index);
             return;
         };
-
-        // Read the application list from config
-        let app_list = Self::collect_app_list_items(controller);
-
-        // Update the app list in the UI
-        let count = app_list.len();
-        let app_list_model = std::rc::Rc::new(slint::VecModel::from(app_list));
-        window.set_app_list(app_list_model.into());
-        debug!("Manually updated application list in UI ({} apps)", count);
+        controller.lock().cancel_pending(id);
     }
 
-    /// Remove application at the specified index
+    /// Show the pending actions with the time left until each runs
     ///
-    /// Gets the application UUID from the config at the specified index,
-    /// then calls `controller.remove_application()` to remove it.
-    #[cfg(windows)]
-    fn remove_app_at_index(controller: &Arc<Mutex<AppController>>, index: i32) {
-        use tracing::{info, warn};
+    /// Called on every tick of the UI pump; the list is only replaced when a countdown
+    /// changed, so the banner does not flicker.
+    fn update_pending_actions(window: &MainWindow, pending_actions: &[PendingAction]) {
+        let now = std::time::Instant::now();
+        let texts: Vec<slint::SharedString> = pending_actions
+            .iter()
+            .map(|action| action.countdown(now).into())
+            .collect();
+        if !window
+            .get_pending_actions()
+            .iter()
+            .eq(texts.iter().cloned())
+        {
+            window.set_pending_actions(slint::ModelRc::new(slint::VecModel::from(texts)));
+        }
+    }
 
-        info!("Removing application at index: {}", index);
+    /// End the snooze of the application at the specified index
+    ///
+    /// Cancels the snooze started from an HDR toggle notification, so automatic HDR
+    /// management watches the application again.
+    fn resume_app(controller: &Arc<Mutex<AppController>>, index: i32) {
+        use easyhdr::controller::PendingActionId;
+        use tracing::warn;
 
-        // Get the app UUID at the specified index
         let mut controller_guard = controller.lock();
 
-        // Access the config to get the app UUID
         let app_id = {
             let config = controller_guard.config.read();
-            // Validate index is non-negative and within bounds
-            // We check index < 0 first, then cast is safe
             #[expect(
                 clippy::cast_sign_loss,
                 reason = "index is validated to be non-negative before casting"
             )]
-            if index < 0 || (index as usize) >= config.monitored_apps.len() {
-                warn!("Invalid index: {}", index);
-                drop(config);
-                drop(controller_guard);
-                Self::show_error_dialog("Invalid application index");
+            let app = (index >= 0)
+                .then(|| config.monitored_apps.get(index as usize))
+                .flatten();
+            let Some(app) = app else {
+                warn!("Invalid index for resuming an application: {}", index);
                 return;
-            }
+            };
+            *app.id()
+        };
+
+        controller_guard.cancel_pending(PendingActionId::AppSnooze(app_id));
+    }
+
+    /// Cycle the HDR capture tone mapping of the application at the specified index
+    ///
+    /// Steps through Default, On and Off, then calls `controller.set_capture_tone_mapping()`,
+    /// which saves the setting to the config.
+    fn cycle_capture_tone_mapping(controller: &Arc<Mutex<AppController>>, index: i32) {
+        use tracing::warn;
+
+        let mut controller_guard = controller.lock();
+
+        let (app_id, capture_tone_mapping) = {
+            let config = controller_guard.config.read();
+            // Validate index is non-negative and within bounds
             #[expect(
                 clippy::cast_sign_loss,
-                reason = "index is validated to be non-negative"
+                reason = "index is validated to be non-negative before casting"
             )]
-            let idx = index as usize;
-            *config.monitored_apps[idx].id()
+            let app = (index >= 0)
+                .then(|| config.monitored_apps.get(index as usize))
+                .flatten();
+            let Some(app) = app else {
+                warn!("Invalid index for capture tone mapping: {}", index);
+                return;
+            };
+            let capture_tone_mapping = match app.capture_tone_mapping() {
+                None => Some(true),
+                Some(true) => Some(false),
+                Some(false) => None,
+            };
+            (*app.id(), capture_tone_mapping)
         };
 
-        // Remove the application
-        match controller_guard.remove_application(app_id) {
-            Ok(()) => {
-                info!("Application removed successfully");
-            }
-            Err(e) => {
-                warn!("Failed to remove application: {}", e);
-                drop(controller_guard);
-                Self::show_error_dialog_from_error(&e);
-            }
+        if let Err(e) = controller_guard.set_capture_tone_mapping(app_id, capture_tone_mapping) {
+            warn!("Failed to set capture tone mapping: {}", e);
+            drop(controller_guard);
+            Self::show_error_dialog_from_error(&e);
+        }
+    }
+
+    /// Fill the app settings dialog with the settings of the application at the specified
+    /// index
+    #[cfg(windows)]
+    fn open_app_settings(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+        index: i32,
+    ) {
+        use easyhdr::config::MatchRule;
+        use easyhdr::controller::AppSettings;
+        use tracing::warn;
+
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        let controller_guard = controller.lock();
+        let config = controller_guard.config.read();
+        #[expect(
+            clippy::cast_sign_loss,
+            reason = "index is validated to be non-negative before casting"
+        )]
+        let app = (index >= 0)
+            .then(|| config.monitored_apps.get(index as usize))
+            .flatten();
+        let Some(app) = app else {
+            warn!("Invalid index for app settings: {}", index);
+            return;
+        };
+
+        let settings = AppSettings::of(app);
+        let display_mode = settings.display_mode.unwrap_or_default();
+        window.set_app_settings_name(settings.display_name.into());
+        window.set_app_settings_color_mode(
+            settings
+                .color_mode
+                .unwrap_or(ColorMode::Hdr)
+                .as_str()
+                .into(),
+        );
+        window.set_app_settings_delay_text(
+            settings
+                .hdr_disable_debounce_ms
+                .map(|ms| ms.to_string())
+                .unwrap_or_default()
+                .into(),
+        );
+        window.set_app_settings_refresh_text(
+            display_mode
+                .refresh_rate_hz
+                .map(|hz| hz.to_string())
+                .unwrap_or_default()
+                .into(),
+        );
+        window.set_app_settings_resolution_text(
+            display_mode
+                .resolution
+                .map(|resolution| resolution.to_string())
+                .unwrap_or_default()
+                .into(),
+        );
+        window.set_app_settings_win32(matches!(app, MonitoredApp::Win32(_)));
+        let stats = controller_guard.get_app_stats(*app.id());
+        let stats_text = if stats.sessions == 0 {
+            "Not seen running yet".to_string()
+        } else {
+            stats.details(std::time::SystemTime::now())
+        };
+        window.set_app_settings_stats(stats_text.into());
+        let (match_kind, match_text) = match settings.match_rule {
+            Some(MatchRule::Regex(pattern)) => ("regex", pattern),
+            Some(MatchRule::Glob(pattern)) => ("glob", pattern),
+            None => ("glob", String::new()),
+        };
+        window.set_app_settings_match_kind(match_kind.into());
+        window.set_app_settings_match_text(match_text.into());
+        window.set_app_settings_aliases_text(settings.aliases.join(", ").into());
+
+        // A group that is no longer defined stays selectable until it is changed
+        let mut display_groups: Vec<slint::SharedString> = config
+            .display_groups
+            .iter()
+            .map(|group| group.name.as_str().into())
+            .collect();
+        let display_group = settings.display_group.unwrap_or_default();
+        if !display_group.is_empty() && config.display_group(&display_group).is_none() {
+            display_groups.push(display_group.as_str().into());
         }
+        window.set_app_settings_display_groups(slint::ModelRc::new(slint::VecModel::from(
+            display_groups,
+        )));
+        window.set_app_settings_display_group(display_group.into());
     }
 
     /// Stub implementation for non-Windows platforms
     #[cfg(not(windows))]
-    fn remove_app_at_index(_controller: &Arc<Mutex<AppController>>, _index: i32) {
-        Self::show_error_dialog("Application management is only supported on Windows");
+    fn open_app_settings(
+        _controller: &Arc<Mutex<AppController>>,
+        _window: &slint::Weak<MainWindow>,
+        _index: i32,
+    ) {
     }
 
-    /// Toggle the enabled state of an application at the specified index
+    /// Save the settings entered for the application at the specified index
     ///
-    /// Gets the application UUID from the config at the specified index,
-    /// then calls `controller.toggle_app_enabled()` to update the enabled state.
+    /// An empty delay falls back to the global preference. Empty display mode fields
+    /// keep the current setting of the display; leaving both empty clears the display
+    /// mode of the application. An empty match pattern removes the match rule, and an
+    /// empty display group turns HDR on for every display.
     #[cfg(windows)]
-    fn toggle_app_enabled(controller: &Arc<Mutex<AppController>>, index: i32, enabled: bool) {
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "Parameters mirror Slint app settings dialog fields"
+    )]
+    fn save_app_settings(
+        controller: &Arc<Mutex<AppController>>,
+        index: i32,
+        display_name: &str,
+        color_mode: &str,
+        delay: &str,
+        refresh_rate: &str,
+        resolution: &str,
+        match_kind: &str,
+        match_text: &str,
+        aliases: &str,
+        display_group: &str,
+    ) {
+        use easyhdr::config::MatchRule;
+        use easyhdr::config::models::Win32App;
+        use easyhdr::controller::AppSettings;
+        use easyhdr::hdr::{DisplayModeOverride, Resolution};
         use tracing::{info, warn};
 
-        info!(
-            "Toggling application at index {} to enabled={}",
-            index, enabled
-        );
+        let delay = delay.trim();
+        let hdr_disable_debounce_ms = if delay.is_empty() {
+            None
+        } else if let Ok(ms) = delay.parse::<u64>() {
+            Some(ms)
+        } else {
+            Self::show_error_dialog("The delay must be a whole number of milliseconds, e.g. 3000.");
+            return;
+        };
+
+        let refresh_rate = refresh_rate.trim();
+        let refresh_rate_hz = if refresh_rate.is_empty() {
+            None
+        } else if let Some(hz) = refresh_rate.parse::<u32>().ok().filter(|&hz| hz > 0) {
+            Some(hz)
+        } else {
+            Self::show_error_dialog("The refresh rate must be a whole number of Hz, e.g. 120.");
+            return;
+        };
+
+        let resolution = resolution.trim();
+        let resolution = if resolution.is_empty() {
+            None
+        } else if let Some(resolution) = Resolution::parse(resolution) {
+            Some(resolution)
+        } else {
+            Self::show_error_dialog(
+                "The resolution must be written as WIDTHxHEIGHT, e.g. 2560x1440.",
+            );
+            return;
+        };
+
+        let match_text = match_text.trim();
+        let match_rule = match (match_kind, match_text.is_empty()) {
+            (_, true) => None,
+            ("regex", false) => Some(MatchRule::Regex(match_text.to_string())),
+            (_, false) => Some(MatchRule::Glob(match_text.to_string())),
+        };
 
-        // Get the app UUID at the specified index
         let mut controller_guard = controller.lock();
 
-        // Access the config to get the app UUID
         let app_id = {
             let config = controller_guard.config.read();
-            // Validate index is non-negative and within bounds
-            // We check index < 0 first, then cast is safe
             #[expect(
                 clippy::cast_sign_loss,
                 reason = "index is validated to be non-negative before casting"
             )]
-            if index < 0 || (index as usize) >= config.monitored_apps.len() {
-                warn!("Invalid index: {}", index);
-                drop(config);
-                drop(controller_guard);
-                Self::show_error_dialog("Invalid application index");
+            let app = (index >= 0)
+                .then(|| config.monitored_apps.get(index as usize))
+                .flatten();
+            let Some(app) = app else {
+                warn!("Invalid index for app settings: {}", index);
                 return;
-            }
-            #[expect(
-                clippy::cast_sign_loss,
-                reason = "index is validated to be non-negative"
-            )]
-            let idx = index as usize;
-            *config.monitored_apps[idx].id()
+            };
+            *app.id()
         };
 
-        // Toggle the enabled state
-        match controller_guard.toggle_app_enabled(app_id, enabled) {
+        let settings = AppSettings {
+            display_name: display_name.to_string(),
+            color_mode: ColorMode::parse(color_mode),
+            hdr_disable_debounce_ms,
+            display_mode: Some(DisplayModeOverride {
+                refresh_rate_hz,
+                resolution,
+            }),
+            match_rule,
+            aliases: Win32App::parse_aliases(aliases),
+            display_group: Some(display_group.to_string()).filter(|name| !name.is_empty()),
+        };
+        match controller_guard.update_application(app_id, settings) {
             Ok(()) => {
-                info!("Application enabled state updated successfully");
+                info!("Application settings updated successfully");
             }
             Err(e) => {
-                warn!("Failed to toggle application enabled state: {}", e);
+                warn!("Failed to update application settings: {}", e);
                 drop(controller_guard);
                 Self::show_error_dialog_from_error(&e);
             }
@@ -816,8 +2769,249 @@ impl GuiController {
 
     /// Stub implementation for non-Windows platforms
     #[cfg(not(windows))]
-    fn toggle_app_enabled(_controller: &Arc<Mutex<AppController>>, _index: i32, _enabled: bool) {
-        Self::show_error_dialog("Application management is only supported on Windows");
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "Parameters mirror Slint app settings dialog fields"
+    )]
+    fn save_app_settings(
+        _controller: &Arc<Mutex<AppController>>,
+        _index: i32,
+        _display_name: &str,
+        _color_mode: &str,
+        _delay: &str,
+        _refresh_rate: &str,
+        _resolution: &str,
+        _match_kind: &str,
+        _match_text: &str,
+        _aliases: &str,
+        _display_group: &str,
+    ) {
+        Self::show_error_dialog("Application settings are only supported on Windows");
+    }
+
+    /// Banner text for a failed HDR toggle, one line per display with what to try
+    ///
+    /// Empty when the last toggle succeeded.
+    fn hdr_error_text(state: &AppState) -> String {
+        if state.hdr_toggle_failures.is_empty() {
+            return state.hdr_toggle_error.clone().unwrap_or_default();
+        }
+        state
+            .hdr_toggle_failures
+            .iter()
+            .map(|failure| match failure.hint() {
+                Some(hint) => format!("{failure}. {hint}"),
+                None => failure.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Fill the displays dialog with the connected displays and their settings
+    fn open_displays(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
+        use tracing::warn;
+
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        let controller_guard = controller.lock();
+        let display_items: Vec<crate::DisplayListItem> = controller_guard
+            .display_settings()
+            .into_iter()
+            .map(|(display, known)| {
+                let care = known.oled_care.clone().unwrap_or_default();
+                let mut details = format!(
+                    "{} · {}",
+                    display.connector.name(),
+                    if display.supports_hdr {
+                        "HDR"
+                    } else {
+                        "SDR only"
+                    }
+                );
+                if let Some(edid_hdr) = &display.edid_hdr {
+                    details.push_str(&format!(" · EDID: {}", edid_hdr.summary()));
+                }
+                let mut warnings: Vec<String> = Vec::new();
+                if let Some(hint) = display.hdr_discrepancy() {
+                    warnings.push(hint.to_string());
+                }
+                if let Some(advisory) = controller_guard.display_link_advisory(&display) {
+                    warnings.push(advisory.to_string());
+                }
+                crate::DisplayListItem {
+                    identity: known.identity.to_string().into(),
+                    name: display.display_name().into(),
+                    details: details.into(),
+                    capabilities: display
+                        .capabilities
+                        .map(|capabilities| capabilities.summary())
+                        .unwrap_or_default()
+                        .into(),
+                    warning: warnings.join("\n").into(),
+                    supports_hdr: display.supports_hdr,
+                    oled: known.oled_care.is_some(),
+                    oled_care_hours: i32::try_from(care.after_minutes / 60).unwrap_or(i32::MAX),
+                    pixel_refresh_command: care
+                        .pixel_refresh_command
+                        .map(|command| command.to_string())
+                        .unwrap_or_default()
+                        .into(),
+                    hdr_color_profile: known.hdr_color_profile.unwrap_or_default().into(),
+                    color_mode: controller_guard
+                        .display_color_mode(&display)
+                        .unwrap_or_default()
+                        .as_str()
+                        .into(),
+                }
+            })
+            .collect();
+        drop(controller_guard);
+        window.set_display_list(slint::ModelRc::new(slint::VecModel::from(display_items)));
+    }
+
+    /// Fill the statistics dialog with the totals of every monitored application
+    ///
+    /// Applications that ran without HDR ever turning on, and ones never seen running,
+    /// are flagged so the user can check how they are matched.
+    fn open_statistics(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
+        use tracing::warn;
+
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        let now = std::time::SystemTime::now();
+        let stats_items: Vec<crate::StatsListItem> = controller
+            .lock()
+            .all_app_stats()
+            .into_iter()
+            .map(|(name, stats)| {
+                let (details, warning) = if stats.sessions == 0 {
+                    ("Not seen running yet".to_string(), "")
+                } else if stats.hdr_sessions == 0 {
+                    (
+                        stats.details(now),
+                        "HDR never turned on while this application ran. Check that an HDR display is connected and that no excluded process was running.",
+                    )
+                } else {
+                    (stats.details(now), "")
+                };
+                crate::StatsListItem {
+                    name: name.into(),
+                    details: details.into(),
+                    warning: warning.into(),
+                }
+            })
+            .collect();
+        window.set_stats_list(slint::ModelRc::new(slint::VecModel::from(stats_items)));
+    }
+
+    /// Save the per-display settings edited in the displays dialog
+    ///
+    /// Rows are matched to the displays by position, as listed by [`Self::open_displays`].
+    /// An invalid DDC/CI command or color profile name cancels the whole save. Displays
+    /// whose color mode was changed are switched to it right away.
+    fn save_displays(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
+        use easyhdr::config::OledCare;
+        use easyhdr::hdr::VcpCommand;
+        use easyhdr::hdr::color_profile::validate_profile_name;
+        use tracing::{info, warn};
+
+        let Some(window) = window.upgrade() else {
+            warn!("Failed to upgrade window weak reference");
+            return;
+        };
+
+        let mut changes = Vec::new();
+        for item in window.get_display_list().iter() {
+            let profile = item.hdr_color_profile.trim();
+            let hdr_color_profile = if profile.is_empty() {
+                None
+            } else if let Ok(profile) = validate_profile_name(profile) {
+                Some(profile.to_string())
+            } else {
+                Self::show_error_dialog(
+                    "The HDR color profile must be the file name of an installed profile, \
+                     e.g. Windows HDR Calibration.icc.",
+                );
+                return;
+            };
+
+            let color_mode = ColorMode::parse(&item.color_mode);
+
+            if !item.oled {
+                changes.push((item.identity, None, hdr_color_profile, color_mode));
+                continue;
+            }
+
+            let command = item.pixel_refresh_command.trim();
+            let pixel_refresh_command = if command.is_empty() {
+                None
+            } else if let Some(command) = VcpCommand::parse(command) {
+                Some(command)
+            } else {
+                Self::show_error_dialog(
+                    "The pixel refresh command must be a hexadecimal VCP code and value \
+                     written as CODE=VALUE, e.g. E1=01.",
+                );
+                return;
+            };
+            let oled_care = OledCare {
+                after_minutes: u32::try_from(item.oled_care_hours.max(1)).unwrap_or(1) * 60,
+                pixel_refresh_command,
+            };
+            changes.push((
+                item.identity,
+                Some(oled_care),
+                hdr_color_profile,
+                color_mode,
+            ));
+        }
+
+        // Rows are matched by identity, as displays may have been reordered or unplugged
+        // since the list was filled
+        let mut controller_guard = controller.lock();
+        let displays = controller_guard.display_settings();
+        for (identity, oled_care, hdr_color_profile, color_mode) in changes {
+            let Some((display, known)) = displays
+                .iter()
+                .find(|(_, known)| known.identity.to_string() == identity.as_str())
+            else {
+                warn!(
+                    "Display {} is no longer connected, skipping its settings",
+                    identity
+                );
+                continue;
+            };
+
+            let mut result = Ok(());
+            if known.oled_care != oled_care {
+                result = controller_guard.set_oled_care(&known.identity, oled_care);
+            }
+            if result.is_ok() && known.hdr_color_profile != hdr_color_profile {
+                result = controller_guard
+                    .set_hdr_color_profile(&known.identity, hdr_color_profile.as_deref());
+            }
+            if result.is_ok()
+                && let Some(color_mode) = color_mode
+                && controller_guard.color_modes_supported()
+                && display.supports_hdr
+                && controller_guard.display_color_mode(display) != Some(color_mode)
+            {
+                result = controller_guard.set_display_color_mode(&known.identity, color_mode);
+            }
+            if let Err(e) = result {
+                warn!("Failed to save display settings: {}", e);
+                drop(controller_guard);
+                Self::show_error_dialog_from_error(&e);
+                return;
+            }
+        }
+        info!("Display settings saved");
     }
 
     /// Save user preferences settings
@@ -825,64 +3019,81 @@ impl GuiController {
     /// Updates user preferences in the configuration and handles auto-start registry
     /// management. Called when the user clicks "Save" in the settings dialog.
     ///
-    /// Uses partial update pattern to preserve update check metadata fields
-    /// (`last_update_check_time`, `cached_latest_version`) as per Rust-Bible
-    /// "State Persistence & I/O Discipline" guidelines.
-    #[cfg(windows)]
-    #[expect(
-        clippy::fn_params_excessive_bools,
-        clippy::too_many_arguments,
-        reason = "Parameters mirror Slint UI settings dialog fields"
-    )]
-    fn save_settings(
-        controller: &Arc<Mutex<AppController>>,
-        auto_start: bool,
-        monitoring_interval_ms: i32,
-        show_tray_notifications: bool,
-        show_update_notifications: bool,
-        auto_open_release_page: bool,
-        minimize_to_tray_on_minimize: bool,
-        minimize_to_tray_on_close: bool,
-        start_minimized_to_tray: bool,
-    ) {
+    /// Uses partial update pattern to preserve update check metadata fields
+    /// (`last_update_check_time`, `cached_latest_version`) as per Rust-Bible
+    /// "State Persistence & I/O Discipline" guidelines.
+    #[cfg(windows)]
+    fn save_settings(controller: &Arc<Mutex<AppController>>, settings: &crate::SettingsData) {
+        use easyhdr::config::{AutoStartMethod, BatteryHdrPolicy, ShutdownPolicy, UpdateChannel};
         use easyhdr::utils::AutoStartManager;
         use tracing::{info, warn};
 
-        info!(
-            "Saving settings: auto_start={}, monitoring_interval_ms={}, show_tray_notifications={}, show_update_notifications={}, auto_open_release_page={}, minimize_to_tray_on_minimize={}, minimize_to_tray_on_close={}, start_minimized_to_tray={}",
-            auto_start,
-            monitoring_interval_ms,
-            show_tray_notifications,
-            show_update_notifications,
-            auto_open_release_page,
-            minimize_to_tray_on_minimize,
-            minimize_to_tray_on_close,
-            start_minimized_to_tray
-        );
+        info!("Saving settings: {:?}", settings);
 
         // Apply partial update pattern: mutate existing preferences to preserve update metadata
-        let controller_guard = controller.lock();
+        let mut controller_guard = controller.lock();
         {
             let mut config = controller_guard.config.write();
 
             // Update only the UI-controlled fields, preserving update check metadata
-            config.preferences.auto_start = auto_start;
+            config.preferences.auto_start = settings.auto_start;
             #[expect(
                 clippy::cast_sign_loss,
                 reason = "monitoring_interval_ms is validated to be non-negative by UI constraints"
             )]
             {
-                config.preferences.monitoring_interval_ms = monitoring_interval_ms as u64;
-            }
-            config.preferences.show_tray_notifications = show_tray_notifications;
-            config.preferences.show_update_notifications = show_update_notifications;
-            config.preferences.auto_open_release_page = auto_open_release_page;
-            config.preferences.minimize_to_tray_on_minimize = minimize_to_tray_on_minimize;
-            config.preferences.minimize_to_tray_on_close = minimize_to_tray_on_close;
-            config.preferences.start_minimized_to_tray = start_minimized_to_tray;
+                config.preferences.monitoring_interval_ms = settings.monitoring_interval_ms as u64;
+            }
+            config.preferences.show_tray_notifications = settings.show_tray_notifications;
+            config.preferences.show_update_notifications = settings.show_update_notifications;
+            config.preferences.auto_open_release_page = settings.auto_open_release_page;
+            config.preferences.minimize_to_tray_on_minimize = settings.minimize_to_tray_on_minimize;
+            config.preferences.minimize_to_tray_on_close = settings.minimize_to_tray_on_close;
+            config.preferences.start_minimized_to_tray = settings.start_minimized_to_tray;
+            config.preferences.track_child_processes = settings.track_child_processes;
+            config.preferences.wait_for_window = settings.wait_for_window;
+            config.preferences.suspend_night_light = settings.suspend_night_light;
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "hdr_disable_debounce_ms is validated to be non-negative by UI constraints"
+            )]
+            {
+                config.preferences.hdr_disable_debounce_ms =
+                    settings.hdr_disable_debounce_ms as u64;
+            }
+            config.preferences.weekly_summary_notification = settings.weekly_summary_notification;
+            config.preferences.shutdown_policy =
+                ShutdownPolicy::parse(settings.shutdown_policy.as_str()).unwrap_or_default();
+            config.preferences.max_hdr_session_minutes =
+                u32::try_from(settings.max_hdr_session_minutes).unwrap_or(0);
+            config.preferences.external_change_hold_minutes =
+                u32::try_from(settings.external_change_hold_minutes).unwrap_or(0);
+            config.preferences.battery_hdr_policy =
+                BatteryHdrPolicy::parse(settings.battery_hdr_policy.as_str()).unwrap_or_default();
+            config.preferences.battery_hdr_threshold_percent =
+                u8::try_from(settings.battery_hdr_threshold_percent.clamp(0, 100)).unwrap_or(20);
+            config.set_excluded_processes(settings.excluded_processes.as_str());
+            config.preferences.write_event_log = settings.write_event_log;
+            config.preferences.metrics_enabled = settings.metrics_enabled;
+            config.preferences.discord_presence = settings.discord_presence;
+            config.preferences.discord_client_id = settings.discord_client_id.trim().to_string();
+            config.preferences.run_in_background = settings.run_in_background;
+            config.preferences.autostart_method =
+                AutoStartMethod::parse(settings.autostart_method.as_str()).unwrap_or_default();
+            config.preferences.autostart_elevated = settings.autostart_elevated;
+            config.preferences.autostart_delay_secs =
+                u32::try_from(settings.autostart_delay_secs).unwrap_or(0);
+            config.preferences.update_channel =
+                UpdateChannel::parse(settings.update_channel.as_str()).unwrap_or_default();
             // last_update_check_time and cached_latest_version are intentionally NOT modified
         }
 
+        // Apply monitoring preferences and exclusions to the running process monitor
+        controller_guard.update_process_monitor_watch_list();
+        easyhdr::utils::event_log::set_enabled(settings.write_event_log);
+        easyhdr::utils::metrics::get_metrics().set_enabled(settings.metrics_enabled);
+        controller_guard.apply_discord_preferences();
+
         // Save configuration to disk
         // Phase 3.1: Use read lock for saving (no mutation needed)
         let config = controller_guard.config.read();
@@ -897,38 +3108,26 @@ impl GuiController {
             Self::show_error_dialog_from_error(&e);
             return;
         }
+        let preferences = config.preferences.clone();
         drop(config);
+        controller_guard.notify_config_saved();
         drop(controller_guard);
 
-        // Handle auto-start registry management
-        if auto_start {
-            info!("Enabling auto-start");
-            match AutoStartManager::enable() {
-                Ok(()) => {
-                    info!("Auto-start enabled successfully");
-                }
-                Err(e) => {
-                    warn!("Failed to enable auto-start: {}", e);
-                    Self::show_error_dialog(&format!(
-                        "Settings saved, but failed to enable auto-start:\n\n{e}"
-                    ));
-                    return;
-                }
-            }
-        } else {
-            info!("Disabling auto-start");
-            match AutoStartManager::disable() {
-                Ok(()) => {
-                    info!("Auto-start disabled successfully");
-                }
-                Err(e) => {
-                    warn!("Failed to disable auto-start: {}", e);
-                    Self::show_error_dialog(&format!(
-                        "Settings saved, but failed to disable auto-start:\n\n{e}"
-                    ));
-                    return;
-                }
+        // Handle auto-start registry or logon task management
+        info!(
+            "{} auto-start",
+            if settings.auto_start {
+                "Enabling"
+            } else {
+                "Disabling"
             }
+        );
+        if let Err(e) = AutoStartManager::apply(&preferences) {
+            warn!("Failed to update auto-start: {}", e);
+            Self::show_error_dialog(&format!(
+                "Settings saved, but failed to update auto-start:\n\n{e}"
+            ));
+            return;
         }
 
         info!("All settings saved successfully");
@@ -938,45 +3137,42 @@ impl GuiController {
 
     /// Stub implementation for non-Windows platforms
     #[cfg(not(windows))]
-    #[expect(
-        clippy::fn_params_excessive_bools,
-        clippy::too_many_arguments,
-        reason = "Non-Windows stub mirrors Windows implementation signature"
-    )]
-    fn save_settings(
-        _controller: &Arc<Mutex<AppController>>,
-        _auto_start: bool,
-        _monitoring_interval_ms: i32,
-        _show_tray_notifications: bool,
-        _show_update_notifications: bool,
-        _auto_open_release_page: bool,
-        _minimize_to_tray_on_minimize: bool,
-        _minimize_to_tray_on_close: bool,
-        _start_minimized_to_tray: bool,
-    ) {
+    fn save_settings(_controller: &Arc<Mutex<AppController>>, _settings: &crate::SettingsData) {
         Self::show_error_dialog("Settings management is only supported on Windows");
     }
 
     /// Check for application updates from GitHub
     ///
     /// This method:
-    /// - Checks rate limiting (minimum 60 seconds between checks)
+    /// - Checks rate limiting (minimum 60 seconds between manual checks, 6 hours between
+    ///   `automatic` ones, and the backoff after GitHub rate limit errors)
     /// - Spawns a background thread to avoid blocking the UI
     /// - Shows a notification if an update is available
     /// - Updates the cached version and last check time in config
     /// - Fails silently on network errors
-    fn check_for_updates(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
+    fn check_for_updates(
+        controller: &Arc<Mutex<AppController>>,
+        window: &slint::Weak<MainWindow>,
+        automatic: bool,
+    ) {
         use easyhdr::utils::UpdateChecker;
+        use easyhdr::utils::update_checker::AUTOMATIC_CHECK_INTERVAL_SECS;
         use semver::Version;
         use tracing::{info, warn};
 
-        info!("Manual update check requested");
+        if !automatic {
+            info!("Manual update check requested");
+        }
 
         // Check rate limiting
-        let (should_check, last_check_time) = {
+        let (should_check, last_check_time, backoff_remaining) = {
             let controller_guard = controller.lock();
             let config = controller_guard.config.read();
             let last_check = config.preferences.last_update_check_time;
+            let backoff_remaining = config
+                .preferences
+                .update_check_cache
+                .backoff_remaining(UpdateChecker::current_timestamp());
             drop(config);
             drop(controller_guard);
 
@@ -984,21 +3180,46 @@ impl GuiController {
                 "engels74",
                 "EasyHDR",
                 Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
-                60, // Minimum 60 seconds between checks
+                // Minimum 60 seconds between manual checks
+                if automatic {
+                    AUTOMATIC_CHECK_INTERVAL_SECS
+                } else {
+                    60
+                },
             );
 
-            (checker.should_check(last_check), last_check)
+            (
+                checker.should_check(last_check),
+                last_check,
+                backoff_remaining,
+            )
         };
 
+        if let Some(remaining) = backoff_remaining {
+            info!("Update check skipped, GitHub rate limit backoff for another {remaining}s");
+            if !automatic {
+                Self::show_info_notification(
+                    &tr("update-check-title"),
+                    &tr_args(
+                        "update-rate-limited",
+                        &[("minutes", &remaining.div_ceil(60).to_string())],
+                    ),
+                );
+            }
+            return;
+        }
+
         if !should_check {
             info!(
                 "Update check rate limited (last check: {})",
                 last_check_time
             );
-            Self::show_info_notification(
-                "Update Check",
-                "Please wait at least 60 seconds between update checks",
-            );
+            if !automatic {
+                Self::show_info_notification(
+                    &tr("update-check-title"),
+                    &tr("update-check-too-soon"),
+                );
+            }
             return;
         }
 
@@ -1021,13 +3242,22 @@ impl GuiController {
             );
 
             // Perform the update check
-            let result = checker.check_for_updates();
+            let (mut cache, channel) = {
+                let controller_guard = controller_clone.lock();
+                let config = controller_guard.config.read();
+                (
+                    config.preferences.update_check_cache.clone(),
+                    config.preferences.update_channel,
+                )
+            };
+            let result = checker.with_channel(channel).check_for_updates(&mut cache);
 
             // Update last check time and cache in config, then persist immediately
             {
                 let controller_guard = controller_clone.lock();
                 let mut config = controller_guard.config.write();
                 config.preferences.last_update_check_time = UpdateChecker::current_timestamp();
+                config.preferences.update_check_cache = cache;
 
                 if let Ok(ref check_result) = result {
                     config.preferences.cached_latest_version =
@@ -1051,6 +3281,20 @@ impl GuiController {
                 window.set_checking_for_updates(false);
             }
 
+            // Offer to install an update the release has the assets for
+            if let Ok(check_result) = &result
+                && check_result.update_available
+                && check_result.download.is_some()
+            {
+                let version = check_result.latest_version.to_string();
+                let _ = window_weak.upgrade_in_event_loop(move |window| {
+                    if window.get_update_stage().is_empty() {
+                        window.set_update_version(version.into());
+                        window.set_update_stage("available".into());
+                    }
+                });
+            }
+
             // Handle result
             match result {
                 Ok(check_result) => {
@@ -1065,6 +3309,93 @@ impl GuiController {
         });
     }
 
+    /// Download the update found by the last check in the background
+    ///
+    /// Reports the progress on the update button, which offers to restart once the
+    /// update is staged. A failed download can be tried again.
+    fn install_update(controller: &Arc<Mutex<AppController>>, window: &slint::Weak<MainWindow>) {
+        use easyhdr::utils::self_update;
+        use semver::Version;
+        use tracing::warn;
+
+        let (version, download) = {
+            let controller_guard = controller.lock();
+            let config = controller_guard.config.read();
+            (
+                Version::parse(&config.preferences.cached_latest_version).ok(),
+                config.preferences.update_check_cache.download.clone(),
+            )
+        };
+        let (Some(version), Some(download)) = (version, download) else {
+            warn!("No downloadable update is known, check for updates first");
+            return;
+        };
+
+        if let Some(window) = window.upgrade() {
+            window.set_update_progress(0.0);
+            window.set_update_stage("downloading".into());
+        }
+
+        let window_weak = window.clone();
+        std::thread::spawn(move || {
+            // Only whole percents reach the window
+            let mut reported = None;
+            let progress_window = window_weak.clone();
+            let result = self_update::download(&download, &version, |done, total| {
+                let percent = done.saturating_mul(100) / total.max(1);
+                if reported != Some(percent) {
+                    reported = Some(percent);
+                    let _ = progress_window.upgrade_in_event_loop(move |window| {
+                        #[expect(
+                            clippy::cast_precision_loss,
+                            reason = "A percentage of at most 100 is exact as f32"
+                        )]
+                        window.set_update_progress(percent as f32 / 100.0);
+                    });
+                }
+            });
+
+            let stage = match result {
+                Ok(_) => "ready",
+                Err(e) => {
+                    warn!("Failed to download the update: {}", e);
+                    Self::show_info_notification(
+                        "Update Failed",
+                        &format!("EasyHDR {version} could not be downloaded: {e}"),
+                    );
+                    "available"
+                }
+            };
+            let _ = window_weak.upgrade_in_event_loop(move |window| {
+                window.set_update_stage(stage.into());
+            });
+        });
+    }
+
+    /// Restart EasyHDR so it installs the downloaded update
+    ///
+    /// HDR is left as it is for the restarted instance instead of applying the shutdown
+    /// policy.
+    fn restart_to_update(controller: &Arc<Mutex<AppController>>) {
+        use easyhdr::utils::self_update;
+        use tracing::{info, warn};
+
+        if let Err(e) = self_update::restart() {
+            warn!("Failed to restart EasyHDR: {}", e);
+            Self::show_error_dialog(&format!("Failed to restart EasyHDR:\n\n{e}"));
+            return;
+        }
+        let mut controller_guard = controller.lock();
+        if controller_guard.is_front_end() {
+            // The restarted instance needs the single-instance lock the agent holds
+            easyhdr::controller::agent::stop_running_agent();
+        } else {
+            controller_guard.hand_off();
+        }
+        info!("Restarting to install the update");
+        std::process::exit(0);
+    }
+
     /// Handle the result of an update check
     ///
     /// Shows a notification if an update is available and the user has enabled update notifications.
@@ -1093,9 +3424,12 @@ impl GuiController {
             // Only show notification if user has enabled update notifications
             if show_notifications {
                 // Show notification with update information
-                let message = format!(
-                    "A new version is available!\n\nCurrent: {}\nLatest: {}\n\nClick to view releases",
-                    result.current_version, result.latest_version
+                let message = tr_args(
+                    "update-available",
+                    &[
+                        ("current", &result.current_version.to_string()),
+                        ("latest", &result.latest_version.to_string()),
+                    ],
                 );
 
                 Self::show_update_notification(
@@ -1110,7 +3444,7 @@ impl GuiController {
             info!("Application is up to date");
             // Only show "up to date" notification if user has enabled update notifications
             if show_notifications {
-                Self::show_info_notification("Update Check", "You are running the latest version!");
+                Self::show_info_notification(&tr("update-check-title"), &tr("up-to-date"));
             }
         }
     }
@@ -1122,7 +3456,7 @@ impl GuiController {
         use tracing::warn;
 
         let toast = Toast::new(Toast::POWERSHELL_APP_ID)
-            .title("EasyHDR Update Available")
+            .title(&tr("update-available-title"))
             .text1(message)
             .duration(Duration::Long);
 
@@ -1185,6 +3519,8 @@ impl GuiController {
 
         info!("Releasing GUI resources (icon cache)");
 
+        HIGH_DPI_ICONS.with_borrow_mut(std::collections::HashMap::clear);
+
         let controller_guard = controller.lock();
         // Phase 3.1: Use write lock for exclusive access
         let mut config = controller_guard.config.write();
@@ -1214,36 +3550,60 @@ impl GuiController {
 
     /// Reload GUI resources when window is shown
     ///
-    /// Reloads icon data that was released when the window was minimized to the tray.
-    /// This ensures icons are available for display.
+    /// Reloads icon data that was released when the window was minimized to the tray,
+    /// or never loaded because EasyHDR started in the tray. Icons come from the disk
+    /// cache first and are only extracted again when the cache misses; both happen in the
+    /// background and reach the application list through state updates.
     fn reload_gui_resources(controller: &Arc<Mutex<AppController>>) {
         use tracing::info;
 
-        info!("Reloading GUI resources (icon cache)");
+        info!("Reloading GUI resources (icons) in the background");
+        controller.lock().load_icons_in_background();
+    }
 
-        let controller_guard = controller.lock();
-        // Phase 3.1: Use write lock for exclusive access
-        let mut config = controller_guard.config.write();
+    /// Run the shutdown sequence and exit the application
+    ///
+    /// Applies the shutdown policy through `AppController::shutdown` before exiting, or
+    /// hands monitoring off to a background agent if `run_in_background` is set. A window
+    /// that is a front-end of the agent leaves it running, or asks it to shut down if
+    /// `run_in_background` was turned off in the meantime.
+    /// We use `std::process::exit(0)` instead of `slint::quit_event_loop()` because:
+    /// 1. `quit_event_loop()` is asynchronous and doesn't guarantee immediate termination
+    /// 2. Background threads (`ProcessMonitor`, `AppController`) run infinite loops with no shutdown signal
+    /// 3. Returning `KeepWindowShown` after `quit_event_loop()` causes the window to hang
+    /// 4. The OS will clean up all resources (memory, handles, threads) on process exit
+    /// 5. Configuration is saved whenever it changes, so no data loss occurs
+    fn exit_application(controller: &Arc<Mutex<AppController>>) -> ! {
+        use easyhdr::controller::agent;
+        use tracing::{info, warn};
 
-        // Reload all icon data
-        let mut reloaded_count = 0;
-        for app in &mut config.monitored_apps {
-            if app.ensure_icon_loaded().is_some() {
-                reloaded_count += 1;
+        let mut controller_guard = controller.lock();
+        if controller_guard.is_front_end() {
+            if controller_guard.config.read().preferences.run_in_background {
+                info!("Monitoring continues in the background agent");
+            } else if let Err(e) = agent::send(agent::AgentCommand::Shutdown) {
+                warn!("Failed to stop the background agent: {}", e);
             }
+            info!("Exiting application");
+            std::process::exit(0);
         }
 
-        drop(config);
-        drop(controller_guard);
-
-        info!("Reloaded {} icon(s) into cache", reloaded_count);
-
-        // Log memory stats after reload
-        #[cfg(windows)]
-        {
-            use crate::utils::memory_profiler;
-            memory_profiler::get_profiler().log_stats();
+        let run_in_background = controller_guard.config.read().preferences.run_in_background
+            && match agent::spawn_process() {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Failed to start the background agent: {}", e);
+                    false
+                }
+            };
+        if run_in_background {
+            controller_guard.hand_off();
+            info!("Monitoring continues in the background agent");
+        } else {
+            controller_guard.shutdown();
         }
+        info!("Exiting application");
+        std::process::exit(0);
     }
 
     /// Minimize the window to tray and release GUI resources
@@ -1269,6 +3629,7 @@ impl GuiController {
 
         // Release GUI resources to reduce memory usage
         Self::release_gui_resources(controller);
+        easyhdr::utils::memory_profiler::trim_working_set();
 
         info!("Window minimized to tray successfully");
     }
@@ -1281,7 +3642,7 @@ impl GuiController {
         controller: &Arc<Mutex<AppController>>,
         window: &slint::Weak<MainWindow>,
     ) {
-        use easyhdr::config::models::UwpApp;
+        use easyhdr::config::models::{AppSource, UwpApp};
         use tracing::{info, warn};
 
         info!("UWP picker: Adding selected packages");
@@ -1314,6 +3675,15 @@ impl GuiController {
                 package_family_name: pkg.package_family_name.to_string(),
                 app_id: pkg.app_id.to_string(),
                 enabled: true,
+                // Multi-app packages are matched per application so each entry is independent
+                match_app_id: pkg.multi_app_package,
+                source: AppSource::Manual,
+                display_mode: None,
+                hdr_disable_debounce_ms: None,
+                max_hdr_session_minutes: None,
+                color_mode: None,
+                capture_tone_mapping: None,
+                display_group: None,
                 icon_data: None, // Will be loaded on demand
             };
 
@@ -1460,7 +3830,7 @@ impl GuiController {
         info!("Showing error dialog: {}", message);
 
         rfd::MessageDialog::new()
-            .set_title("EasyHDR - Error")
+            .set_title(tr("error-title"))
             .set_description(message)
             .set_buttons(rfd::MessageButtons::Ok)
             .show();
@@ -1494,7 +3864,7 @@ impl GuiController {
 
         // Show the dialog with the user-friendly message
         rfd::MessageDialog::new()
-            .set_title("EasyHDR - Error")
+            .set_title(tr("error-title"))
             .set_description(&message)
             .set_buttons(rfd::MessageButtons::Ok)
             .show();
@@ -1502,12 +3872,326 @@ impl GuiController {
 
     /// Stub implementation for non-Windows platforms
     #[cfg(not(windows))]
-    #[expect(dead_code)]
     fn show_error_dialog_from_error(error: &easyhdr::error::EasyHdrError) {
         use easyhdr::error::get_user_friendly_error;
         eprintln!("Error: {}", get_user_friendly_error(error));
     }
 
+    /// Start a time-boxed process detection trace
+    ///
+    /// The button stays disabled until the trace ends on its own.
+    fn start_process_trace(
+        controller: &Arc<Mutex<AppController>>,
+        window_weak: &slint::Weak<MainWindow>,
+    ) {
+        use easyhdr::monitor::PROCESS_TRACE_DURATION;
+
+        controller
+            .lock()
+            .start_process_trace(PROCESS_TRACE_DURATION);
+
+        if let Some(window) = window_weak.upgrade() {
+            window.set_process_trace_active(true);
+        }
+
+        let window_weak = window_weak.clone();
+        Timer::single_shot(PROCESS_TRACE_DURATION, move || {
+            if let Some(window) = window_weak.upgrade() {
+                window.set_process_trace_active(false);
+            }
+        });
+    }
+
+    /// Export the usage statistics
+    ///
+    /// Asks where to save the statistics; the file extension picks the format (a
+    /// plain-text table, CSV with one row per session, or JSON).
+    #[cfg(windows)]
+    fn export_stats(controller: &Arc<Mutex<AppController>>) {
+        use easyhdr::controller::StatsFormat;
+        use tracing::{info, warn};
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Text Files", &["txt"])
+            .add_filter("CSV Files", &["csv"])
+            .add_filter("JSON Files", &["json"])
+            .set_file_name("easyhdr-stats.txt")
+            .set_title("Export Usage Statistics")
+            .save_file()
+        else {
+            return;
+        };
+
+        let format = StatsFormat::from_extension(
+            &path
+                .extension()
+                .map(|extension| extension.to_string_lossy())
+                .unwrap_or_default(),
+        );
+        let result = controller
+            .lock()
+            .export_stats(format)
+            .and_then(|report| Ok(std::fs::write(&path, report)?));
+
+        match result {
+            Ok(()) => {
+                info!("Exported usage statistics to {:?}", path);
+                Self::show_info_notification(
+                    "Statistics Exported",
+                    &format!("Usage statistics saved to {}", path.display()),
+                );
+            }
+            Err(e) => {
+                warn!("Failed to export usage statistics to {:?}: {}", path, e);
+                Self::show_error_dialog(&format!("Failed to export usage statistics:\n\n{e}"));
+            }
+        }
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn export_stats(_controller: &Arc<Mutex<AppController>>) {
+        Self::show_error_dialog("Exporting statistics is only supported on Windows");
+    }
+
+    /// Export the configuration to a file chosen by the user
+    #[cfg(windows)]
+    fn export_config(controller: &Arc<Mutex<AppController>>) {
+        use tracing::warn;
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON Files", &["json"])
+            .set_file_name("easyhdr-config.json")
+            .set_title("Export Configuration")
+            .save_file()
+        else {
+            return;
+        };
+
+        match controller.lock().export_config(&path) {
+            Ok(()) => Self::show_info_notification(
+                "Configuration Exported",
+                &format!("Configuration saved to {}", path.display()),
+            ),
+            Err(e) => {
+                warn!("Failed to export configuration to {:?}: {}", path, e);
+                Self::show_error_dialog(&format!("Failed to export the configuration:\n\n{e}"));
+            }
+        }
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn export_config(_controller: &Arc<Mutex<AppController>>) {
+        Self::show_error_dialog("Exporting the configuration is only supported on Windows");
+    }
+
+    /// Replace the watch list and preferences with a configuration chosen by the user
+    ///
+    /// The settings dialog is refreshed so saving it afterwards keeps the imported values.
+    #[cfg(windows)]
+    fn import_config(
+        controller: &Arc<Mutex<AppController>>,
+        window_weak: &slint::Weak<MainWindow>,
+    ) {
+        use tracing::warn;
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON Files", &["json"])
+            .set_title("Import Configuration")
+            .pick_file()
+        else {
+            return;
+        };
+
+        let confirmed = rfd::MessageDialog::new()
+            .set_title("EasyHDR - Import Configuration")
+            .set_description(
+                "Replace your monitored applications, exclusions and settings with the imported \
+                 configuration? The current configuration is backed up first.",
+            )
+            .set_buttons(rfd::MessageButtons::YesNo)
+            .show();
+        if confirmed != rfd::MessageDialogResult::Yes {
+            return;
+        }
+
+        let mut controller_guard = controller.lock();
+        match controller_guard.import_config(&path) {
+            Ok(()) => {
+                if let Some(window) = window_weak.upgrade() {
+                    Self::load_settings(&window, &controller_guard);
+                }
+                drop(controller_guard);
+                Self::show_info_notification(
+                    "Configuration Imported",
+                    &format!("Configuration loaded from {}", path.display()),
+                );
+            }
+            Err(e) => {
+                drop(controller_guard);
+                warn!("Failed to import configuration from {:?}: {}", path, e);
+                Self::show_error_dialog(&format!("Failed to import the configuration:\n\n{e}"));
+            }
+        }
+    }
+
+    /// Stub implementation for non-Windows platforms
+    #[cfg(not(windows))]
+    fn import_config(
+        _controller: &Arc<Mutex<AppController>>,
+        _window_weak: &slint::Weak<MainWindow>,
+    ) {
+        Self::show_error_dialog("Importing a configuration is only supported on Windows");
+    }
+
+    /// Set the settings dialog properties from the configuration
+    fn load_settings(window: &MainWindow, controller: &AppController) {
+        use tracing::info;
+
+        let config = controller.config.read();
+
+        window.set_settings_auto_start(config.preferences.auto_start);
+        window.set_onboarding_auto_start(config.preferences.auto_start);
+        // Safe cast: monitoring_interval_ms is constrained to 500-2000ms range
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "monitoring_interval_ms is constrained to 500-2000ms range by validation, well within i32 range"
+        )]
+        {
+            window.set_settings_monitoring_interval_ms(
+                config.preferences.monitoring_interval_ms as i32,
+            );
+        }
+        window.set_settings_show_tray_notifications(config.preferences.show_tray_notifications);
+        window.set_settings_show_update_notifications(config.preferences.show_update_notifications);
+        window.set_settings_auto_open_release_page(config.preferences.auto_open_release_page);
+        window.set_settings_minimize_to_tray_on_minimize(
+            config.preferences.minimize_to_tray_on_minimize,
+        );
+        window.set_settings_minimize_to_tray_on_close(config.preferences.minimize_to_tray_on_close);
+        window.set_settings_start_minimized_to_tray(config.preferences.start_minimized_to_tray);
+        window.set_settings_track_child_processes(config.preferences.track_child_processes);
+        window.set_settings_wait_for_window(config.preferences.wait_for_window);
+        window.set_settings_suspend_night_light(config.preferences.suspend_night_light);
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "hdr_disable_debounce() is clamped to MAX_HDR_DISABLE_DEBOUNCE_MS (10s), well within i32 range"
+        )]
+        {
+            window.set_settings_hdr_disable_debounce_ms(
+                config.preferences.hdr_disable_debounce().as_millis() as i32,
+            );
+        }
+        window.set_settings_weekly_summary_notification(
+            config.preferences.weekly_summary_notification,
+        );
+        window.set_settings_shutdown_policy(config.preferences.shutdown_policy.as_str().into());
+        window.set_settings_max_hdr_session_minutes(
+            i32::try_from(config.preferences.max_hdr_session_minutes).unwrap_or(i32::MAX),
+        );
+        window.set_settings_external_change_hold_minutes(
+            i32::try_from(config.preferences.external_change_hold_minutes).unwrap_or(i32::MAX),
+        );
+        window
+            .set_settings_battery_hdr_policy(config.preferences.battery_hdr_policy.as_str().into());
+        window.set_settings_battery_hdr_threshold_percent(i32::from(
+            config.preferences.battery_hdr_threshold_percent,
+        ));
+        window.set_settings_excluded_processes(config.excluded_processes.join(", ").into());
+        window.set_settings_write_event_log(config.preferences.write_event_log);
+        window.set_settings_metrics_enabled(config.preferences.metrics_enabled);
+        window.set_settings_discord_presence(config.preferences.discord_presence);
+        window.set_settings_discord_client_id(config.preferences.discord_client_id.clone().into());
+        window.set_settings_run_in_background(config.preferences.run_in_background);
+        window.set_settings_autostart_method(config.preferences.autostart_method.as_str().into());
+        window.set_settings_autostart_elevated(config.preferences.autostart_elevated);
+        window.set_settings_autostart_delay_secs(
+            i32::try_from(config.preferences.autostart_delay_secs).unwrap_or(0),
+        );
+        window.set_settings_update_channel(config.preferences.update_channel.as_str().into());
+        drop(config);
+        window.set_auto_hdr_supported(controller.auto_hdr_supported());
+        window.set_color_modes_supported(controller.color_modes_supported());
+        window.set_capture_tone_mapping_supported(controller.capture_tone_mapping_supported());
+
+        info!("Settings properties initialized from config");
+    }
+
+    /// Show first-run setup over the main window, or hide it once it is done
+    fn update_onboarding(window: &MainWindow, onboarding: Option<&OnboardingState>) {
+        let Some(onboarding) = onboarding else {
+            window.set_onboarding_step("".into());
+            return;
+        };
+
+        window.set_onboarding_step(onboarding.step.as_str().into());
+        window.set_onboarding_hdr_displays(onboarding.hdr_displays.join("\n").into());
+        window.set_onboarding_test_seconds_left(
+            onboarding
+                .hdr_test_seconds_left
+                .and_then(|seconds| i32::try_from(seconds).ok())
+                .unwrap_or(-1),
+        );
+        window.set_onboarding_test_result(
+            match onboarding.hdr_test_passed {
+                Some(true) => "passed",
+                Some(false) => "failed",
+                None => "",
+            }
+            .into(),
+        );
+    }
+
+    /// Show the most recent HDR toggles in the troubleshooting section of the settings
+    fn update_hdr_history(window: &MainWindow, controller: &Arc<Mutex<AppController>>) {
+        /// Number of toggles listed in the settings dialog
+        const SHOWN_TOGGLES: usize = 20;
+
+        let now = std::time::SystemTime::now();
+        let history: Vec<slint::SharedString> = controller
+            .lock()
+            .get_history()
+            .iter()
+            .take(SHOWN_TOGGLES)
+            .map(|record| format!("{} · {}", record.age(now), record.description()).into())
+            .collect();
+        window.set_settings_hdr_history(slint::ModelRc::new(slint::VecModel::from(history)));
+    }
+
+    /// Show the errors `EasyHDR` recovered from in the troubleshooting section of the settings
+    fn update_recent_errors(window: &MainWindow) {
+        let errors: Vec<slint::SharedString> = easyhdr::utils::error_center::entries()
+            .iter()
+            .map(|entry| entry.describe().into())
+            .collect();
+        window.set_settings_recent_errors(slint::ModelRc::new(slint::VecModel::from(errors)));
+    }
+
+    /// Update process scan diagnostics in the settings dialog
+    ///
+    /// Shows the rolling average scan duration and, when scans exceed the monitoring
+    /// interval, the longer interval suggested by the process monitor.
+    fn update_scan_diagnostics(window: &MainWindow, controller: &Arc<Mutex<AppController>>) {
+        let timings = controller.lock().poll_timings();
+
+        let text = match (timings.average(), timings.last()) {
+            (Some(average), Some(last)) => format!(
+                "Process scan time: {:.1}ms average, {:.1}ms last",
+                average.as_secs_f64() * 1000.0,
+                last.as_secs_f64() * 1000.0
+            ),
+            _ => String::new(),
+        };
+        window.set_settings_process_scan_text(text.into());
+
+        let suggested_ms = timings
+            .suggested_interval()
+            .and_then(|interval| i32::try_from(interval.as_millis()).ok())
+            .unwrap_or(0);
+        window.set_settings_suggested_interval_ms(suggested_ms);
+    }
+
     /// Update cache info display in the UI
     ///
     /// Reads cache statistics from the icon cache and updates the UI display
@@ -1745,6 +4429,8 @@ impl GuiController {
         let window_visibility = Rc::new(Cell::new(true));
         let window_minimized = Rc::new(Cell::new(false));
         let previous_hdr_state = Rc::new(Cell::new(None::<bool>));
+        let previous_apps = Rc::new(RefCell::new(None::<Vec<AppSnapshot>>));
+        let pending_actions = self.pending_actions.clone();
         let ui_cmd_rx = Rc::new(ui_cmd_rx);
         let ui_update_timer = Rc::new(Timer::default());
 
@@ -1755,6 +4441,8 @@ impl GuiController {
             let window_visibility = window_visibility.clone();
             let window_minimized = window_minimized.clone();
             let previous_hdr_state = previous_hdr_state.clone();
+            let previous_apps = previous_apps.clone();
+            let pending_actions = pending_actions.clone();
             let ui_cmd_rx = ui_cmd_rx.clone();
             let timer_handle = ui_update_timer.clone();
 
@@ -1798,8 +4486,18 @@ impl GuiController {
                                 &tray_icon,
                                 &window_visibility,
                                 &previous_hdr_state,
+                                &previous_apps,
                                 &state,
                             );
+                            // The conflict pause has its own banner with a Resume button
+                            pending_actions.replace(
+                                state
+                                    .pending_actions
+                                    .iter()
+                                    .filter(|action| action.id != PendingActionId::HdrConflictPause)
+                                    .cloned()
+                                    .collect(),
+                            );
                         }
                         Err(TryRecvError::Empty) => break,
                         Err(TryRecvError::Disconnected) => {
@@ -1809,6 +4507,11 @@ impl GuiController {
                         }
                     }
                 }
+
+                // Count down the pending actions between state updates
+                if let Some(window) = window_weak.upgrade() {
+                    Self::update_pending_actions(&window, &pending_actions.borrow());
+                }
             });
         }
 
@@ -1843,6 +4546,7 @@ impl GuiController {
             info!("Starting minimized to tray (user preference)");
             // Window is already hidden by default, no need to explicitly hide
             // The tray icon is already created and visible
+            easyhdr::utils::memory_profiler::trim_working_set();
         } else {
             // Show the window explicitly before starting the event loop
             // Note: run_event_loop_until_quit() doesn't automatically show the window (unlike run())
@@ -1853,6 +4557,9 @@ impl GuiController {
                 // Preserve error chain by wrapping the source error
                 EasyHdrError::ConfigError(Box::new(e))
             })?;
+
+            // Icons follow the first paint through state updates
+            self.controller_handle.lock().load_icons_in_background();
         }
 
         // Run the Slint event loop on the main thread
@@ -2,8 +2,12 @@
 //!
 //! Provides the Slint-based graphical user interface and system tray integration.
 //! Includes main window, settings panel, and state synchronization with the application controller.
+//! The main window follows the app mode and accent color of Windows personalization, and
+//! the application list can be used with the keyboard and screen readers.
 
+pub mod accessibility;
 pub mod gui_controller;
+pub mod theme;
 pub mod tray;
 
 pub use gui_controller::GuiController;
@@ -0,0 +1,250 @@
+//! Windows app mode and accent color
+//!
+//! The main window follows the "Choose your app mode" setting and the accent color of
+//! Windows personalization instead of a fixed palette. [`SystemTheme::current`] reads
+//! both from the registry and [`SystemTheme::apply`] hands them to the `Theme` global in
+//! `main.slint`, which the design tokens derive their colors from.
+//!
+//! [`watch`] keeps the window up to date while EasyHDR runs: a hidden window receives the
+//! `WM_SETTINGCHANGE` broadcast Windows sends when personalization changes, and a changed
+//! theme is applied on the Slint event loop.
+
+use crate::MainWindow;
+
+/// Registry key under `HKEY_CURRENT_USER` holding the app mode
+#[cfg(windows)]
+const PERSONALIZE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+
+/// DWORD value that is 0 while apps use the dark mode
+#[cfg(windows)]
+const APPS_USE_LIGHT_THEME: &str = "AppsUseLightTheme";
+
+/// Registry key under `HKEY_CURRENT_USER` holding the accent color
+#[cfg(windows)]
+const DWM_KEY: &str = r"Software\Microsoft\Windows\DWM";
+
+/// DWORD value holding the accent color as `0xAABBGGRR`
+#[cfg(windows)]
+const ACCENT_COLOR: &str = "AccentColor";
+
+/// Accent color used when Windows doesn't report one, EasyHDR's brand blue
+pub const DEFAULT_ACCENT: [u8; 3] = [0x4A, 0x90, 0xE2];
+
+/// App mode and accent color chosen in Windows personalization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemTheme {
+    /// Whether apps use the dark mode
+    pub dark: bool,
+    /// Accent color as RGB
+    pub accent: [u8; 3],
+}
+
+impl Default for SystemTheme {
+    fn default() -> Self {
+        Self {
+            dark: false,
+            accent: DEFAULT_ACCENT,
+        }
+    }
+}
+
+impl SystemTheme {
+    /// Read the app mode and accent color from the registry
+    ///
+    /// Settings that cannot be read keep their defaults, light mode and
+    /// [`DEFAULT_ACCENT`].
+    #[cfg(windows)]
+    pub fn current() -> Self {
+        use winreg::RegKey;
+        use winreg::enums::HKEY_CURRENT_USER;
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let read = |key: &str, value: &str| {
+            hkcu.open_subkey(key)
+                .and_then(|key| key.get_value::<u32, _>(value))
+                .ok()
+        };
+        let defaults = Self::default();
+        Self {
+            dark: read(PERSONALIZE_KEY, APPS_USE_LIGHT_THEME)
+                .map_or(defaults.dark, |light| light == 0),
+            accent: read(DWM_KEY, ACCENT_COLOR).map_or(defaults.accent, accent_from_dword),
+        }
+    }
+
+    /// Windows personalization only exists on Windows
+    #[cfg(not(windows))]
+    pub fn current() -> Self {
+        Self::default()
+    }
+
+    /// Show the theme in the main window
+    pub fn apply(self, window: &MainWindow) {
+        use slint::ComponentHandle;
+
+        let [red, green, blue] = self.accent;
+        let theme = window.global::<crate::Theme>();
+        theme.set_dark(self.dark);
+        theme.set_accent(slint::Color::from_rgb_u8(red, green, blue));
+    }
+}
+
+/// Accent color from the `AccentColor` registry value, stored as `0xAABBGGRR`
+pub fn accent_from_dword(value: u32) -> [u8; 3] {
+    let [red, green, blue, _alpha] = value.to_le_bytes();
+    [red, green, blue]
+}
+
+/// Keep the main window's theme in line with Windows personalization
+///
+/// Spawns a thread with a hidden window that rereads the theme whenever Windows
+/// broadcasts a settings or colorization change, and applies it to `window` when it
+/// changed.
+#[cfg(windows)]
+pub fn watch(window: slint::Weak<MainWindow>, initial: SystemTheme) {
+    use tracing::{error, info};
+
+    let spawned = std::thread::Builder::new()
+        .name("theme-watcher".to_string())
+        .spawn(move || {
+            WATCH_STATE.with(|cell| *cell.borrow_mut() = Some((window, initial)));
+            if let Err(e) = run_message_loop() {
+                error!("Theme watcher failed: {}", e);
+            }
+            info!("Theme watcher thread exited");
+        });
+    if let Err(e) = spawned {
+        error!("Failed to spawn the theme watcher thread: {}", e);
+    }
+}
+
+/// Personalization change broadcasts only exist on Windows
+#[cfg(not(windows))]
+pub fn watch(_window: slint::Weak<MainWindow>, _initial: SystemTheme) {}
+
+#[cfg(windows)]
+thread_local! {
+    /// Window to update and the theme it shows, for the window procedure
+    static WATCH_STATE: std::cell::RefCell<Option<(slint::Weak<MainWindow>, SystemTheme)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Create the hidden window and run its message loop until it is destroyed
+///
+/// # Safety
+///
+/// The class and window names are null-terminated wide strings that outlive the calls
+/// using them. `CreateWindowExW` errors are propagated. `GetMessageW` gets a valid stack
+/// `MSG` and its -1 error return ends the loop.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for hidden window creation and message loop via CreateWindowExW and GetMessageW"
+)]
+fn run_message_loop() -> easyhdr::error::Result<()> {
+    use easyhdr::error::EasyHdrError;
+    use tracing::debug;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DispatchMessageW, GetMessageW, MSG, RegisterClassW, WINDOW_EX_STYLE,
+        WNDCLASSW, WS_OVERLAPPEDWINDOW,
+    };
+    use windows::core::w;
+
+    let class_name = w!("EasyHDR_ThemeWatcher");
+    let wnd_class = WNDCLASSW {
+        lpfnWndProc: Some(window_proc),
+        lpszClassName: class_name,
+        ..Default::default()
+    };
+    // SAFETY: the class points at a valid window procedure and a static class name
+    if unsafe { RegisterClassW(&raw const wnd_class) } == 0 {
+        return Err(EasyHdrError::WindowsApiError(
+            windows::core::Error::from_thread(),
+        ));
+    }
+
+    // Not a message-only window, those don't receive broadcasts
+    // SAFETY: the class was registered above and the names are static
+    unsafe {
+        CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            class_name,
+            w!("EasyHDR Theme Watcher"),
+            WS_OVERLAPPEDWINDOW,
+            -32000,
+            -32000,
+            1,
+            1,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+    .map_err(EasyHdrError::WindowsApiError)?;
+    debug!("Created hidden window for theme changes");
+
+    let mut msg = MSG::default();
+    // SAFETY: `msg` is a valid MSG for every call
+    while unsafe { GetMessageW(&raw mut msg, None, 0, 0) }.0 > 0 {
+        // SAFETY: `msg` was filled in by GetMessageW
+        unsafe { DispatchMessageW(&raw const msg) };
+    }
+    Ok(())
+}
+
+/// Window procedure of the hidden window
+///
+/// # Safety
+///
+/// Signature matches the `WNDPROC` contract and Windows passes valid parameters. Other
+/// messages go to `DefWindowProcW` unchanged.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI callback for window procedure handling WM_SETTINGCHANGE and WM_DWMCOLORIZATIONCOLORCHANGED"
+)]
+unsafe extern "system" fn window_proc(
+    hwnd: windows::Win32::Foundation::HWND,
+    msg: u32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use tracing::info;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DefWindowProcW, WM_DWMCOLORIZATIONCOLORCHANGED, WM_SETTINGCHANGE,
+    };
+
+    if msg == WM_SETTINGCHANGE || msg == WM_DWMCOLORIZATIONCOLORCHANGED {
+        // Settings broadcasts are frequent and rarely about personalization, so only a
+        // changed theme reaches the window
+        let theme = SystemTheme::current();
+        WATCH_STATE.with(|cell| {
+            if let Some((window, shown)) = cell.borrow_mut().as_mut()
+                && *shown != theme
+            {
+                info!(
+                    "Windows theme changed to {} mode with accent {:02X?}",
+                    if theme.dark { "dark" } else { "light" },
+                    theme.accent
+                );
+                *shown = theme;
+                let _ = window.upgrade_in_event_loop(move |window| theme.apply(&window));
+            }
+        });
+    }
+    // SAFETY: the parameters come unchanged from Windows
+    unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accent_from_dword() {
+        // Windows' default blue accent, #0078D4, with full alpha
+        assert_eq!(accent_from_dword(0xFFD4_7800), [0x00, 0x78, 0xD4]);
+        assert_eq!(SystemTheme::default().accent, DEFAULT_ACCENT);
+    }
+}
@@ -2,21 +2,126 @@
 //!
 //! This module implements system tray icon and menu functionality using the `tray-icon` crate.
 //! The tray icon displays the current HDR state and provides quick access to the main window
-//! via a context menu with "Open", "Current HDR State", and "Exit" items.
+//! via a context menu with "Open", "Current HDR State", "Displays", "Log Level", and "Exit"
+//! items. "Displays" lists each HDR-capable display with a checkable "HDR" item that turns
+//! HDR on or off for that display alone.
 
+use easyhdr::controller::DisplayHdrState;
+#[cfg(windows)]
+use easyhdr::controller::HdrOverride;
 #[cfg(windows)]
 use easyhdr::error::{EasyHdrError, Result, StringError};
 #[cfg(windows)]
+use easyhdr::hdr::DisplayTarget;
+use easyhdr::utils::LogLevel;
+#[cfg(windows)]
+use easyhdr::utils::{tr, tr_args};
+#[cfg(windows)]
+use parking_lot::Mutex;
+#[cfg(windows)]
 use slint::{ComponentHandle, Weak};
 #[cfg(windows)]
+use std::sync::Arc;
+#[cfg(windows)]
 use tracing::error;
 
 #[cfg(windows)]
 use tray_icon::{
     Icon, MouseButton, TrayIconBuilder, TrayIconEvent,
-    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
 };
 
+/// Toast action argument for the "Keep HDR on" button
+#[cfg(windows)]
+const ACTION_KEEP_HDR_ON: &str = "keep-hdr-on";
+
+/// Toast action argument for the "Turn off now" button
+#[cfg(windows)]
+const ACTION_TURN_HDR_OFF: &str = "turn-hdr-off";
+
+/// Toast action argument for the "Snooze for this app" button
+#[cfg(windows)]
+const ACTION_SNOOZE_APP: &str = "snooze-app";
+
+/// Toast action argument for the "Keep on 1 more hour" button
+#[cfg(windows)]
+const ACTION_EXTEND_SESSION: &str = "extend-hdr-session";
+
+/// Toast action argument for the "Resume automatic HDR" button
+#[cfg(windows)]
+const ACTION_RESUME_AUTOMATION: &str = "resume-automatic-hdr";
+
+/// Toast action argument for the "Start pixel refresh" button
+#[cfg(windows)]
+const ACTION_START_PIXEL_REFRESH: &str = "start-pixel-refresh";
+
+/// HDR state shown by the tray icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayHdrState {
+    /// HDR is off on every display
+    Off,
+    /// HDR is on on every HDR-capable display
+    On,
+    /// HDR is on on some HDR-capable displays and off on others
+    Mixed,
+}
+
+impl TrayHdrState {
+    /// Derive the icon state from the overall HDR state and the per-display states
+    pub fn from_displays(hdr_enabled: bool, displays: &[DisplayHdrState]) -> Self {
+        let any_on = displays.iter().any(|display| display.hdr_enabled);
+        let any_off = displays.iter().any(|display| !display.hdr_enabled);
+        if any_on && any_off {
+            Self::Mixed
+        } else if hdr_enabled {
+            Self::On
+        } else {
+            Self::Off
+        }
+    }
+
+    /// Label used in the status menu item
+    #[cfg_attr(
+        not(windows),
+        expect(dead_code, reason = "Only the Windows tray shows the state")
+    )]
+    fn label(self) -> String {
+        easyhdr::utils::tr(match self {
+            Self::Off => "tray-state-off",
+            Self::On => "tray-state-on",
+            Self::Mixed => "tray-state-mixed",
+        })
+    }
+}
+
+/// Combine the HDR ON and OFF icons along the diagonal, ON in the upper-left half
+#[cfg(windows)]
+fn mix_icons(mut on: image::RgbaImage, off: &image::RgbaImage) -> image::RgbaImage {
+    if on.dimensions() != off.dimensions() {
+        return on;
+    }
+    let width = on.width();
+    for (x, y, pixel) in on.enumerate_pixels_mut() {
+        if x + y >= width {
+            *pixel = *off.get_pixel(x, y);
+        }
+    }
+    on
+}
+
+/// Entry of the "Displays" submenu
+#[cfg(windows)]
+struct DisplayMenuEntry {
+    /// Submenu named after the display
+    submenu: Submenu,
+    /// Checkable "HDR" item inside the submenu
+    hdr_item: CheckMenuItem,
+}
+
+/// Display behind each "HDR" item and whether HDR was on when the menu was last updated
+#[cfg(windows)]
+type DisplayItemIds = Arc<Mutex<Vec<(tray_icon::menu::MenuId, DisplayTarget, bool)>>>;
+
 /// System tray icon with context menu showing HDR state.
 #[cfg(windows)]
 pub struct TrayIcon {
@@ -30,6 +135,16 @@ pub struct TrayIcon {
     exit_item_id: tray_icon::menu::MenuId,
     /// Reference to the status menu item. Updated via `update_icon()` to reflect current HDR state.
     status_item: MenuItem,
+    /// "Log Level" submenu entries. Updated via `update_log_level()` to check the active level.
+    log_level_items: Vec<(LogLevel, CheckMenuItem)>,
+    /// State the icon currently shows, `None` before the first update
+    icon_state: Option<TrayHdrState>,
+    /// "Displays" submenu. Rebuilt via `update_displays()` when displays come and go.
+    displays_menu: Submenu,
+    /// Entries of the "Displays" submenu, in the order of `display_item_ids`
+    display_entries: Vec<DisplayMenuEntry>,
+    /// Shared with the menu event handler to map clicks to displays
+    display_item_ids: DisplayItemIds,
 }
 
 /// Placeholder for non-Windows platforms
@@ -38,8 +153,25 @@ pub struct TrayIcon;
 
 #[cfg(windows)]
 impl TrayIcon {
-    /// Creates a new tray icon with a context menu containing "Open", HDR status, and "Exit" items.
-    pub fn new(window: &crate::MainWindow) -> Result<Self> {
+    /// Creates a new tray icon with a context menu containing "Open", HDR status, "Displays",
+    /// "Log Level", and "Exit" items.
+    ///
+    /// `on_display_hdr` is called with the display and the requested state when an "HDR"
+    /// item of the "Displays" submenu is clicked, `on_log_level` when a level is picked
+    /// from the "Log Level" submenu and `on_exit` when "Exit" is clicked; `on_exit` is
+    /// expected to end the process.
+    pub fn new<D, F, E>(
+        window: &crate::MainWindow,
+        log_level: LogLevel,
+        on_display_hdr: D,
+        on_log_level: F,
+        on_exit: E,
+    ) -> Result<Self>
+    where
+        D: Fn(DisplayTarget, bool) + Send + Sync + 'static,
+        F: Fn(LogLevel) + Send + Sync + 'static,
+        E: Fn() + Send + Sync + 'static,
+    {
         use tracing::{debug, info};
 
         info!("Creating system tray icon");
@@ -49,16 +181,41 @@ impl TrayIcon {
 
         // Create menu items
         // "Open" - Restores the main window
-        let open_item = MenuItem::new("Open", true, None);
+        let open_item = MenuItem::new(tr("tray-open"), true, None);
 
         // "Current HDR State: OFF" - Info item showing HDR status (disabled)
-        let status_item = MenuItem::new("Current HDR State: OFF", false, None);
+        let status_item = MenuItem::new(
+            tr_args("tray-hdr-state", &[("state", &TrayHdrState::Off.label())]),
+            false,
+            None,
+        );
+
+        // "Displays" - Per-display HDR toggles, filled in by update_displays()
+        let displays_menu = Submenu::new(tr("tray-displays"), false);
 
         // Separator
         let separator = PredefinedMenuItem::separator();
 
+        // "Log Level" - Changes logging verbosity without restarting
+        let log_level_menu = Submenu::new(tr("tray-log-level"), true);
+        let log_level_items: Vec<(LogLevel, CheckMenuItem)> = LogLevel::ALL
+            .into_iter()
+            .map(|level| {
+                let item = CheckMenuItem::new(level.label(), true, level == log_level, None);
+                (level, item)
+            })
+            .collect();
+        for (_, item) in &log_level_items {
+            log_level_menu.append(item).map_err(|e| {
+                error!("Failed to add log level menu item to tray: {}", e);
+                EasyHdrError::ConfigError(StringError::new(format!(
+                    "Failed to add log level menu item: {e}"
+                )))
+            })?;
+        }
+
         // "Exit" - Exits the application
-        let exit_item = MenuItem::new("Exit", true, None);
+        let exit_item = MenuItem::new(tr("tray-exit"), true, None);
 
         // Append items to menu
         tray_menu.append(&open_item).map_err(|e| {
@@ -75,11 +232,25 @@ impl TrayIcon {
             )))
         })?;
 
+        tray_menu.append(&displays_menu).map_err(|e| {
+            error!("Failed to add Displays submenu to tray: {}", e);
+            EasyHdrError::ConfigError(StringError::new(format!(
+                "Failed to add Displays submenu: {e}"
+            )))
+        })?;
+
         tray_menu.append(&separator).map_err(|e| {
             error!("Failed to add separator to tray menu: {}", e);
             EasyHdrError::ConfigError(StringError::new(format!("Failed to add separator: {e}")))
         })?;
 
+        tray_menu.append(&log_level_menu).map_err(|e| {
+            error!("Failed to add Log Level submenu to tray: {}", e);
+            EasyHdrError::ConfigError(StringError::new(format!(
+                "Failed to add Log Level submenu: {e}"
+            )))
+        })?;
+
         tray_menu.append(&exit_item).map_err(|e| {
             error!("Failed to add Exit menu item to tray: {}", e);
             EasyHdrError::ConfigError(StringError::new(format!(
@@ -87,10 +258,10 @@ impl TrayIcon {
             )))
         })?;
 
-        debug!("Tray menu created with 4 items");
+        debug!("Tray menu created with 6 items");
 
         // Load the initial tray icon (HDR OFF state)
-        let icon = Self::load_tray_icon(false)?;
+        let icon = Self::load_tray_icon(TrayHdrState::Off)?;
 
         // Build the tray icon
         let tray = TrayIconBuilder::new()
@@ -118,10 +289,15 @@ impl TrayIcon {
             open_item_id,
             exit_item_id,
             status_item,
+            log_level_items,
+            icon_state: None,
+            displays_menu,
+            display_entries: Vec::new(),
+            display_item_ids: Arc::new(Mutex::new(Vec::new())),
         };
 
         // Set up MenuEvent handler for menu item clicks
-        tray_icon.setup_menu_event_handler();
+        tray_icon.setup_menu_event_handler(on_display_hdr, on_log_level, on_exit);
 
         // Set up TrayIconEvent handler for tray icon clicks (left-click to restore window)
         tray_icon.setup_tray_icon_event_handler();
@@ -130,45 +306,29 @@ impl TrayIcon {
     }
 
     /// Loads the tray icon from embedded assets. Uses `icon_hdr_on.ico` when HDR is enabled,
-    /// `icon_hdr_off.ico` when disabled. Falls back to a generated icon if loading fails.
-    fn load_tray_icon(hdr_enabled: bool) -> Result<Icon> {
-        use image::ImageReader;
-        use std::io::Cursor;
+    /// `icon_hdr_off.ico` when disabled, and a diagonal split of both when only some
+    /// displays have HDR on. Falls back to a generated icon if loading fails.
+    fn load_tray_icon(state: TrayHdrState) -> Result<Icon> {
         use tracing::{debug, warn};
 
         // Embed icon files at compile time
         const ICON_HDR_ON: &[u8] = include_bytes!("../../assets/icon_hdr_on.ico");
         const ICON_HDR_OFF: &[u8] = include_bytes!("../../assets/icon_hdr_off.ico");
 
-        let icon_data = if hdr_enabled {
-            ICON_HDR_ON
-        } else {
-            ICON_HDR_OFF
-        };
-
         debug!(
             "Loading tray icon from embedded assets (HDR: {})",
-            if hdr_enabled { "ON" } else { "OFF" }
+            state.label()
         );
 
-        // Decode the ICO file using the image crate
-        match ImageReader::new(Cursor::new(icon_data))
-            .with_guessed_format()
-            .map_err(|e| {
-                EasyHdrError::ConfigError(StringError::new(format!(
-                    "Failed to guess icon format: {e}"
-                )))
-            })
-            .and_then(|reader| {
-                reader.decode().map_err(|e| {
-                    EasyHdrError::ConfigError(StringError::new(format!(
-                        "Failed to decode icon: {e}"
-                    )))
-                })
-            }) {
-            Ok(img) => {
-                // Convert to RGBA8
-                let rgba_img = img.to_rgba8();
+        let decoded = match state {
+            TrayHdrState::On => Self::decode_icon(ICON_HDR_ON),
+            TrayHdrState::Off => Self::decode_icon(ICON_HDR_OFF),
+            TrayHdrState::Mixed => Self::decode_icon(ICON_HDR_ON)
+                .and_then(|on| Self::decode_icon(ICON_HDR_OFF).map(|off| mix_icons(on, &off))),
+        };
+
+        match decoded {
+            Ok(rgba_img) => {
                 let (width, height) = rgba_img.dimensions();
                 let rgba_data = rgba_img.into_raw();
 
@@ -192,23 +352,46 @@ impl TrayIcon {
                     "Failed to decode icon from embedded assets: {}, falling back to generated icon",
                     e
                 );
-                Self::create_fallback_icon(hdr_enabled)
+                Self::create_fallback_icon(state)
             }
         }
     }
 
-    /// Creates a simple 32x32 fallback icon (green for HDR ON, red for HDR OFF).
-    fn create_fallback_icon(hdr_enabled: bool) -> Result<Icon> {
+    /// Decodes an embedded ICO file to RGBA8 using the image crate.
+    fn decode_icon(icon_data: &[u8]) -> Result<image::RgbaImage> {
+        use image::ImageReader;
+        use std::io::Cursor;
+
+        ImageReader::new(Cursor::new(icon_data))
+            .with_guessed_format()
+            .map_err(|e| {
+                EasyHdrError::ConfigError(StringError::new(format!(
+                    "Failed to guess icon format: {e}"
+                )))
+            })
+            .and_then(|reader| {
+                reader.decode().map_err(|e| {
+                    EasyHdrError::ConfigError(StringError::new(format!(
+                        "Failed to decode icon: {e}"
+                    )))
+                })
+            })
+            .map(|img| img.to_rgba8())
+    }
+
+    /// Creates a simple 32x32 fallback icon (green for HDR ON, red for HDR OFF, amber
+    /// when only some displays have HDR on).
+    fn create_fallback_icon(state: TrayHdrState) -> Result<Icon> {
         use tracing::debug;
 
         const ICON_SIZE: usize = 32;
         let mut rgba = vec![0u8; ICON_SIZE * ICON_SIZE * 4];
 
         // Choose color based on HDR state
-        let (r, g, b) = if hdr_enabled {
-            (0_u8, 204_u8, 0_u8) // Green for HDR ON
-        } else {
-            (204_u8, 0_u8, 0_u8) // Red for HDR OFF
+        let (r, g, b) = match state {
+            TrayHdrState::On => (0_u8, 204_u8, 0_u8), // Green for HDR ON
+            TrayHdrState::Off => (204_u8, 0_u8, 0_u8), // Red for HDR OFF
+            TrayHdrState::Mixed => (204_u8, 153_u8, 0_u8), // Amber for mixed
         };
 
         // Fill the icon with the chosen color
@@ -233,10 +416,7 @@ impl TrayIcon {
             }
         }
 
-        debug!(
-            "Created fallback tray icon (HDR: {})",
-            if hdr_enabled { "ON" } else { "OFF" }
-        );
+        debug!("Created fallback tray icon (HDR: {})", state.label());
 
         #[expect(
             clippy::cast_possible_truncation,
@@ -250,9 +430,14 @@ impl TrayIcon {
         })
     }
 
-    /// Sets up the menu event handler to process "Open" and "Exit" clicks.
-    /// Uses a weak reference to avoid keeping the window alive unnecessarily.
-    fn setup_menu_event_handler(&self) {
+    /// Sets up the menu event handler to process "Open", "Displays", "Log Level", and "Exit"
+    /// clicks. Uses a weak reference to avoid keeping the window alive unnecessarily.
+    fn setup_menu_event_handler<D, F, E>(&self, on_display_hdr: D, on_log_level: F, on_exit: E)
+    where
+        D: Fn(DisplayTarget, bool) + Send + Sync + 'static,
+        F: Fn(LogLevel) + Send + Sync + 'static,
+        E: Fn() + Send + Sync + 'static,
+    {
         use tracing::{info, warn};
 
         info!("Setting up menu event handler");
@@ -260,6 +445,12 @@ impl TrayIcon {
         // Clone the IDs and window handle for the event handler closure
         let open_item_id = self.open_item_id.clone();
         let exit_item_id = self.exit_item_id.clone();
+        let log_level_item_ids: Vec<(tray_icon::menu::MenuId, LogLevel)> = self
+            .log_level_items
+            .iter()
+            .map(|(level, item)| (item.id().clone(), *level))
+            .collect();
+        let display_item_ids = Arc::clone(&self.display_item_ids);
         let window_weak = self.window_handle.clone();
 
         // Set up the MenuEvent handler
@@ -291,17 +482,46 @@ impl TrayIcon {
             else if event.id == exit_item_id {
                 info!("Exit menu item clicked - exiting application");
 
-                // Exit immediately using std::process::exit because background threads
-                // run infinite loops with no shutdown signal. Configuration is automatically
-                // saved by AppController, so no data loss occurs.
-                info!("Exiting application");
-                std::process::exit(0);
+                // Runs the shutdown sequence and exits the process
+                on_exit();
+            }
+            // Handle "Log Level" submenu clicks
+            else if let Some((_, level)) =
+                log_level_item_ids.iter().find(|(id, _)| *id == event.id)
+            {
+                info!("Log level {} selected from tray menu", level.label());
+                on_log_level(*level);
+            }
+            // Handle "HDR" clicks in the "Displays" submenu
+            else if let Some((target, hdr_enabled)) =
+                Self::find_display_item(&display_item_ids, &event.id)
+            {
+                info!(
+                    "HDR {} requested for {} from tray menu",
+                    if hdr_enabled { "off" } else { "on" },
+                    target.display_name()
+                );
+                on_display_hdr(target, !hdr_enabled);
             }
         }));
 
         info!("Menu event handler set up successfully");
     }
 
+    /// Looks up the display behind a clicked "HDR" item and whether its HDR was on.
+    ///
+    /// Releases the lock before returning so the click handler can change HDR state.
+    fn find_display_item(
+        display_item_ids: &DisplayItemIds,
+        id: &tray_icon::menu::MenuId,
+    ) -> Option<(DisplayTarget, bool)> {
+        display_item_ids
+            .lock()
+            .iter()
+            .find(|(item_id, ..)| item_id == id)
+            .map(|(_, target, hdr_enabled)| (target.clone(), *hdr_enabled))
+    }
+
     /// Sets up the tray icon event handler to restore the window on left-click.
     /// Uses a weak reference to avoid keeping the window alive unnecessarily.
     fn setup_tray_icon_event_handler(&self) {
@@ -349,15 +569,18 @@ impl TrayIcon {
     }
 
     /// Updates the tray icon and menu item text to reflect the current HDR state.
-    pub fn update_icon(&mut self, hdr_enabled: bool) {
+    ///
+    /// Does nothing when the icon already shows `state`.
+    pub fn update_icon(&mut self, state: TrayHdrState) {
         use tracing::{info, warn};
 
-        info!(
-            "Updating tray icon: HDR {}",
-            if hdr_enabled { "ON" } else { "OFF" }
-        );
+        if self.icon_state.replace(state) == Some(state) {
+            return;
+        }
 
-        match Self::load_tray_icon(hdr_enabled) {
+        info!("Updating tray icon: HDR {}", state.label());
+
+        match Self::load_tray_icon(state) {
             Ok(icon) => {
                 // Update the tray icon
                 if let Err(e) = self.tray.set_icon(Some(icon)) {
@@ -371,16 +594,77 @@ impl TrayIcon {
             }
         }
 
-        let status_text = if hdr_enabled {
-            "Current HDR State: ON"
-        } else {
-            "Current HDR State: OFF"
-        };
-
-        self.status_item.set_text(status_text);
+        let status_text = tr_args("tray-hdr-state", &[("state", &state.label())]);
+        self.status_item.set_text(&status_text);
         info!("Status menu item updated to: {}", status_text);
     }
 
+    /// Lists the HDR-capable displays in the "Displays" submenu and checks the "HDR" item
+    /// of those with HDR on.
+    ///
+    /// The submenu is only rebuilt when displays are connected or disconnected.
+    pub fn update_displays(&mut self, displays: &[DisplayHdrState]) {
+        use tracing::{debug, warn};
+
+        let mut item_ids = self.display_item_ids.lock();
+        let same_displays = item_ids.len() == displays.len()
+            && item_ids
+                .iter()
+                .zip(displays)
+                .all(|((_, target, _), display)| *target == display.target);
+
+        if same_displays {
+            for ((_, _, hdr_enabled), (entry, display)) in item_ids
+                .iter_mut()
+                .zip(self.display_entries.iter().zip(displays))
+            {
+                *hdr_enabled = display.hdr_enabled;
+                entry.hdr_item.set_checked(display.hdr_enabled);
+            }
+            return;
+        }
+
+        debug!(
+            "Rebuilding tray Displays submenu with {} displays",
+            displays.len()
+        );
+        for entry in self.display_entries.drain(..) {
+            if let Err(e) = self.displays_menu.remove(&entry.submenu) {
+                warn!("Failed to remove display from tray menu: {}", e);
+            }
+        }
+        item_ids.clear();
+
+        for display in displays {
+            let submenu = Submenu::new(display.target.display_name(), true);
+            let hdr_item =
+                CheckMenuItem::new(tr("tray-display-hdr"), true, display.hdr_enabled, None);
+            if let Err(e) = submenu
+                .append(&hdr_item)
+                .and_then(|()| self.displays_menu.append(&submenu))
+            {
+                warn!("Failed to add display to tray menu: {}", e);
+                continue;
+            }
+            item_ids.push((
+                hdr_item.id().clone(),
+                display.target.clone(),
+                display.hdr_enabled,
+            ));
+            self.display_entries
+                .push(DisplayMenuEntry { submenu, hdr_item });
+        }
+        self.displays_menu
+            .set_enabled(!self.display_entries.is_empty());
+    }
+
+    /// Checks the active level in the "Log Level" submenu.
+    pub fn update_log_level(&self, log_level: LogLevel) {
+        for (level, item) in &self.log_level_items {
+            item.set_checked(*level == log_level);
+        }
+    }
+
     /// Displays a Windows toast notification (respects user's notification preference).
     #[expect(
         clippy::unused_self,
@@ -422,6 +706,213 @@ impl TrayIcon {
             debug!("Notification would be shown on Windows: {}", message);
         }
     }
+
+    /// Displays a toast notification for an automatic HDR toggle with "Keep HDR on"
+    /// and "Turn off now" action buttons.
+    ///
+    /// `on_override` is called from the notification's activation thread with the
+    /// override selected by the user; clicking the toast body does nothing. A "Snooze for
+    /// this app" button is added when `on_snooze` is set, which is called when it is
+    /// clicked.
+    #[expect(
+        clippy::unused_self,
+        reason = "Method signature matches show_notification for a consistent tray API"
+    )]
+    pub fn show_hdr_toggle_notification<F, S>(
+        &self,
+        message: &str,
+        on_override: F,
+        on_snooze: Option<S>,
+    ) where
+        F: Fn(HdrOverride) + Send + 'static,
+        S: Fn() + Send + 'static,
+    {
+        use tauri_winrt_notification::{Duration, Sound, Toast};
+        use tracing::{debug, info, warn};
+
+        info!("Showing HDR toggle notification: {}", message);
+
+        let mut toast = Toast::new(Toast::POWERSHELL_APP_ID)
+            .title("EasyHDR")
+            .text1(message)
+            .duration(Duration::Short)
+            .sound(Some(Sound::Default))
+            .add_button(&tr("toast-keep-hdr-on"), ACTION_KEEP_HDR_ON)
+            .add_button(&tr("toast-turn-off-now"), ACTION_TURN_HDR_OFF);
+        if on_snooze.is_some() {
+            toast = toast.add_button(&tr("toast-snooze-app"), ACTION_SNOOZE_APP);
+        }
+        let result = toast
+            .on_activated(move |action| {
+                if action.as_deref() == Some(ACTION_SNOOZE_APP) {
+                    if let Some(on_snooze) = &on_snooze {
+                        info!("Application snoozed from notification");
+                        on_snooze();
+                    }
+                    return Ok(());
+                }
+                match Self::parse_toast_action(action.as_deref()) {
+                    Some(hdr_override) => {
+                        info!(
+                            "HDR override selected from notification: {:?}",
+                            hdr_override
+                        );
+                        on_override(hdr_override);
+                    }
+                    None => debug!("Notification activated without an override action"),
+                }
+                Ok(())
+            })
+            .show();
+
+        match result {
+            Ok(()) => {
+                info!("Notification shown successfully");
+            }
+            Err(e) => {
+                warn!("Failed to show notification: {}", e);
+                debug!("Notification error details: {:?}", e);
+            }
+        }
+    }
+
+    /// Displays a toast notification warning that the HDR session limit is about to turn
+    /// HDR off, with a "Keep on 1 more hour" action button.
+    ///
+    /// `on_extend` is called from the notification's activation thread when the button
+    /// is clicked; clicking the toast body does nothing.
+    #[expect(
+        clippy::unused_self,
+        reason = "Method signature matches show_notification for a consistent tray API"
+    )]
+    pub fn show_session_limit_notification<F>(&self, message: &str, on_extend: F)
+    where
+        F: Fn() + Send + 'static,
+    {
+        use tauri_winrt_notification::{Duration, Sound, Toast};
+        use tracing::{debug, info, warn};
+
+        info!("Showing HDR session limit notification: {}", message);
+
+        let result = Toast::new(Toast::POWERSHELL_APP_ID)
+            .title("EasyHDR")
+            .text1(message)
+            .duration(Duration::Long)
+            .sound(Some(Sound::Default))
+            .add_button(&tr("toast-extend-session"), ACTION_EXTEND_SESSION)
+            .on_activated(move |action| {
+                if action.as_deref() == Some(ACTION_EXTEND_SESSION) {
+                    info!("HDR session extended from notification");
+                    on_extend();
+                } else {
+                    debug!("Notification activated without an extend action");
+                }
+                Ok(())
+            })
+            .show();
+
+        if let Err(e) = result {
+            warn!("Failed to show notification: {}", e);
+            debug!("Notification error details: {:?}", e);
+        }
+    }
+
+    /// Displays a toast notification that automatic HDR management paused because HDR
+    /// keeps changing outside EasyHDR, with a "Resume automatic HDR" action button.
+    ///
+    /// `on_resume` is called from the notification's activation thread when the button
+    /// is clicked; clicking the toast body does nothing.
+    #[expect(
+        clippy::unused_self,
+        reason = "Method signature matches show_notification for a consistent tray API"
+    )]
+    pub fn show_hdr_conflict_notification<F>(&self, message: &str, on_resume: F)
+    where
+        F: Fn() + Send + 'static,
+    {
+        use tauri_winrt_notification::{Duration, Sound, Toast};
+        use tracing::{debug, info, warn};
+
+        info!("Showing HDR conflict notification: {}", message);
+
+        let result = Toast::new(Toast::POWERSHELL_APP_ID)
+            .title("EasyHDR")
+            .text1(message)
+            .duration(Duration::Long)
+            .sound(Some(Sound::Default))
+            .add_button(&tr("toast-resume-automation"), ACTION_RESUME_AUTOMATION)
+            .on_activated(move |action| {
+                if action.as_deref() == Some(ACTION_RESUME_AUTOMATION) {
+                    info!("Automatic HDR management resumed from notification");
+                    on_resume();
+                } else {
+                    debug!("Notification activated without a resume action");
+                }
+                Ok(())
+            })
+            .show();
+
+        if let Err(e) = result {
+            warn!("Failed to show notification: {}", e);
+            debug!("Notification error details: {:?}", e);
+        }
+    }
+
+    /// Displays a toast notification reminding the user to run OLED panel maintenance.
+    ///
+    /// Adds a "Start pixel refresh" action button when `can_start_pixel_refresh` is set;
+    /// `on_refresh` is called from the notification's activation thread when it is clicked.
+    #[expect(
+        clippy::unused_self,
+        reason = "Method signature matches show_notification for a consistent tray API"
+    )]
+    pub fn show_oled_care_notification<F>(
+        &self,
+        message: &str,
+        can_start_pixel_refresh: bool,
+        on_refresh: F,
+    ) where
+        F: Fn() + Send + 'static,
+    {
+        use tauri_winrt_notification::{Duration, Sound, Toast};
+        use tracing::{debug, info, warn};
+
+        info!("Showing OLED care notification: {}", message);
+
+        let mut toast = Toast::new(Toast::POWERSHELL_APP_ID)
+            .title("EasyHDR")
+            .text1(message)
+            .duration(Duration::Long)
+            .sound(Some(Sound::Default));
+        if can_start_pixel_refresh {
+            toast = toast.add_button(&tr("toast-start-pixel-refresh"), ACTION_START_PIXEL_REFRESH);
+        }
+        let result = toast
+            .on_activated(move |action| {
+                if action.as_deref() == Some(ACTION_START_PIXEL_REFRESH) {
+                    info!("Pixel refresh requested from notification");
+                    on_refresh();
+                } else {
+                    debug!("Notification activated without a pixel refresh action");
+                }
+                Ok(())
+            })
+            .show();
+
+        if let Err(e) = result {
+            warn!("Failed to show notification: {}", e);
+            debug!("Notification error details: {:?}", e);
+        }
+    }
+
+    /// Maps a toast activation argument to the override it requests.
+    fn parse_toast_action(action: Option<&str>) -> Option<HdrOverride> {
+        match action? {
+            ACTION_KEEP_HDR_ON => Some(HdrOverride::KeepOn),
+            ACTION_TURN_HDR_OFF => Some(HdrOverride::TurnOff),
+            _ => None,
+        }
+    }
 }
 
 /// Stub implementation for non-Windows platforms
@@ -435,7 +926,18 @@ impl TrayIcon {
         clippy::unnecessary_wraps,
         reason = "Non-Windows stub maintains API compatibility"
     )]
-    pub fn new(_window: &crate::MainWindow) -> easyhdr::error::Result<Self> {
+    pub fn new<D, F, E>(
+        _window: &crate::MainWindow,
+        _log_level: LogLevel,
+        _on_display_hdr: D,
+        _on_log_level: F,
+        _on_exit: E,
+    ) -> easyhdr::error::Result<Self>
+    where
+        D: Fn(easyhdr::hdr::DisplayTarget, bool) + Send + Sync + 'static,
+        F: Fn(LogLevel) + Send + Sync + 'static,
+        E: Fn() + Send + Sync + 'static,
+    {
         Ok(Self)
     }
 
@@ -444,7 +946,21 @@ impl TrayIcon {
         clippy::unused_self,
         reason = "Non-Windows stub maintains API compatibility"
     )]
-    pub fn update_icon(&mut self, _hdr_enabled: bool) {}
+    pub fn update_icon(&mut self, _state: TrayHdrState) {}
+
+    #[expect(
+        dead_code,
+        clippy::unused_self,
+        reason = "Non-Windows stub maintains API compatibility"
+    )]
+    pub fn update_displays(&mut self, _displays: &[DisplayHdrState]) {}
+
+    #[expect(
+        dead_code,
+        clippy::unused_self,
+        reason = "Non-Windows stub maintains API compatibility"
+    )]
+    pub fn update_log_level(&self, _log_level: LogLevel) {}
 
     #[expect(
         dead_code,
@@ -452,6 +968,59 @@ impl TrayIcon {
         reason = "Non-Windows stub maintains API compatibility"
     )]
     pub fn show_notification(&self, _message: &str) {}
+
+    #[expect(
+        dead_code,
+        clippy::unused_self,
+        reason = "Non-Windows stub maintains API compatibility"
+    )]
+    pub fn show_hdr_toggle_notification<F, S>(
+        &self,
+        _message: &str,
+        _on_override: F,
+        _on_snooze: Option<S>,
+    ) where
+        F: Fn(easyhdr::controller::HdrOverride) + Send + 'static,
+        S: Fn() + Send + 'static,
+    {
+    }
+
+    #[expect(
+        dead_code,
+        clippy::unused_self,
+        reason = "Non-Windows stub maintains API compatibility"
+    )]
+    pub fn show_session_limit_notification<F>(&self, _message: &str, _on_extend: F)
+    where
+        F: Fn() + Send + 'static,
+    {
+    }
+
+    #[expect(
+        dead_code,
+        clippy::unused_self,
+        reason = "Non-Windows stub maintains API compatibility"
+    )]
+    pub fn show_hdr_conflict_notification<F>(&self, _message: &str, _on_resume: F)
+    where
+        F: Fn() + Send + 'static,
+    {
+    }
+
+    #[expect(
+        dead_code,
+        clippy::unused_self,
+        reason = "Non-Windows stub maintains API compatibility"
+    )]
+    pub fn show_oled_care_notification<F>(
+        &self,
+        _message: &str,
+        _can_start_pixel_refresh: bool,
+        _on_refresh: F,
+    ) where
+        F: Fn() + Send + 'static,
+    {
+    }
 }
 
 #[cfg(test)]
@@ -500,6 +1069,69 @@ mod tests {
         // This test just ensures the method exists and can be called
     }
 
+    #[test]
+    #[cfg(windows)]
+    fn test_parse_toast_action() {
+        use super::{ACTION_KEEP_HDR_ON, ACTION_TURN_HDR_OFF, TrayIcon};
+        use easyhdr::controller::HdrOverride;
+
+        assert_eq!(
+            TrayIcon::parse_toast_action(Some(ACTION_KEEP_HDR_ON)),
+            Some(HdrOverride::KeepOn)
+        );
+        assert_eq!(
+            TrayIcon::parse_toast_action(Some(ACTION_TURN_HDR_OFF)),
+            Some(HdrOverride::TurnOff)
+        );
+        assert_eq!(TrayIcon::parse_toast_action(Some("")), None);
+        assert_eq!(TrayIcon::parse_toast_action(None), None);
+    }
+
+    #[test]
+    fn test_tray_hdr_state_from_displays() {
+        use super::TrayHdrState;
+        use easyhdr::controller::DisplayHdrState;
+        use easyhdr::hdr::DisplayTarget;
+
+        let display = |target_id, hdr_enabled| DisplayHdrState {
+            target: DisplayTarget {
+                target_id,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+            hdr_enabled,
+        };
+
+        assert_eq!(TrayHdrState::from_displays(false, &[]), TrayHdrState::Off);
+        assert_eq!(TrayHdrState::from_displays(true, &[]), TrayHdrState::On);
+        assert_eq!(
+            TrayHdrState::from_displays(true, &[display(1, true), display(2, true)]),
+            TrayHdrState::On
+        );
+        assert_eq!(
+            TrayHdrState::from_displays(true, &[display(1, true), display(2, false)]),
+            TrayHdrState::Mixed
+        );
+        assert_eq!(
+            TrayHdrState::from_displays(false, &[display(1, false)]),
+            TrayHdrState::Off
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_mix_icons() {
+        use super::mix_icons;
+        use image::{Rgba, RgbaImage};
+
+        let on = RgbaImage::from_pixel(4, 4, Rgba([0, 255, 0, 255]));
+        let off = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+        let mixed = mix_icons(on, &off);
+        assert_eq!(mixed.get_pixel(0, 0), &Rgba([0, 255, 0, 255]));
+        assert_eq!(mixed.get_pixel(3, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(mixed.get_pixel(3, 3), &Rgba([255, 0, 0, 255]));
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn test_show_notification_stub() {
@@ -0,0 +1,231 @@
+//! Raw Display Configuration API tracing
+//!
+//! An advanced diagnostic switch for maintainers. When the `trace_hdr_api` preference
+//! is set or `EasyHDR` is started with `--trace-hdr-api`, every
+//! `DisplayConfigGetDeviceInfo` and `DisplayConfigSetDeviceInfo` call is written to
+//! `hdr_api_trace.log` next to the configuration: the packet type, adapter and target,
+//! the return code and the raw packet bytes in hex, before and after the call. Users
+//! with a new Windows build or an unusual monitor can send that file instead of running
+//! a custom build.
+//!
+//! The file is rotated to `hdr_api_trace.1.log` once it grows past [`MAX_TRACE_BYTES`].
+
+use crate::config::ConfigManager;
+use crate::hdr::windows_api::DISPLAYCONFIG_DEVICE_INFO_HEADER;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Size after which the trace file is rotated
+pub const MAX_TRACE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Whether calls are traced
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Serializes writes so entries from different threads don't interleave
+static WRITE_LOCK: parking_lot::Mutex<()> = parking_lot::Mutex::new(());
+
+/// Turn tracing of the Display Configuration API on or off
+pub fn set_enabled(enabled: bool) {
+    use tracing::info;
+
+    if ENABLED.swap(enabled, Ordering::SeqCst) != enabled {
+        info!(
+            "HDR API tracing {}",
+            if enabled {
+                format!("enabled, writing to {}", get_trace_path().display())
+            } else {
+                "disabled".to_string()
+            }
+        );
+    }
+}
+
+/// Whether Display Configuration API calls are traced
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Path of the trace file, next to the configuration file
+pub fn get_trace_path() -> PathBuf {
+    ConfigManager::get_config_path().with_file_name("hdr_api_trace.log")
+}
+
+/// `DisplayConfigGetDeviceInfo`, traced when enabled
+///
+/// # Safety
+///
+/// Same contract as `DisplayConfigGetDeviceInfo`: `packet` points to an initialized
+/// packet whose header `size` matches the allocation.
+#[expect(unsafe_code, reason = "Wraps the DisplayConfigGetDeviceInfo FFI call")]
+pub unsafe fn display_config_get_device_info(packet: *mut DISPLAYCONFIG_DEVICE_INFO_HEADER) -> i32 {
+    use crate::hdr::windows_api::DisplayConfigGetDeviceInfo;
+
+    if !is_enabled() {
+        // SAFETY: forwarded from the caller
+        return unsafe { DisplayConfigGetDeviceInfo(packet) };
+    }
+
+    // SAFETY: the caller guarantees `packet` covers `size` initialized bytes
+    let request = unsafe { packet_bytes(packet) };
+    // SAFETY: forwarded from the caller
+    let result = unsafe { DisplayConfigGetDeviceInfo(packet) };
+    // SAFETY: as above; the call only writes within the packet
+    let response = unsafe { packet_bytes(packet) };
+    // SAFETY: `packet` points to a valid header
+    write_entry(
+        "GET",
+        unsafe { &*packet },
+        result,
+        &request,
+        Some(&response),
+    );
+    result
+}
+
+/// `DisplayConfigSetDeviceInfo`, traced when enabled
+///
+/// # Safety
+///
+/// Same contract as `DisplayConfigSetDeviceInfo`: `packet` points to an initialized
+/// packet whose header `size` matches the allocation.
+#[expect(unsafe_code, reason = "Wraps the DisplayConfigSetDeviceInfo FFI call")]
+pub unsafe fn display_config_set_device_info(
+    packet: *const DISPLAYCONFIG_DEVICE_INFO_HEADER,
+) -> i32 {
+    use crate::hdr::windows_api::DisplayConfigSetDeviceInfo;
+
+    if !is_enabled() {
+        // SAFETY: forwarded from the caller
+        return unsafe { DisplayConfigSetDeviceInfo(packet) };
+    }
+
+    // SAFETY: the caller guarantees `packet` covers `size` initialized bytes
+    let request = unsafe { packet_bytes(packet) };
+    // SAFETY: forwarded from the caller
+    let result = unsafe { DisplayConfigSetDeviceInfo(packet) };
+    // SAFETY: `packet` points to a valid header
+    write_entry("SET", unsafe { &*packet }, result, &request, None);
+    result
+}
+
+/// Copy the `size` bytes of a packet, header included
+///
+/// # Safety
+///
+/// `packet` must point to at least `(*packet).size` initialized bytes.
+#[expect(unsafe_code, reason = "Reads the raw bytes of an FFI packet")]
+unsafe fn packet_bytes(packet: *const DISPLAYCONFIG_DEVICE_INFO_HEADER) -> Vec<u8> {
+    // SAFETY: guaranteed by the caller
+    let size = unsafe { (*packet).size } as usize;
+    let size = size.max(size_of::<DISPLAYCONFIG_DEVICE_INFO_HEADER>());
+    // SAFETY: guaranteed by the caller; the header itself is always readable
+    unsafe { std::slice::from_raw_parts(packet.cast::<u8>(), size) }.to_vec()
+}
+
+/// Format bytes as space-separated hex, with `|` between rows of 16 bytes
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 3);
+    for (i, byte) in bytes.iter().enumerate() {
+        if i > 0 {
+            hex.push(if i % 16 == 0 { '|' } else { ' ' });
+        }
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// Format one call as a trace entry
+fn format_entry(
+    call: &str,
+    header: &DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    result: i32,
+    request: &[u8],
+    response: Option<&[u8]>,
+) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let mut entry = format!(
+        "{timestamp} {call} type={:?} size={} adapter={:#x}:{:#x} id={} result={result}\n  request:  {}\n",
+        header.type_,
+        header.size,
+        header.adapterId.LowPart,
+        header.adapterId.HighPart,
+        header.id,
+        hex(request)
+    );
+    if let Some(response) = response {
+        let _ = writeln!(entry, "  response: {}", hex(response));
+    }
+    entry
+}
+
+/// Append an entry to the trace file
+///
+/// Failures are logged and otherwise ignored; tracing never affects the call itself.
+fn write_entry(
+    call: &str,
+    header: &DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    result: i32,
+    request: &[u8],
+    response: Option<&[u8]>,
+) {
+    use tracing::warn;
+
+    let entry = format_entry(call, header, result, request, response);
+    let path = get_trace_path();
+
+    let _guard = WRITE_LOCK.lock();
+    if let Err(e) = append(&path, &entry) {
+        warn!("Failed to write HDR API trace to {:?}: {}", path, e);
+    }
+}
+
+/// Append `entry` to `path`, rotating the file first if it grew too large
+fn append(path: &std::path::Path, entry: &str) -> std::io::Result<()> {
+    if std::fs::metadata(path).is_ok_and(|metadata| metadata.len() >= MAX_TRACE_BYTES) {
+        std::fs::rename(path, path.with_extension("1.log"))?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(entry.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hdr::windows_api::{DISPLAYCONFIG_DEVICE_INFO_TYPE, LUID};
+
+    #[test]
+    fn test_hex_groups_sixteen_bytes() {
+        assert_eq!(hex(&[0x00, 0x0f, 0xff]), "00 0f ff");
+        let bytes: Vec<u8> = (0..18).collect();
+        assert!(hex(&bytes).ends_with("0e 0f|10 11"));
+    }
+
+    #[test]
+    fn test_entry_includes_header_and_bytes() {
+        let header = DISPLAYCONFIG_DEVICE_INFO_HEADER {
+            type_: DISPLAYCONFIG_DEVICE_INFO_TYPE::DISPLAYCONFIG_DEVICE_INFO_SET_HDR_STATE,
+            size: 24,
+            adapterId: LUID {
+                LowPart: 0x1234,
+                HighPart: 0,
+            },
+            id: 7,
+        };
+        let entry = format_entry("SET", &header, 87, &[1, 2], None);
+
+        assert!(entry.contains(
+            "SET type=DISPLAYCONFIG_DEVICE_INFO_SET_HDR_STATE size=24 adapter=0x1234:0x0 id=7 result=87"
+        ));
+        assert!(entry.contains("request:  01 02"));
+        assert!(!entry.contains("response"));
+    }
+}
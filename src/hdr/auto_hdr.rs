@@ -0,0 +1,266 @@
+//! Windows 11 Auto HDR per-application control
+//!
+//! Auto HDR is configured per executable in the per-user DirectX graphics settings under
+//! `HKCU\Software\Microsoft\DirectX\UserGpuPreferences`. The value name is the full
+//! executable path and the data is a `Key=Value;` list shared with other graphics
+//! settings such as `GpuPreference`. The Graphics settings page writes
+//! `AutoHDREnable=2097` to force Auto HDR on and `AutoHDREnable=2096` to force it off;
+//! without the key the global Auto HDR setting applies. DirectX reads the setting when
+//! a game starts, so it is applied when the user changes it rather than on process start.
+//! Value names are always plain paths, even for installs nested deeper than `MAX_PATH`.
+
+use crate::error::Result;
+use crate::hdr::WindowsVersion;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Registry key holding per-application DirectX graphics settings
+#[cfg(windows)]
+const USER_GPU_PREFERENCES_KEY: &str = r"Software\Microsoft\DirectX\UserGpuPreferences";
+
+/// Settings key for the per-application Auto HDR override
+const AUTO_HDR_KEY: &str = "AutoHDREnable";
+
+/// `AutoHDREnable` value forcing Auto HDR on
+const AUTO_HDR_ON: &str = "2097";
+
+/// `AutoHDREnable` value forcing Auto HDR off
+const AUTO_HDR_OFF: &str = "2096";
+
+/// Per-application Auto HDR override
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoHdrMode {
+    /// Leave the Windows setting alone (global Auto HDR setting applies)
+    #[default]
+    Default,
+    /// Force Auto HDR on for the application
+    On,
+    /// Force Auto HDR off for the application
+    Off,
+}
+
+impl AutoHdrMode {
+    /// Short label used by the GUI
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::On => "on",
+            Self::Off => "off",
+        }
+    }
+
+    /// Next mode when cycling through the options in the GUI
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            Self::Default => Self::On,
+            Self::On => Self::Off,
+            Self::Off => Self::Default,
+        }
+    }
+
+    /// Read the mode from a DirectX graphics settings string
+    pub fn from_settings(settings: &str) -> Self {
+        match parse_settings(settings)
+            .into_iter()
+            .find(|(key, _)| *key == AUTO_HDR_KEY)
+        {
+            Some((_, AUTO_HDR_ON)) => Self::On,
+            Some((_, AUTO_HDR_OFF)) => Self::Off,
+            _ => Self::Default,
+        }
+    }
+
+    /// Apply the mode to a DirectX graphics settings string
+    ///
+    /// Other settings in the string are preserved. [`AutoHdrMode::Default`] removes the
+    /// override, which may leave an empty string.
+    pub fn apply_to_settings(self, settings: &str) -> String {
+        use std::fmt::Write;
+
+        let value = match self {
+            Self::Default => None,
+            Self::On => Some(AUTO_HDR_ON),
+            Self::Off => Some(AUTO_HDR_OFF),
+        };
+
+        parse_settings(settings)
+            .into_iter()
+            .filter(|(key, _)| *key != AUTO_HDR_KEY)
+            .chain(value.map(|value| (AUTO_HDR_KEY, value)))
+            .fold(String::new(), |mut settings, (key, value)| {
+                let _ = write!(settings, "{key}={value};");
+                settings
+            })
+    }
+}
+
+/// Split a `Key=Value;` settings string into pairs, skipping malformed entries
+fn parse_settings(settings: &str) -> Vec<(&str, &str)> {
+    settings
+        .split(';')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+/// Auto HDR controller
+///
+/// Reads and writes per-application Auto HDR overrides. Auto HDR only exists on
+/// Windows 11; on older versions every operation is a no-op.
+#[derive(Debug, Clone, Copy)]
+pub struct AutoHdrController {
+    /// Windows version, used to skip Auto HDR on Windows 10
+    windows_version: WindowsVersion,
+}
+
+impl AutoHdrController {
+    /// Create a controller for the given Windows version
+    pub fn new(windows_version: WindowsVersion) -> Self {
+        Self { windows_version }
+    }
+
+    /// Whether Auto HDR is available on this Windows version
+    pub fn is_supported(&self) -> bool {
+        matches!(
+            self.windows_version,
+            WindowsVersion::Windows11 | WindowsVersion::Windows11_24H2
+        )
+    }
+
+    /// Get the Auto HDR override configured for an executable
+    #[cfg(windows)]
+    pub fn get_mode(&self, exe_path: &Path) -> Result<AutoHdrMode> {
+        use crate::utils::long_path;
+        use winreg::RegKey;
+        use winreg::enums::HKEY_CURRENT_USER;
+
+        if !self.is_supported() {
+            return Ok(AutoHdrMode::Default);
+        }
+
+        let exe_path = long_path::to_plain(exe_path);
+
+        let Ok(key) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(USER_GPU_PREFERENCES_KEY)
+        else {
+            return Ok(AutoHdrMode::Default);
+        };
+        let settings: String = key.get_value(exe_path.as_os_str()).unwrap_or_default();
+
+        Ok(AutoHdrMode::from_settings(&settings))
+    }
+
+    /// Auto HDR is only supported on Windows
+    #[cfg(not(windows))]
+    pub fn get_mode(&self, _exe_path: &Path) -> Result<AutoHdrMode> {
+        Ok(AutoHdrMode::Default)
+    }
+
+    /// Set the Auto HDR override for an executable
+    ///
+    /// Other graphics settings for the executable are preserved. Setting
+    /// [`AutoHdrMode::Default`] removes the override, deleting the registry value when
+    /// nothing else is left in it.
+    #[cfg(windows)]
+    pub fn set_mode(&self, exe_path: &Path, mode: AutoHdrMode) -> Result<()> {
+        use crate::utils::long_path;
+        use tracing::{debug, info};
+        use winreg::RegKey;
+        use winreg::enums::{HKEY_CURRENT_USER, KEY_READ, KEY_WRITE};
+
+        if !self.is_supported() {
+            debug!("Auto HDR is not available on {:?}", self.windows_version);
+            return Ok(());
+        }
+
+        let exe_path = long_path::to_plain(exe_path);
+
+        let (key, _) = RegKey::predef(HKEY_CURRENT_USER)
+            .create_subkey_with_flags(USER_GPU_PREFERENCES_KEY, KEY_READ | KEY_WRITE)?;
+        let current: String = key.get_value(exe_path.as_os_str()).unwrap_or_default();
+        let updated = mode.apply_to_settings(&current);
+
+        if updated == current {
+            return Ok(());
+        }
+
+        if updated.is_empty() {
+            match key.delete_value(exe_path.as_os_str()) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        } else {
+            key.set_value(exe_path.as_os_str(), &updated)?;
+        }
+
+        info!(
+            "Set Auto HDR to {} for {}",
+            mode.as_str(),
+            exe_path.display()
+        );
+        Ok(())
+    }
+
+    /// Auto HDR is only supported on Windows
+    #[cfg(not(windows))]
+    pub fn set_mode(&self, _exe_path: &Path, _mode: AutoHdrMode) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_settings() {
+        assert_eq!(AutoHdrMode::from_settings(""), AutoHdrMode::Default);
+        assert_eq!(
+            AutoHdrMode::from_settings("GpuPreference=2;"),
+            AutoHdrMode::Default
+        );
+        assert_eq!(
+            AutoHdrMode::from_settings("GpuPreference=2;AutoHDREnable=2097;"),
+            AutoHdrMode::On
+        );
+        assert_eq!(
+            AutoHdrMode::from_settings("AutoHDREnable=2096;SwapEffectUpgradeEnable=1;"),
+            AutoHdrMode::Off
+        );
+    }
+
+    #[test]
+    fn test_apply_to_settings_preserves_other_settings() {
+        assert_eq!(
+            AutoHdrMode::On.apply_to_settings("GpuPreference=2;"),
+            "GpuPreference=2;AutoHDREnable=2097;"
+        );
+        assert_eq!(
+            AutoHdrMode::Off.apply_to_settings("AutoHDREnable=2097;GpuPreference=2;"),
+            "GpuPreference=2;AutoHDREnable=2096;"
+        );
+        assert_eq!(
+            AutoHdrMode::Default.apply_to_settings("GpuPreference=2;AutoHDREnable=2097;"),
+            "GpuPreference=2;"
+        );
+        assert_eq!(
+            AutoHdrMode::Default.apply_to_settings("AutoHDREnable=2096;"),
+            ""
+        );
+        assert_eq!(AutoHdrMode::On.apply_to_settings(""), "AutoHDREnable=2097;");
+    }
+
+    #[test]
+    fn test_mode_cycle_and_support() {
+        assert_eq!(AutoHdrMode::Default.next(), AutoHdrMode::On);
+        assert_eq!(AutoHdrMode::On.next(), AutoHdrMode::Off);
+        assert_eq!(AutoHdrMode::Off.next(), AutoHdrMode::Default);
+
+        assert!(!AutoHdrController::new(WindowsVersion::Windows10).is_supported());
+        assert!(AutoHdrController::new(WindowsVersion::Windows11).is_supported());
+        assert!(AutoHdrController::new(WindowsVersion::Windows11_24H2).is_supported());
+    }
+}
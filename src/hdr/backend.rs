@@ -0,0 +1,595 @@
+//! Pluggable HDR backend
+//!
+//! [`HdrControl`] is the interface `AppController` drives HDR through. The Windows
+//! implementation is [`HdrController`](crate::hdr::HdrController); [`MockHdrControl`]
+//! keeps display state in memory so tests and embedding crates can run the controller
+//! without touching real displays. Implementors only provide display enumeration and
+//! per-display state access; global toggling, verification and retries are shared.
+//! DDC/CI commands and color profile associations are optional and unavailable unless a
+//! backend provides them; backends without wide color gamut control only offer the SDR
+//! and HDR color modes, and backends that cannot read the link signal give no bandwidth
+//! advisory.
+
+use crate::error::{ApiError, EasyHdrError, Result, StringError};
+use crate::hdr::{
+    ColorMode, DisplayIdentity, DisplayTarget, LinkSignal, VcpCommand, WindowsVersion,
+};
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// Attempts per display when setting HDR, including the first one
+pub const HDR_TOGGLE_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry of a failed HDR toggle, doubled for each further retry
+const HDR_TOGGLE_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Backend that enumerates displays and reads and sets their HDR state
+pub trait HdrControl: Send {
+    /// Enumerate the active displays, replacing the list returned by [`HdrControl::displays`]
+    fn enumerate_displays(&mut self) -> Result<Vec<DisplayTarget>>;
+
+    /// Displays found by the last enumeration
+    fn displays(&self) -> &[DisplayTarget];
+
+    /// Whether HDR is currently enabled on a display
+    fn is_hdr_enabled(&self, target: &DisplayTarget) -> Result<bool>;
+
+    /// Enable or disable HDR on a single display
+    fn set_hdr_state(&self, target: &DisplayTarget, enable: bool) -> Result<()>;
+
+    /// Windows version the backend runs on, used to gate Windows 11 features
+    fn windows_version(&self) -> WindowsVersion;
+
+    /// Send a DDC/CI command to the monitor of a display
+    ///
+    /// Backends without DDC/CI access report an error.
+    fn send_vcp_command(&self, target: &DisplayTarget, command: VcpCommand) -> Result<()> {
+        Err(EasyHdrError::DriverError(StringError::new(format!(
+            "DDC/CI command {command} is not available for {target}"
+        ))))
+    }
+
+    /// Make a color profile the default HDR profile of a display
+    ///
+    /// Backends without color management report an error.
+    fn set_color_profile(&self, target: &DisplayTarget, profile: &str) -> Result<()> {
+        Err(EasyHdrError::DriverError(StringError::new(format!(
+            "color profile '{profile}' cannot be set for {target}"
+        ))))
+    }
+
+    /// Current color mode of a display
+    ///
+    /// Backends that only know about HDR report SDR or HDR.
+    fn color_mode(&self, target: &DisplayTarget) -> Result<ColorMode> {
+        Ok(if self.is_hdr_enabled(target)? {
+            ColorMode::Hdr
+        } else {
+            ColorMode::Sdr
+        })
+    }
+
+    /// Switch a display to a color mode
+    ///
+    /// Backends without wide color gamut control only switch between SDR and HDR and
+    /// report an error for WCG.
+    fn set_color_mode(&self, target: &DisplayTarget, mode: ColorMode) -> Result<()> {
+        match mode {
+            ColorMode::Sdr => self.set_hdr_state(target, false),
+            ColorMode::Hdr => self.set_hdr_state(target, true),
+            ColorMode::Wcg => Err(EasyHdrError::DriverError(StringError::new(format!(
+                "wide color gamut mode is not available for {target}"
+            )))),
+        }
+    }
+
+    /// Mode, bit depth and color encoding a display is currently driven with
+    ///
+    /// Backends without access to the signal report an error.
+    fn link_signal(&self, target: &DisplayTarget) -> Result<LinkSignal> {
+        Err(EasyHdrError::DriverError(StringError::new(format!(
+            "the link signal of {target} is not available"
+        ))))
+    }
+
+    /// Log display changes instead of making them
+    ///
+    /// Backends that never touch real displays can ignore this.
+    fn set_dry_run(&mut self, _dry_run: bool) {}
+
+    /// Read the signal format and colorimetry of the displays again after HDR or the color
+    /// mode changed
+    ///
+    /// Backends that don't report them can ignore this.
+    fn refresh_capabilities(&mut self) {}
+
+    /// Detect the current HDR state from the system.
+    ///
+    /// Returns `true` if HDR is enabled on any HDR-capable display, `false` otherwise.
+    fn detect_current_hdr_state(&self) -> bool {
+        use tracing::{debug, warn};
+
+        let displays = self.displays();
+
+        for disp in displays.iter().filter(|d| d.supports_hdr) {
+            match self.is_hdr_enabled(disp) {
+                Ok(enabled) => {
+                    if enabled {
+                        debug!(
+                            "Display (adapter={:#x}:{:#x}, target={}) has HDR enabled",
+                            disp.adapter_id.LowPart, disp.adapter_id.HighPart, disp.target_id
+                        );
+                        return true;
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to check HDR state for display (adapter={:#x}:{:#x}, target={}): {}",
+                        disp.adapter_id.LowPart, disp.adapter_id.HighPart, disp.target_id, e
+                    );
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Enable or disable HDR globally across all HDR-capable displays
+    ///
+    /// Returns results for each display, allowing partial success. Continues with remaining
+    /// displays if some fail (e.g., due to disconnection).
+    fn set_hdr_global(&self, enable: bool) -> Result<Vec<(DisplayTarget, Result<()>)>> {
+        use tracing::{debug, info, warn};
+
+        info!(
+            "Setting HDR {} globally for {} display(s)",
+            if enable { "ON" } else { "OFF" },
+            self.displays().len()
+        );
+
+        let mut results = Vec::with_capacity(self.displays().len());
+
+        for target in self.displays() {
+            if !target.supports_hdr {
+                debug!(
+                    "Skipping display (adapter={:#x}:{:#x}, target={}) - HDR not supported",
+                    target.adapter_id.LowPart, target.adapter_id.HighPart, target.target_id
+                );
+                continue;
+            }
+
+            let result = self.set_hdr_state(target, enable);
+
+            match &result {
+                Ok(()) => {
+                    info!(
+                        "Successfully set HDR {} for display (adapter={:#x}:{:#x}, target={})",
+                        if enable { "ON" } else { "OFF" },
+                        target.adapter_id.LowPart,
+                        target.adapter_id.HighPart,
+                        target.target_id
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to set HDR {} for display (adapter={:#x}:{:#x}, target={}): {}. \
+                         Display may have been disconnected or driver issue occurred. Continuing with other displays.",
+                        if enable { "ON" } else { "OFF" },
+                        target.adapter_id.LowPart,
+                        target.adapter_id.HighPart,
+                        target.target_id,
+                        e
+                    );
+                }
+            }
+
+            results.push((target.clone(), result));
+        }
+
+        info!(
+            "HDR global toggle complete: {} successful, {} failed",
+            results.iter().filter(|(_, r)| r.is_ok()).count(),
+            results.iter().filter(|(_, r)| r.is_err()).count()
+        );
+
+        Ok(results)
+    }
+
+    /// Enable or disable HDR globally and verify the result, retrying failed displays
+    ///
+    /// After the initial [`HdrControl::set_hdr_global`], the HDR state of every display
+    /// is read back. Displays that failed or didn't change are retried up to
+    /// [`HDR_TOGGLE_ATTEMPTS`] attempts in total, with the delay doubling between
+    /// attempts. Returns the final result for each display.
+    fn set_hdr_global_verified(&self, enable: bool) -> Result<Vec<(DisplayTarget, Result<()>)>> {
+        let mut results = self.set_hdr_global(enable)?;
+        for (target, result) in &mut results {
+            if result.is_ok() {
+                *result = self.verify_hdr_state(target, enable);
+            }
+        }
+        self.retry_hdr_toggle(&mut results, enable);
+
+        Ok(results)
+    }
+
+    /// Enable or disable HDR on some displays only and verify the result, retrying
+    /// failed displays
+    ///
+    /// Works like [`HdrControl::set_hdr_global_verified`] for the given displays; those
+    /// without HDR support are skipped. Returns the final result for each display.
+    fn set_hdr_verified_on(
+        &self,
+        targets: &[DisplayTarget],
+        enable: bool,
+    ) -> Vec<(DisplayTarget, Result<()>)> {
+        use tracing::info;
+
+        info!(
+            "Setting HDR {} for {} display(s)",
+            if enable { "ON" } else { "OFF" },
+            targets.len()
+        );
+        let mut results: Vec<_> = targets
+            .iter()
+            .filter(|target| target.supports_hdr)
+            .map(|target| {
+                let result = self
+                    .set_hdr_state(target, enable)
+                    .and_then(|()| self.verify_hdr_state(target, enable));
+                (target.clone(), result)
+            })
+            .collect();
+        self.retry_hdr_toggle(&mut results, enable);
+        results
+    }
+
+    /// Retry the displays whose HDR toggle failed
+    ///
+    /// Failed displays are retried up to [`HDR_TOGGLE_ATTEMPTS`] attempts in total, with
+    /// the delay doubling between attempts, and their results replaced.
+    fn retry_hdr_toggle(&self, results: &mut [(DisplayTarget, Result<()>)], enable: bool) {
+        use tracing::{info, warn};
+
+        let mut delay = HDR_TOGGLE_RETRY_DELAY;
+        for attempt in 2..=HDR_TOGGLE_ATTEMPTS {
+            if results.iter().all(|(_, result)| result.is_ok()) {
+                break;
+            }
+
+            std::thread::sleep(delay);
+            delay *= 2;
+
+            for (target, result) in results.iter_mut().filter(|(_, result)| result.is_err()) {
+                *result = self
+                    .set_hdr_state(target, enable)
+                    .and_then(|()| self.verify_hdr_state(target, enable));
+                match result {
+                    Ok(()) => info!(
+                        "HDR {} for {} succeeded on attempt {}",
+                        if enable { "ON" } else { "OFF" },
+                        target,
+                        attempt
+                    ),
+                    Err(e) => warn!(
+                        "HDR {} for {} failed on attempt {}/{}: {}",
+                        if enable { "ON" } else { "OFF" },
+                        target,
+                        attempt,
+                        HDR_TOGGLE_ATTEMPTS,
+                        e
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Live displays with the given stable identities, in the order given
+    ///
+    /// Identities of monitors that are not connected are skipped.
+    fn resolve_displays(&self, identities: &[DisplayIdentity]) -> Vec<DisplayTarget> {
+        identities
+            .iter()
+            .filter_map(|identity| {
+                self.displays()
+                    .iter()
+                    .find(|target| target.identity.as_ref() == Some(identity))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Check that a display reports the expected HDR state after it was set
+    fn verify_hdr_state(&self, target: &DisplayTarget, enable: bool) -> Result<()> {
+        if self.is_hdr_enabled(target)? == enable {
+            Ok(())
+        } else {
+            Err(EasyHdrError::DriverError(StringError::new(format!(
+                "display still reports HDR {} after it was set",
+                if enable { "OFF" } else { "ON" }
+            ))))
+        }
+    }
+}
+
+/// In-memory HDR backend for tests and embedding
+///
+/// Clones share the same state, so a test can keep a handle after moving the backend
+/// into an `AppController` and inspect or change the simulated displays.
+#[derive(Debug, Clone)]
+pub struct MockHdrControl {
+    /// Shared simulated state
+    state: Arc<Mutex<MockState>>,
+    /// Snapshot of the display list, refreshed by [`HdrControl::enumerate_displays`]
+    displays: Vec<DisplayTarget>,
+}
+
+/// Simulated displays and their HDR state
+#[derive(Debug)]
+struct MockState {
+    /// Connected displays with their HDR state
+    displays: Vec<(DisplayTarget, bool)>,
+    /// Target IDs of displays whose HDR state cannot be changed
+    failing: Vec<u32>,
+    /// Number of `set_hdr_state` calls
+    set_calls: usize,
+    /// DDC/CI commands sent, by target ID
+    vcp_commands: Vec<(u32, VcpCommand)>,
+    /// Color profiles set, by target ID
+    color_profiles: Vec<(u32, String)>,
+    /// Target IDs of displays in wide color gamut mode
+    wcg: Vec<u32>,
+    /// Reported Windows version
+    windows_version: WindowsVersion,
+}
+
+impl MockHdrControl {
+    /// Create a backend with the given displays, all with HDR off
+    pub fn new(displays: Vec<DisplayTarget>) -> Self {
+        let state = MockState {
+            displays: displays
+                .iter()
+                .cloned()
+                .map(|target| (target, false))
+                .collect(),
+            failing: Vec::new(),
+            set_calls: 0,
+            vcp_commands: Vec::new(),
+            color_profiles: Vec::new(),
+            wcg: Vec::new(),
+            // Windows 11 is the most common target
+            windows_version: WindowsVersion::Windows11,
+        };
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            displays,
+        }
+    }
+
+    /// Create a backend with one HDR-capable display
+    pub fn with_hdr_display() -> Self {
+        Self::new(vec![DisplayTarget {
+            target_id: 1,
+            supports_hdr: true,
+            friendly_name: Some("Mock HDR Display".to_string()),
+            ..DisplayTarget::default()
+        }])
+    }
+
+    /// Report a different Windows version
+    #[must_use]
+    pub fn with_windows_version(self, windows_version: WindowsVersion) -> Self {
+        self.state.lock().windows_version = windows_version;
+        self
+    }
+
+    /// HDR state of a display, `None` if no display has the target ID
+    pub fn hdr_state(&self, target_id: u32) -> Option<bool> {
+        self.state
+            .lock()
+            .displays
+            .iter()
+            .find(|(target, _)| target.target_id == target_id)
+            .map(|(_, enabled)| *enabled)
+    }
+
+    /// Change the HDR state of a display behind `EasyHDR`'s back, like Windows settings would
+    pub fn set_external_hdr_state(&self, target_id: u32, enable: bool) {
+        for (target, enabled) in &mut self.state.lock().displays {
+            if target.target_id == target_id {
+                *enabled = enable;
+            }
+        }
+    }
+
+    /// Make HDR changes on a display fail, as the driver would with `ERROR_GEN_FAILURE`
+    pub fn set_failing(&self, target_id: u32, failing: bool) {
+        let mut state = self.state.lock();
+        state.failing.retain(|&id| id != target_id);
+        if failing {
+            state.failing.push(target_id);
+        }
+    }
+
+    /// Number of `set_hdr_state` calls so far, including retries
+    pub fn set_calls(&self) -> usize {
+        self.state.lock().set_calls
+    }
+
+    /// DDC/CI commands sent so far, with the target ID they were sent to
+    pub fn vcp_commands(&self) -> Vec<(u32, VcpCommand)> {
+        self.state.lock().vcp_commands.clone()
+    }
+
+    /// Color profiles set so far, with the target ID they were set for
+    pub fn color_profiles(&self) -> Vec<(u32, String)> {
+        self.state.lock().color_profiles.clone()
+    }
+}
+
+impl HdrControl for MockHdrControl {
+    fn enumerate_displays(&mut self) -> Result<Vec<DisplayTarget>> {
+        self.displays = self
+            .state
+            .lock()
+            .displays
+            .iter()
+            .map(|(target, _)| target.clone())
+            .collect();
+        Ok(self.displays.clone())
+    }
+
+    fn displays(&self) -> &[DisplayTarget] {
+        &self.displays
+    }
+
+    fn is_hdr_enabled(&self, target: &DisplayTarget) -> Result<bool> {
+        self.hdr_state(target.target_id).ok_or_else(|| {
+            EasyHdrError::DriverError(StringError::new(format!(
+                "display {} is not connected",
+                target.target_id
+            )))
+        })
+    }
+
+    fn set_hdr_state(&self, target: &DisplayTarget, enable: bool) -> Result<()> {
+        let mut state = self.state.lock();
+        state.set_calls += 1;
+
+        if state.failing.contains(&target.target_id) {
+            return Err(EasyHdrError::DriverError(ApiError::new(
+                format!("simulated failure on display {}", target.target_id),
+                31,
+            )));
+        }
+
+        match state
+            .displays
+            .iter_mut()
+            .find(|(display, _)| display.target_id == target.target_id)
+        {
+            Some((display, enabled)) if display.supports_hdr => {
+                *enabled = enable;
+                Ok(())
+            }
+            Some(_) => Err(EasyHdrError::HdrNotSupported),
+            None => Err(EasyHdrError::DriverError(StringError::new(format!(
+                "display {} is not connected",
+                target.target_id
+            )))),
+        }
+    }
+
+    fn windows_version(&self) -> WindowsVersion {
+        self.state.lock().windows_version
+    }
+
+    fn send_vcp_command(&self, target: &DisplayTarget, command: VcpCommand) -> Result<()> {
+        let mut state = self.state.lock();
+        if state.failing.contains(&target.target_id) {
+            return Err(EasyHdrError::DriverError(StringError::new(format!(
+                "simulated DDC/CI failure on display {}",
+                target.target_id
+            ))));
+        }
+        state.vcp_commands.push((target.target_id, command));
+        Ok(())
+    }
+
+    fn set_color_profile(&self, target: &DisplayTarget, profile: &str) -> Result<()> {
+        let mut state = self.state.lock();
+        if state.failing.contains(&target.target_id) {
+            return Err(EasyHdrError::DriverError(StringError::new(format!(
+                "simulated color profile failure on display {}",
+                target.target_id
+            ))));
+        }
+        state
+            .color_profiles
+            .push((target.target_id, profile.to_string()));
+        Ok(())
+    }
+
+    fn color_mode(&self, target: &DisplayTarget) -> Result<ColorMode> {
+        let wcg = self.state.lock().wcg.contains(&target.target_id);
+        Ok(if self.is_hdr_enabled(target)? {
+            ColorMode::Hdr
+        } else if wcg {
+            ColorMode::Wcg
+        } else {
+            ColorMode::Sdr
+        })
+    }
+
+    fn set_color_mode(&self, target: &DisplayTarget, mode: ColorMode) -> Result<()> {
+        if mode == ColorMode::Wcg && self.windows_version() != WindowsVersion::Windows11_24H2 {
+            return Err(EasyHdrError::DriverError(StringError::new(format!(
+                "wide color gamut mode is not available for {target}"
+            ))));
+        }
+
+        self.set_hdr_state(target, mode == ColorMode::Hdr)?;
+        let mut state = self.state.lock();
+        state.wcg.retain(|&id| id != target.target_id);
+        if mode == ColorMode::Wcg {
+            state.wcg.push(target.target_id);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_toggles_hdr_displays() {
+        let mut mock = MockHdrControl::new(vec![
+            DisplayTarget {
+                target_id: 1,
+                supports_hdr: true,
+                ..DisplayTarget::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                supports_hdr: false,
+                ..DisplayTarget::default()
+            },
+        ]);
+        assert_eq!(mock.enumerate_displays().unwrap().len(), 2);
+        assert!(!mock.detect_current_hdr_state());
+
+        let results = mock.set_hdr_global_verified(true).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].1.is_ok());
+        assert_eq!(mock.hdr_state(1), Some(true));
+        assert_eq!(mock.hdr_state(2), Some(false));
+        assert!(mock.detect_current_hdr_state());
+    }
+
+    #[test]
+    fn test_mock_failures_are_retried_and_reported() {
+        let mock = MockHdrControl::with_hdr_display();
+        mock.set_failing(1, true);
+
+        let results = mock.set_hdr_global_verified(true).unwrap();
+        assert!(results[0].1.is_err());
+        assert_eq!(mock.set_calls(), HDR_TOGGLE_ATTEMPTS as usize);
+        assert_eq!(mock.hdr_state(1), Some(false));
+    }
+
+    #[test]
+    fn test_mock_clones_share_state() {
+        let mock = MockHdrControl::with_hdr_display();
+        let handle = mock.clone();
+
+        handle.set_external_hdr_state(1, true);
+        assert!(mock.detect_current_hdr_state());
+        assert_eq!(
+            mock.with_windows_version(WindowsVersion::Windows10)
+                .windows_version(),
+            WindowsVersion::Windows10
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! HDR capability details of a display
+//!
+//! Windows reports the signal a display is driven with (bits per channel and color
+//! encoding) through the advanced color info, and the luminance range and color
+//! primaries of the panel through DXGI (`IDXGIOutput6::GetDesc1`), which takes them from
+//! the EDID or from the Windows HDR Calibration profile. [`DisplayCapabilities`] collects
+//! both for the displays dialog and the log.
+//!
+//! Luminance and chromaticity are kept in the fixed-point units of HDR10 static metadata
+//! (0.0001 cd/m² and 0.00002) so the struct stays `Eq` and `Hash` like the display it
+//! belongs to.
+
+use crate::hdr::ColorEncoding;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Luminance units per cd/m²
+const LUMINANCE_SCALE: f32 = 10_000.0;
+
+/// Chromaticity units per 1.0
+const CHROMATICITY_SCALE: f32 = 50_000.0;
+
+/// Largest distance of the red primary from a standard gamut's to be named after it
+const GAMUT_TOLERANCE: f32 = 0.02;
+
+/// Red primaries (x, y) of the gamuts a panel is named after, widest first
+const STANDARD_GAMUTS: [(&str, [f32; 2]); 3] = [
+    ("BT.2020", [0.708, 0.292]),
+    ("DCI-P3", [0.680, 0.320]),
+    ("sRGB", [0.640, 0.330]),
+];
+
+/// Signal format and panel capabilities of a display
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DisplayCapabilities {
+    /// Bits per color channel of the current signal
+    pub bits_per_channel: Option<u32>,
+    /// Color encoding of the current signal
+    pub color_encoding: Option<ColorEncoding>,
+    /// Luminance range and color primaries reported by DXGI
+    pub colorimetry: Option<OutputColorimetry>,
+}
+
+impl DisplayCapabilities {
+    /// Short description for users, e.g. "10-bit RGB, 1015 nits peak, 0.0005 nits black"
+    ///
+    /// Empty when nothing is known about the display.
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        match (self.bits_per_channel, self.color_encoding) {
+            (Some(bits), Some(encoding)) => parts.push(format!("{bits}-bit {}", encoding.name())),
+            (Some(bits), None) => parts.push(format!("{bits}-bit")),
+            (None, Some(encoding)) => parts.push(encoding.name().to_string()),
+            (None, None) => {}
+        }
+        if let Some(colorimetry) = &self.colorimetry {
+            if colorimetry.max_luminance > 0 {
+                parts.push(format!("{:.0} nits peak", colorimetry.max_luminance_nits()));
+            }
+            if colorimetry.max_full_frame_luminance > 0 {
+                parts.push(format!(
+                    "{:.0} nits full frame",
+                    colorimetry.max_full_frame_luminance_nits()
+                ));
+            }
+            if colorimetry.min_luminance > 0 {
+                parts.push(format!(
+                    "{:.4} nits black",
+                    colorimetry.min_luminance_nits()
+                ));
+            }
+            if let Some(gamut) = colorimetry.gamut_name() {
+                parts.push(format!("{gamut} gamut"));
+            }
+        }
+        parts.join(", ")
+    }
+}
+
+/// Luminance range and color primaries of a display as reported by DXGI
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OutputColorimetry {
+    /// Peak luminance in 0.0001 cd/m²
+    pub max_luminance: u32,
+    /// Minimum luminance in 0.0001 cd/m²
+    pub min_luminance: u32,
+    /// Luminance a full white frame can sustain, in 0.0001 cd/m²
+    pub max_full_frame_luminance: u32,
+    /// Red primary (x, y) in units of 0.00002
+    pub red_primary: [u16; 2],
+    /// Green primary (x, y) in units of 0.00002
+    pub green_primary: [u16; 2],
+    /// Blue primary (x, y) in units of 0.00002
+    pub blue_primary: [u16; 2],
+    /// White point (x, y) in units of 0.00002
+    pub white_point: [u16; 2],
+    /// Whether the output uses the HDR10 color space (PQ, BT.2020), which DXGI only
+    /// reports while HDR is on
+    pub hdr_color_space: bool,
+}
+
+impl OutputColorimetry {
+    /// Build from the cd/m² and CIE 1931 xy values of a `DXGI_OUTPUT_DESC1`
+    ///
+    /// `primaries` holds red, green, blue and the white point, in that order.
+    pub fn from_dxgi(
+        max_luminance: f32,
+        min_luminance: f32,
+        max_full_frame_luminance: f32,
+        primaries: [[f32; 2]; 4],
+    ) -> Self {
+        let [red, green, blue, white] = primaries.map(|xy| xy.map(chromaticity_units));
+        Self {
+            max_luminance: luminance_units(max_luminance),
+            min_luminance: luminance_units(min_luminance),
+            max_full_frame_luminance: luminance_units(max_full_frame_luminance),
+            red_primary: red,
+            green_primary: green,
+            blue_primary: blue,
+            white_point: white,
+            hdr_color_space: false,
+        }
+    }
+
+    /// Peak luminance in cd/m²
+    pub fn max_luminance_nits(&self) -> f32 {
+        nits(self.max_luminance)
+    }
+
+    /// Minimum luminance in cd/m²
+    pub fn min_luminance_nits(&self) -> f32 {
+        nits(self.min_luminance)
+    }
+
+    /// Full-frame luminance in cd/m²
+    pub fn max_full_frame_luminance_nits(&self) -> f32 {
+        nits(self.max_full_frame_luminance)
+    }
+
+    /// Standard gamut the panel's red primary is closest to, if it is close to one
+    pub fn gamut_name(&self) -> Option<&'static str> {
+        let [x, y] = chromaticity(self.red_primary);
+        STANDARD_GAMUTS
+            .iter()
+            .find(|(_, [red_x, red_y])| (x - red_x).hypot(y - red_y) <= GAMUT_TOLERANCE)
+            .map(|(name, _)| *name)
+    }
+}
+
+impl fmt::Display for OutputColorimetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [red, green, blue, white] = [
+            self.red_primary,
+            self.green_primary,
+            self.blue_primary,
+            self.white_point,
+        ]
+        .map(chromaticity);
+        write!(
+            f,
+            "{:.0}/{:.0}/{:.4} nits (peak/full frame/min), R({:.3}, {:.3}) G({:.3}, {:.3}) \
+             B({:.3}, {:.3}) W({:.4}, {:.4})",
+            self.max_luminance_nits(),
+            self.max_full_frame_luminance_nits(),
+            self.min_luminance_nits(),
+            red[0],
+            red[1],
+            green[0],
+            green[1],
+            blue[0],
+            blue[1],
+            white[0],
+            white[1]
+        )
+    }
+}
+
+/// Convert cd/m² to 0.0001 cd/m² units, treating negative and invalid values as unknown
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    reason = "The value is clamped to the u32 range before the cast"
+)]
+fn luminance_units(nits: f32) -> u32 {
+    if nits.is_finite() && nits > 0.0 {
+        (nits * LUMINANCE_SCALE).round().min(u32::MAX as f32) as u32
+    } else {
+        0
+    }
+}
+
+/// Convert a chromaticity coordinate to units of 0.00002
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "Coordinates are clamped to 0..=1 before the cast"
+)]
+fn chromaticity_units(coordinate: f32) -> u16 {
+    if coordinate.is_finite() {
+        (coordinate.clamp(0.0, 1.0) * CHROMATICITY_SCALE).round() as u16
+    } else {
+        0
+    }
+}
+
+/// Convert 0.0001 cd/m² units to cd/m²
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "Luminance does not need more precision than f32 offers"
+)]
+fn nits(units: u32) -> f32 {
+    units as f32 / LUMINANCE_SCALE
+}
+
+/// Convert chromaticity units back to CIE 1931 xy
+fn chromaticity(units: [u16; 2]) -> [f32; 2] {
+    units.map(|unit| f32::from(unit) / CHROMATICITY_SCALE)
+}
+
+/// Luminance, primaries and color space of every DXGI output, by GDI device name (e.g.
+/// `\\.\DISPLAY1`)
+///
+/// Outputs whose adapter does not offer `IDXGIOutput6` (before Windows 10 1803) are left
+/// out. Returns an empty map if DXGI is unavailable.
+///
+/// # Safety
+///
+/// The factory, adapters and outputs are reference-counted COM objects released when
+/// dropped. Enumeration stops at the first index DXGI reports as not found.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows COM FFI for reading output descriptions via DXGI"
+)]
+pub fn output_colorimetry() -> HashMap<String, OutputColorimetry> {
+    use tracing::debug;
+    use windows::Win32::Graphics::Dxgi::Common::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+    use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1, IDXGIOutput6};
+    use windows::core::Interface;
+
+    let mut outputs = HashMap::new();
+    // SAFETY: creating a factory has no preconditions
+    let factory: IDXGIFactory1 = match unsafe { CreateDXGIFactory1() } {
+        Ok(factory) => factory,
+        Err(e) => {
+            debug!("Failed to create a DXGI factory: {}", e);
+            return outputs;
+        }
+    };
+
+    for adapter_index in 0u32.. {
+        // SAFETY: out-of-range indices return DXGI_ERROR_NOT_FOUND
+        let Ok(adapter) = (unsafe { factory.EnumAdapters1(adapter_index) }) else {
+            break;
+        };
+        for output_index in 0u32.. {
+            // SAFETY: out-of-range indices return DXGI_ERROR_NOT_FOUND
+            let Ok(output) = (unsafe { adapter.EnumOutputs(output_index) }) else {
+                break;
+            };
+            let Ok(output) = output.cast::<IDXGIOutput6>() else {
+                continue;
+            };
+            // SAFETY: `output` is a valid IDXGIOutput6 for the duration of the call
+            let desc = match unsafe { output.GetDesc1() } {
+                Ok(desc) => desc,
+                Err(e) => {
+                    debug!("Failed to read the description of a DXGI output: {}", e);
+                    continue;
+                }
+            };
+
+            let len = desc
+                .DeviceName
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(desc.DeviceName.len());
+            outputs.insert(
+                String::from_utf16_lossy(&desc.DeviceName[..len]),
+                OutputColorimetry {
+                    hdr_color_space: desc.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020,
+                    ..OutputColorimetry::from_dxgi(
+                        desc.MaxLuminance,
+                        desc.MinLuminance,
+                        desc.MaxFullFrameLuminance,
+                        [
+                            desc.RedPrimary,
+                            desc.GreenPrimary,
+                            desc.BluePrimary,
+                            desc.WhitePoint,
+                        ],
+                    )
+                },
+            );
+        }
+    }
+    outputs
+}
+
+/// DXGI only exists on Windows
+#[cfg(not(windows))]
+pub fn output_colorimetry() -> HashMap<String, OutputColorimetry> {
+    HashMap::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oled_colorimetry() -> OutputColorimetry {
+        OutputColorimetry::from_dxgi(
+            1015.0,
+            0.0005,
+            250.0,
+            [
+                [0.681, 0.319],
+                [0.243, 0.709],
+                [0.139, 0.052],
+                [0.3127, 0.329],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_dxgi_values_are_converted() {
+        let colorimetry = oled_colorimetry();
+        assert_eq!(colorimetry.max_luminance, 10_150_000);
+        assert_eq!(colorimetry.min_luminance, 5);
+        assert_eq!(colorimetry.red_primary, [34_050, 15_950]);
+        assert_eq!(colorimetry.white_point, [15_635, 16_450]);
+        assert_eq!(colorimetry.gamut_name(), Some("DCI-P3"));
+
+        let invalid = OutputColorimetry::from_dxgi(f32::NAN, -1.0, 0.0, [[2.0, -0.5]; 4]);
+        assert_eq!(invalid.max_luminance, 0);
+        assert_eq!(invalid.min_luminance, 0);
+        assert_eq!(invalid.red_primary, [50_000, 0]);
+        assert_eq!(invalid.gamut_name(), None);
+    }
+
+    #[test]
+    fn test_summary() {
+        let capabilities = DisplayCapabilities {
+            bits_per_channel: Some(10),
+            color_encoding: Some(ColorEncoding::Rgb),
+            colorimetry: Some(oled_colorimetry()),
+        };
+        assert_eq!(
+            capabilities.summary(),
+            "10-bit RGB, 1015 nits peak, 250 nits full frame, 0.0005 nits black, DCI-P3 gamut"
+        );
+
+        let signal_only = DisplayCapabilities {
+            bits_per_channel: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(signal_only.summary(), "8-bit");
+        assert_eq!(DisplayCapabilities::default().summary(), "");
+    }
+}
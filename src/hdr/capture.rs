@@ -0,0 +1,270 @@
+//! Tone mapping of HDR screen captures
+//!
+//! Screenshots and clips of HDR games taken with the Game Bar come out washed out when
+//! they are saved as SDR without tone mapping. On Windows 11 the Game Bar capture
+//! settings live under `HKCU\Software\Microsoft\Windows\CurrentVersion\GameDVR`, and the
+//! tone mapping switch is the DWORD [`TONE_MAPPING_VALUE`] (1 on, 0 off, missing for the
+//! Windows default). Monitored applications can turn it on or off while HDR is on for
+//! them; the value in effect before is restored when HDR turns off again, and by the
+//! [crash guard](crate::hdr::crash_guard) when `EasyHDR` exits without turning HDR off.
+//! Systems without the Game Bar capture key are left alone.
+
+use crate::error::Result;
+use crate::hdr::{WindowsVersion, crash_guard};
+
+/// Registry key holding the Game Bar capture settings
+#[cfg(windows)]
+const GAME_DVR_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\GameDVR";
+
+/// DWORD value switching tone mapping of HDR captures
+pub const TONE_MAPPING_VALUE: &str = "HDRCaptureToneMapping";
+
+/// Storage of the tone mapping value
+///
+/// [`RegistryCaptureSettings`] outside of tests.
+pub trait CaptureSettings: Send {
+    /// Whether the Game Bar capture settings exist for the current user
+    fn exists(&self) -> bool;
+
+    /// Read the tone mapping value, `None` when it is missing
+    fn read(&self) -> Result<Option<bool>>;
+
+    /// Write the tone mapping value, deleting it for `None`
+    fn write(&self, value: Option<bool>) -> Result<()>;
+}
+
+/// Capture tone mapping controller
+///
+/// Applies the tone mapping setting of a monitored application and restores the
+/// original value afterwards. Only the first applied setting records the original
+/// value, so applying several in a row still restores the one the user had.
+pub struct CaptureToneMappingController {
+    /// Windows version, used to skip the setting on Windows 10
+    windows_version: WindowsVersion,
+    /// Where the tone mapping value is stored
+    settings: Box<dyn CaptureSettings>,
+    /// Value before the first applied setting (`Some(None)` when it was missing),
+    /// `None` when nothing is applied
+    #[expect(
+        clippy::option_option,
+        reason = "Distinguishes nothing applied from a setting that was missing"
+    )]
+    original: Option<Option<bool>>,
+}
+
+impl std::fmt::Debug for CaptureToneMappingController {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CaptureToneMappingController")
+            .field("windows_version", &self.windows_version)
+            .field("original", &self.original)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CaptureToneMappingController {
+    /// Create a controller for the given Windows version with nothing applied
+    pub fn new(windows_version: WindowsVersion) -> Self {
+        Self::with_settings(windows_version, Box::new(RegistryCaptureSettings))
+    }
+
+    /// Create a controller storing the tone mapping value in `settings`
+    pub fn with_settings(
+        windows_version: WindowsVersion,
+        settings: Box<dyn CaptureSettings>,
+    ) -> Self {
+        Self {
+            windows_version,
+            settings,
+            original: None,
+        }
+    }
+
+    /// Whether capture tone mapping can be switched on this system
+    pub fn is_supported(&self) -> bool {
+        matches!(
+            self.windows_version,
+            WindowsVersion::Windows11 | WindowsVersion::Windows11_24H2
+        ) && self.settings.exists()
+    }
+
+    /// Whether a setting is currently applied
+    pub fn is_applied(&self) -> bool {
+        self.original.is_some()
+    }
+
+    /// Turn tone mapping of HDR captures on or off
+    pub fn apply(&mut self, enable: bool) -> Result<()> {
+        use tracing::{debug, info};
+
+        if !self.is_supported() {
+            debug!("HDR capture tone mapping is not available on this system");
+            return Ok(());
+        }
+
+        let current = self.settings.read()?;
+        if current == Some(enable) {
+            return Ok(());
+        }
+
+        // Recorded first, so a crash right after the write still restores it
+        if self.original.is_none() {
+            self.original = Some(current);
+            crash_guard::arm_capture_tone_mapping(current);
+        }
+        self.settings.write(Some(enable))?;
+        info!(
+            "Turned tone mapping of HDR captures {}",
+            if enable { "on" } else { "off" }
+        );
+        Ok(())
+    }
+
+    /// Restore the value that was in effect before the first applied setting
+    pub fn restore(&mut self) -> Result<()> {
+        use tracing::info;
+
+        let Some(original) = self.original.take() else {
+            return Ok(());
+        };
+
+        self.settings.write(original)?;
+        crash_guard::disarm_capture_tone_mapping();
+        info!("Restored tone mapping of HDR captures");
+        Ok(())
+    }
+}
+
+/// Tone mapping value in the Game Bar capture settings of the current user
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RegistryCaptureSettings;
+
+impl CaptureSettings for RegistryCaptureSettings {
+    #[cfg(windows)]
+    fn exists(&self) -> bool {
+        use winreg::RegKey;
+        use winreg::enums::HKEY_CURRENT_USER;
+
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(GAME_DVR_KEY)
+            .is_ok()
+    }
+
+    /// The Game Bar only exists on Windows
+    #[cfg(not(windows))]
+    fn exists(&self) -> bool {
+        false
+    }
+
+    #[cfg(windows)]
+    fn read(&self) -> Result<Option<bool>> {
+        use winreg::RegKey;
+        use winreg::enums::HKEY_CURRENT_USER;
+
+        let key = RegKey::predef(HKEY_CURRENT_USER).open_subkey(GAME_DVR_KEY)?;
+        Ok(key
+            .get_value::<u32, _>(TONE_MAPPING_VALUE)
+            .ok()
+            .map(|value| value != 0))
+    }
+
+    /// The Game Bar only exists on Windows
+    #[cfg(not(windows))]
+    #[expect(
+        clippy::unnecessary_wraps,
+        reason = "Only the Windows implementation can fail"
+    )]
+    fn read(&self) -> Result<Option<bool>> {
+        Ok(None)
+    }
+
+    #[cfg(windows)]
+    fn write(&self, value: Option<bool>) -> Result<()> {
+        use winreg::RegKey;
+        use winreg::enums::{HKEY_CURRENT_USER, KEY_READ, KEY_WRITE};
+
+        let key = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey_with_flags(GAME_DVR_KEY, KEY_READ | KEY_WRITE)?;
+        match value {
+            Some(enable) => key.set_value(TONE_MAPPING_VALUE, &u32::from(enable))?,
+            None => match key.delete_value(TONE_MAPPING_VALUE) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            },
+        }
+        Ok(())
+    }
+
+    /// The Game Bar only exists on Windows
+    #[cfg(not(windows))]
+    #[expect(
+        clippy::unnecessary_wraps,
+        reason = "Only the Windows implementation can fail"
+    )]
+    fn write(&self, _value: Option<bool>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    /// Tone mapping value kept in memory, `None` when it is missing
+    #[derive(Clone, Default)]
+    struct MemorySettings(Arc<Mutex<Option<bool>>>);
+
+    impl CaptureSettings for MemorySettings {
+        fn exists(&self) -> bool {
+            true
+        }
+
+        fn read(&self) -> Result<Option<bool>> {
+            Ok(*self.0.lock())
+        }
+
+        fn write(&self, value: Option<bool>) -> Result<()> {
+            *self.0.lock() = value;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_apply_and_restore_round_trip() {
+        // The value was missing, so restoring deletes it again
+        let settings = MemorySettings::default();
+        let mut controller = CaptureToneMappingController::with_settings(
+            WindowsVersion::Windows11,
+            Box::new(settings.clone()),
+        );
+        assert!(controller.is_supported());
+        controller.apply(true).unwrap();
+        assert!(controller.is_applied());
+        assert_eq!(*settings.0.lock(), Some(true));
+        controller.apply(false).unwrap();
+        assert_eq!(*settings.0.lock(), Some(false));
+        controller.restore().unwrap();
+        assert!(!controller.is_applied());
+        assert_eq!(*settings.0.lock(), None);
+
+        // A value that is already set is left as it is
+        *settings.0.lock() = Some(false);
+        controller.apply(false).unwrap();
+        assert!(!controller.is_applied());
+        controller.apply(true).unwrap();
+        controller.restore().unwrap();
+        assert_eq!(*settings.0.lock(), Some(false));
+    }
+
+    #[test]
+    fn test_windows_10_is_unsupported() {
+        let mut controller = CaptureToneMappingController::new(WindowsVersion::Windows10);
+        assert!(!controller.is_supported());
+        controller.apply(true).unwrap();
+        assert!(!controller.is_applied());
+        controller.restore().unwrap();
+    }
+}
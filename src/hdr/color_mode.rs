@@ -0,0 +1,82 @@
+//! Advanced color modes of a display
+//!
+//! Windows 11 24H2 tells three color modes apart: SDR, wide color gamut (WCG, advanced
+//! color for SDR content with color management) and HDR. It reports the active one in
+//! `activeColorMode` and switches HDR and WCG independently. Older builds only switch
+//! advanced color as a whole, so they can only choose between SDR and HDR.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Color mode of a display
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMode {
+    /// Standard dynamic range without advanced color
+    #[default]
+    Sdr,
+    /// Wide color gamut: advanced color for SDR content (Windows 11 24H2+)
+    Wcg,
+    /// High dynamic range
+    Hdr,
+}
+
+impl ColorMode {
+    /// Short label used by the GUI
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sdr => "sdr",
+            Self::Wcg => "wcg",
+            Self::Hdr => "hdr",
+        }
+    }
+
+    /// Parse a label written by [`ColorMode::as_str`], ignoring case
+    pub fn parse(text: &str) -> Option<Self> {
+        [Self::Sdr, Self::Wcg, Self::Hdr]
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(text.trim()))
+    }
+
+    /// Decode `DISPLAYCONFIG_ADVANCED_COLOR_MODE` as reported in `activeColorMode`
+    pub fn from_active_color_mode(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Sdr),
+            1 => Some(Self::Wcg),
+            2 => Some(Self::Hdr),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Sdr => "SDR",
+            Self::Wcg => "WCG",
+            Self::Hdr => "HDR",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for mode in [ColorMode::Sdr, ColorMode::Wcg, ColorMode::Hdr] {
+            assert_eq!(ColorMode::parse(mode.as_str()), Some(mode));
+            assert_eq!(ColorMode::parse(&mode.to_string()), Some(mode));
+        }
+        assert_eq!(ColorMode::parse("dolby vision"), None);
+    }
+
+    #[test]
+    fn test_from_active_color_mode() {
+        assert_eq!(ColorMode::from_active_color_mode(0), Some(ColorMode::Sdr));
+        assert_eq!(ColorMode::from_active_color_mode(1), Some(ColorMode::Wcg));
+        assert_eq!(ColorMode::from_active_color_mode(2), Some(ColorMode::Hdr));
+        assert_eq!(ColorMode::from_active_color_mode(3), None);
+    }
+}
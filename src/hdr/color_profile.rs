@@ -0,0 +1,98 @@
+//! HDR color profiles applied after HDR is turned on
+//!
+//! The Windows HDR Calibration app saves its result as an ICC profile with an MHC2 tag
+//! and makes it the default advanced color profile of the display. When HDR is toggled
+//! programmatically, Windows does not always pick that profile up again, leaving the
+//! display uncalibrated. A profile configured for a display is therefore associated
+//! with its source again, as the default advanced color profile, each time `EasyHDR`
+//! turns HDR on.
+
+use crate::error::{EasyHdrError, Result, StringError};
+use crate::hdr::DisplayTarget;
+
+/// Make an installed color profile the default HDR profile of a display
+///
+/// `profile` is the file name of a profile in the Windows color store, e.g.
+/// `Windows HDR Calibration.icc`. The association is made for the current user, like the
+/// one the HDR Calibration app creates.
+///
+/// # Errors
+///
+/// Returns error if the profile name is empty or Windows rejects the association
+/// (unknown profile, or a Windows version older than 10 2004).
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for display color profile association via mscms"
+)]
+pub fn set_default_hdr_profile(target: &DisplayTarget, profile: &str) -> Result<()> {
+    use tracing::info;
+    use windows::Win32::UI::ColorSystem::{
+        ColorProfileAddDisplayAssociation, WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+    };
+    use windows::core::HSTRING;
+
+    let profile = validate_profile_name(profile)?;
+
+    // SAFETY: the profile name is a NUL-terminated HSTRING that outlives the call, and the
+    // adapter and source IDs come from the last display enumeration
+    unsafe {
+        ColorProfileAddDisplayAssociation(
+            WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+            &HSTRING::from(profile),
+            target.adapter_id.into(),
+            target.source_id,
+            true,
+            true,
+        )
+    }
+    .map_err(|e| {
+        EasyHdrError::DriverError(StringError::new(format!(
+            "failed to set color profile '{profile}' for {target}: {e}"
+        )))
+    })?;
+
+    info!("Set HDR color profile '{}' for {}", profile, target);
+    Ok(())
+}
+
+/// Color profile associations are only available on Windows
+#[cfg(not(windows))]
+pub fn set_default_hdr_profile(target: &DisplayTarget, profile: &str) -> Result<()> {
+    let profile = validate_profile_name(profile)?;
+    Err(EasyHdrError::DriverError(StringError::new(format!(
+        "cannot set color profile '{profile}' for {target}: only supported on Windows"
+    ))))
+}
+
+/// Trim a profile name and check that it names a file in the color store
+///
+/// # Errors
+///
+/// Returns error if the name is empty or contains a path separator.
+pub fn validate_profile_name(profile: &str) -> Result<&str> {
+    let profile = profile.trim();
+    if profile.is_empty() || profile.contains(['\\', '/']) {
+        return Err(EasyHdrError::ConfigError(StringError::new(format!(
+            "'{profile}' is not a color profile file name"
+        ))));
+    }
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_profile_name() {
+        assert_eq!(
+            validate_profile_name("  Windows HDR Calibration.icc ").ok(),
+            Some("Windows HDR Calibration.icc")
+        );
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("   ").is_err());
+        assert!(validate_profile_name(r"C:\Profiles\hdr.icc").is_err());
+        assert!(validate_profile_name("profiles/hdr.icc").is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! Windows build compatibility matrix
+//!
+//! The display configuration API grew over several Windows releases. Windows 11 24H2
+//! separates HDR from wide color gamut in `DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2` and
+//! can switch HDR directly; older builds only report "advanced color", which they also
+//! report for wide color gamut panels that run in advanced color mode without HDR.
+//!
+//! [`COMPAT_MATRIX`] lists what each range of builds can do, and [`LegacyAdvancedColor`]
+//! is the shim that reads HDR state out of the legacy advanced color bits. Keeping both
+//! as plain data lets the pre-24H2 path be tested on any machine.
+//!
+//! Insider builds newer than [`LATEST_KNOWN_BUILD`] and Windows Server editions are not
+//! in the matrix's tested range. `EasyHDR` still runs on them, but the controller probes
+//! which advanced color API actually answers instead of trusting the build number.
+
+use crate::hdr::WindowsVersion;
+
+/// Oldest build `EasyHDR` supports (Windows 10 21H2)
+pub const MIN_SUPPORTED_BUILD: u32 = 19044;
+
+/// Newest release build in [`COMPAT_MATRIX`] (Windows 11 25H2)
+pub const LATEST_KNOWN_BUILD: u32 = 26200;
+
+/// How well `EasyHDR` supports a Windows installation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildSupport {
+    /// A tested client build
+    Supported,
+    /// An Insider build newer than [`LATEST_KNOWN_BUILD`] or a Server edition; runs with
+    /// probed capabilities
+    Untested,
+    /// Older than [`MIN_SUPPORTED_BUILD`]; only runs when the user allows it
+    TooOld,
+}
+
+impl BuildSupport {
+    /// Classify a build number, `server` being whether the edition is Windows Server
+    pub fn classify(build_number: u32, server: bool) -> Self {
+        if build_number < MIN_SUPPORTED_BUILD {
+            Self::TooOld
+        } else if server || build_number > LATEST_KNOWN_BUILD {
+            Self::Untested
+        } else {
+            Self::Supported
+        }
+    }
+
+    /// Whether the advanced color APIs should be probed instead of chosen by build number
+    pub fn needs_probe(self) -> bool {
+        self != Self::Supported
+    }
+}
+
+/// What the display APIs of a Windows build range support
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Each flag is an independent display API of the build range"
+)]
+pub struct Capabilities {
+    /// Whether `EasyHDR` supports the build at all
+    pub supported: bool,
+    /// `DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2` and `DISPLAYCONFIG_SET_HDR_STATE` exist
+    pub hdr_state_api: bool,
+    /// Per-application Auto HDR overrides are available
+    pub auto_hdr: bool,
+    /// Display color profiles can be associated per source (`ColorProfileAddDisplayAssociation`)
+    pub color_profile_associations: bool,
+    /// Wide color gamut displays report advanced color, so HDR must be told apart with
+    /// [`LegacyAdvancedColor`]
+    pub wide_color_reported_as_advanced_color: bool,
+}
+
+/// Capabilities by first build of each range, in ascending order
+///
+/// A build uses the last entry whose first build is not above it.
+pub const COMPAT_MATRIX: &[(u32, Capabilities)] = &[
+    // Windows 10 before 21H2
+    (
+        0,
+        Capabilities {
+            supported: false,
+            hdr_state_api: false,
+            auto_hdr: false,
+            color_profile_associations: false,
+            wide_color_reported_as_advanced_color: true,
+        },
+    ),
+    // Windows 10 2004 through 21H1: color profile associations, still unsupported
+    (
+        19041,
+        Capabilities {
+            supported: false,
+            hdr_state_api: false,
+            auto_hdr: false,
+            color_profile_associations: true,
+            wide_color_reported_as_advanced_color: true,
+        },
+    ),
+    // Windows 10 21H2 and 22H2
+    (
+        MIN_SUPPORTED_BUILD,
+        Capabilities {
+            supported: true,
+            hdr_state_api: false,
+            auto_hdr: false,
+            color_profile_associations: true,
+            wide_color_reported_as_advanced_color: true,
+        },
+    ),
+    // Windows 11 21H2 through 23H2
+    (
+        22000,
+        Capabilities {
+            supported: true,
+            hdr_state_api: false,
+            auto_hdr: true,
+            color_profile_associations: true,
+            wide_color_reported_as_advanced_color: true,
+        },
+    ),
+    // Windows 11 24H2 and later
+    (
+        26100,
+        Capabilities {
+            supported: true,
+            hdr_state_api: true,
+            auto_hdr: true,
+            color_profile_associations: true,
+            wide_color_reported_as_advanced_color: false,
+        },
+    ),
+];
+
+impl Capabilities {
+    /// Look up the capabilities of a Windows build
+    pub fn for_build(build_number: u32) -> Self {
+        COMPAT_MATRIX
+            .iter()
+            .rev()
+            .find(|(first_build, _)| *first_build <= build_number)
+            .map_or(COMPAT_MATRIX[0].1, |(_, capabilities)| *capabilities)
+    }
+
+    /// Look up the capabilities of the oldest supported build of a Windows version
+    pub fn for_version(version: WindowsVersion) -> Self {
+        Self::for_build(match version {
+            WindowsVersion::Windows10 => 19044,
+            WindowsVersion::Windows11 => 22000,
+            WindowsVersion::Windows11_24H2 => 26100,
+        })
+    }
+}
+
+/// Advanced color bits of the legacy `DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO`
+///
+/// On builds before 24H2 a display with `wideColorEnforced` set is a wide color gamut
+/// display in advanced color mode, not an HDR display, even though it reports advanced
+/// color as supported and enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Fields map 1:1 to the bits of the Windows structure"
+)]
+pub struct LegacyAdvancedColor {
+    /// Bit 0: `advancedColorSupported`
+    pub supported: bool,
+    /// Bit 1: `advancedColorEnabled`
+    pub enabled: bool,
+    /// Bit 2: `wideColorEnforced`
+    pub wide_color_enforced: bool,
+    /// Bit 3: `advancedColorForceDisabled`
+    pub force_disabled: bool,
+}
+
+impl LegacyAdvancedColor {
+    /// Decode the bitfield of the legacy structure
+    pub const fn from_bits(value: u32) -> Self {
+        Self {
+            supported: value & 0x1 != 0,
+            enabled: value & 0x2 != 0,
+            wide_color_enforced: value & 0x4 != 0,
+            force_disabled: value & 0x8 != 0,
+        }
+    }
+
+    /// Whether the display supports HDR rather than only wide color gamut
+    pub const fn hdr_supported(self) -> bool {
+        self.supported && !self.wide_color_enforced
+    }
+
+    /// Whether HDR (not wide color gamut) is currently on
+    pub const fn hdr_enabled(self) -> bool {
+        self.hdr_supported() && self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hdr::AutoHdrController;
+
+    /// Release builds and the capabilities expected for each
+    ///
+    /// Columns: build, supported, HDR state API, Auto HDR, color profile associations,
+    /// wide color reported as advanced color.
+    const EXPECTED: &[(u32, bool, bool, bool, bool, bool)] = &[
+        (17763, false, false, false, false, true),
+        (19041, false, false, false, true, true),
+        (19043, false, false, false, true, true),
+        (19044, true, false, false, true, true),
+        (19045, true, false, false, true, true),
+        (22000, true, false, true, true, true),
+        (22621, true, false, true, true, true),
+        (22631, true, false, true, true, true),
+        (26100, true, true, true, true, false),
+        (26200, true, true, true, true, false),
+    ];
+
+    #[test]
+    fn test_matrix_is_sorted() {
+        assert_eq!(COMPAT_MATRIX[0].0, 0);
+        assert!(COMPAT_MATRIX.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_capabilities_by_build() {
+        for &(build, supported, hdr_state_api, auto_hdr, profiles, wide_color) in EXPECTED {
+            assert_eq!(
+                Capabilities::for_build(build),
+                Capabilities {
+                    supported,
+                    hdr_state_api,
+                    auto_hdr,
+                    color_profile_associations: profiles,
+                    wide_color_reported_as_advanced_color: wide_color,
+                },
+                "build {build}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_matrix_agrees_with_version_detection() {
+        // The controller picks APIs by WindowsVersion, so the matrix must not drift from it
+        for &(build, ..) in EXPECTED {
+            let capabilities = Capabilities::for_build(build);
+            let version = WindowsVersion::parse_build_number(build);
+            assert_eq!(
+                capabilities.hdr_state_api,
+                version == WindowsVersion::Windows11_24H2,
+                "build {build}"
+            );
+            assert_eq!(
+                capabilities.auto_hdr,
+                AutoHdrController::new(version).is_supported(),
+                "build {build}"
+            );
+            if capabilities.supported {
+                assert_eq!(Capabilities::for_version(version), capabilities);
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_support() {
+        assert_eq!(BuildSupport::classify(17763, false), BuildSupport::TooOld);
+        assert_eq!(BuildSupport::classify(19043, false), BuildSupport::TooOld);
+        assert_eq!(
+            BuildSupport::classify(19044, false),
+            BuildSupport::Supported
+        );
+        assert_eq!(
+            BuildSupport::classify(26100, false),
+            BuildSupport::Supported
+        );
+        assert_eq!(
+            BuildSupport::classify(LATEST_KNOWN_BUILD, false),
+            BuildSupport::Supported
+        );
+
+        // Insider Canary and Windows Server 2022/2025 run with probed capabilities
+        assert_eq!(BuildSupport::classify(27723, false), BuildSupport::Untested);
+        assert_eq!(BuildSupport::classify(20348, true), BuildSupport::Untested);
+        assert_eq!(BuildSupport::classify(26100, true), BuildSupport::Untested);
+        // Server 2019 is still too old
+        assert_eq!(BuildSupport::classify(17763, true), BuildSupport::TooOld);
+
+        assert!(!BuildSupport::Supported.needs_probe());
+        assert!(BuildSupport::Untested.needs_probe());
+        assert!(BuildSupport::TooOld.needs_probe());
+    }
+
+    #[test]
+    fn test_legacy_bits_on_hdr_display() {
+        // HDR display with HDR off, then on
+        let off = LegacyAdvancedColor::from_bits(0x1);
+        assert!(off.hdr_supported());
+        assert!(!off.hdr_enabled());
+
+        let on = LegacyAdvancedColor::from_bits(0x3);
+        assert!(on.hdr_supported());
+        assert!(on.hdr_enabled());
+    }
+
+    #[test]
+    fn test_legacy_bits_on_wide_color_display() {
+        // 19044 reports a wide color gamut display in advanced color mode as supported and
+        // enabled; only wideColorEnforced tells it apart from HDR
+        let wide_color = LegacyAdvancedColor::from_bits(0x7);
+        assert!(wide_color.supported && wide_color.enabled);
+        assert!(!wide_color.hdr_supported());
+        assert!(!wide_color.hdr_enabled());
+    }
+
+    #[test]
+    fn test_legacy_bits_on_sdr_display() {
+        let sdr = LegacyAdvancedColor::from_bits(0x0);
+        assert!(!sdr.hdr_supported());
+        assert!(!sdr.hdr_enabled());
+
+        // Enabled without supported is not HDR either
+        assert!(!LegacyAdvancedColor::from_bits(0x2).hdr_enabled());
+
+        let force_disabled = LegacyAdvancedColor::from_bits(0x9);
+        assert!(force_disabled.force_disabled);
+        assert!(force_disabled.hdr_supported());
+        assert!(!force_disabled.hdr_enabled());
+    }
+}
@@ -4,26 +4,102 @@
 //! for Windows displays.
 
 use crate::error::Result;
-use crate::hdr::WindowsVersion;
-use crate::hdr::windows_api::LUID;
+use crate::hdr::capabilities::{DisplayCapabilities, OutputColorimetry};
+use crate::hdr::edid::HdrStaticMetadata;
+use crate::hdr::quirks::GpuInfo;
+use crate::hdr::windows_api::{LUID, output_technology};
+use crate::hdr::{BuildSupport, ColorMode, HdrControl, LinkSignal, VcpCommand, WindowsVersion};
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
 #[cfg(windows)]
 use crate::hdr::windows_api::{
     DISPLAYCONFIG_ADVANCED_COLOR_MODE, DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO,
     DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2, DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO,
-    DisplayConfigGetDeviceInfo, DisplayConfigSetDeviceInfo, GetDisplayConfigBufferSizes,
-    QDC_ONLY_ACTIVE_PATHS, QueryDisplayConfig,
+    DISPLAYCONFIG_SOURCE_DEVICE_NAME, DISPLAYCONFIG_TARGET_DEVICE_NAME,
+    GetDisplayConfigBufferSizes, QDC_ONLY_ACTIVE_PATHS, QueryDisplayConfig,
 };
 
 #[cfg(windows)]
+use crate::hdr::api_trace::{display_config_get_device_info, display_config_set_device_info};
+
 use crate::error::EasyHdrError;
 
+#[cfg(windows)]
+use crate::hdr::compat::LegacyAdvancedColor;
+
+#[cfg(windows)]
+use crate::hdr::quirks::{self, Workarounds};
+
+#[cfg(windows)]
+use crate::hdr::{ColorEncoding, DisplayModeController};
+
 #[cfg(windows)]
 use tracing::error;
 
+/// Physical connector a display target is attached through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ConnectorType {
+    /// VGA
+    Vga,
+    /// DVI
+    Dvi,
+    /// HDMI
+    Hdmi,
+    /// External `DisplayPort`
+    DisplayPort,
+    /// `DisplayPort` over USB-C
+    UsbC,
+    /// Built-in panel (eDP, LVDS)
+    Internal,
+    /// Miracast wireless display
+    Wireless,
+    /// Any other or unknown connector
+    #[default]
+    Other,
+}
+
+impl ConnectorType {
+    /// Classify a `DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY` value
+    pub fn from_output_technology(value: u32) -> Self {
+        match value {
+            output_technology::HD15 => Self::Vga,
+            output_technology::DVI => Self::Dvi,
+            output_technology::HDMI => Self::Hdmi,
+            output_technology::DISPLAYPORT_EXTERNAL => Self::DisplayPort,
+            output_technology::DISPLAYPORT_USB_TUNNEL => Self::UsbC,
+            output_technology::LVDS
+            | output_technology::DISPLAYPORT_EMBEDDED
+            | output_technology::UDI_EMBEDDED
+            | output_technology::INTERNAL => Self::Internal,
+            output_technology::MIRACAST => Self::Wireless,
+            _ => Self::Other,
+        }
+    }
+
+    /// Human-readable connector name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Vga => "VGA",
+            Self::Dvi => "DVI",
+            Self::Hdmi => "HDMI",
+            Self::DisplayPort => "DisplayPort",
+            Self::UsbC => "USB-C",
+            Self::Internal => "Internal",
+            Self::Wireless => "Wireless",
+            Self::Other => "Other",
+        }
+    }
+}
+
+impl std::fmt::Display for ConnectorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// Represents a display target
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct DisplayTarget {
     /// Adapter LUID
     pub adapter_id: LUID,
@@ -31,13 +107,55 @@ pub struct DisplayTarget {
     pub target_id: u32,
     /// Whether this display supports HDR
     pub supports_hdr: bool,
+    /// Monitor friendly name (e.g. "DELL U3223QE"), if Windows reports one
+    pub friendly_name: Option<String>,
+    /// Connector the display is attached through
+    pub connector: ConnectorType,
+    /// EDID manufacturer PNP ID (e.g. "DEL"), if the EDID IDs are valid
+    pub edid_manufacturer: Option<String>,
+    /// EDID product code, if the EDID IDs are valid
+    pub edid_product_code: Option<u16>,
+    /// Identity that survives reboots, if the EDID IDs are valid
+    pub identity: Option<DisplayIdentity>,
+    /// GDI device name of the display's source (e.g. `\\.\DISPLAY1`), used for DDC/CI
+    pub gdi_device_name: Option<String>,
+    /// Source ID of the display's path, used for color profile associations
+    pub source_id: u32,
+    /// HDR capabilities the panel advertises in its EDID, if it has an HDR metadata block
+    pub edid_hdr: Option<HdrStaticMetadata>,
+    /// Bit depth, color encoding, luminance range and primaries, if any could be read
+    pub capabilities: Option<DisplayCapabilities>,
+    /// Whether the HDR state is read from the DXGI output color space, because Windows
+    /// rejects the advanced color queries for this display
+    pub hdr_state_from_dxgi: bool,
+}
+
+impl DisplayTarget {
+    /// Name to show users for this display
+    ///
+    /// Falls back to "Built-in display" for internal panels without an EDID name and to
+    /// "Display <target id>" otherwise.
+    pub fn display_name(&self) -> String {
+        match &self.friendly_name {
+            Some(name) => name.clone(),
+            None if self.connector == ConnectorType::Internal => "Built-in display".to_string(),
+            None => format!("Display {}", self.target_id),
+        }
+    }
+
+    /// Hint for the user when the EDID and Windows disagree about HDR support
+    pub fn hdr_discrepancy(&self) -> Option<&'static str> {
+        self.edid_hdr?.discrepancy(self.supports_hdr)
+    }
 }
 
 impl std::fmt::Display for DisplayTarget {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Display(adapter={:#x}:{:#x}, target={}, HDR={})",
+            "{} ({}, adapter={:#x}:{:#x}, target={}, HDR={})",
+            self.display_name(),
+            self.connector,
             self.adapter_id.LowPart,
             self.adapter_id.HighPart,
             self.target_id,
@@ -46,6 +164,143 @@ impl std::fmt::Display for DisplayTarget {
     }
 }
 
+/// Stable identity of a physical monitor
+///
+/// Adapter LUIDs (and sometimes target IDs) change across reboots, so anything stored
+/// per display is keyed on the monitor itself: its EDID manufacturer and product code
+/// plus a hash of its serial number. Monitors without a readable serial are hashed by
+/// their device instance ID instead, which is stable for a given monitor and port.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DisplayIdentity {
+    /// EDID manufacturer PNP ID (e.g. "DEL")
+    pub manufacturer: String,
+    /// EDID product code
+    pub product_code: u16,
+    /// FNV-1a hash of the serial number (or device instance ID)
+    pub serial_hash: u64,
+}
+
+impl DisplayIdentity {
+    /// Build an identity from EDID IDs and the raw serial bytes
+    pub fn new(manufacturer: impl Into<String>, product_code: u16, serial: &[u8]) -> Self {
+        Self {
+            manufacturer: manufacturer.into(),
+            product_code,
+            serial_hash: fnv1a_64(serial),
+        }
+    }
+}
+
+impl std::fmt::Display for DisplayIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{:04X}-{:016x}",
+            self.manufacturer, self.product_code, self.serial_hash
+        )
+    }
+}
+
+/// 64-bit FNV-1a hash
+///
+/// Used instead of `DefaultHasher`, whose output may change between Rust releases and
+/// would invalidate persisted identities.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Extract the serial number from an EDID base block
+///
+/// Prefers the ASCII serial descriptor (tag `0xFF`) and falls back to the 32-bit serial
+/// number in the vendor/product block. Returns `None` for blocks that are too short,
+/// lack the EDID header, or carry no serial at all.
+pub fn edid_serial(edid: &[u8]) -> Option<Vec<u8>> {
+    const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+    const DESCRIPTOR_OFFSETS: [usize; 4] = [54, 72, 90, 108];
+
+    if edid.len() < 128 || edid[..8] != EDID_HEADER {
+        return None;
+    }
+
+    let descriptor_serial = DESCRIPTOR_OFFSETS.iter().find_map(|&offset| {
+        let descriptor = &edid[offset..offset + 18];
+        if descriptor[..3] != [0, 0, 0] || descriptor[3] != 0xFF {
+            return None;
+        }
+        let text = &descriptor[5..];
+        let end = text.iter().position(|&b| b == b'\n').unwrap_or(text.len());
+        let serial = text[..end].trim_ascii();
+        (!serial.is_empty()).then(|| serial.to_vec())
+    });
+
+    descriptor_serial.or_else(|| {
+        let numeric = &edid[12..16];
+        (numeric != [0, 0, 0, 0]).then(|| numeric.to_vec())
+    })
+}
+
+/// Extract the device instance ID from a monitor device interface path
+///
+/// `\\?\DISPLAY#DEL4141#5&2a3b&0&UID4355#{e6f07b5f-...}` becomes
+/// `DISPLAY\DEL4141\5&2a3b&0&UID4355`, the monitor's key under
+/// `HKLM\SYSTEM\CurrentControlSet\Enum`.
+pub fn device_instance_id(device_path: &str) -> Option<String> {
+    let path = device_path.strip_prefix(r"\\?\").unwrap_or(device_path);
+    let mut parts = path.split('#');
+    let (Some(class), Some(hardware_id), Some(instance)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    Some(format!(r"{class}\{hardware_id}\{instance}"))
+}
+
+/// Read a monitor's EDID from the registry
+#[cfg(windows)]
+fn read_edid(instance_id: &str) -> Option<Vec<u8>> {
+    use winreg::RegKey;
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(format!(
+            r"SYSTEM\CurrentControlSet\Enum\{instance_id}\Device Parameters"
+        ))
+        .ok()?
+        .get_raw_value("EDID")
+        .ok()
+        .map(|value| value.bytes.into_owned())
+}
+
+/// Decode an EDID manufacturer ID into its three-letter PNP ID
+///
+/// The ID is stored big-endian in the EDID as three 5-bit letters (1 = 'A').
+/// Returns `None` if any letter is out of range.
+pub fn decode_pnp_id(edid_manufacture_id: u16) -> Option<String> {
+    let id = edid_manufacture_id.swap_bytes();
+    [10u16, 5, 0]
+        .into_iter()
+        .map(|shift| match (id >> shift) & 0x1f {
+            letter @ 1..=26 => char::from_u32(u32::from('A') + u32::from(letter) - 1),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a display supports HDR, for when `DisplayConfigGetDeviceInfo` fails
+///
+/// Some driver combinations reject the advanced color queries. DXGI reporting the HDR10
+/// color space for the display's output proves HDR support, and its state is then read
+/// from DXGI as well. The EDID alone is not trusted, since the cable or port may not
+/// carry HDR even if the panel does, so the display is otherwise assumed to be SDR only.
+pub fn fallback_hdr_support(output: Option<&OutputColorimetry>) -> bool {
+    output.is_some_and(|output| output.hdr_color_space)
+}
+
 /// HDR controller
 pub struct HdrController {
     /// Windows version
@@ -55,6 +310,14 @@ pub struct HdrController {
     /// Uses `SmallVec` with inline capacity of 4 to avoid heap allocation for typical
     /// 1-4 display setups.
     display_cache: SmallVec<[DisplayTarget; 4]>,
+    /// Whether display changes are only logged, see [`HdrController::set_dry_run`]
+    dry_run: bool,
+    /// Display adapters by LUID, for driver quirk workarounds
+    #[cfg_attr(
+        not(windows),
+        expect(dead_code, reason = "Field used only on Windows when setting HDR")
+    )]
+    gpus: std::collections::HashMap<(u32, i32), GpuInfo>,
 }
 
 impl HdrController {
@@ -64,14 +327,67 @@ impl HdrController {
         let mut controller = Self {
             windows_version,
             display_cache: SmallVec::new(),
+            dry_run: false,
+            gpus: std::collections::HashMap::new(),
         };
 
         // Enumerate displays on creation
         controller.enumerate_displays()?;
 
+        // Insider and Server builds outside the tested range may not match the API set
+        // their build number suggests
+        let build_number = WindowsVersion::get_build_number().unwrap_or(0);
+        if BuildSupport::classify(build_number, WindowsVersion::is_server()).needs_probe() {
+            controller.probe_windows_version();
+        }
+
         Ok(controller)
     }
 
+    /// Pick the advanced color API by what the first display answers to
+    ///
+    /// `DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2` succeeding means the 24H2 API set is
+    /// present regardless of the build number. Without displays the detected version is
+    /// kept.
+    #[cfg_attr(
+        not(windows),
+        expect(clippy::unused_self, reason = "Probing uses Windows APIs only")
+    )]
+    #[cfg_attr(
+        windows,
+        expect(unsafe_code, reason = "Windows FFI for advanced color API probing")
+    )]
+    fn probe_windows_version(&mut self) {
+        #[cfg(windows)]
+        {
+            use tracing::info;
+
+            let Some(target) = self.display_cache.first() else {
+                return;
+            };
+
+            let mut color_info =
+                DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2::new(target.adapter_id, target.target_id);
+            // SAFETY: the header is initialized with the type and size of `color_info`
+            let result = unsafe {
+                display_config_get_device_info(std::ptr::addr_of_mut!(color_info.header).cast())
+            };
+
+            let probed = if result == 0 {
+                WindowsVersion::Windows11_24H2
+            } else if self.windows_version == WindowsVersion::Windows11_24H2 {
+                WindowsVersion::Windows11
+            } else {
+                self.windows_version
+            };
+            info!(
+                "Untested Windows build: probed advanced color API (result {}), using {:?} instead of {:?}",
+                result, probed, self.windows_version
+            );
+            self.windows_version = probed;
+        }
+    }
+
     /// Create a mock HDR controller for testing
     ///
     /// Returns a minimal `HdrController` with empty display cache.
@@ -83,9 +399,24 @@ impl HdrController {
             windows_version: WindowsVersion::Windows11,
             // Empty display cache - profiling test doesn't exercise HDR operations
             display_cache: SmallVec::new(),
+            dry_run: false,
+            gpus: std::collections::HashMap::new(),
         })
     }
 
+    /// Log HDR, wide color gamut and color profile changes instead of making them
+    ///
+    /// Lets a new configuration be tried on a machine without the screen flickering on
+    /// every toggle. Reads still query the displays.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Whether display changes are only logged
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
     /// Get a reference to the display cache
     ///
     /// Returns a slice of all enumerated display targets.
@@ -93,42 +424,31 @@ impl HdrController {
         &self.display_cache
     }
 
-    /// Get the detected Windows version
+    /// Find the live display target for a stable display identity
     ///
-    /// Returns the Windows version that was detected during controller initialization.
-    pub fn get_windows_version(&self) -> WindowsVersion {
-        self.windows_version
+    /// Returns `None` if the monitor is not currently connected.
+    pub fn resolve_display(&self, identity: &DisplayIdentity) -> Option<&DisplayTarget> {
+        self.display_cache
+            .iter()
+            .find(|target| target.identity.as_ref() == Some(identity))
     }
 
-    /// Detect the current HDR state from the system.
+    /// Find the live display targets of a display group
     ///
-    /// Returns `true` if HDR is enabled on any HDR-capable display, `false` otherwise.
-    pub fn detect_current_hdr_state(&self) -> bool {
-        use tracing::{debug, warn};
-
-        let displays = self.get_display_cache();
-
-        for disp in displays.iter().filter(|d| d.supports_hdr) {
-            match self.is_hdr_enabled(disp) {
-                Ok(enabled) => {
-                    if enabled {
-                        debug!(
-                            "Display (adapter={:#x}:{:#x}, target={}) has HDR enabled",
-                            disp.adapter_id.LowPart, disp.adapter_id.HighPart, disp.target_id
-                        );
-                        return true;
-                    }
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to check HDR state for display (adapter={:#x}:{:#x}, target={}): {}",
-                        disp.adapter_id.LowPart, disp.adapter_id.HighPart, disp.target_id, e
-                    );
-                }
-            }
-        }
+    /// Monitors of the group that are not currently connected are left out.
+    pub fn resolve_displays(&self, identities: &[DisplayIdentity]) -> Vec<DisplayTarget> {
+        identities
+            .iter()
+            .filter_map(|identity| self.resolve_display(identity))
+            .cloned()
+            .collect()
+    }
 
-        false
+    /// Get the detected Windows version
+    ///
+    /// Returns the Windows version that was detected during controller initialization.
+    pub fn get_windows_version(&self) -> WindowsVersion {
+        self.windows_version
     }
 
     /// Enumerate all active displays and detect HDR support.
@@ -148,7 +468,7 @@ impl HdrController {
     pub fn enumerate_displays(&mut self) -> Result<Vec<DisplayTarget>> {
         #[cfg(windows)]
         {
-            use tracing::{debug, info};
+            use tracing::{debug, info, warn};
 
             let mut path_count: u32 = 0;
             let mut mode_count: u32 = 0;
@@ -210,6 +530,24 @@ impl HdrController {
 
             self.display_cache.clear();
             self.display_cache.reserve(path_count as usize);
+            let colorimetry = crate::hdr::capabilities::output_colorimetry();
+            self.gpus = quirks::detect_gpus();
+            for gpu in self.gpus.values() {
+                let applied: Vec<_> = quirks::QUIRKS
+                    .iter()
+                    .filter(|quirk| quirk.applies_to(gpu))
+                    .map(|quirk| quirk.description)
+                    .collect();
+                if applied.is_empty() {
+                    debug!("Display adapter: {}", gpu);
+                } else {
+                    info!(
+                        "Display adapter {} needs workarounds: {}",
+                        gpu,
+                        applied.join("; ")
+                    );
+                }
+            }
 
             for (index, path) in paths.iter().enumerate() {
                 debug!(
@@ -224,8 +562,12 @@ impl HdrController {
                 let mut target = DisplayTarget {
                     adapter_id: path.targetInfo.adapterId,
                     target_id: path.targetInfo.id,
-                    supports_hdr: false,
+                    source_id: path.sourceInfo.id,
+                    ..Default::default()
                 };
+                Self::query_target_name(&mut target);
+                target.gdi_device_name =
+                    Self::query_source_name(path.sourceInfo.adapterId, path.sourceInfo.id);
 
                 match self.is_hdr_supported(&target) {
                     Ok(supported) => {
@@ -240,12 +582,19 @@ impl HdrController {
                         );
                     }
                     Err(e) => {
-                        debug!(
-                            "Display {}: Failed to detect HDR support: {}. Assuming not supported.",
-                            index, e
+                        let output = target
+                            .gdi_device_name
+                            .as_ref()
+                            .and_then(|name| colorimetry.get(name));
+                        target.supports_hdr = fallback_hdr_support(output);
+                        target.hdr_state_from_dxgi = target.supports_hdr;
+                        warn!(
+                            "Display {}: Failed to detect HDR support: {}. Falling back to DXGI: HDR supported={}",
+                            index, e, target.supports_hdr
                         );
                     }
                 }
+                target.capabilities = Self::query_capabilities(&target, &colorimetry);
 
                 self.display_cache.push(target);
             }
@@ -304,7 +653,7 @@ impl HdrController {
                     );
 
                     unsafe {
-                        let result = DisplayConfigGetDeviceInfo(
+                        let result = display_config_get_device_info(
                             std::ptr::addr_of_mut!(color_info.header).cast(),
                         );
                         debug!(
@@ -383,6 +732,170 @@ impl HdrController {
         }
     }
 
+    /// Fill in the friendly name, connector type and EDID IDs of a display target.
+    ///
+    /// Failures are logged and leave the fields at their defaults, since names are only
+    /// used for display.
+    ///
+    /// # Safety
+    ///
+    /// Structure initialized with correct size/type fields. Return code checked before
+    /// data access.
+    #[cfg(windows)]
+    #[expect(unsafe_code, reason = "Windows FFI for display target name query")]
+    fn query_target_name(target: &mut DisplayTarget) {
+        use tracing::debug;
+
+        let mut name = DISPLAYCONFIG_TARGET_DEVICE_NAME::new(target.adapter_id, target.target_id);
+
+        unsafe {
+            let result = display_config_get_device_info(std::ptr::addr_of_mut!(name.header).cast());
+            if result != 0 {
+                debug!(
+                    "DisplayConfigGetDeviceInfo (GET_TARGET_NAME) failed for adapter={:#x}:{:#x}, target={}: error code {result}",
+                    target.adapter_id.LowPart, target.adapter_id.HighPart, target.target_id
+                );
+                return;
+            }
+        }
+
+        target.friendly_name = name.friendly_name();
+        target.connector = ConnectorType::from_output_technology(name.outputTechnology);
+        if name.edidIdsValid() {
+            target.edid_manufacturer = decode_pnp_id(name.edidManufactureId);
+            target.edid_product_code = Some(name.edidProductCodeId);
+        }
+
+        let instance_id = name
+            .monitor_device_path()
+            .as_deref()
+            .and_then(device_instance_id);
+        let edid = instance_id.as_deref().and_then(read_edid);
+        target.edid_hdr = edid
+            .as_deref()
+            .and_then(crate::hdr::edid::hdr_static_metadata);
+
+        if let Some(manufacturer) = &target.edid_manufacturer {
+            let serial = edid
+                .as_deref()
+                .and_then(edid_serial)
+                .or_else(|| instance_id.map(String::into_bytes));
+            target.identity = serial.map(|serial| {
+                DisplayIdentity::new(manufacturer.clone(), name.edidProductCodeId, &serial)
+            });
+        }
+
+        debug!(
+            "Display target {}: name={:?} (from EDID: {}), connector={}, EDID={:?}/{:?}, identity={:?}, EDID HDR={:?}",
+            target.target_id,
+            target.friendly_name,
+            name.friendlyNameFromEdid(),
+            target.connector,
+            target.edid_manufacturer,
+            target.edid_product_code,
+            target.identity.as_ref().map(ToString::to_string),
+            target.edid_hdr.as_ref().map(HdrStaticMetadata::summary)
+        );
+    }
+
+    /// Query the GDI device name of a display source
+    ///
+    /// # Safety
+    ///
+    /// Structure initialized with correct size/type fields. Return code checked before
+    /// data access.
+    #[cfg(windows)]
+    #[expect(unsafe_code, reason = "Windows FFI for display source name query")]
+    fn query_source_name(adapter_id: LUID, source_id: u32) -> Option<String> {
+        use tracing::debug;
+
+        let mut name = DISPLAYCONFIG_SOURCE_DEVICE_NAME::new(adapter_id, source_id);
+
+        unsafe {
+            let result = display_config_get_device_info(std::ptr::addr_of_mut!(name.header).cast());
+            if result != 0 {
+                debug!(
+                    "DisplayConfigGetDeviceInfo (GET_SOURCE_NAME) failed for adapter={:#x}:{:#x}, source={}: error code {result}",
+                    adapter_id.LowPart, adapter_id.HighPart, source_id
+                );
+                return None;
+            }
+        }
+
+        name.gdi_device_name()
+    }
+
+    /// Collect the signal format and DXGI colorimetry of a display
+    ///
+    /// `colorimetry` maps GDI device names to what DXGI reports for them, see
+    /// [`crate::hdr::capabilities::output_colorimetry`]. Returns `None` if nothing could be
+    /// read.
+    #[cfg(windows)]
+    fn query_capabilities(
+        target: &DisplayTarget,
+        colorimetry: &std::collections::HashMap<String, OutputColorimetry>,
+    ) -> Option<DisplayCapabilities> {
+        use tracing::{debug, info};
+
+        let signal = Self::read_signal_format(target)
+            .map_err(|e| debug!("Failed to read the signal format of {}: {}", target, e))
+            .ok();
+        let capabilities = DisplayCapabilities {
+            bits_per_channel: signal.map(|(bits, _)| bits),
+            color_encoding: signal.and_then(|(_, encoding)| encoding),
+            colorimetry: target
+                .gdi_device_name
+                .as_ref()
+                .and_then(|name| colorimetry.get(name))
+                .copied(),
+        };
+        if capabilities == DisplayCapabilities::default() {
+            return None;
+        }
+
+        info!(
+            "Display {} capabilities: {}",
+            target.display_name(),
+            capabilities.summary()
+        );
+        if let Some(colorimetry) = &capabilities.colorimetry {
+            debug!("Display {} colorimetry: {}", target.target_id, colorimetry);
+        }
+        Some(capabilities)
+    }
+
+    /// Read the bits per channel and color encoding a display is driven with
+    ///
+    /// The encoding is `None` for values newer than [`ColorEncoding`] knows.
+    ///
+    /// # Safety
+    ///
+    /// Structure initialized with correct size/type fields. IDs from validated `DisplayTarget`.
+    /// Return code checked before data access.
+    #[cfg(windows)]
+    #[expect(unsafe_code, reason = "Windows FFI for advanced color info query")]
+    fn read_signal_format(target: &DisplayTarget) -> Result<(u32, Option<ColorEncoding>)> {
+        let mut color_info =
+            DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO::new(target.adapter_id, target.target_id);
+
+        unsafe {
+            let result =
+                display_config_get_device_info(std::ptr::addr_of_mut!(color_info.header).cast());
+            if result != 0 {
+                return Err(EasyHdrError::HdrControlFailed(
+                    crate::error::StringError::new(format!(
+                        "Failed to get advanced color info (legacy): error code {result}",
+                    )),
+                ));
+            }
+        }
+
+        Ok((
+            color_info.bitsPerColorChannel,
+            ColorEncoding::from_raw(color_info.colorEncoding),
+        ))
+    }
+
     /// Check HDR support using legacy API (Windows 10/11, or fallback for 24H2+).
     ///
     /// # Safety
@@ -406,7 +919,7 @@ impl HdrController {
 
         unsafe {
             let result =
-                DisplayConfigGetDeviceInfo(std::ptr::addr_of_mut!(color_info.header).cast());
+                display_config_get_device_info(std::ptr::addr_of_mut!(color_info.header).cast());
             debug!(
                 "DisplayConfigGetDeviceInfo (GET_ADVANCED_COLOR_INFO) returned: result={result}",
             );
@@ -428,7 +941,7 @@ impl HdrController {
         let wide_color_enforced = color_info.wideColorEnforced();
         let advanced_color_force_disabled = color_info.advancedColorForceDisabled();
 
-        let supported = advanced_color_supported && !wide_color_enforced;
+        let supported = LegacyAdvancedColor::from_bits(color_info.value).hdr_supported();
 
         debug!(
             "Display (adapter={:#x}:{:#x}, target={}) - Legacy API results:",
@@ -477,6 +990,10 @@ impl HdrController {
         {
             use tracing::debug;
 
+            if target.hdr_state_from_dxgi {
+                return Self::is_hdr_enabled_dxgi(target);
+            }
+
             match self.windows_version {
                 WindowsVersion::Windows11_24H2 => {
                     let mut color_info = DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2::new(
@@ -485,7 +1002,7 @@ impl HdrController {
                     );
 
                     unsafe {
-                        let result = DisplayConfigGetDeviceInfo(
+                        let result = display_config_get_device_info(
                             std::ptr::addr_of_mut!(color_info.header).cast(),
                         );
                         if result != 0 {
@@ -525,6 +1042,23 @@ impl HdrController {
         }
     }
 
+    /// Check HDR enabled state from the DXGI output color space
+    ///
+    /// Used for displays whose advanced color queries fail, see [`fallback_hdr_support`].
+    #[cfg(windows)]
+    fn is_hdr_enabled_dxgi(target: &DisplayTarget) -> Result<bool> {
+        target
+            .gdi_device_name
+            .as_ref()
+            .and_then(|name| crate::hdr::capabilities::output_colorimetry().remove(name))
+            .map(|output| output.hdr_color_space)
+            .ok_or_else(|| {
+                EasyHdrError::HdrControlFailed(crate::error::StringError::new(format!(
+                    "DXGI reports no output for {target}"
+                )))
+            })
+    }
+
     /// Check HDR enabled state using legacy API (Windows 10/11, or fallback for 24H2+).
     ///
     /// # Safety
@@ -545,7 +1079,7 @@ impl HdrController {
 
         unsafe {
             let result =
-                DisplayConfigGetDeviceInfo(std::ptr::addr_of_mut!(color_info.header).cast());
+                display_config_get_device_info(std::ptr::addr_of_mut!(color_info.header).cast());
             if result != 0 {
                 error!(
                     "Windows API error - DisplayConfigGetDeviceInfo (legacy advanced color info for HDR enabled check) failed for adapter {:?}, target {}: error code {result}",
@@ -559,9 +1093,7 @@ impl HdrController {
             }
         }
 
-        let enabled = color_info.advancedColorSupported()
-            && color_info.advancedColorEnabled()
-            && !color_info.wideColorEnforced();
+        let enabled = LegacyAdvancedColor::from_bits(color_info.value).hdr_enabled();
         debug!(
             "Display (adapter={:#x}:{:#x}, target={}): advancedColorSupported={}, advancedColorEnabled={}, wideColorEnforced={}, HDR enabled (legacy API) = {}",
             target.adapter_id.LowPart,
@@ -578,126 +1110,322 @@ impl HdrController {
 
     /// Enable or disable HDR on a single display.
     ///
-    /// Includes a 100ms delay for state propagation.
-    ///
-    /// # Safety
-    ///
-    /// Structures initialized correctly. IDs from validated `DisplayTarget`. Return codes checked.
-    /// 100ms delay ensures state propagation before return.
-    #[cfg_attr(
-        windows,
-        expect(unsafe_code, reason = "Windows FFI for HDR state control")
-    )]
+    /// Waits 100ms for the change to propagate, longer on adapters whose driver needs
+    /// it, and sends the change twice to drivers known to drop it (see
+    /// [`quirks`](crate::hdr::quirks)).
     pub fn set_hdr_state(&self, target: &DisplayTarget, enable: bool) -> Result<()> {
+        if self.dry_run {
+            use tracing::info;
+            info!(
+                "Dry run: would set HDR {} for {}",
+                if enable { "ON" } else { "OFF" },
+                target
+            );
+            return Ok(());
+        }
+
         #[cfg(windows)]
         {
-            use crate::hdr::windows_api::{
-                DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE, DISPLAYCONFIG_SET_HDR_STATE,
-            };
             use tracing::{debug, info};
 
-            match self.windows_version {
-                WindowsVersion::Windows11_24H2 => {
-                    let mut set_state = DISPLAYCONFIG_SET_HDR_STATE::new(
-                        target.adapter_id,
-                        target.target_id,
-                        enable,
-                    );
+            /// Time for an HDR change to propagate before it is reported as done
+            const HDR_SETTLE_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
 
-                    debug!(
-                        "Setting HDR state (24H2+) for display (adapter={:#x}:{:#x}, target={}): {}",
-                        target.adapter_id.LowPart,
-                        target.adapter_id.HighPart,
-                        target.target_id,
-                        if enable { "ON" } else { "OFF" }
-                    );
+            let workarounds = self.driver_workarounds(target);
+            self.send_hdr_state(target, enable)?;
+            if workarounds.reapply {
+                debug!(
+                    "Setting HDR {} for {} again to work around the display driver",
+                    if enable { "ON" } else { "OFF" },
+                    target
+                );
+                std::thread::sleep(HDR_SETTLE_DELAY);
+                self.send_hdr_state(target, enable)?;
+            }
 
-                    unsafe {
-                        let result = DisplayConfigSetDeviceInfo(
-                            std::ptr::addr_of_mut!(set_state.header).cast(),
-                        );
-                        if result != 0 {
-                            error!(
-                                "Windows API error - DisplayConfigSetDeviceInfo (set HDR state 24H2+) failed for adapter {:?}, target {}: error code {result}",
-                                target.adapter_id, target.target_id
-                            );
-                            return Err(EasyHdrError::HdrControlFailed(
-                                crate::error::StringError::new(format!(
-                                    "Failed to set HDR state (24H2+): error code {result}",
-                                )),
-                            ));
-                        }
-                    }
+            std::thread::sleep(HDR_SETTLE_DELAY + workarounds.extra_settle_delay);
 
-                    std::thread::sleep(std::time::Duration::from_millis(100));
+            info!(
+                "Successfully set HDR {} for display (adapter={:#x}:{:#x}, target={})",
+                if enable { "ON" } else { "OFF" },
+                target.adapter_id.LowPart,
+                target.adapter_id.HighPart,
+                target.target_id
+            );
 
-                    info!(
-                        "Successfully set HDR {} for display (adapter={:#x}:{:#x}, target={})",
-                        if enable { "ON" } else { "OFF" },
-                        target.adapter_id.LowPart,
-                        target.adapter_id.HighPart,
-                        target.target_id
-                    );
+            Ok(())
+        }
 
-                    Ok(())
-                }
-                WindowsVersion::Windows10 | WindowsVersion::Windows11 => {
-                    let mut set_state = DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE::new(
-                        target.adapter_id,
-                        target.target_id,
-                        enable,
-                    );
+        #[cfg(not(windows))]
+        {
+            use tracing::debug;
+            debug!(
+                "Mock: Setting HDR state for display (target={}): {}",
+                target.target_id,
+                if enable { "ON" } else { "OFF" }
+            );
+            Ok(())
+        }
+    }
 
-                    debug!(
-                        "Setting HDR state (legacy) for display (adapter={:#x}:{:#x}, target={}): {}",
-                        target.adapter_id.LowPart,
-                        target.adapter_id.HighPart,
-                        target.target_id,
-                        if enable { "ON" } else { "OFF" }
-                    );
+    /// Driver workarounds for the adapter a display is connected to
+    #[cfg(windows)]
+    fn driver_workarounds(&self, target: &DisplayTarget) -> Workarounds {
+        self.gpus
+            .get(&(target.adapter_id.LowPart, target.adapter_id.HighPart))
+            .map(|gpu| quirks::workarounds_for(gpu, quirks::QUIRKS))
+            .unwrap_or_default()
+    }
 
-                    unsafe {
-                        let result = DisplayConfigSetDeviceInfo(
-                            std::ptr::addr_of_mut!(set_state.header).cast(),
+    /// Send the request that turns HDR on or off for a display
+    ///
+    /// Windows 11 24H2+ uses `DISPLAYCONFIG_SET_HDR_STATE`; older versions use
+    /// `DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE`.
+    ///
+    /// # Safety
+    ///
+    /// Structures initialized correctly. IDs from validated `DisplayTarget`. Return codes checked.
+    #[cfg(windows)]
+    #[expect(unsafe_code, reason = "Windows FFI for HDR state control")]
+    fn send_hdr_state(&self, target: &DisplayTarget, enable: bool) -> Result<()> {
+        use crate::hdr::windows_api::{
+            DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE, DISPLAYCONFIG_SET_HDR_STATE,
+        };
+        use tracing::debug;
+
+        match self.windows_version {
+            WindowsVersion::Windows11_24H2 => {
+                let mut set_state =
+                    DISPLAYCONFIG_SET_HDR_STATE::new(target.adapter_id, target.target_id, enable);
+
+                debug!(
+                    "Setting HDR state (24H2+) for display (adapter={:#x}:{:#x}, target={}): {}",
+                    target.adapter_id.LowPart,
+                    target.adapter_id.HighPart,
+                    target.target_id,
+                    if enable { "ON" } else { "OFF" }
+                );
+
+                unsafe {
+                    let result = display_config_set_device_info(
+                        std::ptr::addr_of_mut!(set_state.header).cast(),
+                    );
+                    if result != 0 {
+                        error!(
+                            "Windows API error - DisplayConfigSetDeviceInfo (set HDR state 24H2+) failed for adapter {:?}, target {}: error code {result}",
+                            target.adapter_id, target.target_id
                         );
-                        if result != 0 {
-                            error!(
-                                "Windows API error - DisplayConfigSetDeviceInfo (set advanced color state) failed for adapter {:?}, target {}: error code {result}",
-                                target.adapter_id, target.target_id
-                            );
-                            return Err(EasyHdrError::HdrControlFailed(
-                                crate::error::StringError::new(format!(
-                                    "Failed to set advanced color state: error code {result}",
-                                )),
-                            ));
-                        }
+                        return Err(EasyHdrError::HdrControlFailed(crate::error::ApiError::new(
+                            "Failed to set HDR state (24H2+)",
+                            result,
+                        )));
                     }
+                }
+            }
+            WindowsVersion::Windows10 | WindowsVersion::Windows11 => {
+                let mut set_state = DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE::new(
+                    target.adapter_id,
+                    target.target_id,
+                    enable,
+                );
 
-                    std::thread::sleep(std::time::Duration::from_millis(100));
+                debug!(
+                    "Setting HDR state (legacy) for display (adapter={:#x}:{:#x}, target={}): {}",
+                    target.adapter_id.LowPart,
+                    target.adapter_id.HighPart,
+                    target.target_id,
+                    if enable { "ON" } else { "OFF" }
+                );
 
-                    info!(
-                        "Successfully set HDR {} for display (adapter={:#x}:{:#x}, target={})",
-                        if enable { "ON" } else { "OFF" },
-                        target.adapter_id.LowPart,
-                        target.adapter_id.HighPart,
-                        target.target_id
+                unsafe {
+                    let result = display_config_set_device_info(
+                        std::ptr::addr_of_mut!(set_state.header).cast(),
                     );
+                    if result != 0 {
+                        error!(
+                            "Windows API error - DisplayConfigSetDeviceInfo (set advanced color state) failed for adapter {:?}, target {}: error code {result}",
+                            target.adapter_id, target.target_id
+                        );
+                        return Err(EasyHdrError::HdrControlFailed(crate::error::ApiError::new(
+                            "Failed to set advanced color state",
+                            result,
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 
-                    Ok(())
+    /// Read the active color mode of a display.
+    ///
+    /// Windows 11 24H2+ reports SDR, WCG or HDR. Older builds only report whether HDR is
+    /// on, so a display in advanced color without HDR reads as SDR there.
+    ///
+    /// # Safety
+    ///
+    /// Same soundness as `is_hdr_enabled`: structure initialized correctly, IDs validated,
+    /// return code checked before data access.
+    #[cfg_attr(
+        windows,
+        expect(unsafe_code, reason = "Windows FFI for color mode detection")
+    )]
+    pub fn color_mode(&self, target: &DisplayTarget) -> Result<ColorMode> {
+        #[cfg(windows)]
+        if self.windows_version == WindowsVersion::Windows11_24H2 {
+            let mut color_info =
+                DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2::new(target.adapter_id, target.target_id);
+
+            unsafe {
+                let result = display_config_get_device_info(
+                    std::ptr::addr_of_mut!(color_info.header).cast(),
+                );
+                if result != 0 {
+                    return Err(EasyHdrError::HdrControlFailed(
+                        crate::error::StringError::new(format!(
+                            "Failed to get advanced color info (24H2+): error code {result}",
+                        )),
+                    ));
                 }
             }
+
+            return ColorMode::from_active_color_mode(color_info.activeColorMode).ok_or_else(
+                || {
+                    EasyHdrError::HdrControlFailed(crate::error::StringError::new(format!(
+                        "Unknown active color mode {}",
+                        color_info.activeColorMode
+                    )))
+                },
+            );
+        }
+
+        Ok(if self.is_hdr_enabled(target)? {
+            ColorMode::Hdr
+        } else {
+            ColorMode::Sdr
+        })
+    }
+
+    /// Mode, bit depth and color encoding a display is currently driven with
+    ///
+    /// The mode is read through the display's GDI device name, bit depth and encoding
+    /// from the legacy advanced color info, which every supported Windows build answers.
+    pub fn link_signal(&self, target: &DisplayTarget) -> Result<LinkSignal> {
+        #[cfg(windows)]
+        {
+            let Some(device_name) = target.gdi_device_name.as_deref() else {
+                return Err(EasyHdrError::HdrControlFailed(
+                    crate::error::StringError::new(format!("No GDI device name for {target}")),
+                ));
+            };
+            let mode = DisplayModeController::current_mode_of(Some(device_name))?;
+            let (bits_per_channel, encoding) = Self::read_signal_format(target)?;
+            let encoding = encoding.ok_or_else(|| {
+                EasyHdrError::HdrControlFailed(crate::error::StringError::new(format!(
+                    "Unknown color encoding of {target}"
+                )))
+            })?;
+
+            Ok(LinkSignal {
+                mode,
+                bits_per_channel,
+                encoding,
+            })
         }
 
         #[cfg(not(windows))]
         {
-            use tracing::debug;
-            debug!(
-                "Mock: Setting HDR state for display (target={}): {}",
-                target.target_id,
-                if enable { "ON" } else { "OFF" }
+            Err(EasyHdrError::HdrControlFailed(
+                crate::error::StringError::new(format!(
+                    "The link signal of {target} can only be read on Windows"
+                )),
+            ))
+        }
+    }
+
+    /// Switch a display to SDR, WCG or HDR.
+    ///
+    /// WCG needs Windows 11 24H2+; on older builds only SDR and HDR can be set. Leaving
+    /// HDR for WCG turns HDR off first, since Windows only applies the WCG state while
+    /// HDR is off.
+    pub fn set_color_mode(&self, target: &DisplayTarget, mode: ColorMode) -> Result<()> {
+        match mode {
+            ColorMode::Hdr => self.set_hdr_state(target, true),
+            ColorMode::Sdr | ColorMode::Wcg
+                if self.windows_version == WindowsVersion::Windows11_24H2 =>
+            {
+                self.set_hdr_state(target, false)?;
+                self.set_wcg_state(target, mode == ColorMode::Wcg)
+            }
+            ColorMode::Sdr => self.set_hdr_state(target, false),
+            ColorMode::Wcg => Err(EasyHdrError::HdrControlFailed(
+                crate::error::StringError::new(format!(
+                    "Wide color gamut mode for {target} requires Windows 11 24H2 or later"
+                )),
+            )),
+        }
+    }
+
+    /// Turn wide color gamut on or off for a display (Windows 11 24H2+).
+    ///
+    /// # Safety
+    ///
+    /// Structure initialized correctly. IDs from validated `DisplayTarget`. Return code checked.
+    #[cfg_attr(
+        not(windows),
+        expect(
+            clippy::unnecessary_wraps,
+            reason = "Only the Windows implementation can fail"
+        )
+    )]
+    #[cfg_attr(
+        windows,
+        expect(unsafe_code, reason = "Windows FFI for WCG state control")
+    )]
+    fn set_wcg_state(&self, target: &DisplayTarget, enable: bool) -> Result<()> {
+        use tracing::info;
+
+        if self.dry_run {
+            info!(
+                "Dry run: would set WCG {} for {}",
+                if enable { "ON" } else { "OFF" },
+                target
             );
-            Ok(())
+            return Ok(());
         }
+
+        #[cfg(windows)]
+        {
+            use crate::hdr::windows_api::DISPLAYCONFIG_SET_WCG_STATE;
+
+            let mut set_state =
+                DISPLAYCONFIG_SET_WCG_STATE::new(target.adapter_id, target.target_id, enable);
+
+            unsafe {
+                let result =
+                    display_config_set_device_info(std::ptr::addr_of_mut!(set_state.header).cast());
+                if result != 0 {
+                    error!(
+                        "Windows API error - DisplayConfigSetDeviceInfo (set WCG state) failed for adapter {:?}, target {}: error code {result}",
+                        target.adapter_id, target.target_id
+                    );
+                    return Err(EasyHdrError::HdrControlFailed(
+                        crate::error::StringError::new(format!(
+                            "Failed to set WCG state: error code {result}",
+                        )),
+                    ));
+                }
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        info!(
+            "Set WCG {} for {}",
+            if enable { "ON" } else { "OFF" },
+            target
+        );
+        Ok(())
     }
 
     /// Refresh the display cache by re-enumerating all displays
@@ -710,65 +1438,88 @@ impl HdrController {
         self.enumerate_displays()
     }
 
-    /// Enable or disable HDR globally across all HDR-capable displays
+    /// Read the bit depth, color encoding and colorimetry of the cached displays again
     ///
-    /// Returns results for each display, allowing partial success. Continues with remaining
-    /// displays if some fail (e.g., due to disconnection).
-    pub fn set_hdr_global(&self, enable: bool) -> Result<Vec<(DisplayTarget, Result<()>)>> {
-        use tracing::{debug, info, warn};
+    /// They change with HDR and the color mode, so [`DisplayTarget::capabilities`] is only
+    /// current after a toggle once this ran.
+    pub fn refresh_capabilities(&mut self) {
+        #[cfg(windows)]
+        {
+            let colorimetry = crate::hdr::capabilities::output_colorimetry();
+            for target in &mut self.display_cache {
+                target.capabilities = Self::query_capabilities(target, &colorimetry);
+            }
+        }
+    }
+}
 
-        info!(
-            "Setting HDR {} globally for {} display(s)",
-            if enable { "ON" } else { "OFF" },
-            self.display_cache.len()
-        );
+impl HdrControl for HdrController {
+    fn enumerate_displays(&mut self) -> Result<Vec<DisplayTarget>> {
+        self.refresh_displays()
+    }
 
-        let mut results = Vec::with_capacity(self.display_cache.len());
+    fn displays(&self) -> &[DisplayTarget] {
+        self.get_display_cache()
+    }
 
-        for target in &self.display_cache {
-            if !target.supports_hdr {
-                debug!(
-                    "Skipping display (adapter={:#x}:{:#x}, target={}) - HDR not supported",
-                    target.adapter_id.LowPart, target.adapter_id.HighPart, target.target_id
-                );
-                continue;
-            }
+    fn is_hdr_enabled(&self, target: &DisplayTarget) -> Result<bool> {
+        HdrController::is_hdr_enabled(self, target)
+    }
 
-            let result = self.set_hdr_state(target, enable);
+    fn set_hdr_state(&self, target: &DisplayTarget, enable: bool) -> Result<()> {
+        HdrController::set_hdr_state(self, target, enable)
+    }
 
-            match &result {
-                Ok(()) => {
-                    info!(
-                        "Successfully set HDR {} for display (adapter={:#x}:{:#x}, target={})",
-                        if enable { "ON" } else { "OFF" },
-                        target.adapter_id.LowPart,
-                        target.adapter_id.HighPart,
-                        target.target_id
-                    );
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to set HDR {} for display (adapter={:#x}:{:#x}, target={}): {}. \
-                         Display may have been disconnected or driver issue occurred. Continuing with other displays.",
-                        if enable { "ON" } else { "OFF" },
-                        target.adapter_id.LowPart,
-                        target.adapter_id.HighPart,
-                        target.target_id,
-                        e
-                    );
-                }
-            }
+    fn resolve_displays(&self, identities: &[DisplayIdentity]) -> Vec<DisplayTarget> {
+        HdrController::resolve_displays(self, identities)
+    }
 
-            results.push((target.clone(), result));
+    fn windows_version(&self) -> WindowsVersion {
+        self.windows_version
+    }
+
+    fn send_vcp_command(&self, target: &DisplayTarget, command: VcpCommand) -> Result<()> {
+        if self.dry_run {
+            use tracing::info;
+            info!(
+                "Dry run: would send DDC/CI command {} to {}",
+                command, target
+            );
+            return Ok(());
         }
+        crate::hdr::ddc::send_vcp_command(target, command)
+    }
 
-        info!(
-            "HDR global toggle complete: {} successful, {} failed",
-            results.iter().filter(|(_, r)| r.is_ok()).count(),
-            results.iter().filter(|(_, r)| r.is_err()).count()
-        );
+    fn set_color_profile(&self, target: &DisplayTarget, profile: &str) -> Result<()> {
+        if self.dry_run {
+            use tracing::info;
+            info!(
+                "Dry run: would make '{}' the HDR color profile of {}",
+                profile, target
+            );
+            return Ok(());
+        }
+        crate::hdr::color_profile::set_default_hdr_profile(target, profile)
+    }
+
+    fn color_mode(&self, target: &DisplayTarget) -> Result<ColorMode> {
+        HdrController::color_mode(self, target)
+    }
+
+    fn set_color_mode(&self, target: &DisplayTarget, mode: ColorMode) -> Result<()> {
+        HdrController::set_color_mode(self, target, mode)
+    }
 
-        Ok(results)
+    fn link_signal(&self, target: &DisplayTarget) -> Result<LinkSignal> {
+        HdrController::link_signal(self, target)
+    }
+
+    fn set_dry_run(&mut self, dry_run: bool) {
+        HdrController::set_dry_run(self, dry_run);
+    }
+
+    fn refresh_capabilities(&mut self) {
+        HdrController::refresh_capabilities(self);
     }
 }
 
@@ -820,6 +1571,7 @@ mod tests {
             },
             target_id: 42,
             supports_hdr: false,
+            ..Default::default()
         };
 
         let cloned = target.clone();
@@ -830,6 +1582,148 @@ mod tests {
         assert!(!cloned.supports_hdr);
     }
 
+    #[test]
+    fn test_display_target_names() {
+        let mut target = DisplayTarget {
+            target_id: 3,
+            ..Default::default()
+        };
+        assert_eq!(target.display_name(), "Display 3");
+
+        target.connector = ConnectorType::Internal;
+        assert_eq!(target.display_name(), "Built-in display");
+
+        target.friendly_name = Some("LG ULTRAGEAR".to_string());
+        target.connector = ConnectorType::Hdmi;
+        assert_eq!(target.display_name(), "LG ULTRAGEAR");
+        assert!(target.to_string().starts_with("LG ULTRAGEAR (HDMI, "));
+    }
+
+    #[test]
+    fn test_fallback_hdr_support() {
+        let sdr_output = OutputColorimetry::default();
+        let hdr_output = OutputColorimetry {
+            hdr_color_space: true,
+            ..Default::default()
+        };
+        assert!(!fallback_hdr_support(None));
+        assert!(!fallback_hdr_support(Some(&sdr_output)));
+        assert!(fallback_hdr_support(Some(&hdr_output)));
+    }
+
+    #[test]
+    fn test_connector_type_from_output_technology() {
+        assert_eq!(
+            ConnectorType::from_output_technology(output_technology::HDMI),
+            ConnectorType::Hdmi
+        );
+        assert_eq!(
+            ConnectorType::from_output_technology(output_technology::DISPLAYPORT_EMBEDDED),
+            ConnectorType::Internal
+        );
+        assert_eq!(
+            ConnectorType::from_output_technology(output_technology::INTERNAL),
+            ConnectorType::Internal
+        );
+        assert_eq!(
+            ConnectorType::from_output_technology(0xFFFF_FFFF),
+            ConnectorType::Other
+        );
+    }
+
+    #[test]
+    fn test_edid_serial() {
+        let mut edid = vec![0u8; 128];
+        edid[..8].copy_from_slice(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]);
+        assert_eq!(edid_serial(&edid), None);
+
+        // Numeric serial only
+        edid[12..16].copy_from_slice(&[0x34, 0x12, 0, 0]);
+        assert_eq!(edid_serial(&edid), Some(vec![0x34, 0x12, 0, 0]));
+
+        // Serial descriptor wins over the numeric serial
+        edid[72..77].copy_from_slice(&[0, 0, 0, 0xFF, 0]);
+        edid[77..90].copy_from_slice(b"7MT0123ABCD\n ");
+        assert_eq!(edid_serial(&edid), Some(b"7MT0123ABCD".to_vec()));
+
+        assert_eq!(edid_serial(&edid[..100]), None);
+        assert_eq!(edid_serial(&[0u8; 128]), None);
+    }
+
+    #[test]
+    fn test_display_identity_is_stable() {
+        let identity = DisplayIdentity::new("DEL", 0x4141, b"7MT0123ABCD");
+        assert_eq!(
+            identity,
+            DisplayIdentity::new("DEL", 0x4141, b"7MT0123ABCD")
+        );
+        assert_ne!(
+            identity,
+            DisplayIdentity::new("DEL", 0x4141, b"7MT0123ABCE")
+        );
+
+        // FNV-1a must not change, or persisted identities stop resolving
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert!(identity.to_string().starts_with("DEL4141-"));
+    }
+
+    #[test]
+    fn test_device_instance_id() {
+        assert_eq!(
+            device_instance_id(
+                r"\\?\DISPLAY#DEL4141#5&2a3b&0&UID4355#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}"
+            )
+            .as_deref(),
+            Some(r"DISPLAY\DEL4141\5&2a3b&0&UID4355")
+        );
+        assert_eq!(device_instance_id("garbage"), None);
+    }
+
+    #[test]
+    fn test_resolve_display() {
+        let identity = DisplayIdentity::new("SAM", 0x7325, b"H4ZR");
+        let mut controller = HdrController::new_mock().unwrap();
+        controller.display_cache = SmallVec::from_vec(vec![
+            DisplayTarget {
+                target_id: 1,
+                ..Default::default()
+            },
+            DisplayTarget {
+                target_id: 2,
+                identity: Some(identity.clone()),
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(
+            controller.resolve_display(&identity).map(|t| t.target_id),
+            Some(2)
+        );
+        assert!(
+            controller
+                .resolve_display(&DisplayIdentity::new("SAM", 0x7325, b"other"))
+                .is_none()
+        );
+        assert_eq!(
+            controller
+                .resolve_displays(&[DisplayIdentity::new("SAM", 0x7325, b"other"), identity])
+                .iter()
+                .map(|t| t.target_id)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_decode_pnp_id() {
+        // "DEL" = 0x10AC in EDID byte order, reported little-endian by Windows
+        assert_eq!(decode_pnp_id(0xAC10).as_deref(), Some("DEL"));
+        // "SAM" = 0x4C2D
+        assert_eq!(decode_pnp_id(0x2D4C).as_deref(), Some("SAM"));
+        assert_eq!(decode_pnp_id(0), None);
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_is_hdr_supported() {
@@ -886,6 +1780,7 @@ mod tests {
             },
             target_id: 0,
             supports_hdr: false,
+            ..Default::default()
         };
 
         let result = controller.is_hdr_supported(&target);
@@ -906,6 +1801,7 @@ mod tests {
             },
             target_id: 0,
             supports_hdr: false,
+            ..Default::default()
         };
 
         let result = controller.is_hdr_enabled(&target);
@@ -1081,6 +1977,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dry_run_skips_vcp_commands() {
+        use crate::hdr::{HdrControl, VcpCommand};
+
+        let mut controller = HdrController::new().expect("Failed to create controller");
+        // No GDI device name, so a real DDC/CI command can't reach a monitor
+        let target = DisplayTarget {
+            target_id: 7,
+            supports_hdr: true,
+            ..Default::default()
+        };
+        let command = VcpCommand {
+            code: 0xE1,
+            value: 0x01,
+        };
+
+        assert!(HdrControl::send_vcp_command(&controller, &target, command).is_err());
+
+        controller.set_dry_run(true);
+        assert!(HdrControl::send_vcp_command(&controller, &target, command).is_ok());
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn test_set_hdr_state_non_windows() {
@@ -1094,6 +2012,7 @@ mod tests {
             },
             target_id: 0,
             supports_hdr: true,
+            ..Default::default()
         };
 
         let result = controller.set_hdr_state(&target, true);
@@ -1142,6 +2061,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_hdr_global_verified_reports_every_hdr_display() {
+        // Verified toggling returns one final result per HDR-capable display, the same
+        // displays set_hdr_global reports on
+        let controller = HdrController::new().expect("Failed to create controller");
+
+        let results = controller
+            .set_hdr_global_verified(false)
+            .expect("set_hdr_global_verified should succeed");
+
+        let hdr_displays = controller
+            .get_display_cache()
+            .iter()
+            .filter(|target| target.supports_hdr)
+            .count();
+        assert_eq!(results.len(), hdr_displays);
+        assert!(results.iter().all(|(target, _)| target.supports_hdr));
+    }
+
     #[test]
     fn test_refresh_displays() {
         // This test verifies that refresh_displays updates the display cache
@@ -1259,6 +2197,7 @@ mod tests {
             },
             target_id: 0xFFFF_FFFF,
             supports_hdr: false,
+            ..Default::default()
         };
 
         // Test HDR support detection on invalid display
@@ -1302,6 +2241,7 @@ mod tests {
             },
             target_id: 0xFFFF_FFFF,
             supports_hdr: true, // Pretend it supports HDR
+            ..Default::default()
         };
 
         // Try to set HDR state on invalid display
@@ -0,0 +1,704 @@
+//! Restoring HDR when `EasyHDR` exits abnormally
+//!
+//! While HDR is on because `EasyHDR` turned it on, a small marker file next to the
+//! configuration records that the displays were in SDR before. A panic hook, a console
+//! control handler and the `WM_ENDSESSION` handler of the HDR state monitor turn HDR back
+//! off before the process goes away. When the process is killed without a chance to clean
+//! up, the marker survives and [`repair_after_crash`] turns HDR off on the next launch.
+//!
+//! Displays switched to wide color gamut are guarded the same way: the marker lists the
+//! color mode each of them had before, and that mode is restored instead of turning HDR
+//! off. The tone mapping of HDR captures an application switched is put back as well, and
+//! Night Light is turned back on if it was suspended for the session.
+//!
+//! A background thread that died silently would leave process monitoring or HDR control
+//! stopped while the tray icon still looks alive. The panic hook logs the thread and
+//! location. Components supervised by the [watchdog](crate::utils::watchdog) are then
+//! restarted by it; a panic anywhere else, or in a component that keeps panicking, is
+//! reported to the Event Log when enabled, shown to the user, and ends `EasyHDR`.
+
+use crate::config::ConfigManager;
+use crate::error::{EasyHdrError, Result, StringError};
+use crate::hdr::capture::{CaptureSettings, RegistryCaptureSettings};
+use crate::hdr::night_light::NightLightController;
+use crate::hdr::windows_api::LUID;
+use crate::hdr::{ColorMode, DisplayTarget, HdrControl, HdrController};
+use crate::utils::event_log::{self, EventKind};
+use crate::utils::watchdog;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Whether [`install`] ran; the guard is inert before that so tests never write the marker
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether HDR is currently on because `EasyHDR` turned it on
+static ARMED: AtomicBool = AtomicBool::new(false);
+
+/// Color modes to restore instead of turning HDR off, while armed for wide color gamut
+static COLOR_MODES: parking_lot::Mutex<Vec<SavedColorMode>> = parking_lot::Mutex::new(Vec::new());
+
+/// Tone mapping of HDR captures to restore, while an application has it switched
+static CAPTURE_TONE_MAPPING: parking_lot::Mutex<Option<SavedToneMapping>> =
+    parking_lot::Mutex::new(None);
+
+/// Whether Night Light is turned off for HDR and has to be turned back on
+static NIGHT_LIGHT_SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Contents of the marker file
+#[derive(Debug, Serialize, Deserialize)]
+struct CrashMarker {
+    /// Process that turned HDR on
+    pid: u32,
+    /// When HDR was turned on (Unix timestamp in seconds)
+    armed_at: u64,
+    /// Displays switched to wide color gamut and the mode to restore, empty for HDR
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    color_modes: Vec<SavedColorMode>,
+    /// Tone mapping of HDR captures to restore, `None` if it was not switched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    capture_tone_mapping: Option<SavedToneMapping>,
+    /// Whether Night Light was turned off for HDR and has to be turned back on
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    night_light_suspended: bool,
+}
+
+/// Tone mapping of HDR captures before an application switched it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct SavedToneMapping {
+    /// Value to restore, `None` to delete it
+    original: Option<bool>,
+}
+
+/// Color mode a display had before `EasyHDR` switched it to wide color gamut
+///
+/// Displays are matched by adapter LUID and target ID, which stay the same until the
+/// next reboot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SavedColorMode {
+    /// Low part of the adapter LUID
+    adapter_low: u32,
+    /// High part of the adapter LUID
+    adapter_high: i32,
+    /// Target ID
+    target_id: u32,
+    /// Color mode to restore
+    mode: ColorMode,
+}
+
+impl SavedColorMode {
+    /// Remember the color mode of a display
+    fn new(target: &DisplayTarget, mode: ColorMode) -> Self {
+        Self {
+            adapter_low: target.adapter_id.LowPart,
+            adapter_high: target.adapter_id.HighPart,
+            target_id: target.target_id,
+            mode,
+        }
+    }
+
+    /// Whether this is the color mode of `target`
+    fn matches(&self, target: &DisplayTarget) -> bool {
+        target.adapter_id
+            == (LUID {
+                LowPart: self.adapter_low,
+                HighPart: self.adapter_high,
+            })
+            && target.target_id == self.target_id
+    }
+}
+
+/// Get the marker file path (`hdr_restore.json` next to `config.json`)
+pub fn get_marker_path() -> PathBuf {
+    ConfigManager::get_config_path().with_file_name("hdr_restore.json")
+}
+
+/// Install the panic hook and console control handler
+///
+/// Call once at startup, after logging is initialized.
+pub fn install() {
+    use tracing::info;
+
+    INSTALLED.store(true, Ordering::SeqCst);
+
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        use tracing::error;
+
+        let current = std::thread::current();
+        let thread = current.name().unwrap_or("unnamed");
+        error!("EasyHDR panicked in thread '{}': {}", thread, panic_info);
+        previous_hook(panic_info);
+
+        // The watchdog restarts the component or gives up on it
+        if !watchdog::is_supervised() {
+            exit_after_panic(thread, &panic_info.to_string());
+        }
+    }));
+
+    #[cfg(windows)]
+    install_console_handler();
+
+    info!("Crash guard installed");
+}
+
+/// Turn HDR back off, tell the user about the panic in `thread` and exit
+pub fn exit_after_panic(thread: &str, details: &str) -> ! {
+    restore_hdr("a panic");
+
+    let message = panic_message(thread, details);
+    event_log::report(EventKind::ComponentStopped, &message);
+    show_panic_message(&message);
+    std::process::exit(1);
+}
+
+/// Name of the component running in `thread`, as shown to the user
+pub fn component_name(thread: &str) -> &'static str {
+    match thread {
+        "process-monitor" => "process monitoring",
+        "hdr-state-monitor" => "HDR state monitoring",
+        "app-controller" => "HDR control",
+        "main" => "the main window",
+        _ => "a background task",
+    }
+}
+
+/// Text shown to the user after a panic in `thread`
+fn panic_message(thread: &str, details: &str) -> String {
+    let component = component_name(thread);
+    format!(
+        "EasyHDR stopped because of an internal error in {component}.\n\n\
+         HDR was turned back off if EasyHDR had turned it on. Please restart EasyHDR and \
+         report the problem with the log file ({}).\n\n{details}",
+        ConfigManager::get_config_path()
+            .with_file_name("app.log")
+            .display()
+    )
+}
+
+/// Tell the user that EasyHDR is about to exit after a panic
+#[cfg(windows)]
+fn show_panic_message(message: &str) {
+    rfd::MessageDialog::new()
+        .set_title("EasyHDR - Error")
+        .set_description(message)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .set_level(rfd::MessageLevel::Error)
+        .show();
+}
+
+/// Without a message box the message goes to standard error
+#[cfg(not(windows))]
+fn show_panic_message(message: &str) {
+    eprintln!("{message}");
+}
+
+/// Record that `EasyHDR` turned HDR on
+pub fn arm() {
+    use tracing::warn;
+
+    if !INSTALLED.load(Ordering::SeqCst) || ARMED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    if let Err(e) = write_marker(
+        &get_marker_path(),
+        &[],
+        *CAPTURE_TONE_MAPPING.lock(),
+        NIGHT_LIGHT_SUSPENDED.load(Ordering::SeqCst),
+    ) {
+        warn!("Failed to write HDR restore marker: {}", e);
+    }
+}
+
+/// Record that `EasyHDR` switched displays to wide color gamut
+///
+/// `previous` lists each switched display with the color mode to restore.
+pub fn arm_color_modes(previous: &[(DisplayTarget, ColorMode)]) {
+    use tracing::warn;
+
+    if !INSTALLED.load(Ordering::SeqCst) || ARMED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let color_modes: Vec<SavedColorMode> = previous
+        .iter()
+        .map(|(target, mode)| SavedColorMode::new(target, *mode))
+        .collect();
+    if let Err(e) = write_marker(
+        &get_marker_path(),
+        &color_modes,
+        *CAPTURE_TONE_MAPPING.lock(),
+        NIGHT_LIGHT_SUSPENDED.load(Ordering::SeqCst),
+    ) {
+        warn!("Failed to write HDR restore marker: {}", e);
+    }
+    *COLOR_MODES.lock() = color_modes;
+}
+
+/// Record that an application switched the tone mapping of HDR captures
+///
+/// `original` is the value to restore, `None` when it was missing. Written to the marker
+/// with the next [`arm`], or right away while armed.
+pub fn arm_capture_tone_mapping(original: Option<bool>) {
+    if !INSTALLED.load(Ordering::SeqCst) {
+        return;
+    }
+
+    *CAPTURE_TONE_MAPPING.lock() = Some(SavedToneMapping { original });
+    rewrite_marker_if_armed();
+}
+
+/// Record that the tone mapping of HDR captures was restored
+pub fn disarm_capture_tone_mapping() {
+    if !INSTALLED.load(Ordering::SeqCst) || CAPTURE_TONE_MAPPING.lock().take().is_none() {
+        return;
+    }
+
+    rewrite_marker_if_armed();
+}
+
+/// Record that Night Light was turned off for HDR
+///
+/// Written to the marker with the next [`arm`], or right away while armed.
+pub fn arm_night_light() {
+    if !INSTALLED.load(Ordering::SeqCst) || NIGHT_LIGHT_SUSPENDED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    rewrite_marker_if_armed();
+}
+
+/// Record that Night Light was turned back on
+pub fn disarm_night_light() {
+    if !INSTALLED.load(Ordering::SeqCst) || !NIGHT_LIGHT_SUSPENDED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    rewrite_marker_if_armed();
+}
+
+/// Write the marker again with the current state, if armed
+fn rewrite_marker_if_armed() {
+    use tracing::warn;
+
+    if !ARMED.load(Ordering::SeqCst) {
+        return;
+    }
+    if let Err(e) = write_marker(
+        &get_marker_path(),
+        &COLOR_MODES.lock(),
+        *CAPTURE_TONE_MAPPING.lock(),
+        NIGHT_LIGHT_SUSPENDED.load(Ordering::SeqCst),
+    ) {
+        warn!("Failed to write HDR restore marker: {}", e);
+    }
+}
+
+/// Record that HDR is no longer on because of `EasyHDR`
+pub fn disarm() {
+    if !INSTALLED.load(Ordering::SeqCst) || !ARMED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    COLOR_MODES.lock().clear();
+    CAPTURE_TONE_MAPPING.lock().take();
+    NIGHT_LIGHT_SUSPENDED.store(false, Ordering::SeqCst);
+    remove_marker(&get_marker_path());
+}
+
+/// Turn HDR off if `EasyHDR` turned it on, because the process is about to go away
+///
+/// Runs at most once per arm; `reason` is only used for logging.
+pub fn restore_hdr(reason: &str) {
+    use tracing::{error, warn};
+
+    if !ARMED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    warn!("Turning HDR off after {}", reason);
+    if let Some(saved) = CAPTURE_TONE_MAPPING.lock().take() {
+        restore_capture_tone_mapping(saved);
+    }
+    if NIGHT_LIGHT_SUSPENDED.swap(false, Ordering::SeqCst) {
+        restore_night_light();
+    }
+    let color_modes = std::mem::take(&mut *COLOR_MODES.lock());
+    match restore_displays(&color_modes) {
+        Ok(()) => remove_marker(&get_marker_path()),
+        // Keep the marker so the next launch can repair the state
+        Err(e) => error!("Failed to turn HDR off after {}: {}", reason, e),
+    }
+}
+
+/// Turn HDR off if a previous run exited while HDR was on
+///
+/// Call at startup before the HDR state is detected. Returns `true` if a leftover marker
+/// was found and HDR was turned off.
+pub fn repair_after_crash() -> bool {
+    repair_with(
+        &get_marker_path(),
+        restore_displays,
+        restore_capture_tone_mapping,
+        restore_night_light,
+    )
+}
+
+/// Repair using the given marker path and restore functions
+fn repair_with(
+    path: &Path,
+    restore: impl FnOnce(&[SavedColorMode]) -> Result<()>,
+    restore_capture: impl FnOnce(SavedToneMapping),
+    restore_night_light: impl FnOnce(),
+) -> bool {
+    use tracing::{error, warn};
+
+    if !path.exists() {
+        return false;
+    }
+
+    let color_modes = if let Some(marker) = read_marker(path) {
+        warn!(
+            "EasyHDR (PID {}) exited while HDR was on since {}, turning HDR off",
+            marker.pid, marker.armed_at
+        );
+        if let Some(saved) = marker.capture_tone_mapping {
+            restore_capture(saved);
+        }
+        if marker.night_light_suspended {
+            restore_night_light();
+        }
+        marker.color_modes
+    } else {
+        warn!("Found an unreadable HDR restore marker, turning HDR off");
+        Vec::new()
+    };
+
+    match restore(&color_modes) {
+        Ok(()) => {
+            remove_marker(path);
+            true
+        }
+        Err(e) => {
+            error!("Failed to repair HDR state from previous run: {}", e);
+            false
+        }
+    }
+}
+
+/// Put the displays back the way they were before `EasyHDR` changed them
+///
+/// Restores the saved color modes of displays switched to wide color gamut, or turns HDR
+/// off on every display when there are none.
+fn restore_displays(color_modes: &[SavedColorMode]) -> Result<()> {
+    use tracing::warn;
+
+    if color_modes.is_empty() {
+        return turn_hdr_off();
+    }
+
+    let controller = HdrController::new()?;
+    let mut result = Ok(());
+    for saved in color_modes {
+        let Some(target) = controller
+            .displays()
+            .iter()
+            .find(|target| saved.matches(target))
+        else {
+            warn!(
+                "Display {} is no longer connected, leaving its color mode",
+                saved.target_id
+            );
+            continue;
+        };
+        if let Err(e) = controller.set_color_mode(target, saved.mode) {
+            result = Err(EasyHdrError::HdrControlFailed(StringError::new(format!(
+                "Failed to switch {target} back to {}: {e}",
+                saved.mode
+            ))));
+        }
+    }
+    result
+}
+
+/// Put the tone mapping of HDR captures back, logging a failure
+fn restore_capture_tone_mapping(saved: SavedToneMapping) {
+    use tracing::{info, warn};
+
+    match RegistryCaptureSettings.write(saved.original) {
+        Ok(()) => info!("Restored tone mapping of HDR captures"),
+        Err(e) => warn!("Failed to restore tone mapping of HDR captures: {}", e),
+    }
+}
+
+/// Turn Night Light back on, logging a failure
+fn restore_night_light() {
+    use tracing::warn;
+
+    if let Err(e) = NightLightController::suspended_earlier().restore() {
+        warn!("Failed to turn Night Light back on: {}", e);
+    }
+}
+
+/// Turn HDR off on every display
+fn turn_hdr_off() -> Result<()> {
+    let results = HdrController::new()?.set_hdr_global(false)?;
+
+    match results
+        .into_iter()
+        .find_map(|(target, result)| result.err().map(|e| (target, e)))
+    {
+        Some((target, e)) => Err(EasyHdrError::HdrControlFailed(StringError::new(format!(
+            "Failed to turn HDR off for {target}: {e}"
+        )))),
+        None => Ok(()),
+    }
+}
+
+/// Write the marker file for the current process
+fn write_marker(
+    path: &Path,
+    color_modes: &[SavedColorMode],
+    capture_tone_mapping: Option<SavedToneMapping>,
+    night_light_suspended: bool,
+) -> Result<()> {
+    let marker = CrashMarker {
+        pid: std::process::id(),
+        armed_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs()),
+        color_modes: color_modes.to_vec(),
+        capture_tone_mapping,
+        night_light_suspended,
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, serde_json::to_string(&marker)?)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// Read the marker file, `None` if it is missing or corrupt
+fn read_marker(path: &Path) -> Option<CrashMarker> {
+    let json = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Delete the marker file, ignoring a missing file
+fn remove_marker(path: &Path) {
+    use tracing::warn;
+
+    match std::fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!("Failed to remove HDR restore marker: {}", e),
+    }
+}
+
+/// Register [`console_ctrl_handler`] for Ctrl+C, console close, logoff and shutdown
+///
+/// # Safety
+///
+/// The handler is a plain function that lives for the whole process.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for console control handler registration"
+)]
+fn install_console_handler() {
+    use tracing::warn;
+    use windows::Win32::System::Console::SetConsoleCtrlHandler;
+
+    if let Err(e) = unsafe { SetConsoleCtrlHandler(Some(console_ctrl_handler), true) } {
+        warn!("Failed to install console control handler: {}", e);
+    }
+}
+
+/// Turn HDR off before the console or session goes away
+///
+/// Returns `FALSE` so the default handler still terminates the process.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI callback registered with SetConsoleCtrlHandler"
+)]
+unsafe extern "system" fn console_ctrl_handler(_ctrl_type: u32) -> windows::core::BOOL {
+    restore_hdr("the console or session closed");
+    windows::core::BOOL(0)
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_dir;
+
+    #[test]
+    fn test_marker_round_trip() {
+        let temp_dir = create_test_dir();
+        let path = temp_dir.path().join("EasyHDR").join("hdr_restore.json");
+
+        write_marker(&path, &[], None, false).unwrap();
+        let marker = read_marker(&path).unwrap();
+        assert_eq!(marker.pid, std::process::id());
+        assert!(marker.armed_at > 0);
+        assert!(marker.color_modes.is_empty());
+
+        remove_marker(&path);
+        assert!(!path.exists());
+        // Removing again is harmless
+        remove_marker(&path);
+    }
+
+    #[test]
+    fn test_repair_turns_hdr_off_and_removes_marker() {
+        let temp_dir = create_test_dir();
+        let path = temp_dir.path().join("hdr_restore.json");
+
+        // Nothing to repair without a marker
+        assert!(!repair_with(
+            &path,
+            |_| panic!("HDR must not be touched"),
+            |_| panic!("Capture settings must not be touched"),
+            || panic!("Night Light must not be touched")
+        ));
+
+        write_marker(&path, &[], None, false).unwrap();
+        let mut turned_off = false;
+        assert!(repair_with(
+            &path,
+            |color_modes| {
+                turned_off = color_modes.is_empty();
+                Ok(())
+            },
+            |_| panic!("Capture settings were not switched"),
+            || panic!("Night Light was not suspended")
+        ));
+        assert!(turned_off);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_failed_repair_keeps_marker() {
+        let temp_dir = create_test_dir();
+        let path = temp_dir.path().join("hdr_restore.json");
+
+        // A corrupt marker still means HDR may have been left on
+        std::fs::write(&path, "not json").unwrap();
+        assert!(!repair_with(
+            &path,
+            |_| {
+                Err(EasyHdrError::HdrControlFailed(StringError::new(
+                    "no displays",
+                )))
+            },
+            |_| {},
+            || {}
+        ));
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_repair_restores_saved_color_modes() {
+        let temp_dir = create_test_dir();
+        let path = temp_dir.path().join("hdr_restore.json");
+
+        let target = DisplayTarget {
+            adapter_id: LUID {
+                LowPart: 7,
+                HighPart: 1,
+            },
+            target_id: 42,
+            supports_hdr: true,
+            ..Default::default()
+        };
+        let saved = SavedColorMode::new(&target, ColorMode::Sdr);
+        assert!(saved.matches(&target));
+        assert!(!saved.matches(&DisplayTarget {
+            target_id: 43,
+            ..target.clone()
+        }));
+
+        write_marker(&path, std::slice::from_ref(&saved), None, false).unwrap();
+        let mut restored = Vec::new();
+        assert!(repair_with(
+            &path,
+            |color_modes| {
+                restored = color_modes.to_vec();
+                Ok(())
+            },
+            |_| {},
+            || {}
+        ));
+        assert_eq!(restored, vec![saved]);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_repair_restores_capture_tone_mapping() {
+        let temp_dir = create_test_dir();
+        let path = temp_dir.path().join("hdr_restore.json");
+
+        // The value was missing before, so it is deleted again
+        let saved = SavedToneMapping { original: None };
+        write_marker(&path, &[], Some(saved), false).unwrap();
+        assert_eq!(
+            read_marker(&path).unwrap().capture_tone_mapping,
+            Some(saved)
+        );
+
+        let mut restored = None;
+        assert!(repair_with(
+            &path,
+            |_| Ok(()),
+            |capture| restored = Some(capture),
+            || {}
+        ));
+        assert_eq!(restored, Some(saved));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_repair_restores_night_light() {
+        let temp_dir = create_test_dir();
+        let path = temp_dir.path().join("hdr_restore.json");
+
+        write_marker(&path, &[], None, true).unwrap();
+        assert!(read_marker(&path).unwrap().night_light_suspended);
+
+        let mut restored = false;
+        assert!(repair_with(&path, |_| Ok(()), |_| {}, || restored = true));
+        assert!(restored);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_panic_message_names_component() {
+        let message = panic_message("process-monitor", "panicked at src/monitor/x.rs:1:1");
+        assert!(message.contains("error in process monitoring"));
+        assert!(message.contains("app.log"));
+        assert!(message.ends_with("panicked at src/monitor/x.rs:1:1"));
+
+        assert!(panic_message("volume-check", "").contains("error in a background task"));
+    }
+
+    #[test]
+    fn test_guard_is_inert_until_installed() {
+        // Tests never install the guard, so arming must not touch the real marker
+        arm();
+        assert!(!ARMED.load(Ordering::SeqCst));
+        arm_capture_tone_mapping(Some(true));
+        assert_eq!(*CAPTURE_TONE_MAPPING.lock(), None);
+        disarm_capture_tone_mapping();
+        arm_night_light();
+        assert!(!NIGHT_LIGHT_SUSPENDED.load(Ordering::SeqCst));
+        disarm_night_light();
+        disarm();
+        restore_hdr("a test");
+    }
+}
@@ -0,0 +1,225 @@
+//! DDC/CI commands for monitor features outside the Windows display settings
+//!
+//! Monitors accept Monitor Control Command Set (MCCS) commands over the display cable.
+//! Panel maintenance such as an OLED pixel refresh has no standard VCP code, so the
+//! command is configured per display as a vendor-specific code and value and sent to
+//! the physical monitors behind the display's GDI device.
+
+use crate::error::{EasyHdrError, Result, StringError};
+use crate::hdr::DisplayTarget;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A VCP feature code and the value to set it to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VcpCommand {
+    /// VCP feature code
+    pub code: u8,
+    /// Value written to the feature
+    pub value: u16,
+}
+
+impl VcpCommand {
+    /// Parse a command written as hexadecimal `CODE=VALUE`, e.g. `E1=01` or `0xE1=0x1`
+    pub fn parse(text: &str) -> Option<Self> {
+        fn hex<T: TryFrom<u32>>(text: &str) -> Option<T> {
+            let text = text.trim();
+            let digits = text
+                .strip_prefix("0x")
+                .or_else(|| text.strip_prefix("0X"))
+                .unwrap_or(text);
+            T::try_from(u32::from_str_radix(digits, 16).ok()?).ok()
+        }
+
+        let (code, value) = text.split_once('=')?;
+        Some(Self {
+            code: hex(code)?,
+            value: hex(value)?,
+        })
+    }
+}
+
+impl fmt::Display for VcpCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02X}={:02X}", self.code, self.value)
+    }
+}
+
+/// Send a VCP command to the monitor of a display
+///
+/// Every physical monitor behind the display's GDI device receives the command; there
+/// is normally exactly one.
+///
+/// # Errors
+///
+/// Returns error if the display has no GDI device name, no monitor answers for it, or
+/// the monitor rejects the command (DDC/CI disabled in its menu, or unsupported).
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for DDC/CI via the dxva2 monitor configuration API"
+)]
+pub fn send_vcp_command(target: &DisplayTarget, command: VcpCommand) -> Result<()> {
+    use tracing::info;
+    use windows::Win32::Devices::Display::{
+        DestroyPhysicalMonitors, GetNumberOfPhysicalMonitorsFromHMONITOR,
+        GetPhysicalMonitorsFromHMONITOR, PHYSICAL_MONITOR, SetVCPFeature,
+    };
+
+    let Some(monitor) = target.gdi_device_name.as_deref().and_then(find_monitor) else {
+        return Err(EasyHdrError::DriverError(StringError::new(format!(
+            "no monitor handle found for {target}"
+        ))));
+    };
+
+    let mut count = 0u32;
+    // SAFETY: `monitor` was just returned by EnumDisplayMonitors
+    unsafe { GetNumberOfPhysicalMonitorsFromHMONITOR(monitor, &raw mut count) }
+        .map_err(|e| EasyHdrError::DriverError(Box::new(e)))?;
+
+    let mut physical = vec![PHYSICAL_MONITOR::default(); count as usize];
+    // SAFETY: `physical` has room for the `count` monitors Windows reported
+    unsafe { GetPhysicalMonitorsFromHMONITOR(monitor, &mut physical) }
+        .map_err(|e| EasyHdrError::DriverError(Box::new(e)))?;
+
+    let result = physical.iter().try_for_each(|physical_monitor| {
+        // SAFETY: the handle belongs to `physical`, which is destroyed only afterwards
+        let ok = unsafe {
+            SetVCPFeature(
+                physical_monitor.hPhysicalMonitor,
+                command.code,
+                u32::from(command.value),
+            )
+        };
+        if ok == 0 {
+            Err(EasyHdrError::DriverError(StringError::new(format!(
+                "{target} rejected DDC/CI command {command}"
+            ))))
+        } else {
+            Ok(())
+        }
+    });
+
+    // SAFETY: the handles were returned by GetPhysicalMonitorsFromHMONITOR above
+    if let Err(e) = unsafe { DestroyPhysicalMonitors(&physical) } {
+        use tracing::warn;
+        warn!("Failed to release physical monitor handles: {}", e);
+    }
+
+    result?;
+    info!("Sent DDC/CI command {} to {}", command, target);
+    Ok(())
+}
+
+/// Find the monitor handle of a GDI device such as `\\.\DISPLAY1`
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for monitor enumeration via EnumDisplayMonitors"
+)]
+fn find_monitor(gdi_device_name: &str) -> Option<windows::Win32::Graphics::Gdi::HMONITOR> {
+    use windows::Win32::Foundation::{LPARAM, RECT};
+    use windows::Win32::Graphics::Gdi::{
+        EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFOEXW,
+    };
+    use windows::core::BOOL;
+
+    struct Search<'a> {
+        name: &'a str,
+        found: Option<HMONITOR>,
+    }
+
+    unsafe extern "system" fn callback(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        data: LPARAM,
+    ) -> BOOL {
+        // SAFETY: `data` points to the `Search` owned by the enclosing call
+        let search = unsafe { &mut *(data.0 as *mut Search<'_>) };
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "size_of::<MONITORINFOEXW>() is a compile-time constant that fits in u32"
+        )]
+        let size = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = size;
+        // SAFETY: `info` is a MONITORINFOEXW with cbSize set accordingly
+        if unsafe { GetMonitorInfoW(monitor, (&raw mut info).cast()) }.as_bool() {
+            let len = info
+                .szDevice
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(info.szDevice.len());
+            if String::from_utf16_lossy(&info.szDevice[..len]).eq_ignore_ascii_case(search.name) {
+                search.found = Some(monitor);
+                return BOOL(0);
+            }
+        }
+        BOOL(1)
+    }
+
+    let mut search = Search {
+        name: gdi_device_name,
+        found: None,
+    };
+    // SAFETY: `search` outlives the enumeration, which calls `callback` synchronously.
+    // The result is ignored because stopping early also reports failure.
+    let _ = unsafe {
+        EnumDisplayMonitors(
+            None,
+            None,
+            Some(callback),
+            LPARAM((&raw mut search) as isize),
+        )
+    };
+    search.found
+}
+
+/// DDC/CI is only available on Windows
+#[cfg(not(windows))]
+pub fn send_vcp_command(target: &DisplayTarget, command: VcpCommand) -> Result<()> {
+    Err(EasyHdrError::DriverError(StringError::new(format!(
+        "cannot send DDC/CI command {command} to {target}: only supported on Windows"
+    ))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vcp_command_parse() {
+        assert_eq!(
+            VcpCommand::parse("E1=01"),
+            Some(VcpCommand {
+                code: 0xE1,
+                value: 0x01
+            })
+        );
+        assert_eq!(
+            VcpCommand::parse(" 0xFD = 0x0100 "),
+            Some(VcpCommand {
+                code: 0xFD,
+                value: 0x0100
+            })
+        );
+
+        // Codes are a single byte and both parts are required
+        assert_eq!(VcpCommand::parse("1E1=01"), None);
+        assert_eq!(VcpCommand::parse("E1"), None);
+        assert_eq!(VcpCommand::parse("E1="), None);
+        assert_eq!(VcpCommand::parse("pixel=refresh"), None);
+    }
+
+    #[test]
+    fn test_vcp_command_display_round_trip() {
+        let command = VcpCommand {
+            code: 0xE1,
+            value: 0x1,
+        };
+        assert_eq!(command.to_string(), "E1=01");
+        assert_eq!(VcpCommand::parse(&command.to_string()), Some(command));
+    }
+}
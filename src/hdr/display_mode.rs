@@ -0,0 +1,370 @@
+//! Display mode (resolution and refresh rate) switching
+//!
+//! Monitored applications can request a refresh rate and/or resolution that is applied
+//! to the primary display together with the HDR toggle, e.g. switching to 120 Hz when
+//! an HDR game starts. The mode in effect before the change is remembered and restored
+//! when HDR is turned off again. Changes are made with `ChangeDisplaySettingsExW`
+//! without updating the registry, so they never outlive the Windows session.
+
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Display resolution in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Resolution {
+    /// Horizontal resolution in pixels
+    pub width: u32,
+    /// Vertical resolution in pixels
+    pub height: u32,
+}
+
+impl Resolution {
+    /// Parse a resolution written as `WIDTHxHEIGHT`, e.g. `2560x1440`
+    pub fn parse(text: &str) -> Option<Self> {
+        let (width, height) = text.trim().split_once(['x', 'X', '×'])?;
+        let width = width.trim().parse().ok().filter(|&width| width > 0)?;
+        let height = height.trim().parse().ok().filter(|&height| height > 0)?;
+        Some(Self { width, height })
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// Display mode requested by a monitored application
+///
+/// Fields left as `None` keep the current setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DisplayModeOverride {
+    /// Refresh rate in Hz
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_rate_hz: Option<u32>,
+    /// Resolution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution>,
+}
+
+impl DisplayModeOverride {
+    /// Whether the override leaves every setting unchanged
+    pub fn is_empty(&self) -> bool {
+        self.refresh_rate_hz.is_none() && self.resolution.is_none()
+    }
+
+    /// Resolve the override against the current mode of the display
+    #[must_use]
+    pub fn resolve(&self, current: DisplayMode) -> DisplayMode {
+        DisplayMode {
+            resolution: self.resolution.unwrap_or(current.resolution),
+            refresh_rate_hz: self.refresh_rate_hz.unwrap_or(current.refresh_rate_hz),
+        }
+    }
+}
+
+impl fmt::Display for DisplayModeOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.resolution, self.refresh_rate_hz) {
+            (Some(resolution), Some(refresh_rate_hz)) => {
+                write!(f, "{resolution} @ {refresh_rate_hz} Hz")
+            }
+            (Some(resolution), None) => write!(f, "{resolution}"),
+            (None, Some(refresh_rate_hz)) => write!(f, "{refresh_rate_hz} Hz"),
+            (None, None) => write!(f, "unchanged"),
+        }
+    }
+}
+
+/// Concrete display mode of a display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayMode {
+    /// Resolution
+    pub resolution: Resolution,
+    /// Refresh rate in Hz
+    pub refresh_rate_hz: u32,
+}
+
+impl fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} @ {} Hz", self.resolution, self.refresh_rate_hz)
+    }
+}
+
+/// Display mode controller
+///
+/// Applies display mode overrides to the primary display and restores the original
+/// mode afterwards. Only the first applied override records the original mode, so
+/// applying several overrides in a row still restores the mode the user had.
+#[derive(Debug, Default)]
+pub struct DisplayModeController {
+    /// Mode in effect before the first applied override, `None` when nothing is applied
+    original: Option<DisplayMode>,
+}
+
+impl DisplayModeController {
+    /// Create a controller with no override applied
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether an override is currently applied
+    pub fn is_applied(&self) -> bool {
+        self.original.is_some()
+    }
+
+    /// Apply a display mode override to the primary display
+    ///
+    /// The mode is tested before it is set, so unsupported combinations fail without
+    /// touching the display.
+    pub fn apply(&mut self, request: DisplayModeOverride) -> Result<()> {
+        use tracing::info;
+
+        if request.is_empty() {
+            return Ok(());
+        }
+
+        let current = Self::current_mode()?;
+        let target = request.resolve(current);
+        if target == current {
+            return Ok(());
+        }
+
+        Self::set_mode(target)?;
+        info!("Changed display mode from {} to {}", current, target);
+
+        if self.original.is_none() {
+            self.original = Some(current);
+        }
+        Ok(())
+    }
+
+    /// Restore the display mode that was in effect before the first applied override
+    pub fn restore(&mut self) -> Result<()> {
+        use tracing::info;
+
+        let Some(original) = self.original.take() else {
+            return Ok(());
+        };
+
+        Self::set_mode(original)?;
+        info!("Restored display mode to {}", original);
+        Ok(())
+    }
+
+    /// Read the current mode of the primary display
+    fn current_mode() -> Result<DisplayMode> {
+        Self::current_mode_of(None)
+    }
+
+    /// Read the current mode of a display by its GDI device name (e.g. `\\.\DISPLAY1`),
+    /// or of the primary display for `None`
+    ///
+    /// # Safety
+    ///
+    /// `DEVMODEW` initialized with correct `dmSize`. The device name is NUL-terminated and
+    /// outlives the call. Return value checked before data access.
+    #[cfg(windows)]
+    #[expect(unsafe_code, reason = "Windows FFI for display mode query")]
+    pub fn current_mode_of(device_name: Option<&str>) -> Result<DisplayMode> {
+        use crate::error::{EasyHdrError, StringError};
+        use windows::Win32::Graphics::Gdi::{
+            DEVMODEW, ENUM_CURRENT_SETTINGS, EnumDisplaySettingsW,
+        };
+        use windows::core::PCWSTR;
+
+        let device_name: Option<Vec<u16>> =
+            device_name.map(|name| name.encode_utf16().chain(std::iter::once(0)).collect());
+        let device = device_name
+            .as_ref()
+            .map_or(PCWSTR::null(), |name| PCWSTR(name.as_ptr()));
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "size_of::<DEVMODEW>() is a compile-time constant (220 bytes) that fits in u16"
+        )]
+        let mut devmode = DEVMODEW {
+            dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+            ..Default::default()
+        };
+
+        unsafe {
+            if !EnumDisplaySettingsW(device, ENUM_CURRENT_SETTINGS, &raw mut devmode).as_bool() {
+                return Err(EasyHdrError::DriverError(StringError::new(
+                    "Failed to read the current display mode",
+                )));
+            }
+        }
+
+        Ok(DisplayMode {
+            resolution: Resolution {
+                width: devmode.dmPelsWidth,
+                height: devmode.dmPelsHeight,
+            },
+            refresh_rate_hz: devmode.dmDisplayFrequency,
+        })
+    }
+
+    /// Display modes are only supported on Windows
+    #[cfg(not(windows))]
+    pub fn current_mode_of(_device_name: Option<&str>) -> Result<DisplayMode> {
+        use crate::error::{EasyHdrError, StringError};
+
+        Err(EasyHdrError::DriverError(StringError::new(
+            "Display mode switching is only supported on Windows",
+        )))
+    }
+
+    /// Switch the primary display to a mode, testing it first
+    ///
+    /// # Safety
+    ///
+    /// `DEVMODEW` initialized with correct `dmSize` and `dmFields` matching the fields set.
+    /// Return codes checked.
+    #[cfg(windows)]
+    #[expect(unsafe_code, reason = "Windows FFI for display mode change")]
+    fn set_mode(mode: DisplayMode) -> Result<()> {
+        use crate::error::{EasyHdrError, StringError};
+        use windows::Win32::Graphics::Gdi::{
+            CDS_TEST, CDS_TYPE, ChangeDisplaySettingsExW, DEVMODEW, DISP_CHANGE_SUCCESSFUL,
+            DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
+        };
+        use windows::core::PCWSTR;
+
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "size_of::<DEVMODEW>() is a compile-time constant (220 bytes) that fits in u16"
+        )]
+        let devmode = DEVMODEW {
+            dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+            dmFields: DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYFREQUENCY,
+            dmPelsWidth: mode.resolution.width,
+            dmPelsHeight: mode.resolution.height,
+            dmDisplayFrequency: mode.refresh_rate_hz,
+            ..Default::default()
+        };
+
+        unsafe {
+            let result = ChangeDisplaySettingsExW(
+                PCWSTR::null(),
+                Some(&raw const devmode),
+                None,
+                CDS_TEST,
+                None,
+            );
+            if result != DISP_CHANGE_SUCCESSFUL {
+                return Err(EasyHdrError::DriverError(StringError::new(format!(
+                    "Display mode {mode} is not supported by the primary display (code {})",
+                    result.0
+                ))));
+            }
+
+            // No CDS_UPDATEREGISTRY: the change only lasts for the current session
+            let result = ChangeDisplaySettingsExW(
+                PCWSTR::null(),
+                Some(&raw const devmode),
+                None,
+                CDS_TYPE(0),
+                None,
+            );
+            if result != DISP_CHANGE_SUCCESSFUL {
+                return Err(EasyHdrError::DriverError(StringError::new(format!(
+                    "Failed to change display mode to {mode} (code {})",
+                    result.0
+                ))));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Display modes are only supported on Windows
+    #[cfg(not(windows))]
+    #[expect(
+        clippy::unnecessary_wraps,
+        reason = "Only the Windows implementation can fail"
+    )]
+    fn set_mode(_mode: DisplayMode) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const CURRENT: DisplayMode = DisplayMode {
+        resolution: Resolution {
+            width: 3840,
+            height: 2160,
+        },
+        refresh_rate_hz: 60,
+    };
+
+    #[test]
+    fn test_resolution_parse() {
+        assert_eq!(
+            Resolution::parse("2560x1440"),
+            Some(Resolution {
+                width: 2560,
+                height: 1440
+            })
+        );
+        assert_eq!(
+            Resolution::parse(" 1920 X 1080 "),
+            Some(Resolution {
+                width: 1920,
+                height: 1080
+            })
+        );
+        assert_eq!(Resolution::parse("2560"), None);
+        assert_eq!(Resolution::parse("0x1440"), None);
+        assert_eq!(Resolution::parse("widexhigh"), None);
+    }
+
+    #[test]
+    fn test_override_resolve_keeps_unset_fields() {
+        let refresh_only = DisplayModeOverride {
+            refresh_rate_hz: Some(120),
+            resolution: None,
+        };
+        assert_eq!(
+            refresh_only.resolve(CURRENT),
+            DisplayMode {
+                resolution: CURRENT.resolution,
+                refresh_rate_hz: 120,
+            }
+        );
+
+        assert!(DisplayModeOverride::default().is_empty());
+        assert_eq!(DisplayModeOverride::default().resolve(CURRENT), CURRENT);
+    }
+
+    #[test]
+    fn test_override_display() {
+        let both = DisplayModeOverride {
+            refresh_rate_hz: Some(120),
+            resolution: Resolution::parse("2560x1440"),
+        };
+        assert_eq!(both.to_string(), "2560x1440 @ 120 Hz");
+        assert_eq!(
+            DisplayModeOverride {
+                refresh_rate_hz: Some(144),
+                resolution: None,
+            }
+            .to_string(),
+            "144 Hz"
+        );
+    }
+
+    #[test]
+    fn test_restore_without_apply_is_noop() {
+        let mut controller = DisplayModeController::new();
+        assert!(!controller.is_applied());
+        controller.restore().unwrap();
+        controller.apply(DisplayModeOverride::default()).unwrap();
+        assert!(!controller.is_applied());
+    }
+}
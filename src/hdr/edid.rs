@@ -0,0 +1,277 @@
+//! HDR capabilities read from a monitor's EDID
+//!
+//! Parses the CTA-861 HDR Static Metadata Data Block, either from a CTA-861 extension
+//! block or embedded in a `DisplayID` 2.0 extension, to learn which transfer functions
+//! (EOTFs) the panel accepts and its luminance range. Comparing that with what Windows
+//! reports points at cable, port and driver problems: a panel that advertises HDR10 on a
+//! link where Windows says HDR is unsupported usually sits behind an HDMI 1.4 cable,
+//! adapter or outdated driver.
+
+use serde::{Deserialize, Serialize};
+
+/// Size of the EDID base block and of each extension block
+const BLOCK_SIZE: usize = 128;
+
+/// Tag of a CTA-861 extension block
+const CTA_EXTENSION_TAG: u8 = 0x02;
+
+/// Tag of a `DisplayID` extension block
+const DISPLAYID_EXTENSION_TAG: u8 = 0x70;
+
+/// `DisplayID` 2.0 data block carrying CTA-861 data blocks
+const DISPLAYID_CTA_BLOCK_TAG: u8 = 0x81;
+
+/// CTA-861 data block tag that is followed by an extended tag code
+const CTA_EXTENDED_TAG: u8 = 7;
+
+/// Extended tag code of the HDR Static Metadata Data Block
+const HDR_STATIC_METADATA_TAG: u8 = 6;
+
+/// HDR capabilities from the HDR Static Metadata Data Block
+///
+/// Luminance values are kept as the raw code values so the struct stays `Eq` and
+/// `Hash` like the display it belongs to; the methods convert them to cd/m².
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Fields map 1:1 to the EOTF bits of the data block"
+)]
+pub struct HdrStaticMetadata {
+    /// Traditional gamma, SDR luminance range
+    pub sdr: bool,
+    /// Traditional gamma, HDR luminance range
+    pub traditional_hdr: bool,
+    /// SMPTE ST 2084 (PQ), the transfer function of HDR10
+    pub pq: bool,
+    /// Hybrid Log-Gamma
+    pub hlg: bool,
+    /// Desired content max luminance code value
+    pub max_luminance_code: Option<u8>,
+    /// Desired content max frame-average luminance code value
+    pub max_frame_average_code: Option<u8>,
+    /// Desired content min luminance code value
+    pub min_luminance_code: Option<u8>,
+}
+
+impl HdrStaticMetadata {
+    /// Parse the payload following the extended tag code
+    fn parse(payload: &[u8]) -> Option<Self> {
+        let &eotfs = payload.first()?;
+        Some(Self {
+            sdr: eotfs & 0x01 != 0,
+            traditional_hdr: eotfs & 0x02 != 0,
+            pq: eotfs & 0x04 != 0,
+            hlg: eotfs & 0x08 != 0,
+            max_luminance_code: payload.get(2).copied().filter(|&code| code != 0),
+            max_frame_average_code: payload.get(3).copied().filter(|&code| code != 0),
+            min_luminance_code: payload.get(4).copied(),
+        })
+    }
+
+    /// Whether the panel accepts HDR10 (PQ) signals
+    pub fn supports_hdr10(&self) -> bool {
+        self.pq
+    }
+
+    /// Desired content max luminance in cd/m²
+    pub fn max_luminance(&self) -> Option<f64> {
+        self.max_luminance_code.map(luminance_from_code)
+    }
+
+    /// Desired content max frame-average luminance in cd/m²
+    pub fn max_frame_average_luminance(&self) -> Option<f64> {
+        self.max_frame_average_code.map(luminance_from_code)
+    }
+
+    /// Desired content min luminance in cd/m², which depends on the max luminance
+    pub fn min_luminance(&self) -> Option<f64> {
+        let max = self.max_luminance()?;
+        let code = f64::from(self.min_luminance_code?);
+        Some(max * (code / 255.0).powi(2) / 100.0)
+    }
+
+    /// Short summary, e.g. `HDR10, HLG, 993 nits`
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if self.pq {
+            parts.push("HDR10".to_string());
+        }
+        if self.hlg {
+            parts.push("HLG".to_string());
+        }
+        if parts.is_empty() {
+            parts.push("no HDR transfer function".to_string());
+        }
+        if let Some(max) = self.max_luminance() {
+            parts.push(format!("{max:.0} nits"));
+        }
+        parts.join(", ")
+    }
+
+    /// Compare the EDID with whether Windows reports HDR support for the display
+    ///
+    /// Returns a hint for the user when they disagree.
+    pub fn discrepancy(&self, windows_supports_hdr: bool) -> Option<&'static str> {
+        match (self.supports_hdr10(), windows_supports_hdr) {
+            (true, false) => Some(
+                "The panel supports HDR10, but Windows reports HDR as unsupported. Check the \
+                 cable, port and graphics driver.",
+            ),
+            (false, true) => Some(
+                "Windows reports HDR support, but the panel's EDID does not list HDR10. The \
+                 driver or an adapter may be overriding it.",
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Convert a max (frame-average) luminance code value to cd/m² (CTA-861-G 7.5.13)
+fn luminance_from_code(code: u8) -> f64 {
+    50.0 * 2f64.powf(f64::from(code) / 32.0)
+}
+
+/// Find the HDR Static Metadata Data Block in an EDID
+///
+/// Returns `None` if the EDID has no such block, which is also the case for most SDR
+/// monitors.
+pub fn hdr_static_metadata(edid: &[u8]) -> Option<HdrStaticMetadata> {
+    edid.chunks_exact(BLOCK_SIZE)
+        .skip(1)
+        .find_map(|block| match block[0] {
+            CTA_EXTENSION_TAG => cta_extension_metadata(block),
+            DISPLAYID_EXTENSION_TAG => displayid_metadata(block),
+            _ => None,
+        })
+}
+
+/// Search the data block collection of a CTA-861 extension block
+fn cta_extension_metadata(block: &[u8]) -> Option<HdrStaticMetadata> {
+    // Byte 2 is the offset of the detailed timings, which follow the data blocks
+    let end = usize::from(block[2]).min(BLOCK_SIZE);
+    if end <= 4 {
+        return None;
+    }
+    cta_data_blocks_metadata(&block[4..end])
+}
+
+/// Search a `DisplayID` extension block for an embedded CTA-861 data block
+fn displayid_metadata(block: &[u8]) -> Option<HdrStaticMetadata> {
+    // Byte 2 is the size of the section payload, which starts at byte 5
+    let end = (5 + usize::from(block[2])).min(BLOCK_SIZE - 1);
+    let mut offset = 5;
+    while offset + 3 <= end {
+        let tag = block[offset];
+        let length = usize::from(block[offset + 2]);
+        let payload = block.get(offset + 3..(offset + 3 + length).min(end))?;
+        if tag == DISPLAYID_CTA_BLOCK_TAG
+            && let Some(metadata) = cta_data_blocks_metadata(payload)
+        {
+            return Some(metadata);
+        }
+        if tag == 0 && length == 0 {
+            // Padding
+            break;
+        }
+        offset += 3 + length;
+    }
+    None
+}
+
+/// Search a collection of CTA-861 data blocks
+fn cta_data_blocks_metadata(blocks: &[u8]) -> Option<HdrStaticMetadata> {
+    let mut offset = 0;
+    while let Some(&header) = blocks.get(offset) {
+        let tag = header >> 5;
+        let length = usize::from(header & 0x1f);
+        let body = blocks.get(offset + 1..offset + 1 + length)?;
+        if tag == CTA_EXTENDED_TAG && body.first() == Some(&HDR_STATIC_METADATA_TAG) {
+            return HdrStaticMetadata::parse(&body[1..]);
+        }
+        offset += 1 + length;
+    }
+    None
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    /// HDR Static Metadata Data Block: SDR + PQ + HLG, max 138 (≈ 993 nits),
+    /// frame-average 90 (≈ 351 nits), min 20 (≈ 0.06 nits)
+    const HDR_BLOCK: [u8; 7] = [0xe6, 0x06, 0x0d, 0x01, 0x8a, 0x5a, 0x14];
+
+    /// EDID with an empty base block and one extension
+    fn edid_with_extension(extension: [u8; BLOCK_SIZE]) -> Vec<u8> {
+        let mut edid = vec![0; BLOCK_SIZE];
+        edid[126] = 1;
+        edid.extend_from_slice(&extension);
+        edid
+    }
+
+    fn cta_extension(data_blocks: &[u8]) -> [u8; BLOCK_SIZE] {
+        let mut block = [0; BLOCK_SIZE];
+        block[0] = CTA_EXTENSION_TAG;
+        block[1] = 3;
+        block[2] = u8::try_from(4 + data_blocks.len()).unwrap();
+        block[4..4 + data_blocks.len()].copy_from_slice(data_blocks);
+        block
+    }
+
+    #[test]
+    fn test_cta_extension_hdr_block() {
+        // A video data block before the HDR block is skipped
+        let mut data_blocks = vec![0x42, 0x10, 0x04];
+        data_blocks.extend_from_slice(&HDR_BLOCK);
+        let metadata = hdr_static_metadata(&edid_with_extension(cta_extension(&data_blocks)))
+            .expect("HDR block found");
+
+        assert!(metadata.sdr && metadata.pq && metadata.hlg);
+        assert!(!metadata.traditional_hdr);
+        assert!((metadata.max_luminance().unwrap() - 993.5).abs() < 0.1);
+        assert!((metadata.max_frame_average_luminance().unwrap() - 351.3).abs() < 0.1);
+        assert!((metadata.min_luminance().unwrap() - 0.061).abs() < 0.001);
+        assert_eq!(metadata.summary(), "HDR10, HLG, 993 nits");
+    }
+
+    #[test]
+    fn test_displayid_embedded_cta_block() {
+        let mut block = [0; BLOCK_SIZE];
+        block[0] = DISPLAYID_EXTENSION_TAG;
+        block[1] = 0x20;
+        block[2] = u8::try_from(3 + HDR_BLOCK.len()).unwrap();
+        block[5] = DISPLAYID_CTA_BLOCK_TAG;
+        block[7] = u8::try_from(HDR_BLOCK.len()).unwrap();
+        block[8..8 + HDR_BLOCK.len()].copy_from_slice(&HDR_BLOCK);
+
+        let metadata = hdr_static_metadata(&edid_with_extension(block)).unwrap();
+        assert!(metadata.supports_hdr10());
+    }
+
+    #[test]
+    fn test_sdr_monitor_has_no_metadata() {
+        assert_eq!(
+            hdr_static_metadata(&edid_with_extension(cta_extension(&[0x42, 0x10, 0x04]))),
+            None
+        );
+        assert_eq!(hdr_static_metadata(&[0; BLOCK_SIZE]), None);
+        // Truncated data blocks don't panic
+        assert_eq!(
+            hdr_static_metadata(&edid_with_extension(cta_extension(&[0xe6, 0x06]))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_discrepancy() {
+        let hdr10 = HdrStaticMetadata {
+            pq: true,
+            ..HdrStaticMetadata::default()
+        };
+        assert!(hdr10.discrepancy(false).unwrap().contains("cable"));
+        assert_eq!(hdr10.discrepancy(true), None);
+        assert!(HdrStaticMetadata::default().discrepancy(true).is_some());
+        assert_eq!(HdrStaticMetadata::default().discrepancy(false), None);
+    }
+}
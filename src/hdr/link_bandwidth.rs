@@ -0,0 +1,373 @@
+//! Cable and link bandwidth advisory
+//!
+//! HDR needs 10 bits per channel. At high resolutions and refresh rates that no longer
+//! fits through older links in full RGB/4:4:4, and Windows silently falls back to 4:2:2
+//! or 4:2:0 chroma (blurry colored text) or to 8-bit color with dithering, e.g. 4K at
+//! 120 Hz over HDMI 2.0. When a display with HDR on is driven with 8-bit subsampled
+//! chroma, this module estimates the data rate of the signal, infers the slowest link
+//! generation that can be carrying it and explains why full-chroma HDR does not fit.
+//! An SDR signal says nothing about the link, since 8-bit RGB is what Windows sends over
+//! any cable.
+//!
+//! The numbers are estimates: the blanking interval depends on the timing the display
+//! asks for, and Display Stream Compression (DSC) is invisible to Windows, so a display
+//! using DSC may show the advisory even though HDR keeps full chroma.
+
+use crate::hdr::{ConnectorType, DisplayMode};
+use std::fmt;
+
+/// Bits per channel HDR is sent with
+const HDR_BITS_PER_CHANNEL: u32 = 10;
+
+/// Refresh rate below which lowering the refresh rate is not suggested
+const MIN_SUGGESTED_REFRESH_HZ: u32 = 30;
+
+/// Color encoding of the signal (`DISPLAYCONFIG_COLOR_ENCODING`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorEncoding {
+    /// RGB
+    Rgb,
+    /// YCbCr without chroma subsampling
+    YCbCr444,
+    /// YCbCr with half horizontal chroma resolution
+    YCbCr422,
+    /// YCbCr with half horizontal and vertical chroma resolution
+    YCbCr420,
+    /// Single channel (grayscale)
+    Intensity,
+}
+
+impl ColorEncoding {
+    /// Convert a `DISPLAYCONFIG_COLOR_ENCODING` value
+    pub fn from_raw(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Rgb),
+            1 => Some(Self::YCbCr444),
+            2 => Some(Self::YCbCr422),
+            3 => Some(Self::YCbCr420),
+            4 => Some(Self::Intensity),
+            _ => None,
+        }
+    }
+
+    /// Whether the chroma channels are sent at a lower resolution
+    pub fn is_subsampled(self) -> bool {
+        matches!(self, Self::YCbCr422 | Self::YCbCr420)
+    }
+
+    /// Average number of channels sent per pixel
+    fn channels_per_pixel(self) -> f64 {
+        match self {
+            Self::Rgb | Self::YCbCr444 => 3.0,
+            Self::YCbCr422 => 2.0,
+            Self::YCbCr420 => 1.5,
+            Self::Intensity => 1.0,
+        }
+    }
+
+    /// Short name, e.g. `4:2:0`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rgb => "RGB",
+            Self::YCbCr444 => "4:4:4",
+            Self::YCbCr422 => "4:2:2",
+            Self::YCbCr420 => "4:2:0",
+            Self::Intensity => "grayscale",
+        }
+    }
+}
+
+/// Signal a display is currently driven with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkSignal {
+    /// Resolution and refresh rate
+    pub mode: DisplayMode,
+    /// Bits per color channel
+    pub bits_per_channel: u32,
+    /// Color encoding
+    pub encoding: ColorEncoding,
+}
+
+impl fmt::Display for LinkSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}-bit {}",
+            self.mode,
+            self.bits_per_channel,
+            self.encoding.name()
+        )
+    }
+}
+
+/// Link generation a display is attached through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkGeneration {
+    /// HDMI 2.0 (18 Gbit/s TMDS)
+    Hdmi20,
+    /// HDMI 2.1 (48 Gbit/s FRL)
+    Hdmi21,
+    /// `DisplayPort` 1.4 (HBR3)
+    DisplayPort14,
+    /// `DisplayPort` 2.1 (UHBR20)
+    DisplayPort21,
+}
+
+impl LinkGeneration {
+    /// Generations a connector may use, slowest first
+    fn candidates(connector: ConnectorType) -> &'static [Self] {
+        match connector {
+            ConnectorType::Hdmi => &[Self::Hdmi20, Self::Hdmi21],
+            ConnectorType::DisplayPort | ConnectorType::UsbC => {
+                &[Self::DisplayPort14, Self::DisplayPort21]
+            }
+            _ => &[],
+        }
+    }
+
+    /// Data rate available for pixels after line coding, in Gbit/s
+    pub fn capacity_gbps(self) -> f64 {
+        match self {
+            // 8b/10b
+            Self::Hdmi20 => 14.4,
+            // 16b/18b
+            Self::Hdmi21 => 42.67,
+            // 8b/10b over four lanes of 8.1 Gbit/s
+            Self::DisplayPort14 => 25.92,
+            // 128b/132b over four lanes of 20 Gbit/s
+            Self::DisplayPort21 => 77.37,
+        }
+    }
+
+    /// Ratio of total to active pixels the link's usual timings add for blanking
+    ///
+    /// HDMI sinks mostly use CTA-861 timings (e.g. 4400x2250 for 3840x2160), PC
+    /// monitors on `DisplayPort` mostly CVT reduced blanking.
+    fn blanking_overhead(self) -> f64 {
+        match self {
+            Self::Hdmi20 | Self::Hdmi21 => 1.19,
+            Self::DisplayPort14 | Self::DisplayPort21 => 1.08,
+        }
+    }
+
+    /// Human-readable name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hdmi20 => "HDMI 2.0",
+            Self::Hdmi21 => "HDMI 2.1",
+            Self::DisplayPort14 => "DisplayPort 1.4",
+            Self::DisplayPort21 => "DisplayPort 2.1",
+        }
+    }
+
+    /// Estimated data rate of a mode on this link, in Gbit/s
+    fn data_rate_gbps(
+        self,
+        mode: DisplayMode,
+        bits_per_channel: u32,
+        encoding: ColorEncoding,
+    ) -> f64 {
+        let pixels_per_second = f64::from(mode.resolution.width)
+            * f64::from(mode.resolution.height)
+            * f64::from(mode.refresh_rate_hz)
+            * self.blanking_overhead();
+        pixels_per_second * f64::from(bits_per_channel) * encoding.channels_per_pixel() / 1e9
+    }
+}
+
+impl fmt::Display for LinkGeneration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// HDR would not fit through the link in full chroma
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandwidthAdvisory {
+    /// Slowest link generation that can carry the current signal
+    pub link: LinkGeneration,
+    /// Current signal
+    pub signal: LinkSignal,
+    /// Estimated data rate of 10-bit RGB at the current mode, in Gbit/s
+    pub hdr_gbps: f64,
+    /// Highest refresh rate at which 10-bit RGB fits, if a useful one exists
+    pub max_full_chroma_hz: Option<u32>,
+}
+
+impl fmt::Display for BandwidthAdvisory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "HDR at {} needs about {:.1} Gbit/s for 10-bit RGB, more than {} carries ({:.1} Gbit/s), \
+             so Windows falls back to {}-bit {}",
+            self.signal.mode,
+            self.hdr_gbps,
+            self.link,
+            self.link.capacity_gbps(),
+            self.signal.bits_per_channel,
+            self.signal.encoding.name()
+        )?;
+        match self.max_full_chroma_hz {
+            Some(hz) => write!(
+                f,
+                ". Lower the refresh rate to {hz} Hz or use a faster cable and port for full chroma."
+            ),
+            None => f.write_str(". Use a faster cable and port for full chroma."),
+        }
+    }
+}
+
+/// Check whether HDR on a display degrades chroma and bit depth for lack of bandwidth
+///
+/// `hdr_enabled` is whether HDR is on for the display. Only an HDR signal that dropped
+/// to fewer than 10 bits with subsampled chroma points at a slow link; the link is then
+/// assumed to be the slowest generation of the connector that can carry it. Returns
+/// `None` for any other signal, when HDR fits, when the connector is not HDMI or
+/// `DisplayPort`, or when the current signal already exceeds every known generation
+/// (which means DSC is in use).
+pub fn hdr_advisory(
+    connector: ConnectorType,
+    signal: LinkSignal,
+    hdr_enabled: bool,
+) -> Option<BandwidthAdvisory> {
+    if !hdr_enabled
+        || signal.bits_per_channel >= HDR_BITS_PER_CHANNEL
+        || !signal.encoding.is_subsampled()
+    {
+        return None;
+    }
+
+    let link = LinkGeneration::candidates(connector)
+        .iter()
+        .copied()
+        .find(|link| {
+            link.data_rate_gbps(signal.mode, signal.bits_per_channel, signal.encoding)
+                <= link.capacity_gbps()
+        })?;
+
+    let hdr_gbps = link.data_rate_gbps(signal.mode, HDR_BITS_PER_CHANNEL, ColorEncoding::Rgb);
+    if hdr_gbps <= link.capacity_gbps() {
+        return None;
+    }
+
+    let per_hz = hdr_gbps / f64::from(signal.mode.refresh_rate_hz.max(1));
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "Refresh rates are small positive numbers"
+    )]
+    let max_hz = (link.capacity_gbps() / per_hz).floor() as u32;
+
+    Some(BandwidthAdvisory {
+        link,
+        signal,
+        hdr_gbps,
+        max_full_chroma_hz: (max_hz >= MIN_SUGGESTED_REFRESH_HZ).then_some(max_hz),
+    })
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::hdr::Resolution;
+
+    fn signal(width: u32, height: u32, hz: u32, bits: u32, encoding: ColorEncoding) -> LinkSignal {
+        LinkSignal {
+            mode: DisplayMode {
+                resolution: Resolution { width, height },
+                refresh_rate_hz: hz,
+            },
+            bits_per_channel: bits,
+            encoding,
+        }
+    }
+
+    #[test]
+    fn test_hdmi_20_at_4k120_degrades_chroma() {
+        let current = signal(3840, 2160, 120, 8, ColorEncoding::YCbCr420);
+        let advisory = hdr_advisory(ConnectorType::Hdmi, current, true).unwrap();
+
+        assert_eq!(advisory.link, LinkGeneration::Hdmi20);
+        assert!(advisory.hdr_gbps > 30.0);
+        assert_eq!(advisory.max_full_chroma_hz, Some(48));
+        let message = advisory.to_string();
+        assert!(message.contains("HDMI 2.0"));
+        assert!(message.contains("falls back to 8-bit 4:2:0"));
+    }
+
+    #[test]
+    fn test_hdmi_20_at_4k60_suggests_lower_refresh_rate() {
+        // 10-bit RGB at 4K60 does not fit HDMI 2.0, so HDR is sent as 8-bit 4:2:2
+        let advisory = hdr_advisory(
+            ConnectorType::Hdmi,
+            signal(3840, 2160, 60, 8, ColorEncoding::YCbCr422),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(advisory.link, LinkGeneration::Hdmi20);
+        assert_eq!(advisory.max_full_chroma_hz, Some(48));
+        assert!(
+            advisory
+                .to_string()
+                .contains("Lower the refresh rate to 48 Hz")
+        );
+    }
+
+    #[test]
+    fn test_full_chroma_signals_have_no_advisory() {
+        // 8-bit RGB is the usual SDR signal over any cable, fast or slow
+        let sdr = signal(3840, 2160, 60, 8, ColorEncoding::Rgb);
+        assert_eq!(hdr_advisory(ConnectorType::Hdmi, sdr, false), None);
+        assert_eq!(hdr_advisory(ConnectorType::Hdmi, sdr, true), None);
+        assert_eq!(
+            hdr_advisory(
+                ConnectorType::Hdmi,
+                signal(3840, 2160, 120, 10, ColorEncoding::Rgb),
+                true
+            ),
+            None
+        );
+        // Subsampled SDR may be what the display asks for, not a bandwidth limit
+        assert_eq!(
+            hdr_advisory(
+                ConnectorType::Hdmi,
+                signal(3840, 2160, 120, 8, ColorEncoding::YCbCr420),
+                false
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fast_links_have_no_advisory() {
+        // 8-bit 4:2:0 at 5120x2160 100 Hz over HDMI only fits HDMI 2.1, where 10-bit RGB
+        // fits as well
+        assert_eq!(
+            hdr_advisory(
+                ConnectorType::Hdmi,
+                signal(5120, 2160, 100, 8, ColorEncoding::YCbCr420),
+                true
+            ),
+            None
+        );
+        // Internal panels and unknown connectors are never flagged
+        assert_eq!(
+            hdr_advisory(
+                ConnectorType::Internal,
+                signal(7680, 4320, 240, 8, ColorEncoding::YCbCr420),
+                true
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_color_encoding_from_raw() {
+        assert_eq!(ColorEncoding::from_raw(0), Some(ColorEncoding::Rgb));
+        assert_eq!(ColorEncoding::from_raw(3), Some(ColorEncoding::YCbCr420));
+        assert!(ColorEncoding::from_raw(2).unwrap().is_subsampled());
+        assert_eq!(ColorEncoding::from_raw(9), None);
+    }
+}
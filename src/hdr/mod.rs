@@ -1,11 +1,55 @@
 //! HDR control module
 //!
 //! Controls HDR settings on Windows displays using the Windows Display Configuration API.
-//! Provides display enumeration, capability detection, and state control.
+//! Provides display enumeration, capability detection, and state control, plus
+//! per-application Windows 11 Auto HDR overrides and display mode switching. The crash
+//! guard turns HDR back off when `EasyHDR` exits abnormally while HDR is on. DDC/CI
+//! commands start vendor panel maintenance such as an OLED pixel refresh, and configured
+//! HDR color profiles are applied again after HDR turns on. On Windows 11 24H2 displays can
+//! also be switched to wide color gamut instead of HDR. A compatibility matrix records
+//! which APIs each Windows build range offers and how legacy advanced color is read. For
+//! debugging, the raw packets of every device info call can be traced to a file, and the
+//! HDR capabilities in each monitor's EDID are compared with what Windows reports. Each
+//! display's bit depth, color encoding, luminance range and primaries are shown in the
+//! displays dialog. The link bandwidth advisory warns when a cable or port is too slow for
+//! full-chroma HDR, and monitored applications can switch tone mapping of Game Bar HDR
+//! captures while they run. Known GPU driver quirks, such as a slow or dropped HDR
+//! switch, are worked around per display adapter, and Night Light can be suspended while
+//! HDR is on.
+//!
+//! The controller drives HDR through the [`HdrControl`] trait, so an in-memory
+//! [`MockHdrControl`] or a custom backend can stand in for [`HdrController`].
 
+pub mod api_trace;
+pub mod auto_hdr;
+pub mod backend;
+pub mod capabilities;
+pub mod capture;
+pub mod color_mode;
+pub mod color_profile;
+pub mod compat;
 pub mod controller;
+pub mod crash_guard;
+pub mod ddc;
+pub mod display_mode;
+pub mod edid;
+pub mod link_bandwidth;
+pub mod night_light;
+pub mod quirks;
 pub mod version;
 pub mod windows_api;
 
-pub use controller::{DisplayTarget, HdrController};
+pub use auto_hdr::{AutoHdrController, AutoHdrMode};
+pub use backend::{HDR_TOGGLE_ATTEMPTS, HdrControl, MockHdrControl};
+pub use capabilities::{DisplayCapabilities, OutputColorimetry};
+pub use capture::CaptureToneMappingController;
+pub use color_mode::ColorMode;
+pub use compat::{BuildSupport, Capabilities, LegacyAdvancedColor};
+pub use controller::{ConnectorType, DisplayIdentity, DisplayTarget, HdrController};
+pub use ddc::VcpCommand;
+pub use display_mode::{DisplayMode, DisplayModeController, DisplayModeOverride, Resolution};
+pub use edid::HdrStaticMetadata;
+pub use link_bandwidth::{BandwidthAdvisory, ColorEncoding, LinkGeneration, LinkSignal};
+pub use night_light::NightLightController;
+pub use quirks::{GpuInfo, GpuVendor, Workarounds};
 pub use version::WindowsVersion;
@@ -0,0 +1,272 @@
+//! Night Light suspension during HDR sessions
+//!
+//! Night Light shifts the whole desktop towards red, which in HDR skews the colors and
+//! brightness of games that are mastered for the full range. With the
+//! `suspend_night_light` preference, Night Light is turned off while a monitored
+//! application has HDR on and turned back on when HDR turns off again.
+//!
+//! Windows has no API for Night Light. Its state is the binary value [`STATE_VALUE`] in
+//! the current user's cloud store (see [`STATE_KEY`]): the byte at [`STATE_OFFSET`] is
+//! [`STATE_ON`] or [`STATE_OFF`], a two-byte field is only present while it is on, and
+//! Windows only picks up a change once the timestamp in front of them grows. The format
+//! is undocumented, so data that doesn't look like it is left alone.
+//!
+//! The suspension is recorded by the [crash guard](crate::hdr::crash_guard), so Night
+//! Light is turned back on at the next start if `EasyHDR` exits during the session.
+
+use crate::error::{EasyHdrError, Result, StringError};
+use crate::hdr::crash_guard;
+use std::ops::Range;
+
+/// Registry key holding the Night Light state
+pub const STATE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\CloudStore\Store\DefaultAccount\Current\default$windows.data.bluelightreduction.bluelightreductionstate\windows.data.bluelightreduction.bluelightreductionstate";
+
+/// Binary value holding the Night Light state
+pub const STATE_VALUE: &str = "Data";
+
+/// Offset of the byte telling whether Night Light is on
+pub const STATE_OFFSET: usize = 18;
+
+/// State byte while Night Light is on
+pub const STATE_ON: u8 = 0x15;
+
+/// State byte while Night Light is off
+pub const STATE_OFF: u8 = 0x13;
+
+/// Offset of the field only present while Night Light is on
+const ON_FIELD_OFFSET: usize = 23;
+
+/// Field only present while Night Light is on
+const ON_FIELD: [u8; 2] = [0x10, 0x00];
+
+/// Bytes of the timestamp that has to grow for Windows to apply a change
+const TIMESTAMP: Range<usize> = 10..15;
+
+/// Whether Night Light data says it is on, `None` if the data is not recognized
+pub fn is_on(data: &[u8]) -> Option<bool> {
+    if data.len() < ON_FIELD_OFFSET + ON_FIELD.len() {
+        return None;
+    }
+    match data[STATE_OFFSET] {
+        STATE_ON => Some(true),
+        STATE_OFF => Some(false),
+        _ => None,
+    }
+}
+
+/// Night Light data switched on or off, with its timestamp advanced
+///
+/// Returns `None` if the data is not recognized.
+pub fn with_state(data: &[u8], on: bool) -> Option<Vec<u8>> {
+    let was_on = is_on(data)?;
+    let mut data = data.to_vec();
+    if was_on != on {
+        if on {
+            data[STATE_OFFSET] = STATE_ON;
+            data.splice(ON_FIELD_OFFSET..ON_FIELD_OFFSET, ON_FIELD);
+        } else {
+            let field = ON_FIELD_OFFSET..ON_FIELD_OFFSET + ON_FIELD.len();
+            if data[field.clone()] != ON_FIELD {
+                return None;
+            }
+            data[STATE_OFFSET] = STATE_OFF;
+            data.drain(field);
+        }
+    }
+
+    let byte = data[TIMESTAMP].iter_mut().find(|byte| **byte != u8::MAX)?;
+    *byte += 1;
+    Some(data)
+}
+
+/// Night Light controller
+///
+/// Turns Night Light off for an HDR session and back on afterwards. Night Light is only
+/// turned back on if this controller turned it off, so it stays off for users who had
+/// it off and stays as it is if the user changed it during the session.
+#[derive(Debug, Default)]
+pub struct NightLightController {
+    /// Whether Night Light was turned off by [`NightLightController::suspend`]
+    suspended: bool,
+}
+
+impl NightLightController {
+    /// Create a controller that has not suspended Night Light
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a controller for Night Light that an earlier run turned off
+    ///
+    /// Used by the [crash guard](crate::hdr::crash_guard) to turn it back on after
+    /// `EasyHDR` exited during an HDR session.
+    pub fn suspended_earlier() -> Self {
+        Self { suspended: true }
+    }
+
+    /// Whether Night Light is currently suspended by this controller
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Turn Night Light off if it is on
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the Night Light state cannot be read or written.
+    pub fn suspend(&mut self) -> Result<()> {
+        use tracing::{debug, info};
+
+        if self.suspended {
+            return Ok(());
+        }
+        let Some(data) = Self::read()? else {
+            debug!("Night Light has never been set up for this user");
+            return Ok(());
+        };
+        if is_on(&data) != Some(true) {
+            return Ok(());
+        }
+
+        Self::write(&with_state(&data, false).ok_or_else(unrecognized)?)?;
+        self.suspended = true;
+        crash_guard::arm_night_light();
+        info!("Turned Night Light off for HDR");
+        Ok(())
+    }
+
+    /// Turn Night Light back on if [`NightLightController::suspend`] turned it off
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the Night Light state cannot be read or written.
+    pub fn restore(&mut self) -> Result<()> {
+        use tracing::info;
+
+        if !std::mem::take(&mut self.suspended) {
+            return Ok(());
+        }
+        crash_guard::disarm_night_light();
+        let Some(data) = Self::read()? else {
+            return Ok(());
+        };
+        // Turned back on during the session
+        if is_on(&data) != Some(false) {
+            return Ok(());
+        }
+
+        Self::write(&with_state(&data, true).ok_or_else(unrecognized)?)?;
+        info!("Turned Night Light back on");
+        Ok(())
+    }
+
+    /// Read the Night Light state, `None` when it was never set
+    #[cfg(windows)]
+    fn read() -> Result<Option<Vec<u8>>> {
+        use winreg::RegKey;
+        use winreg::enums::HKEY_CURRENT_USER;
+
+        let Ok(key) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(STATE_KEY) else {
+            return Ok(None);
+        };
+        match key.get_raw_value(STATE_VALUE) {
+            Ok(value) => Ok(Some(value.bytes.into_owned())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Night Light only exists on Windows
+    #[cfg(not(windows))]
+    #[expect(
+        clippy::unnecessary_wraps,
+        reason = "Only the Windows implementation can fail"
+    )]
+    fn read() -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    /// Write the Night Light state
+    #[cfg(windows)]
+    fn write(data: &[u8]) -> Result<()> {
+        use std::borrow::Cow;
+        use winreg::enums::{HKEY_CURRENT_USER, KEY_READ, KEY_WRITE, RegType};
+        use winreg::{RegKey, RegValue};
+
+        let key = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey_with_flags(STATE_KEY, KEY_READ | KEY_WRITE)?;
+        key.set_raw_value(
+            STATE_VALUE,
+            &RegValue {
+                bytes: Cow::Borrowed(data),
+                vtype: RegType::REG_BINARY,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Night Light only exists on Windows
+    #[cfg(not(windows))]
+    #[expect(
+        clippy::unnecessary_wraps,
+        reason = "Only the Windows implementation can fail"
+    )]
+    fn write(_data: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Error for Night Light data in an unknown format
+fn unrecognized() -> EasyHdrError {
+    EasyHdrError::ConfigError(StringError::new(
+        "Night Light state has an unrecognized format",
+    ))
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    /// Night Light state in the layout Windows writes while Night Light is off
+    fn off_data() -> Vec<u8> {
+        let mut data = vec![
+            0x43, 0x42, 0x01, 0x00, 0x0A, 0x02, 0x01, 0x00, 0x2A, 0x06, 0x9A, 0xC5, 0xD1, 0xB5,
+            0x06, 0x2A, 0x2B, 0x0E, 0x13, 0x43, 0x42, 0x01, 0x00,
+        ];
+        data.extend_from_slice(&[0xD0, 0x0A, 0x02, 0xC6, 0x14, 0xB1, 0xB4, 0xF6]);
+        data.extend_from_slice(&[0x80, 0x92, 0xF4, 0xB2, 0xDB, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        data
+    }
+
+    #[test]
+    fn test_switching_keeps_the_format() {
+        let off = off_data();
+        assert_eq!(is_on(&off), Some(false));
+
+        let on = with_state(&off, true).unwrap();
+        assert_eq!(is_on(&on), Some(true));
+        assert_eq!(on.len(), off.len() + 2);
+        assert_eq!(on[ON_FIELD_OFFSET..ON_FIELD_OFFSET + 2], ON_FIELD);
+        assert_eq!(on[10], off[10] + 1);
+
+        let off_again = with_state(&on, false).unwrap();
+        assert_eq!(is_on(&off_again), Some(false));
+        assert_eq!(off_again.len(), off.len());
+        assert_eq!(off_again[10], off[10] + 2);
+        assert_eq!(off_again[ON_FIELD_OFFSET..], off[ON_FIELD_OFFSET..]);
+    }
+
+    #[test]
+    fn test_unknown_data_is_left_alone() {
+        assert_eq!(is_on(&[0; 8]), None);
+        let mut data = off_data();
+        data[STATE_OFFSET] = 0x00;
+        assert_eq!(is_on(&data), None);
+        assert_eq!(with_state(&data, true), None);
+
+        let mut controller = NightLightController::new();
+        controller.restore().unwrap();
+        assert!(!controller.is_suspended());
+    }
+}
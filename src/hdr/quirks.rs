@@ -0,0 +1,317 @@
+//! GPU driver workarounds for setting HDR
+//!
+//! Some display drivers report success for an HDR change before the display has
+//! finished switching, or drop the first request after a mode change. [`detect_gpus`]
+//! reads the vendor and driver version of every display adapter from DXGI, and
+//! [`workarounds_for`] looks them up in [`QUIRKS`] to find out how
+//! [`HdrController::set_hdr_state`](crate::hdr::HdrController::set_hdr_state) should
+//! treat displays on that adapter: wait longer before reporting the change, or send it
+//! twice.
+//!
+//! Entries are kept as narrow as the reports behind them allow; a new entry needs the
+//! vendor and, where known, the range of driver versions it applies to.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// PCI vendor ID of NVIDIA
+const VENDOR_ID_NVIDIA: u32 = 0x10DE;
+/// PCI vendor ID of AMD
+const VENDOR_ID_AMD: u32 = 0x1002;
+/// PCI vendor ID of Intel
+const VENDOR_ID_INTEL: u32 = 0x8086;
+
+/// Manufacturer of a display adapter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GpuVendor {
+    /// NVIDIA
+    Nvidia,
+    /// AMD
+    Amd,
+    /// Intel
+    Intel,
+    /// Any other vendor, by PCI vendor ID
+    Other(u32),
+}
+
+impl GpuVendor {
+    /// Vendor from a PCI vendor ID
+    pub fn from_pci_id(vendor_id: u32) -> Self {
+        match vendor_id {
+            VENDOR_ID_NVIDIA => Self::Nvidia,
+            VENDOR_ID_AMD => Self::Amd,
+            VENDOR_ID_INTEL => Self::Intel,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl std::fmt::Display for GpuVendor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nvidia => f.write_str("NVIDIA"),
+            Self::Amd => f.write_str("AMD"),
+            Self::Intel => f.write_str("Intel"),
+            Self::Other(vendor_id) => write!(f, "vendor {vendor_id:#06x}"),
+        }
+    }
+}
+
+/// Version of a display driver as Windows reports it, e.g. `32.0.15.6590`
+///
+/// Compares part by part, so ranges of versions can be checked with `<=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DriverVersion(pub [u16; 4]);
+
+impl DriverVersion {
+    /// Version from the 64-bit user-mode driver version DXGI reports
+    pub fn from_umd_version(version: i64) -> Self {
+        #[expect(
+            clippy::cast_sign_loss,
+            reason = "The driver version is a packed 64-bit value read as a signed integer"
+        )]
+        let version = version as u64;
+        let part = |shift: u32| u16::try_from((version >> shift) & 0xFFFF).unwrap_or(u16::MAX);
+        Self([part(48), part(32), part(16), part(0)])
+    }
+}
+
+impl std::fmt::Display for DriverVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [major, minor, build, revision] = self.0;
+        write!(f, "{major}.{minor}.{build}.{revision}")
+    }
+}
+
+/// Display adapter with the driver it runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuInfo {
+    /// Adapter manufacturer
+    pub vendor: GpuVendor,
+    /// Adapter name, e.g. "NVIDIA `GeForce` RTX 4080"
+    pub name: String,
+    /// Driver version, `None` if DXGI did not report one
+    pub driver_version: Option<DriverVersion>,
+}
+
+impl std::fmt::Display for GpuInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.driver_version {
+            Some(version) => write!(f, "{} (driver {})", self.name, version),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// Driver behavior that needs a workaround when setting HDR
+#[derive(Debug, Clone, Copy)]
+pub struct Quirk {
+    /// Vendor whose drivers show the behavior
+    pub vendor: GpuVendor,
+    /// Oldest affected driver version, `None` for all older versions
+    pub min_driver: Option<DriverVersion>,
+    /// Newest affected driver version, `None` for all newer versions
+    pub max_driver: Option<DriverVersion>,
+    /// Extra time to wait after setting HDR, on top of the usual settle delay
+    pub extra_settle_delay: Duration,
+    /// Whether to send the HDR change a second time
+    pub reapply: bool,
+    /// What the workaround is for, logged when it applies
+    pub description: &'static str,
+}
+
+impl Quirk {
+    /// Whether the quirk applies to an adapter
+    ///
+    /// Quirks limited to a range of driver versions don't apply to adapters whose
+    /// driver version is unknown.
+    pub fn applies_to(&self, gpu: &GpuInfo) -> bool {
+        if gpu.vendor != self.vendor {
+            return false;
+        }
+        if self.min_driver.is_none() && self.max_driver.is_none() {
+            return true;
+        }
+        gpu.driver_version.is_some_and(|version| {
+            self.min_driver.is_none_or(|min| version >= min)
+                && self.max_driver.is_none_or(|max| version <= max)
+        })
+    }
+}
+
+/// Known driver quirks
+///
+/// Empty until a report pins down the vendor and driver versions a quirk shows up on; a
+/// vendor-wide entry would slow down or repeat every HDR switch on all of its GPUs.
+pub const QUIRKS: &[Quirk] = &[];
+
+/// Workarounds to use for displays on one adapter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Workarounds {
+    /// Extra time to wait after setting HDR
+    pub extra_settle_delay: Duration,
+    /// Whether to send the HDR change a second time
+    pub reapply: bool,
+}
+
+/// Combine the quirks from `table` that apply to an adapter
+///
+/// The longest extra delay wins, and the change is sent twice if any quirk asks for it.
+pub fn workarounds_for(gpu: &GpuInfo, table: &[Quirk]) -> Workarounds {
+    table.iter().filter(|quirk| quirk.applies_to(gpu)).fold(
+        Workarounds::default(),
+        |workarounds, quirk| Workarounds {
+            extra_settle_delay: workarounds.extra_settle_delay.max(quirk.extra_settle_delay),
+            reapply: workarounds.reapply || quirk.reapply,
+        },
+    )
+}
+
+/// Hardware display adapters by their LUID, as `(LowPart, HighPart)`
+///
+/// The LUID matches [`DisplayTarget::adapter_id`](crate::hdr::DisplayTarget). Software
+/// adapters such as the Microsoft Basic Render Driver are left out. Returns an empty map
+/// if DXGI is unavailable.
+///
+/// # Safety
+///
+/// The factory and adapters are reference-counted COM objects released when dropped.
+/// Enumeration stops at the first index DXGI reports as not found.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows COM FFI for reading adapter descriptions via DXGI"
+)]
+pub fn detect_gpus() -> HashMap<(u32, i32), GpuInfo> {
+    use tracing::debug;
+    use windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, DXGI_ADAPTER_FLAG_SOFTWARE, IDXGIDevice, IDXGIFactory1,
+    };
+    use windows::core::Interface;
+
+    let mut gpus = HashMap::new();
+    // SAFETY: creating a factory has no preconditions
+    let factory: IDXGIFactory1 = match unsafe { CreateDXGIFactory1() } {
+        Ok(factory) => factory,
+        Err(e) => {
+            debug!("Failed to create a DXGI factory: {}", e);
+            return gpus;
+        }
+    };
+
+    for adapter_index in 0u32.. {
+        // SAFETY: out-of-range indices return DXGI_ERROR_NOT_FOUND
+        let Ok(adapter) = (unsafe { factory.EnumAdapters1(adapter_index) }) else {
+            break;
+        };
+        // SAFETY: `adapter` is a valid IDXGIAdapter1 for the duration of the call
+        let desc = match unsafe { adapter.GetDesc1() } {
+            Ok(desc) => desc,
+            Err(e) => {
+                debug!("Failed to read the description of a display adapter: {}", e);
+                continue;
+            }
+        };
+        #[expect(
+            clippy::cast_sign_loss,
+            reason = "DXGI adapter flags are a bit mask declared as i32"
+        )]
+        let software = desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0;
+        if software {
+            continue;
+        }
+
+        // SAFETY: the GUID outlives the call; DXGI only reports the driver version for
+        // IDXGIDevice
+        let driver_version = unsafe { adapter.CheckInterfaceSupport(&IDXGIDevice::IID) }
+            .ok()
+            .map(DriverVersion::from_umd_version);
+        let len = desc
+            .Description
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(desc.Description.len());
+        gpus.insert(
+            (desc.AdapterLuid.LowPart, desc.AdapterLuid.HighPart),
+            GpuInfo {
+                vendor: GpuVendor::from_pci_id(desc.VendorId),
+                name: String::from_utf16_lossy(&desc.Description[..len]),
+                driver_version,
+            },
+        );
+    }
+    gpus
+}
+
+/// DXGI only exists on Windows
+#[cfg(not(windows))]
+pub fn detect_gpus() -> HashMap<(u32, i32), GpuInfo> {
+    HashMap::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpu(vendor: GpuVendor, driver_version: Option<[u16; 4]>) -> GpuInfo {
+        GpuInfo {
+            vendor,
+            name: "Test GPU".to_string(),
+            driver_version: driver_version.map(DriverVersion),
+        }
+    }
+
+    #[test]
+    fn test_driver_version() {
+        let version = DriverVersion::from_umd_version(0x0020_0000_000F_19BE);
+        assert_eq!(version, DriverVersion([32, 0, 15, 6590]));
+        assert_eq!(version.to_string(), "32.0.15.6590");
+        assert!(DriverVersion([31, 0, 15, 5222]) < version);
+        assert_eq!(GpuVendor::from_pci_id(0x10DE), GpuVendor::Nvidia);
+        assert_eq!(GpuVendor::from_pci_id(0x1234).to_string(), "vendor 0x1234");
+    }
+
+    #[test]
+    fn test_workarounds_for() {
+        let table = [
+            Quirk {
+                vendor: GpuVendor::Nvidia,
+                min_driver: Some(DriverVersion([32, 0, 15, 6000])),
+                max_driver: Some(DriverVersion([32, 0, 15, 6999])),
+                extra_settle_delay: Duration::from_millis(200),
+                reapply: false,
+                description: "slow",
+            },
+            Quirk {
+                vendor: GpuVendor::Nvidia,
+                min_driver: None,
+                max_driver: None,
+                extra_settle_delay: Duration::from_millis(50),
+                reapply: true,
+                description: "dropped",
+            },
+        ];
+
+        assert_eq!(
+            workarounds_for(&gpu(GpuVendor::Nvidia, Some([32, 0, 15, 6590])), &table),
+            Workarounds {
+                extra_settle_delay: Duration::from_millis(200),
+                reapply: true,
+            }
+        );
+        // Outside the range, and without a known version, only the general quirk applies
+        for version in [Some([32, 0, 15, 7000]), None] {
+            assert_eq!(
+                workarounds_for(&gpu(GpuVendor::Nvidia, version), &table),
+                Workarounds {
+                    extra_settle_delay: Duration::from_millis(50),
+                    reapply: true,
+                }
+            );
+        }
+        assert_eq!(
+            workarounds_for(&gpu(GpuVendor::Intel, Some([32, 0, 15, 6590])), &table),
+            Workarounds::default()
+        );
+    }
+}
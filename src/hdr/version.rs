@@ -104,6 +104,28 @@ impl WindowsVersion {
         }
     }
 
+    /// Whether this is a Windows Server edition
+    ///
+    /// Server editions share build numbers with client releases but are not tested, so
+    /// HDR capabilities are probed on them instead of assumed from the build number.
+    /// Returns `false` if the product type cannot be read.
+    pub fn is_server() -> bool {
+        #[cfg(windows)]
+        {
+            /// `wProductType` of client editions (`VER_NT_WORKSTATION`)
+            const VER_NT_WORKSTATION: u8 = 1;
+
+            Self::version_info_with_rtl_get_version()
+                .or_else(|_| Self::version_info_with_get_version_ex())
+                .is_ok_and(|info| info.wProductType != VER_NT_WORKSTATION)
+        }
+
+        #[cfg(not(windows))]
+        {
+            false
+        }
+    }
+
     /// Detect Windows version using `RtlGetVersion` from ntdll.dll
     ///
     /// This is the most reliable method as it's not subject to compatibility shims.
@@ -116,6 +138,12 @@ impl WindowsVersion {
     /// Get Windows build number using `RtlGetVersion` from ntdll.dll
     ///
     /// This is the most reliable method as it's not subject to compatibility shims.
+    #[cfg(windows)]
+    fn get_build_number_with_rtl_get_version() -> crate::error::Result<u32> {
+        Ok(Self::version_info_with_rtl_get_version()?.dwBuildNumber)
+    }
+
+    /// Get the full version information using `RtlGetVersion` from ntdll.dll
     ///
     /// # Safety
     ///
@@ -132,7 +160,7 @@ impl WindowsVersion {
         unsafe_code,
         reason = "Windows FFI for RtlGetVersion via ntdll.dll to detect Windows build number"
     )]
-    fn get_build_number_with_rtl_get_version() -> crate::error::Result<u32> {
+    fn version_info_with_rtl_get_version() -> crate::error::Result<OSVERSIONINFOEXW> {
         // Define the function signature for RtlGetVersion
         type RtlGetVersionFn = unsafe extern "system" fn(*mut OSVERSIONINFOEXW) -> i32;
 
@@ -176,7 +204,7 @@ impl WindowsVersion {
                 ));
             }
 
-            Ok(version_info.dwBuildNumber)
+            Ok(version_info)
         }
     }
 
@@ -192,6 +220,12 @@ impl WindowsVersion {
     /// Get Windows build number using `GetVersionExW` (fallback method)
     ///
     /// This method may be affected by compatibility shims but serves as a fallback.
+    #[cfg(windows)]
+    fn get_build_number_with_get_version_ex() -> crate::error::Result<u32> {
+        Ok(Self::version_info_with_get_version_ex()?.dwBuildNumber)
+    }
+
+    /// Get the full version information using `GetVersionExW` (fallback method)
     ///
     /// # Safety
     ///
@@ -204,7 +238,7 @@ impl WindowsVersion {
         unsafe_code,
         reason = "Windows FFI for GetVersionExW fallback method to detect Windows build number"
     )]
-    fn get_build_number_with_get_version_ex() -> crate::error::Result<u32> {
+    fn version_info_with_get_version_ex() -> crate::error::Result<OSVERSIONINFOEXW> {
         unsafe {
             #[expect(
                 clippy::cast_possible_truncation,
@@ -219,7 +253,7 @@ impl WindowsVersion {
             let result = GetVersionExW(std::ptr::addr_of_mut!(version_info).cast());
 
             if result.is_ok() {
-                Ok(version_info.dwBuildNumber)
+                Ok(version_info)
             } else {
                 Err(crate::error::EasyHdrError::WindowsApiError(
                     windows::core::Error::from_thread(),
@@ -368,6 +368,182 @@ impl DISPLAYCONFIG_SET_HDR_STATE {
     }
 }
 
+/// `DISPLAYCONFIG_SET_WCG_STATE` structure (Windows 11 24H2+)
+///
+/// Used to turn wide color gamut (advanced color for SDR content) on or off for a display
+/// target while HDR is off.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct DISPLAYCONFIG_SET_WCG_STATE {
+    /// Header
+    pub header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    /// Anonymous union containing bit fields
+    pub value: u32,
+}
+
+impl DISPLAYCONFIG_SET_WCG_STATE {
+    /// Create a new structure to enable or disable wide color gamut
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Structure size is a compile-time constant (size_of::<Self>()) which is always less than u32::MAX"
+    )]
+    pub fn new(adapter_id: LUID, target_id: u32, enable: bool) -> Self {
+        Self {
+            header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                type_: DISPLAYCONFIG_DEVICE_INFO_TYPE::DISPLAYCONFIG_DEVICE_INFO_SET_WCG_STATE,
+                size: std::mem::size_of::<Self>() as u32,
+                adapterId: adapter_id,
+                id: target_id,
+            },
+            value: u32::from(enable),
+        }
+    }
+}
+
+/// `DISPLAYCONFIG_TARGET_DEVICE_NAME` structure
+///
+/// Used to query the monitor name, connector type and EDID identifiers of a display
+/// target (`DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME`).
+///
+/// Total size: 420 bytes
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct DISPLAYCONFIG_TARGET_DEVICE_NAME {
+    /// Header
+    pub header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    /// `DISPLAYCONFIG_TARGET_DEVICE_NAME_FLAGS` bit fields
+    pub flags: u32,
+    /// Connector type (`DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY`)
+    pub outputTechnology: u32,
+    /// EDID manufacturer ID (big-endian PNP ID as stored in the EDID)
+    pub edidManufactureId: u16,
+    /// EDID product code
+    pub edidProductCodeId: u16,
+    /// Connector instance on the adapter
+    pub connectorInstance: u32,
+    /// Monitor friendly name (null-terminated UTF-16)
+    pub monitorFriendlyDeviceName: [u16; 64],
+    /// Monitor device interface path (null-terminated UTF-16)
+    pub monitorDevicePath: [u16; 128],
+}
+
+impl DISPLAYCONFIG_TARGET_DEVICE_NAME {
+    /// Create a new structure for querying the target name
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Structure size is a compile-time constant (size_of::<Self>()) which is always less than u32::MAX"
+    )]
+    pub fn new(adapter_id: LUID, target_id: u32) -> Self {
+        Self {
+            header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                type_: DISPLAYCONFIG_DEVICE_INFO_TYPE::DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+                size: std::mem::size_of::<Self>() as u32,
+                adapterId: adapter_id,
+                id: target_id,
+            },
+            flags: 0,
+            outputTechnology: 0,
+            edidManufactureId: 0,
+            edidProductCodeId: 0,
+            connectorInstance: 0,
+            monitorFriendlyDeviceName: [0; 64],
+            monitorDevicePath: [0; 128],
+        }
+    }
+
+    /// Check if the friendly name was read from the EDID
+    pub fn friendlyNameFromEdid(&self) -> bool {
+        (self.flags & 0x1) != 0
+    }
+
+    /// Check if the EDID manufacturer and product IDs are valid
+    pub fn edidIdsValid(&self) -> bool {
+        (self.flags & 0x4) != 0
+    }
+
+    /// Monitor friendly name, or `None` if Windows reported an empty name
+    pub fn friendly_name(&self) -> Option<String> {
+        utf16_until_nul(&self.monitorFriendlyDeviceName)
+    }
+
+    /// Monitor device interface path, or `None` if Windows reported an empty path
+    pub fn monitor_device_path(&self) -> Option<String> {
+        utf16_until_nul(&self.monitorDevicePath)
+    }
+}
+
+/// `DISPLAYCONFIG_SOURCE_DEVICE_NAME` structure
+///
+/// Used to query the GDI device name (e.g. `\\.\DISPLAY1`) of a display source
+/// (`DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME`), which identifies the monitor for GDI
+/// and DDC/CI APIs.
+///
+/// Total size: 84 bytes
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+    /// Header
+    pub header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    /// GDI device name (null-terminated UTF-16)
+    pub viewGdiDeviceName: [u16; 32],
+}
+
+impl DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+    /// Create a new structure for querying the source name
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Structure size is a compile-time constant (size_of::<Self>()) which is always less than u32::MAX"
+    )]
+    pub fn new(adapter_id: LUID, source_id: u32) -> Self {
+        Self {
+            header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                type_: DISPLAYCONFIG_DEVICE_INFO_TYPE::DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+                size: std::mem::size_of::<Self>() as u32,
+                adapterId: adapter_id,
+                id: source_id,
+            },
+            viewGdiDeviceName: [0; 32],
+        }
+    }
+
+    /// GDI device name, or `None` if Windows reported an empty name
+    pub fn gdi_device_name(&self) -> Option<String> {
+        utf16_until_nul(&self.viewGdiDeviceName)
+    }
+}
+
+/// Decode a null-terminated UTF-16 buffer, returning `None` for blank strings
+fn utf16_until_nul(buffer: &[u16]) -> Option<String> {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    let text = String::from_utf16_lossy(&buffer[..len]);
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// `DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY` values used to classify connectors
+pub mod output_technology {
+    /// VGA (HD15)
+    pub const HD15: u32 = 0;
+    /// DVI
+    pub const DVI: u32 = 4;
+    /// HDMI
+    pub const HDMI: u32 = 5;
+    /// LVDS (internal laptop panel)
+    pub const LVDS: u32 = 6;
+    /// External `DisplayPort`
+    pub const DISPLAYPORT_EXTERNAL: u32 = 10;
+    /// Embedded `DisplayPort` (internal laptop panel)
+    pub const DISPLAYPORT_EMBEDDED: u32 = 11;
+    /// Embedded UDI (internal panel)
+    pub const UDI_EMBEDDED: u32 = 13;
+    /// Miracast wireless display
+    pub const MIRACAST: u32 = 15;
+    /// `DisplayPort` tunnelled over USB-C
+    pub const DISPLAYPORT_USB_TUNNEL: u32 = 18;
+    /// Internal connection (e.g. integrated panel)
+    pub const INTERNAL: u32 = 0x8000_0000;
+}
+
 // DISPLAYCONFIG structures and constants
 // These are not available in windows-rs 0.52, so we define them manually
 
@@ -722,6 +898,48 @@ impl Default for DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_displayconfig_source_device_name() {
+        assert_eq!(std::mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>(), 84);
+
+        let mut name = DISPLAYCONFIG_SOURCE_DEVICE_NAME::new(LUID::default(), 2);
+        assert_eq!(name.header.size, 84);
+        assert_eq!(name.header.id, 2);
+        assert_eq!(name.gdi_device_name(), None);
+
+        for (slot, c) in name
+            .viewGdiDeviceName
+            .iter_mut()
+            .zip(r"\\.\DISPLAY2".encode_utf16())
+        {
+            *slot = c;
+        }
+        assert_eq!(name.gdi_device_name().as_deref(), Some(r"\\.\DISPLAY2"));
+    }
+
+    #[test]
+    fn test_displayconfig_target_device_name() {
+        assert_eq!(std::mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>(), 420);
+
+        let mut name = DISPLAYCONFIG_TARGET_DEVICE_NAME::new(LUID::default(), 7);
+        assert_eq!(name.header.size, 420);
+        assert_eq!(name.header.id, 7);
+        assert_eq!(name.friendly_name(), None);
+
+        for (slot, c) in name
+            .monitorFriendlyDeviceName
+            .iter_mut()
+            .zip("DELL U3223QE".encode_utf16())
+        {
+            *slot = c;
+        }
+        name.flags = 0x5;
+        assert_eq!(name.friendly_name().as_deref(), Some("DELL U3223QE"));
+        assert_eq!(name.monitor_device_path(), None);
+        assert!(name.friendlyNameFromEdid());
+        assert!(name.edidIdsValid());
+    }
+
     #[test]
     fn test_displayconfig_get_advanced_color_info_bit_fields() {
         // Test advancedColorSupported bit
@@ -887,6 +1105,33 @@ mod tests {
         assert_eq!(state.value, 0);
     }
 
+    #[test]
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Test uses compile-time constant (size_of::<Self>()) which is always less than u32::MAX"
+    )]
+    fn test_displayconfig_set_wcg_state_new() {
+        let luid = LUID {
+            LowPart: 0xABCD,
+            HighPart: 0xEF01,
+        };
+
+        let state = DISPLAYCONFIG_SET_WCG_STATE::new(luid, 7, true);
+        assert_eq!(
+            state.header.type_,
+            DISPLAYCONFIG_DEVICE_INFO_TYPE::DISPLAYCONFIG_DEVICE_INFO_SET_WCG_STATE
+        );
+        assert_eq!(
+            state.header.size,
+            std::mem::size_of::<DISPLAYCONFIG_SET_WCG_STATE>() as u32
+        );
+        assert_eq!(state.header.id, 7);
+        assert_eq!(state.value, 1);
+
+        let state = DISPLAYCONFIG_SET_WCG_STATE::new(luid, 7, false);
+        assert_eq!(state.value, 0);
+    }
+
     #[test]
     fn test_structure_sizes() {
         // Verify structure sizes are reasonable (should be multiples of 4 for alignment)
@@ -895,6 +1140,7 @@ mod tests {
         assert!(std::mem::size_of::<DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO_2>().is_multiple_of(4));
         assert!(std::mem::size_of::<DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE>().is_multiple_of(4));
         assert!(std::mem::size_of::<DISPLAYCONFIG_SET_HDR_STATE>().is_multiple_of(4));
+        assert!(std::mem::size_of::<DISPLAYCONFIG_SET_WCG_STATE>().is_multiple_of(4));
     }
 
     #[test]
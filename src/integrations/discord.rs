@@ -0,0 +1,434 @@
+//! Discord Rich Presence
+//!
+//! Shows "Playing <game> in HDR" on the user's Discord profile while a monitored
+//! application runs with HDR on. The presence is off by default and needs the client ID
+//! of a Discord application, whose name Discord shows above the activity.
+//!
+//! Discord listens on the named pipes `\\.\pipe\discord-ipc-0` to `-9`. Every message is
+//! a frame of a little-endian opcode, a little-endian payload length and a JSON payload.
+//! A connection starts with a handshake naming the client ID, after which Discord answers
+//! `SET_ACTIVITY` commands. A background thread owns the connection: it connects when
+//! there is something to show, retries every [`RETRY_INTERVAL`] while Discord is not
+//! running, and reconnects when Discord restarts. Closing the pipe clears the presence.
+
+use serde_json::{Value, json};
+use std::io::{self, Read, Write};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Time between connection attempts while Discord is not reachable
+pub const RETRY_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Number of pipes Discord may listen on
+#[cfg(windows)]
+const PIPE_COUNT: u32 = 10;
+
+/// Largest payload accepted from Discord, guarding against a corrupt length
+const MAX_PAYLOAD_LEN: u32 = 64 * 1024;
+
+/// Opcodes of the IPC frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opcode {
+    Handshake = 0,
+    Frame = 1,
+    Close = 2,
+    Ping = 3,
+    Pong = 4,
+}
+
+impl Opcode {
+    fn from_raw(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Handshake),
+            1 => Some(Self::Frame),
+            2 => Some(Self::Close),
+            3 => Some(Self::Ping),
+            4 => Some(Self::Pong),
+            _ => None,
+        }
+    }
+}
+
+/// Activity shown on the user's profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    /// First line, e.g. `Playing Cyberpunk 2077 in HDR`
+    pub details: String,
+    /// Start of the session in seconds since the Unix epoch, shown as elapsed time
+    pub started_at: u64,
+}
+
+impl Activity {
+    /// Activity for a monitored application running with HDR on
+    pub fn playing_in_hdr(app_name: &str, started_at: u64) -> Self {
+        Self {
+            details: format!("Playing {app_name} in HDR"),
+            started_at,
+        }
+    }
+}
+
+/// Encode one IPC frame
+fn encode_frame(opcode: Opcode, payload: &Value) -> Vec<u8> {
+    let payload = payload.to_string();
+    let len = u32::try_from(payload.len()).unwrap_or(u32::MAX);
+    let mut frame = Vec::with_capacity(8 + payload.len());
+    frame.extend_from_slice(&(opcode as u32).to_le_bytes());
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend_from_slice(payload.as_bytes());
+    frame
+}
+
+/// Read one IPC frame
+fn read_frame(reader: &mut impl Read) -> io::Result<(Opcode, Value)> {
+    let mut header = [0u8; 8];
+    reader.read_exact(&mut header)?;
+    let opcode = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+    let opcode = Opcode::from_raw(opcode)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unknown opcode"))?;
+    if len > MAX_PAYLOAD_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "payload too large",
+        ));
+    }
+
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload)?;
+    let payload = serde_json::from_slice(&payload)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok((opcode, payload))
+}
+
+/// Payload of the handshake frame
+fn handshake_payload(client_id: &str) -> Value {
+    json!({ "v": 1, "client_id": client_id })
+}
+
+/// Payload of a `SET_ACTIVITY` command; `None` clears the presence
+fn set_activity_payload(pid: u32, activity: Option<&Activity>, nonce: u64) -> Value {
+    let activity = activity.map(|activity| {
+        json!({
+            "details": activity.details,
+            "timestamps": { "start": activity.started_at },
+        })
+    });
+    json!({
+        "cmd": "SET_ACTIVITY",
+        "args": { "pid": pid, "activity": activity },
+        "nonce": nonce.to_string(),
+    })
+}
+
+/// Open IPC connection to the Discord client
+struct Connection<S> {
+    stream: S,
+    nonce: u64,
+}
+
+impl<S: Read + Write> Connection<S> {
+    /// Perform the handshake on a freshly opened stream
+    #[cfg_attr(
+        all(not(windows), not(test)),
+        expect(dead_code, reason = "Discord IPC pipes are only opened on Windows")
+    )]
+    fn handshake(mut stream: S, client_id: &str) -> io::Result<Self> {
+        stream.write_all(&encode_frame(
+            Opcode::Handshake,
+            &handshake_payload(client_id),
+        ))?;
+        let mut connection = Self { stream, nonce: 0 };
+        connection.read_response()?;
+        Ok(connection)
+    }
+
+    /// Show an activity, or clear the presence for `None`
+    fn set_activity(&mut self, activity: Option<&Activity>) -> io::Result<()> {
+        self.nonce += 1;
+        let payload = set_activity_payload(std::process::id(), activity, self.nonce);
+        self.stream
+            .write_all(&encode_frame(Opcode::Frame, &payload))?;
+        let response = self.read_response()?;
+        if response.get("evt").and_then(Value::as_str) == Some("ERROR") {
+            let message = response
+                .pointer("/data/message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(io::Error::other(format!(
+                "Discord rejected the activity: {message}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Wait for the answer to the last frame, replying to pings on the way
+    fn read_response(&mut self) -> io::Result<Value> {
+        loop {
+            match read_frame(&mut self.stream)? {
+                (Opcode::Frame, payload) => return Ok(payload),
+                (Opcode::Ping, payload) => {
+                    self.stream
+                        .write_all(&encode_frame(Opcode::Pong, &payload))?;
+                }
+                (Opcode::Close, payload) => {
+                    let message = payload
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("connection closed");
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        message.to_string(),
+                    ));
+                }
+                (Opcode::Handshake | Opcode::Pong, _) => {}
+            }
+        }
+    }
+}
+
+/// Open the first Discord IPC pipe that accepts a connection
+#[cfg(windows)]
+fn connect(client_id: &str) -> io::Result<Connection<std::fs::File>> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "Discord is not running");
+    for index in 0..PIPE_COUNT {
+        let pipe = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!(r"\\.\pipe\discord-ipc-{index}"));
+        match pipe.and_then(|pipe| Connection::handshake(pipe, client_id)) {
+            Ok(connection) => return Ok(connection),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+/// Discord IPC pipes only exist on Windows
+#[cfg(not(windows))]
+fn connect(_client_id: &str) -> io::Result<Connection<std::fs::File>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Discord Rich Presence is only supported on Windows",
+    ))
+}
+
+/// Handle to the background thread showing the presence
+///
+/// Dropping the handle stops the thread, which closes the connection and so clears the
+/// presence.
+#[derive(Debug)]
+pub struct DiscordPresence {
+    /// Client ID of the Discord application the thread was started with
+    client_id: String,
+    /// Activity updates for the thread
+    sender: mpsc::Sender<Option<Activity>>,
+}
+
+impl DiscordPresence {
+    /// Start the background thread for a Discord application client ID
+    ///
+    /// Nothing is shown, and no connection is made, until [`Self::update`] sets an
+    /// activity.
+    pub fn start(client_id: &str) -> io::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let thread_client_id = client_id.to_string();
+        std::thread::Builder::new()
+            .name("discord-presence".to_string())
+            .spawn(move || run(&thread_client_id, &receiver))?;
+        Ok(Self {
+            client_id: client_id.to_string(),
+            sender,
+        })
+    }
+
+    /// Client ID the presence was started with
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Show an activity, or clear the presence for `None`
+    ///
+    /// Repeating the current activity costs nothing.
+    pub fn update(&self, activity: Option<Activity>) {
+        // The thread only exits once this handle is dropped
+        let _ = self.sender.send(activity);
+    }
+}
+
+/// Body of the background thread
+fn run(client_id: &str, receiver: &mpsc::Receiver<Option<Activity>>) {
+    use tracing::{debug, info};
+
+    let mut wanted: Option<Activity> = None;
+    let mut shown: Option<Option<Activity>> = None;
+    let mut connection: Option<Connection<std::fs::File>> = None;
+    let mut last_attempt: Option<Instant> = None;
+
+    loop {
+        match receiver.recv_timeout(RETRY_INTERVAL) {
+            Ok(activity) => wanted = activity,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        // Only the latest of several queued updates matters
+        while let Ok(activity) = receiver.try_recv() {
+            wanted = activity;
+        }
+
+        if connection.is_none()
+            && wanted.is_some()
+            && last_attempt.is_none_or(|attempt| attempt.elapsed() >= RETRY_INTERVAL)
+        {
+            last_attempt = Some(Instant::now());
+            match connect(client_id) {
+                Ok(connected) => {
+                    info!("Connected to Discord for Rich Presence");
+                    connection = Some(connected);
+                    shown = None;
+                }
+                Err(e) => debug!("Discord is not reachable: {}", e),
+            }
+        }
+
+        let Some(open) = connection.as_mut() else {
+            continue;
+        };
+        if shown.as_ref() == Some(&wanted) {
+            continue;
+        }
+        match open.set_activity(wanted.as_ref()) {
+            Ok(()) => {
+                if let Some(activity) = &wanted {
+                    debug!("Discord presence: {}", activity.details);
+                } else {
+                    debug!("Discord presence cleared");
+                }
+                shown = Some(wanted.clone());
+            }
+            Err(e) => {
+                info!("Lost the Discord connection: {}", e);
+                connection = None;
+                last_attempt = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// In-memory stream replaying Discord's answers and recording what was written
+    struct FakeDiscord {
+        answers: Cursor<Vec<u8>>,
+        written: Vec<u8>,
+    }
+
+    impl FakeDiscord {
+        fn new(answers: &[(Opcode, Value)]) -> Self {
+            let answers = answers
+                .iter()
+                .flat_map(|(opcode, payload)| encode_frame(*opcode, payload))
+                .collect();
+            Self {
+                answers: Cursor::new(answers),
+                written: Vec::new(),
+            }
+        }
+    }
+
+    impl Read for FakeDiscord {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.answers.read(buf)
+        }
+    }
+
+    impl Write for FakeDiscord {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let payload = json!({ "cmd": "DISPATCH", "evt": "READY" });
+        let frame = encode_frame(Opcode::Frame, &payload);
+        assert_eq!(&frame[..4], &1u32.to_le_bytes());
+        assert_eq!(
+            read_frame(&mut Cursor::new(frame)).unwrap(),
+            (Opcode::Frame, payload.clone())
+        );
+
+        let mut corrupt = encode_frame(Opcode::Frame, &payload);
+        corrupt[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(read_frame(&mut Cursor::new(corrupt)).is_err());
+    }
+
+    #[test]
+    fn test_set_activity_payload() {
+        let activity = Activity::playing_in_hdr("Cyberpunk 2077", 1_700_000_000);
+        let payload = set_activity_payload(42, Some(&activity), 7);
+        assert_eq!(payload["cmd"], "SET_ACTIVITY");
+        assert_eq!(payload["args"]["pid"], 42);
+        assert_eq!(
+            payload["args"]["activity"]["details"],
+            "Playing Cyberpunk 2077 in HDR"
+        );
+        assert_eq!(
+            payload["args"]["activity"]["timestamps"]["start"],
+            1_700_000_000
+        );
+        assert_eq!(payload["nonce"], "7");
+
+        assert!(set_activity_payload(42, None, 8)["args"]["activity"].is_null());
+    }
+
+    #[test]
+    fn test_connection_handshake_and_activity() {
+        let stream = FakeDiscord::new(&[
+            (Opcode::Frame, json!({ "cmd": "DISPATCH", "evt": "READY" })),
+            (Opcode::Ping, json!({})),
+            (Opcode::Frame, json!({ "cmd": "SET_ACTIVITY", "evt": null })),
+            (
+                Opcode::Frame,
+                json!({ "evt": "ERROR", "data": { "message": "bad activity" } }),
+            ),
+        ]);
+        let mut connection = Connection::handshake(stream, "123").unwrap();
+        let activity = Activity::playing_in_hdr("Game", 1);
+        connection.set_activity(Some(&activity)).unwrap();
+        let error = connection.set_activity(None).unwrap_err();
+        assert!(error.to_string().contains("bad activity"));
+
+        // Handshake, activity, pong and the rejected clear were written in order
+        let mut written = Cursor::new(connection.stream.written);
+        let opcodes: Vec<Opcode> = std::iter::from_fn(|| read_frame(&mut written).ok())
+            .map(|(opcode, _)| opcode)
+            .collect();
+        assert_eq!(
+            opcodes,
+            [
+                Opcode::Handshake,
+                Opcode::Frame,
+                Opcode::Pong,
+                Opcode::Frame
+            ]
+        );
+    }
+
+    #[test]
+    fn test_close_frame_is_an_error() {
+        let stream =
+            FakeDiscord::new(&[(Opcode::Close, json!({ "message": "Invalid Client ID" }))]);
+        let error = Connection::handshake(stream, "0").err().unwrap();
+        assert!(error.to_string().contains("Invalid Client ID"));
+    }
+}
@@ -0,0 +1,179 @@
+//! Epic Games Launcher library enumeration
+//!
+//! The launcher writes one JSON manifest (`*.item`) per installed app to
+//! `%ProgramData%\Epic\EpicGamesLauncher\Data\Manifests`. Each manifest names the
+//! install location and the executable the launcher starts, so no guessing is needed
+//! unless the executable field is empty.
+
+use super::{GameLibrary, LibraryGame};
+use crate::error::Result;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Subset of an Epic Games Launcher `.item` manifest
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EpicManifest {
+    display_name: String,
+    install_location: PathBuf,
+    #[serde(default)]
+    launch_executable: String,
+    #[serde(default)]
+    app_categories: Vec<String>,
+    #[serde(default, rename = "bIsIncompleteInstall")]
+    is_incomplete_install: bool,
+}
+
+/// Parse a single `.item` manifest into a game
+///
+/// Returns `None` for malformed manifests, incomplete installs and non-game apps
+/// (e.g. Unreal Engine installs, whose categories do not include `games`). The
+/// executable path is not checked for existence.
+pub fn parse_manifest(json: &str) -> Option<LibraryGame> {
+    let manifest: EpicManifest = serde_json::from_str(json).ok()?;
+
+    if manifest.is_incomplete_install
+        || (!manifest.app_categories.is_empty()
+            && !manifest
+                .app_categories
+                .iter()
+                .any(|category| category.eq_ignore_ascii_case("games")))
+    {
+        return None;
+    }
+
+    let exe_path = if manifest.launch_executable.is_empty() {
+        super::find_main_executable(&manifest.install_location, &manifest.display_name)?
+    } else {
+        manifest.install_location.join(&manifest.launch_executable)
+    };
+
+    Some(LibraryGame {
+        name: manifest.display_name,
+        exe_path,
+        library: GameLibrary::Epic,
+    })
+}
+
+/// Enumerate installed games from a manifest directory
+pub fn games_in_manifest_dir(dir: &Path) -> Result<Vec<LibraryGame>> {
+    use tracing::debug;
+
+    let mut games = Vec::new();
+
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("item"))
+        {
+            continue;
+        }
+
+        match std::fs::read_to_string(&path)
+            .ok()
+            .as_deref()
+            .and_then(parse_manifest)
+        {
+            Some(game) if game.exe_path.is_file() => games.push(game),
+            Some(game) => debug!(
+                "Skipping Epic game {} with missing executable {:?}",
+                game.name, game.exe_path
+            ),
+            None => debug!("Skipping Epic manifest {:?}", path),
+        }
+    }
+
+    Ok(games)
+}
+
+/// Enumerate installed Epic Games Launcher games
+///
+/// Returns an empty list if the launcher is not installed.
+pub fn enumerate_games() -> Result<Vec<LibraryGame>> {
+    use tracing::debug;
+
+    let Some(program_data) = std::env::var_os("ProgramData") else {
+        return Ok(Vec::new());
+    };
+
+    let manifest_dir = PathBuf::from(program_data).join(r"Epic\EpicGamesLauncher\Data\Manifests");
+    if !manifest_dir.is_dir() {
+        debug!("Epic Games Launcher manifests not found");
+        return Ok(Vec::new());
+    }
+
+    games_in_manifest_dir(&manifest_dir)
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let json = r#"{
+            "FormatVersion": 0,
+            "DisplayName": "Alan Wake 2",
+            "InstallLocation": "D:\\Epic Games\\AlanWake2",
+            "LaunchExecutable": "AlanWake2.exe",
+            "AppCategories": ["public", "games", "applications"],
+            "bIsIncompleteInstall": false
+        }"#;
+
+        let game = parse_manifest(json).unwrap();
+        assert_eq!(game.name, "Alan Wake 2");
+        assert_eq!(
+            game.exe_path,
+            PathBuf::from("D:\\Epic Games\\AlanWake2").join("AlanWake2.exe")
+        );
+        assert_eq!(game.library, GameLibrary::Epic);
+    }
+
+    #[test]
+    fn test_parse_manifest_skips_non_games_and_incomplete_installs() {
+        let engine = r#"{
+            "DisplayName": "Unreal Engine",
+            "InstallLocation": "C:\\UE_5.3",
+            "LaunchExecutable": "Engine/Binaries/Win64/UnrealEditor.exe",
+            "AppCategories": ["engines"]
+        }"#;
+        assert!(parse_manifest(engine).is_none());
+
+        let incomplete = r#"{
+            "DisplayName": "Fortnite",
+            "InstallLocation": "C:\\Fortnite",
+            "LaunchExecutable": "FortniteLauncher.exe",
+            "AppCategories": ["games"],
+            "bIsIncompleteInstall": true
+        }"#;
+        assert!(parse_manifest(incomplete).is_none());
+
+        assert!(parse_manifest("not json").is_none());
+    }
+
+    #[test]
+    fn test_games_in_manifest_dir_requires_existing_executable() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let install = temp.path().join("Game");
+        std::fs::create_dir_all(&install).unwrap();
+        std::fs::write(install.join("Game.exe"), b"MZ").unwrap();
+
+        let manifest = |name: &str, exe: &str| {
+            serde_json::json!({
+                "DisplayName": name,
+                "InstallLocation": install,
+                "LaunchExecutable": exe,
+                "AppCategories": ["games"],
+            })
+            .to_string()
+        };
+        std::fs::write(temp.path().join("a.item"), manifest("Game", "Game.exe")).unwrap();
+        std::fs::write(temp.path().join("b.item"), manifest("Gone", "Gone.exe")).unwrap();
+
+        let games = games_in_manifest_dir(temp.path()).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Game");
+    }
+}
@@ -0,0 +1,81 @@
+//! GOG Galaxy library enumeration
+//!
+//! GOG installers register every game under `HKLM\SOFTWARE\WOW6432Node\GOG.com\Games`,
+//! one subkey per product ID, with the game title (`gameName`) and the full path of
+//! the main executable (`exe`). DLCs are registered the same way but name their base
+//! game in `dependsOn` and are skipped.
+
+use super::LibraryGame;
+use crate::error::Result;
+
+/// Registry paths GOG registers games under (64-bit view first)
+#[cfg(windows)]
+const GOG_GAMES_KEYS: &[&str] = &[
+    r"SOFTWARE\WOW6432Node\GOG.com\Games",
+    r"SOFTWARE\GOG.com\Games",
+];
+
+/// Enumerate installed GOG games
+///
+/// Returns an empty list if no GOG games are registered.
+#[cfg(windows)]
+pub fn enumerate_games() -> Result<Vec<LibraryGame>> {
+    use super::GameLibrary;
+    use std::path::PathBuf;
+    use tracing::debug;
+    use winreg::RegKey;
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let Some(games_key) = GOG_GAMES_KEYS
+        .iter()
+        .find_map(|path| hklm.open_subkey(path).ok())
+    else {
+        debug!("No GOG games registered");
+        return Ok(Vec::new());
+    };
+
+    let mut games = Vec::new();
+    for product_id in games_key.enum_keys().flatten() {
+        let Ok(game_key) = games_key.open_subkey(&product_id) else {
+            continue;
+        };
+
+        let depends_on = game_key
+            .get_value::<String, _>("dependsOn")
+            .unwrap_or_default();
+        if !depends_on.is_empty() {
+            continue; // DLC
+        }
+
+        let (Ok(name), Ok(exe)) = (
+            game_key.get_value::<String, _>("gameName"),
+            game_key.get_value::<String, _>("exe"),
+        ) else {
+            debug!("Skipping incomplete GOG registration {}", product_id);
+            continue;
+        };
+
+        let exe_path = PathBuf::from(exe);
+        if exe_path.is_file() {
+            games.push(LibraryGame {
+                name,
+                exe_path,
+                library: GameLibrary::Gog,
+            });
+        } else {
+            debug!(
+                "Skipping GOG game {} with missing executable {:?}",
+                name, exe_path
+            );
+        }
+    }
+
+    Ok(games)
+}
+
+/// GOG Galaxy is only supported on Windows
+#[cfg(not(windows))]
+pub fn enumerate_games() -> Result<Vec<LibraryGame>> {
+    Ok(Vec::new())
+}
@@ -0,0 +1,259 @@
+//! Game launcher library integrations
+//!
+//! Enumerates games installed through Steam, the Epic Games Launcher and GOG Galaxy so
+//! the GUI can offer an "import games" list instead of requiring users to locate each
+//! executable manually. Every launcher is optional: a launcher that is not installed
+//! simply contributes no games. The optional Discord Rich Presence shows which monitored
+//! application is running in HDR.
+
+pub mod discord;
+pub mod epic;
+pub mod gog;
+pub mod steam;
+
+use crate::utils::canonical_path;
+use std::path::{Path, PathBuf};
+
+/// Launcher a game was discovered through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameLibrary {
+    /// Valve Steam
+    Steam,
+    /// Epic Games Launcher
+    Epic,
+    /// GOG Galaxy
+    Gog,
+}
+
+impl GameLibrary {
+    /// Human-readable launcher name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Steam => "Steam",
+            Self::Epic => "Epic Games",
+            Self::Gog => "GOG",
+        }
+    }
+}
+
+impl std::fmt::Display for GameLibrary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Installed game found in a launcher library
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryGame {
+    /// Game title as reported by the launcher
+    pub name: String,
+    /// Main executable of the game
+    pub exe_path: PathBuf,
+    /// Launcher the game was found in
+    pub library: GameLibrary,
+}
+
+/// Enumerate installed games from all supported launchers
+///
+/// Failures of individual launchers are logged and skipped. Games are deduplicated by
+/// executable path (the same install can be registered with more than one launcher)
+/// and sorted alphabetically by name.
+pub fn enumerate_installed_games() -> Vec<LibraryGame> {
+    use tracing::{info, warn};
+
+    let mut games = Vec::new();
+
+    for (library, result) in [
+        (GameLibrary::Steam, steam::enumerate_games()),
+        (GameLibrary::Epic, epic::enumerate_games()),
+        (GameLibrary::Gog, gog::enumerate_games()),
+    ] {
+        match result {
+            Ok(found) => {
+                info!("Found {} installed {} game(s)", found.len(), library);
+                games.extend(found);
+            }
+            Err(e) => warn!("Failed to enumerate {} games: {}", library, e),
+        }
+    }
+
+    // Libraries reached through junctions can list the same install twice
+    let mut seen = std::collections::HashSet::new();
+    games.retain(|game| seen.insert(canonical_path::path_key(&game.exe_path)));
+    games.sort_by_key(|game| game.name.to_lowercase());
+    games
+}
+
+/// Maximum directory depth searched below an install directory for the main executable
+const MAX_EXE_SEARCH_DEPTH: usize = 3;
+
+/// Executable name fragments that identify installers, redistributables and helpers
+const EXCLUDED_EXE_FRAGMENTS: &[&str] = &[
+    "unins",
+    "setup",
+    "install",
+    "redist",
+    "crash",
+    "report",
+    "prereq",
+    "dxsetup",
+    "dotnet",
+    "easyanticheat",
+    "battleye",
+    "be_service",
+    "cefprocess",
+    "update",
+];
+
+/// Guess the main executable of a game from its install directory
+///
+/// Launchers such as Steam do not record which executable starts the game, so the
+/// install directory is searched (up to [`MAX_EXE_SEARCH_DEPTH`] levels) and each
+/// candidate is scored: names matching the game title score highest, Unreal Engine
+/// `-Shipping` binaries are preferred over their bootstrap stubs, shallower files beat
+/// deeper ones, and remaining ties go to the largest file.
+pub fn find_main_executable(install_dir: &Path, game_name: &str) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    collect_executables(install_dir, 0, &mut candidates);
+
+    let normalized_name = normalize(game_name);
+    let normalized_dir = install_dir
+        .file_name()
+        .map(|dir| normalize(&dir.to_string_lossy()))
+        .unwrap_or_default();
+
+    candidates
+        .into_iter()
+        .filter_map(|(path, depth, size)| {
+            let stem = path.file_stem()?.to_string_lossy().to_lowercase();
+            if EXCLUDED_EXE_FRAGMENTS
+                .iter()
+                .any(|fragment| stem.contains(fragment))
+            {
+                return None;
+            }
+
+            let normalized_stem = normalize(&stem.replace("-win64-shipping", ""));
+            let mut score: i64 = 0;
+            if !normalized_stem.is_empty() {
+                if normalized_stem == normalized_name || normalized_stem == normalized_dir {
+                    score += 100;
+                } else if normalized_name.contains(&normalized_stem)
+                    || normalized_stem.contains(&normalized_name)
+                    || normalized_dir.contains(&normalized_stem)
+                {
+                    score += 50;
+                }
+            }
+            if stem.ends_with("-shipping") {
+                score += 20;
+            }
+            #[expect(
+                clippy::cast_possible_wrap,
+                reason = "Depth is bounded by MAX_EXE_SEARCH_DEPTH"
+            )]
+            let depth_penalty = depth as i64 * 5;
+            score -= depth_penalty;
+
+            Some((score, size, path))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)))
+        .map(|(_, _, path)| path)
+}
+
+/// Recursively collect `.exe` files as `(path, depth, size)`
+fn collect_executables(dir: &Path, depth: usize, out: &mut Vec<(PathBuf, usize, u64)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            if depth < MAX_EXE_SEARCH_DEPTH {
+                collect_executables(&path, depth + 1, out);
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+        {
+            let size = entry.metadata().map_or(0, |m| m.len());
+            out.push((path, depth, size));
+        }
+    }
+}
+
+/// Lowercase a name and strip everything except ASCII letters and digits
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_exe(path: &Path, size: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; size]).unwrap();
+    }
+
+    #[test]
+    fn test_find_main_executable_prefers_name_match() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("Hollow Knight");
+        write_exe(&dir.join("hollow_knight.exe"), 10);
+        write_exe(&dir.join("UnityCrashHandler64.exe"), 100);
+        write_exe(&dir.join("tool.exe"), 50);
+
+        assert_eq!(
+            find_main_executable(&dir, "Hollow Knight"),
+            Some(dir.join("hollow_knight.exe"))
+        );
+    }
+
+    #[test]
+    fn test_find_main_executable_prefers_unreal_shipping_binary() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("Stray");
+        write_exe(&dir.join("Stray.exe"), 10);
+        write_exe(
+            &dir.join("Hk_project/Binaries/Win64/Stray-Win64-Shipping.exe"),
+            1000,
+        );
+
+        assert_eq!(
+            find_main_executable(&dir, "Stray"),
+            Some(dir.join("Hk_project/Binaries/Win64/Stray-Win64-Shipping.exe"))
+        );
+    }
+
+    #[test]
+    fn test_find_main_executable_skips_installers() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("Game");
+        write_exe(&dir.join("unins000.exe"), 100);
+        write_exe(&dir.join("_CommonRedist/vcredist_x64.exe"), 100);
+
+        assert_eq!(find_main_executable(&dir, "Game"), None);
+        assert_eq!(
+            find_main_executable(&temp.path().join("missing"), "Game"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("DOOM Eternal"), "doometernal");
+        assert_eq!(normalize("Baldur's Gate 3"), "baldursgate3");
+    }
+}
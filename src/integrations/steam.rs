@@ -0,0 +1,411 @@
+//! Steam library enumeration
+//!
+//! Steam records its library folders in `steamapps/libraryfolders.vdf` below the
+//! Steam installation, and every installed app in an `appmanifest_<appid>.acf` file
+//! inside the library's `steamapps` folder. Both use Valve's text `KeyValues` format
+//! (VDF). Manifests only name the install directory, so the main executable is
+//! located with [`super::find_main_executable`].
+
+use super::{GameLibrary, LibraryGame};
+use crate::error::Result;
+use crate::utils::canonical_path;
+use std::path::{Path, PathBuf};
+
+/// App IDs of Steam tools that are installed like games but never need HDR
+const IGNORED_APP_IDS: &[&str] = &[
+    "228980",  // Steamworks Common Redistributables
+    "1070560", // Steam Linux Runtime
+    "1391110", // Steam Linux Runtime - Soldier
+    "1628350", // Steam Linux Runtime - Sniper
+];
+
+/// Parsed value of a `KeyValues` (VDF) document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VdfValue {
+    /// Plain string value
+    String(String),
+    /// Nested block of key/value pairs, in document order
+    Object(Vec<(String, VdfValue)>),
+}
+
+impl VdfValue {
+    /// Look up a child value by key (case-insensitive, like Steam itself)
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        match self {
+            Self::Object(entries) => entries
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v),
+            Self::String(_) => None,
+        }
+    }
+
+    /// Get the string value, if this is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Object(_) => None,
+        }
+    }
+
+    /// Iterate over child key/value pairs (empty for strings)
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &VdfValue)> {
+        let entries: &[(String, VdfValue)] = match self {
+            Self::Object(entries) => entries,
+            Self::String(_) => &[],
+        };
+        entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+/// Parse a text `KeyValues` document into its root object
+///
+/// Supports quoted and unquoted tokens, `\\`, `\"`, `\n` and `\t` escapes, and `//`
+/// comments. Returns `None` for unbalanced braces or a key without a value.
+pub fn parse_vdf(input: &str) -> Option<VdfValue> {
+    let tokens = tokenize(input)?;
+    let root = parse_object(&mut tokens.into_iter(), false)?;
+    Some(VdfValue::Object(root))
+}
+
+/// Lexical token of a `KeyValues` document
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    /// Quoted or unquoted string
+    Text(String),
+    /// `{`
+    Open,
+    /// `}`
+    Close,
+}
+
+/// Split a `KeyValues` document into tokens, returning `None` for unterminated strings
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '/' if chars.peek() == Some(&'/') => {
+                // Comment until end of line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            'n' => text.push('\n'),
+                            't' => text.push('\t'),
+                            other => text.push(other),
+                        },
+                        other => text.push(other),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut text = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '{' | '}' | '"') {
+                        break;
+                    }
+                    text.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Text(text));
+            }
+        }
+    }
+
+    Some(tokens)
+}
+
+/// Parse key/value pairs until the closing brace (or end of input at the root level)
+fn parse_object(
+    tokens: &mut std::vec::IntoIter<Token>,
+    nested: bool,
+) -> Option<Vec<(String, VdfValue)>> {
+    let mut entries = Vec::new();
+
+    loop {
+        let key = match tokens.next() {
+            Some(Token::Text(key)) => key,
+            Some(Token::Close) if nested => return Some(entries),
+            None if !nested => return Some(entries),
+            _ => return None,
+        };
+
+        let value = match tokens.next()? {
+            Token::Text(value) => VdfValue::String(value),
+            Token::Open => VdfValue::Object(parse_object(tokens, true)?),
+            Token::Close => return None,
+        };
+
+        entries.push((key, value));
+    }
+}
+
+/// Library folder paths listed in `libraryfolders.vdf`
+///
+/// Handles both the current format (`"0" { "path" "..." }`) and the legacy format
+/// where the numbered keys map directly to paths.
+pub fn parse_library_folders(vdf: &str) -> Vec<PathBuf> {
+    let Some(root) = parse_vdf(vdf) else {
+        return Vec::new();
+    };
+    let Some(folders) = root.get("libraryfolders") else {
+        return Vec::new();
+    };
+
+    folders
+        .entries()
+        .filter(|(key, _)| key.chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|(_, value)| match value {
+            VdfValue::String(path) => Some(PathBuf::from(path)),
+            VdfValue::Object(_) => value.get("path")?.as_str().map(PathBuf::from),
+        })
+        .collect()
+}
+
+/// Installed app described by an `appmanifest_<appid>.acf` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppManifest {
+    /// Steam app ID
+    pub app_id: String,
+    /// App title
+    pub name: String,
+    /// Directory name below `steamapps/common`
+    pub install_dir: String,
+}
+
+/// Parse an `appmanifest_<appid>.acf` file
+pub fn parse_app_manifest(acf: &str) -> Option<AppManifest> {
+    let root = parse_vdf(acf)?;
+    let state = root.get("AppState")?;
+
+    Some(AppManifest {
+        app_id: state.get("appid")?.as_str()?.to_string(),
+        name: state.get("name")?.as_str()?.to_string(),
+        install_dir: state.get("installdir")?.as_str()?.to_string(),
+    })
+}
+
+/// Enumerate installed games in a single Steam library folder
+pub fn games_in_library(library: &Path) -> Result<Vec<LibraryGame>> {
+    use tracing::debug;
+
+    let steamapps = library.join("steamapps");
+    let mut games = Vec::new();
+
+    for entry in std::fs::read_dir(&steamapps)?.flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_lowercase();
+        if !(file_name.starts_with("appmanifest_")
+            && path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("acf")))
+        {
+            continue;
+        }
+
+        let Some(manifest) = std::fs::read_to_string(&path)
+            .ok()
+            .as_deref()
+            .and_then(parse_app_manifest)
+        else {
+            debug!("Skipping unreadable Steam manifest {:?}", entry.path());
+            continue;
+        };
+
+        if IGNORED_APP_IDS.contains(&manifest.app_id.as_str()) {
+            continue;
+        }
+
+        let install_dir = steamapps.join("common").join(&manifest.install_dir);
+        if let Some(exe_path) = super::find_main_executable(&install_dir, &manifest.name) {
+            games.push(LibraryGame {
+                name: manifest.name,
+                exe_path,
+                library: GameLibrary::Steam,
+            });
+        } else {
+            debug!(
+                "No executable found for Steam app {} ({})",
+                manifest.name, manifest.app_id
+            );
+        }
+    }
+
+    Ok(games)
+}
+
+/// Enumerate installed games across all Steam library folders
+///
+/// Returns an empty list if Steam is not installed.
+pub fn enumerate_games() -> Result<Vec<LibraryGame>> {
+    use tracing::{debug, warn};
+
+    let Some(steam_root) = steam_install_path() else {
+        debug!("Steam installation not found");
+        return Ok(Vec::new());
+    };
+
+    let mut libraries = std::fs::read_to_string(steam_root.join("steamapps/libraryfolders.vdf"))
+        .map(|vdf| parse_library_folders(&vdf))
+        .unwrap_or_default();
+    // The root is usually listed too, possibly through a junction or another spelling
+    if !libraries
+        .iter()
+        .any(|path| canonical_path::same_path(path, &steam_root))
+    {
+        libraries.insert(0, steam_root);
+    }
+
+    let mut games = Vec::new();
+    for library in libraries {
+        match games_in_library(&library) {
+            Ok(found) => games.extend(found),
+            Err(e) => warn!("Failed to read Steam library {:?}: {}", library, e),
+        }
+    }
+
+    Ok(games)
+}
+
+/// Locate the Steam installation from the registry
+#[cfg(windows)]
+fn steam_install_path() -> Option<PathBuf> {
+    use winreg::RegKey;
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"Software\Valve\Steam")
+        .and_then(|key| key.get_value::<String, _>("SteamPath"))
+        .or_else(|_| {
+            RegKey::predef(HKEY_LOCAL_MACHINE)
+                .open_subkey(r"SOFTWARE\WOW6432Node\Valve\Steam")
+                .and_then(|key| key.get_value::<String, _>("InstallPath"))
+        })
+        .ok()
+        .map(PathBuf::from)
+        .filter(|path| path.is_dir())
+}
+
+/// Steam is only supported on Windows
+#[cfg(not(windows))]
+fn steam_install_path() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const LIBRARY_FOLDERS: &str = r#"
+"libraryfolders"
+{
+    "0"
+    {
+        "path"      "C:\\Program Files (x86)\\Steam"
+        "label"     ""
+        "apps"
+        {
+            "228980"    "0"
+        }
+    }
+    "1"
+    {
+        "path"      "D:\\SteamLibrary"
+    }
+}
+"#;
+
+    const APP_MANIFEST: &str = r#"
+"AppState"
+{
+    "appid"     "1091500"
+    "Universe"  "1"
+    "name"      "Cyberpunk 2077"
+    "StateFlags"    "4"
+    "installdir"    "Cyberpunk 2077"
+    // Trailing comment
+}
+"#;
+
+    #[test]
+    fn test_parse_vdf_nested_and_escaped() {
+        let root = parse_vdf(r#""a" { "b" "x\"y" c { d "e" } }"#).unwrap();
+        let a = root.get("A").unwrap();
+        assert_eq!(a.get("b").unwrap().as_str(), Some("x\"y"));
+        assert_eq!(a.get("c").unwrap().get("d").unwrap().as_str(), Some("e"));
+    }
+
+    #[test]
+    fn test_parse_vdf_rejects_unbalanced_input() {
+        assert!(parse_vdf(r#""a" { "b" "c""#).is_none());
+        assert!(parse_vdf(r#""a" "b" }"#).is_none());
+        assert!(parse_vdf(r#""key""#).is_none());
+    }
+
+    #[test]
+    fn test_parse_library_folders() {
+        assert_eq!(
+            parse_library_folders(LIBRARY_FOLDERS),
+            vec![
+                PathBuf::from(r"C:\Program Files (x86)\Steam"),
+                PathBuf::from(r"D:\SteamLibrary"),
+            ]
+        );
+
+        // Legacy format maps numbered keys straight to paths
+        let legacy = r#""LibraryFolders" { "TimeNextStatsReport" "0" "1" "E:\\Games" }"#;
+        assert_eq!(
+            parse_library_folders(legacy),
+            vec![PathBuf::from(r"E:\Games")]
+        );
+    }
+
+    #[test]
+    fn test_parse_app_manifest() {
+        assert_eq!(
+            parse_app_manifest(APP_MANIFEST),
+            Some(AppManifest {
+                app_id: "1091500".to_string(),
+                name: "Cyberpunk 2077".to_string(),
+                install_dir: "Cyberpunk 2077".to_string(),
+            })
+        );
+        assert_eq!(parse_app_manifest(r#""AppState" { "appid" "1" }"#), None);
+    }
+
+    #[test]
+    fn test_games_in_library() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let steamapps = temp.path().join("steamapps");
+        let game_dir = steamapps.join("common/Cyberpunk 2077/bin/x64");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::write(game_dir.join("Cyberpunk2077.exe"), b"MZ").unwrap();
+        std::fs::write(steamapps.join("appmanifest_1091500.acf"), APP_MANIFEST).unwrap();
+        std::fs::write(
+            steamapps.join("appmanifest_228980.acf"),
+            r#""AppState" { "appid" "228980" "name" "Steamworks Common Redistributables" "installdir" "Steamworks Shared" }"#,
+        )
+        .unwrap();
+
+        let games = games_in_library(temp.path()).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Cyberpunk 2077");
+        assert_eq!(games[0].exe_path, game_dir.join("Cyberpunk2077.exe"));
+        assert_eq!(games[0].library, GameLibrary::Steam);
+    }
+}
@@ -2,12 +2,16 @@
 //!
 //! Automatically toggles HDR on Windows displays when configured applications start/stop.
 //! Uses multi-threaded event-driven architecture with process monitoring and HDR control.
+//!
+//! When embedding the controller, implement [`hdr::HdrControl`] (or use
+//! [`hdr::MockHdrControl`]) and create it with `AppController::with_hdr_control`.
 
 // Module declarations
 pub mod config;
 pub mod controller;
 pub mod error;
 pub mod hdr;
+pub mod integrations;
 pub mod monitor;
 pub mod utils;
 
@@ -5,38 +5,111 @@
 
 // Set Windows subsystem to hide console window
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-#![expect(
-    missing_docs,
-    reason = "Slint-generated code from include_modules! lacks doc comments"
+#![cfg_attr(
+    feature = "gui",
+    expect(
+        missing_docs,
+        reason = "Slint-generated code from include_modules! lacks doc comments"
+    )
 )]
-#![expect(
-    clippy::unwrap_used,
-    reason = "Slint-generated code from include_modules! uses .unwrap() extensively"
+#![cfg_attr(
+    feature = "gui",
+    expect(
+        clippy::unwrap_used,
+        reason = "Slint-generated code from include_modules! uses .unwrap() extensively"
+    )
 )]
 
 // GUI module is only in the binary, not the library
+#[cfg(feature = "gui")]
 mod gui;
+#[cfg(feature = "tui")]
+mod tui;
 
 use anyhow::{Context, Result};
 use easyhdr::{
     config::ConfigManager,
-    controller::{AppController, AppState},
+    controller::{AppController, AppState, agent},
     error::EasyHdrError,
-    hdr::HdrController,
+    hdr::{HdrController, compat::MIN_SUPPORTED_BUILD, crash_guard},
     monitor::{HdrStateEvent, HdrStateMonitor, ProcessEvent, ProcessMonitor},
     utils,
 };
+#[cfg(feature = "gui")]
 use gui::GuiController;
 use parking_lot::Mutex;
 use std::sync::{Arc, mpsc};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
 
 // Include Slint-generated code
+#[cfg(feature = "gui")]
 slint::include_modules!();
 
-/// Minimum supported Windows build number (Windows 10 21H2)
-const MIN_WINDOWS_BUILD: u32 = 19044;
+/// User interface that drives the application controller
+enum Frontend {
+    /// Slint window and tray icon
+    #[cfg(feature = "gui")]
+    Gui(GuiController),
+    /// No user interface, started with `--agent` to keep monitoring in the background, and
+    /// always without the `gui` feature
+    Agent(Arc<Mutex<AppController>>, mpsc::Receiver<AppState>),
+}
+
+/// How long a starting instance waits for the one it takes over from to exit
+const HANDOFF_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a front-end window waits for a newly started background agent to answer
+///
+/// Longer than [`HANDOFF_TIMEOUT`], which the agent waits for the single-instance lock.
+#[cfg(feature = "gui")]
+const AGENT_START_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Command-line flags
+#[derive(Debug, Clone, Copy, Default)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Independent command-line flags"
+)]
+struct LaunchOptions {
+    /// `--tui`: use the terminal UI instead of the window and tray icon
+    #[cfg(feature = "tui")]
+    tui: bool,
+    /// `--dry-run`: simulate HDR toggles for this run, like the `dry_run` preference
+    dry_run: bool,
+    /// `--register-event-source`: register the Event Log source and exit (needs admin)
+    register_event_source: bool,
+    /// `--trace-hdr-api`: trace raw Display Configuration API calls for this run, like
+    /// the `trace_hdr_api` preference
+    trace_hdr_api: bool,
+    /// `--agent`: run without a window as the background agent, see
+    /// `easyhdr::controller::agent`
+    agent: bool,
+    /// `--restart`: started by an exiting instance, see `easyhdr::utils::self_update`
+    restart: bool,
+}
+
+impl LaunchOptions {
+    /// Parses the flags `EasyHDR` was started with, ignoring unknown ones
+    fn from_args() -> Self {
+        let mut options = Self::default();
+        for arg in std::env::args().skip(1) {
+            match arg.as_str() {
+                #[cfg(feature = "tui")]
+                "--tui" => options.tui = true,
+                "--dry-run" => options.dry_run = true,
+                "--register-event-source" => options.register_event_source = true,
+                "--trace-hdr-api" => options.trace_hdr_api = true,
+                agent::AGENT_FLAG => options.agent = true,
+                utils::self_update::RESTART_FLAG => options.restart = true,
+                // Read before logging starts, see `utils::data_dir::init`
+                "--portable" => {}
+                _ => warn!("Ignoring unknown command-line argument: {}", arg),
+            }
+        }
+        options
+    }
+}
 
 /// Main entry point for the application
 ///
@@ -51,25 +124,93 @@ fn main() -> Result<()> {
     let profiler = startup_profiler::get_profiler();
     profiler.record_phase(StartupPhase::AppStart);
 
+    // Logs go to the data directory, so portable mode is decided first
+    let data_dir_override =
+        utils::data_dir::init(std::env::args().skip(1).any(|arg| arg == "--portable"));
+
     utils::init_logging().context("Failed to initialize logging system")?;
     profiler.record_phase(StartupPhase::LoggingInit);
 
     info!("EasyHDR v{} starting...", env!("CARGO_PKG_VERSION"));
+    if let Some(dir) = data_dir_override {
+        info!(
+            "Using data directory {:?}{}",
+            dir,
+            if utils::data_dir::is_portable() {
+                " (portable mode)"
+            } else {
+                ""
+            }
+        );
+    }
+
+    // Elevated helper started by an update that can't replace the executable itself
+    if let Some(staged) = utils::self_update::helper_args(std::env::args().skip(1)) {
+        return utils::self_update::run_helper(&staged)
+            .context("Failed to replace the EasyHDR executable");
+    }
+
+    let options = LaunchOptions::from_args();
+
+    // One-off setup step, usually run from an elevated prompt while EasyHDR is running
+    if options.register_event_source {
+        utils::event_log::register_source()
+            .context("Failed to register the Event Log source (run as administrator)")?;
+        return Ok(());
+    }
+
+    // The terminal UI is a client of the background agent, which keeps monitoring after it
+    // exits, so it neither takes over nor needs the single-instance lock
+    #[cfg(feature = "tui")]
+    if options.tui {
+        return tui::run().context("Terminal UI terminated with error");
+    }
+
+    let config = ConfigManager::load().context("Failed to load application configuration")?;
+    profiler.record_phase(StartupPhase::ConfigLoad);
+    info!(
+        "Configuration loaded with {} monitored apps",
+        config.monitored_apps.len()
+    );
+
+    // RUST_LOG takes precedence at startup; the tray menu can still change the level later
+    if std::env::var_os("RUST_LOG").is_none() {
+        utils::set_log_level(config.preferences.log_level);
+    }
+    utils::set_log_format(config.preferences.log_format);
+    utils::i18n::set_language(&config.preferences.language);
+    utils::event_log::set_enabled(config.preferences.write_event_log);
+    utils::metrics::get_metrics().set_enabled(config.preferences.metrics_enabled);
+    // Enabled before the HDR controller is created so display enumeration is traced too
+    easyhdr::hdr::api_trace::set_enabled(options.trace_hdr_api || config.preferences.trace_hdr_api);
+
+    // With `run_in_background`, the window is a front-end of the background agent, so
+    // closing it or the window crashing leaves monitoring running. First-run setup runs in
+    // a window of its own.
+    #[cfg(feature = "gui")]
+    if !options.agent
+        && !options.restart
+        && config.preferences.run_in_background
+        && config.preferences.onboarding_completed
+    {
+        return run_front_end(config);
+    }
+
+    // Otherwise the window takes over from a background agent instead of running beside
+    // it, and an agent or restart started by a closing window waits for that window to exit
+    let handing_off = options.agent || options.restart || agent::stop_running_agent();
 
     // Enforce single instance - only one instance of EasyHDR should run at a time
     // This must be done early, before any other initialization
-    let _single_instance_guard = match utils::SingleInstanceGuard::new() {
+    let _single_instance_guard = match acquire_single_instance(handing_off) {
         Ok(guard) => guard,
         Err(e) => {
             error!("Single instance check failed: {}", e);
 
             #[cfg(windows)]
-            {
-                show_error_and_exit(
-                    "Another instance of EasyHDR is already running.\n\n\
-                     Please close the existing instance before starting a new one.\n\n\
-                     Check the system tray for the EasyHDR icon.",
-                );
+            if !options.agent {
+                // The configuration isn't loaded yet, so this follows the Windows locale
+                show_error_and_exit(&utils::tr("already-running"));
             }
 
             return Err(e.into());
@@ -78,14 +219,27 @@ fn main() -> Result<()> {
 
     info!("Single instance check passed");
 
-    if let Err(e) =
-        verify_windows_version().context("Failed to verify Windows version compatibility")
+    // Install an update downloaded by the previous run before anything else starts
+    let current_version = semver::Version::parse(env!("CARGO_PKG_VERSION"))
+        .context("Failed to parse the EasyHDR version")?;
+    match utils::self_update::install_staged(&current_version) {
+        Ok(Some(version)) => {
+            info!("Installed EasyHDR {}, restarting", version);
+            utils::self_update::restart().context("Failed to start the updated EasyHDR")?;
+            return Ok(());
+        }
+        Ok(None) => utils::self_update::remove_backup(),
+        Err(e) => warn!("Failed to install the downloaded update: {}", e),
+    }
+
+    // Checked after loading the configuration so its override can let older builds run
+    if let Err(e) = verify_windows_version(config.preferences.allow_unsupported_windows)
+        .context("Failed to verify Windows version compatibility")
     {
         error!("Windows version check failed: {}", e);
-        show_error_and_exit(&format!(
-            "EasyHDR requires Windows 10 21H2 (build {MIN_WINDOWS_BUILD}) or later.\n\n\
-             Your Windows version is not supported.\n\n\
-             Please update Windows to continue."
+        show_error_and_exit(&utils::tr_args(
+            "unsupported-windows",
+            &[("build", &MIN_SUPPORTED_BUILD.to_string())],
         ));
         return Err(e);
     }
@@ -93,12 +247,11 @@ fn main() -> Result<()> {
 
     info!("Windows version check passed");
 
-    let config = ConfigManager::load().context("Failed to load application configuration")?;
-    profiler.record_phase(StartupPhase::ConfigLoad);
-    info!(
-        "Configuration loaded with {} monitored apps",
-        config.monitored_apps.len()
-    );
+    // Turn HDR back off if the previous run died while HDR was on, then guard this run
+    if crash_guard::repair_after_crash() {
+        warn!("Turned off HDR left on by a previous run");
+    }
+    crash_guard::install();
 
     #[cfg_attr(
         not(windows),
@@ -107,8 +260,10 @@ fn main() -> Result<()> {
             reason = "Components only used on Windows for HDR control and GUI"
         )
     )]
-    let (process_monitor, gui_controller, should_show_hdr_warning) =
-        match initialize_components(&config).context("Failed to initialize core components") {
+    let (process_monitor, frontend, should_show_hdr_warning) =
+        match initialize_components(&config, options)
+            .context("Failed to initialize core components")
+        {
             Ok(components) => components,
             Err(e) => {
                 error!("Failed to initialize components: {:#}", e);
@@ -134,9 +289,9 @@ fn main() -> Result<()> {
                             format!("{e:#}")
                         };
 
-                    show_error_and_exit(&format!(
-                        "Failed to initialize EasyHDR:\n\n{error_message}\n\n\
-                         Please ensure your display drivers are up to date."
+                    show_error_and_exit(&utils::tr_args(
+                        "init-failed",
+                        &[("error", &error_message)],
                     ));
                     return Err(e);
                 }
@@ -167,10 +322,18 @@ fn main() -> Result<()> {
         info!("No HDR-capable displays detected at startup - notification will be shown via tray");
     }
 
-    info!("Starting GUI event loop");
-    gui_controller
-        .run()
-        .context("GUI event loop terminated with error")?;
+    match frontend {
+        #[cfg(feature = "gui")]
+        Frontend::Gui(gui_controller) => {
+            info!("Starting GUI event loop");
+            gui_controller
+                .run()
+                .context("GUI event loop terminated with error")?;
+        }
+        Frontend::Agent(app_controller, app_state_rx) => {
+            run_agent(&app_controller, app_state_rx)?;
+        }
+    }
 
     #[cfg(windows)]
     {
@@ -184,29 +347,178 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Takes the single-instance lock, retrying for up to [`HANDOFF_TIMEOUT`] while another
+/// instance hands off to this one
+fn acquire_single_instance(wait: bool) -> easyhdr::error::Result<utils::SingleInstanceGuard> {
+    let deadline = Instant::now() + HANDOFF_TIMEOUT;
+    loop {
+        match utils::SingleInstanceGuard::new() {
+            Err(_) if wait && Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Runs without a user interface until asked to stop
+///
+/// Answers the front-end window, the terminal UI and status requests on the control
+/// channel from the controller. Stopping so a window can take over saves the session
+/// history without applying the shutdown policy; a front-end window closed after running
+/// in the background was turned off applies it.
+fn run_agent(
+    app_controller: &Arc<Mutex<AppController>>,
+    app_state_rx: mpsc::Receiver<AppState>,
+) -> Result<()> {
+    let server =
+        agent::AgentServer::bind().context("Failed to open the background agent channel")?;
+
+    // Queued for a front-end window; the controller blocks on a full channel otherwise
+    let updates = Arc::new(agent::StateUpdates::default());
+    std::thread::Builder::new()
+        .name("agent-state".to_string())
+        .spawn({
+            let updates = Arc::clone(&updates);
+            move || {
+                for state in app_state_rx {
+                    updates.push(state);
+                }
+            }
+        })
+        .context("Failed to start the background agent state thread")?;
+
+    info!("Running in the background");
+    let stopped_by = server
+        .serve(|command| agent::execute(app_controller, &updates, command))
+        .context("Background agent channel failed")?;
+
+    if stopped_by == agent::AgentCommand::Shutdown {
+        app_controller.lock().shutdown();
+    } else {
+        app_controller.lock().hand_off();
+    }
+    Ok(())
+}
+
+/// Runs the window as a front-end of the background agent, starting an agent if none
+/// runs
+///
+/// The window takes no single-instance lock and starts no monitoring. Its controller
+/// edits the configuration and reads the displays, and forwards HDR actions to the agent,
+/// whose state the window shows; see `easyhdr::controller::agent`.
+#[cfg(feature = "gui")]
+fn run_front_end(config: easyhdr::config::AppConfig) -> Result<()> {
+    use easyhdr::monitor::SharedWatchState;
+
+    if let Err(e) = verify_windows_version(config.preferences.allow_unsupported_windows) {
+        error!("Windows version check failed: {}", e);
+        show_error_and_exit(&utils::tr_args(
+            "unsupported-windows",
+            &[("build", &MIN_SUPPORTED_BUILD.to_string())],
+        ));
+        return Err(e);
+    }
+
+    if let Err(e) = agent::ensure_running(AGENT_START_TIMEOUT) {
+        error!("Background agent unavailable: {}", e);
+        show_error_and_exit(&utils::tr_args(
+            "init-failed",
+            &[(
+                "error",
+                &format!("The background agent did not start ({e})"),
+            )],
+        ));
+        return Err(e).context("Failed to start the background agent");
+    }
+    let link = agent::AgentLink::at(agent::endpoint_path());
+
+    // The front-end runs no event loop, so nothing reads these
+    let (_process_event_tx, process_event_rx) = mpsc::sync_channel::<ProcessEvent>(1);
+    let (_hdr_state_tx, hdr_state_rx) = mpsc::sync_channel::<HdrStateEvent>(1);
+    let (app_state_tx, app_state_rx) = mpsc::sync_channel::<AppState>(32);
+
+    info!("Creating application controller as a front-end of the background agent");
+    let app_controller = AppController::new(
+        config,
+        process_event_rx,
+        hdr_state_rx,
+        app_state_tx.clone(),
+        Arc::new(SharedWatchState::default()),
+    )
+    .context("Failed to create application controller")?
+    .front_end_of(link.clone());
+    let app_controller = Arc::new(Mutex::new(app_controller));
+
+    agent::forward_state_updates(
+        link,
+        {
+            let app_controller = Arc::clone(&app_controller);
+            move || app_controller.lock().display_hdr_states()
+        },
+        app_state_tx,
+    )
+    .context("Failed to start the background agent link thread")?;
+
+    let gui_controller = GuiController::new(app_controller, app_state_rx)
+        .context("Failed to create GUI controller")?;
+    info!("Starting GUI event loop");
+    gui_controller
+        .run()
+        .context("GUI event loop terminated with error")
+}
+
 /// Verifies that the Windows version is compatible (Windows 10 21H2+ / build 19044+).
-fn verify_windows_version() -> Result<()> {
+///
+/// Insider and Server builds outside the tested range only log a warning; the HDR
+/// controller probes their capabilities. Older builds are refused unless
+/// `allow_unsupported` is set.
+#[cfg_attr(
+    not(windows),
+    expect(
+        unused_variables,
+        reason = "The override only applies to the Windows build check"
+    )
+)]
+fn verify_windows_version(allow_unsupported: bool) -> Result<()> {
     #[cfg(windows)]
     {
-        use easyhdr::hdr::WindowsVersion;
+        use easyhdr::hdr::{BuildSupport, WindowsVersion};
 
         let version = WindowsVersion::detect().context("Failed to detect Windows version")?;
 
         let build_number =
             get_windows_build_number().context("Failed to retrieve Windows build number")?;
+        let server = WindowsVersion::is_server();
 
         info!(
-            "Detected Windows version: {:?}, build: {}",
-            version, build_number
+            "Detected Windows version: {:?}, build: {}, server: {}",
+            version, build_number, server
         );
 
-        if build_number < MIN_WINDOWS_BUILD {
-            return Err(
-                EasyHdrError::ConfigError(easyhdr::error::StringError::new(format!(
-                    "Windows build {build_number} is too old. Minimum required: {MIN_WINDOWS_BUILD}"
-                )))
-                .into(),
-            );
+        match BuildSupport::classify(build_number, server) {
+            BuildSupport::Supported => {}
+            BuildSupport::Untested => {
+                warn!(
+                    "Windows build {} ({}) has not been tested with EasyHDR; HDR capabilities will be probed",
+                    build_number,
+                    if server { "Server" } else { "Insider" }
+                );
+            }
+            BuildSupport::TooOld if allow_unsupported => {
+                warn!(
+                    "Windows build {} is older than the minimum {}; continuing because allow_unsupported_windows is set",
+                    build_number, MIN_SUPPORTED_BUILD
+                );
+            }
+            BuildSupport::TooOld => {
+                return Err(
+                    EasyHdrError::ConfigError(easyhdr::error::StringError::new(format!(
+                        "Windows build {build_number} is too old. Minimum required: {MIN_SUPPORTED_BUILD}"
+                    )))
+                    .into(),
+                );
+            }
         }
 
         Ok(())
@@ -289,6 +601,7 @@ fn get_windows_build_number() -> Result<u32> {
 /// detected displays, HDR capabilities, and current HDR state.
 #[cfg(windows)]
 fn log_hdr_startup_summary(hdr_controller: &HdrController) {
+    use easyhdr::hdr::link_bandwidth;
     use easyhdr::hdr::version::WindowsVersion;
 
     info!("=== HDR Startup Summary ===");
@@ -298,6 +611,10 @@ fn log_hdr_startup_summary(hdr_controller: &HdrController) {
 
     info!("Windows Version: {:?}", windows_version);
     info!("Windows Build Number: {}", build_number);
+    info!(
+        "Windows Capabilities: {:?}",
+        easyhdr::hdr::Capabilities::for_build(build_number)
+    );
 
     let displays = hdr_controller.get_display_cache();
     info!("Total Displays Detected: {}", displays.len());
@@ -309,13 +626,37 @@ fn log_hdr_startup_summary(hdr_controller: &HdrController) {
     }
 
     for (index, disp) in displays.iter().enumerate() {
-        info!("--- Display {} ---", index);
+        info!("--- Display {}: {} ---", index, disp.display_name());
+        info!("  Connector: {}", disp.connector);
+        if let (Some(manufacturer), Some(product)) =
+            (&disp.edid_manufacturer, disp.edid_product_code)
+        {
+            info!("  EDID: {} {:#06x}", manufacturer, product);
+        }
         info!(
             "  Adapter ID: LowPart={:#010x}, HighPart={:#010x}",
             disp.adapter_id.LowPart, disp.adapter_id.HighPart
         );
         info!("  Target ID: {}", disp.target_id);
         info!("  HDR Supported: {}", disp.supports_hdr);
+        if let Some(edid_hdr) = &disp.edid_hdr {
+            info!("  EDID HDR: {}", edid_hdr.summary());
+        }
+        if let Some(hint) = disp.hdr_discrepancy() {
+            warn!("  {}", hint);
+        }
+        if let Ok(signal) = hdr_controller.link_signal(disp) {
+            info!("  Signal: {}", signal);
+            if disp.supports_hdr
+                && let Some(advisory) = link_bandwidth::hdr_advisory(
+                    disp.connector,
+                    signal,
+                    hdr_controller.is_hdr_enabled(disp).unwrap_or(false),
+                )
+            {
+                warn!("  {}", advisory);
+            }
+        }
 
         if disp.supports_hdr {
             match hdr_controller.is_hdr_enabled(disp) {
@@ -345,11 +686,13 @@ fn log_hdr_startup_summary(hdr_controller: &HdrController) {
 fn log_hdr_startup_summary(_hdr_controller: &HdrController) {}
 
 /// Initializes all core components including HDR controller, process monitor,
-/// HDR state monitor, app controller, and GUI. Returns a tuple of
-/// (`ProcessMonitor`, `GuiController`, `should_show_hdr_warning`).
+/// HDR state monitor, app controller, and the GUI or, with `--tui`, the terminal UI
+/// (none with `--agent`).
+/// Returns a tuple of (`ProcessMonitor`, `Frontend`, `should_show_hdr_warning`).
 fn initialize_components(
     config: &easyhdr::config::AppConfig,
-) -> Result<(ProcessMonitor, GuiController, bool)> {
+    options: LaunchOptions,
+) -> Result<(ProcessMonitor, Frontend, bool)> {
     use easyhdr::utils::startup_profiler::{self, StartupPhase};
     let profiler = startup_profiler::get_profiler();
 
@@ -384,8 +727,16 @@ fn initialize_components(
         "Creating process monitor with interval: {:?}",
         monitoring_interval
     );
-    let process_monitor = ProcessMonitor::new(monitoring_interval, process_event_tx);
+    let process_monitor = ProcessMonitor::new(monitoring_interval, process_event_tx.clone());
     let watch_state_ref = process_monitor.get_watch_state_ref();
+
+    #[cfg(windows)]
+    if let Err(e) = easyhdr::uwp::watch_running_apps(&watch_state_ref, &process_event_tx) {
+        info!(
+            "UWP app watcher unavailable, detecting UWP apps from processes: {}",
+            e
+        );
+    }
     profiler.record_phase(StartupPhase::ProcessMonitorInit);
 
     info!("Creating HDR state monitor");
@@ -397,7 +748,7 @@ fn initialize_components(
     profiler.record_phase(StartupPhase::HdrMonitorInit);
 
     info!("Creating application controller");
-    let app_controller = AppController::new(
+    let mut app_controller = AppController::new(
         config.clone(),
         process_event_rx,
         hdr_state_rx,
@@ -405,13 +756,25 @@ fn initialize_components(
         watch_state_ref,
     )
     .context("Failed to create application controller")?;
+    if options.dry_run {
+        app_controller.set_dry_run(true);
+    }
     profiler.record_phase(StartupPhase::AppControllerInit);
 
+    // Drop icons a crash or disk error left unreadable, so they are extracted again, and
+    // icons of removed applications, and keep the cache below its size limit
+    utils::IconCache::maintain_in_background(
+        Arc::clone(&app_controller.config),
+        u64::from(config.preferences.icon_cache_max_mb) * 1024 * 1024,
+    );
+
     let app_controller_handle = Arc::new(Mutex::new(app_controller));
 
-    info!("Creating GUI controller");
-    let gui_controller = GuiController::new(Arc::clone(&app_controller_handle), app_state_rx)
-        .context("Failed to create GUI controller")?;
+    let frontend = if options.agent {
+        Frontend::Agent(Arc::clone(&app_controller_handle), app_state_rx)
+    } else {
+        create_gui(&app_controller_handle, app_state_rx)?
+    };
     profiler.record_phase(StartupPhase::GuiControllerInit);
 
     info!("Starting application controller thread");
@@ -426,7 +789,34 @@ fn initialize_components(
     info!("Starting HDR state monitor thread");
     let _hdr_monitor_handle = hdr_state_monitor.start();
 
-    Ok((process_monitor, gui_controller, should_show_hdr_warning))
+    Ok((process_monitor, frontend, should_show_hdr_warning))
+}
+
+/// Creates the GUI controller with its main window and tray icon.
+#[cfg(feature = "gui")]
+fn create_gui(
+    app_controller: &Arc<Mutex<AppController>>,
+    app_state_rx: mpsc::Receiver<AppState>,
+) -> Result<Frontend> {
+    info!("Creating GUI controller");
+    let gui_controller = GuiController::new(Arc::clone(app_controller), app_state_rx)
+        .context("Failed to create GUI controller")?;
+    Ok(Frontend::Gui(gui_controller))
+}
+
+/// Without the `gui` feature there is no window, so the binary runs as the background
+/// agent.
+#[cfg(not(feature = "gui"))]
+#[expect(
+    clippy::unnecessary_wraps,
+    reason = "Matches the GUI build, where creating the window can fail"
+)]
+fn create_gui(
+    app_controller: &Arc<Mutex<AppController>>,
+    app_state_rx: mpsc::Receiver<AppState>,
+) -> Result<Frontend> {
+    info!("Built without the GUI, running as the background agent");
+    Ok(Frontend::Agent(Arc::clone(app_controller), app_state_rx))
 }
 
 /// Shows an error dialog and exits the application.
@@ -435,7 +825,7 @@ fn show_error_and_exit(message: &str) {
     use rfd::MessageDialog;
 
     MessageDialog::new()
-        .set_title("EasyHDR - Error")
+        .set_title(utils::tr("error-title"))
         .set_description(message)
         .set_buttons(rfd::MessageButtons::Ok)
         .set_level(rfd::MessageLevel::Error)
@@ -0,0 +1,293 @@
+//! Processes `EasyHDR` cannot inspect
+//!
+//! A game started as administrator, or shielded by an anti-cheat driver, often cannot be
+//! opened by a non-elevated `EasyHDR`, so its image path stays unknown and watched folders
+//! cannot match it. Matching by executable name keeps working, since the process snapshot
+//! reports every name. For such processes the monitor first asks WMI for the path in one
+//! batched query per scan, which succeeds for some processes `OpenProcess` is denied.
+//! When that fails too, it looks for the executable in the watched folders to tell the
+//! user which watched application it probably belongs to.
+
+use crate::config::MonitoredApp;
+use crate::utils::unicode::fold_case;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Directory levels below a watched folder searched for an executable
+pub const MAX_SEARCH_DEPTH: usize = 3;
+
+/// Directory entries visited per folder, bounding the work on large library folders
+pub const MAX_SEARCH_ENTRIES: usize = 10_000;
+
+/// Executable names in watched folders, read once per folder
+///
+/// Processes that cannot be opened are looked up here on every scan, so each watched
+/// folder is only searched the first time it is asked about. The index belongs to one
+/// watch list and is dropped when the watch list changes.
+#[derive(Debug, Default)]
+pub struct FolderIndex {
+    /// Watch list the index was built for
+    watch_list: Option<Arc<Vec<MonitoredApp>>>,
+    /// Case-folded executable file names by watched folder
+    executables: HashMap<PathBuf, HashSet<String>>,
+}
+
+impl FolderIndex {
+    /// Drop the index unless it was built for `watch_list`
+    pub fn invalidate_on_change(&mut self, watch_list: &Arc<Vec<MonitoredApp>>) {
+        if !self
+            .watch_list
+            .as_ref()
+            .is_some_and(|current| Arc::ptr_eq(current, watch_list))
+        {
+            self.executables.clear();
+            self.watch_list = Some(Arc::clone(watch_list));
+        }
+    }
+
+    /// Whether an executable with the given file name lies in a folder or its subfolders
+    ///
+    /// Searches the folder with [`index_executables`] the first time it is asked about.
+    /// File names compare case-insensitively.
+    pub fn contains(&mut self, folder: &Path, exe_file: &str) -> bool {
+        self.executables
+            .entry(folder.to_path_buf())
+            .or_insert_with(|| index_executables(folder))
+            .contains(&fold_case(exe_file))
+    }
+}
+
+/// Case-folded file names of the executables in a folder and its subfolders
+///
+/// Searches at most [`MAX_SEARCH_DEPTH`] levels deep and stops after
+/// [`MAX_SEARCH_ENTRIES`] entries, so the index of a very large folder is incomplete.
+pub fn index_executables(folder: &Path) -> HashSet<String> {
+    let mut executables = HashSet::new();
+    let mut pending = vec![(folder.to_path_buf(), 0)];
+    let mut visited = 0;
+
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            visited += 1;
+            if visited > MAX_SEARCH_ENTRIES {
+                return executables;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth < MAX_SEARCH_DEPTH {
+                    pending.push((entry.path(), depth + 1));
+                }
+            } else if let Some(name) = entry.file_name().to_str()
+                && Path::new(name)
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("exe"))
+            {
+                executables.insert(fold_case(name));
+            }
+        }
+    }
+    executables
+}
+
+/// WQL query for the image paths of the given processes
+#[cfg_attr(
+    all(not(windows), not(test)),
+    expect(dead_code, reason = "Function used only on Windows for WMI queries")
+)]
+fn image_path_query(pids: &[u32]) -> String {
+    let conditions: Vec<String> = pids
+        .iter()
+        .map(|pid| format!("ProcessId = {pid}"))
+        .collect();
+    format!(
+        "SELECT ProcessId, ExecutablePath FROM Win32_Process WHERE {}",
+        conditions.join(" OR ")
+    )
+}
+
+/// Ask WMI for the image paths of processes that could not be opened
+///
+/// Returns the paths WMI knows; processes it cannot report either are missing from the
+/// map. Failures are logged and yield an empty map.
+#[cfg(windows)]
+pub fn query_image_paths(pids: &[u32]) -> HashMap<u32, PathBuf> {
+    use tracing::debug;
+
+    if pids.is_empty() {
+        return HashMap::new();
+    }
+    match query_image_paths_wmi(pids) {
+        Ok(paths) => {
+            debug!(
+                "WMI reported {} of {} inaccessible process paths",
+                paths.len(),
+                pids.len()
+            );
+            paths
+        }
+        Err(e) => {
+            debug!("WMI process path query failed: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Run the WMI query for [`query_image_paths`]
+///
+/// # Safety
+///
+/// COM is initialized for the calling thread and uninitialized again by
+/// [`ComGuard`](crate::utils::com::ComGuard) when it was initialized here. All interfaces
+/// and `VARIANT`s are wrappers released on drop.
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows COM FFI for WMI process queries")]
+fn query_image_paths_wmi(pids: &[u32]) -> windows::core::Result<HashMap<u32, PathBuf>> {
+    use crate::utils::com::ComGuard;
+    use windows::Win32::System::Com::{
+        CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, CoCreateInstance, CoSetProxyBlanket, EOAC_NONE,
+        RPC_C_AUTHN_LEVEL_CALL, RPC_C_IMP_LEVEL_IMPERSONATE,
+    };
+    use windows::Win32::System::Rpc::{RPC_C_AUTHN_WINNT, RPC_C_AUTHZ_NONE};
+    use windows::Win32::System::Variant::VARIANT;
+    use windows::Win32::System::Wmi::{
+        IWbemClassObject, IWbemLocator, WBEM_FLAG_FORWARD_ONLY, WBEM_FLAG_RETURN_IMMEDIATELY,
+        WBEM_INFINITE, WbemLocator,
+    };
+    use windows::core::{BSTR, PCWSTR, w};
+
+    let _com = ComGuard::init(COINIT_MULTITHREADED);
+
+    let mut paths = HashMap::with_capacity(pids.len());
+    unsafe {
+        let locator: IWbemLocator = CoCreateInstance(&WbemLocator, None, CLSCTX_INPROC_SERVER)?;
+        let services = locator.ConnectServer(
+            &BSTR::from("ROOT\\CIMV2"),
+            &BSTR::new(),
+            &BSTR::new(),
+            &BSTR::new(),
+            0,
+            &BSTR::new(),
+            None,
+        )?;
+        CoSetProxyBlanket(
+            &services,
+            RPC_C_AUTHN_WINNT,
+            RPC_C_AUTHZ_NONE,
+            PCWSTR::null(),
+            RPC_C_AUTHN_LEVEL_CALL,
+            RPC_C_IMP_LEVEL_IMPERSONATE,
+            None,
+            EOAC_NONE,
+        )?;
+
+        let results = services.ExecQuery(
+            &BSTR::from("WQL"),
+            &BSTR::from(image_path_query(pids)),
+            WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+            None,
+        )?;
+
+        loop {
+            let mut objects: [Option<IWbemClassObject>; 1] = [None];
+            let mut returned = 0u32;
+            results
+                .Next(WBEM_INFINITE, &mut objects, &raw mut returned)
+                .ok()?;
+            let Some(object) = objects[0].take().filter(|_| returned > 0) else {
+                break;
+            };
+
+            let mut pid_value = VARIANT::default();
+            let mut path_value = VARIANT::default();
+            let read = object
+                .Get(w!("ProcessId"), 0, &raw mut pid_value, None, None)
+                .and_then(|()| {
+                    object.Get(w!("ExecutablePath"), 0, &raw mut path_value, None, None)
+                });
+            if read.is_ok()
+                && let Ok(pid) = u32::try_from(&pid_value)
+                && let Ok(path) = BSTR::try_from(&path_value)
+                && !path.is_empty()
+            {
+                paths.insert(pid, PathBuf::from(path.to_string()));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// WMI is only available on Windows
+#[cfg(not(windows))]
+pub fn query_image_paths(_pids: &[u32]) -> HashMap<u32, PathBuf> {
+    HashMap::new()
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_dir;
+
+    #[test]
+    fn test_folder_index_finds_executables_in_subfolders() {
+        let library = create_test_dir();
+        let game_dir = library.path().join("Game").join("Binaries").join("Win64");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::write(game_dir.join("Game-Win64-Shipping.exe"), b"").unwrap();
+        std::fs::write(game_dir.join("readme.txt"), b"").unwrap();
+
+        let mut index = FolderIndex::default();
+        index.invalidate_on_change(&Arc::new(Vec::new()));
+        assert!(index.contains(library.path(), "game-win64-shipping.EXE"));
+        assert!(!index.contains(library.path(), "other.exe"));
+        assert_eq!(
+            index_executables(library.path()),
+            HashSet::from(["game-win64-shipping.exe".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_folder_index_is_kept_until_watch_list_changes() {
+        let library = create_test_dir();
+        let watch_list = Arc::new(Vec::new());
+        let mut index = FolderIndex::default();
+        index.invalidate_on_change(&watch_list);
+        assert!(!index.contains(library.path(), "game.exe"));
+
+        // The folder is not searched again for the same watch list
+        std::fs::write(library.path().join("game.exe"), b"").unwrap();
+        index.invalidate_on_change(&watch_list);
+        assert!(!index.contains(library.path(), "game.exe"));
+
+        index.invalidate_on_change(&Arc::new(Vec::new()));
+        assert!(index.contains(library.path(), "game.exe"));
+    }
+
+    #[test]
+    fn test_folder_search_depth_is_bounded() {
+        let library = create_test_dir();
+        let mut dir = library.path().to_path_buf();
+        for level in 0..=MAX_SEARCH_DEPTH {
+            dir.push(format!("level{level}"));
+        }
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("deep.exe"), b"").unwrap();
+
+        assert!(index_executables(library.path()).is_empty());
+        assert!(index_executables(&library.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_image_path_query() {
+        assert_eq!(
+            image_path_query(&[4, 1234]),
+            "SELECT ProcessId, ExecutablePath FROM Win32_Process WHERE ProcessId = 4 OR ProcessId = 1234"
+        );
+    }
+}
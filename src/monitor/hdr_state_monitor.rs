@@ -4,9 +4,38 @@
 //! Uses periodic rechecks (500ms × 10 = 5s max) to handle race condition where Windows messages
 //! arrive before `DisplayConfig` APIs reflect state change. Hidden window (not message-only) required
 //! to receive broadcast messages. Performance: <0.1% CPU average.
+//!
+//! The same window registers for console display power notifications. While all displays
+//! are off (display timeout, or a laptop lid closed without an external display) display
+//! change messages are ignored and a `DisplayPowerChanged` event lets the controller
+//! suspend process polling; the state is rechecked as soon as the displays come back on.
+//!
+//! Resuming from sleep is reported as `SystemResumed`, because Windows sometimes reverts
+//! HDR across a sleep while the monitored application keeps running.
+//!
+//! A display driver reset (TDR) that leaves the display configuration as it was sends no
+//! display change message. A watcher thread therefore keeps a Direct3D device without a
+//! swap chain and waits for Windows to report it removed, which happens on every driver
+//! reset. The reset is reported as `DisplayDriverReset` and the HDR state read again.
+//!
+//! Power source and battery charge notifications are registered on the same window and
+//! forwarded as `PowerStatusChanged`, so HDR can be kept off on battery power.
+//!
+//! Volume arrival and removal broadcasts (`WM_DEVICECHANGE`) are forwarded as well, so
+//! applications on network shares and removable drives can be shown as offline or back
+//! online without polling.
+//!
+//! Not every HDR toggle is broadcast: the Win+Alt+B shortcut and some driver control
+//! panels change advanced color without a `WM_SETTINGCHANGE`. A watcher thread therefore
+//! waits for changes to the `MonitorDataStore` registry key, where Windows records the
+//! advanced color state of every display, and posts them to the hidden window. If the key
+//! cannot be watched, the window polls the HDR state every few seconds instead.
 
 use crate::error::Result;
-use crate::hdr::HdrController;
+#[cfg(windows)]
+use crate::hdr::HdrControl;
+use crate::hdr::{DisplayTarget, HdrController};
+use crate::utils::PowerStatus;
 use parking_lot::Mutex;
 use std::sync::Arc;
 use std::sync::mpsc;
@@ -21,9 +50,11 @@ use tracing::error;
 use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
 #[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, KillTimer, MSG,
-    PostQuitMessage, RegisterClassW, SetTimer, UnregisterClassW, WINDOW_EX_STYLE, WM_DESTROY,
-    WM_DISPLAYCHANGE, WM_SETTINGCHANGE, WM_TIMER, WNDCLASSW, WS_OVERLAPPEDWINDOW,
+    CreateWindowExW, DBT_DEVICEARRIVAL, DBT_DEVICEREMOVECOMPLETE, DBT_DEVTYP_VOLUME,
+    DEV_BROADCAST_HDR, DefWindowProcW, DispatchMessageW, GetMessageW, KillTimer, MSG,
+    PBT_APMRESUMEAUTOMATIC, PBT_POWERSETTINGCHANGE, PostQuitMessage, RegisterClassW, SetTimer,
+    UnregisterClassW, WINDOW_EX_STYLE, WM_APP, WM_DESTROY, WM_DEVICECHANGE, WM_DISPLAYCHANGE,
+    WM_ENDSESSION, WM_POWERBROADCAST, WM_SETTINGCHANGE, WM_TIMER, WNDCLASSW, WS_OVERLAPPEDWINDOW,
 };
 
 // Timing constants for HDR state recheck strategy
@@ -36,20 +67,56 @@ const RECHECK_INTERVAL_MS: u32 = 500; // Interval between rechecks
 #[cfg(windows)]
 const MAX_RECHECK_COUNT: u32 = 10; // Maximum number of rechecks (5 seconds total)
 
-// Timer ID for HDR state rechecks
+// Timer IDs for HDR state rechecks
 #[cfg(windows)]
 const TIMER_ID_HDR_RECHECK: usize = 1; // Periodic recheck timer
+#[cfg(windows)]
+const TIMER_ID_FALLBACK_POLL: usize = 2; // Poll timer when registry notifications are unavailable
+
+/// Interval of the fallback HDR state poll when the registry cannot be watched
+#[cfg(windows)]
+const FALLBACK_POLL_INTERVAL_MS: u32 = 3000;
+
+/// Registry key under `HKEY_LOCAL_MACHINE` holding the advanced color state of each display
+#[cfg(windows)]
+const MONITOR_DATA_STORE_KEY: &str =
+    r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers\MonitorDataStore";
+
+/// Posted to the hidden window when the advanced color registry key changed
+#[cfg(windows)]
+const WM_ADVANCED_COLOR_CHANGED: u32 = WM_APP + 1;
+
+/// Posted to the hidden window when the registry watch stopped working
+#[cfg(windows)]
+const WM_ADVANCED_COLOR_WATCH_FAILED: u32 = WM_APP + 2;
+
+/// Posted to the hidden window when the watched Direct3D device was removed
+#[cfg(windows)]
+const WM_DISPLAY_DRIVER_RESET: u32 = WM_APP + 3;
+
+/// Pause after a driver reset before a new Direct3D device is created
+#[cfg(windows)]
+const DEVICE_RECREATE_DELAY_MS: u32 = 2000;
 
 /// HDR state change events
 ///
 /// These events are sent when the HDR state changes externally (e.g., via Windows settings)
 /// or when display configuration changes (HDR-capable displays added/removed).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HdrStateEvent {
-    /// HDR was enabled (detected via Windows display change notification)
-    Enabled,
-    /// HDR was disabled (detected via Windows display change notification)
-    Disabled,
+    /// HDR was turned on or off on a display (detected via Windows display change notification)
+    ///
+    /// Sent once for every display whose HDR state changed, so turning HDR off on a
+    /// secondary monitor can be told apart from a change on the primary one. A newly
+    /// connected display that already has HDR on is reported as a change from off.
+    DisplayHdrChanged {
+        /// Display whose HDR state changed
+        display: DisplayTarget,
+        /// HDR state before the change
+        previous: bool,
+        /// HDR state now
+        enabled: bool,
+    },
     /// Display configuration changed - HDR-capable displays added or removed
     ///
     /// This event is sent when the number of HDR-capable displays changes,
@@ -59,6 +126,38 @@ pub enum HdrStateEvent {
         /// Number of HDR-capable displays now available
         hdr_capable_count: usize,
     },
+    /// Console displays were turned off or back on
+    ///
+    /// Sent when Windows reports a console display power change, e.g. on display timeout
+    /// or when a laptop lid is closed with no external display. While displays are off
+    /// there is nothing to manage, so the application can stop polling.
+    DisplayPowerChanged {
+        /// Whether the displays are on (dimmed counts as on)
+        displays_on: bool,
+    },
+    /// A volume was mounted or removed
+    ///
+    /// Sent when a removable drive is plugged in or out or a network drive is mapped or
+    /// disconnected, so the application can recheck which monitored executables are
+    /// reachable.
+    VolumesChanged,
+    /// The power source or the battery charge changed
+    ///
+    /// Sent when the system switches between AC and battery power and whenever the
+    /// remaining battery charge changes by a percent.
+    PowerStatusChanged {
+        /// Power status now
+        status: PowerStatus,
+    },
+    /// The system resumed from sleep or hibernation
+    ///
+    /// Windows can revert HDR across a sleep, so the application should check that HDR
+    /// is still in the state the monitored applications need.
+    SystemResumed,
+    /// The display driver was reset (TDR) without the display configuration changing
+    ///
+    /// Like a resume, a driver reset can turn HDR off behind the application's back.
+    DisplayDriverReset,
 }
 
 /// HDR state monitor
@@ -70,8 +169,8 @@ pub struct HdrStateMonitor {
     event_sender: mpsc::SyncSender<HdrStateEvent>,
     /// HDR controller for querying HDR state
     hdr_controller: Arc<Mutex<HdrController>>,
-    /// Cached HDR state for change detection
-    cached_hdr_state: Arc<Mutex<bool>>,
+    /// Cached HDR state of each HDR-capable display for change detection
+    cached_hdr_states: Arc<Mutex<Vec<(DisplayTarget, bool)>>>,
 }
 
 impl HdrStateMonitor {
@@ -90,16 +189,20 @@ impl HdrStateMonitor {
         event_sender: mpsc::SyncSender<HdrStateEvent>,
     ) -> Result<Self> {
         // Detect initial HDR state
-        let initial_state = Self::detect_current_hdr_state_internal(&hdr_controller);
+        let initial_states = Self::detect_display_hdr_states(&hdr_controller);
         debug!(
-            "HdrStateMonitor initialized with HDR state: {}",
-            initial_state
+            "HdrStateMonitor initialized with HDR on for {} of {} HDR-capable display(s)",
+            initial_states
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .count(),
+            initial_states.len()
         );
 
         Ok(Self {
             event_sender,
             hdr_controller: Arc::new(Mutex::new(hdr_controller)),
-            cached_hdr_state: Arc::new(Mutex::new(initial_state)),
+            cached_hdr_states: Arc::new(Mutex::new(initial_states)),
         })
     }
 
@@ -112,26 +215,30 @@ impl HdrStateMonitor {
     ///
     /// Returns a `JoinHandle` for the background thread
     pub fn start(self) -> std::thread::JoinHandle<()> {
-        std::thread::spawn(move || {
-            #[cfg(windows)]
-            {
-                info!("Starting HDR state monitor thread");
-                if let Err(e) = self.run_message_loop() {
-                    error!("HDR state monitor failed: {}", e);
+        std::thread::Builder::new()
+            .name("hdr-state-monitor".to_string())
+            .spawn(move || {
+                #[cfg(windows)]
+                {
+                    info!("Starting HDR state monitor thread");
+                    // Restarted by the watchdog after a panic, with a new message window
+                    crate::utils::watchdog::supervise("hdr-state-monitor", || {
+                        if let Err(e) = self.run_message_loop() {
+                            error!("HDR state monitor failed: {}", e);
+                        }
+                    });
+                    info!("HDR state monitor thread exited");
                 }
-                info!("HDR state monitor thread exited");
-            }
 
-            #[cfg(not(windows))]
-            {
-                info!("HDR state monitor not supported on non-Windows platforms");
-            }
-        })
+                #[cfg(not(windows))]
+                {
+                    info!("HDR state monitor not supported on non-Windows platforms");
+                }
+            })
+            .expect("Failed to spawn the HDR state monitor thread")
     }
 
-    /// Detect current HDR state from the system
-    ///
-    /// Checks all HDR-capable displays and returns true if any of them have HDR enabled.
+    /// Detect the current HDR state of each display from the system
     ///
     /// # Arguments
     ///
@@ -139,52 +246,108 @@ impl HdrStateMonitor {
     ///
     /// # Returns
     ///
-    /// Returns true if HDR is enabled on any HDR-capable display, false otherwise.
-    fn detect_current_hdr_state_internal(hdr_controller: &HdrController) -> bool {
-        // Delegate to the shared implementation in HdrController
-        hdr_controller.detect_current_hdr_state()
+    /// Returns every HDR-capable display with whether HDR is enabled on it. Displays whose
+    /// state cannot be read are left out, so a failed read is never reported as a change.
+    fn detect_display_hdr_states(hdr_controller: &HdrController) -> Vec<(DisplayTarget, bool)> {
+        hdr_controller
+            .get_display_cache()
+            .iter()
+            .filter(|display| display.supports_hdr)
+            .filter_map(|target| match hdr_controller.is_hdr_enabled(target) {
+                Ok(enabled) => Some((target.clone(), enabled)),
+                Err(e) => {
+                    warn!("Failed to check HDR state for {}: {}", target, e);
+                    None
+                }
+            })
+            .collect()
     }
 
-    /// Check if HDR state has changed and send event if it has
+    /// Events for the displays whose HDR state differs from the cached state
     ///
-    /// This method is called when a display configuration change is detected.
-    /// It queries the actual HDR state and compares with the cached state.
-    #[expect(dead_code)] // Used in Windows-specific window procedure
-    fn check_and_notify_hdr_state_change(&self) {
-        debug!("Checking for HDR state change");
-
-        // Query current HDR state
-        let controller = self.hdr_controller.lock();
-        let current_state = Self::detect_current_hdr_state_internal(&controller);
-        drop(controller);
-
-        // Compare with cached state
-        let mut cached_state = self.cached_hdr_state.lock();
-        if current_state == *cached_state {
-            debug!("HDR state unchanged: {}", current_state);
-        } else {
-            info!(
-                "HDR state changed: {} -> {}",
-                if *cached_state { "ON" } else { "OFF" },
-                if current_state { "ON" } else { "OFF" }
-            );
+    /// Displays are matched by adapter and target ID. Displays that are no longer
+    /// connected produce no event, and new displays count as having had HDR off.
+    fn hdr_state_changes(
+        cached: &[(DisplayTarget, bool)],
+        current: &[(DisplayTarget, bool)],
+    ) -> Vec<HdrStateEvent> {
+        current
+            .iter()
+            .filter_map(|(display, enabled)| {
+                let previous = cached
+                    .iter()
+                    .find(|(known, _)| {
+                        known.adapter_id == display.adapter_id
+                            && known.target_id == display.target_id
+                    })
+                    .is_some_and(|(_, previous)| *previous);
+                (previous != *enabled).then(|| HdrStateEvent::DisplayHdrChanged {
+                    display: display.clone(),
+                    previous,
+                    enabled: *enabled,
+                })
+            })
+            .collect()
+    }
 
-            // Update cached state
-            *cached_state = current_state;
+    /// Compare the HDR state of each display with the cached state and send an event
+    /// for every display that changed
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if any display changed.
+    fn notify_hdr_state_changes(
+        hdr_controller: &Mutex<HdrController>,
+        cached_hdr_states: &Mutex<Vec<(DisplayTarget, bool)>>,
+        event_sender: &mpsc::SyncSender<HdrStateEvent>,
+    ) -> bool {
+        let current = Self::detect_display_hdr_states(&hdr_controller.lock());
+
+        let mut cached = cached_hdr_states.lock();
+        let changes = Self::hdr_state_changes(&cached, &current);
+        *cached = current;
+        drop(cached);
+
+        if changes.is_empty() {
+            debug!("HDR state unchanged on all displays");
+            return false;
+        }
 
-            // Send event
-            let event = if current_state {
-                HdrStateEvent::Enabled
-            } else {
-                HdrStateEvent::Disabled
-            };
+        for event in changes {
+            if let HdrStateEvent::DisplayHdrChanged {
+                display: changed,
+                previous,
+                enabled,
+            } = &event
+            {
+                info!(
+                    "HDR state changed on {}: {} -> {}",
+                    changed,
+                    if *previous { "ON" } else { "OFF" },
+                    if *enabled { "ON" } else { "OFF" }
+                );
+            }
 
-            if let Err(e) = self.event_sender.send(event) {
+            debug!("Sending HDR state event: {:?}", event);
+            if let Err(e) = event_sender.send(event) {
                 warn!("Failed to send HDR state event: {}", e);
-            } else {
-                debug!("Sent HDR state event: {:?}", event);
             }
         }
+        true
+    }
+
+    /// Check if HDR state has changed and send event if it has
+    ///
+    /// This method is called when a display configuration change is detected.
+    /// It queries the actual HDR state and compares with the cached state.
+    #[expect(dead_code)] // Used in Windows-specific window procedure
+    fn check_and_notify_hdr_state_change(&self) {
+        debug!("Checking for HDR state change");
+        Self::notify_hdr_state_changes(
+            &self.hdr_controller,
+            &self.cached_hdr_states,
+            &self.event_sender,
+        );
     }
 
     /// Run the Windows message loop
@@ -208,6 +371,14 @@ impl HdrStateMonitor {
     fn run_message_loop(&self) -> Result<()> {
         use std::ffi::OsStr;
         use std::os::windows::ffi::OsStrExt;
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::Power::{
+            RegisterPowerSettingNotification, UnregisterPowerSettingNotification,
+        };
+        use windows::Win32::System::SystemServices::{
+            GUID_ACDC_POWER_SOURCE, GUID_BATTERY_PERCENTAGE_REMAINING, GUID_CONSOLE_DISPLAY_STATE,
+        };
+        use windows::Win32::UI::WindowsAndMessaging::DEVICE_NOTIFY_WINDOW_HANDLE;
         use windows::core::PCWSTR;
 
         // Convert strings to wide strings for Windows API
@@ -235,12 +406,14 @@ impl HdrStateMonitor {
 
         let monitor_state = Arc::new(MonitorState {
             hdr_controller: self.hdr_controller.clone(),
-            cached_hdr_state: self.cached_hdr_state.clone(),
+            cached_hdr_states: self.cached_hdr_states.clone(),
             event_sender: self.event_sender.clone(),
             recheck_count: Arc::new(Mutex::new(0)),
             cached_hdr_capable_count: Arc::new(Mutex::new(initial_hdr_count)),
             // None means never refreshed, allowing immediate first refresh
             last_display_refresh: Arc::new(Mutex::new(None)),
+            displays_on: Arc::new(Mutex::new(true)),
+            power_status: Arc::new(Mutex::new(PowerStatus::current())),
         });
 
         unsafe {
@@ -297,6 +470,54 @@ impl HdrStateMonitor {
 
             info!("Created hidden window for HDR state monitoring (positioned off-screen)");
 
+            // Display power notifications; the current state is delivered right away
+            let power_notify = match RegisterPowerSettingNotification(
+                HANDLE(hwnd.0),
+                &GUID_CONSOLE_DISPLAY_STATE,
+                DEVICE_NOTIFY_WINDOW_HANDLE,
+            ) {
+                Ok(handle) => Some(handle),
+                Err(e) => {
+                    warn!("Failed to register for display power notifications: {e}");
+                    None
+                }
+            };
+
+            // Power source and battery charge notifications
+            let battery_notify: Vec<_> =
+                [GUID_ACDC_POWER_SOURCE, GUID_BATTERY_PERCENTAGE_REMAINING]
+                    .iter()
+                    .filter_map(|setting| {
+                        RegisterPowerSettingNotification(
+                            HANDLE(hwnd.0),
+                            std::ptr::from_ref(setting),
+                            DEVICE_NOTIFY_WINDOW_HANDLE,
+                        )
+                        .inspect_err(|e| {
+                            warn!("Failed to register for power source notifications: {e}")
+                        })
+                        .ok()
+                    })
+                    .collect();
+
+            // Advanced color registry notifications, falling back to polling
+            let color_watcher = match AdvancedColorWatcher::start(hwnd) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    warn!(
+                        "Failed to watch the advanced color registry key, polling HDR state every {}ms instead: {e}",
+                        FALLBACK_POLL_INTERVAL_MS
+                    );
+                    start_fallback_polling(hwnd);
+                    None
+                }
+            };
+
+            // Direct3D device removal, which is how driver resets show
+            let device_watcher = DeviceLostWatcher::start(hwnd)
+                .inspect_err(|e| warn!("Failed to watch for display driver resets: {e}"))
+                .ok();
+
             // Enter message loop
             let mut msg = MSG::default();
             while GetMessageW(&raw mut msg, None, 0, 0).as_bool() {
@@ -304,6 +525,18 @@ impl HdrStateMonitor {
             }
 
             // Cleanup
+            if let Some(watcher) = color_watcher {
+                watcher.stop();
+            }
+            if let Some(watcher) = device_watcher {
+                watcher.stop();
+            }
+            if let Some(handle) = power_notify {
+                let _ = UnregisterPowerSettingNotification(handle);
+            }
+            for handle in battery_notify {
+                let _ = UnregisterPowerSettingNotification(handle);
+            }
             let _ = UnregisterClassW(PCWSTR(class_name_wide.as_ptr()), None);
             debug!("Unregistered window class and cleaned up");
 
@@ -316,7 +549,7 @@ impl HdrStateMonitor {
 #[cfg(windows)]
 struct MonitorState {
     hdr_controller: Arc<Mutex<HdrController>>,
-    cached_hdr_state: Arc<Mutex<bool>>,
+    cached_hdr_states: Arc<Mutex<Vec<(DisplayTarget, bool)>>>,
     event_sender: mpsc::SyncSender<HdrStateEvent>,
     recheck_count: Arc<Mutex<u32>>, // Counter for remaining rechecks
     /// Cached count of HDR-capable displays for change detection
@@ -324,6 +557,10 @@ struct MonitorState {
     /// Last time display configuration was refreshed (for debouncing)
     /// `None` means never refreshed, allowing immediate first refresh
     last_display_refresh: Arc<Mutex<Option<std::time::Instant>>>,
+    /// Last reported console display power state
+    displays_on: Arc<Mutex<bool>>,
+    /// Last reported power source and battery charge
+    power_status: Arc<Mutex<PowerStatus>>,
 }
 
 // Thread-local storage for monitor state
@@ -334,9 +571,10 @@ thread_local! {
 
 /// Window procedure for the hidden window
 ///
-/// Handles `WM_DISPLAYCHANGE`/`WM_SETTINGCHANGE`. Immediate check, then periodic rechecks
-/// (500ms × 10 max) if state unchanged. Handles race condition where Windows messages
-/// arrive before `DisplayConfig` APIs reflect state change.
+/// Handles `WM_DISPLAYCHANGE`/`WM_SETTINGCHANGE` and advanced color registry changes.
+/// Immediate check, then periodic rechecks (500ms × 10 max) if state unchanged. Handles
+/// race condition where Windows messages arrive before `DisplayConfig` APIs reflect state
+/// change.
 ///
 /// # Safety
 ///
@@ -348,7 +586,7 @@ thread_local! {
 #[cfg(windows)]
 #[expect(
     unsafe_code,
-    reason = "Windows FFI callback for window procedure handling WM_DISPLAYCHANGE and WM_SETTINGCHANGE messages"
+    reason = "Windows FFI callback for window procedure handling WM_DISPLAYCHANGE, WM_SETTINGCHANGE, WM_POWERBROADCAST, WM_DEVICECHANGE and WM_ENDSESSION messages"
 )]
 unsafe extern "system" fn window_proc(
     hwnd: HWND,
@@ -357,6 +595,50 @@ unsafe extern "system" fn window_proc(
     lparam: LPARAM,
 ) -> LRESULT {
     match msg {
+        WM_DISPLAYCHANGE | WM_SETTINGCHANGE | WM_ADVANCED_COLOR_CHANGED if !displays_on() => {
+            debug!("Ignoring display change message while displays are off");
+            LRESULT(0)
+        }
+        WM_POWERBROADCAST if wparam.0 == PBT_POWERSETTINGCHANGE as usize => {
+            // SAFETY: For PBT_POWERSETTINGCHANGE, lparam points to a POWERBROADCAST_SETTING
+            // that Windows keeps valid for the duration of this message.
+            let setting = unsafe {
+                &*(lparam.0 as *const windows::Win32::System::Power::POWERBROADCAST_SETTING)
+            };
+            let power_setting = setting.PowerSetting;
+            if power_setting == windows::Win32::System::SystemServices::GUID_CONSOLE_DISPLAY_STATE
+                && setting.DataLength >= 1
+            {
+                // 0 = off, 1 = on, 2 = dimmed
+                handle_display_power_change(hwnd, setting.Data[0] != 0);
+            } else if setting.DataLength >= 4 {
+                // SAFETY: DataLength says Windows provided at least a DWORD of data
+                // following the header, which may not be aligned
+                let value =
+                    unsafe { std::ptr::read_unaligned(setting.Data.as_ptr().cast::<u32>()) };
+                if power_setting == windows::Win32::System::SystemServices::GUID_ACDC_POWER_SOURCE {
+                    // 0 = AC, 1 = battery, 2 = short-term source such as a UPS
+                    handle_power_status_change(|status| status.on_battery = value == 1);
+                } else if power_setting
+                    == windows::Win32::System::SystemServices::GUID_BATTERY_PERCENTAGE_REMAINING
+                {
+                    handle_power_status_change(|status| {
+                        status.battery_percent = u8::try_from(value.min(100)).ok();
+                    });
+                }
+            }
+            LRESULT(1)
+        }
+        WM_POWERBROADCAST if wparam.0 == PBT_APMRESUMEAUTOMATIC as usize => {
+            info!("System resumed from sleep");
+            handle_display_reset(hwnd, HdrStateEvent::SystemResumed);
+            LRESULT(1)
+        }
+        WM_DISPLAY_DRIVER_RESET => {
+            info!("Display driver was reset");
+            handle_display_reset(hwnd, HdrStateEvent::DisplayDriverReset);
+            LRESULT(0)
+        }
         WM_DISPLAYCHANGE => {
             debug!("Received WM_DISPLAYCHANGE message");
 
@@ -397,6 +679,31 @@ unsafe extern "system" fn window_proc(
             }
             LRESULT(0)
         }
+        WM_ADVANCED_COLOR_CHANGED => {
+            debug!("Advanced color registry key changed");
+
+            if check_hdr_state_change() {
+                stop_periodic_rechecks(hwnd);
+            } else {
+                // The registry can be written before DisplayConfig reports the new state
+                start_periodic_rechecks(hwnd);
+            }
+            LRESULT(0)
+        }
+        WM_ADVANCED_COLOR_WATCH_FAILED => {
+            warn!(
+                "Advanced color registry watch stopped, polling HDR state every {}ms instead",
+                FALLBACK_POLL_INTERVAL_MS
+            );
+            start_fallback_polling(hwnd);
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == TIMER_ID_FALLBACK_POLL => {
+            if displays_on() {
+                check_hdr_state_change();
+            }
+            LRESULT(0)
+        }
         WM_TIMER if wparam.0 == TIMER_ID_HDR_RECHECK => {
             // Periodic recheck
             if check_hdr_state_change() {
@@ -425,10 +732,29 @@ unsafe extern "system" fn window_proc(
             }
             LRESULT(0)
         }
+        WM_DEVICECHANGE
+            if (wparam.0 == DBT_DEVICEARRIVAL as usize
+                || wparam.0 == DBT_DEVICEREMOVECOMPLETE as usize)
+                && lparam.0 != 0 =>
+        {
+            // SAFETY: For DBT_DEVICEARRIVAL and DBT_DEVICEREMOVECOMPLETE, a non-null lparam
+            // points to a DEV_BROADCAST_HDR that Windows keeps valid for this message.
+            let header = unsafe { &*(lparam.0 as *const DEV_BROADCAST_HDR) };
+            if header.dbch_devicetype == DBT_DEVTYP_VOLUME {
+                notify_volumes_changed();
+            }
+            LRESULT(1)
+        }
+        // Windows is logging off or shutting down; this is the last chance to restore HDR
+        WM_ENDSESSION if wparam.0 != 0 => {
+            crate::hdr::crash_guard::restore_hdr("the Windows session ended");
+            LRESULT(0)
+        }
         WM_DESTROY => {
             debug!("Received WM_DESTROY message");
             stop_periodic_rechecks(hwnd);
             unsafe {
+                let _ = KillTimer(Some(hwnd), TIMER_ID_FALLBACK_POLL);
                 PostQuitMessage(0);
             }
             LRESULT(0)
@@ -437,6 +763,77 @@ unsafe extern "system" fn window_proc(
     }
 }
 
+/// Whether the console displays are currently on
+#[cfg(windows)]
+fn displays_on() -> bool {
+    MONITOR_STATE_TLS.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_none_or(|state| *state.displays_on.lock())
+    })
+}
+
+/// Handle a console display power change
+///
+/// Notifies the controller so it can suspend or resume process polling. Pending rechecks
+/// are cancelled when the displays turn off; when they turn back on, the display
+/// configuration and HDR state are checked immediately since changes made while the
+/// displays were off were ignored.
+#[cfg(windows)]
+fn handle_display_power_change(hwnd: HWND, now_on: bool) {
+    let changed = MONITOR_STATE_TLS.with(|cell| {
+        let Some(state) = cell.borrow().as_ref().cloned() else {
+            return false;
+        };
+
+        let mut displays_on = state.displays_on.lock();
+        if *displays_on == now_on {
+            return false;
+        }
+        *displays_on = now_on;
+        drop(displays_on);
+
+        info!(
+            "Console displays turned {}",
+            if now_on { "on" } else { "off" }
+        );
+
+        let event = HdrStateEvent::DisplayPowerChanged {
+            displays_on: now_on,
+        };
+        if let Err(e) = state.event_sender.send(event) {
+            warn!("Failed to send display power event: {e}");
+        }
+        true
+    });
+
+    if !changed {
+        return;
+    }
+
+    if now_on {
+        let config_changed = check_display_configuration_change();
+        if !check_hdr_state_change() && !config_changed {
+            start_periodic_rechecks(hwnd);
+        }
+    } else {
+        stop_periodic_rechecks(hwnd);
+    }
+}
+
+/// Notify the controller that a volume was mounted or removed
+#[cfg(windows)]
+fn notify_volumes_changed() {
+    debug!("Received volume arrival or removal notification");
+    MONITOR_STATE_TLS.with(|cell| {
+        if let Some(state) = cell.borrow().as_ref()
+            && let Err(e) = state.event_sender.send(HdrStateEvent::VolumesChanged)
+        {
+            warn!("Failed to send volume change event: {e}");
+        }
+    });
+}
+
 /// Start periodic HDR state rechecks
 ///
 /// Initializes the recheck counter and starts a timer for periodic rechecks.
@@ -493,10 +890,484 @@ fn stop_periodic_rechecks(hwnd: HWND) {
     });
 }
 
+/// Poll the HDR state on a timer
+///
+/// Used only when advanced color registry notifications are unavailable, so external HDR
+/// toggles that are not broadcast are still noticed.
+///
+/// # Safety
+///
+/// `hwnd` is the hidden window owned by this thread. `TIMER_ID_FALLBACK_POLL` differs from
+/// the recheck timer, and the null callback posts `WM_TIMER` to `window_proc`.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for SetTimer to poll the HDR state when notifications are unavailable"
+)]
+fn start_fallback_polling(hwnd: HWND) {
+    unsafe {
+        SetTimer(
+            Some(hwnd),
+            TIMER_ID_FALLBACK_POLL,
+            FALLBACK_POLL_INTERVAL_MS,
+            None,
+        );
+    }
+}
+
+/// Watches the advanced color registry key on a background thread
+///
+/// Every change is posted to the hidden window as `WM_ADVANCED_COLOR_CHANGED`. If a
+/// notification cannot be re-armed, `WM_ADVANCED_COLOR_WATCH_FAILED` is posted and the
+/// thread exits.
+#[cfg(windows)]
+struct AdvancedColorWatcher {
+    /// Event signalled to stop the thread, as a raw handle so it can cross threads
+    stop_event: isize,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[cfg(windows)]
+impl AdvancedColorWatcher {
+    /// Open the registry key and start watching it
+    ///
+    /// The first notification is armed before returning, so a key that cannot be watched
+    /// is reported here and the caller can fall back to polling.
+    ///
+    /// # Safety
+    ///
+    /// The key path is null-terminated and outlives `RegOpenKeyExW`. Handles created here
+    /// are closed on every error path, and otherwise by the watcher thread (key and change
+    /// event) and `stop` (stop event). `hwnd` is only used with `PostMessageW`, which fails
+    /// harmlessly once the window is gone.
+    #[expect(
+        unsafe_code,
+        reason = "Windows FFI for RegOpenKeyExW, RegNotifyChangeKeyValue and CreateEventW"
+    )]
+    fn start(hwnd: HWND) -> Result<Self> {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Registry::{
+            HKEY, HKEY_LOCAL_MACHINE, KEY_NOTIFY, RegCloseKey, RegOpenKeyExW,
+        };
+        use windows::Win32::System::Threading::CreateEventW;
+        use windows::core::PCWSTR;
+
+        let key_path: Vec<u16> = MONITOR_DATA_STORE_KEY
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+
+        unsafe {
+            let mut key = HKEY::default();
+            RegOpenKeyExW(
+                HKEY_LOCAL_MACHINE,
+                PCWSTR(key_path.as_ptr()),
+                None,
+                KEY_NOTIFY,
+                &raw mut key,
+            )
+            .ok()?;
+
+            let changed = match CreateEventW(None, false, false, PCWSTR::null()) {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = RegCloseKey(key);
+                    return Err(e.into());
+                }
+            };
+            let stop = match CreateEventW(None, true, false, PCWSTR::null()) {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = RegCloseKey(key);
+                    let _ = CloseHandle(changed);
+                    return Err(e.into());
+                }
+            };
+            if let Err(e) = arm_registry_notification(key, changed) {
+                let _ = RegCloseKey(key);
+                let _ = CloseHandle(changed);
+                let _ = CloseHandle(stop);
+                return Err(e);
+            }
+
+            // Raw handles are plain values; the thread is the only user of key and changed
+            let raw = (
+                hwnd.0 as isize,
+                key.0 as isize,
+                changed.0 as isize,
+                stop.0 as isize,
+            );
+            let thread = std::thread::Builder::new()
+                .name("advanced-color-watcher".to_string())
+                .spawn(move || watch_registry(raw.0, raw.1, raw.2, raw.3));
+            match thread {
+                Ok(thread) => {
+                    debug!(
+                        "Watching {} for advanced color changes",
+                        MONITOR_DATA_STORE_KEY
+                    );
+                    Ok(Self {
+                        stop_event: raw.3,
+                        thread,
+                    })
+                }
+                Err(e) => {
+                    let _ = RegCloseKey(key);
+                    let _ = CloseHandle(changed);
+                    let _ = CloseHandle(stop);
+                    Err(e.into())
+                }
+            }
+        }
+    }
+
+    /// Stop the watcher thread and wait for it to exit
+    ///
+    /// # Safety
+    ///
+    /// `stop_event` is the event created in `start`, closed only here after the thread,
+    /// its other user, has exited.
+    #[expect(
+        unsafe_code,
+        reason = "Windows FFI for SetEvent and CloseHandle on the watcher stop event"
+    )]
+    fn stop(self) {
+        use windows::Win32::Foundation::{CloseHandle, HANDLE};
+        use windows::Win32::System::Threading::SetEvent;
+
+        let stop = HANDLE(self.stop_event as *mut std::ffi::c_void);
+        unsafe {
+            let _ = SetEvent(stop);
+        }
+        if self.thread.join().is_err() {
+            warn!("Advanced color watcher thread panicked");
+        }
+        unsafe {
+            let _ = CloseHandle(stop);
+        }
+    }
+}
+
+/// Ask for `changed` to be signalled on the next change below the registry key
+///
+/// # Safety
+///
+/// `key` was opened with `KEY_NOTIFY` and `changed` is a valid event; both stay open
+/// while the notification is armed. Asynchronous notifications end when the arming thread
+/// exits, which is why the watcher thread re-arms them itself.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for RegNotifyChangeKeyValue on the advanced color registry key"
+)]
+fn arm_registry_notification(
+    key: windows::Win32::System::Registry::HKEY,
+    changed: windows::Win32::Foundation::HANDLE,
+) -> Result<()> {
+    use windows::Win32::System::Registry::{
+        REG_NOTIFY_CHANGE_LAST_SET, REG_NOTIFY_CHANGE_NAME, RegNotifyChangeKeyValue,
+    };
+
+    unsafe {
+        RegNotifyChangeKeyValue(
+            key,
+            true,
+            REG_NOTIFY_CHANGE_NAME | REG_NOTIFY_CHANGE_LAST_SET,
+            Some(changed),
+            true,
+        )
+        .ok()?;
+    }
+    Ok(())
+}
+
+/// Body of the advanced color watcher thread
+///
+/// Waits for registry changes or the stop event, posting each change to the hidden
+/// window and re-arming the notification. Closes the key and change event on exit.
+///
+/// # Safety
+///
+/// The raw values are the window, key and events handed over by
+/// `AdvancedColorWatcher::start`; the key and change event belong to this thread, and the
+/// stop event stays open until `AdvancedColorWatcher::stop` has joined it.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for WaitForMultipleObjects, PostMessageW and closing the registry key"
+)]
+fn watch_registry(hwnd: isize, key: isize, changed: isize, stop: isize) {
+    use std::ffi::c_void;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0};
+    use windows::Win32::System::Registry::{HKEY, RegCloseKey};
+    use windows::Win32::System::Threading::{INFINITE, WaitForMultipleObjects};
+    use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+
+    let hwnd = HWND(hwnd as *mut c_void);
+    let key = HKEY(key as *mut c_void);
+    let changed = HANDLE(changed as *mut c_void);
+    let stop = HANDLE(stop as *mut c_void);
+
+    unsafe {
+        loop {
+            let signalled = WaitForMultipleObjects(&[changed, stop], false, INFINITE);
+            if signalled != WAIT_OBJECT_0 {
+                break;
+            }
+
+            // Re-arm before posting, so a change made while the window handles this
+            // one still wakes the thread
+            if let Err(e) = arm_registry_notification(key, changed) {
+                warn!("Failed to re-arm the advanced color registry notification: {e}");
+                let _ = PostMessageW(
+                    Some(hwnd),
+                    WM_ADVANCED_COLOR_WATCH_FAILED,
+                    WPARAM(0),
+                    LPARAM(0),
+                );
+                break;
+            }
+            if PostMessageW(Some(hwnd), WM_ADVANCED_COLOR_CHANGED, WPARAM(0), LPARAM(0)).is_err() {
+                break;
+            }
+        }
+
+        let _ = RegCloseKey(key);
+        let _ = CloseHandle(changed);
+    }
+    debug!("Advanced color watcher thread exited");
+}
+
+/// Thread waiting for the display driver to remove a Direct3D device
+///
+/// The thread posts [`WM_DISPLAY_DRIVER_RESET`] to the hidden window for every removal
+/// and then creates a new device, so later resets are noticed too.
+#[cfg(windows)]
+struct DeviceLostWatcher {
+    /// Manual-reset event (raw handle) telling the watcher thread to exit
+    stop_event: isize,
+    thread: std::thread::JoinHandle<()>,
+}
+
+#[cfg(windows)]
+impl DeviceLostWatcher {
+    /// Start the watcher thread
+    ///
+    /// # Safety
+    ///
+    /// The stop event is closed on the error path, and otherwise by `stop` once the
+    /// thread, its other user, has exited.
+    #[expect(unsafe_code, reason = "Windows FFI for CreateEventW and CloseHandle")]
+    fn start(hwnd: HWND) -> Result<Self> {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Threading::CreateEventW;
+        use windows::core::PCWSTR;
+
+        unsafe {
+            let stop = CreateEventW(None, true, false, PCWSTR::null())?;
+
+            // Raw handles are plain values
+            let raw = (hwnd.0 as isize, stop.0 as isize);
+            let thread = std::thread::Builder::new()
+                .name("device-lost-watcher".to_string())
+                .spawn(move || watch_device_removal(raw.0, raw.1));
+            match thread {
+                Ok(thread) => Ok(Self {
+                    stop_event: raw.1,
+                    thread,
+                }),
+                Err(e) => {
+                    let _ = CloseHandle(stop);
+                    Err(e.into())
+                }
+            }
+        }
+    }
+
+    /// Stop the watcher thread and wait for it to exit
+    ///
+    /// # Safety
+    ///
+    /// `stop_event` is the event created in `start`, closed only here after the thread
+    /// has exited.
+    #[expect(
+        unsafe_code,
+        reason = "Windows FFI for SetEvent and CloseHandle on the watcher stop event"
+    )]
+    fn stop(self) {
+        use windows::Win32::Foundation::{CloseHandle, HANDLE};
+        use windows::Win32::System::Threading::SetEvent;
+
+        let stop = HANDLE(self.stop_event as *mut std::ffi::c_void);
+        unsafe {
+            let _ = SetEvent(stop);
+        }
+        if self.thread.join().is_err() {
+            warn!("Device lost watcher thread panicked");
+        }
+        unsafe {
+            let _ = CloseHandle(stop);
+        }
+    }
+}
+
+/// Body of the device lost watcher thread
+///
+/// Creates a Direct3D 11 device on the default adapter, registers an event Windows
+/// signals when the device is removed and waits for it or the stop event. After a
+/// removal the hidden window is told and, after [`DEVICE_RECREATE_DELAY_MS`], a new
+/// device is watched. Exits when no device can be created.
+///
+/// # Safety
+///
+/// The raw values are the window and stop event handed over by
+/// `DeviceLostWatcher::start`; the stop event stays open until `DeviceLostWatcher::stop`
+/// has joined this thread. The removal event is created, unregistered and closed here.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for D3D11CreateDevice, RegisterDeviceRemovedEvent, WaitForMultipleObjects and PostMessageW"
+)]
+fn watch_device_removal(hwnd: isize, stop: isize) {
+    use std::ffi::c_void;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0};
+    use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_HARDWARE;
+    use windows::Win32::Graphics::Direct3D11::{
+        D3D11_CREATE_DEVICE_FLAG, D3D11_SDK_VERSION, D3D11CreateDevice, ID3D11Device, ID3D11Device4,
+    };
+    use windows::Win32::System::Threading::{
+        CreateEventW, INFINITE, WaitForMultipleObjects, WaitForSingleObject,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+    use windows::core::{Interface, PCWSTR};
+
+    let hwnd = HWND(hwnd as *mut c_void);
+    let stop = HANDLE(stop as *mut c_void);
+
+    unsafe {
+        loop {
+            let mut device: Option<ID3D11Device> = None;
+            let device = match D3D11CreateDevice(
+                None,
+                D3D_DRIVER_TYPE_HARDWARE,
+                windows::Win32::Foundation::HMODULE::default(),
+                D3D11_CREATE_DEVICE_FLAG(0),
+                None,
+                D3D11_SDK_VERSION,
+                Some(&raw mut device),
+                None,
+                None,
+            )
+            .and_then(|()| device.ok_or_else(windows::core::Error::empty))
+            .and_then(|device| device.cast::<ID3D11Device4>())
+            {
+                Ok(device) => device,
+                Err(e) => {
+                    warn!("Failed to create a Direct3D device to watch for driver resets: {e}");
+                    break;
+                }
+            };
+            let removed = match CreateEventW(None, false, false, PCWSTR::null()) {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("Failed to create the device removal event: {e}");
+                    break;
+                }
+            };
+            let cookie = match device.RegisterDeviceRemovedEvent(removed) {
+                Ok(cookie) => cookie,
+                Err(e) => {
+                    warn!("Failed to register for Direct3D device removal: {e}");
+                    let _ = CloseHandle(removed);
+                    break;
+                }
+            };
+
+            let signalled = WaitForMultipleObjects(&[removed, stop], false, INFINITE);
+            device.UnregisterDeviceRemoved(cookie);
+            drop(device);
+            let _ = CloseHandle(removed);
+            if signalled != WAIT_OBJECT_0
+                || PostMessageW(Some(hwnd), WM_DISPLAY_DRIVER_RESET, WPARAM(0), LPARAM(0)).is_err()
+            {
+                break;
+            }
+
+            // The driver is still coming back; creating a device right away may fail
+            if WaitForSingleObject(stop, DEVICE_RECREATE_DELAY_MS) == WAIT_OBJECT_0 {
+                break;
+            }
+        }
+    }
+    debug!("Device lost watcher thread exited");
+}
+
 // Debounce interval for display re-enumeration (milliseconds)
 #[cfg(windows)]
 const DISPLAY_REFRESH_DEBOUNCE_MS: u64 = 1000;
 
+/// Report a resume from sleep or a display driver reset and recheck the displays
+///
+/// Windows sends `PBT_APMRESUMEAUTOMATIC` on every resume, whether or not a user is
+/// present. After a resume or a driver reset the displays and their HDR state may still
+/// be settling, so the state is rechecked periodically as after a display change.
+#[cfg(windows)]
+fn handle_display_reset(hwnd: HWND, event: HdrStateEvent) {
+    MONITOR_STATE_TLS.with(|cell| {
+        let Some(state) = cell.borrow().as_ref().cloned() else {
+            return;
+        };
+
+        if let Err(e) = state.event_sender.send(event) {
+            warn!("Failed to send display reset event: {e}");
+        }
+    });
+
+    if !displays_on() {
+        return;
+    }
+    let config_changed = check_display_configuration_change();
+    if !check_hdr_state_change() && !config_changed {
+        start_periodic_rechecks(hwnd);
+    }
+}
+
+/// Apply a power source or battery charge notification
+///
+/// Sends `PowerStatusChanged` when the status differs from the last one reported.
+#[cfg(windows)]
+fn handle_power_status_change(update: impl FnOnce(&mut PowerStatus)) {
+    MONITOR_STATE_TLS.with(|cell| {
+        let Some(state) = cell.borrow().as_ref().cloned() else {
+            return;
+        };
+
+        let mut power_status = state.power_status.lock();
+        let previous = *power_status;
+        update(&mut power_status);
+        let status = *power_status;
+        drop(power_status);
+        if status == previous {
+            return;
+        }
+
+        if status.on_battery != previous.on_battery {
+            info!(
+                "Switched to {} power",
+                if status.on_battery { "battery" } else { "AC" }
+            );
+        } else {
+            debug!("Battery charge now {:?}%", status.battery_percent);
+        }
+        if let Err(e) = state
+            .event_sender
+            .send(HdrStateEvent::PowerStatusChanged { status })
+        {
+            warn!("Failed to send power status event: {e}");
+        }
+    });
+}
+
 /// Check if display configuration has changed (HDR displays added/removed)
 ///
 /// Re-enumerates displays and compares HDR-capable count with cached value.
@@ -553,10 +1424,9 @@ fn check_display_configuration_change() -> bool {
                 let event = HdrStateEvent::DisplayConfigurationChanged {
                     hdr_capable_count: new_hdr_count,
                 };
+                debug!("Sending display configuration change event: {:?}", event);
                 if let Err(e) = state.event_sender.send(event) {
                     warn!("Failed to send display configuration change event: {}", e);
-                } else {
-                    debug!("Sent display configuration change event: {:?}", event);
                 }
                 return true;
             }
@@ -578,50 +1448,18 @@ fn check_display_configuration_change() -> bool {
 ///
 /// # Returns
 ///
-/// Returns `true` if the HDR state changed and an event was sent, `false` if the state
-/// remained unchanged. This allows callers to schedule rechecks if needed.
+/// Returns `true` if the HDR state of any display changed and events were sent, `false`
+/// if every display remained unchanged. This allows callers to schedule rechecks if needed.
 #[cfg(windows)]
 fn check_hdr_state_change() -> bool {
     MONITOR_STATE_TLS.with(|cell| {
-        if let Some(state) = cell.borrow().as_ref() {
-            // Query current HDR state
-            let controller = state.hdr_controller.lock();
-            let current_state = HdrStateMonitor::detect_current_hdr_state_internal(&controller);
-            drop(controller);
-
-            // Compare with cached state
-            let mut cached_state = state.cached_hdr_state.lock();
-            if current_state == *cached_state {
-                debug!("HDR state unchanged: {}", current_state);
-                false // State unchanged
-            } else {
-                info!(
-                    "HDR state changed: {} -> {}",
-                    if *cached_state { "ON" } else { "OFF" },
-                    if current_state { "ON" } else { "OFF" }
-                );
-
-                // Update cached state
-                *cached_state = current_state;
-
-                // Send event
-                let event = if current_state {
-                    HdrStateEvent::Enabled
-                } else {
-                    HdrStateEvent::Disabled
-                };
-
-                if let Err(e) = state.event_sender.send(event) {
-                    warn!("Failed to send HDR state event: {e}");
-                } else {
-                    debug!("Sent HDR state event: {event:?}");
-                }
-
-                true // State changed
-            }
-        } else {
-            false // No state available
-        }
+        cell.borrow().as_ref().is_some_and(|state| {
+            HdrStateMonitor::notify_hdr_state_changes(
+                &state.hdr_controller,
+                &state.cached_hdr_states,
+                &state.event_sender,
+            )
+        })
     })
 }
 
@@ -645,19 +1483,60 @@ mod tests {
     #[test]
     fn test_hdr_state_event_types() {
         // Test event types
-        assert_eq!(HdrStateEvent::Enabled, HdrStateEvent::Enabled);
-        assert_eq!(HdrStateEvent::Disabled, HdrStateEvent::Disabled);
-        assert_ne!(HdrStateEvent::Enabled, HdrStateEvent::Disabled);
+        assert_ne!(
+            HdrStateEvent::SystemResumed,
+            HdrStateEvent::DisplayDriverReset
+        );
+        assert_ne!(
+            HdrStateEvent::DisplayPowerChanged { displays_on: true },
+            HdrStateEvent::DisplayPowerChanged { displays_on: false }
+        );
+        assert_ne!(
+            HdrStateEvent::VolumesChanged,
+            HdrStateEvent::DisplayPowerChanged { displays_on: true }
+        );
     }
 
     #[test]
-    fn test_detect_current_hdr_state() {
+    fn test_detect_display_hdr_states() {
         // Create HDR controller
         let hdr_controller = HdrController::new().expect("Failed to create HDR controller");
 
         // Detect current state - this should not panic
-        let _state = HdrStateMonitor::detect_current_hdr_state_internal(&hdr_controller);
-        // The state is either true or false, both are valid
+        let states = HdrStateMonitor::detect_display_hdr_states(&hdr_controller);
+        // Only HDR-capable displays are reported
+        assert!(states.iter().all(|(display, _)| display.supports_hdr));
+    }
+
+    #[test]
+    fn test_hdr_state_changes_per_display() {
+        let display = |target_id| DisplayTarget {
+            target_id,
+            supports_hdr: true,
+            ..DisplayTarget::default()
+        };
+        let cached = vec![(display(1), true), (display(2), true), (display(3), false)];
+
+        // The secondary display was turned off and a new display arrived with HDR on;
+        // display 3 was disconnected
+        let current = vec![(display(1), true), (display(2), false), (display(4), true)];
+
+        assert_eq!(
+            HdrStateMonitor::hdr_state_changes(&cached, &current),
+            vec![
+                HdrStateEvent::DisplayHdrChanged {
+                    display: display(2),
+                    previous: true,
+                    enabled: false,
+                },
+                HdrStateEvent::DisplayHdrChanged {
+                    display: display(4),
+                    previous: false,
+                    enabled: true,
+                },
+            ]
+        );
+        assert!(HdrStateMonitor::hdr_state_changes(&current, &current).is_empty());
     }
 
     #[test]
@@ -672,15 +1551,16 @@ mod tests {
         // Create monitor state
         let state = MonitorState {
             hdr_controller: Arc::new(Mutex::new(hdr_controller)),
-            cached_hdr_state: Arc::new(Mutex::new(false)),
+            cached_hdr_states: Arc::new(Mutex::new(Vec::new())),
             event_sender: tx,
             recheck_count: Arc::new(Mutex::new(0)),
             cached_hdr_capable_count: Arc::new(Mutex::new(0)),
             last_display_refresh: Arc::new(Mutex::new(None)),
+            displays_on: Arc::new(Mutex::new(true)),
+            power_status: Arc::new(Mutex::new(PowerStatus::default())),
         };
 
         // Verify state structure
-        let cached = *state.cached_hdr_state.lock();
-        assert!(!cached);
+        assert!(state.cached_hdr_states.lock().is_empty());
     }
 }
@@ -1,10 +1,29 @@
 //! Process monitoring module
 //!
 //! Provides background monitoring of running processes to detect when configured
-//! applications start or stop, enabling automatic HDR toggling.
+//! applications start or stop, enabling automatic HDR toggling. Processes launched by a
+//! monitored application are attributed to it through the process tree. A snapshot of
+//! running applications backs the picker for adding apps that are already running.
+//! Processes `EasyHDR` may not open, such as games running as administrator, are resolved
+//! through WMI or reported when they keep a watched folder from matching. Optionally, an
+//! application only counts as started once it shows a visible window, and the polling
+//! interval adapts to how much is going on.
 
+pub mod elevated;
 pub mod hdr_state_monitor;
+pub mod poll_schedule;
 pub mod process_monitor;
+pub mod process_tree;
+pub mod running_candidates;
+pub mod top_level_windows;
+pub mod uwp_apps;
 
 pub use hdr_state_monitor::{HdrStateEvent, HdrStateMonitor};
-pub use process_monitor::{AppIdentifier, ProcessEvent, ProcessMonitor, WatchState};
+pub use poll_schedule::{AdaptivePolling, PollSchedule};
+pub use process_monitor::{
+    AppIdentifier, PROCESS_TRACE_DURATION, PollTimings, PollingSuspension, ProcessEvent,
+    ProcessMonitor, ProcessNamePatterns, ProcessTrace, ScanResults, SharedWatchState, WatchState,
+};
+pub use process_tree::{ProcessEntry, ProcessTree};
+pub use running_candidates::{CandidateKind, RunningCandidate};
+pub use uwp_apps::RunningUwpApps;
@@ -0,0 +1,156 @@
+//! Adaptive process polling interval
+//!
+//! A fixed interval wakes the CPU every second while nothing changes, yet notices a game
+//! launch no sooner than the next poll. With adaptive polling the process monitor polls
+//! faster for a short while after the watch list changes, the foreground window moves to
+//! another process or a monitored application starts or stops, since that is when a
+//! launch or exit tends to follow. After a minute without any of these, the interval
+//! doubles for every further idle minute, up to [`AdaptivePolling::max_idle_interval`].
+
+use std::time::{Duration, Instant};
+
+/// Interval while polling faster after activity
+pub const ACTIVE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long polling stays faster after activity
+pub const ACTIVE_POLL_DURATION: Duration = Duration::from_secs(10);
+
+/// Time without activity after which the interval starts to stretch
+pub const IDLE_POLL_AFTER: Duration = Duration::from_mins(1);
+
+/// Settings of adaptive polling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptivePolling {
+    /// Longest interval polling stretches to while nothing happens
+    pub max_idle_interval: Duration,
+}
+
+/// Chooses how long the process monitor waits before each poll
+///
+/// Without [`AdaptivePolling`] settings the configured interval is always used.
+#[derive(Debug, Clone)]
+pub struct PollSchedule {
+    /// Configured monitoring interval
+    base: Duration,
+    /// Most recent activity that makes a launch or exit likely
+    last_activity: Instant,
+    /// Watch list version seen by the previous poll
+    watch_version: u64,
+    /// Process owning the foreground window at the previous poll
+    foreground_pid: Option<u32>,
+}
+
+impl PollSchedule {
+    /// Create a schedule around the configured interval, starting out active
+    pub fn new(base: Duration, now: Instant) -> Self {
+        Self {
+            base,
+            last_activity: now,
+            watch_version: 0,
+            foreground_pid: None,
+        }
+    }
+
+    /// Record activity that makes a launch or exit likely
+    pub fn note_activity(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// Record the published watch list version; a new version counts as activity
+    pub fn observe_watch_version(&mut self, version: u64, now: Instant) {
+        if version != self.watch_version {
+            self.watch_version = version;
+            self.note_activity(now);
+        }
+    }
+
+    /// Record the process owning the foreground window; a switch counts as activity
+    pub fn observe_foreground(&mut self, pid: Option<u32>, now: Instant) {
+        if pid != self.foreground_pid {
+            self.foreground_pid = pid;
+            self.note_activity(now);
+        }
+    }
+
+    /// Wait before the next poll
+    pub fn next_interval(&self, now: Instant, adaptive: Option<AdaptivePolling>) -> Duration {
+        let Some(adaptive) = adaptive else {
+            return self.base;
+        };
+
+        let idle = now.saturating_duration_since(self.last_activity);
+        if idle < ACTIVE_POLL_DURATION {
+            return ACTIVE_POLL_INTERVAL.min(self.base);
+        }
+        if idle < IDLE_POLL_AFTER {
+            return self.base;
+        }
+
+        let idle_steps =
+            u32::try_from(idle.as_secs() / IDLE_POLL_AFTER.as_secs()).unwrap_or(u32::MAX);
+        self.base
+            .saturating_mul(2u32.saturating_pow(idle_steps))
+            .min(adaptive.max_idle_interval)
+            .max(self.base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_follows_activity() {
+        let start = Instant::now();
+        let base = Duration::from_millis(1000);
+        let adaptive = Some(AdaptivePolling {
+            max_idle_interval: Duration::from_millis(5000),
+        });
+        let mut schedule = PollSchedule::new(base, start);
+
+        // Without adaptive polling the configured interval is kept
+        assert_eq!(schedule.next_interval(start, None), base);
+        assert_eq!(
+            schedule.next_interval(start + Duration::from_secs(600), None),
+            base
+        );
+
+        assert_eq!(
+            schedule.next_interval(start, adaptive),
+            ACTIVE_POLL_INTERVAL
+        );
+        assert_eq!(
+            schedule.next_interval(start + Duration::from_secs(30), adaptive),
+            base
+        );
+        assert_eq!(
+            schedule.next_interval(start + Duration::from_secs(60), adaptive),
+            Duration::from_millis(2000)
+        );
+        assert_eq!(
+            schedule.next_interval(start + Duration::from_secs(120), adaptive),
+            Duration::from_millis(4000)
+        );
+        assert_eq!(
+            schedule.next_interval(start + Duration::from_secs(3600), adaptive),
+            Duration::from_millis(5000)
+        );
+
+        // A foreground switch or a new watch list makes polling fast again
+        let later = start + Duration::from_secs(3600);
+        schedule.observe_foreground(Some(42), later);
+        assert_eq!(
+            schedule.next_interval(later, adaptive),
+            ACTIVE_POLL_INTERVAL
+        );
+        let idle = later + Duration::from_secs(120);
+        schedule.observe_foreground(Some(42), idle);
+        schedule.observe_watch_version(0, idle);
+        assert_eq!(
+            schedule.next_interval(idle, adaptive),
+            Duration::from_millis(4000)
+        );
+        schedule.observe_watch_version(1, idle);
+        assert_eq!(schedule.next_interval(idle, adaptive), ACTIVE_POLL_INTERVAL);
+    }
+}
@@ -2,9 +2,39 @@
 //!
 //! Polls Windows processes and detects state changes. Matches by executable filename only
 //! (lowercase, no extension). Name collisions trigger HDR for all matching processes.
-
-use parking_lot::RwLock;
-use std::collections::{HashMap, HashSet};
+//! When child process tracking is enabled, processes spawned by a matched process count
+//! as that application until the whole process tree has exited. Polling is suspended
+//! while all displays are off (e.g. laptop lid closed without an external display).
+//! Scan durations are tracked in [`PollTimings`] so slow machines can be told to use a
+//! longer interval. With [`WatchState::adaptive_polling`], [`PollSchedule`] shortens the
+//! interval after activity and stretches it while idle. A time-boxed [`ProcessTrace`]
+//! logs every considered process and why it did or didn't match, for diagnosing apps
+//! that are never detected.
+//!
+//! Each scan only identifies processes it has not seen before: processes still running
+//! with the same executable and parent keep the identifier determined when they first
+//! appeared, so `OpenProcess` and the UWP package queries run once per process rather
+//! than once per poll. Identifiers are matched against the hashed index in
+//! [`WatchState`], and events come from the difference between consecutive scans. The
+//! controller publishes each new watch state through [`SharedWatchState`], and every scan
+//! loads the latest one without locking. Scans publish what they found back as
+//! [`ScanResults`] snapshots, which the controller loads the same way.
+//! While the app diagnostics watcher runs, UWP apps come from [`RunningUwpApps`] instead.
+//!
+//! Processes whose image path cannot be read, typically games running as administrator,
+//! are looked up through WMI once per scan so watched folders can still match them; those
+//! that stay unknown are reported in [`ScanResults::elevated_apps`] when their executable
+//! lies in a watched folder.
+//!
+//! With [`WatchState::wait_for_window`], newly matched applications are held back until
+//! one of their processes shows a visible top-level window, see
+//! [`super::top_level_windows`].
+
+use arc_swap::ArcSwap;
+use parking_lot::{Condvar, Mutex};
+use regex::{RegexSet, RegexSetBuilder};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, mpsc};
 use std::thread::{self, JoinHandle};
@@ -24,20 +54,106 @@ use windows::Win32::Foundation::{CloseHandle, ERROR_NO_MORE_FILES};
 #[cfg(windows)]
 use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
 
+use super::elevated;
+use super::poll_schedule::{AdaptivePolling, PollSchedule};
+#[cfg(windows)]
+use super::process_tree::ProcessEntry;
+use super::process_tree::ProcessTree;
+use super::top_level_windows;
+use super::uwp_apps::RunningUwpApps;
 use crate::config::MonitoredApp;
 use crate::error::{EasyHdrError, Result};
+use crate::utils::canonical_path;
+use crate::utils::unicode::{eq_ignore_case, fold_chars, normalize_process_name};
+use crate::utils::watchdog;
 
 /// Identifier for a monitored application
 ///
-/// Distinguishes between Win32 desktop applications and UWP applications.
-/// Win32 apps are identified by their process name (lowercase, no extension),
-/// while UWP apps are identified by their package family name.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Distinguishes between Win32 desktop applications, UWP applications and folders.
+/// Win32 apps are identified by their normalized process name (no extension),
+/// while UWP apps are identified by their package family name, or by their
+/// Application User Model ID when a specific application within a package is watched.
+/// Watched folders are identified by their path.
+///
+/// Identifiers compare and hash case-insensitively with the same Unicode case folding
+/// Windows uses for file and package names, so a watched `Überspiel` matches a running
+/// `ÜBERSPIEL.exe` and package family names match regardless of case. The original
+/// spelling is kept for display.
+#[derive(Debug, Clone)]
 pub enum AppIdentifier {
     /// Win32 application identified by process name (lowercase, no extension)
     Win32(String),
     /// UWP application identified by package family name
     Uwp(String),
+    /// UWP application identified by AUMID (`PackageFamilyName!AppId`)
+    UwpApplication(String),
+    /// Watched folder identified by its path (backslash separators, no trailing one)
+    Folder(String),
+}
+
+impl AppIdentifier {
+    /// Build the watch-list identifier for a monitored application
+    pub fn from_monitored_app(app: &MonitoredApp) -> Self {
+        match app {
+            MonitoredApp::Win32(win32_app) => {
+                Self::Win32(normalize_process_name(&win32_app.process_name))
+            }
+            MonitoredApp::Uwp(uwp_app) if uwp_app.match_app_id => {
+                Self::UwpApplication(uwp_app.aumid())
+            }
+            MonitoredApp::Uwp(uwp_app) => Self::Uwp(uwp_app.package_family_name.clone()),
+            MonitoredApp::Folder(folder_app) => Self::Folder(
+                folder_app
+                    .path
+                    .to_string_lossy()
+                    .replace('/', "\\")
+                    .trim_end_matches('\\')
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Whether this identifies a UWP package or application
+    pub const fn is_uwp(&self) -> bool {
+        matches!(self, Self::Uwp(_) | Self::UwpApplication(_))
+    }
+
+    /// Package-level identifier for an application-level UWP identifier
+    ///
+    /// Returns `None` for identifiers that are already package-level, Win32 or folders.
+    pub fn package_identifier(&self) -> Option<Self> {
+        match self {
+            Self::UwpApplication(aumid) => aumid
+                .split_once('!')
+                .map(|(family_name, _)| Self::Uwp(family_name.to_string())),
+            Self::Win32(_) | Self::Uwp(_) | Self::Folder(_) => None,
+        }
+    }
+}
+
+impl PartialEq for AppIdentifier {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Win32(a), Self::Win32(b))
+            | (Self::Uwp(a), Self::Uwp(b))
+            | (Self::UwpApplication(a), Self::UwpApplication(b))
+            | (Self::Folder(a), Self::Folder(b)) => eq_ignore_case(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for AppIdentifier {}
+
+impl std::hash::Hash for AppIdentifier {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        let (Self::Win32(name) | Self::Uwp(name) | Self::UwpApplication(name) | Self::Folder(name)) =
+            self;
+        for c in fold_chars(name) {
+            c.hash(state);
+        }
+    }
 }
 
 impl std::fmt::Display for AppIdentifier {
@@ -45,6 +161,8 @@ impl std::fmt::Display for AppIdentifier {
         match self {
             Self::Win32(name) => write!(f, "Win32: {name}"),
             Self::Uwp(family_name) => write!(f, "UWP: {family_name}"),
+            Self::UwpApplication(aumid) => write!(f, "UWP: {aumid}"),
+            Self::Folder(path) => write!(f, "Folder: {path}"),
         }
     }
 }
@@ -65,13 +183,46 @@ pub enum ProcessEvent {
 /// `poll_processes()` could observe an inconsistent state between the two caches.
 ///
 /// The `apps` field is wrapped in `Arc` to enable cheap cloning during event handling,
-/// while `identifiers` is owned directly for O(1) lookups.
+/// while `identifiers` and `package_apps` are owned directly for O(1) lookups. Build a
+/// new state with [`WatchState::with_apps`] so both indexes stay in sync with `apps`.
 #[derive(Clone, Debug)]
 pub struct WatchState {
     /// Monitored applications (Arc-wrapped for cheap cloning during event handling)
     pub apps: Arc<Vec<MonitoredApp>>,
-    /// Cached set of monitored app identifiers for O(1) filtering
+    /// Cached set of enabled app identifiers for O(1) filtering
     pub identifiers: HashSet<AppIdentifier>,
+    /// Watched application-level UWP identifiers by package-level identifier
+    ///
+    /// Lets processes that only report their package family name match the applications
+    /// watched from that package without scanning `identifiers`.
+    pub package_apps: HashMap<AppIdentifier, Vec<AppIdentifier>>,
+    /// Watched Win32 identifiers by the process name of one of their aliases
+    pub alias_apps: HashMap<AppIdentifier, Vec<AppIdentifier>>,
+    /// Precompiled match rules of enabled Win32 applications, `None` without rules
+    pub patterns: Option<ProcessNamePatterns>,
+    /// Path keys of enabled watched folders with their identifiers
+    ///
+    /// Processes are only resolved to their image path while this is not empty.
+    pub folders: Vec<(String, AppIdentifier)>,
+    /// Excluded processes, whose presence keeps HDR off
+    ///
+    /// Reported with start and stop events like watched applications, but never
+    /// counted as running monitored applications. Set with [`WatchState::with_exclusions`].
+    pub excluded: HashSet<AppIdentifier>,
+    /// Count processes spawned by a monitored process as that application
+    pub track_child_processes: bool,
+    /// Only report an application as started once one of its processes shows a visible
+    /// window. Set with [`WatchState::with_wait_for_window`].
+    pub wait_for_window: bool,
+    /// Settings of adaptive polling, `None` to always poll at the monitoring interval.
+    /// Set with [`WatchState::with_adaptive_polling`].
+    pub adaptive_polling: Option<AdaptivePolling>,
+    /// Suspends polling while there is nothing to manage (shared across updates)
+    pub suspension: Arc<PollingSuspension>,
+    /// On-demand logging of every considered process (shared across updates)
+    pub trace: Arc<ProcessTrace>,
+    /// UWP applications reported by the app diagnostics watcher (shared across updates)
+    pub uwp_apps: Arc<RunningUwpApps>,
 }
 
 impl Default for WatchState {
@@ -86,6 +237,637 @@ impl WatchState {
         Self {
             apps: Arc::new(Vec::new()),
             identifiers: HashSet::new(),
+            package_apps: HashMap::new(),
+            alias_apps: HashMap::new(),
+            patterns: None,
+            folders: Vec::new(),
+            excluded: HashSet::new(),
+            track_child_processes: false,
+            wait_for_window: false,
+            adaptive_polling: None,
+            suspension: Arc::new(PollingSuspension::default()),
+            trace: Arc::new(ProcessTrace::default()),
+            uwp_apps: Arc::new(RunningUwpApps::default()),
+        }
+    }
+
+    /// Build the state for a new watch list, sharing suspension, trace and
+    /// watcher-reported UWP apps with this state
+    ///
+    /// Keeps the excluded processes, window requirement and polling settings of this state.
+    ///
+    /// Indexes the enabled applications once, so scans match processes with hash lookups
+    /// instead of walking the list for every process.
+    #[must_use]
+    pub fn with_apps(
+        &self,
+        monitored_apps: Vec<MonitoredApp>,
+        track_child_processes: bool,
+    ) -> Self {
+        let identifiers: HashSet<AppIdentifier> = monitored_apps
+            .iter()
+            .filter(|app| app.is_enabled())
+            .map(AppIdentifier::from_monitored_app)
+            .collect();
+
+        let mut package_apps: HashMap<AppIdentifier, Vec<AppIdentifier>> = HashMap::new();
+        for app_id in &identifiers {
+            if let Some(package_id) = app_id.package_identifier() {
+                package_apps
+                    .entry(package_id)
+                    .or_default()
+                    .push(app_id.clone());
+            }
+        }
+
+        let mut alias_apps: HashMap<AppIdentifier, Vec<AppIdentifier>> = HashMap::new();
+        for app in monitored_apps.iter().filter(|app| app.is_enabled()) {
+            if let MonitoredApp::Win32(win32_app) = app {
+                for alias in win32_app.alias_process_names() {
+                    alias_apps
+                        .entry(AppIdentifier::Win32(alias))
+                        .or_default()
+                        .push(AppIdentifier::from_monitored_app(app));
+                }
+            }
+        }
+
+        let patterns = ProcessNamePatterns::new(&monitored_apps);
+
+        // Folders are resolved once here, so junctioned libraries match the image paths
+        // Windows reports for their executables
+        let folders = monitored_apps
+            .iter()
+            .filter(|app| app.is_enabled())
+            .filter_map(|app| match app {
+                MonitoredApp::Folder(folder_app) => Some((
+                    canonical_path::path_key(&folder_app.path),
+                    AppIdentifier::from_monitored_app(app),
+                )),
+                MonitoredApp::Win32(_) | MonitoredApp::Uwp(_) => None,
+            })
+            .collect();
+
+        Self {
+            apps: Arc::new(monitored_apps),
+            identifiers,
+            package_apps,
+            alias_apps,
+            patterns,
+            folders,
+            excluded: self.excluded.clone(),
+            track_child_processes,
+            wait_for_window: self.wait_for_window,
+            adaptive_polling: self.adaptive_polling,
+            suspension: Arc::clone(&self.suspension),
+            trace: Arc::clone(&self.trace),
+            uwp_apps: Arc::clone(&self.uwp_apps),
+        }
+    }
+
+    /// Replace the excluded processes with the given process names
+    ///
+    /// Names are normalized like the process names of Win32 applications, with a
+    /// trailing `.exe` removed.
+    #[must_use]
+    pub fn with_exclusions(mut self, process_names: &[String]) -> Self {
+        self.excluded = process_names
+            .iter()
+            .filter_map(|name| {
+                let name = normalize_process_name(name);
+                let name = name.strip_suffix(".exe").unwrap_or(&name);
+                (!name.is_empty()).then(|| AppIdentifier::Win32(name.to_string()))
+            })
+            .collect();
+        self
+    }
+
+    /// Require a visible window before reporting an application as started
+    #[must_use]
+    pub fn with_wait_for_window(mut self, wait_for_window: bool) -> Self {
+        self.wait_for_window = wait_for_window;
+        self
+    }
+
+    /// Adapt the polling interval to activity, or poll at the monitoring interval with `None`
+    #[must_use]
+    pub fn with_adaptive_polling(mut self, adaptive_polling: Option<AdaptivePolling>) -> Self {
+        self.adaptive_polling = adaptive_polling;
+        self
+    }
+
+    /// Check if an identifier belongs to an enabled application on the watch list
+    pub fn is_monitored(&self, app_id: &AppIdentifier) -> bool {
+        self.identifiers.contains(app_id)
+    }
+
+    /// Check if an identifier belongs to an excluded process
+    pub fn is_excluded(&self, app_id: &AppIdentifier) -> bool {
+        self.excluded.contains(app_id)
+    }
+
+    /// Whether processes need their image path resolved to match watched folders
+    pub fn watches_folders(&self) -> bool {
+        !self.folders.is_empty()
+    }
+
+    /// Insert every watched folder containing an executable into `current`
+    ///
+    /// Returns the identifier of the first folder that matched.
+    pub fn insert_watched_folders(
+        &self,
+        exe_path: &Path,
+        current: &mut HashSet<AppIdentifier>,
+    ) -> Option<AppIdentifier> {
+        let key = canonical_path::lexical_key(exe_path);
+        let mut first = None;
+        for (folder_key, folder_id) in &self.folders {
+            if canonical_path::key_is_inside(&key, folder_key) {
+                current.insert(folder_id.clone());
+                first.get_or_insert_with(|| folder_id.clone());
+            }
+        }
+        first
+    }
+
+    /// Watch list identifier of a process identifier
+    ///
+    /// Returns the identifier itself if it is watched, otherwise the identifier of the
+    /// first application with the process name as an alias or whose match rule accepts it.
+    pub fn watched_identifier(&self, app_id: &AppIdentifier) -> Option<AppIdentifier> {
+        if self.identifiers.contains(app_id) {
+            return Some(app_id.clone());
+        }
+        if let Some(watched) = self.alias_apps.get(app_id).and_then(|apps| apps.first()) {
+            return Some(watched.clone());
+        }
+        match (app_id, &self.patterns) {
+            (AppIdentifier::Win32(process_name), Some(patterns)) => {
+                patterns.matching(process_name).next().cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Insert every watched identifier a process matches into `current`
+    ///
+    /// An application-level UWP identifier matches both entries watching that specific
+    /// application and entries watching the whole package. A package-level identifier
+    /// (process without a known AUMID) falls back to matching every watched application
+    /// from its package. Win32 processes also match the applications listing them as an
+    /// alias, and excluded processes are inserted as well. Returns `true` if any watched
+    /// or excluded identifier matched.
+    pub fn insert_watched(
+        &self,
+        app_id: &AppIdentifier,
+        current: &mut HashSet<AppIdentifier>,
+    ) -> bool {
+        let mut matched = false;
+        if self.identifiers.contains(app_id) || self.excluded.contains(app_id) {
+            current.insert(app_id.clone());
+            matched = true;
+        }
+        if let Some(package_id) = app_id.package_identifier()
+            && self.identifiers.contains(&package_id)
+        {
+            current.insert(package_id);
+            matched = true;
+        }
+        if let Some(watched) = self.package_apps.get(app_id) {
+            current.extend(watched.iter().cloned());
+            matched = true;
+        }
+        if let Some(watched) = self.alias_apps.get(app_id) {
+            current.extend(watched.iter().cloned());
+            matched = true;
+        }
+        if let (AppIdentifier::Win32(process_name), Some(patterns)) = (app_id, &self.patterns) {
+            for watched in patterns.matching(process_name) {
+                current.insert(watched.clone());
+                matched = true;
+            }
+        }
+        matched
+    }
+}
+
+/// What the latest scans found, published by the monitor and loaded by the controller
+#[derive(Clone, Debug, Default)]
+pub struct ScanResults {
+    /// Monitored applications found running by the latest scan
+    ///
+    /// `None` until the first scan completes. Lets the controller reconcile its
+    /// active-session count in case a start or stop event was missed.
+    pub running_apps: Option<HashSet<AppIdentifier>>,
+    /// Watched folders with a running executable that could not be matched because its
+    /// image path is unreadable, usually because it runs elevated, by the executable's
+    /// file name
+    pub elevated_apps: HashMap<AppIdentifier, String>,
+    /// Scan duration statistics
+    pub poll_timings: PollTimings,
+    /// When the latest scan started, before it listed the processes
+    ///
+    /// Applications the scan found running count their activation latency from here.
+    pub scan_started: Option<Instant>,
+}
+
+/// Latest [`WatchState`], published by the controller and loaded by the monitor
+///
+/// Each watch list or preference change publishes a new immutable snapshot. Readers load
+/// the current one without taking a lock, so a scan never holds up a watch list update
+/// and an update never stalls a scan; a scan keeps the snapshot it loaded until it is
+/// done. Every publish bumps [`SharedWatchState::version`]. Scan outputs travel the other
+/// way as [`ScanResults`] snapshots, kept apart from the watch inputs.
+#[derive(Debug)]
+pub struct SharedWatchState {
+    /// Current snapshot
+    current: ArcSwap<WatchState>,
+    /// Serializes publishers so concurrent updates are not lost
+    publish: Mutex<()>,
+    /// Number of snapshots published since creation
+    version: AtomicU64,
+    /// Latest scan results
+    results: ArcSwap<ScanResults>,
+    /// Serializes result publishers, separately from watch state publishers
+    publish_results: Mutex<()>,
+}
+
+impl SharedWatchState {
+    /// Share `state` as the first snapshot
+    pub fn new(state: WatchState) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(state),
+            publish: Mutex::new(()),
+            version: AtomicU64::new(0),
+            results: ArcSwap::from_pointee(ScanResults::default()),
+            publish_results: Mutex::new(()),
+        }
+    }
+
+    /// Current snapshot
+    pub fn load(&self) -> Arc<WatchState> {
+        self.current.load_full()
+    }
+
+    /// Publish a snapshot derived from the current one
+    pub fn update(&self, update: impl FnOnce(&WatchState) -> WatchState) {
+        let _publishing = self.publish.lock();
+        let next = update(&self.current.load());
+        self.current.store(Arc::new(next));
+        self.version.fetch_add(1, Ordering::Release);
+    }
+
+    /// Number of snapshots published since creation
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Latest scan results
+    pub fn results(&self) -> Arc<ScanResults> {
+        self.results.load_full()
+    }
+
+    /// Publish scan results derived from the latest ones
+    pub fn update_results(&self, update: impl FnOnce(&mut ScanResults)) {
+        let _publishing = self.publish_results.lock();
+        let mut next = ScanResults::clone(&self.results.load());
+        update(&mut next);
+        self.results.store(Arc::new(next));
+    }
+}
+
+impl Default for SharedWatchState {
+    fn default() -> Self {
+        Self::new(WatchState::new())
+    }
+}
+
+/// Match rules of the watch list compiled into a single regex set
+///
+/// A process name is tested against every rule in one pass, so scans stay linear in
+/// the number of processes however many rules are configured. Matching processes are
+/// reported under the identifier of the application the rule belongs to.
+#[derive(Clone, Debug)]
+pub struct ProcessNamePatterns {
+    /// Compiled rules, in watch list order
+    set: RegexSet,
+    /// Identifier of the application of each rule in `set`
+    identifiers: Vec<AppIdentifier>,
+}
+
+impl ProcessNamePatterns {
+    /// Compile the match rules of the enabled Win32 applications
+    ///
+    /// Returns `None` if no application has a valid rule. Invalid rules are skipped;
+    /// their applications still match by exact process name.
+    pub fn new(monitored_apps: &[MonitoredApp]) -> Option<Self> {
+        use tracing::warn;
+
+        let mut sources = Vec::new();
+        let mut identifiers = Vec::new();
+        for app in monitored_apps.iter().filter(|app| app.is_enabled()) {
+            let MonitoredApp::Win32(win32_app) = app else {
+                continue;
+            };
+            let Some(rule) = &win32_app.match_rule else {
+                continue;
+            };
+            if let Err(e) = rule.validate() {
+                warn!("Skipping match rule of '{}': {}", win32_app.display_name, e);
+                continue;
+            }
+            sources.push(rule.regex_source());
+            identifiers.push(AppIdentifier::from_monitored_app(app));
+        }
+
+        if sources.is_empty() {
+            return None;
+        }
+
+        match RegexSetBuilder::new(sources).case_insensitive(true).build() {
+            Ok(set) => Some(Self { set, identifiers }),
+            Err(e) => {
+                warn!("Failed to compile match rules: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Identifiers of the applications whose rules match a normalized process name
+    pub fn matching<'a>(&'a self, process_name: &str) -> impl Iterator<Item = &'a AppIdentifier> {
+        self.set
+            .matches(process_name)
+            .into_iter()
+            .map(|index| &self.identifiers[index])
+    }
+}
+
+/// Process seen by the previous scan, with the identifier determined for it
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    all(not(windows), not(test)),
+    expect(
+        dead_code,
+        reason = "Struct used only on Windows for incremental process scans"
+    )
+)]
+struct KnownProcess {
+    /// Executable file name reported by the snapshot
+    exe_file: Option<String>,
+    /// Parent process ID reported by the snapshot
+    parent_pid: u32,
+    /// Identifier determined when the process first appeared
+    app_id: Option<AppIdentifier>,
+    /// Image path, resolved the first time a folder is watched while the process runs
+    ///
+    /// `Some(None)` if the path could not be queried, e.g. for protected processes.
+    #[expect(
+        clippy::option_option,
+        reason = "Distinguishes a path not resolved yet from one that could not be queried"
+    )]
+    exe_path: Option<Option<PathBuf>>,
+    /// Watched folder containing the executable, found when the path could not be queried
+    suspected_folder: Option<AppIdentifier>,
+    /// Creation time, queried while child processes are tracked
+    created: Option<u64>,
+}
+
+impl KnownProcess {
+    /// Whether a snapshot entry with this PID is still the same process
+    ///
+    /// A PID reused between two scans almost always comes with a different executable
+    /// or parent, so the new process is identified from scratch.
+    #[cfg_attr(
+        all(not(windows), not(test)),
+        expect(
+            dead_code,
+            reason = "Function used only on Windows for incremental process scans"
+        )
+    )]
+    fn is_same_process(&self, exe_file: Option<&str>, parent_pid: u32) -> bool {
+        self.parent_pid == parent_pid && self.exe_file.as_deref() == exe_file
+    }
+}
+
+/// Number of recent scans kept for [`PollTimings`] statistics
+const POLL_TIMING_WINDOW: usize = 30;
+
+/// Longest monitoring interval the settings allow
+const MAX_MONITORING_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Granularity of suggested monitoring intervals
+const INTERVAL_SUGGESTION_STEP_MS: u64 = 250;
+
+/// Rolling duration statistics for the scan/diff/match pipeline
+///
+/// Each poll records how long the process snapshot, change detection and app matching
+/// took. A scan that takes longer than the polling interval means the interval is too
+/// aggressive for the machine; [`suggested_interval`](Self::suggested_interval) then
+/// proposes a longer one.
+#[derive(Debug, Clone, Default)]
+pub struct PollTimings {
+    /// Most recent scan durations, oldest first
+    samples: VecDeque<Duration>,
+    /// Polling interval the scans were measured against
+    interval: Duration,
+    /// Whether the most recent scan exceeded the interval
+    over_budget: bool,
+    /// Total number of scans that exceeded the interval
+    over_budget_scans: u64,
+}
+
+impl PollTimings {
+    /// Record the duration of a scan
+    ///
+    /// Returns `true` if this scan exceeded `interval` while the previous one did not,
+    /// so callers can warn once per slow streak instead of on every poll.
+    pub fn record(&mut self, scan: Duration, interval: Duration) -> bool {
+        if self.samples.len() == POLL_TIMING_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(scan);
+        self.interval = interval;
+
+        let was_over_budget = self.over_budget;
+        self.over_budget = scan > interval;
+        if self.over_budget {
+            self.over_budget_scans += 1;
+        }
+        self.over_budget && !was_over_budget
+    }
+
+    /// Duration of the most recent scan
+    pub fn last(&self) -> Option<Duration> {
+        self.samples.back().copied()
+    }
+
+    /// Average duration of the recent scans
+    pub fn average(&self) -> Option<Duration> {
+        let count = u32::try_from(self.samples.len()).ok().filter(|&n| n > 0)?;
+        Some(self.samples.iter().sum::<Duration>() / count)
+    }
+
+    /// Total number of scans that took longer than the polling interval
+    pub fn over_budget_scans(&self) -> u64 {
+        self.over_budget_scans
+    }
+
+    /// Suggest a longer polling interval if recent scans exceeded the current one
+    ///
+    /// The suggestion leaves room for twice the slowest recent scan, rounded up to
+    /// 250ms and capped at the longest interval the settings allow. Returns `None`
+    /// if all recent scans fit or no longer interval is available.
+    pub fn suggested_interval(&self) -> Option<Duration> {
+        let peak = self.samples.iter().max()?;
+        if *peak <= self.interval {
+            return None;
+        }
+
+        let wanted_ms = u64::try_from((*peak * 2).as_millis()).unwrap_or(u64::MAX);
+        let rounded_ms = wanted_ms
+            .div_ceil(INTERVAL_SUGGESTION_STEP_MS)
+            .saturating_mul(INTERVAL_SUGGESTION_STEP_MS);
+        let suggested = Duration::from_millis(rounded_ms).min(MAX_MONITORING_INTERVAL);
+
+        (suggested > self.interval).then_some(suggested)
+    }
+}
+
+/// How long a process trace started from the GUI runs
+pub const PROCESS_TRACE_DURATION: Duration = Duration::from_mins(2);
+
+/// Time-boxed trace mode for the process monitor
+///
+/// While active, every poll logs each process considered and why it did or didn't
+/// match the watch list, so "my game is never detected" reports can be debugged from a
+/// regular build's log file. The trace ends on its own so it cannot flood the log.
+#[derive(Debug, Default)]
+pub struct ProcessTrace {
+    /// When the current trace ends, if one is running
+    until: Mutex<Option<Instant>>,
+}
+
+impl ProcessTrace {
+    /// Start tracing for `duration`, extending a trace that is already running
+    pub fn start(&self, duration: Duration) {
+        *self.until.lock() = Some(Instant::now() + duration);
+    }
+
+    /// Stop tracing immediately
+    pub fn stop(&self) {
+        *self.until.lock() = None;
+    }
+
+    /// Whether a trace is currently running
+    pub fn is_active(&self) -> bool {
+        self.until
+            .lock()
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Clear an expired trace
+    ///
+    /// Returns `true` exactly once per trace, on the first call after it ran out.
+    pub fn finish_if_expired(&self) -> bool {
+        let mut until = self.until.lock();
+        if until.is_some_and(|until| Instant::now() >= until) {
+            *until = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// Describe why a process did or didn't match the watch list for trace output
+///
+/// `app_id` is the identifier determined for the process (if any) and `matched` whether
+/// it matched an entry. For misses, a watched entry with a similar name is mentioned,
+/// since a slightly different executable name is the most common cause.
+#[cfg_attr(
+    all(not(windows), not(test)),
+    expect(
+        dead_code,
+        reason = "Function used only on Windows for process tracing"
+    )
+)]
+fn describe_match(
+    app_id: Option<&AppIdentifier>,
+    matched: bool,
+    identifiers: &HashSet<AppIdentifier>,
+) -> String {
+    let Some(app_id) = app_id else {
+        return "not matched: could not determine an identifier for the process".to_string();
+    };
+
+    if matched {
+        return format!("matched watch list as {app_id}");
+    }
+    if identifiers.is_empty() {
+        return format!("not matched: {app_id} (watch list is empty)");
+    }
+
+    let similar = identifiers.iter().find(|watched| match (app_id, watched) {
+        (AppIdentifier::Win32(name), AppIdentifier::Win32(watched_name)) => {
+            name.contains(watched_name.as_str()) || watched_name.contains(name.as_str())
+        }
+        (AppIdentifier::UwpApplication(_), AppIdentifier::UwpApplication(_)) => {
+            watched.package_identifier() == app_id.package_identifier()
+        }
+        _ => false,
+    });
+
+    match similar {
+        Some(AppIdentifier::Win32(watched_name)) => format!(
+            "not matched: {app_id} is not on the watch list (similar entry '{watched_name}'; executable names must match exactly)"
+        ),
+        Some(watched) => format!(
+            "not matched: {app_id} is not on the watch list (only {watched} from the same package is watched)"
+        ),
+        None => format!("not matched: {app_id} is not on the watch list"),
+    }
+}
+
+/// Suspension flag for the process monitor thread
+///
+/// While suspended the monitor thread blocks instead of polling, so it causes no
+/// wakeups at all. Resuming wakes it immediately.
+#[derive(Debug, Default)]
+pub struct PollingSuspension {
+    /// Whether polling is currently suspended
+    suspended: Mutex<bool>,
+    /// Signalled when polling resumes
+    resumed: Condvar,
+}
+
+impl PollingSuspension {
+    /// Suspend or resume polling
+    ///
+    /// Returns `true` if the state changed.
+    pub fn set_suspended(&self, suspended: bool) -> bool {
+        let mut current = self.suspended.lock();
+        if *current == suspended {
+            return false;
+        }
+
+        *current = suspended;
+        if !suspended {
+            self.resumed.notify_all();
+        }
+        true
+    }
+
+    /// Whether polling is currently suspended
+    pub fn is_suspended(&self) -> bool {
+        *self.suspended.lock()
+    }
+
+    /// Block the calling thread until polling is resumed
+    ///
+    /// Returns immediately if polling is not suspended.
+    pub fn wait_until_resumed(&self) {
+        let mut suspended = self.suspended.lock();
+        while *suspended {
+            self.resumed.wait(&mut suspended);
         }
     }
 }
@@ -94,17 +876,16 @@ impl WatchState {
 ///
 /// Matches processes by executable filename only (without path or extension).
 pub struct ProcessMonitor {
-    /// Combined watch list and identifier cache for atomic state updates.
+    /// Combined watch list and identifier cache, published by `AppController`
     ///
-    /// Prevents race conditions by updating both caches simultaneously. Shared with
-    /// `AppController` via `Arc` for coordinated updates.
-    watch_state: Arc<RwLock<WatchState>>,
-    /// PID → (`AppIdentifier`, timestamp) cache; expires after 5s to handle PID reuse
+    /// Each scan loads the latest snapshot, so both caches are always seen together.
+    watch_state: Arc<SharedWatchState>,
+    /// Processes seen by the previous scan by PID; exited processes drop out each scan
     #[cfg_attr(
         all(not(windows), not(test)),
         expect(dead_code, reason = "Field used only on Windows for process detection")
     )]
-    app_id_cache: HashMap<u32, (AppIdentifier, Instant)>,
+    known_processes: HashMap<u32, KnownProcess>,
     #[cfg_attr(
         all(not(windows), not(test)),
         expect(
@@ -114,6 +895,8 @@ pub struct ProcessMonitor {
     )]
     event_sender: mpsc::SyncSender<ProcessEvent>,
     interval: Duration,
+    /// Wait before each poll, shortened after activity with adaptive polling
+    schedule: PollSchedule,
     /// Previous snapshot for change detection
     #[cfg_attr(
         all(not(windows), not(test)),
@@ -128,6 +911,30 @@ pub struct ProcessMonitor {
         )
     )]
     estimated_process_count: usize,
+    /// Descendants of monitored processes (launcher → game child tracking)
+    #[cfg_attr(
+        all(not(windows), not(test)),
+        expect(
+            dead_code,
+            reason = "Field used only on Windows for child process tracking"
+        )
+    )]
+    process_tree: ProcessTree,
+    /// When the current scan started, published with its results
+    #[cfg_attr(
+        all(not(windows), not(test)),
+        expect(dead_code, reason = "Field used only on Windows for detection latency")
+    )]
+    scan_started: Instant,
+    /// Executables in watched folders, for processes that cannot be opened
+    #[cfg_attr(
+        all(not(windows), not(test)),
+        expect(
+            dead_code,
+            reason = "Field used only on Windows for inaccessible processes"
+        )
+    )]
+    folder_index: elevated::FolderIndex,
     /// Poll cycles completed (test/diagnostic counter)
     poll_cycle_count: Arc<AtomicU64>,
 }
@@ -139,42 +946,31 @@ impl ProcessMonitor {
         const DEFAULT_PROCESS_COUNT: usize = 200;
 
         Self {
-            watch_state: Arc::new(RwLock::new(WatchState::new())),
-            app_id_cache: HashMap::with_capacity(DEFAULT_PROCESS_COUNT),
+            watch_state: Arc::new(SharedWatchState::default()),
+            known_processes: HashMap::with_capacity(DEFAULT_PROCESS_COUNT),
             event_sender,
             interval,
+            schedule: PollSchedule::new(interval, Instant::now()),
             running_processes: HashSet::with_capacity(DEFAULT_PROCESS_COUNT),
             estimated_process_count: DEFAULT_PROCESS_COUNT,
+            process_tree: ProcessTree::new(),
+            scan_started: Instant::now(),
+            folder_index: elevated::FolderIndex::default(),
             poll_cycle_count: Arc::new(AtomicU64::new(0)),
         }
     }
 
     /// Update the list of monitored applications to watch.
     ///
-    /// Only enabled applications should be passed. Performs atomic update of both app list
+    /// Only enabled applications are matched. Performs atomic update of both app list
     /// and identifier cache to prevent race conditions.
     pub fn update_watch_list(&self, monitored_apps: Vec<MonitoredApp>) {
-        let identifiers: HashSet<AppIdentifier> = monitored_apps
-            .iter()
-            .map(|app| match app {
-                MonitoredApp::Win32(win32_app) => {
-                    AppIdentifier::Win32(win32_app.process_name.to_lowercase())
-                }
-                MonitoredApp::Uwp(uwp_app) => {
-                    AppIdentifier::Uwp(uwp_app.package_family_name.clone())
-                }
-            })
-            .collect();
-
-        let mut state = self.watch_state.write();
-        *state = WatchState {
-            apps: Arc::new(monitored_apps),
-            identifiers,
-        };
+        self.watch_state
+            .update(|state| state.with_apps(monitored_apps, state.track_child_processes));
     }
 
     /// Get a reference to the watch state for external updates.
-    pub fn get_watch_state_ref(&self) -> Arc<RwLock<WatchState>> {
+    pub fn get_watch_state_ref(&self) -> Arc<SharedWatchState> {
         Arc::clone(&self.watch_state)
     }
 
@@ -200,14 +996,67 @@ impl ProcessMonitor {
 
     /// Start the monitoring thread
     pub fn start(mut self) -> JoinHandle<()> {
-        thread::spawn(move || {
+        thread::Builder::new()
+            .name("process-monitor".to_string())
+            // Restarted by the watchdog after a panic, keeping the processes already seen
+            .spawn(move || watchdog::supervise("process-monitor", || {
+            let mut previous_wait = self.interval;
             loop {
+                let (suspension, adaptive_polling) = {
+                    let state = self.watch_state.load();
+                    (Arc::clone(&state.suspension), state.adaptive_polling)
+                };
+                if suspension.is_suspended() {
+                    tracing::info!("Process polling suspended while displays are off");
+                    suspension.wait_until_resumed();
+                    tracing::info!("Process polling resumed");
+                    self.schedule.note_activity(Instant::now());
+                }
+
+                let scan_start = Instant::now();
+                self.scan_started = scan_start;
                 if let Err(e) = self.poll_processes() {
                     tracing::error!("Error polling processes: {}", e);
                 }
-                thread::sleep(self.interval);
+                let scan_time = scan_start.elapsed();
+
+                let interval = self.interval;
+                self.watch_state.update_results(|results| {
+                    let timings = &mut results.poll_timings;
+                    if timings.record(scan_time, interval) {
+                        if let Some(suggested) = timings.suggested_interval() {
+                            tracing::warn!(
+                                    "Process scan took {:?}, longer than the {:?} monitoring interval; consider increasing the interval to {}ms",
+                                    scan_time,
+                                    interval,
+                                    suggested.as_millis()
+                                );
+                        } else {
+                            tracing::warn!(
+                                    "Process scan took {:?}, longer than the {:?} monitoring interval",
+                                    scan_time,
+                                    interval
+                                );
+                        }
+                    }
+                });
+
+                let now = Instant::now();
+                self.schedule
+                    .observe_watch_version(self.watch_state.version(), now);
+                if adaptive_polling.is_some() {
+                    self.schedule
+                        .observe_foreground(top_level_windows::foreground_window_pid(), now);
+                }
+                let wait = self.schedule.next_interval(now, adaptive_polling);
+                if wait != previous_wait {
+                    tracing::debug!("Process polling interval now {:?}", wait);
+                    previous_wait = wait;
+                }
+                thread::sleep(wait);
             }
-        })
+        }))
+        .expect("Failed to spawn the process monitor thread")
     }
 
     /// Poll processes and detect changes.
@@ -237,12 +1086,7 @@ impl ProcessMonitor {
     fn poll_processes(&mut self) -> Result<()> {
         #[cfg(windows)]
         {
-            use tracing::{debug, warn};
-
-            const CACHE_EXPIRY: Duration = Duration::from_secs(5);
-            let now = Instant::now();
-            self.app_id_cache
-                .retain(|_pid, (_app_id, last_seen)| now.duration_since(*last_seen) < CACHE_EXPIRY);
+            use tracing::{debug, info, warn};
 
             let mut cache_hits = 0usize;
             let mut cache_misses = 0usize;
@@ -263,6 +1107,26 @@ impl ProcessMonitor {
                 .max(self.estimated_process_count);
             let mut current_processes = HashSet::with_capacity(capacity);
 
+            // One snapshot for the whole scan; watch list updates apply to the next one
+            let state = self.watch_state.load();
+            let track_child_processes = state.track_child_processes;
+            // UWP apps are reported by the app diagnostics watcher while it runs
+            let uwp_watcher_active = state.uwp_apps.is_active();
+            let watches_folders = state.watches_folders();
+
+            if state.trace.finish_if_expired() {
+                info!("Process trace finished");
+            }
+            let trace_active = state.trace.is_active();
+            if trace_active {
+                info!("Process trace: watch list {:?}", state.identifiers);
+            }
+            let mut process_entries = Vec::with_capacity(capacity);
+            let mut root_processes = HashMap::new();
+            let mut previous_processes = std::mem::take(&mut self.known_processes);
+            let mut known_processes = HashMap::with_capacity(capacity);
+            let mut inaccessible = Vec::new();
+
             #[expect(
                 clippy::cast_possible_truncation,
                 reason = "size_of::<PROCESSENTRY32W>() is a compile-time constant (592 bytes) that fits in u32"
@@ -276,82 +1140,92 @@ impl ProcessMonitor {
 
             while has_process {
                 let pid = entry.th32ProcessID;
+                let parent_pid = entry.th32ParentProcessID;
+                let exe_file = extract_process_name(&entry.szExeFile);
+
+                // Only processes that appeared since the previous scan are opened
+                let (app_id, mut exe_path, suspected_folder, mut created) =
+                    match previous_processes.remove(&pid) {
+                        Some(known) if known.is_same_process(exe_file.as_deref(), parent_pid) => {
+                            cache_hits += 1;
+                            (
+                                known.app_id,
+                                known.exe_path,
+                                known.suspected_folder,
+                                known.created,
+                            )
+                        }
+                        _ => {
+                            cache_misses += 1;
+                            (identify_process(pid, &entry.szExeFile), None, None, None)
+                        }
+                    };
 
-                let handle_result =
-                    unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) };
-
-                if let Some((cached_app_id, _)) = self.app_id_cache.get(&pid) {
-                    cache_hits += 1;
-                    if self.watch_state.read().identifiers.contains(cached_app_id) {
-                        current_processes.insert(cached_app_id.clone());
-                        self.app_id_cache.insert(pid, (cached_app_id.clone(), now));
+                if track_child_processes && let Some(name) = &exe_file {
+                    if created.is_none() {
+                        created = query_creation_time(pid);
                     }
-                } else {
-                    cache_misses += 1;
-
-                    match handle_result {
-                        Ok(handle) => {
-                            let _guard = ProcessHandleGuard(handle);
-
-                            match unsafe { crate::uwp::detect_uwp_process(handle) } {
-                                Ok(Some(family_name)) => {
-                                    let app_id = AppIdentifier::Uwp(family_name);
-
-                                    self.app_id_cache.insert(pid, (app_id.clone(), now));
-
-                                    if self.watch_state.read().identifiers.contains(&app_id) {
-                                        debug!(
-                                            "Found monitored UWP process (PID {}): {}",
-                                            pid, app_id
-                                        );
-                                        current_processes.insert(app_id);
-                                    }
-                                }
-                                Ok(None) => {
-                                    if let Some(app_id) =
-                                        extract_win32_app_identifier(&entry.szExeFile, pid)
-                                    {
-                                        self.app_id_cache.insert(pid, (app_id.clone(), now));
-
-                                        if self.watch_state.read().identifiers.contains(&app_id) {
-                                            current_processes.insert(app_id);
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!(
-                                        "Failed to detect UWP package for process ID {}: {:#}",
-                                        pid, e
-                                    );
-
-                                    if let Some(app_id) =
-                                        extract_win32_app_identifier(&entry.szExeFile, pid)
-                                    {
-                                        self.app_id_cache.insert(pid, (app_id.clone(), now));
-
-                                        if self.watch_state.read().identifiers.contains(&app_id) {
-                                            current_processes.insert(app_id);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            debug!("Failed to open process handle for PID {}: {}", pid, e);
+                    process_entries.push(ProcessEntry {
+                        pid,
+                        parent_pid,
+                        exe_name: extract_filename_without_extension(name),
+                        created,
+                    });
+                }
+                if watches_folders && exe_path.is_none() {
+                    let path = query_image_path(pid);
+                    if path.is_none() {
+                        // Looked up through WMI after the scan, in one query for all
+                        inaccessible.push(pid);
+                    }
+                    exe_path = Some(path);
+                }
 
-                            if let Some(app_id) =
-                                extract_win32_app_identifier(&entry.szExeFile, pid)
-                            {
-                                self.app_id_cache.insert(pid, (app_id.clone(), now));
+                if let Some(app_id) = &app_id
+                    && !(uwp_watcher_active && app_id.is_uwp())
+                    && state.insert_watched(app_id, &mut current_processes)
+                {
+                    debug!("Found monitored process (PID {}): {}", pid, app_id);
+                    root_processes.insert(pid, app_id.clone());
+                }
+                if watches_folders
+                    && let Some(Some(path)) = &exe_path
+                    && let Some(folder_id) =
+                        state.insert_watched_folders(path, &mut current_processes)
+                {
+                    debug!(
+                        "Found process in monitored folder (PID {}): {:?}",
+                        pid, path
+                    );
+                    root_processes.entry(pid).or_insert(folder_id);
+                }
 
-                                if self.watch_state.read().identifiers.contains(&app_id) {
-                                    current_processes.insert(app_id);
-                                }
-                            }
-                        }
-                    }
+                if trace_active {
+                    let outcome = describe_match(
+                        app_id.as_ref(),
+                        root_processes.contains_key(&pid),
+                        &state.identifiers,
+                    );
+                    info!(
+                        "Process trace: PID {} ({}): {}",
+                        pid,
+                        exe_file.as_deref().unwrap_or_default(),
+                        outcome
+                    );
                 }
 
+                known_processes.insert(
+                    pid,
+                    KnownProcess {
+                        exe_file,
+                        parent_pid,
+                        app_id,
+                        exe_path,
+                        suspected_folder,
+                        created,
+                    },
+                );
+
                 has_process = unsafe {
                     match Process32NextW(snapshot, &raw mut entry) {
                         Ok(()) => true,
@@ -367,6 +1241,65 @@ impl ProcessMonitor {
                 };
             }
 
+            if !inaccessible.is_empty() {
+                self.folder_index.invalidate_on_change(&state.apps);
+                Self::resolve_inaccessible(
+                    &inaccessible,
+                    &state,
+                    &mut self.folder_index,
+                    &mut known_processes,
+                    &mut root_processes,
+                    &mut current_processes,
+                );
+            }
+            let elevated_apps = known_processes
+                .values()
+                .filter_map(|known| {
+                    let folder = known
+                        .suspected_folder
+                        .as_ref()
+                        .filter(|folder| state.identifiers.contains(*folder))?;
+                    Some((folder.clone(), known.exe_file.clone()?))
+                })
+                .collect();
+            self.watch_state
+                .update_results(|results| results.elevated_apps = elevated_apps);
+
+            // Processes left over have exited, so their PIDs may be reused
+            self.known_processes = known_processes;
+
+            if track_child_processes {
+                // Descendants keep their root application running, even after the
+                // launcher itself has exited
+                let descendants = self.process_tree.update(&process_entries, &root_processes);
+                for app_id in descendants {
+                    if trace_active {
+                        info!("Process trace: {} kept running by a child process", app_id);
+                    }
+                    state.insert_watched(&app_id, &mut current_processes);
+                }
+            } else if !self.process_tree.is_empty() {
+                self.process_tree.clear();
+            }
+
+            // Only looks for windows while an application is about to be reported
+            if state.wait_for_window
+                && current_processes
+                    .iter()
+                    .any(|app_id| !self.running_processes.contains(app_id))
+            {
+                let windowed = self.windowed_apps(&state);
+                for app_id in top_level_windows::hold_until_windowed(
+                    &mut current_processes,
+                    &self.running_processes,
+                    &windowed,
+                    &state,
+                ) {
+                    debug!("{} is running but shows no window yet", app_id);
+                }
+            }
+            drop(state);
+
             debug!("Found {} running processes", current_processes.len());
 
             let total_lookups = cache_hits + cache_misses;
@@ -397,6 +1330,89 @@ impl ProcessMonitor {
         }
     }
 
+    /// Applications with a process that shows a visible top-level window
+    ///
+    /// Covers processes matched by name or folder as well as child processes attributed
+    /// to an application.
+    #[cfg(windows)]
+    fn windowed_apps(&self, state: &WatchState) -> HashSet<AppIdentifier> {
+        let mut windowed = HashSet::new();
+        for pid in top_level_windows::visible_window_pids() {
+            if let Some(known) = self.known_processes.get(&pid) {
+                if let Some(app_id) = &known.app_id {
+                    state.insert_watched(app_id, &mut windowed);
+                }
+                if let Some(Some(path)) = &known.exe_path {
+                    state.insert_watched_folders(path, &mut windowed);
+                }
+            }
+            if let Some(app_id) = self.process_tree.app_id(pid) {
+                windowed.insert(app_id.clone());
+            }
+        }
+        windowed
+    }
+
+    /// Match processes whose image path could not be queried against watched folders
+    ///
+    /// Asks WMI for their paths in one query. Processes it cannot resolve either are
+    /// attributed to the first watched folder containing their executable according to
+    /// `folder_index`, so the user can be told why the folder does not match.
+    #[cfg(windows)]
+    fn resolve_inaccessible(
+        pids: &[u32],
+        state: &WatchState,
+        folder_index: &mut elevated::FolderIndex,
+        known_processes: &mut HashMap<u32, KnownProcess>,
+        root_processes: &mut HashMap<u32, AppIdentifier>,
+        current: &mut HashSet<AppIdentifier>,
+    ) {
+        use tracing::debug;
+
+        let paths = elevated::query_image_paths(pids);
+        for pid in pids {
+            let Some(known) = known_processes.get_mut(pid) else {
+                continue;
+            };
+            if let Some(path) = paths.get(pid) {
+                if let Some(folder_id) = state.insert_watched_folders(path, current) {
+                    debug!(
+                        "Found process in monitored folder through WMI (PID {}): {:?}",
+                        pid, path
+                    );
+                    root_processes.entry(*pid).or_insert(folder_id);
+                }
+                known.exe_path = Some(Some(path.clone()));
+                continue;
+            }
+
+            // Processes matched by name need no path; names without `.exe` are system
+            // processes such as `System` or `Registry`
+            let Some(exe_file) = known.exe_file.as_deref() else {
+                continue;
+            };
+            if root_processes.contains_key(pid) || !exe_file.to_ascii_lowercase().ends_with(".exe")
+            {
+                continue;
+            }
+            known.suspected_folder = state
+                .folders
+                .iter()
+                .map(|(_, folder_id)| folder_id)
+                .find(|folder_id| {
+                    matches!(folder_id, AppIdentifier::Folder(folder)
+                        if folder_index.contains(Path::new(folder), exe_file))
+                })
+                .cloned();
+            if let Some(folder_id) = &known.suspected_folder {
+                debug!(
+                    "PID {} ({}) cannot be opened and is probably in monitored folder {}",
+                    pid, exe_file, folder_id
+                );
+            }
+        }
+    }
+
     /// Detect changes between current and previous snapshots.
     #[cfg_attr(
         all(not(windows), not(test)),
@@ -408,40 +1424,78 @@ impl ProcessMonitor {
     fn detect_changes(&mut self, current: HashSet<AppIdentifier>) {
         use tracing::info;
 
-        let apps = {
-            let state = self.watch_state.read();
-            Arc::clone(&state.apps)
+        // Events are sent after dropping the snapshot, so a full channel doesn't keep an
+        // outdated watch list alive
+        let (started, stopped, uwp_events): (Vec<_>, Vec<_>, _) = {
+            let state = self.watch_state.load();
+            let reported =
+                |app_id: &&AppIdentifier| state.is_monitored(app_id) || state.is_excluded(app_id);
+            let started = current
+                .iter()
+                .filter(reported)
+                .filter(|app_id| !self.running_processes.contains(*app_id))
+                .cloned()
+                .collect();
+            let stopped = self
+                .running_processes
+                .difference(&current)
+                .filter(reported)
+                .cloned()
+                .collect();
+            // Excluded processes are not monitored applications, even if also watched
+            let mut running: HashSet<AppIdentifier> = current
+                .iter()
+                .filter(|app_id| state.is_monitored(app_id) && !state.is_excluded(app_id))
+                .cloned()
+                .collect();
+
+            // Watch list changes can start or stop UWP apps the watcher already reported
+            let uwp_events = state.uwp_apps.update(&state);
+            running.extend(state.uwp_apps.watched());
+            let scan_started = self.scan_started;
+            self.watch_state.update_results(|results| {
+                results.running_apps = Some(running);
+                results.scan_started = Some(scan_started);
+            });
+            (started, stopped, uwp_events)
         };
+        if !(started.is_empty() && stopped.is_empty() && uwp_events.is_empty()) {
+            self.schedule.note_activity(Instant::now());
+        }
+
+        for event in uwp_events {
+            info!("UWP app watcher reported: {:?}", event);
+            if let Err(e) = self.event_sender.send(event) {
+                use tracing::error;
+                error!("Failed to send UWP app watcher event: {}", e);
+            }
+        }
 
-        for app_id in current.difference(&self.running_processes) {
-            if Self::is_monitored(app_id, &apps) {
-                info!("Detected process started: {:?}", app_id);
-                if let Err(e) = self
-                    .event_sender
-                    .send(ProcessEvent::Started(app_id.clone()))
-                {
-                    use tracing::error;
-                    error!(
-                        "Failed to send ProcessEvent::Started for '{:?}': {}",
-                        app_id, e
-                    );
-                }
+        for app_id in started {
+            info!("Detected process started: {:?}", app_id);
+            if let Err(e) = self
+                .event_sender
+                .send(ProcessEvent::Started(app_id.clone()))
+            {
+                use tracing::error;
+                error!(
+                    "Failed to send ProcessEvent::Started for '{:?}': {}",
+                    app_id, e
+                );
             }
         }
 
-        for app_id in self.running_processes.difference(&current) {
-            if Self::is_monitored(app_id, &apps) {
-                info!("Detected process stopped: {:?}", app_id);
-                if let Err(e) = self
-                    .event_sender
-                    .send(ProcessEvent::Stopped(app_id.clone()))
-                {
-                    use tracing::error;
-                    error!(
-                        "Failed to send ProcessEvent::Stopped for '{:?}': {}",
-                        app_id, e
-                    );
-                }
+        for app_id in stopped {
+            info!("Detected process stopped: {:?}", app_id);
+            if let Err(e) = self
+                .event_sender
+                .send(ProcessEvent::Stopped(app_id.clone()))
+            {
+                use tracing::error;
+                error!(
+                    "Failed to send ProcessEvent::Stopped for '{:?}': {}",
+                    app_id, e
+                );
             }
         }
 
@@ -449,33 +1503,6 @@ impl ProcessMonitor {
 
         self.running_processes = current;
     }
-
-    /// Check if an app identifier is monitored.
-    #[cfg_attr(
-        all(not(windows), not(test)),
-        expect(
-            dead_code,
-            reason = "Function used only on Windows for process monitoring"
-        )
-    )]
-    fn is_monitored(app_id: &AppIdentifier, watch_list: &[MonitoredApp]) -> bool {
-        match app_id {
-            AppIdentifier::Win32(process_name) => watch_list.iter().any(|app| {
-                if let MonitoredApp::Win32(win32_app) = app {
-                    win32_app.enabled && win32_app.process_name.eq_ignore_ascii_case(process_name)
-                } else {
-                    false
-                }
-            }),
-            AppIdentifier::Uwp(package_family_name) => watch_list.iter().any(|app| {
-                if let MonitoredApp::Uwp(uwp_app) = app {
-                    uwp_app.enabled && uwp_app.package_family_name == *package_family_name
-                } else {
-                    false
-                }
-            }),
-        }
-    }
 }
 
 /// RAII guard for Windows snapshot handle.
@@ -518,6 +1545,133 @@ impl Drop for ProcessHandleGuard {
     }
 }
 
+/// Determine the identifier of a process that was not seen by the previous scan
+///
+/// Packaged processes are identified by their AUMID (or package family name when the
+/// AUMID is unavailable); everything else, including processes that cannot be opened,
+/// by executable name.
+///
+/// # Safety
+///
+/// The process handle is checked before use and closed by `ProcessHandleGuard` (RAII).
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for OpenProcess and UWP package queries"
+)]
+fn identify_process(pid: u32, sz_exe_file: &[u16; 260]) -> Option<AppIdentifier> {
+    use tracing::{debug, warn};
+
+    let handle = match unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) } {
+        Ok(handle) => handle,
+        Err(e) => {
+            debug!("Failed to open process handle for PID {}: {}", pid, e);
+            return extract_win32_app_identifier(sz_exe_file, pid);
+        }
+    };
+    let _guard = ProcessHandleGuard(handle);
+
+    match unsafe { crate::uwp::detect_uwp_process(handle) } {
+        Ok(Some(family_name)) => {
+            // Prefer the application identity when available so multi-app packages can
+            // be matched per application
+            match unsafe { crate::uwp::detect_uwp_application_user_model_id(handle) } {
+                Ok(Some(aumid)) => Some(AppIdentifier::UwpApplication(aumid)),
+                Ok(None) => Some(AppIdentifier::Uwp(family_name)),
+                Err(e) => {
+                    debug!("Failed to get AUMID for process ID {}: {:#}", pid, e);
+                    Some(AppIdentifier::Uwp(family_name))
+                }
+            }
+        }
+        Ok(None) => extract_win32_app_identifier(sz_exe_file, pid),
+        Err(e) => {
+            warn!(
+                "Failed to detect UWP package for process ID {}: {:#}",
+                pid, e
+            );
+            extract_win32_app_identifier(sz_exe_file, pid)
+        }
+    }
+}
+
+/// Query the full image path of a process
+///
+/// Returns `None` if the process cannot be opened or has already exited.
+///
+/// # Safety
+///
+/// The process handle is checked before use and closed by `ProcessHandleGuard` (RAII).
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for OpenProcess and QueryFullProcessImageNameW"
+)]
+fn query_image_path(pid: u32) -> Option<PathBuf> {
+    use windows::Win32::System::Threading::{PROCESS_NAME_WIN32, QueryFullProcessImageNameW};
+    use windows::core::PWSTR;
+
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+    let _guard = ProcessHandleGuard(handle);
+
+    let mut buffer = [0u16; 1024];
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "The buffer length is a small compile-time constant"
+    )]
+    let mut len = buffer.len() as u32;
+    unsafe {
+        QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &raw mut len,
+        )
+    }
+    .ok()?;
+
+    Some(PathBuf::from(String::from_utf16_lossy(
+        &buffer[..len as usize],
+    )))
+}
+
+/// Query the creation time of a process in 100 ns ticks since 1601
+///
+/// Returns `None` if the process cannot be opened or has already exited.
+///
+/// # Safety
+///
+/// The process handle is checked before use and closed by `ProcessHandleGuard` (RAII).
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for OpenProcess and GetProcessTimes"
+)]
+fn query_creation_time(pid: u32) -> Option<u64> {
+    use windows::Win32::Foundation::FILETIME;
+    use windows::Win32::System::Threading::GetProcessTimes;
+
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+    let _guard = ProcessHandleGuard(handle);
+
+    let mut created = FILETIME::default();
+    let mut exited = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+    unsafe {
+        GetProcessTimes(
+            handle,
+            &raw mut created,
+            &raw mut exited,
+            &raw mut kernel,
+            &raw mut user,
+        )
+    }
+    .ok()?;
+
+    Some((u64::from(created.dwHighDateTime) << 32) | u64::from(created.dwLowDateTime))
+}
+
 /// Helper to extract Win32 app identifier from process entry.
 ///
 /// Returns `None` if the process name cannot be extracted (invalid UTF-16, etc.).
@@ -543,7 +1697,7 @@ fn extract_process_name(sz_exe_file: &[u16; 260]) -> Option<String> {
     String::from_utf16(&sz_exe_file[..len]).ok()
 }
 
-/// Extract filename without extension and convert to lowercase.
+/// Extract filename without extension and normalize it for matching.
 #[cfg_attr(
     all(not(windows), not(test)),
     expect(
@@ -557,17 +1711,15 @@ fn extract_filename_without_extension(path: &str) -> String {
     // but this function must parse Windows-style paths on any host.
     let filename = path.rfind(['\\', '/']).map_or(path, |pos| &path[pos + 1..]);
 
-    filename
-        .rfind('.')
-        .map_or(filename, |pos| &filename[..pos])
-        .to_lowercase()
+    normalize_process_name(filename.rfind('.').map_or(filename, |pos| &filename[..pos]))
 }
 
 #[cfg(test)]
 #[expect(clippy::unwrap_used)]
 mod tests {
     use super::*;
-    use crate::config::{MonitoredApp, Win32App};
+    use crate::config::{AppSource, MatchRule, MonitoredApp, Win32App};
+    use crate::hdr::AutoHdrMode;
     use std::path::PathBuf;
     use uuid::Uuid;
 
@@ -579,10 +1731,136 @@ mod tests {
             exe_path: PathBuf::from(format!("C:\\test\\{process_name}.exe")),
             process_name: process_name.to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         })
     }
 
+    #[test]
+    fn test_poll_timings_rolling_average() {
+        let mut timings = PollTimings::default();
+        assert_eq!(timings.average(), None);
+        assert_eq!(timings.last(), None);
+
+        let interval = Duration::from_millis(1000);
+        assert!(!timings.record(Duration::from_millis(10), interval));
+        assert!(!timings.record(Duration::from_millis(30), interval));
+        assert_eq!(timings.average(), Some(Duration::from_millis(20)));
+        assert_eq!(timings.last(), Some(Duration::from_millis(30)));
+        assert_eq!(timings.suggested_interval(), None);
+
+        for _ in 0..POLL_TIMING_WINDOW {
+            timings.record(Duration::from_millis(5), interval);
+        }
+        assert_eq!(timings.average(), Some(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn test_poll_timings_over_budget() {
+        let mut timings = PollTimings::default();
+        let interval = Duration::from_millis(500);
+
+        // Only the first scan of a slow streak reports
+        assert!(timings.record(Duration::from_millis(600), interval));
+        assert!(!timings.record(Duration::from_millis(550), interval));
+        assert!(!timings.record(Duration::from_millis(100), interval));
+        assert!(timings.record(Duration::from_millis(520), interval));
+        assert_eq!(timings.over_budget_scans(), 3);
+
+        // Twice the 600ms peak, rounded up to 250ms
+        assert_eq!(
+            timings.suggested_interval(),
+            Some(Duration::from_millis(1250))
+        );
+
+        // Capped at the longest allowed interval
+        timings.record(Duration::from_millis(1900), interval);
+        assert_eq!(timings.suggested_interval(), Some(MAX_MONITORING_INTERVAL));
+
+        // Nothing longer to suggest
+        timings.record(Duration::from_millis(2500), MAX_MONITORING_INTERVAL);
+        assert_eq!(timings.suggested_interval(), None);
+    }
+
+    #[test]
+    fn test_process_trace_time_box() {
+        let trace = ProcessTrace::default();
+        assert!(!trace.is_active());
+        assert!(!trace.finish_if_expired());
+
+        trace.start(Duration::from_secs(60));
+        assert!(trace.is_active());
+        assert!(!trace.finish_if_expired());
+        trace.stop();
+        assert!(!trace.is_active());
+
+        trace.start(Duration::ZERO);
+        assert!(!trace.is_active());
+        assert!(trace.finish_if_expired());
+        assert!(!trace.finish_if_expired());
+    }
+
+    #[test]
+    fn test_describe_match() {
+        let identifiers: HashSet<AppIdentifier> = [
+            AppIdentifier::Win32("cyberpunk2077".to_string()),
+            AppIdentifier::UwpApplication("Pkg_abc!Game".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        let game = AppIdentifier::Win32("cyberpunk2077".to_string());
+        assert_eq!(
+            describe_match(Some(&game), true, &identifiers),
+            "matched watch list as Win32: cyberpunk2077"
+        );
+
+        let launcher = AppIdentifier::Win32("cyberpunk2077-launcher".to_string());
+        assert!(
+            describe_match(Some(&launcher), false, &identifiers)
+                .contains("similar entry 'cyberpunk2077'")
+        );
+
+        let editor = AppIdentifier::UwpApplication("Pkg_abc!Editor".to_string());
+        assert!(describe_match(Some(&editor), false, &identifiers).contains("same package"));
+
+        let other = AppIdentifier::Win32("notepad".to_string());
+        assert!(
+            describe_match(Some(&other), false, &identifiers).ends_with("not on the watch list")
+        );
+        assert!(describe_match(None, false, &identifiers).contains("could not determine"));
+        assert!(
+            describe_match(Some(&other), false, &HashSet::new()).contains("watch list is empty")
+        );
+    }
+
+    #[test]
+    fn test_polling_suspension() {
+        let suspension = Arc::new(PollingSuspension::default());
+        assert!(!suspension.is_suspended());
+
+        assert!(suspension.set_suspended(true));
+        assert!(!suspension.set_suspended(true));
+        assert!(suspension.is_suspended());
+
+        let waiter = {
+            let suspension = Arc::clone(&suspension);
+            std::thread::spawn(move || suspension.wait_until_resumed())
+        };
+        assert!(suspension.set_suspended(false));
+        waiter.join().unwrap();
+        assert!(!suspension.is_suspended());
+    }
+
     #[test]
     fn test_extract_filename_without_extension() {
         // Test full Windows path
@@ -616,6 +1894,59 @@ mod tests {
         assert_eq!(extract_filename_without_extension("my.app.exe"), "my.app");
     }
 
+    #[test]
+    fn test_extract_filename_without_extension_unicode() {
+        assert_eq!(
+            extract_filename_without_extension("C:\\Games\\原神\\原神.exe"),
+            "原神"
+        );
+        assert_eq!(
+            extract_filename_without_extension("D:\\ゲーム\\ＦＦ14.EXE"),
+            "ｆｆ14"
+        );
+        assert_eq!(
+            extract_filename_without_extension("C:\\Spiele\\Überspiel.exe"),
+            "überspiel"
+        );
+    }
+
+    #[test]
+    fn test_unicode_process_names_match_watch_list() {
+        let app = create_test_win32_app("Überspiel", "Überspiel");
+        let running = extract_filename_without_extension("C:\\Spiele\\ÜBERSPIEL.exe");
+
+        assert_eq!(
+            AppIdentifier::from_monitored_app(&app),
+            AppIdentifier::Win32(running)
+        );
+        let app_id = AppIdentifier::from_monitored_app(&app);
+        assert!(
+            WatchState::new()
+                .with_apps(vec![app], true)
+                .is_monitored(&app_id)
+        );
+    }
+
+    #[test]
+    fn test_shared_watch_state_publishes_snapshots() {
+        let shared = SharedWatchState::default();
+        let before = shared.load();
+
+        shared.update(|state| state.with_apps(vec![create_test_win32_app("game", "Game")], true));
+
+        // Loaded snapshots stay as they were, the next load sees the update
+        assert_eq!(shared.version(), 1);
+        assert!(before.apps.is_empty());
+        let after = shared.load();
+        assert_eq!(after.apps.len(), 1);
+        assert!(Arc::ptr_eq(&before.suspension, &after.suspension));
+
+        // Scan results are published separately and leave the watch state alone
+        shared.update_results(|results| results.running_apps = Some(HashSet::new()));
+        assert_eq!(shared.version(), 1);
+        assert_eq!(shared.results().running_apps, Some(HashSet::new()));
+    }
+
     #[test]
     fn test_detect_changes_started() {
         let (tx, rx) = mpsc::sync_channel(32);
@@ -641,12 +1972,57 @@ mod tests {
         let event = rx.recv_timeout(Duration::from_millis(100)).unwrap();
         match event {
             ProcessEvent::Started(AppIdentifier::Win32(name)) => assert_eq!(name, "notepad"),
-            ProcessEvent::Started(AppIdentifier::Uwp(_)) => panic!("Expected Win32 Started event"),
+            ProcessEvent::Started(
+                AppIdentifier::Uwp(_) | AppIdentifier::UwpApplication(_) | AppIdentifier::Folder(_),
+            ) => {
+                panic!("Expected Win32 Started event")
+            }
             ProcessEvent::Stopped(_) => panic!("Expected Started event, got Stopped"),
         }
 
         // Should not receive event for explorer (not monitored)
         assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+        // Only monitored applications are published for reconciliation
+        let running_apps = monitor.watch_state.results().running_apps.clone();
+        assert_eq!(
+            running_apps,
+            Some(HashSet::from([AppIdentifier::Win32("notepad".to_string())]))
+        );
+    }
+
+    #[test]
+    fn test_detect_changes_reports_excluded_processes() {
+        let (tx, rx) = mpsc::sync_channel(32);
+        let mut monitor = ProcessMonitor::new(Duration::from_secs(1), tx);
+
+        monitor.update_watch_list(vec![create_test_win32_app("game", "Game")]);
+        monitor
+            .watch_state
+            .update(|state| state.clone().with_exclusions(&["OBS64.exe".to_string()]));
+        // Watch list updates keep the exclusions
+        monitor.update_watch_list(vec![create_test_win32_app("game", "Game")]);
+
+        let recorder = AppIdentifier::Win32("obs64".to_string());
+        let mut current = HashSet::new();
+        assert!(
+            monitor
+                .watch_state
+                .load()
+                .insert_watched(&recorder, &mut current)
+        );
+        monitor.detect_changes(current);
+
+        let event = rx.recv_timeout(Duration::from_millis(100)).unwrap();
+        assert!(matches!(event, ProcessEvent::Started(app_id) if app_id == recorder));
+
+        // Excluded processes are not running monitored applications
+        let running_apps = monitor.watch_state.results().running_apps.clone();
+        assert_eq!(running_apps, Some(HashSet::new()));
+
+        monitor.detect_changes(HashSet::new());
+        let event = rx.recv_timeout(Duration::from_millis(100)).unwrap();
+        assert!(matches!(event, ProcessEvent::Stopped(app_id) if app_id == recorder));
     }
 
     #[test]
@@ -676,7 +2052,11 @@ mod tests {
         let event = rx.recv_timeout(Duration::from_millis(100)).unwrap();
         match event {
             ProcessEvent::Stopped(AppIdentifier::Win32(name)) => assert_eq!(name, "notepad"),
-            ProcessEvent::Stopped(AppIdentifier::Uwp(_)) => panic!("Expected Win32 Stopped event"),
+            ProcessEvent::Stopped(
+                AppIdentifier::Uwp(_) | AppIdentifier::UwpApplication(_) | AppIdentifier::Folder(_),
+            ) => {
+                panic!("Expected Win32 Stopped event")
+            }
             ProcessEvent::Started(_) => panic!("Expected Stopped event, got Started"),
         }
     }
@@ -702,7 +2082,11 @@ mod tests {
         let event = rx.recv_timeout(Duration::from_millis(100)).unwrap();
         match event {
             ProcessEvent::Started(AppIdentifier::Win32(name)) => assert_eq!(name, "notepad"),
-            ProcessEvent::Started(AppIdentifier::Uwp(_)) => panic!("Expected Win32 Started event"),
+            ProcessEvent::Started(
+                AppIdentifier::Uwp(_) | AppIdentifier::UwpApplication(_) | AppIdentifier::Folder(_),
+            ) => {
+                panic!("Expected Win32 Started event")
+            }
             ProcessEvent::Stopped(_) => panic!("Expected Started event, got Stopped"),
         }
     }
@@ -738,7 +2122,11 @@ mod tests {
                 ProcessEvent::Started(AppIdentifier::Win32(name)) => {
                     received.insert(name);
                 }
-                ProcessEvent::Started(AppIdentifier::Uwp(_)) => {
+                ProcessEvent::Started(
+                    AppIdentifier::Uwp(_)
+                    | AppIdentifier::UwpApplication(_)
+                    | AppIdentifier::Folder(_),
+                ) => {
                     panic!("Expected Win32 Started event")
                 }
                 ProcessEvent::Stopped(_) => panic!("Expected Started event, got Stopped"),
@@ -894,7 +2282,11 @@ mod tests {
         let event = rx.recv_timeout(Duration::from_millis(100)).unwrap();
         match event {
             ProcessEvent::Started(AppIdentifier::Win32(name)) => assert_eq!(name, "game"),
-            ProcessEvent::Started(AppIdentifier::Uwp(_)) => panic!("Expected Win32 Started event"),
+            ProcessEvent::Started(
+                AppIdentifier::Uwp(_) | AppIdentifier::UwpApplication(_) | AppIdentifier::Folder(_),
+            ) => {
+                panic!("Expected Win32 Started event")
+            }
             ProcessEvent::Stopped(_) => panic!("Expected Started event for game, got Stopped"),
         }
 
@@ -902,6 +2294,356 @@ mod tests {
         assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
     }
 
+    /// Helper function to create a test `UwpApp`
+    fn create_test_uwp_app(
+        package_family_name: &str,
+        app_id: &str,
+        match_app_id: bool,
+    ) -> MonitoredApp {
+        MonitoredApp::Uwp(crate::config::UwpApp {
+            id: Uuid::new_v4(),
+            display_name: app_id.to_string(),
+            package_family_name: package_family_name.to_string(),
+            app_id: app_id.to_string(),
+            enabled: true,
+            match_app_id,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        })
+    }
+
+    #[test]
+    fn test_detect_changes_forwards_uwp_watcher_apps() {
+        let (tx, rx) = mpsc::sync_channel(32);
+        let mut monitor = ProcessMonitor::new(Duration::from_secs(1), tx);
+        monitor.update_watch_list(vec![
+            create_test_win32_app("notepad", "Notepad"),
+            create_test_uwp_app("Fabrikam.Game_def456", "App", false),
+        ]);
+        let uwp_apps = Arc::clone(&monitor.watch_state.load().uwp_apps);
+        uwp_apps.activate();
+        uwp_apps.app_added("Fabrikam.Game_def456!App");
+
+        monitor.detect_changes(HashSet::new());
+
+        let package_id = AppIdentifier::Uwp("Fabrikam.Game_def456".to_string());
+        assert_eq!(
+            rx.recv_timeout(Duration::from_millis(100)).unwrap(),
+            ProcessEvent::Started(package_id.clone())
+        );
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+        // Watcher-reported apps count as running for reconciliation
+        let running = monitor.watch_state.results().running_apps.clone();
+        assert!(running.unwrap().contains(&package_id));
+
+        // Apps already reported are not reported again
+        monitor.detect_changes(HashSet::new());
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn test_uwp_application_identifier_matches_app_and_package_entries() {
+        let (tx, _rx) = mpsc::sync_channel(32);
+        let monitor = ProcessMonitor::new(Duration::from_secs(1), tx);
+        monitor.update_watch_list(vec![
+            create_test_uwp_app("Contoso.Suite_abc123", "Editor", true),
+            create_test_uwp_app("Fabrikam.Game_def456", "App", false),
+        ]);
+        let state = monitor.watch_state.load().clone();
+
+        // Application-level entry only matches its own AUMID
+        let mut current = HashSet::new();
+        state.insert_watched(
+            &AppIdentifier::UwpApplication("Contoso.Suite_abc123!Editor".to_string()),
+            &mut current,
+        );
+        state.insert_watched(
+            &AppIdentifier::UwpApplication("Contoso.Suite_abc123!Viewer".to_string()),
+            &mut current,
+        );
+        assert_eq!(current.len(), 1);
+        assert!(current.contains(&AppIdentifier::UwpApplication(
+            "Contoso.Suite_abc123!Editor".to_string()
+        )));
+
+        // Package-level entry matches any application from the package
+        let mut current = HashSet::new();
+        state.insert_watched(
+            &AppIdentifier::UwpApplication("Fabrikam.Game_def456!Launcher".to_string()),
+            &mut current,
+        );
+        assert!(current.contains(&AppIdentifier::Uwp("Fabrikam.Game_def456".to_string())));
+
+        // Processes without an AUMID fall back to package-level matching
+        let mut current = HashSet::new();
+        assert!(state.insert_watched(
+            &AppIdentifier::Uwp("CONTOSO.SUITE_ABC123".to_string()),
+            &mut current,
+        ));
+        assert!(current.contains(&AppIdentifier::UwpApplication(
+            "Contoso.Suite_abc123!Editor".to_string()
+        )));
+
+        // Packages without a watched application match nothing
+        let mut current = HashSet::new();
+        assert!(!state.insert_watched(
+            &AppIdentifier::Uwp("Northwind.Tools_ghi789".to_string()),
+            &mut current,
+        ));
+        assert!(current.is_empty());
+    }
+
+    #[test]
+    fn test_aliases_match_watched_app() {
+        let MonitoredApp::Win32(mut game) = create_test_win32_app("game", "Game") else {
+            unreachable!()
+        };
+        game.aliases = vec![
+            "Game_JP.exe".to_string(),
+            "D:\\Games\\KR\\game-kr.exe".to_string(),
+        ];
+        let game_id = AppIdentifier::Win32("game".to_string());
+        let state = WatchState::new().with_apps(vec![MonitoredApp::Win32(game)], true);
+
+        // Aliases are reported under the identifier of the application
+        let mut current = HashSet::new();
+        assert!(state.insert_watched(&AppIdentifier::Win32("game_jp".to_string()), &mut current));
+        assert!(state.insert_watched(&AppIdentifier::Win32("game-kr".to_string()), &mut current));
+        assert_eq!(current, HashSet::from([game_id.clone()]));
+        assert_eq!(
+            state.watched_identifier(&AppIdentifier::Win32("game_jp".to_string())),
+            Some(game_id)
+        );
+        assert_eq!(
+            state.watched_identifier(&AppIdentifier::Win32("game_cn".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_match_rules_match_process_name_variants() {
+        let MonitoredApp::Win32(mut unreal) =
+            create_test_win32_app("ue4game-win64-shipping", "Unreal Game")
+        else {
+            unreachable!()
+        };
+        unreal.match_rule = Some(MatchRule::Glob("UE4Game*.exe".to_string()));
+        let MonitoredApp::Win32(mut disabled) = create_test_win32_app("other", "Other") else {
+            unreachable!()
+        };
+        disabled.match_rule = Some(MatchRule::Regex("other.*".to_string()));
+        disabled.enabled = false;
+
+        let unreal_id = AppIdentifier::Win32("ue4game-win64-shipping".to_string());
+        let state = WatchState::new().with_apps(
+            vec![
+                MonitoredApp::Win32(unreal),
+                MonitoredApp::Win32(disabled),
+                create_test_win32_app("notepad", "Notepad"),
+            ],
+            true,
+        );
+
+        // Variants are reported under the identifier of the entry whose rule matched
+        let mut current = HashSet::new();
+        assert!(state.insert_watched(
+            &AppIdentifier::Win32("ue4game-wingdk-shipping".to_string()),
+            &mut current,
+        ));
+        assert!(state.insert_watched(&AppIdentifier::Win32("ue4game".to_string()), &mut current,));
+        assert_eq!(current, HashSet::from([unreal_id.clone()]));
+        assert_eq!(
+            state.watched_identifier(&AppIdentifier::Win32("ue4game-dx12".to_string())),
+            Some(unreal_id)
+        );
+
+        // Patterns match the whole name and disabled entries contribute no rules
+        let mut current = HashSet::new();
+        assert!(
+            !state.insert_watched(&AppIdentifier::Win32("myue4game".to_string()), &mut current,)
+        );
+        assert!(!state.insert_watched(
+            &AppIdentifier::Win32("other-launcher".to_string()),
+            &mut current,
+        ));
+        assert!(current.is_empty());
+
+        // Exact matching is unaffected
+        let notepad = AppIdentifier::Win32("notepad".to_string());
+        assert_eq!(state.watched_identifier(&notepad), Some(notepad));
+    }
+
+    #[test]
+    fn test_watched_folders_match_executables_inside() {
+        let library = MonitoredApp::Folder(crate::config::FolderApp {
+            id: Uuid::new_v4(),
+            display_name: "Games".to_string(),
+            path: PathBuf::from("D:/Games/"),
+            enabled: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        });
+        let library_id = AppIdentifier::from_monitored_app(&library);
+        assert_eq!(library_id, AppIdentifier::Folder(r"d:\games".to_string()));
+
+        let state = WatchState::new().with_apps(
+            vec![library, create_test_win32_app("notepad", "Notepad")],
+            true,
+        );
+        assert!(state.watches_folders());
+        assert!(state.is_monitored(&library_id));
+
+        let mut current = HashSet::new();
+        assert_eq!(
+            state.insert_watched_folders(
+                Path::new(r"D:\GAMES\Studio\Game\Binaries\game.exe"),
+                &mut current
+            ),
+            Some(library_id.clone())
+        );
+        assert_eq!(current, HashSet::from([library_id]));
+
+        // Sibling folders sharing the prefix and other drives do not match
+        let mut current = HashSet::new();
+        assert_eq!(
+            state.insert_watched_folders(Path::new(r"D:\GamesOld\game.exe"), &mut current),
+            None
+        );
+        assert_eq!(
+            state.insert_watched_folders(Path::new(r"C:\Games\game.exe"), &mut current),
+            None
+        );
+        assert!(current.is_empty());
+
+        // Without folders no image paths are needed
+        let state =
+            WatchState::new().with_apps(vec![create_test_win32_app("notepad", "Notepad")], true);
+        assert!(!state.watches_folders());
+    }
+
+    #[test]
+    fn test_invalid_match_rules_are_skipped() {
+        let MonitoredApp::Win32(mut broken) = create_test_win32_app("game", "Game") else {
+            unreachable!()
+        };
+        broken.match_rule = Some(MatchRule::Regex("game(".to_string()));
+
+        let state = WatchState::new().with_apps(vec![MonitoredApp::Win32(broken)], true);
+
+        assert!(state.patterns.is_none());
+        assert!(state.is_monitored(&AppIdentifier::Win32("game".to_string())));
+        assert_eq!(
+            state.watched_identifier(&AppIdentifier::Win32("game2".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_monitored_respects_match_app_id() {
+        let state = WatchState::new().with_apps(
+            vec![
+                create_test_uwp_app("Contoso.Suite_abc123", "Editor", true),
+                create_test_uwp_app("Fabrikam.Game_def456", "App", false),
+            ],
+            true,
+        );
+
+        assert!(state.is_monitored(&AppIdentifier::UwpApplication(
+            "Contoso.Suite_abc123!Editor".to_string()
+        )));
+        assert!(!state.is_monitored(&AppIdentifier::Uwp("Contoso.Suite_abc123".to_string())));
+        assert!(state.is_monitored(&AppIdentifier::Uwp("Fabrikam.Game_def456".to_string())));
+    }
+
+    #[test]
+    fn test_watch_state_indexes_enabled_apps_only() {
+        let mut disabled = create_test_win32_app("paused", "Paused");
+        if let MonitoredApp::Win32(app) = &mut disabled {
+            app.enabled = false;
+        }
+        let previous = WatchState::new();
+        let state = previous.with_apps(
+            vec![
+                create_test_win32_app("game", "Game"),
+                disabled,
+                create_test_uwp_app("Contoso.Suite_abc123", "Editor", true),
+                create_test_uwp_app("Contoso.Suite_abc123", "Viewer", true),
+            ],
+            false,
+        );
+
+        assert_eq!(state.apps.len(), 4);
+        assert!(state.is_monitored(&AppIdentifier::Win32("GAME".to_string())));
+        assert!(!state.is_monitored(&AppIdentifier::Win32("paused".to_string())));
+        assert_eq!(
+            state.package_apps[&AppIdentifier::Uwp("Contoso.Suite_abc123".to_string())].len(),
+            2
+        );
+        assert!(!state.track_child_processes);
+        // Polling handles are shared with the previous state
+        assert!(Arc::ptr_eq(&state.suspension, &previous.suspension));
+        assert!(Arc::ptr_eq(&state.uwp_apps, &previous.uwp_apps));
+    }
+
+    #[test]
+    fn test_known_process_detects_pid_reuse() {
+        let known = KnownProcess {
+            exe_file: Some("game.exe".to_string()),
+            parent_pid: 42,
+            app_id: Some(AppIdentifier::Win32("game".to_string())),
+            exe_path: None,
+            suspected_folder: None,
+            created: None,
+        };
+
+        assert!(known.is_same_process(Some("game.exe"), 42));
+        assert!(!known.is_same_process(Some("notepad.exe"), 42));
+        assert!(!known.is_same_process(Some("game.exe"), 7));
+        assert!(!known.is_same_process(None, 42));
+    }
+
+    #[test]
+    fn test_identifiers_compare_with_unicode_case_folding() {
+        let watched: HashSet<_> = [
+            AppIdentifier::Win32("überspiel".to_string()),
+            AppIdentifier::Uwp("Fabrikam.Game_def456".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(watched.contains(&AppIdentifier::Win32("ÜBERSPIEL".to_string())));
+        assert!(watched.contains(&AppIdentifier::Uwp("fabrikam.game_DEF456".to_string())));
+        assert!(!watched.contains(&AppIdentifier::Uwp("überspiel".to_string())));
+        assert!(!watched.contains(&AppIdentifier::Win32("uberspiel".to_string())));
+
+        // Original spelling is kept for display
+        assert_eq!(
+            AppIdentifier::Uwp("Fabrikam.Game_def456".to_string()).to_string(),
+            "UWP: Fabrikam.Game_def456"
+        );
+
+        let state = WatchState::new().with_apps(
+            vec![create_test_uwp_app("Contoso.Suite_abc123", "Editor", true)],
+            true,
+        );
+        assert!(state.is_monitored(&AppIdentifier::UwpApplication(
+            "CONTOSO.SUITE_ABC123!editor".to_string()
+        )));
+    }
+
     #[test]
     fn test_empty_watch_list() {
         let (tx, rx) = mpsc::sync_channel(32);
@@ -1004,61 +2746,45 @@ mod tests {
 
                 // Update once
                 monitor.update_watch_list(apps.clone());
-                let first_result_len = monitor.watch_state.read().apps.len();
+                let first_result_len = monitor.watch_state.load().apps.len();
 
                 // Update again with the same input
                 monitor.update_watch_list(apps);
-                let second_result_len = monitor.watch_state.read().apps.len();
+                let second_result_len = monitor.watch_state.load().apps.len();
 
                 // Results should be identical (same number of apps)
                 prop_assert_eq!(first_result_len, second_result_len);
             }
 
-            /// Property: PID cache handles reuse correctly with expiry
+            /// Property: A reused PID is never mistaken for the process it replaced
             ///
-            /// Tests that the cache expires entries after the expiry duration,
-            /// preventing stale data from being returned when PIDs are reused.
-            /// This is critical for correctness on Windows where PIDs can be reused.
+            /// Known processes keep their identifier only while the snapshot reports the
+            /// same executable and parent, so a new process with a recycled PID is
+            /// identified again instead of inheriting a stale identifier.
             #[test]
-            fn pid_cache_expires_old_entries(
-                pid in 100u32..10000u32,
-                app_name in "[a-zA-Z0-9_-]+"
+            fn known_process_is_reidentified_after_pid_reuse(
+                parent_pid in 0u32..10000u32,
+                app_name in "[a-zA-Z0-9_-]+",
+                other_name in "[a-zA-Z0-9_-]+"
             ) {
-                const CACHE_EXPIRY: Duration = Duration::from_secs(5);
-
-                let (tx, _rx) = mpsc::sync_channel(32);
-                let mut monitor = ProcessMonitor::new(Duration::from_secs(1), tx);
-
-                // Create an AppIdentifier for testing
-                let app_id = AppIdentifier::Win32(app_name.clone());
-
-                // Simulate cache entry creation (as poll_processes would do)
-                let initial_time = Instant::now();
-                monitor.app_id_cache.insert(pid, (app_id.clone(), initial_time));
-
-                // Verify cache contains the entry
-                prop_assert!(monitor.app_id_cache.contains_key(&pid));
-                prop_assert_eq!(&monitor.app_id_cache.get(&pid).unwrap().0, &app_id);
-
-                // Simulate time passing beyond expiry (5 seconds)
-                // We can't actually wait 5 seconds in a test, so we manually insert
-                // an old timestamp to simulate an expired entry
-                let old_time = initial_time
-                    .checked_sub(Duration::from_secs(6))
-                    .unwrap();
-                monitor.app_id_cache.insert(pid, (app_id.clone(), old_time));
-
-                // Simulate poll_processes expiry logic
-                let now = Instant::now();
-                monitor.app_id_cache.retain(|_pid, (_app_id, last_seen)| {
-                    now.duration_since(*last_seen) < CACHE_EXPIRY
-                });
+                let exe_file = format!("{app_name}.exe");
+                let known = KnownProcess {
+                    exe_file: Some(exe_file.clone()),
+                    parent_pid,
+                    app_id: Some(AppIdentifier::Win32(app_name.to_lowercase())),
+                    exe_path: None,
+                    suspected_folder: None,
+                    created: None,
+                };
 
-                // After expiry, cache should not contain the PID
-                prop_assert!(!monitor.app_id_cache.contains_key(&pid));
+                prop_assert!(known.is_same_process(Some(&exe_file), parent_pid));
+                prop_assert!(!known.is_same_process(Some(&exe_file), parent_pid.wrapping_add(1)));
 
-                // Verify cache size is 0 (entry was removed)
-                prop_assert_eq!(monitor.app_id_cache.len(), 0);
+                let other_exe = format!("{other_name}.exe");
+                prop_assert_eq!(
+                    known.is_same_process(Some(&other_exe), parent_pid),
+                    other_exe == exe_file
+                );
             }
         }
     }
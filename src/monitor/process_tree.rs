@@ -0,0 +1,273 @@
+//! Process tree tracking for applications started through launchers.
+//!
+//! Many games are started by a launcher whose actual renderer is a child process with an
+//! arbitrary name. `ProcessTree` attributes every descendant of a monitored process to the
+//! monitored application, so the application counts as running until its whole process
+//! tree has exited, even after the launcher itself is gone.
+//!
+//! Processes are identified by PID and creation time, since Windows reuses PIDs: a
+//! reused PID is a new process, and a process is only a child of a tracked process if it
+//! was created after it.
+
+use std::collections::{HashMap, HashSet};
+
+use super::process_monitor::AppIdentifier;
+
+/// Minimal process information from a snapshot needed to follow parent links
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessEntry {
+    /// Process ID
+    pub pid: u32,
+    /// Parent process ID reported by the snapshot
+    pub parent_pid: u32,
+    /// Executable name (lowercase, no extension)
+    pub exe_name: String,
+    /// Creation time in 100 ns ticks since 1601, `None` if the process could not be opened
+    pub created: Option<u64>,
+}
+
+/// Process attributed to a monitored application
+#[derive(Debug, Clone)]
+struct TrackedProcess {
+    /// Identifier of the monitored process at the root of the tree
+    app_id: AppIdentifier,
+    /// Executable name when tracking started
+    exe_name: String,
+    /// Creation time, telling a reused PID apart from the tracked process
+    created: Option<u64>,
+}
+
+/// Tracks descendants of monitored processes across poll cycles
+#[derive(Debug, Default)]
+pub struct ProcessTree {
+    /// PID → tracked process, including the monitored roots themselves
+    tracked: HashMap<u32, TrackedProcess>,
+}
+
+impl ProcessTree {
+    /// Create an empty process tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update tracking from a new snapshot and return identifiers kept alive by descendants
+    ///
+    /// `roots` maps PIDs that matched the watch list directly in this snapshot to their
+    /// identifier. Previously tracked processes stay tracked while the PID still belongs to
+    /// the process created at the same time; any process created after a tracked parent
+    /// inherits the parent's identifier. Processes with an unknown creation time are only
+    /// tracked as roots. The returned set contains the identifiers of tracked processes
+    /// that are not roots themselves.
+    pub fn update(
+        &mut self,
+        processes: &[ProcessEntry],
+        roots: &HashMap<u32, AppIdentifier>,
+    ) -> HashSet<AppIdentifier> {
+        let mut tracked: HashMap<u32, TrackedProcess> = HashMap::with_capacity(self.tracked.len());
+
+        for process in processes {
+            if let Some(app_id) = roots.get(&process.pid) {
+                tracked.insert(
+                    process.pid,
+                    TrackedProcess {
+                        app_id: app_id.clone(),
+                        exe_name: process.exe_name.clone(),
+                        created: process.created,
+                    },
+                );
+            } else if let Some(previous) = self.tracked.get(&process.pid)
+                && process.created.is_some()
+                && previous.created == process.created
+            {
+                tracked.insert(process.pid, previous.clone());
+            }
+        }
+
+        // Propagate down the tree until no new descendants are found. Each pass adds at
+        // least one process, so this terminates after at most `processes.len()` passes.
+        loop {
+            let mut inherited = Vec::new();
+            for process in processes {
+                if tracked.contains_key(&process.pid)
+                    || process.parent_pid == 0
+                    || process.parent_pid == process.pid
+                {
+                    continue;
+                }
+
+                // A parent PID that was reused after the real parent exited belongs to a
+                // process created later than the child
+                if let Some(parent) = tracked.get(&process.parent_pid)
+                    && let (Some(created), Some(parent_created)) = (process.created, parent.created)
+                    && created >= parent_created
+                {
+                    inherited.push((
+                        process.pid,
+                        TrackedProcess {
+                            app_id: parent.app_id.clone(),
+                            exe_name: process.exe_name.clone(),
+                            created: process.created,
+                        },
+                    ));
+                }
+            }
+
+            if inherited.is_empty() {
+                break;
+            }
+
+            for (pid, process) in inherited {
+                tracing::debug!(
+                    "Tracking child process {} (PID {}) for {}",
+                    process.exe_name,
+                    pid,
+                    process.app_id
+                );
+                tracked.insert(pid, process);
+            }
+        }
+
+        self.tracked = tracked;
+
+        self.tracked
+            .iter()
+            .filter(|(pid, _)| !roots.contains_key(pid))
+            .map(|(_, process)| process.app_id.clone())
+            .collect()
+    }
+
+    /// Identifier a tracked process is attributed to
+    pub fn app_id(&self, pid: u32) -> Option<&AppIdentifier> {
+        self.tracked.get(&pid).map(|process| &process.app_id)
+    }
+
+    /// Stop tracking all processes
+    pub fn clear(&mut self) {
+        self.tracked.clear();
+    }
+
+    /// Number of tracked processes, including roots
+    pub fn len(&self) -> usize {
+        self.tracked.len()
+    }
+
+    /// Whether no processes are tracked
+    pub fn is_empty(&self) -> bool {
+        self.tracked.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pid: u32, parent_pid: u32, exe_name: &str) -> ProcessEntry {
+        ProcessEntry {
+            pid,
+            parent_pid,
+            exe_name: exe_name.to_string(),
+            created: Some(u64::from(pid)),
+        }
+    }
+
+    fn launcher_roots() -> HashMap<u32, AppIdentifier> {
+        HashMap::from([(100, AppIdentifier::Win32("launcher".to_string()))])
+    }
+
+    #[test]
+    fn test_children_inherit_root_identifier() {
+        let mut tree = ProcessTree::new();
+        let processes = vec![
+            entry(1, 0, "explorer"),
+            entry(100, 1, "launcher"),
+            entry(200, 100, "game"),
+            entry(300, 200, "crashhandler"),
+            entry(400, 1, "unrelated"),
+        ];
+
+        let kept = tree.update(&processes, &launcher_roots());
+
+        assert_eq!(
+            kept,
+            HashSet::from([AppIdentifier::Win32("launcher".to_string())])
+        );
+        assert_eq!(tree.len(), 3);
+    }
+
+    #[test]
+    fn test_children_keep_app_running_after_launcher_exits() {
+        let mut tree = ProcessTree::new();
+        tree.update(
+            &[entry(100, 1, "launcher"), entry(200, 100, "game")],
+            &launcher_roots(),
+        );
+
+        // Launcher exits; the game is still running with a dangling parent PID
+        let kept = tree.update(&[entry(200, 100, "game")], &HashMap::new());
+        assert!(kept.contains(&AppIdentifier::Win32("launcher".to_string())));
+
+        // Whole tree has exited
+        let kept = tree.update(&[], &HashMap::new());
+        assert!(kept.is_empty());
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn test_reused_pid_is_not_tracked() {
+        let mut tree = ProcessTree::new();
+        tree.update(
+            &[entry(100, 1, "launcher"), entry(200, 100, "game")],
+            &launcher_roots(),
+        );
+
+        // PID 200 now belongs to a process of the same name created later
+        let reused = ProcessEntry {
+            created: Some(1_000),
+            ..entry(200, 1, "game")
+        };
+        let kept = tree.update(&[reused], &HashMap::new());
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn test_process_older_than_reused_parent_pid_is_not_a_child() {
+        let mut tree = ProcessTree::new();
+        // PID 50 belonged to a process that exited before the launcher reused it; its
+        // old child still reports it as parent but was created before the launcher
+        let launcher = ProcessEntry {
+            created: Some(1_000),
+            ..entry(50, 1, "launcher")
+        };
+        let orphan = ProcessEntry {
+            created: Some(500),
+            ..entry(300, 50, "updater")
+        };
+        let roots = HashMap::from([(50, AppIdentifier::Win32("launcher".to_string()))]);
+
+        let kept = tree.update(&[launcher, orphan], &roots);
+
+        assert!(kept.is_empty());
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_creation_time_is_not_tracked_as_child() {
+        let mut tree = ProcessTree::new();
+        let protected = ProcessEntry {
+            created: None,
+            ..entry(200, 100, "game")
+        };
+
+        let kept = tree.update(&[entry(100, 1, "launcher"), protected], &launcher_roots());
+
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn test_roots_are_not_reported_as_descendants() {
+        let mut tree = ProcessTree::new();
+        let kept = tree.update(&[entry(100, 1, "launcher")], &launcher_roots());
+        assert!(kept.is_empty());
+        assert_eq!(tree.len(), 1);
+    }
+}
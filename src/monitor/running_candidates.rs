@@ -0,0 +1,554 @@
+//! Running applications offered when adding an application
+//!
+//! Launchers and portable games are awkward to add through the file picker because their
+//! executables are hard to find. The running-process picker lists what is running instead:
+//! one entry per executable path for desktop applications and one per application (AUMID)
+//! for UWP applications, whose processes often run several times. Windows' own processes
+//! and applications that are already monitored are left out.
+
+use crate::config::MonitoredApp;
+use crate::error::Result;
+use crate::utils::canonical_path;
+use crate::utils::unicode::{eq_ignore_case, fold_case, normalize_process_name};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// What a running application would be added as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CandidateKind {
+    /// Desktop application, added by executable path
+    Win32 {
+        /// Full path of the running executable
+        exe_path: PathBuf,
+    },
+    /// UWP application, added by package and application ID
+    Uwp {
+        /// Package family name
+        package_family_name: String,
+        /// Application ID within the package
+        app_id: String,
+        /// Whether the package contains several applications
+        multi_app_package: bool,
+    },
+}
+
+/// A running application that can be added to the watch list
+#[derive(Debug, Clone)]
+pub struct RunningCandidate {
+    /// Name shown in the picker
+    pub display_name: String,
+    /// How the application would be added
+    pub kind: CandidateKind,
+    /// 32x32 RGBA icon, if one could be extracted
+    pub icon_data: Option<Vec<u8>>,
+}
+
+/// A process seen in a snapshot, before deduplication
+#[derive(Debug, Clone, Default)]
+pub struct RunningProcess {
+    /// Full executable path, if the process could be queried
+    pub exe_path: Option<PathBuf>,
+    /// Application User Model ID of packaged processes
+    pub aumid: Option<String>,
+}
+
+impl CandidateKind {
+    /// Whether an application on the list already covers this candidate
+    ///
+    /// Desktop applications count as monitored when an entry has the same executable
+    /// (through junctions and case) or the same process name, which would already match
+    /// it, or a watched folder contains the executable. UWP applications count when an
+    /// entry watches their package or application.
+    pub fn is_monitored_by(&self, apps: &[MonitoredApp]) -> bool {
+        match self {
+            Self::Win32 { exe_path } => {
+                let process_name = exe_path
+                    .file_stem()
+                    .map(|stem| normalize_process_name(&stem.to_string_lossy()))
+                    .unwrap_or_default();
+                apps.iter().any(|app| match app {
+                    MonitoredApp::Win32(win32_app) => {
+                        canonical_path::same_path(&win32_app.exe_path, exe_path)
+                            || eq_ignore_case(
+                                &normalize_process_name(&win32_app.process_name),
+                                &process_name,
+                            )
+                            || win32_app
+                                .alias_process_names()
+                                .any(|alias| eq_ignore_case(&alias, &process_name))
+                    }
+                    MonitoredApp::Folder(folder_app) => is_inside(exe_path, &folder_app.path),
+                    MonitoredApp::Uwp(_) => false,
+                })
+            }
+            Self::Uwp {
+                package_family_name,
+                app_id,
+                ..
+            } => apps.iter().any(|app| match app {
+                MonitoredApp::Uwp(uwp_app) => {
+                    eq_ignore_case(&uwp_app.package_family_name, package_family_name)
+                        && (!uwp_app.match_app_id || eq_ignore_case(&uwp_app.app_id, app_id))
+                }
+                MonitoredApp::Win32(_) | MonitoredApp::Folder(_) => false,
+            }),
+        }
+    }
+}
+
+/// Reduce a process snapshot to one candidate per application
+///
+/// Packaged processes are grouped by AUMID, everything else by executable path.
+/// Executables inside `excluded_dirs` (the Windows directory) and processes without a
+/// path are skipped. The first occurrence keeps its position.
+pub fn collect_candidates(
+    processes: impl IntoIterator<Item = RunningProcess>,
+    excluded_dirs: &[PathBuf],
+) -> Vec<CandidateKind> {
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+
+    for process in processes {
+        let (key, kind) = if let Some((family_name, app_id)) = process
+            .aumid
+            .as_deref()
+            .and_then(|aumid| aumid.split_once('!'))
+            .filter(|(family_name, app_id)| !family_name.is_empty() && !app_id.is_empty())
+        {
+            (
+                format!("uwp:{}", fold_case(&format!("{family_name}!{app_id}"))),
+                CandidateKind::Uwp {
+                    package_family_name: family_name.to_string(),
+                    app_id: app_id.to_string(),
+                    multi_app_package: false,
+                },
+            )
+        } else if let Some(exe_path) = process.exe_path {
+            if excluded_dirs.iter().any(|dir| is_inside(&exe_path, dir)) {
+                continue;
+            }
+            (
+                format!("win32:{}", canonical_path::path_key(&exe_path)),
+                CandidateKind::Win32 { exe_path },
+            )
+        } else {
+            continue;
+        };
+
+        if seen.insert(key) {
+            candidates.push(kind);
+        }
+    }
+
+    candidates
+}
+
+/// Whether `path` lies inside `dir`, comparing Windows paths case-insensitively
+fn is_inside(path: &Path, dir: &Path) -> bool {
+    canonical_path::key_is_inside(
+        &canonical_path::lexical_key(path),
+        &canonical_path::lexical_key(dir),
+    )
+}
+
+/// Snapshot the running applications that could be added
+///
+/// Names and icons come from the executable's version resource and icon, or from the
+/// installed package for UWP applications. The list is sorted by name.
+///
+/// # Errors
+///
+/// Returns error if the process snapshot fails.
+pub fn list_running_candidates() -> Result<Vec<RunningCandidate>> {
+    let windows_dir =
+        std::env::var_os("SystemRoot").map_or_else(|| PathBuf::from(r"C:\Windows"), PathBuf::from);
+    let mut processes = snapshot_processes()?;
+    // EasyHDR itself is never a candidate
+    if let Ok(own_exe) = std::env::current_exe() {
+        processes.retain(|process| {
+            process
+                .exe_path
+                .as_deref()
+                .is_none_or(|path| !canonical_path::same_path(path, &own_exe))
+        });
+    }
+
+    let mut candidates: Vec<RunningCandidate> = collect_candidates(processes, &[windows_dir])
+        .into_iter()
+        .map(describe_candidate)
+        .collect();
+    candidates.sort_by_cached_key(|candidate| fold_case(&candidate.display_name));
+    Ok(candidates)
+}
+
+/// List Start Menu applications as picker candidates named after their shortcut
+///
+/// Resolves every shortcut and extracts icons, so it is slow; call it without holding
+/// the controller and leave out monitored applications afterwards.
+pub fn list_start_menu_candidates() -> Vec<RunningCandidate> {
+    crate::utils::start_menu::scan()
+        .into_iter()
+        .map(|entry| RunningCandidate {
+            display_name: entry.display_name,
+            kind: CandidateKind::Win32 {
+                exe_path: entry.exe_path,
+            },
+            icon_data: entry.icon_data,
+        })
+        .collect()
+}
+
+/// Look up the name and icon of a candidate
+fn describe_candidate(kind: CandidateKind) -> RunningCandidate {
+    match kind {
+        CandidateKind::Win32 { exe_path } => {
+            let display_name = crate::utils::extract_display_name_from_exe(&exe_path).map_or_else(
+                |_| {
+                    exe_path
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().into_owned())
+                        .unwrap_or_default()
+                },
+                |name| crate::utils::clean_display_name(&name),
+            );
+            let icon_data = crate::utils::extract_icon_from_exe(&exe_path)
+                .ok()
+                .filter(|data| !data.is_empty());
+            RunningCandidate {
+                display_name,
+                kind: CandidateKind::Win32 { exe_path },
+                icon_data,
+            }
+        }
+        CandidateKind::Uwp {
+            package_family_name,
+            app_id,
+            multi_app_package,
+        } => describe_uwp_candidate(package_family_name, app_id, multi_app_package),
+    }
+}
+
+/// Look up a UWP candidate in the installed packages
+#[cfg(windows)]
+fn describe_uwp_candidate(
+    package_family_name: String,
+    app_id: String,
+    multi_app_package: bool,
+) -> RunningCandidate {
+    use tracing::debug;
+
+    let packages = crate::uwp::get_package_cache()
+        .packages()
+        .map_err(|e| debug!("Failed to enumerate UWP packages: {}", e))
+        .ok();
+    let package = packages.as_deref().and_then(|packages| {
+        packages.iter().find(|package| {
+            eq_ignore_case(&package.package_family_name, &package_family_name)
+                && eq_ignore_case(&package.app_id, &app_id)
+        })
+    });
+
+    match package {
+        Some(package) => RunningCandidate {
+            display_name: package.display_name.clone(),
+            icon_data: package.logo_stream.as_ref().and_then(|stream| {
+                crate::uwp::extract_icon_from_stream(stream)
+                    .ok()
+                    .filter(|data| !data.is_empty())
+            }),
+            kind: CandidateKind::Uwp {
+                package_family_name,
+                app_id,
+                multi_app_package: package.multi_app_package,
+            },
+        },
+        None => fallback_uwp_candidate(package_family_name, app_id, multi_app_package),
+    }
+}
+
+/// UWP packages can only be looked up on Windows
+#[cfg(not(windows))]
+fn describe_uwp_candidate(
+    package_family_name: String,
+    app_id: String,
+    multi_app_package: bool,
+) -> RunningCandidate {
+    fallback_uwp_candidate(package_family_name, app_id, multi_app_package)
+}
+
+/// Name a UWP candidate after its package when the package cannot be found
+fn fallback_uwp_candidate(
+    package_family_name: String,
+    app_id: String,
+    multi_app_package: bool,
+) -> RunningCandidate {
+    // "Microsoft.MinecraftUWP_8wekyb3d8bbwe" -> "Microsoft.MinecraftUWP"
+    let display_name = package_family_name
+        .split_once('_')
+        .map_or(package_family_name.as_str(), |(name, _)| name)
+        .to_string();
+    RunningCandidate {
+        display_name,
+        kind: CandidateKind::Uwp {
+            package_family_name,
+            app_id,
+            multi_app_package,
+        },
+        icon_data: None,
+    }
+}
+
+/// Snapshot the running processes with their paths and AUMIDs
+///
+/// Processes that cannot be opened (system services, elevated processes when EasyHDR
+/// is not elevated) are left out.
+///
+/// # Safety
+///
+/// Snapshot and process handles are closed when the function returns. Buffers passed to
+/// the queries are sized as reported to Windows.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for process enumeration and image path queries"
+)]
+fn snapshot_processes() -> Result<Vec<RunningProcess>> {
+    use crate::error::EasyHdrError;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW,
+        TH32CS_SNAPPROCESS,
+    };
+    use windows::Win32::System::Threading::{
+        OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+        QueryFullProcessImageNameW,
+    };
+    use windows::core::PWSTR;
+
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }
+        .map_err(|e| EasyHdrError::ProcessMonitorError(Box::new(e)))?;
+
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "size_of::<PROCESSENTRY32W>() is a compile-time constant (592 bytes) that fits in u32"
+    )]
+    let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+        ..Default::default()
+    };
+
+    let mut processes = Vec::new();
+    let mut has_process = unsafe { Process32FirstW(snapshot, &raw mut entry) }.is_ok();
+    while has_process {
+        if let Ok(handle) = unsafe {
+            OpenProcess(
+                PROCESS_QUERY_LIMITED_INFORMATION,
+                false,
+                entry.th32ProcessID,
+            )
+        } {
+            let aumid = unsafe { crate::uwp::detect_uwp_application_user_model_id(handle) }
+                .ok()
+                .flatten();
+
+            let mut buffer = [0u16; 1024];
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "The buffer length is a small compile-time constant"
+            )]
+            let mut len = buffer.len() as u32;
+            let exe_path = unsafe {
+                QueryFullProcessImageNameW(
+                    handle,
+                    PROCESS_NAME_WIN32,
+                    PWSTR(buffer.as_mut_ptr()),
+                    &raw mut len,
+                )
+            }
+            .ok()
+            .map(|()| PathBuf::from(String::from_utf16_lossy(&buffer[..len as usize])));
+
+            let _ = unsafe { CloseHandle(handle) };
+            processes.push(RunningProcess { exe_path, aumid });
+        }
+
+        has_process = unsafe { Process32NextW(snapshot, &raw mut entry) }.is_ok();
+    }
+
+    let _ = unsafe { CloseHandle(snapshot) };
+    Ok(processes)
+}
+
+/// Process snapshots are only available on Windows
+#[cfg(not(windows))]
+fn snapshot_processes() -> Result<Vec<RunningProcess>> {
+    Err(crate::error::EasyHdrError::ProcessMonitorError(
+        crate::error::StringError::new("Listing running applications is only supported on Windows"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppSource, FolderApp, UwpApp, Win32App};
+    use crate::hdr::AutoHdrMode;
+    use uuid::Uuid;
+
+    fn win32(path: &str) -> RunningProcess {
+        RunningProcess {
+            exe_path: Some(PathBuf::from(path)),
+            aumid: None,
+        }
+    }
+
+    fn packaged(path: &str, aumid: &str) -> RunningProcess {
+        RunningProcess {
+            exe_path: Some(PathBuf::from(path)),
+            aumid: Some(aumid.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_collect_candidates_deduplicates() {
+        let processes = vec![
+            win32(r"D:\Games\Portable\game.exe"),
+            win32(r"D:\GAMES\portable\GAME.EXE"),
+            win32(r"C:\Windows\System32\svchost.exe"),
+            win32(r"c:\windows\explorer.exe"),
+            packaged(
+                r"C:\Program Files\WindowsApps\Minecraft\Minecraft.Windows.exe",
+                "Microsoft.MinecraftUWP_8wekyb3d8bbwe!App",
+            ),
+            packaged(
+                r"C:\Program Files\WindowsApps\Minecraft\Minecraft.Windows.exe",
+                "microsoft.minecraftuwp_8wekyb3d8bbwe!app",
+            ),
+            // Packaged helper hosted in the Windows directory is still the app
+            packaged(
+                r"C:\Windows\System32\ApplicationFrameHost.exe",
+                "Microsoft.WindowsCalculator_8wekyb3d8bbwe!App",
+            ),
+            RunningProcess::default(),
+        ];
+
+        assert_eq!(
+            collect_candidates(processes, &[PathBuf::from(r"C:\Windows\")]),
+            vec![
+                CandidateKind::Win32 {
+                    exe_path: PathBuf::from(r"D:\Games\Portable\game.exe")
+                },
+                CandidateKind::Uwp {
+                    package_family_name: "Microsoft.MinecraftUWP_8wekyb3d8bbwe".to_string(),
+                    app_id: "App".to_string(),
+                    multi_app_package: false,
+                },
+                CandidateKind::Uwp {
+                    package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
+                    app_id: "App".to_string(),
+                    multi_app_package: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_inside() {
+        let windows = Path::new(r"C:\Windows");
+        assert!(is_inside(Path::new(r"C:\Windows\explorer.exe"), windows));
+        assert!(is_inside(
+            Path::new(r"c:/windows/system32/dwm.exe"),
+            windows
+        ));
+        assert!(!is_inside(Path::new(r"C:\WindowsApps\game.exe"), windows));
+        assert!(!is_inside(Path::new(r"D:\Games\game.exe"), windows));
+    }
+
+    #[test]
+    fn test_is_monitored_by() {
+        let game = Win32App {
+            id: Uuid::new_v4(),
+            display_name: "Game".to_string(),
+            exe_path: PathBuf::from(r"D:\Games\Portable\game.exe"),
+            process_name: "game".to_string(),
+            enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: vec!["game_jp.exe".to_string()],
+            icon_data: None,
+        };
+        let mut calculator = UwpApp::from_package_info(
+            "Calculator".to_string(),
+            "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
+            "App".to_string(),
+            None,
+        );
+        calculator.match_app_id = true;
+        let apps = vec![MonitoredApp::Win32(game), MonitoredApp::Uwp(calculator)];
+
+        let same_exe = CandidateKind::Win32 {
+            exe_path: PathBuf::from(r"d:\games\portable\GAME.exe"),
+        };
+        // Another copy with the same process name is already matched by name
+        let same_name = CandidateKind::Win32 {
+            exe_path: PathBuf::from(r"E:\Backup\game.exe"),
+        };
+        let other_exe = CandidateKind::Win32 {
+            exe_path: PathBuf::from(r"D:\Games\Other\other.exe"),
+        };
+        let alias = CandidateKind::Win32 {
+            exe_path: PathBuf::from(r"D:\Games\Japan\Game_JP.exe"),
+        };
+        assert!(same_exe.is_monitored_by(&apps));
+        assert!(same_name.is_monitored_by(&apps));
+        assert!(alias.is_monitored_by(&apps));
+        assert!(!other_exe.is_monitored_by(&apps));
+
+        let calculator_app = CandidateKind::Uwp {
+            package_family_name: "microsoft.windowscalculator_8wekyb3d8bbwe".to_string(),
+            app_id: "App".to_string(),
+            multi_app_package: false,
+        };
+        let other_app_of_package = CandidateKind::Uwp {
+            package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
+            app_id: "Widget".to_string(),
+            multi_app_package: true,
+        };
+        assert!(calculator_app.is_monitored_by(&apps));
+        assert!(!other_app_of_package.is_monitored_by(&apps));
+
+        // Executables inside a watched folder are covered by it
+        let library = MonitoredApp::Folder(FolderApp {
+            id: Uuid::new_v4(),
+            display_name: "Other".to_string(),
+            path: PathBuf::from(r"D:\Games\Other\"),
+            enabled: true,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            icon_data: None,
+        });
+        assert!(other_exe.is_monitored_by(&[library]));
+    }
+
+    #[test]
+    fn test_fallback_uwp_candidate_name() {
+        let candidate = fallback_uwp_candidate(
+            "Microsoft.MinecraftUWP_8wekyb3d8bbwe".to_string(),
+            "App".to_string(),
+            false,
+        );
+        assert_eq!(candidate.display_name, "Microsoft.MinecraftUWP");
+        assert!(candidate.icon_data.is_none());
+    }
+}
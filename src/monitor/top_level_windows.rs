@@ -0,0 +1,230 @@
+//! Visible top-level windows of running processes
+//!
+//! Some games start helper processes, such as crash reporters, anti-cheat bootstrappers
+//! or shader compilers, under the game's own executable name before the real game window
+//! exists, which turns HDR on too early or toggles it on and off. With the
+//! `wait_for_window` preference the process monitor only reports a watched application
+//! as started once one of its processes, or a child process attributed to it, shows a
+//! visible top-level window. Applications that are already running stay running until
+//! their processes exit, whether or not their windows are still visible.
+//!
+//! UWP applications don't own their top-level window: it belongs to
+//! `ApplicationFrameHost.exe`, which hosts the application's own window as a child. For
+//! those frames the process of the hosted child window is the one that counts.
+
+use super::process_monitor::{AppIdentifier, WatchState};
+use std::collections::HashSet;
+
+/// Hold back applications that have no visible window yet
+///
+/// Removes from `current` every watched application that was not running before
+/// (`running`) and has no visible window (`windowed`), so the monitor reports it once
+/// the window appears. Excluded processes never wait for a window. Returns the
+/// applications held back.
+#[expect(
+    clippy::implicit_hasher,
+    reason = "Called with the process monitor's default-hashed sets only"
+)]
+pub fn hold_until_windowed(
+    current: &mut HashSet<AppIdentifier>,
+    running: &HashSet<AppIdentifier>,
+    windowed: &HashSet<AppIdentifier>,
+    state: &WatchState,
+) -> Vec<AppIdentifier> {
+    let held: Vec<_> = current
+        .iter()
+        .filter(|app_id| {
+            state.is_monitored(app_id)
+                && !state.is_excluded(app_id)
+                && !running.contains(*app_id)
+                && !windowed.contains(*app_id)
+        })
+        .cloned()
+        .collect();
+    for app_id in &held {
+        current.remove(app_id);
+    }
+    held
+}
+
+/// Processes owning a visible, unowned top-level window
+///
+/// Tool windows, such as floating palettes and tray helpers, don't count. For a UWP
+/// frame the process of the application window it hosts is included as well.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for enumerating top-level windows via EnumWindows"
+)]
+pub fn visible_window_pids() -> HashSet<u32> {
+    use windows::Win32::Foundation::{HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumChildWindows, EnumWindows, GW_OWNER, GWL_EXSTYLE, GetClassNameW, GetWindow,
+        GetWindowLongW, GetWindowThreadProcessId, IsWindowVisible, WS_EX_TOOLWINDOW,
+    };
+    use windows::core::BOOL;
+
+    /// Window class of the frames `ApplicationFrameHost.exe` draws around UWP apps
+    const UWP_FRAME_CLASS: &str = "ApplicationFrameWindow";
+
+    /// Processes found so far and the frame host whose children are being enumerated
+    struct Search {
+        pids: HashSet<u32>,
+        frame_host: u32,
+    }
+
+    unsafe extern "system" fn collect_hosted(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        // SAFETY: `lparam` is the `Search` passed to `EnumChildWindows` below, which
+        // outlives the enumeration
+        let search = unsafe { &mut *(lparam.0 as *mut Search) };
+
+        let mut pid = 0;
+        // SAFETY: `hwnd` is a window handle supplied by `EnumChildWindows`
+        if unsafe { GetWindowThreadProcessId(hwnd, Some(&raw mut pid)) } != 0
+            && pid != search.frame_host
+        {
+            search.pids.insert(pid);
+        }
+        BOOL::from(true)
+    }
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        // SAFETY: `lparam` is the `Search` passed to `EnumWindows` below, which outlives
+        // the enumeration, and callbacks run one at a time on this thread
+        let search = unsafe { &mut *(lparam.0 as *mut Search) };
+
+        // SAFETY: `hwnd` is a window handle supplied by `EnumWindows`
+        unsafe {
+            if !IsWindowVisible(hwnd).as_bool() || GetWindow(hwnd, GW_OWNER).is_ok() {
+                return BOOL::from(true);
+            }
+            #[expect(
+                clippy::cast_sign_loss,
+                reason = "Extended window styles are a bit mask read as a signed integer"
+            )]
+            let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+            if ex_style & WS_EX_TOOLWINDOW.0 != 0 {
+                return BOOL::from(true);
+            }
+
+            let mut pid = 0;
+            if GetWindowThreadProcessId(hwnd, Some(&raw mut pid)) == 0 {
+                return BOOL::from(true);
+            }
+            search.pids.insert(pid);
+
+            let mut class = [0u16; 64];
+            let len = usize::try_from(GetClassNameW(hwnd, &mut class)).unwrap_or(0);
+            if String::from_utf16_lossy(&class[..len]) == UWP_FRAME_CLASS {
+                search.frame_host = pid;
+                // Returns false when the frame hosts no window yet
+                let _ = EnumChildWindows(Some(hwnd), Some(collect_hosted), lparam);
+            }
+        }
+        BOOL::from(true)
+    }
+
+    let mut search = Search {
+        pids: HashSet::new(),
+        frame_host: 0,
+    };
+    // SAFETY: the callbacks only use `lparam` as the `Search` above while `EnumWindows`
+    // runs
+    if let Err(e) = unsafe { EnumWindows(Some(collect), LPARAM(&raw mut search as isize)) } {
+        tracing::debug!("Failed to enumerate top-level windows: {}", e);
+    }
+    search.pids
+}
+
+/// Windows only exist on Windows
+#[cfg(not(windows))]
+pub fn visible_window_pids() -> HashSet<u32> {
+    HashSet::new()
+}
+
+/// Process owning the foreground window, `None` if no window has the focus
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for reading the foreground window via GetForegroundWindow"
+)]
+pub fn foreground_window_pid() -> Option<u32> {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    // SAFETY: GetForegroundWindow takes no arguments and may return a null handle
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return None;
+    }
+    let mut pid = 0;
+    // SAFETY: `hwnd` was just returned by GetForegroundWindow and `pid` is a valid,
+    // writable u32 on the stack
+    (unsafe { GetWindowThreadProcessId(hwnd, Some(&raw mut pid)) } != 0).then_some(pid)
+}
+
+/// Windows only exist on Windows
+#[cfg(not(windows))]
+pub fn foreground_window_pid() -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppSource, MonitoredApp, Win32App};
+    use crate::hdr::AutoHdrMode;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn watch_state(process_names: &[&str]) -> WatchState {
+        let apps = process_names
+            .iter()
+            .map(|name| {
+                MonitoredApp::Win32(Win32App {
+                    id: Uuid::new_v4(),
+                    display_name: (*name).to_string(),
+                    exe_path: PathBuf::from(format!("C:\\Games\\{name}.exe")),
+                    process_name: (*name).to_string(),
+                    enabled: true,
+                    source: AppSource::Manual,
+                    auto_hdr: AutoHdrMode::Default,
+                    display_mode: None,
+                    hdr_disable_debounce_ms: None,
+                    max_hdr_session_minutes: None,
+                    color_mode: None,
+                    capture_tone_mapping: None,
+                    display_group: None,
+                    match_rule: None,
+                    aliases: Vec::new(),
+                    icon_data: None,
+                })
+            })
+            .collect();
+        WatchState::new()
+            .with_apps(apps, true)
+            .with_exclusions(&["obs64".to_string()])
+    }
+
+    fn win32(name: &str) -> AppIdentifier {
+        AppIdentifier::Win32(name.to_string())
+    }
+
+    #[test]
+    fn test_new_apps_wait_for_a_window() {
+        let state = watch_state(&["game", "other", "running"]);
+        let mut current: HashSet<_> = ["game", "other", "running", "obs64"]
+            .into_iter()
+            .map(win32)
+            .collect();
+        let running = HashSet::from([win32("running")]);
+        let windowed = HashSet::from([win32("other")]);
+
+        let held = hold_until_windowed(&mut current, &running, &windowed, &state);
+
+        assert_eq!(held, [win32("game")]);
+        assert_eq!(
+            current,
+            HashSet::from([win32("other"), win32("running"), win32("obs64")])
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! UWP applications reported by the app diagnostics watcher
+//!
+//! Identifying UWP apps from process handles is fragile: many apps run inside
+//! `ApplicationFrameHost`, and some of their processes cannot be opened at all. On builds
+//! that support it, the watcher in [`crate::uwp::detector`] is notified whenever an app
+//! starts or stops and records it here. While it runs, the process monitor leaves UWP
+//! identifiers to [`RunningUwpApps`] and only matches Win32 processes itself. When the
+//! watcher is unavailable or stops, process queries take over again.
+
+use super::process_monitor::{AppIdentifier, ProcessEvent, WatchState};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+
+/// Running UWP applications reported by the watcher (shared across watch list updates)
+#[derive(Debug, Default)]
+pub struct RunningUwpApps {
+    inner: Mutex<RunningUwpAppsInner>,
+}
+
+#[derive(Debug, Default)]
+struct RunningUwpAppsInner {
+    /// Whether the watcher is running and its reports are authoritative
+    active: bool,
+    /// Reported instances of each running application, by AUMID
+    running: HashMap<AppIdentifier, usize>,
+    /// Watched identifiers matched by the running applications at the last update
+    watched: HashSet<AppIdentifier>,
+}
+
+impl RunningUwpApps {
+    /// Whether the watcher reports UWP applications instead of process queries
+    pub fn is_active(&self) -> bool {
+        self.inner.lock().active
+    }
+
+    /// Start accepting reports from the watcher
+    pub fn activate(&self) {
+        let mut inner = self.inner.lock();
+        inner.active = true;
+        inner.running.clear();
+        inner.watched.clear();
+    }
+
+    /// Stop accepting reports, handing UWP detection back to process queries
+    ///
+    /// Returns `Stopped` events for the watched applications that were running, so the
+    /// controller does not keep sessions the process monitor will report again.
+    pub fn deactivate(&self) -> Vec<ProcessEvent> {
+        let mut inner = self.inner.lock();
+        inner.active = false;
+        inner.running.clear();
+        inner.watched.drain().map(ProcessEvent::Stopped).collect()
+    }
+
+    /// Record that an application instance started
+    pub fn app_added(&self, aumid: &str) {
+        let mut inner = self.inner.lock();
+        if inner.active {
+            *inner
+                .running
+                .entry(AppIdentifier::UwpApplication(aumid.to_string()))
+                .or_default() += 1;
+        }
+    }
+
+    /// Record that an application instance stopped
+    pub fn app_removed(&self, aumid: &str) {
+        let mut inner = self.inner.lock();
+        let app_id = AppIdentifier::UwpApplication(aumid.to_string());
+        if let Some(count) = inner.running.get_mut(&app_id) {
+            *count -= 1;
+            if *count == 0 {
+                inner.running.remove(&app_id);
+            }
+        }
+    }
+
+    /// Match the running applications against the watch list
+    ///
+    /// Returns the events for watched applications that started or stopped since the
+    /// previous update. Does nothing while the watcher is not running.
+    pub fn update(&self, state: &WatchState) -> Vec<ProcessEvent> {
+        let mut inner = self.inner.lock();
+        if !inner.active {
+            return Vec::new();
+        }
+
+        let mut watched = HashSet::new();
+        for app_id in inner.running.keys() {
+            state.insert_watched(app_id, &mut watched);
+        }
+
+        let mut events: Vec<ProcessEvent> = watched
+            .difference(&inner.watched)
+            .cloned()
+            .map(ProcessEvent::Started)
+            .collect();
+        events.extend(
+            inner
+                .watched
+                .difference(&watched)
+                .cloned()
+                .map(ProcessEvent::Stopped),
+        );
+        inner.watched = watched;
+        events
+    }
+
+    /// Watched identifiers matched at the last update
+    pub fn watched(&self) -> HashSet<AppIdentifier> {
+        self.inner.lock().watched.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MonitoredApp, UwpApp};
+
+    fn watch_state(match_app_id: bool) -> WatchState {
+        let mut app = UwpApp::from_package_info(
+            "Minecraft".to_string(),
+            "Microsoft.MinecraftUWP_8wekyb3d8bbwe".to_string(),
+            "App".to_string(),
+            None,
+        );
+        app.match_app_id = match_app_id;
+        WatchState::new().with_apps(vec![MonitoredApp::Uwp(app)], true)
+    }
+
+    #[test]
+    fn test_reports_are_ignored_until_activated() {
+        let apps = RunningUwpApps::default();
+        let state = watch_state(false);
+
+        apps.app_added("Microsoft.MinecraftUWP_8wekyb3d8bbwe!App");
+        assert!(!apps.is_active());
+        assert!(apps.update(&state).is_empty());
+        assert!(apps.watched().is_empty());
+    }
+
+    #[test]
+    fn test_started_and_stopped_follow_instances() {
+        let apps = RunningUwpApps::default();
+        let state = watch_state(true);
+        let app_id =
+            AppIdentifier::UwpApplication("Microsoft.MinecraftUWP_8wekyb3d8bbwe!App".to_string());
+        apps.activate();
+
+        // Unwatched apps produce no events
+        apps.app_added("Microsoft.WindowsCalculator_8wekyb3d8bbwe!App");
+        assert!(apps.update(&state).is_empty());
+
+        apps.app_added("Microsoft.MinecraftUWP_8wekyb3d8bbwe!App");
+        apps.app_added("microsoft.minecraftuwp_8wekyb3d8bbwe!app");
+        assert_eq!(
+            apps.update(&state),
+            vec![ProcessEvent::Started(app_id.clone())]
+        );
+
+        // The app keeps running while one instance is left
+        apps.app_removed("Microsoft.MinecraftUWP_8wekyb3d8bbwe!App");
+        assert!(apps.update(&state).is_empty());
+
+        apps.app_removed("Microsoft.MinecraftUWP_8wekyb3d8bbwe!App");
+        assert_eq!(apps.update(&state), vec![ProcessEvent::Stopped(app_id)]);
+    }
+
+    #[test]
+    fn test_package_entries_match_any_app_of_the_package() {
+        let apps = RunningUwpApps::default();
+        let state = watch_state(false);
+        let package_id = AppIdentifier::Uwp("Microsoft.MinecraftUWP_8wekyb3d8bbwe".to_string());
+        apps.activate();
+
+        apps.app_added("Microsoft.MinecraftUWP_8wekyb3d8bbwe!Launcher");
+        assert_eq!(
+            apps.update(&state),
+            vec![ProcessEvent::Started(package_id.clone())]
+        );
+        assert!(apps.watched().contains(&package_id));
+
+        // Watching nothing stops the running app
+        let empty = WatchState::new();
+        assert_eq!(apps.update(&empty), vec![ProcessEvent::Stopped(package_id)]);
+    }
+
+    #[test]
+    fn test_deactivate_stops_watched_apps() {
+        let apps = RunningUwpApps::default();
+        let state = watch_state(false);
+        apps.activate();
+        apps.app_added("Microsoft.MinecraftUWP_8wekyb3d8bbwe!App");
+        apps.update(&state);
+
+        assert_eq!(
+            apps.deactivate(),
+            vec![ProcessEvent::Stopped(AppIdentifier::Uwp(
+                "Microsoft.MinecraftUWP_8wekyb3d8bbwe".to_string()
+            ))]
+        );
+        assert!(!apps.is_active());
+        assert!(apps.watched().is_empty());
+    }
+}
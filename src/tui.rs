@@ -0,0 +1,343 @@
+//! Terminal user interface
+//!
+//! A console front-end for machines that are managed from a remote shell or rarely show
+//! the desktop, such as home theater PCs. It is a client of the background agent (see
+//! `easyhdr::controller::agent`): monitored applications can be listed, added by
+//! executable path, removed, enabled and disabled, HDR can be toggled by hand and pending
+//! actions cancelled, all over the agent's control channel. An agent is started if none
+//! runs, and it keeps monitoring after the terminal UI exits.
+//!
+//! Built with the `tui` feature and started with `easyhdr --tui`.
+
+use easyhdr::controller::agent::{self, AgentCommand, AgentResponse, AgentState};
+use easyhdr::error::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// How long to wait for a key press before redrawing
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often the agent's state is read while no key is pressed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait for a newly started agent to answer
+///
+/// Longer than the agent waits for the single-instance lock, so a window still holding
+/// it is reported instead of a timeout.
+const AGENT_START_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Key bindings shown at the bottom of the screen
+const HELP: &str =
+    "↑/↓ select  space enable/disable  a add  d remove  h toggle HDR  c cancel pending  q quit";
+
+/// Start the terminal UI as a client of the background agent and run it until the user
+/// quits
+pub fn run() -> Result<()> {
+    use tracing::info;
+
+    #[cfg(windows)]
+    attach_console();
+
+    let state = connect()?;
+
+    info!("Starting terminal UI");
+    let mut tui = TuiController::new(state);
+    let mut terminal = ratatui::init();
+    let result = tui.event_loop(&mut terminal);
+    ratatui::restore();
+
+    info!("Terminal UI closed, the background agent keeps running");
+    result
+}
+
+/// Read the state of the running agent, starting one if none answers
+fn connect() -> Result<AgentState> {
+    use tracing::info;
+
+    if let Ok(AgentResponse::State(state)) = agent::send(AgentCommand::State) {
+        return Ok(state);
+    }
+
+    info!("No background agent answered, starting one");
+    agent::spawn_process()?;
+    let deadline = Instant::now() + AGENT_START_TIMEOUT;
+    loop {
+        std::thread::sleep(Duration::from_millis(200));
+        match agent::send(AgentCommand::State) {
+            Ok(AgentResponse::State(state)) => return Ok(state),
+            _ if Instant::now() < deadline => {}
+            Ok(response) => {
+                return Err(io::Error::other(format!(
+                    "Unexpected answer from the background agent: {response:?}"
+                ))
+                .into());
+            }
+            Err(e) => {
+                return Err(io::Error::other(format!(
+                    "The background agent did not start ({e}). If the EasyHDR window is \
+                     open, enable running in the background and close it first."
+                ))
+                .into());
+            }
+        }
+    }
+}
+
+/// Terminal front-end over the background agent
+struct TuiController {
+    /// Latest state read from the agent, `None` while it does not answer
+    state: Option<AgentState>,
+    /// When the state is read next
+    next_refresh: Instant,
+    selected: ListState,
+    /// Executable path being typed after pressing `a`
+    input: Option<String>,
+    /// Result of the last action
+    status: String,
+}
+
+impl TuiController {
+    /// Create the terminal front-end showing the agent's `state`
+    fn new(state: AgentState) -> Self {
+        Self {
+            state: Some(state),
+            next_refresh: Instant::now() + REFRESH_INTERVAL,
+            selected: ListState::default().with_selected(Some(0)),
+            input: None,
+            status: String::new(),
+        }
+    }
+
+    /// Redraw and handle keys until the user quits
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            if Instant::now() >= self.next_refresh {
+                self.refresh();
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(POLL_INTERVAL)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle_key(key)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Read the agent's state
+    fn refresh(&mut self) {
+        match agent::send(AgentCommand::State) {
+            Ok(AgentResponse::State(state)) => self.state = Some(state),
+            Ok(response) => {
+                self.status = format!("Unexpected answer from the background agent: {response:?}");
+            }
+            Err(e) => {
+                self.state = None;
+                self.status = format!("The background agent did not answer: {e}");
+            }
+        }
+        self.next_refresh = Instant::now() + REFRESH_INTERVAL;
+    }
+
+    /// Send a command changing the controller and show its result
+    fn send(&mut self, command: AgentCommand) {
+        self.status = match agent::send(command) {
+            Ok(AgentResponse::Done { message } | AgentResponse::Error { message }) => message,
+            Ok(response) => format!("Unexpected answer from the background agent: {response:?}"),
+            Err(e) => format!("The background agent did not answer: {e}"),
+        };
+        self.refresh();
+    }
+
+    /// Handle a key press, returning `false` when the user quits
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+
+        if let Some(input) = &mut self.input {
+            match key.code {
+                KeyCode::Enter => {
+                    let exe_path = input.trim().trim_matches('"').to_string();
+                    self.input = None;
+                    if !exe_path.is_empty() {
+                        self.send(AgentCommand::AddApp { exe_path });
+                    }
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.selected.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.selected.select_next(),
+            KeyCode::Char(' ') => self.toggle_selected(),
+            KeyCode::Char('a') => self.input = Some(String::new()),
+            KeyCode::Char('d') | KeyCode::Delete => self.remove_selected(),
+            KeyCode::Char('h') => self.send(AgentCommand::ToggleHdr),
+            KeyCode::Char('c') => self.cancel_pending(),
+            _ => {}
+        }
+        true
+    }
+
+    /// The selected application's ID and enabled flag
+    fn selected_app(&self) -> Option<(uuid::Uuid, bool)> {
+        let apps = &self.state.as_ref()?.apps;
+        self.selected
+            .selected()
+            .and_then(|index| apps.get(index))
+            .map(|app| (app.id, app.enabled))
+    }
+
+    /// Enable or disable the selected application
+    fn toggle_selected(&mut self) {
+        if let Some((id, enabled)) = self.selected_app() {
+            self.send(AgentCommand::SetAppEnabled {
+                id,
+                enabled: !enabled,
+            });
+        }
+    }
+
+    /// Remove the selected application from the watch list
+    fn remove_selected(&mut self) {
+        if let Some((id, _)) = self.selected_app() {
+            self.send(AgentCommand::RemoveApp { id });
+        }
+    }
+
+    /// Cancel every pending controller action
+    fn cancel_pending(&mut self) {
+        let pending: Vec<_> = self
+            .state
+            .iter()
+            .flat_map(|state| &state.pending_actions)
+            .map(|action| action.id)
+            .collect();
+        if pending.is_empty() {
+            self.status = "Nothing pending".to_string();
+            return;
+        }
+
+        let cancelled = pending
+            .into_iter()
+            .filter(|&id| {
+                matches!(
+                    agent::send(AgentCommand::CancelPending { id }),
+                    Ok(AgentResponse::Done { .. })
+                )
+            })
+            .count();
+        self.status = format!("Cancelled {cancelled} pending action(s)");
+        self.refresh();
+    }
+
+    /// Render the status, application list, pending actions and key bindings
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header_area, apps_area, pending_area, footer_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Min(3),
+            Constraint::Length(4),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+
+        let header = match &self.state {
+            Some(state) => {
+                let hdr = match (state.hdr_enabled, state.dry_run) {
+                    (true, false) => "ON",
+                    (false, false) => "OFF",
+                    (true, true) => "ON (dry run)",
+                    (false, true) => "OFF (dry run)",
+                };
+                let mut lines = vec![Line::from(format!("HDR: {hdr}"))];
+                if let Some(session) = &state.manual_session {
+                    lines.push(Line::from(session.as_str()));
+                }
+                if let Some(error) = &state.hdr_toggle_error {
+                    lines.push(Line::from(format!("HDR toggle failed: {error}")));
+                }
+                lines
+            }
+            None => vec![Line::from("Waiting for the background agent...")],
+        };
+        frame.render_widget(
+            Paragraph::new(header).block(Block::bordered().title(" EasyHDR ")),
+            header_area,
+        );
+
+        let items: Vec<ListItem> = self
+            .state
+            .iter()
+            .flat_map(|state| &state.apps)
+            .map(|app| {
+                let mark = if app.enabled { "[x]" } else { "[ ]" };
+                ListItem::new(format!("{mark} {}", app.name))
+            })
+            .collect();
+        if items.is_empty() {
+            self.selected.select(None);
+        } else if self
+            .selected
+            .selected()
+            .is_none_or(|index| index >= items.len())
+        {
+            self.selected.select(Some(items.len() - 1));
+        }
+        let apps = List::new(items)
+            .block(Block::bordered().title(" Applications "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(apps, apps_area, &mut self.selected);
+
+        let pending: Vec<Line> = self
+            .state
+            .iter()
+            .flat_map(|state| &state.pending_actions)
+            .map(|action| Line::from(action.description.as_str()))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(pending).block(Block::bordered().title(" Pending ")),
+            pending_area,
+        );
+
+        let footer = match &self.input {
+            Some(input) => Paragraph::new(format!("{input}_"))
+                .block(Block::bordered().title(" Executable path (Enter to add, Esc to cancel) ")),
+            None => Paragraph::new(vec![Line::from(self.status.as_str()), Line::from(HELP)]),
+        };
+        frame.render_widget(footer, footer_area);
+    }
+}
+
+/// Attach to the console EasyHDR was started from, or open one
+///
+/// Release builds use the Windows GUI subsystem and start without a console.
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for console allocation")]
+fn attach_console() {
+    use windows::Win32::System::Console::{ATTACH_PARENT_PROCESS, AllocConsole, AttachConsole};
+
+    // Both fail when a console is already attached, which is fine
+    if unsafe { AttachConsole(ATTACH_PARENT_PROCESS) }.is_err() {
+        let _ = unsafe { AllocConsole() };
+    }
+}
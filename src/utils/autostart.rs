@@ -1,8 +1,11 @@
 //! Auto-start registry management
 //!
 //! Manages Windows auto-start functionality via registry entries in
-//! `HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Run`.
+//! `HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Run`, or through a
+//! Task Scheduler logon task (see [`crate::utils::logon_task`]) when the preferences
+//! select it.
 
+use crate::config::UserPreferences;
 use crate::error::Result;
 
 #[cfg(windows)]
@@ -64,17 +67,81 @@ impl AutoStartManager {
     /// Enable auto-start by creating a registry entry with the current executable path
     #[cfg(windows)]
     pub fn enable() -> Result<()> {
-        // Get the current executable path
-        let exe_path = std::env::current_exe().map_err(|e| {
+        Self::register(false)
+    }
+
+    /// Make auto-start match the preferences
+    ///
+    /// Registers EasyHDR with the selected method and removes the entry of the other
+    /// one, which moves an existing `Run` entry to a logon task and back. The new entry is
+    /// created first, so a failure leaves the old one in place.
+    #[cfg(windows)]
+    pub fn apply(preferences: &UserPreferences) -> Result<()> {
+        use crate::config::AutoStartMethod;
+        use crate::utils::logon_task::{self, LogonTask};
+
+        if !preferences.auto_start {
+            Self::disable()?;
+            return logon_task::delete();
+        }
+
+        match preferences.autostart_method {
+            AutoStartMethod::Registry => {
+                Self::register(preferences.run_in_background)?;
+                logon_task::delete()
+            }
+            AutoStartMethod::TaskScheduler => {
+                let exe_path = Self::exe_path()?;
+                LogonTask::for_current_user(
+                    exe_path.to_string_lossy().into_owned(),
+                    Self::launch_arguments(preferences.run_in_background).join(" "),
+                    preferences.autostart_elevated,
+                    preferences.autostart_delay_secs,
+                )?
+                .register()?;
+                Self::disable()
+            }
+        }
+    }
+
+    /// Arguments `EasyHDR` is started with at login
+    ///
+    /// Keeps portable mode even without a marker file, and starts the background agent
+    /// instead of the window if `background` is set.
+    pub fn launch_arguments(background: bool) -> Vec<&'static str> {
+        let mut arguments = Vec::new();
+        if crate::utils::data_dir::is_portable() {
+            arguments.push("--portable");
+        }
+        if background {
+            arguments.push(crate::controller::agent::AGENT_FLAG);
+        }
+        arguments
+    }
+
+    /// Location of the running executable
+    #[cfg(windows)]
+    fn exe_path() -> Result<std::path::PathBuf> {
+        std::env::current_exe().map_err(|e| {
             error!("Failed to get current executable path: {e}");
             EasyHdrError::ConfigError(crate::error::StringError::new(format!(
                 "Failed to determine application location: {e}"
             )))
-        })?;
+        })
+    }
+
+    /// Create the registry entry, starting the background agent if `background` is set
+    #[cfg(windows)]
+    fn register(background: bool) -> Result<()> {
+        let exe_path = Self::exe_path()?;
 
         // Quote the path to handle spaces (e.g., "C:\Program Files\EasyHDR\easyhdr.exe")
         // This respects Windows shell parsing rules per Platform Fit & OS Contracts guidelines
-        let quoted_path = format!("\"{}\"", exe_path.to_string_lossy());
+        let mut quoted_path = format!("\"{}\"", exe_path.to_string_lossy());
+        for argument in Self::launch_arguments(background) {
+            quoted_path.push(' ');
+            quoted_path.push_str(argument);
+        }
 
         // Open the registry key with write permissions
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
@@ -151,6 +218,12 @@ impl AutoStartManager {
         Ok(())
     }
 
+    /// Non-Windows stub for `apply`
+    #[cfg(not(windows))]
+    pub fn apply(_preferences: &UserPreferences) -> Result<()> {
+        Ok(())
+    }
+
     /// Non-Windows stub for disable
     #[cfg(not(windows))]
     pub fn disable() -> Result<()> {
@@ -160,8 +233,15 @@ impl AutoStartManager {
 
 #[cfg(test)]
 mod tests {
+    use super::AutoStartManager;
     #[cfg(windows)]
-    use super::{APP_NAME, AutoStartManager, RUN_KEY_PATH};
+    use super::{APP_NAME, RUN_KEY_PATH};
+
+    #[test]
+    fn test_launch_arguments() {
+        assert!(AutoStartManager::launch_arguments(false).is_empty());
+        assert_eq!(AutoStartManager::launch_arguments(true), vec!["--agent"]);
+    }
 
     /// Test that auto-start can be enabled and disabled
     ///
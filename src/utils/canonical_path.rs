@@ -0,0 +1,160 @@
+//! Canonical executable paths for matching and duplicate detection
+//!
+//! The same executable can be reached through several paths: Steam library folders are
+//! often junctions to another drive, `OneDrive` redirects Desktop and Documents, and users
+//! link game folders with `mklink`. Paths are compared through the resolved final path
+//! so all of them count as one application.
+//!
+//! Resolving a path touches the file system, so results are cached per process. Paths
+//! that cannot be resolved (missing files, offline drives) fall back to their lexical
+//! form and are not cached, so they resolve properly once they become available.
+
+use crate::utils::long_path;
+use crate::utils::unicode::fold_case;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Upper bound on cached paths; the cache is cleared when it is reached
+const MAX_CACHED_PATHS: usize = 1024;
+
+/// Resolved final paths, keyed by the path as given
+static CACHE: OnceLock<Mutex<HashMap<PathBuf, PathBuf>>> = OnceLock::new();
+
+/// Resolve symlinks and junctions to the final path of a file or directory
+///
+/// Returns the plain form of the resolved path (without the `\\?\` prefix that
+/// `std::fs::canonicalize` adds on Windows), or the path unchanged if it cannot be
+/// resolved.
+pub fn canonicalize(path: &Path) -> PathBuf {
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(resolved) = cache.lock().get(path) {
+        return resolved.clone();
+    }
+
+    let Ok(resolved) = std::fs::canonicalize(path) else {
+        return path.to_path_buf();
+    };
+    let resolved = long_path::to_plain(&resolved);
+
+    let mut cache = cache.lock();
+    if cache.len() >= MAX_CACHED_PATHS {
+        cache.clear();
+    }
+    cache.insert(path.to_path_buf(), resolved.clone());
+    resolved
+}
+
+/// Key identifying the file a path refers to
+///
+/// Paths reaching the same file through junctions or symlinks, or differing only in
+/// case or separator style, produce the same key.
+pub fn path_key(path: &Path) -> String {
+    lexical_key(&canonicalize(path))
+}
+
+/// Key of a path as written, without resolving it
+///
+/// Folds case and separator style only, so it is cheap enough for paths that are
+/// already final, such as process image paths reported by Windows.
+pub fn lexical_key(path: &Path) -> String {
+    fold_case(&path.to_string_lossy().replace('/', "\\"))
+}
+
+/// Whether the path with key `path_key` lies inside the directory with key `dir_key`
+pub fn key_is_inside(path_key: &str, dir_key: &str) -> bool {
+    let dir_key = dir_key.trim_end_matches('\\');
+    !dir_key.is_empty()
+        && path_key
+            .strip_prefix(dir_key)
+            .is_some_and(|rest| rest.starts_with('\\'))
+}
+
+/// Whether two paths refer to the same file
+pub fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || path_key(a) == path_key(b)
+}
+
+/// Forget all resolved paths, e.g. after a library folder was moved
+pub fn clear_cache() {
+    if let Some(cache) = CACHE.get() {
+        cache.lock().clear();
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_dir;
+
+    #[test]
+    fn test_unresolvable_paths_compare_lexically() {
+        let a = Path::new(r"C:\Games\Missing\Game.exe");
+        let b = Path::new("c:/games/missing/GAME.EXE");
+        assert!(same_path(a, b));
+        assert!(!same_path(a, Path::new(r"C:\Games\Other\Game.exe")));
+        assert_eq!(canonicalize(a), a);
+    }
+
+    #[test]
+    fn test_relative_components_resolve_to_same_file() {
+        let temp_dir = create_test_dir();
+        let game_dir = temp_dir.path().join("Game");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        let exe = game_dir.join("game.exe");
+        std::fs::write(&exe, b"MZ").unwrap();
+
+        let indirect = temp_dir
+            .path()
+            .join("Game")
+            .join("..")
+            .join("Game/game.exe");
+        assert!(same_path(&exe, &indirect));
+        assert_eq!(canonicalize(&indirect), canonicalize(&exe));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_library_resolves_to_same_file() {
+        let temp_dir = create_test_dir();
+        let library = temp_dir.path().join("SteamLibrary");
+        std::fs::create_dir_all(library.join("common")).unwrap();
+        let exe = library.join("common").join("game.exe");
+        std::fs::write(&exe, b"MZ").unwrap();
+
+        let link = temp_dir.path().join("LinkedLibrary");
+        std::os::unix::fs::symlink(&library, &link).unwrap();
+        let linked_exe = link.join("common").join("game.exe");
+
+        assert!(same_path(&exe, &linked_exe));
+        assert_eq!(path_key(&exe), path_key(&linked_exe));
+        clear_cache();
+        assert!(same_path(&linked_exe, &exe));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_symlinked_library_resolves_to_same_file() {
+        let temp_dir = create_test_dir();
+        let library = temp_dir.path().join("SteamLibrary");
+        std::fs::create_dir_all(library.join("common")).unwrap();
+        let exe = library.join("common").join("game.exe");
+        std::fs::write(&exe, b"MZ").unwrap();
+
+        // Creating directory symlinks needs developer mode or elevation
+        let link = temp_dir.path().join("LinkedLibrary");
+        if std::os::windows::fs::symlink_dir(&library, &link).is_err() {
+            return;
+        }
+        let linked_exe = link.join("common").join("game.exe");
+
+        assert!(same_path(&exe, &linked_exe));
+        assert!(
+            !canonicalize(&linked_exe)
+                .to_string_lossy()
+                .starts_with(r"\\?\")
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! COM initialization
+//!
+//! Shell and WMI helpers run on whatever thread calls them, so they initialize COM
+//! themselves. [`ComGuard`] initializes it for the calling thread and balances a
+//! successful initialization when dropped; threads already in another apartment keep it.
+
+use std::marker::PhantomData;
+use windows::Win32::System::Com::{COINIT, CoInitializeEx, CoUninitialize};
+
+/// COM initialization of the calling thread, undone on drop
+///
+/// Not `Send`, since COM must be uninitialized on the thread that initialized it.
+pub struct ComGuard {
+    /// Whether `CoInitializeEx` succeeded and needs balancing
+    initialized: bool,
+    /// Keeps the guard on its thread
+    _thread: PhantomData<*const ()>,
+}
+
+impl ComGuard {
+    /// Initialize COM for the calling thread in the given apartment
+    ///
+    /// `S_FALSE` (already initialized) also needs balancing; `RPC_E_CHANGED_MODE` means
+    /// the thread is in another apartment, where COM is usable too, and is not balanced.
+    ///
+    /// # Safety
+    ///
+    /// `CoInitializeEx` has no preconditions; the guard only uninitializes after it
+    /// succeeded, and stays on the thread it was created on.
+    #[expect(
+        unsafe_code,
+        reason = "Windows FFI for COM initialization via CoInitializeEx"
+    )]
+    pub fn init(apartment: COINIT) -> Self {
+        // SAFETY: has no preconditions
+        let initialized = unsafe { CoInitializeEx(None, apartment) }.is_ok();
+        Self {
+            initialized,
+            _thread: PhantomData,
+        }
+    }
+}
+
+impl Drop for ComGuard {
+    #[expect(
+        unsafe_code,
+        reason = "Windows FFI for balancing COM initialization via CoUninitialize"
+    )]
+    fn drop(&mut self) {
+        if self.initialized {
+            // SAFETY: CoInitializeEx succeeded on this thread, the guard is not Send
+            unsafe { CoUninitialize() };
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Location of `EasyHDR`'s data directory
+//!
+//! The configuration, icon cache, logs and the other data files normally live in
+//! `%APPDATA%\EasyHDR`. Portable mode keeps them next to the executable instead, so
+//! `EasyHDR` can run from a USB stick or a shared folder without touching the user profile.
+//! It is selected with the `--portable` flag or a [`PORTABLE_MARKER`] file next to the
+//! executable. Setting [`CONFIG_DIR_ENV`] picks any other directory and takes precedence
+//! over both, which also lets several users or setups keep separate configurations.
+//!
+//! `main` calls [`init`] before logging starts; until then, and in tests, the data
+//! directory follows `%APPDATA%`.
+
+use parking_lot::RwLock;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable naming the data directory
+pub const CONFIG_DIR_ENV: &str = "EASYHDR_CONFIG_DIR";
+
+/// File next to the executable that turns on portable mode
+pub const PORTABLE_MARKER: &str = "portable.txt";
+
+/// Data directory chosen by [`init`], `None` for `%APPDATA%\EasyHDR`
+static OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Whether [`init`] chose the directory of the executable
+static PORTABLE: AtomicBool = AtomicBool::new(false);
+
+/// Choose the data directory for this run
+///
+/// Returns the directory if it differs from the default.
+pub fn init(portable_flag: bool) -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let env_dir = std::env::var_os(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty());
+    let from_env = env_dir.is_some();
+    let dir = resolve(env_dir, portable_flag, exe_dir.as_deref());
+    PORTABLE.store(dir.is_some() && !from_env, Ordering::SeqCst);
+    OVERRIDE.write().clone_from(&dir);
+    dir
+}
+
+/// Pick the data directory from the environment variable, the flag and the marker file
+///
+/// Returns `None` for the default `%APPDATA%\EasyHDR`.
+pub fn resolve(
+    env_dir: Option<OsString>,
+    portable_flag: bool,
+    exe_dir: Option<&Path>,
+) -> Option<PathBuf> {
+    if let Some(dir) = env_dir.filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    let exe_dir = exe_dir?;
+    (portable_flag || exe_dir.join(PORTABLE_MARKER).is_file()).then(|| exe_dir.to_path_buf())
+}
+
+/// Whether the data directory is the directory of the executable
+pub fn is_portable() -> bool {
+    PORTABLE.load(Ordering::SeqCst)
+}
+
+/// Directory holding the configuration, icon cache and logs
+pub fn get_data_dir() -> PathBuf {
+    if let Some(dir) = OVERRIDE.read().as_ref() {
+        return dir.clone();
+    }
+    let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(appdata).join("EasyHDR")
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_dir;
+
+    #[test]
+    fn test_default_without_flag_or_marker() {
+        let exe_dir = create_test_dir();
+        assert_eq!(resolve(None, false, Some(exe_dir.path())), None);
+        assert_eq!(
+            resolve(Some(OsString::new()), false, Some(exe_dir.path())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_flag_and_marker_select_exe_dir() {
+        let exe_dir = create_test_dir();
+        assert_eq!(
+            resolve(None, true, Some(exe_dir.path())),
+            Some(exe_dir.path().to_path_buf())
+        );
+
+        std::fs::write(exe_dir.path().join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(
+            resolve(None, false, Some(exe_dir.path())),
+            Some(exe_dir.path().to_path_buf())
+        );
+    }
+
+    #[test]
+    fn test_environment_variable_takes_precedence() {
+        let exe_dir = create_test_dir();
+        std::fs::write(exe_dir.path().join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(
+            resolve(
+                Some(OsString::from("D:\\EasyHDR")),
+                true,
+                Some(exe_dir.path())
+            ),
+            Some(PathBuf::from("D:\\EasyHDR"))
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Process elevation detection
+//!
+//! Reports whether the current process runs with an elevated (administrator) token,
+//! for features whose Windows APIs are only available to elevated callers.
+
+/// Check whether the current process is running elevated
+///
+/// Returns `false` if the token cannot be queried.
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for process token queries")]
+pub fn is_elevated() -> bool {
+    use tracing::debug;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{
+        GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation,
+    };
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    let mut token = HANDLE::default();
+
+    // SAFETY: GetCurrentProcess returns a pseudo-handle that needs no cleanup;
+    // token is a valid out-pointer and is closed below on success.
+    if let Err(e) = unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &raw mut token) } {
+        debug!("OpenProcessToken failed: {}", e);
+        return false;
+    }
+
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut returned = 0u32;
+
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "size_of::<TOKEN_ELEVATION>() is a compile-time constant that fits in u32"
+    )]
+    let size = size_of::<TOKEN_ELEVATION>() as u32;
+
+    // SAFETY: elevation is a properly sized TOKEN_ELEVATION buffer and token is a
+    // valid handle opened with TOKEN_QUERY access.
+    let result = unsafe {
+        GetTokenInformation(
+            token,
+            TokenElevation,
+            Some((&raw mut elevation).cast()),
+            size,
+            &raw mut returned,
+        )
+    };
+
+    // SAFETY: token was opened successfully above and is not used afterwards.
+    unsafe {
+        let _ = CloseHandle(token);
+    }
+
+    match result {
+        Ok(()) => elevation.TokenIsElevated != 0,
+        Err(e) => {
+            debug!("GetTokenInformation(TokenElevation) failed: {}", e);
+            false
+        }
+    }
+}
+
+/// Non-Windows stub for `is_elevated`
+#[cfg(not(windows))]
+pub fn is_elevated() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_elevated_does_not_panic() {
+        // Result depends on how the test runner was started
+        let _ = is_elevated();
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_is_elevated_non_windows() {
+        assert!(!is_elevated());
+    }
+}
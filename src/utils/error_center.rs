@@ -0,0 +1,141 @@
+//! Recent errors collected in one place
+//!
+//! Failures `EasyHDR` recovered from, such as a background component the watchdog
+//! restarted after a panic or an HDR toggle that failed on every display, are recorded
+//! here besides the log file. The troubleshooting section of the settings dialog lists
+//! them, newest first. Entries are only kept in memory, at most [`MAX_ENTRIES`].
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+/// Number of entries kept; older ones are dropped
+pub const MAX_ENTRIES: usize = 50;
+
+/// Errors recorded by the running process
+static CENTER: Mutex<ErrorCenter> = Mutex::new(ErrorCenter::new());
+
+/// One recorded error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorEntry {
+    /// When the error happened
+    pub at: SystemTime,
+    /// Component that failed, e.g. "process monitoring"
+    pub component: String,
+    /// What went wrong
+    pub message: String,
+}
+
+impl ErrorEntry {
+    /// The error as one line, e.g. `14:03 · process monitoring: ...`
+    ///
+    /// Times are UTC, like the log file.
+    pub fn describe(&self) -> String {
+        use std::time::UNIX_EPOCH;
+
+        let secs = self
+            .at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        format!(
+            "{:02}:{:02} · {}: {}",
+            secs / 3600 % 24,
+            secs / 60 % 60,
+            self.component,
+            self.message
+        )
+    }
+}
+
+/// Bounded list of recorded errors
+#[derive(Debug, Default)]
+pub struct ErrorCenter {
+    /// Recorded errors, oldest first
+    entries: VecDeque<ErrorEntry>,
+}
+
+impl ErrorCenter {
+    /// Create an empty error center
+    pub const fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Record an error of `component`, dropping the oldest one when full
+    pub fn report(&mut self, component: &str, message: &str) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ErrorEntry {
+            at: SystemTime::now(),
+            component: component.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    /// Recorded errors, newest first
+    pub fn entries(&self) -> Vec<ErrorEntry> {
+        self.entries.iter().rev().cloned().collect()
+    }
+
+    /// Forget every recorded error
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Recorded errors as text, newest first and one per line
+    ///
+    /// Empty when nothing was recorded.
+    pub fn summary(&self) -> String {
+        self.entries
+            .iter()
+            .rev()
+            .map(ErrorEntry::describe)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Record an error of `component` in the error center of the process
+pub fn report(component: &str, message: &str) {
+    CENTER.lock().report(component, message);
+}
+
+/// Errors recorded by the process, newest first
+pub fn entries() -> Vec<ErrorEntry> {
+    CENTER.lock().entries()
+}
+
+/// Forget the errors recorded by the process
+pub fn clear() {
+    CENTER.lock().clear();
+}
+
+/// Errors recorded by the process as text, see [`ErrorCenter::summary`]
+pub fn summary() -> String {
+    CENTER.lock().summary()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_keeps_newest_entries() {
+        let mut center = ErrorCenter::new();
+        for index in 0..=MAX_ENTRIES {
+            center.report("process monitoring", &format!("error {index}"));
+        }
+
+        let entries = center.entries();
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0].message, format!("error {MAX_ENTRIES}"));
+        assert_eq!(entries[MAX_ENTRIES - 1].message, "error 1");
+        assert!(center.summary().contains("process monitoring: error 1"));
+
+        center.clear();
+        assert!(center.entries().is_empty());
+        assert!(center.summary().is_empty());
+    }
+}
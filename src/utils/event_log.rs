@@ -0,0 +1,186 @@
+//! Windows Event Log reporting
+//!
+//! When the `write_event_log` preference is on, significant events (HDR toggled, an HDR
+//! toggle that failed, a component that stopped) are written to the Application log
+//! under the `EasyHDR` source, so Event Viewer, scheduled-task triggers and monitoring
+//! agents can pick them up.
+//!
+//! Windows only shows the message text of a registered source. Registration writes to
+//! `HKLM` and needs administrator rights, so it is a separate step:
+//! `easyhdr --register-event-source`. Events from an unregistered source are still
+//! logged, with a "description cannot be found" note in front of the text.
+
+use crate::error::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Event source name shown in Event Viewer
+pub const SOURCE_NAME: &str = "EasyHDR";
+
+/// Registry key of the event source below `HKEY_LOCAL_MACHINE`
+#[cfg_attr(
+    not(windows),
+    expect(dead_code, reason = "Registry only exists on Windows")
+)]
+const SOURCE_KEY: &str = r"SYSTEM\CurrentControlSet\Services\EventLog\Application\EasyHDR";
+
+/// Whether events are written, set from the preferences
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(test)]
+thread_local! {
+    /// Events reported on this thread while a test records them, see [`record`]
+    static RECORDED: std::cell::RefCell<Option<Vec<(EventKind, String)>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Kinds of events written to the Event Log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// HDR was turned on or off (information)
+    HdrToggled,
+    /// HDR could not be turned on or off on any display (error)
+    HdrToggleFailed,
+    /// A background component stopped because of an internal error (warning)
+    ComponentStopped,
+}
+
+impl EventKind {
+    /// Event ID shown in Event Viewer, stable so alerts can filter on it
+    pub fn event_id(self) -> u32 {
+        match self {
+            Self::HdrToggled => 1000,
+            Self::HdrToggleFailed => 1001,
+            Self::ComponentStopped => 1002,
+        }
+    }
+}
+
+/// Turn writing to the Event Log on or off
+pub fn set_enabled(enabled: bool) {
+    use tracing::info;
+
+    if ENABLED.swap(enabled, Ordering::SeqCst) != enabled {
+        info!(
+            "Event Log reporting {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+    }
+}
+
+/// Whether events are written to the Event Log
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Write an event to the Application log if reporting is enabled
+///
+/// Failures are logged and otherwise ignored; the Event Log is a side channel.
+pub fn report(kind: EventKind, message: &str) {
+    #[cfg(test)]
+    RECORDED.with_borrow_mut(|recorded| {
+        if let Some(recorded) = recorded {
+            recorded.push((kind, message.to_string()));
+        }
+    });
+
+    if !is_enabled() {
+        return;
+    }
+
+    #[cfg(windows)]
+    if let Err(e) = report_windows(kind, message) {
+        use tracing::warn;
+        warn!("Failed to write {:?} to the Event Log: {}", kind, e);
+    }
+
+    #[cfg(not(windows))]
+    {
+        use tracing::debug;
+        debug!("Event Log not available, dropping {:?}: {}", kind, message);
+    }
+}
+
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for the Event Log")]
+fn report_windows(kind: EventKind, message: &str) -> windows::core::Result<()> {
+    use windows::Win32::System::EventLog::{
+        DeregisterEventSource, EVENTLOG_ERROR_TYPE, EVENTLOG_INFORMATION_TYPE,
+        EVENTLOG_WARNING_TYPE, RegisterEventSourceW, ReportEventW,
+    };
+    use windows::core::{HSTRING, PCWSTR};
+
+    let event_type = match kind {
+        EventKind::HdrToggled => EVENTLOG_INFORMATION_TYPE,
+        EventKind::HdrToggleFailed => EVENTLOG_ERROR_TYPE,
+        EventKind::ComponentStopped => EVENTLOG_WARNING_TYPE,
+    };
+    let text = HSTRING::from(message);
+    let strings = [PCWSTR(text.as_ptr())];
+
+    // Events are rare, so the source is opened per event rather than kept open
+    let handle = unsafe { RegisterEventSourceW(None, &HSTRING::from(SOURCE_NAME))? };
+    let result = unsafe {
+        ReportEventW(
+            handle,
+            event_type,
+            0,
+            kind.event_id(),
+            None,
+            0,
+            Some(&strings),
+            None,
+        )
+    };
+    let _ = unsafe { DeregisterEventSource(handle) };
+    result
+}
+
+/// Register `EasyHDR` as an Application log source
+///
+/// Uses the message file shipped with the .NET Framework, which passes the event text
+/// through unchanged. Needs administrator rights.
+#[cfg(windows)]
+pub fn register_source() -> Result<()> {
+    use std::path::PathBuf;
+    use tracing::info;
+    use winreg::RegKey;
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+
+    let windows_dir =
+        PathBuf::from(std::env::var_os("SystemRoot").unwrap_or_else(|| r"C:\Windows".into()));
+    let message_file = windows_dir
+        .join(r"Microsoft.NET\Framework64\v4.0.30319\EventLogMessages.dll")
+        .to_string_lossy()
+        .into_owned();
+
+    let (key, _) = RegKey::predef(HKEY_LOCAL_MACHINE).create_subkey(SOURCE_KEY)?;
+    key.set_value("EventMessageFile", &message_file)?;
+    // Error, warning and information events
+    key.set_value("TypesSupported", &7u32)?;
+
+    info!("Registered Event Log source {}", SOURCE_NAME);
+    Ok(())
+}
+
+/// The Event Log only exists on Windows
+#[cfg(not(windows))]
+pub fn register_source() -> Result<()> {
+    use crate::error::{EasyHdrError, StringError};
+
+    Err(EasyHdrError::ConfigError(StringError::new(
+        "The Windows Event Log is only available on Windows",
+    )))
+}
+
+/// Record the events reported on the current thread, whether or not they are written,
+/// until [`take_recorded`] is called
+#[cfg(test)]
+pub(crate) fn record() {
+    RECORDED.set(Some(Vec::new()));
+}
+
+/// Stop recording and return the events reported on the current thread since [`record`]
+#[cfg(test)]
+pub(crate) fn take_recorded() -> Vec<(EventKind, String)> {
+    RECORDED.take().unwrap_or_default()
+}
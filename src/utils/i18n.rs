@@ -0,0 +1,316 @@
+//! Translations of the main window, dialogs, notifications and the tray menu
+//!
+//! User-facing strings are looked up by key in string tables, one JSON object per
+//! language named after its tag, e.g. `de.json`. English and German are built in from
+//! the `locales` directory of the repository. Community translations are dropped into a
+//! `locales` folder next to the configuration file; a table there overrides or adds to
+//! the built-in one of the same language, so a translation can be tried without
+//! rebuilding `EasyHDR`.
+//!
+//! The language follows the Windows user locale unless the `language` preference names
+//! another one. Strings missing from a regional table such as `de-AT` fall back to the
+//! base language (`de`), then to English, so partial translations are usable. Values
+//! can contain `{name}` placeholders that [`tr_args`] fills in.
+
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+/// Language used for strings no other table has
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// String tables built into the executable, by language tag
+const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../../locales/en.json")),
+    ("de", include_str!("../../locales/de.json")),
+];
+
+/// Strings of one language by key
+pub type Table = HashMap<String, String>;
+
+/// Strings of the active language, merged over its fallbacks
+#[derive(Debug, Default)]
+struct Catalog {
+    /// Language tag the strings were loaded for
+    language: String,
+    /// Strings by key
+    strings: Table,
+}
+
+impl Catalog {
+    /// Merge the tables `tables_for` returns for `language` and its fallbacks
+    ///
+    /// Tables of more specific tags are applied last, so they win.
+    fn build(language: &str, tables_for: impl Fn(&str) -> Vec<Table>) -> Self {
+        let mut strings = Table::new();
+        for tag in candidates(language).iter().rev() {
+            for table in tables_for(tag) {
+                strings.extend(table);
+            }
+        }
+        Self {
+            language: language.to_string(),
+            strings,
+        }
+    }
+
+    /// Load the built-in and community tables of `language`
+    fn load(language: &str) -> Self {
+        Self::build(language, |tag| {
+            bundled_table(tag)
+                .into_iter()
+                .chain(community_table(tag))
+                .collect()
+        })
+    }
+}
+
+/// Active strings, following the Windows user locale until [`set_language`] is called
+static CATALOG: LazyLock<RwLock<Catalog>> = LazyLock::new(|| {
+    let language = system_language().unwrap_or_else(|| FALLBACK_LANGUAGE.to_string());
+    RwLock::new(Catalog::load(&language))
+});
+
+/// Switch to the language of the `language` preference
+///
+/// An empty preference follows the Windows user locale.
+pub fn set_language(preference: &str) {
+    use tracing::info;
+
+    let language = match normalize_tag(preference) {
+        Some(tag) => tag,
+        None => system_language().unwrap_or_else(|| FALLBACK_LANGUAGE.to_string()),
+    };
+    let catalog = Catalog::load(&language);
+    info!(
+        "Using language {} ({} strings)",
+        catalog.language,
+        catalog.strings.len()
+    );
+    *CATALOG.write() = catalog;
+}
+
+/// Tag of the active language, e.g. `de-DE`
+pub fn language() -> String {
+    CATALOG.read().language.clone()
+}
+
+/// String for `key` in the active language, the key itself if no table has it
+pub fn tr(key: &str) -> String {
+    CATALOG
+        .read()
+        .strings
+        .get(key)
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// String for `key` with its `{name}` placeholders replaced by `args`
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    fill(&tr(key), args)
+}
+
+/// Replace the `{name}` placeholders in `template`
+pub fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// Language tag in its usual form, e.g. `de_at` becomes `de-AT`
+///
+/// Returns `None` for an empty tag.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let mut parts = tag.trim().split(['-', '_']).filter(|part| !part.is_empty());
+    let mut normalized = parts.next()?.to_ascii_lowercase();
+    for part in parts {
+        normalized.push('-');
+        // Scripts are title case (Latn), regions upper case (AT)
+        if part.len() == 4 && part.is_ascii() {
+            normalized.push_str(&part[..1].to_ascii_uppercase());
+            normalized.push_str(&part[1..].to_ascii_lowercase());
+        } else {
+            normalized.push_str(&part.to_ascii_uppercase());
+        }
+    }
+    Some(normalized)
+}
+
+/// Tags to look strings up in, most specific first, e.g. `de-AT`, `de`, `en`
+pub fn candidates(language: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut tag = language;
+    while !tag.is_empty() {
+        tags.push(tag.to_string());
+        tag = tag.rsplit_once('-').map_or("", |(base, _)| base);
+    }
+    if !tags.iter().any(|tag| tag == FALLBACK_LANGUAGE) {
+        tags.push(FALLBACK_LANGUAGE.to_string());
+    }
+    tags
+}
+
+/// Parse a string table, a JSON object of strings
+///
+/// # Errors
+///
+/// Returns error if the text is not a JSON object of strings.
+pub fn parse_table(json: &str) -> serde_json::Result<Table> {
+    serde_json::from_str(json)
+}
+
+/// Built-in table of a language
+fn bundled_table(tag: &str) -> Option<Table> {
+    use tracing::error;
+
+    let (_, json) = BUNDLED
+        .iter()
+        .find(|(bundled, _)| bundled.eq_ignore_ascii_case(tag))?;
+    parse_table(json)
+        .inspect_err(|e| error!("Built-in {} strings are invalid: {}", tag, e))
+        .ok()
+}
+
+/// Folder holding community translations
+pub fn community_dir() -> PathBuf {
+    crate::config::ConfigManager::get_config_path().with_file_name("locales")
+}
+
+/// Community translation of a language, if one is installed
+fn community_table(tag: &str) -> Option<Table> {
+    use tracing::{info, warn};
+
+    let path = community_dir().join(format!("{tag}.json"));
+    let json = std::fs::read_to_string(&path).ok()?;
+    match parse_table(&json) {
+        Ok(table) => {
+            info!("Loaded {} strings from {:?}", table.len(), path);
+            Some(table)
+        }
+        Err(e) => {
+            warn!("Ignoring invalid translation {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Languages with a built-in or community table, by tag
+pub fn available_languages() -> Vec<String> {
+    let mut tags: Vec<String> = BUNDLED.iter().map(|(tag, _)| (*tag).to_string()).collect();
+    if let Ok(entries) = std::fs::read_dir(community_dir()) {
+        tags.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            normalize_tag(path.file_stem()?.to_str()?)
+        }));
+    }
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Language tag of the Windows user locale, e.g. `de-DE`
+///
+/// # Safety
+///
+/// The buffer is sized for the longest locale name and outlives the call, which writes
+/// at most its length.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for reading the user locale via GetUserDefaultLocaleName"
+)]
+pub fn system_language() -> Option<String> {
+    use windows::Win32::Globalization::GetUserDefaultLocaleName;
+
+    /// `LOCALE_NAME_MAX_LENGTH`, including the terminating null
+    const MAX_LENGTH: usize = 85;
+
+    let mut buffer = [0u16; MAX_LENGTH];
+    // SAFETY: the buffer is valid for writes of its whole length
+    let written = unsafe { GetUserDefaultLocaleName(&mut buffer) };
+    // The count includes the terminating null; zero means the call failed
+    let len = usize::try_from(written).ok()?.checked_sub(1)?;
+    normalize_tag(&String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// The Windows user locale only exists on Windows
+#[cfg(not(windows))]
+pub fn system_language() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_and_fallbacks() {
+        assert_eq!(normalize_tag(" de_at "), Some("de-AT".to_string()));
+        assert_eq!(normalize_tag("zh-hant-tw"), Some("zh-Hant-TW".to_string()));
+        assert_eq!(normalize_tag(""), None);
+        assert_eq!(candidates("de-AT"), ["de-AT", "de", "en"]);
+        assert_eq!(candidates("en-GB"), ["en-GB", "en"]);
+
+        let catalog = Catalog::build("de-AT", |tag| {
+            let table: &[(&str, &str)] = match tag {
+                "en" => &[("a", "A"), ("b", "B"), ("c", "C")],
+                "de" => &[("b", "B de"), ("c", "C de")],
+                "de-AT" => &[("c", "C at")],
+                _ => &[],
+            };
+            vec![
+                table
+                    .iter()
+                    .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+                    .collect(),
+            ]
+        });
+        assert_eq!(catalog.strings["a"], "A");
+        assert_eq!(catalog.strings["b"], "B de");
+        assert_eq!(catalog.strings["c"], "C at");
+
+        assert_eq!(
+            fill(
+                "Current: {current}, latest: {latest}",
+                &[("current", "1.0"), ("latest", "1.1")]
+            ),
+            "Current: 1.0, latest: 1.1"
+        );
+    }
+
+    #[test]
+    fn test_bundled_tables_match_english() {
+        let english = bundled_table(FALLBACK_LANGUAGE).unwrap();
+        for (tag, json) in BUNDLED {
+            let table = parse_table(json).unwrap();
+            assert!(table.contains_key("language-name"), "{tag} has no name");
+            for key in table.keys() {
+                assert!(english.contains_key(key), "{tag} has unknown key {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_main_window_labels_are_in_tables() {
+        let ui = include_str!("../../ui/main.slint");
+        let english = bundled_table(FALLBACK_LANGUAGE).unwrap();
+        let german = bundled_table("de").unwrap();
+
+        let keys: Vec<&str> = ["Strings.tr(\"", "Strings.tr-arg(\""]
+            .iter()
+            .flat_map(|call| ui.split(call).skip(1))
+            .filter_map(|rest| rest.split('"').next())
+            .collect();
+        assert!(!keys.is_empty());
+        for key in keys {
+            assert!(english.contains_key(key), "en has no {key}");
+            assert!(german.contains_key(key), "de has no {key}");
+        }
+    }
+}
@@ -16,6 +16,22 @@
 //! Each icon is 32x32 pixels in RGBA format (4096 bytes uncompressed).
 //! Cache validation uses file modification time comparison for Win32 apps.
 //!
+//! On displays scaled to 125% and more, the GUI shows larger icons from
+//! [`IconCache::get_icon`], stored next to the 32x32 one as `{uuid}@{size}.png` for the
+//! sizes in [`ICON_SIZES`]. They are extracted from the executable the first time they
+//! are needed; apps without one get their largest cached icon resized.
+//!
+//! Icons that no longer decode (truncated by a crash or disk error before atomic writes,
+//! or damaged on disk) are deleted when found, so the next load is a cache miss and the
+//! icon is extracted again instead of failing at every launch. [`IconCache::repair`]
+//! looks for them up front and runs once at startup.
+//!
+//! The startup maintenance also deletes icons of applications that are no longer
+//! configured ([`IconCache::prune`]) and keeps the cache below the `icon_cache_max_mb`
+//! preference by deleting the icons used least recently
+//! ([`IconCache::enforce_size_limit`]). Loading an icon marks it as used by updating its
+//! modification time.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -35,12 +51,26 @@
 //! # Ok::<(), easyhdr::error::EasyHdrError>(())
 //! ```
 
+use crate::config::AppConfig;
 use crate::error::{EasyHdrError, IconCacheError};
 use image::{ImageFormat, ImageReader, imageops::FilterType};
+use parking_lot::RwLock;
+use std::collections::HashSet;
 use std::io::{Cursor, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
 use uuid::Uuid;
 
+/// Size of the icons held in memory, in pixels
+pub const ICON_SIZE: u32 = 32;
+
+/// Icon sizes the cache stores, in pixels
+pub const ICON_SIZES: [u32; 3] = [ICON_SIZE, 48, 64];
+
+/// Age after which a temporary file no longer belongs to a write in progress
+const STALE_TEMP_FILE_AGE: std::time::Duration = std::time::Duration::from_mins(1);
+
 /// Result type alias for icon cache operations
 pub type Result<T> = std::result::Result<T, EasyHdrError>;
 
@@ -87,17 +117,10 @@ impl IconCache {
         Ok(Self { cache_dir })
     }
 
-    /// Get the default cache directory path (`%APPDATA%\EasyHDR\icon_cache` on Windows)
-    ///
-    /// # Panics
-    ///
-    /// Panics if `%APPDATA%` environment variable is not set.
+    /// Get the default cache directory path (`icon_cache` in the data directory,
+    /// `%APPDATA%\EasyHDR\icon_cache` unless portable mode is on)
     pub fn default_cache_dir() -> PathBuf {
-        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
-        let mut path = PathBuf::from(appdata);
-        path.push("EasyHDR");
-        path.push("icon_cache");
-        path
+        crate::utils::data_dir::get_data_dir().join("icon_cache")
     }
 
     /// Load an icon from cache with validation
@@ -110,7 +133,23 @@ impl IconCache {
     /// Returns `IconCacheError` if cache file cannot be read, PNG decoding fails,
     /// or file metadata cannot be accessed.
     pub fn load_icon(&self, app_id: Uuid, source_path: Option<&Path>) -> Result<Option<Vec<u8>>> {
-        let cache_path = self.cache_path(app_id);
+        self.load_icon_sized(app_id, ICON_SIZE, source_path)
+    }
+
+    /// Load an icon of the given size from cache with validation, like
+    /// [`IconCache::load_icon`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError` if cache file cannot be read, PNG decoding fails,
+    /// or file metadata cannot be accessed.
+    pub fn load_icon_sized(
+        &self,
+        app_id: Uuid,
+        size: u32,
+        source_path: Option<&Path>,
+    ) -> Result<Option<Vec<u8>>> {
+        let cache_path = self.cache_path_sized(app_id, size);
 
         // Return Ok(None) if cache file does not exist
         if !cache_path.exists() {
@@ -175,8 +214,25 @@ impl IconCache {
             })
         })?;
 
-        // Decode PNG to RGBA (this already returns proper errors)
-        let rgba_bytes = Self::decode_png_to_rgba(&png_bytes, app_id)?;
+        // Decode PNG to RGBA (this already returns proper errors); a corrupt file is
+        // removed so the icon is re-extracted instead of failing again next time
+        let rgba_bytes =
+            Self::decode_png_to_rgba_sized(&png_bytes, app_id, size).inspect_err(|_| {
+                tracing::warn!("Removing corrupt cached icon {}", cache_path.display());
+                if let Err(e) = std::fs::remove_file(&cache_path) {
+                    tracing::warn!("Failed to remove corrupt cached icon: {}", e);
+                }
+            })?;
+
+        // Mark the icon as used for the size limit. The new time is still later than the
+        // source file's, so the freshness check above is unaffected.
+        if let Err(e) = std::fs::File::options()
+            .write(true)
+            .open(&cache_path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            tracing::debug!("Failed to mark cached icon {} as used: {}", app_id, e);
+        }
 
         tracing::debug!(
             "Loaded icon for app {} from cache ({} bytes PNG -> {} bytes RGBA)",
@@ -198,12 +254,22 @@ impl IconCache {
     /// Returns `IconCacheError` if input size is not 4096 bytes, PNG encoding fails,
     /// temporary file creation fails, or atomic persist fails.
     pub fn save_icon(&self, app_id: Uuid, rgba_bytes: &[u8]) -> Result<()> {
+        self.save_icon_sized(app_id, ICON_SIZE, rgba_bytes)
+    }
+
+    /// Save an icon of the given size (`size` x `size` RGBA) to cache with atomic write
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError` if input size does not match, PNG encoding fails,
+    /// temporary file creation fails, or atomic persist fails.
+    pub fn save_icon_sized(&self, app_id: Uuid, size: u32, rgba_bytes: &[u8]) -> Result<()> {
         // Step 1 & 2: Validate size and encode RGBA to PNG
-        // (validation happens inside encode_rgba_to_png)
-        let png_bytes = Self::encode_rgba_to_png(rgba_bytes, app_id)?;
+        // (validation happens inside encode_rgba_to_png_sized)
+        let png_bytes = Self::encode_rgba_to_png_sized(rgba_bytes, app_id, size)?;
 
         // Get the final cache file path
-        let cache_file_path = self.cache_path(app_id);
+        let cache_file_path = self.cache_path_sized(app_id, size);
 
         // Step 3: Use tempfile::NamedTempFile::persist() for atomic write
         // (Atomic writes via tempfile)
@@ -253,39 +319,89 @@ impl IconCache {
         Ok(())
     }
 
-    /// Remove a single icon from cache (idempotent)
+    /// Remove the icons of an app, in every size, from cache (idempotent)
     ///
     /// # Errors
     ///
-    /// Returns `IconCacheError::IconRemovalFailed` if the file cannot be deleted.
+    /// Returns `IconCacheError::IconRemovalFailed` if a file cannot be deleted.
     pub fn remove_icon(&self, app_id: Uuid) -> Result<()> {
-        let cache_path = self.cache_path(app_id);
+        for size in ICON_SIZES {
+            let cache_path = self.cache_path_sized(app_id, size);
+
+            // Idempotent: OK if file doesn't exist
+            if !cache_path.exists() {
+                continue;
+            }
+
+            std::fs::remove_file(&cache_path).map_err(|source| {
+                EasyHdrError::IconCache(IconCacheError::IconRemovalFailed {
+                    app_id,
+                    path: cache_path.clone(),
+                    source,
+                })
+            })?;
 
-        // Idempotent: OK if file doesn't exist
-        if !cache_path.exists() {
             tracing::debug!(
-                "Icon file for app {} does not exist, nothing to remove",
-                app_id
+                "Removed cached icon for app {} from {}",
+                app_id,
+                cache_path.display()
             );
-            return Ok(());
         }
 
-        // Remove the cache file
-        std::fs::remove_file(&cache_path).map_err(|source| {
-            EasyHdrError::IconCache(IconCacheError::IconRemovalFailed {
-                app_id,
-                path: cache_path.clone(),
-                source,
-            })
-        })?;
+        Ok(())
+    }
 
-        tracing::debug!(
-            "Removed cached icon for app {} from {}",
-            app_id,
-            cache_path.display()
-        );
+    /// Get an icon of the given size, for high-DPI displays
+    ///
+    /// Returns the cached icon of that size if it is fresh. Otherwise the icon is
+    /// extracted from `source_path` (Win32 apps) and cached, or, without a source or when
+    /// extraction fails, the largest cached icon of the app is resized. Returns `Ok(None)`
+    /// if nothing is cached for the app.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError` if a cache file cannot be read or decoded, or file
+    /// metadata cannot be accessed.
+    pub fn get_icon(
+        &self,
+        app_id: Uuid,
+        size: u32,
+        source_path: Option<&Path>,
+    ) -> Result<Option<Vec<u8>>> {
+        if let Some(icon) = self.load_icon_sized(app_id, size, source_path)? {
+            return Ok(Some(icon));
+        }
 
-        Ok(())
+        if let Some(source) = source_path
+            && let Ok(icon) =
+                crate::utils::icon_extractor::extract_icon_from_exe_sized(source, size)
+            && !icon.is_empty()
+        {
+            if let Err(e) = self.save_icon_sized(app_id, size, &icon) {
+                tracing::warn!("Failed to cache {}px icon for app {}: {}", size, app_id, e);
+            }
+            return Ok(Some(icon));
+        }
+
+        for cached_size in ICON_SIZES.into_iter().rev().filter(|&s| s != size) {
+            if let Some(icon) = self.load_icon_sized(app_id, cached_size, None)? {
+                return Ok(Some(resize_rgba(icon, cached_size, size)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Icon size to show at a display scale factor: the smallest of [`ICON_SIZES`]
+    /// covering a 32x32 icon scaled by it
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Icon sizes are small integers that f32 represents exactly"
+    )]
+    pub fn icon_size_for_scale(scale_factor: f32) -> u32 {
+        ICON_SIZES
+            .into_iter()
+            .find(|&size| size as f32 >= ICON_SIZE as f32 * scale_factor - 0.01)
+            .unwrap_or(ICON_SIZES[ICON_SIZES.len() - 1])
     }
 
     /// Clear entire cache directory
@@ -406,6 +522,246 @@ impl IconCache {
         Ok(CacheStats { count, size_bytes })
     }
 
+    /// Check every cached icon for corruption without changing anything
+    ///
+    /// An icon is corrupt when it does not decode or its file name is not an app UUID.
+    /// Temporary files left behind by interrupted writes are reported separately.
+    fn verify(&self) -> Result<CacheVerification> {
+        let mut verification = CacheVerification::default();
+        if !self.cache_dir.exists() {
+            return Ok(verification);
+        }
+
+        let verification_error = |source| {
+            EasyHdrError::IconCache(IconCacheError::CacheVerificationFailed {
+                path: self.cache_dir.clone(),
+                source,
+            })
+        };
+
+        for entry in std::fs::read_dir(&self.cache_dir).map_err(verification_error)? {
+            let path = entry.map_err(verification_error)?.path();
+            match path.extension().and_then(|s| s.to_str()) {
+                Some("png") => {
+                    verification.checked += 1;
+                    let decodes = parse_icon_file_name(&path).is_some_and(|(app_id, size)| {
+                        std::fs::read(&path).is_ok_and(|png_bytes| {
+                            Self::decode_png_to_rgba_sized(&png_bytes, app_id, size).is_ok()
+                        })
+                    });
+                    if !decodes {
+                        verification.corrupt.push(path);
+                    }
+                }
+                // Recent ones may belong to a write in progress
+                Some("tmp")
+                    if std::fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_none_or(|age| age >= STALE_TEMP_FILE_AGE) =>
+                {
+                    verification.stale_temp_files.push(path);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(verification)
+    }
+
+    /// Delete corrupt icons and stale temporary files left in the cache
+    ///
+    /// Apps whose icon was deleted get a cache miss on the next load and their icon is
+    /// extracted again. Returns what was found; files that could not be deleted are
+    /// logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError::CacheVerificationFailed` if the cache directory cannot
+    /// be read.
+    pub fn repair(&self) -> Result<CacheVerification> {
+        let verification = self.verify()?;
+
+        for path in verification
+            .corrupt
+            .iter()
+            .chain(&verification.stale_temp_files)
+        {
+            if let Err(e) = std::fs::remove_file(path) {
+                tracing::warn!("Failed to remove {} from icon cache: {}", path.display(), e);
+            }
+        }
+
+        if verification.is_healthy() {
+            tracing::debug!(
+                "Icon cache verified: {} icons, no problems",
+                verification.checked
+            );
+        } else {
+            tracing::warn!(
+                "Icon cache repaired: removed {} corrupt icon(s) and {} temporary file(s) out of {} icons",
+                verification.corrupt.len(),
+                verification.stale_temp_files.len(),
+                verification.checked
+            );
+        }
+
+        Ok(verification)
+    }
+
+    /// Delete cached icons of applications that are not configured
+    ///
+    /// Icons not named after one of `valid_ids` are orphans, e.g. left behind when a
+    /// corrupt configuration was replaced. Returns the number of icons deleted; icons that
+    /// could not be deleted are logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError::CacheMaintenanceFailed` if the cache directory cannot be
+    /// read.
+    pub fn prune(&self, valid_ids: &[Uuid]) -> Result<usize> {
+        let valid_ids: HashSet<&Uuid> = valid_ids.iter().collect();
+        let mut removed = 0;
+
+        for icon in self.cached_icons()? {
+            if icon
+                .app_id
+                .is_some_and(|app_id| valid_ids.contains(&app_id))
+            {
+                continue;
+            }
+            match std::fs::remove_file(&icon.path) {
+                Ok(()) => removed += 1,
+                Err(e) => tracing::warn!(
+                    "Failed to remove orphaned icon {}: {}",
+                    icon.path.display(),
+                    e
+                ),
+            }
+        }
+
+        if removed > 0 {
+            tracing::info!("Removed {} orphaned icon(s) from the icon cache", removed);
+        }
+        Ok(removed)
+    }
+
+    /// Delete the least recently used icons until the cache holds at most `max_bytes`
+    ///
+    /// Returns the number of icons deleted; icons that could not be deleted are logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError::CacheMaintenanceFailed` if the cache directory cannot be
+    /// read.
+    pub fn enforce_size_limit(&self, max_bytes: u64) -> Result<usize> {
+        let mut icons = self.cached_icons()?;
+        let mut size_bytes: u64 = icons.iter().map(|icon| icon.size_bytes).sum();
+        if size_bytes <= max_bytes {
+            return Ok(0);
+        }
+
+        icons.sort_by_key(|icon| icon.last_used);
+        let mut removed = 0;
+        for icon in icons {
+            if size_bytes <= max_bytes {
+                break;
+            }
+            match std::fs::remove_file(&icon.path) {
+                Ok(()) => {
+                    size_bytes -= icon.size_bytes;
+                    removed += 1;
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to evict icon {} from the icon cache: {}",
+                    icon.path.display(),
+                    e
+                ),
+            }
+        }
+
+        tracing::info!(
+            "Evicted {} least recently used icon(s) to keep the icon cache below {} bytes",
+            removed,
+            max_bytes
+        );
+        Ok(removed)
+    }
+
+    /// Repair, prune and size-limit the default icon cache on a background thread
+    ///
+    /// `config` is the live configuration, and `max_bytes` the size limit, 0 for none.
+    /// Icons are pruned under the configuration's write lock against the applications
+    /// configured then, so an application added meanwhile keeps its icon. Failures are
+    /// logged but do not propagate errors (graceful degradation).
+    pub fn maintain_in_background(config: Arc<RwLock<AppConfig>>, max_bytes: u64) {
+        let spawned = std::thread::Builder::new()
+            .name("icon-cache-maintenance".to_string())
+            .spawn(move || {
+                let cache = match Self::new(Self::default_cache_dir()) {
+                    Ok(cache) => cache,
+                    Err(e) => {
+                        tracing::warn!("Failed to open icon cache for maintenance: {}", e);
+                        return;
+                    }
+                };
+                if let Err(e) = cache.repair() {
+                    tracing::warn!("Failed to verify icon cache: {}", e);
+                }
+                {
+                    let config = config.write();
+                    let valid_ids: Vec<Uuid> =
+                        config.monitored_apps.iter().map(|app| *app.id()).collect();
+                    if let Err(e) = cache.prune(&valid_ids) {
+                        tracing::warn!("Failed to remove orphaned icons: {}", e);
+                    }
+                }
+                if max_bytes > 0
+                    && let Err(e) = cache.enforce_size_limit(max_bytes)
+                {
+                    tracing::warn!("Failed to limit the icon cache size: {}", e);
+                }
+            });
+        if let Err(e) = spawned {
+            tracing::warn!("Failed to start icon cache maintenance: {}", e);
+        }
+    }
+
+    /// Cached icons with their size and last use, for maintenance
+    fn cached_icons(&self) -> Result<Vec<CachedIcon>> {
+        if !self.cache_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let maintenance_error = |source| {
+            EasyHdrError::IconCache(IconCacheError::CacheMaintenanceFailed {
+                path: self.cache_dir.clone(),
+                source,
+            })
+        };
+
+        let mut icons = Vec::new();
+        for entry in std::fs::read_dir(&self.cache_dir).map_err(maintenance_error)? {
+            let entry = entry.map_err(maintenance_error)?;
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("png") {
+                continue;
+            }
+            // Removed since the directory was read
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            icons.push(CachedIcon {
+                app_id: parse_icon_file_name(&path).map(|(app_id, _)| app_id),
+                size_bytes: metadata.len(),
+                last_used: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                path,
+            });
+        }
+        Ok(icons)
+    }
+
     /// Get the cache file path for an application
     ///
     /// Returns the full path to the cached icon file for the specified application.
@@ -418,8 +774,19 @@ impl IconCache {
     /// # Returns
     ///
     /// Returns the cache file path (`{cache_dir}/{uuid}.png`).
+    #[cfg(test)]
     fn cache_path(&self, app_id: Uuid) -> PathBuf {
-        self.cache_dir.join(format!("{app_id}.png"))
+        self.cache_path_sized(app_id, ICON_SIZE)
+    }
+
+    /// Get the cache file path for an icon of the given size (`{uuid}@{size}.png`, or
+    /// `{uuid}.png` for [`ICON_SIZE`])
+    fn cache_path_sized(&self, app_id: Uuid, size: u32) -> PathBuf {
+        if size == ICON_SIZE {
+            self.cache_dir.join(format!("{app_id}.png"))
+        } else {
+            self.cache_dir.join(format!("{app_id}@{size}.png"))
+        }
     }
 
     /// Cache icon data to disk with graceful error handling
@@ -451,11 +818,22 @@ impl IconCache {
     /// # Errors
     ///
     /// Returns `IconCacheError` if input size is not exactly 4096 bytes or PNG encoding fails.
+    #[cfg(test)]
     fn encode_rgba_to_png(rgba_bytes: &[u8], app_id: Uuid) -> Result<Vec<u8>> {
-        // Validate input size is exactly 4096 bytes (32x32 × 4 channels)
-        const EXPECTED_SIZE: usize = 32 * 32 * 4;
-        if rgba_bytes.len() != EXPECTED_SIZE {
+        Self::encode_rgba_to_png_sized(rgba_bytes, app_id, ICON_SIZE)
+    }
+
+    /// Encode `size` x `size` RGBA data to PNG format
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError` if input size does not match or PNG encoding fails.
+    fn encode_rgba_to_png_sized(rgba_bytes: &[u8], app_id: Uuid, size: u32) -> Result<Vec<u8>> {
+        // Validate input size is exactly size x size x 4 channels (4096 bytes for 32x32)
+        let expected = rgba_len(size);
+        if rgba_bytes.len() != expected {
             return Err(EasyHdrError::IconCache(IconCacheError::InvalidIconSize {
+                expected,
                 actual: rgba_bytes.len(),
             }));
         }
@@ -469,8 +847,8 @@ impl IconCache {
         image::write_buffer_with_format(
             &mut Cursor::new(&mut png_bytes),
             rgba_bytes,
-            32,
-            32,
+            size,
+            size,
             image::ExtendedColorType::Rgba8,
             ImageFormat::Png,
         )
@@ -489,7 +867,18 @@ impl IconCache {
     /// # Errors
     ///
     /// Returns `IconCacheError` if PNG decoding or resizing fails.
+    #[cfg(test)]
     fn decode_png_to_rgba(png_bytes: &[u8], app_id: Uuid) -> Result<Vec<u8>> {
+        Self::decode_png_to_rgba_sized(png_bytes, app_id, ICON_SIZE)
+    }
+
+    /// Decode PNG data to `size` x `size` RGBA, resampling like
+    /// [`IconCache::decode_png_to_rgba`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `IconCacheError` if PNG decoding or resizing fails.
+    fn decode_png_to_rgba_sized(png_bytes: &[u8], app_id: Uuid, size: u32) -> Result<Vec<u8>> {
         // Decode PNG from memory buffer
         let img = ImageReader::new(Cursor::new(png_bytes))
             .with_guessed_format()
@@ -506,18 +895,18 @@ impl IconCache {
                 EasyHdrError::IconCache(IconCacheError::PngDecodingError { app_id, source })
             })?;
 
-        // Resize to exactly 32x32 pixels using Lanczos3 filter
+        // Resize to exactly size x size pixels using Lanczos3 filter
         // Lanczos3 provides high-quality resampling with sharp edges
-        let resized = img.resize_exact(32, 32, FilterType::Lanczos3);
+        let resized = img.resize_exact(size, size, FilterType::Lanczos3);
 
         // Convert to RGBA8 format and extract raw bytes
         let rgba_img = resized.to_rgba8();
         let rgba_bytes = rgba_img.into_raw();
 
-        // Verify output size (should always be 4096 bytes)
+        // Verify output size (4096 bytes for 32x32)
         debug_assert_eq!(
             rgba_bytes.len(),
-            4096,
+            rgba_len(size),
             "PNG decode produced unexpected size"
         );
 
@@ -525,6 +914,64 @@ impl IconCache {
     }
 }
 
+/// Length of `size` x `size` RGBA data
+fn rgba_len(size: u32) -> usize {
+    let size = size as usize;
+    size * size * 4
+}
+
+/// Resize `from` x `from` RGBA data to `to` x `to` pixels using Lanczos3 resampling
+fn resize_rgba(rgba_bytes: Vec<u8>, from: u32, to: u32) -> Vec<u8> {
+    match image::RgbaImage::from_raw(from, from, rgba_bytes) {
+        Some(img) => image::imageops::resize(&img, to, to, FilterType::Lanczos3).into_raw(),
+        None => vec![0; rgba_len(to)],
+    }
+}
+
+/// App ID and size of a cached icon from its file name (`{uuid}.png` or
+/// `{uuid}@{size}.png`)
+fn parse_icon_file_name(path: &Path) -> Option<(Uuid, u32)> {
+    let stem = path.file_stem()?.to_str()?;
+    let (app_id, size) = match stem.split_once('@') {
+        Some((app_id, size)) => (app_id, size.parse().ok()?),
+        None => (stem, ICON_SIZE),
+    };
+    ICON_SIZES
+        .contains(&size)
+        .then(|| Uuid::parse_str(app_id).ok())
+        .flatten()
+        .map(|app_id| (app_id, size))
+}
+
+/// A cached icon file, as seen by the maintenance
+#[derive(Debug)]
+struct CachedIcon {
+    path: PathBuf,
+    /// App the icon belongs to, `None` if the file name is not a UUID
+    app_id: Option<Uuid>,
+    size_bytes: u64,
+    /// Last load or save
+    last_used: SystemTime,
+}
+
+/// Result of [`IconCache::repair`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheVerification {
+    /// Number of cached icons checked
+    pub checked: usize,
+    /// Icons that do not decode or are not named after an app UUID
+    pub corrupt: Vec<PathBuf>,
+    /// Temporary files left behind by interrupted writes
+    pub stale_temp_files: Vec<PathBuf>,
+}
+
+impl CacheVerification {
+    /// Whether no corrupt icons or temporary files were found
+    pub fn is_healthy(&self) -> bool {
+        self.corrupt.is_empty() && self.stale_temp_files.is_empty()
+    }
+}
+
 /// Cache statistics
 ///
 /// Contains metadata about the icon cache including count and total size.
@@ -599,7 +1046,8 @@ mod tests {
         let result = IconCache::encode_rgba_to_png(&invalid_small, app_id);
         let err = result.expect_err("Should fail with InvalidIconSize");
         match err {
-            EasyHdrError::IconCache(IconCacheError::InvalidIconSize { actual }) => {
+            EasyHdrError::IconCache(IconCacheError::InvalidIconSize { expected, actual }) => {
+                assert_eq!(expected, 4096);
                 assert_eq!(actual, 100);
             }
             other => panic!("Expected InvalidIconSize, got: {other:?}"),
@@ -610,7 +1058,8 @@ mod tests {
         let result = IconCache::encode_rgba_to_png(&invalid_large, app_id);
         let err = result.expect_err("Should fail with InvalidIconSize");
         match err {
-            EasyHdrError::IconCache(IconCacheError::InvalidIconSize { actual }) => {
+            EasyHdrError::IconCache(IconCacheError::InvalidIconSize { expected, actual }) => {
+                assert_eq!(expected, 4096);
                 assert_eq!(actual, 5000);
             }
             other => panic!("Expected InvalidIconSize, got: {other:?}"),
@@ -669,7 +1118,8 @@ mod tests {
 
         let err = result.expect_err("Invalid size should produce error");
         match err {
-            EasyHdrError::IconCache(IconCacheError::InvalidIconSize { actual }) => {
+            EasyHdrError::IconCache(IconCacheError::InvalidIconSize { expected, actual }) => {
+                assert_eq!(expected, 4096);
                 assert_eq!(actual, 100);
             }
             other => panic!("Expected InvalidIconSize, got: {other:?}"),
@@ -965,6 +1415,139 @@ mod tests {
             }
             other => panic!("Expected PngDecodingError, got: {other:?}"),
         }
+        assert!(!cache_path.exists(), "Corrupt icon should be removed");
+    }
+
+    #[test]
+    fn verify_and_repair_remove_corrupt_icons() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let cache = IconCache::new(temp_dir.path()).expect("Failed to create cache");
+        let good_id = Uuid::new_v4();
+        cache
+            .save_icon(good_id, &[200u8; 4096])
+            .expect("save_icon should succeed");
+
+        // A truncated icon and a temporary file from an interrupted write
+        let good_path = temp_dir.path().join(format!("{good_id}.png"));
+        let png_bytes = std::fs::read(&good_path).unwrap();
+        let truncated_path = temp_dir.path().join(format!("{}.png", Uuid::new_v4()));
+        std::fs::write(&truncated_path, &png_bytes[..png_bytes.len() / 2]).unwrap();
+        let temp_path = temp_dir.path().join(format!("{good_id}_abc.tmp"));
+        std::fs::write(&temp_path, b"partial").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&temp_path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - STALE_TEMP_FILE_AGE * 2)
+            .unwrap();
+
+        let verification = cache.verify().expect("verify should succeed");
+        assert_eq!(verification.checked, 2);
+        assert_eq!(verification.corrupt, vec![truncated_path.clone()]);
+        assert_eq!(verification.stale_temp_files, vec![temp_path.clone()]);
+        assert!(
+            truncated_path.exists(),
+            "verify should not change the cache"
+        );
+
+        let repaired = cache.repair().expect("repair should succeed");
+        assert_eq!(repaired, verification);
+        assert!(!truncated_path.exists());
+        assert!(!temp_path.exists());
+        assert!(good_path.exists());
+        assert!(cache.verify().unwrap().is_healthy());
+    }
+
+    #[test]
+    fn prune_removes_orphaned_icons() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let cache = IconCache::new(temp_dir.path()).expect("Failed to create cache");
+        let kept_id = Uuid::new_v4();
+        let orphan_id = Uuid::new_v4();
+        cache.save_icon(kept_id, &[10u8; 4096]).unwrap();
+        cache.save_icon(orphan_id, &[20u8; 4096]).unwrap();
+        let other_file = temp_dir.path().join("notes.txt");
+        std::fs::write(&other_file, b"not an icon").unwrap();
+
+        assert_eq!(cache.prune(&[kept_id]).unwrap(), 1);
+        assert!(cache.cache_path(kept_id).exists());
+        assert!(!cache.cache_path(orphan_id).exists());
+        assert!(other_file.exists(), "prune should only touch icons");
+    }
+
+    #[test]
+    fn enforce_size_limit_evicts_least_recently_used() {
+        use std::time::Duration;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let cache = IconCache::new(temp_dir.path()).expect("Failed to create cache");
+        let ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        for (age, id) in ids.iter().enumerate() {
+            cache.save_icon(*id, &[30u8; 4096]).unwrap();
+            let hours = u64::try_from(age).unwrap() + 1;
+            std::fs::File::options()
+                .write(true)
+                .open(cache.cache_path(*id))
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_secs(hours * 3600))
+                .unwrap();
+        }
+
+        // Loading the oldest icon makes it the most recently used
+        assert!(cache.load_icon(ids[2], None).unwrap().is_some());
+
+        let size_bytes = cache.get_cache_stats().unwrap().size_bytes;
+        assert_eq!(cache.enforce_size_limit(size_bytes).unwrap(), 0);
+        assert_eq!(cache.enforce_size_limit(size_bytes - 1).unwrap(), 1);
+        assert!(cache.cache_path(ids[0]).exists());
+        assert!(!cache.cache_path(ids[1]).exists());
+        assert!(cache.cache_path(ids[2]).exists());
+    }
+
+    #[test]
+    fn get_icon_resizes_the_largest_cached_icon() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let cache = IconCache::new(temp_dir.path()).expect("Failed to create cache");
+        let app_id = Uuid::new_v4();
+        assert!(cache.get_icon(app_id, 48, None).unwrap().is_none());
+
+        cache.save_icon(app_id, &[40u8; 4096]).unwrap();
+        cache
+            .save_icon_sized(app_id, 64, &[90u8; 64 * 64 * 4])
+            .unwrap();
+        assert!(temp_dir.path().join(format!("{app_id}@64.png")).exists());
+
+        let icon = cache.get_icon(app_id, 48, None).unwrap().unwrap();
+        assert_eq!(icon.len(), 48 * 48 * 4);
+        assert!(
+            icon.iter().all(|&byte| byte.abs_diff(90) <= 1),
+            "resized from 64x64"
+        );
+        assert_eq!(
+            cache.get_icon(app_id, 64, None).unwrap().unwrap(),
+            vec![90u8; 64 * 64 * 4]
+        );
+
+        // Sized icons are not corrupt, and go with the app
+        assert!(cache.verify().unwrap().is_healthy());
+        cache.remove_icon(app_id).unwrap();
+        assert!(cache.get_icon(app_id, 48, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn icon_size_for_scale_covers_the_scaled_icon() {
+        assert_eq!(IconCache::icon_size_for_scale(1.0), 32);
+        assert_eq!(IconCache::icon_size_for_scale(1.25), 48);
+        assert_eq!(IconCache::icon_size_for_scale(1.5), 48);
+        assert_eq!(IconCache::icon_size_for_scale(1.75), 64);
+        assert_eq!(IconCache::icon_size_for_scale(3.0), 64);
     }
 
     // Cache management operation tests
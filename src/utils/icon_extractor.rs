@@ -1,6 +1,9 @@
 //! Icon extraction from executables
 //!
 //! Extracts icons and display names from Windows executables using the Shell32 API.
+//! Icons are 32x32 RGBA; [`extract_icon_from_exe_sized`] extracts the larger sizes shown
+//! on high-DPI displays. Executables without an embedded icon use a same-named `.ico` or
+//! `.dll` next to them, or the icon the shell shows for them.
 
 use crate::error::Result;
 use std::path::Path;
@@ -8,6 +11,10 @@ use tracing::debug;
 
 #[cfg(windows)]
 use crate::error::EasyHdrError;
+#[cfg(windows)]
+use crate::utils::clean_display_name;
+#[cfg(windows)]
+use crate::utils::long_path::to_wide_extended;
 
 #[cfg(windows)]
 use tracing::warn;
@@ -15,13 +22,13 @@ use tracing::warn;
 #[cfg(windows)]
 use windows::Win32::Graphics::Gdi::{
     BI_RGB, BITMAP, BITMAPINFO, BITMAPINFOHEADER, CreateCompatibleDC, DIB_RGB_COLORS, DeleteDC,
-    DeleteObject, GetDIBits, GetObjectW, SelectObject,
+    DeleteObject, GetDIBits, GetObjectW, HBITMAP, SelectObject,
 };
 #[cfg(windows)]
 use windows::Win32::Storage::FileSystem::FILE_FLAGS_AND_ATTRIBUTES;
 #[cfg(windows)]
 use windows::Win32::UI::Shell::{
-    ExtractIconExW, SHFILEINFOW, SHGFI_ICON, SHGFI_LARGEICON, SHGetFileInfoW,
+    ExtractIconExW, SHDefExtractIconW, SHFILEINFOW, SHGFI_ICON, SHGFI_LARGEICON, SHGetFileInfoW,
 };
 #[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::HICON;
@@ -37,7 +44,9 @@ const ICON_SIZE: usize = 32;
 /// Extract icon from an executable file
 ///
 /// Uses Windows Shell32 `ExtractIconExW` to extract the application icon and convert it to
-/// raw RGBA bytes (32x32 pixels). Returns an empty Vec on extraction failure or non-Windows platforms.
+/// raw RGBA bytes (32x32 pixels). Executables without an embedded icon use a same-named
+/// `.ico` or `.dll` next to them, then the shell's icon for the file, then a placeholder.
+/// Returns an empty Vec on non-Windows platforms.
 pub fn extract_icon_from_exe(
     #[cfg_attr(
         not(windows),
@@ -61,14 +70,265 @@ pub fn extract_icon_from_exe(
     }
 }
 
+/// Extract an icon of the given size (`size` x `size` RGBA) from an executable
+///
+/// Asks the shell for the executable's icon at that size, so icons with large images
+/// stay sharp, with the same fallbacks as [`extract_icon_from_exe`] except the
+/// placeholder. Returns empty data if extraction fails; callers fall back to resizing the
+/// 32x32 icon.
+pub fn extract_icon_from_exe_sized(
+    #[cfg_attr(
+        not(windows),
+        expect(
+            unused_variables,
+            reason = "Parameter used only on Windows; non-Windows returns stub"
+        )
+    )]
+    path: &Path,
+    #[cfg_attr(
+        not(windows),
+        expect(
+            unused_variables,
+            reason = "Parameter used only on Windows; non-Windows returns stub"
+        )
+    )]
+    size: u32,
+) -> Result<Vec<u8>> {
+    #[cfg(windows)]
+    {
+        Ok(extract_icon_at_size_windows(path, size))
+    }
+
+    #[cfg(not(windows))]
+    {
+        debug!("Icon extraction not supported on non-Windows platforms");
+        Ok(Vec::new())
+    }
+}
+
+/// Windows-specific sized icon extraction
+///
+/// Tries the executable's own icon, then the companion files and the shell's image
+/// factory (see [`extract_icon_from_exe_windows`]).
+#[cfg(windows)]
+fn extract_icon_at_size_windows(path: &Path, size: u32) -> Vec<u8> {
+    extract_icon_using_shdefextracticon(path, size)
+        .or_else(|| extract_companion_icon(path, size))
+        .or_else(|| extract_icon_using_image_factory(path, size))
+        .unwrap_or_default()
+}
+
+/// Extract the first icon of an executable or DLL at the given size via `SHDefExtractIconW`
+///
+/// # Safety
+///
+/// The path is a NUL-terminated wide string that outlives the call, the icon handle
+/// returned by `SHDefExtractIconW` is owned here and destroyed after conversion.
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for sized icon extraction")]
+fn extract_icon_using_shdefextracticon(path: &Path, size: u32) -> Option<Vec<u8>> {
+    use std::os::windows::ffi::OsStrExt;
+
+    // Shell icon functions only understand plain paths
+    let wide_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut icon = HICON::default();
+
+    // SAFETY: see the function documentation
+    if let Err(e) = unsafe {
+        SHDefExtractIconW(
+            PCWSTR(wide_path.as_ptr()),
+            0, // First icon
+            0,
+            Some(&raw mut icon),
+            None, // No small icon
+            size, // Large icon size in the low word
+        )
+    }
+    .ok()
+    {
+        debug!(
+            "SHDefExtractIconW failed for {:?} at {}px: {}",
+            path, size, e
+        );
+        return None;
+    }
+
+    let icon_data = hicon_to_rgba_bytes(icon, size as usize)
+        .map_err(|e| warn!("Failed to convert {}px HICON to RGBA: {}", size, e))
+        .ok();
+    // SAFETY: the handle was returned by SHDefExtractIconW above and is not used again
+    unsafe {
+        let _ = DestroyIcon(icon);
+    }
+    icon_data
+}
+
+/// Icon from a file next to the executable with the same name
+///
+/// Some games ship an executable without an embedded icon, next to a `<name>.ico` file
+/// or a `<name>.dll` holding the icon resources. The `.ico` file is checked first.
+#[cfg_attr(
+    all(not(windows), not(test)),
+    expect(
+        dead_code,
+        reason = "Function used only on Windows for icon extraction"
+    )
+)]
+fn extract_companion_icon(exe_path: &Path, size: u32) -> Option<Vec<u8>> {
+    let ico_path = exe_path.with_extension("ico");
+    if ico_path.is_file()
+        && let Some(icon_data) = decode_icon_file(&ico_path, size)
+    {
+        debug!("Using companion icon file {:?}", ico_path);
+        return Some(icon_data);
+    }
+
+    #[cfg(windows)]
+    {
+        let dll_path = exe_path.with_extension("dll");
+        if dll_path.is_file()
+            && let Some(icon_data) = extract_icon_using_shdefextracticon(&dll_path, size)
+        {
+            debug!("Using icon resources of companion DLL {:?}", dll_path);
+            return Some(icon_data);
+        }
+    }
+
+    None
+}
+
+/// Decode an `.ico` file to `size` x `size` RGBA, scaling its largest image
+fn decode_icon_file(path: &Path, size: u32) -> Option<Vec<u8>> {
+    let decoded = image::ImageReader::open(path)
+        .map_err(image::ImageError::from)
+        .and_then(image::ImageReader::decode);
+    let image = match decoded {
+        Ok(image) => image.to_rgba8(),
+        Err(e) => {
+            debug!("Failed to decode icon file {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    let image = if image.dimensions() == (size, size) {
+        image
+    } else {
+        image::imageops::resize(&image, size, size, image::imageops::FilterType::Lanczos3)
+    };
+    Some(image.into_raw())
+}
+
+/// Icon of the given size from the shell's image factory
+///
+/// `IShellItemImageFactory` returns the icon Explorer shows for the file, scaled down
+/// from its largest (jumbo, 256x256) image, including icons supplied by icon handlers.
+///
+/// # Safety
+///
+/// COM is initialized for the calling thread and uninitialized again by
+/// [`ComGuard`](crate::utils::com::ComGuard) when it was initialized here. The path is a
+/// NUL-terminated wide string that outlives the call, and the bitmap returned by `GetImage`
+/// is owned here and deleted after conversion.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows COM FFI for icon extraction via IShellItemImageFactory"
+)]
+#[expect(
+    clippy::cast_possible_wrap,
+    reason = "Icon sizes are at most 256 pixels, well within i32 range"
+)]
+fn extract_icon_using_image_factory(path: &Path, size: u32) -> Option<Vec<u8>> {
+    use crate::utils::com::ComGuard;
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::Foundation::SIZE;
+    use windows::Win32::System::Com::COINIT_APARTMENTTHREADED;
+    use windows::Win32::UI::Shell::{
+        IShellItemImageFactory, SHCreateItemFromParsingName, SIIGBF_ICONONLY,
+    };
+
+    // Threads already in a multithreaded apartment keep it
+    let _com = ComGuard::init(COINIT_APARTMENTTHREADED);
+
+    let wide_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let image_size = SIZE {
+        cx: size as i32,
+        cy: size as i32,
+    };
+
+    // SAFETY: see the function documentation
+    let bitmap = match unsafe {
+        SHCreateItemFromParsingName::<_, _, IShellItemImageFactory>(
+            PCWSTR(wide_path.as_ptr()),
+            None,
+        )
+        .and_then(|factory| factory.GetImage(image_size, SIIGBF_ICONONLY))
+    } {
+        Ok(bitmap) => bitmap,
+        Err(e) => {
+            debug!(
+                "Shell image factory failed for {:?} at {}px: {}",
+                path, size, e
+            );
+            return None;
+        }
+    };
+
+    let icon_data = hbitmap_to_rgba_bytes(bitmap, size as usize);
+    // SAFETY: the bitmap was returned by GetImage above and is not used again
+    unsafe {
+        let _ = DeleteObject(bitmap.into());
+    }
+
+    match icon_data {
+        Ok(mut icon_data) => {
+            unpremultiply_alpha(&mut icon_data);
+            Some(icon_data)
+        }
+        Err(e) => {
+            warn!("Failed to convert shell image to RGBA: {}", e);
+            None
+        }
+    }
+}
+
+/// Undo the premultiplied alpha of shell bitmaps, in place
+#[cfg(windows)]
+fn unpremultiply_alpha(rgba: &mut [u8]) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        let alpha = u16::from(pixel[3]);
+        if alpha == 0 || alpha == 255 {
+            continue;
+        }
+        for channel in &mut pixel[..3] {
+            // Premultiplied channels never exceed alpha, so this fits into u8
+            *channel = u8::try_from(u16::from(*channel) * 255 / alpha).unwrap_or(u8::MAX);
+        }
+    }
+}
+
 /// Windows-specific icon extraction implementation
 ///
+/// The first icon embedded in the executable is used. Executables without one fall back
+/// to a companion `<name>.ico` or `<name>.dll` next to them, then to the icon the shell
+/// shows for the file (`IShellItemImageFactory`, then `SHGetFileInfoW`), and finally to a
+/// placeholder.
+///
 /// # Safety
 ///
 /// This function contains unsafe code that is sound because:
 ///
 /// 1. **Wide String Conversion**: The path is converted to a null-terminated wide string
-///    using the standard Windows FFI pattern (`encode_wide` + null terminator).
+///    using the standard Windows FFI pattern (`encode_wide` + null terminator), with the
+///    `\\?\` prefix added for paths longer than `MAX_PATH`.
 ///
 /// 2. **`ExtractIconExW`**: Called with:
 ///    - Valid null-terminated wide string pointer
@@ -86,60 +346,64 @@ pub fn extract_icon_from_exe(
 /// - Icon handles are only valid until `DestroyIcon` is called
 #[cfg(windows)]
 #[expect(unsafe_code, reason = "Windows FFI for icon extraction")]
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "ICON_SIZE is 32, well within u32::MAX"
+)]
 fn extract_icon_from_exe_windows(path: &Path) -> Vec<u8> {
     use std::os::windows::ffi::OsStrExt;
 
-    // Convert path to wide string for Windows API
-    let wide_path: Vec<u16> = path
-        .as_os_str()
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
+    // Convert path to wide string for Windows API (extended-length for deep installs)
+    let wide_path = to_wide_extended(path);
 
     debug!("Extracting icon from: {:?}", path);
 
     // Try to extract icon using ExtractIconExW
     let mut large_icon: HICON = HICON::default();
 
-    unsafe {
-        let result = ExtractIconExW(
+    let extracted = unsafe {
+        ExtractIconExW(
             PCWSTR(wide_path.as_ptr()),
             0, // Extract first icon
             Some(&raw mut large_icon),
             None, // We only need large icon
             1,    // Extract one icon
-        );
+        )
+    };
 
-        if result == 0 {
-            warn!(
-                "ExtractIconExW failed for {:?}, trying SHGetFileInfoW",
-                path
-            );
-            // Fallback to SHGetFileInfoW
-            return extract_icon_using_shgetfileinfo(&wide_path);
+    if extracted == 0 {
+        debug!("{:?} has no embedded icon, trying fallbacks", path);
+    } else {
+        // Convert HICON to RGBA bytes
+        let icon_data = hicon_to_rgba_bytes(large_icon, ICON_SIZE);
+
+        // Cleanup icon handle
+        unsafe {
+            let _ = DestroyIcon(large_icon);
         }
-    }
 
-    // Convert HICON to RGBA bytes
-    let icon_data = match hicon_to_rgba_bytes(large_icon) {
-        Ok(data) => data,
-        Err(e) => {
-            warn!("Failed to convert HICON to RGBA: {e}, using default icon");
-            // Cleanup icon handle
-            unsafe {
-                let _ = DestroyIcon(large_icon);
+        match icon_data {
+            Ok(data) => {
+                debug!("Successfully extracted icon: {} bytes", data.len());
+                return data;
             }
-            return create_default_icon();
+            Err(e) => warn!("Failed to convert HICON to RGBA: {e}, trying fallbacks"),
         }
-    };
+    }
 
-    // Cleanup icon handle
-    unsafe {
-        let _ = DestroyIcon(large_icon);
+    if let Some(icon_data) = extract_companion_icon(path, ICON_SIZE as u32)
+        .or_else(|| extract_icon_using_image_factory(path, ICON_SIZE as u32))
+    {
+        return icon_data;
     }
 
-    debug!("Successfully extracted icon: {} bytes", icon_data.len());
-    icon_data
+    // Last resort: SHGetFileInfoW, which only understands plain paths
+    let plain_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    extract_icon_using_shgetfileinfo(&plain_path)
 }
 
 /// Fallback icon extraction using `SHGetFileInfoW`
@@ -192,7 +456,7 @@ fn extract_icon_using_shgetfileinfo(wide_path: &[u16]) -> Vec<u8> {
             return create_default_icon();
         }
 
-        let icon_data = match hicon_to_rgba_bytes(file_info.hIcon) {
+        let icon_data = match hicon_to_rgba_bytes(file_info.hIcon, ICON_SIZE) {
             Ok(data) => data,
             Err(e) => {
                 tracing::warn!("Failed to convert HICON to RGBA: {}, using default icon", e);
@@ -208,41 +472,86 @@ fn extract_icon_using_shgetfileinfo(wide_path: &[u16]) -> Vec<u8> {
     }
 }
 
-/// Convert HICON to RGBA bytes (32x32 pixels, 4 bytes per pixel)
+/// Convert HICON to RGBA bytes (`size` x `size` pixels, 4 bytes per pixel)
 ///
 /// # Safety
 ///
 /// This function contains unsafe code that is sound because:
 ///
-/// 1. **`zeroed()` for Windows Structures**: Safe for ICONINFO, BITMAP, and BITMAPINFO
-///    because these are C-compatible structs where all-zeros is a valid initial state.
+/// 1. **`zeroed()` for ICONINFO**: Safe because ICONINFO is a C-compatible struct where
+///    all-zeros is a valid initial state.
 ///
 /// 2. **`GetIconInfo`**: Called with a valid HICON handle and mutable pointer to ICONINFO.
 ///    Returns bitmap handles that must be cleaned up with `DeleteObject`.
 ///
-/// 3. **`GetObjectW`**: Called with:
-///    - Valid bitmap handle from `GetIconInfo`
+/// 3. **Resource Cleanup**: Both bitmaps are deleted after conversion, whether or not it
+///    succeeded.
+///
+/// # Invariants
+///
+/// - `hicon` must be a valid HICON handle
+/// - The bitmaps returned by `GetIconInfo` must be deleted before returning
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for icon conversion to RGBA bytes")]
+fn hicon_to_rgba_bytes(hicon: HICON, size: usize) -> Result<Vec<u8>> {
+    use std::mem::zeroed;
+
+    unsafe {
+        // Get icon information
+        let mut icon_info: ICONINFO = zeroed();
+        if GetIconInfo(hicon, &raw mut icon_info).is_err() {
+            return Err(EasyHdrError::WindowsApiError(
+                windows::core::Error::from_thread(),
+            ));
+        }
+
+        let result = hbitmap_to_rgba_bytes(icon_info.hbmColor, size);
+
+        // We own both bitmaps returned by GetIconInfo
+        let _ = DeleteObject(icon_info.hbmColor.into());
+        let _ = DeleteObject(icon_info.hbmMask.into());
+
+        result
+    }
+}
+
+/// Convert a 32-bit bitmap to RGBA bytes (`size` x `size` pixels, 4 bytes per pixel)
+///
+/// The bitmap is not deleted; it stays owned by the caller.
+///
+/// # Safety
+///
+/// This function contains unsafe code that is sound because:
+///
+/// 1. **`zeroed()` for Windows Structures**: Safe for BITMAP and BITMAPINFO because these
+///    are C-compatible structs where all-zeros is a valid initial state.
+///
+/// 2. **`GetObjectW`**: Called with:
+///    - Valid bitmap handle from the caller
 ///    - Correct structure size
 ///    - Valid mutable pointer to BITMAP structure
 ///
-/// 4. **`CreateCompatibleDC`**: Creates a device context that must be cleaned up with `DeleteDC`.
+/// 3. **`CreateCompatibleDC`**: Creates a device context that must be cleaned up with `DeleteDC`.
 ///
-/// 5. **SelectObject/GetDIBits**: Called with valid handles and properly initialized structures:
+/// 4. **SelectObject/GetDIBits**: Called with valid handles and properly initialized structures:
 ///    - BITMAPINFO is initialized with correct size, dimensions, and format
 ///    - Buffer is pre-allocated with exact size (width * height * 4 bytes)
 ///    - Negative height in BITMAPINFO creates top-down DIB for correct orientation
 ///
-/// 6. **Resource Cleanup**: All handles (bitmaps, DC) are properly cleaned up via
-///    DeleteObject/DeleteDC to prevent resource leaks, even on error paths.
+/// 5. **Resource Cleanup**: The DC is cleaned up via `DeleteDC` to prevent resource leaks,
+///    even on error paths.
 ///
 /// # Invariants
 ///
-/// - `hicon` must be a valid HICON handle
-/// - All bitmap and DC handles must be cleaned up before returning
+/// - `bitmap` must be a valid bitmap handle
+/// - The DC must be cleaned up before returning
 /// - Buffer size must match width * height * 4 bytes
 /// - BITMAPINFO structure must have correct size and format fields
 #[cfg(windows)]
-#[expect(unsafe_code, reason = "Windows FFI for icon conversion to RGBA bytes")]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for bitmap conversion to RGBA bytes"
+)]
 #[expect(
     clippy::cast_possible_truncation,
     reason = "size_of::<BITMAP>() is a compile-time constant (32 bytes) well within i32::MAX"
@@ -255,52 +564,36 @@ fn extract_icon_using_shgetfileinfo(wide_path: &[u16]) -> Vec<u8> {
     clippy::cast_possible_wrap,
     reason = "Icon dimensions are typically 16-256 pixels, well within i32 range. Negative height is intentional for top-down DIB"
 )]
-fn hicon_to_rgba_bytes(hicon: HICON) -> Result<Vec<u8>> {
+fn hbitmap_to_rgba_bytes(bitmap_handle: HBITMAP, size: usize) -> Result<Vec<u8>> {
     use std::mem::zeroed;
 
     unsafe {
-        // Get icon information
-        let mut icon_info: ICONINFO = zeroed();
-        if GetIconInfo(hicon, &raw mut icon_info).is_err() {
-            return Err(EasyHdrError::WindowsApiError(
-                windows::core::Error::from_thread(),
-            ));
-        }
-
-        // We need to cleanup these bitmaps when done
-        let color_bitmap = icon_info.hbmColor;
-        let mask_bitmap = icon_info.hbmMask;
-
         // Get bitmap information
         let mut bitmap: BITMAP = zeroed();
         if GetObjectW(
-            color_bitmap.into(),
+            bitmap_handle.into(),
             std::mem::size_of::<BITMAP>() as i32,
             Some((&raw mut bitmap).cast()),
         ) == 0
         {
-            let _ = DeleteObject(color_bitmap.into());
-            let _ = DeleteObject(mask_bitmap.into());
             return Err(EasyHdrError::WindowsApiError(
                 windows::core::Error::from_thread(),
             ));
         }
 
         let width = bitmap.bmWidth as usize;
-        let height = bitmap.bmHeight as usize;
+        let height = bitmap.bmHeight.unsigned_abs() as usize;
 
         // Create a device context
         let hdc = CreateCompatibleDC(None);
         if hdc.is_invalid() {
-            let _ = DeleteObject(color_bitmap.into());
-            let _ = DeleteObject(mask_bitmap.into());
             return Err(EasyHdrError::WindowsApiError(
                 windows::core::Error::from_thread(),
             ));
         }
 
         // Select the bitmap into the DC
-        let old_bitmap = SelectObject(hdc, color_bitmap.into());
+        let old_bitmap = SelectObject(hdc, bitmap_handle.into());
 
         // Prepare BITMAPINFO structure
         let mut bmi: BITMAPINFO = zeroed();
@@ -317,7 +610,7 @@ fn hicon_to_rgba_bytes(hicon: HICON) -> Result<Vec<u8>> {
         // Get the bitmap bits
         let result = GetDIBits(
             hdc,
-            color_bitmap,
+            bitmap_handle,
             0,
             height as u32,
             Some(buffer.as_mut_ptr().cast()),
@@ -328,8 +621,6 @@ fn hicon_to_rgba_bytes(hicon: HICON) -> Result<Vec<u8>> {
         // Cleanup
         let _ = SelectObject(hdc, old_bitmap);
         let _ = DeleteDC(hdc);
-        let _ = DeleteObject(color_bitmap.into());
-        let _ = DeleteObject(mask_bitmap.into());
 
         if result == 0 {
             return Err(EasyHdrError::WindowsApiError(
@@ -342,9 +633,9 @@ fn hicon_to_rgba_bytes(hicon: HICON) -> Result<Vec<u8>> {
             buffer.swap(i, i + 2); // Swap B and R
         }
 
-        // Resize to standard icon size if needed
-        if width != ICON_SIZE || height != ICON_SIZE {
-            buffer = resize_icon_simple(&buffer, width, height, ICON_SIZE, ICON_SIZE);
+        // Resize to the requested icon size if needed
+        if width != size || height != size {
+            buffer = resize_icon_simple(&buffer, width, height, size, size);
         }
 
         Ok(buffer)
@@ -432,17 +723,12 @@ pub fn extract_display_name_from_exe(path: &Path) -> Result<String> {
 #[cfg(windows)]
 #[expect(unsafe_code, reason = "Windows FFI for version info extraction")]
 fn extract_display_name_windows(path: &Path) -> String {
-    use std::os::windows::ffi::OsStrExt;
     use windows::Win32::Storage::FileSystem::{
         GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
     };
 
-    // Convert path to wide string
-    let wide_path: Vec<u16> = path
-        .as_os_str()
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
+    // Convert path to wide string (extended-length for deep installs)
+    let wide_path = to_wide_extended(path);
 
     debug!("Extracting display name from: {:?}", path);
 
@@ -473,12 +759,22 @@ fn extract_display_name_windows(path: &Path) -> String {
         }
 
         // Query for FileDescription
-        // Try common language/codepage combinations
-        let queries = [
-            "\\StringFileInfo\\040904B0\\FileDescription\0", // English (US)
-            "\\StringFileInfo\\040904E4\\FileDescription\0", // English (US) Unicode
-            "\\StringFileInfo\\000004B0\\FileDescription\0", // Language neutral
-        ];
+        // Try the languages listed by the executable first so localized resources (e.g. a
+        // Japanese-only game) are found, then common language/codepage combinations
+        let mut queries: Vec<String> = version_translations(&buffer)
+            .into_iter()
+            .map(|(language, codepage)| {
+                format!("\\StringFileInfo\\{language:04X}{codepage:04X}\\FileDescription\0")
+            })
+            .collect();
+        queries.extend(
+            [
+                "\\StringFileInfo\\040904B0\\FileDescription\0", // English (US)
+                "\\StringFileInfo\\040904E4\\FileDescription\0", // English (US) Unicode
+                "\\StringFileInfo\\000004B0\\FileDescription\0", // Language neutral
+            ]
+            .map(String::from),
+        );
 
         for query in &queries {
             let query_wide: Vec<u16> = query.encode_utf16().collect();
@@ -505,6 +801,7 @@ fn extract_display_name_windows(path: &Path) -> String {
                     .unwrap_or(description_slice.len());
 
                 if let Ok(description) = String::from_utf16(&description_slice[..len])
+                    .map(|description| clean_display_name(&description))
                     && !description.is_empty()
                 {
                     debug!("Extracted display name: {}", description);
@@ -519,6 +816,43 @@ fn extract_display_name_windows(path: &Path) -> String {
     }
 }
 
+/// Read the language/codepage pairs listed in the version info translation table
+///
+/// # Safety
+///
+/// `buffer` holds data filled by `GetFileVersionInfoW`. `VerQueryValueW` returns a pointer
+/// into that buffer and its length in bytes, which bounds the slice.
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for version info translation table")]
+fn version_translations(buffer: &[u8]) -> Vec<(u16, u16)> {
+    use windows::Win32::Storage::FileSystem::VerQueryValueW;
+
+    let query: Vec<u16> = "\\VarFileInfo\\Translation\0".encode_utf16().collect();
+    let mut value_ptr: *mut u16 = std::ptr::null_mut();
+    let mut value_len: u32 = 0;
+
+    unsafe {
+        if !VerQueryValueW(
+            buffer.as_ptr().cast(),
+            PCWSTR(query.as_ptr()),
+            (&raw mut value_ptr).cast::<*mut _>(),
+            &raw mut value_len,
+        )
+        .as_bool()
+            || value_ptr.is_null()
+        {
+            return Vec::new();
+        }
+
+        // Each entry is a language ID followed by a codepage, both 16-bit
+        let words = std::slice::from_raw_parts(value_ptr, value_len as usize / 2);
+        words
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect()
+    }
+}
+
 /// Get filename without extension as fallback display name
 #[cfg(windows)]
 fn get_filename_fallback(path: &Path) -> String {
@@ -568,6 +902,37 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_companion_icon_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe_path = dir.path().join("Game.exe");
+        std::fs::write(&exe_path, b"").unwrap();
+        image::RgbaImage::from_pixel(64, 64, image::Rgba([200, 40, 10, 255]))
+            .save(dir.path().join("Game.ico"))
+            .unwrap();
+
+        let icon = extract_companion_icon(&exe_path, 32).unwrap();
+        assert_eq!(icon.len(), 32 * 32 * 4);
+        for pixel in icon.chunks_exact(4) {
+            assert!(pixel[0].abs_diff(200) <= 1 && pixel[1].abs_diff(40) <= 1);
+            assert_eq!(pixel[3], 255);
+        }
+    }
+
+    #[test]
+    fn test_companion_icon_needs_the_same_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe_path = dir.path().join("Game.exe");
+        std::fs::write(&exe_path, b"").unwrap();
+        image::RgbaImage::new(32, 32)
+            .save(dir.path().join("Other.ico"))
+            .unwrap();
+        std::fs::write(dir.path().join("Broken.ico"), b"not an icon").unwrap();
+
+        assert!(extract_companion_icon(&exe_path, 32).is_none());
+        assert!(extract_companion_icon(&dir.path().join("Broken.exe"), 32).is_none());
+    }
+
     #[cfg(windows)]
     #[test]
     fn test_create_default_icon_size() {
@@ -0,0 +1,227 @@
+//! Letter-tile fallback icons
+//!
+//! Generates a deterministic colored tile showing the first letter of an application's
+//! display name. Used whenever no icon can be extracted so the GUI never shows blank
+//! squares. Tiles use the same 32x32 RGBA layout as extracted icons and can be cached
+//! by `IconCache` like any other icon.
+
+/// Tile size in pixels (matches extracted icon size)
+const TILE_SIZE: usize = 32;
+
+/// Corner radius of the tile background in pixels
+const CORNER_RADIUS: usize = 5;
+
+/// Glyph scale factor applied to the 5x7 bitmap font
+const GLYPH_SCALE: usize = 3;
+
+/// Glyph bitmap width in font pixels
+const GLYPH_WIDTH: usize = 5;
+
+/// Glyph bitmap height in font pixels
+const GLYPH_HEIGHT: usize = 7;
+
+/// Background colors, chosen for contrast against white glyphs
+const PALETTE: [[u8; 3]; 8] = [
+    [0xE5, 0x39, 0x35], // Red
+    [0xD8, 0x1B, 0x60], // Pink
+    [0x8E, 0x24, 0xAA], // Purple
+    [0x39, 0x49, 0xAB], // Indigo
+    [0x1E, 0x88, 0xE5], // Blue
+    [0x00, 0x89, 0x7B], // Teal
+    [0x43, 0xA0, 0x47], // Green
+    [0xF4, 0x51, 0x1E], // Deep orange
+];
+
+/// Generate a 32x32 RGBA letter tile for a display name
+///
+/// The background color is derived from a hash of the (case-insensitive) name, so the
+/// same application always gets the same tile. The glyph is the first ASCII letter or
+/// digit of the name; names without one get a plain colored tile.
+pub fn generate_letter_tile(display_name: &str) -> Vec<u8> {
+    let [r, g, b] = tile_color(display_name);
+    let glyph = display_name
+        .chars()
+        .find(char::is_ascii_alphanumeric)
+        .and_then(glyph_for);
+
+    let mut rgba = vec![0u8; TILE_SIZE * TILE_SIZE * 4];
+
+    for y in 0..TILE_SIZE {
+        for x in 0..TILE_SIZE {
+            if !inside_rounded_rect(x, y) {
+                continue; // Transparent corner
+            }
+
+            let idx = (y * TILE_SIZE + x) * 4;
+            let pixel = if glyph.is_some_and(|rows| glyph_covers(rows, x, y)) {
+                [255, 255, 255, 255]
+            } else {
+                [r, g, b, 255]
+            };
+            rgba[idx..idx + 4].copy_from_slice(&pixel);
+        }
+    }
+
+    rgba
+}
+
+/// Pick a palette color from an FNV-1a hash of the lowercased name
+fn tile_color(display_name: &str) -> [u8; 3] {
+    let hash = display_name
+        .to_lowercase()
+        .bytes()
+        .fold(0x811C_9DC5_u32, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+        });
+
+    PALETTE[hash as usize % PALETTE.len()]
+}
+
+/// Check whether a pixel lies inside the tile's rounded background
+fn inside_rounded_rect(x: usize, y: usize) -> bool {
+    let max = TILE_SIZE - 1 - CORNER_RADIUS;
+    let cx = x.clamp(CORNER_RADIUS, max);
+    let cy = y.clamp(CORNER_RADIUS, max);
+    let (dx, dy) = (x.abs_diff(cx), y.abs_diff(cy));
+
+    dx * dx + dy * dy <= CORNER_RADIUS * CORNER_RADIUS
+}
+
+/// Check whether a pixel is covered by the scaled, centered glyph
+fn glyph_covers(rows: [u8; GLYPH_HEIGHT], x: usize, y: usize) -> bool {
+    const LEFT: usize = (TILE_SIZE - GLYPH_WIDTH * GLYPH_SCALE) / 2;
+    const TOP: usize = (TILE_SIZE - GLYPH_HEIGHT * GLYPH_SCALE) / 2;
+
+    if x < LEFT || y < TOP {
+        return false;
+    }
+
+    let (col, row) = ((x - LEFT) / GLYPH_SCALE, (y - TOP) / GLYPH_SCALE);
+    if col >= GLYPH_WIDTH || row >= GLYPH_HEIGHT {
+        return false;
+    }
+
+    rows[row] & (1 << (GLYPH_WIDTH - 1 - col)) != 0
+}
+
+/// Look up the 5x7 bitmap for an ASCII letter or digit (rows top to bottom, MSB left)
+fn glyph_for(c: char) -> Option<[u8; GLYPH_HEIGHT]> {
+    let rows = match c.to_ascii_uppercase() {
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        _ => return None,
+    };
+
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(rgba: &[u8], x: usize, y: usize) -> &[u8] {
+        let idx = (y * TILE_SIZE + x) * 4;
+        &rgba[idx..idx + 4]
+    }
+
+    #[test]
+    fn test_letter_tile_size() {
+        assert_eq!(generate_letter_tile("Calculator").len(), 32 * 32 * 4);
+        assert_eq!(generate_letter_tile("").len(), 32 * 32 * 4);
+    }
+
+    #[test]
+    fn test_letter_tile_is_deterministic() {
+        assert_eq!(
+            generate_letter_tile("Cyberpunk 2077"),
+            generate_letter_tile("Cyberpunk 2077")
+        );
+        // Color is case-insensitive
+        assert_eq!(tile_color("Steam"), tile_color("STEAM"));
+    }
+
+    #[test]
+    fn test_letter_tile_corners_are_transparent() {
+        let tile = generate_letter_tile("Calculator");
+        assert_eq!(pixel(&tile, 0, 0)[3], 0);
+        assert_eq!(pixel(&tile, 31, 31)[3], 0);
+        assert_eq!(pixel(&tile, 16, 1)[3], 255);
+    }
+
+    #[test]
+    fn test_letter_tile_unicode_names() {
+        // Non-ASCII names still get a deterministic, case-insensitive color
+        assert_eq!(generate_letter_tile("原神"), generate_letter_tile("原神"));
+        assert_eq!(tile_color("Überspiel"), tile_color("ÜBERSPIEL"));
+
+        // CJK names have no glyph in the bitmap font and produce a plain tile
+        let tile = generate_letter_tile("ゼルダの伝説");
+        assert_eq!(tile.len(), 32 * 32 * 4);
+        assert!(
+            tile.chunks_exact(4)
+                .all(|p| p[3] == 0 || p != [255, 255, 255, 255])
+        );
+
+        // Mixed names use their first ASCII letter or digit
+        assert_eq!(
+            generate_letter_tile("原神 Halo"),
+            generate_letter_tile("原神 Halo")
+        );
+        assert_eq!(
+            pixel(&generate_letter_tile("原神 Halo"), 9, 12),
+            [255, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn test_letter_tile_draws_glyph() {
+        let tile = generate_letter_tile("Halo");
+        let background = pixel(&tile, 16, 1).to_vec();
+
+        // Left stem of 'H' is white, center of the top row is background
+        assert_eq!(pixel(&tile, 9, 12), [255, 255, 255, 255]);
+        assert_eq!(pixel(&tile, 16, 6), background.as_slice());
+
+        // Names without ASCII letters or digits produce a plain tile
+        let plain = generate_letter_tile("---");
+        assert!(
+            plain
+                .chunks_exact(4)
+                .all(|p| p[3] == 0 || p != [255, 255, 255, 255])
+        );
+    }
+}
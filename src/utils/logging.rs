@@ -1,23 +1,125 @@
 //! Logging system initialization
 //!
-//! Sets up tracing-based logging with file output to %APPDATA%\EasyHDR\app.log
-//! and automatic rotation on application startup keeping 10 historical files.
+//! Sets up tracing-based logging with file output to `app.log` in the data directory
+//! (`%APPDATA%\EasyHDR` unless portable mode is on) and automatic rotation on application
+//! startup keeping 10 historical files.
+//!
+//! The log level and output format (plain text or one JSON object per line) come from
+//! `UserPreferences` and can be changed at runtime through [`set_log_level`] and
+//! [`set_log_format`] without restarting the application.
 
 use crate::error::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
-use tracing_subscriber::{EnvFilter, fmt};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry, fmt, reload};
 
 /// Maximum number of historical log files to keep (app.log.1 through app.log.9)
 const MAX_LOG_FILES: u8 = 9;
 
+/// Minimum severity of log messages written to the log file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    /// Errors only
+    Error,
+    /// Warnings and errors
+    Warn,
+    /// Informational messages, warnings and errors
+    #[default]
+    Info,
+    /// Debug output from `EasyHDR`
+    Debug,
+    /// Trace output from `EasyHDR` (very verbose)
+    Trace,
+}
+
+impl LogLevel {
+    /// All levels, from least to most verbose
+    pub const ALL: [Self; 5] = [
+        Self::Error,
+        Self::Warn,
+        Self::Info,
+        Self::Debug,
+        Self::Trace,
+    ];
+
+    /// Label used by the tray menu
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Error => "Error",
+            Self::Warn => "Warning",
+            Self::Info => "Info",
+            Self::Debug => "Debug",
+            Self::Trace => "Trace",
+        }
+    }
+
+    /// `EnvFilter` directives for the level
+    ///
+    /// Debug and trace only raise the verbosity of `EasyHDR` itself; dependencies such as
+    /// the GUI toolkit stay at info so the log remains readable.
+    fn directives(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "info,easyhdr=debug",
+            Self::Trace => "info,easyhdr=trace",
+        }
+    }
+}
+
+/// Log file output format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, for log viewers and ingestion tools
+    Json,
+}
+
+/// Handles for changing the installed subscriber at runtime
+struct LogControl {
+    /// Reload handle for the level filter
+    filter: reload::Handle<EnvFilter, Registry>,
+    /// Whether the JSON layer (instead of the text layer) writes events
+    json: Arc<AtomicBool>,
+}
+
+/// Runtime log control, set once by [`init_logging`]
+static LOG_CONTROL: OnceLock<LogControl> = OnceLock::new();
+
+/// Log file shared by the text and JSON layers
+#[derive(Clone)]
+struct SharedLogFile(Arc<Mutex<RollingFileAppender>>);
+
+impl io::Write for SharedLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().flush()
+    }
+}
+
 /// Initialize the logging system
 ///
-/// Log level defaults to INFO but can be configured via `RUST_LOG` environment variable.
-/// Rotates existing logs on startup to maintain a history of the last 10 sessions.
+/// Log level defaults to INFO but can be configured via `RUST_LOG` environment variable
+/// until [`set_log_level`] is called. Output starts as plain text until
+/// [`set_log_format`] selects JSON. Rotates existing logs on startup to maintain a
+/// history of the last 10 sessions.
 pub fn init_logging() -> Result<()> {
-    let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
-    let log_dir = PathBuf::from(appdata).join("EasyHDR");
+    let log_dir = crate::utils::data_dir::get_data_dir();
     std::fs::create_dir_all(&log_dir)?;
 
     // Rotate existing log files on startup
@@ -37,27 +139,91 @@ pub fn init_logging() -> Result<()> {
             crate::error::EasyHdrError::ConfigError(Box::new(e))
         })?;
 
-    // Build the subscriber with file output
-    let subscriber = fmt()
-        .with_writer(file_appender)
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .with_ansi(false) // Disable ANSI colors for file output
-        .with_target(true) // Include target module
-        .with_thread_ids(true) // Include thread IDs
-        .with_file(true) // Include file names
-        .with_line_number(true) // Include line numbers
-        .finish();
+    let file = SharedLogFile(Arc::new(Mutex::new(file_appender)));
+    let json = Arc::new(AtomicBool::new(false));
+
+    // Both formatters are installed; a per-layer filter lets exactly one of them write,
+    // so the format can be switched without replacing the subscriber
+    let text_layer = {
+        let file = file.clone();
+        let json = Arc::clone(&json);
+        fmt::layer()
+            .with_writer(move || file.clone())
+            .with_ansi(false) // Disable ANSI colors for file output
+            .with_target(true) // Include target module
+            .with_thread_ids(true) // Include thread IDs
+            .with_file(true) // Include file names
+            .with_line_number(true) // Include line numbers
+            .with_filter(filter_fn(move |_| !json.load(Ordering::Relaxed)))
+    };
+    let json_layer = {
+        let json = Arc::clone(&json);
+        fmt::layer()
+            .json()
+            .with_writer(move || file.clone())
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_current_span(true)
+            .with_filter(filter_fn(move |_| json.load(Ordering::Relaxed)))
+    };
+
+    let (filter, filter_handle) = reload::Layer::new(
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    );
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(text_layer)
+        .with(json_layer);
 
     tracing::subscriber::set_global_default(subscriber)
         .map_err(|e| crate::error::EasyHdrError::ConfigError(Box::new(e)))?;
 
+    let _ = LOG_CONTROL.set(LogControl {
+        filter: filter_handle,
+        json,
+    });
+
     tracing::info!("EasyHDR v{} started", env!("CARGO_PKG_VERSION"));
 
     Ok(())
 }
 
+/// Change the log level of the running application
+///
+/// Does nothing if logging has not been initialized.
+pub fn set_log_level(level: LogLevel) {
+    use tracing::{info, warn};
+
+    let Some(control) = LOG_CONTROL.get() else {
+        return;
+    };
+
+    match control.filter.reload(EnvFilter::new(level.directives())) {
+        Ok(()) => info!("Log level set to {}", level.label()),
+        Err(e) => warn!("Failed to change log level: {}", e),
+    }
+}
+
+/// Change the log file format of the running application
+///
+/// Does nothing if logging has not been initialized.
+pub fn set_log_format(format: LogFormat) {
+    use tracing::info;
+
+    let Some(control) = LOG_CONTROL.get() else {
+        return;
+    };
+
+    let json = format == LogFormat::Json;
+    if control.json.swap(json, Ordering::Relaxed) != json {
+        // Per-layer filter results are cached per callsite, so ask them again
+        tracing::callsite::rebuild_interest_cache();
+        info!("Log format set to {:?}", format);
+    }
+}
+
 /// Rotate log files on application startup
 ///
 /// Rotates existing logs to maintain a history of the last 10 application sessions:
@@ -124,6 +290,37 @@ mod tests {
     use std::fs;
     use std::io::Write;
 
+    #[test]
+    fn test_log_level_directives_parse() {
+        for level in LogLevel::ALL {
+            assert!(
+                EnvFilter::try_new(level.directives()).is_ok(),
+                "{level:?} directives should parse"
+            );
+        }
+        assert_eq!(LogLevel::default(), LogLevel::Info);
+    }
+
+    #[test]
+    fn test_log_settings_serialization() {
+        assert_eq!(
+            serde_json::to_string(&LogLevel::Debug).unwrap(),
+            "\"debug\""
+        );
+        assert_eq!(
+            serde_json::from_str::<LogFormat>("\"json\"").unwrap(),
+            LogFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_runtime_changes_before_init_are_ignored() {
+        // Tests never install the global subscriber, so these must be no-ops
+        set_log_level(LogLevel::Trace);
+        set_log_format(LogFormat::Json);
+        assert!(LOG_CONTROL.get().is_none());
+    }
+
     /// Helper function to create a test log file with specific content
     fn create_test_log(path: &PathBuf, content: &str) {
         let mut file = fs::File::create(path).unwrap();
@@ -0,0 +1,410 @@
+//! Auto-start through a Task Scheduler logon task
+//!
+//! Entries in the `Run` registry key always start without elevation, and Windows holds
+//! them back for a while after login on some systems. A logon task can instead start
+//! `EasyHDR` with highest privileges, which lets it see games running as administrator,
+//! and after a chosen delay. The task is registered with `schtasks.exe` from an XML
+//! definition; registering an elevated task needs an elevated `EasyHDR`.
+//!
+//! Task names are shared by all users of the PC, so each user's task is named after their
+//! SID (see [`task_name`]) and one user's settings never replace or delete another's task.
+
+use crate::error::Result;
+
+/// Name of the logon task in the Task Scheduler library before it included the user's
+/// SID, and the prefix of the current names
+pub const TASK_NAME: &str = "EasyHDR";
+
+/// Longest delay after login the task can be given
+pub const MAX_DELAY_SECS: u32 = 600;
+
+/// Definition of the logon task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogonTask {
+    /// Account the task runs for, as `DOMAIN\user`
+    pub user: String,
+    /// Executable to start
+    pub command: String,
+    /// Command-line arguments
+    pub arguments: String,
+    /// Whether the task starts with highest privileges
+    pub elevated: bool,
+    /// Seconds to wait after login, capped at [`MAX_DELAY_SECS`]
+    pub delay_secs: u32,
+}
+
+impl LogonTask {
+    /// Task Scheduler XML definition of the task
+    ///
+    /// The task only runs while the user is logged on, ignores battery power and has no
+    /// time limit, since `EasyHDR` keeps running for the whole session.
+    pub fn to_xml(&self) -> String {
+        let user = escape_xml(&self.user);
+        let delay = match self.delay_secs.min(MAX_DELAY_SECS) {
+            0 => String::new(),
+            secs => format!("\n      <Delay>PT{secs}S</Delay>"),
+        };
+        let run_level = if self.elevated {
+            "HighestAvailable"
+        } else {
+            "LeastPrivilege"
+        };
+        let arguments = if self.arguments.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n      <Arguments>{}</Arguments>",
+                escape_xml(&self.arguments)
+            )
+        };
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Description>Starts EasyHDR when you log in</Description>
+  </RegistrationInfo>
+  <Triggers>
+    <LogonTrigger>
+      <Enabled>true</Enabled>
+      <UserId>{user}</UserId>{delay}
+    </LogonTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Author">
+      <UserId>{user}</UserId>
+      <LogonType>InteractiveToken</LogonType>
+      <RunLevel>{run_level}</RunLevel>
+    </Principal>
+  </Principals>
+  <Settings>
+    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>
+    <Priority>7</Priority>
+  </Settings>
+  <Actions Context="Author">
+    <Exec>
+      <Command>{command}</Command>{arguments}
+    </Exec>
+  </Actions>
+</Task>
+"#,
+            command = escape_xml(&self.command),
+        )
+    }
+
+    /// Task for the current user
+    pub fn for_current_user(
+        command: String,
+        arguments: String,
+        elevated: bool,
+        delay_secs: u32,
+    ) -> Result<Self> {
+        Ok(Self {
+            user: current_user()?,
+            command,
+            arguments,
+            elevated,
+            delay_secs,
+        })
+    }
+
+    /// Create the task, replacing an existing one
+    #[cfg(windows)]
+    pub fn register(&self) -> Result<()> {
+        use crate::error::{EasyHdrError, StringError};
+        use std::ffi::OsStr;
+        use std::io::Write;
+        use tracing::info;
+
+        if self.elevated && !crate::utils::is_elevated() {
+            return Err(EasyHdrError::ConfigError(StringError::new(
+                "Run EasyHDR as administrator once to create a logon task that starts it \
+                 with administrator rights",
+            )));
+        }
+
+        // schtasks.exe reads the definition in the encoding its XML declaration names
+        let mut file = tempfile::Builder::new()
+            .prefix("easyhdr-task")
+            .suffix(".xml")
+            .tempfile()?;
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(self.to_xml().encode_utf16().flat_map(u16::to_le_bytes));
+        file.write_all(&bytes)?;
+        file.flush()?;
+
+        let name = current_task_name()?;
+        run_schtasks([
+            OsStr::new("/Create"),
+            OsStr::new("/TN"),
+            OsStr::new(&name),
+            OsStr::new("/XML"),
+            file.path().as_os_str(),
+            OsStr::new("/F"),
+        ])?;
+        delete_legacy_task();
+        info!(
+            "Auto-start logon task created (elevated: {}, delay: {}s)",
+            self.elevated, self.delay_secs
+        );
+        Ok(())
+    }
+
+    /// Task Scheduler only exists on Windows
+    #[cfg(not(windows))]
+    pub fn register(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Name of the logon task of the user with the given SID, e.g. `EasyHDR S-1-5-21-…`
+pub fn task_name(user_sid: &str) -> String {
+    format!("{TASK_NAME} {user_sid}")
+}
+
+/// Name of the current user's logon task
+#[cfg(windows)]
+fn current_task_name() -> Result<String> {
+    Ok(task_name(&current_user_sid()?))
+}
+
+/// Whether the current user's logon task exists
+#[cfg(windows)]
+pub fn exists() -> bool {
+    current_task_name().is_ok_and(|name| run_schtasks(["/Query", "/TN", &name]).is_ok())
+}
+
+/// Task Scheduler only exists on Windows
+#[cfg(not(windows))]
+pub fn exists() -> bool {
+    false
+}
+
+/// Delete the current user's logon task if it exists
+#[cfg(windows)]
+pub fn delete() -> Result<()> {
+    use tracing::info;
+
+    delete_legacy_task();
+    if !exists() {
+        return Ok(());
+    }
+    run_schtasks(["/Delete", "/TN", &current_task_name()?, "/F"])?;
+    info!("Auto-start logon task deleted");
+    Ok(())
+}
+
+/// Delete a task registered under the name used before it included the SID, if it runs
+/// for the current user
+///
+/// A task another user registered under that name is left alone.
+#[cfg(windows)]
+fn delete_legacy_task() {
+    use tracing::{info, warn};
+
+    let Ok(xml) = query_schtasks(["/Query", "/TN", TASK_NAME, "/XML"]) else {
+        return;
+    };
+    let (Ok(user), Ok(sid)) = (current_user(), current_user_sid()) else {
+        return;
+    };
+    if !runs_for_user(&xml, &user, &sid) {
+        return;
+    }
+    match run_schtasks(["/Delete", "/TN", TASK_NAME, "/F"]) {
+        Ok(()) => info!("Deleted the auto-start logon task named without the user's SID"),
+        Err(e) => warn!("Failed to delete the old auto-start logon task: {}", e),
+    }
+}
+
+/// Whether a task definition names `user` or `sid` as the account it runs for
+#[cfg_attr(
+    not(windows),
+    expect(dead_code, reason = "Only Windows has tasks to check")
+)]
+fn runs_for_user(xml: &str, user: &str, sid: &str) -> bool {
+    [user, sid].iter().any(|account| {
+        xml.to_lowercase().contains(&format!(
+            "<userid>{}</userid>",
+            escape_xml(account).to_lowercase()
+        ))
+    })
+}
+
+/// Current user as `DOMAIN\user`
+fn current_user() -> Result<String> {
+    use crate::error::{EasyHdrError, StringError};
+
+    let user = std::env::var("USERNAME").map_err(|_| {
+        EasyHdrError::ConfigError(StringError::new("Failed to determine the current user"))
+    })?;
+    Ok(match std::env::var("USERDOMAIN") {
+        Ok(domain) if !domain.is_empty() => format!("{domain}\\{user}"),
+        _ => user,
+    })
+}
+
+/// SID of the user the process runs as, e.g. `S-1-5-21-…-1001`
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for process token queries")]
+fn current_user_sid() -> Result<String> {
+    use crate::error::{EasyHdrError, StringError};
+    use windows::Win32::Foundation::{CloseHandle, HANDLE, HLOCAL, LocalFree};
+    use windows::Win32::Security::Authorization::ConvertSidToStringSidW;
+    use windows::Win32::Security::{GetTokenInformation, TOKEN_QUERY, TOKEN_USER, TokenUser};
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+    use windows::core::PWSTR;
+
+    let mut token = HANDLE::default();
+    // SAFETY: GetCurrentProcess returns a pseudo-handle that needs no cleanup;
+    // token is a valid out-pointer and is closed below.
+    unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &raw mut token) }?;
+
+    // The first call only reports the size of the SID that follows TOKEN_USER
+    let mut size = 0u32;
+    // SAFETY: token is a valid handle opened with TOKEN_QUERY access; no buffer is passed.
+    let _ = unsafe { GetTokenInformation(token, TokenUser, None, 0, &raw mut size) };
+    let mut buffer = vec![0u64; (size as usize).div_ceil(size_of::<u64>())];
+    // SAFETY: buffer holds at least `size` bytes, aligned for TOKEN_USER.
+    let result = unsafe {
+        GetTokenInformation(
+            token,
+            TokenUser,
+            Some(buffer.as_mut_ptr().cast()),
+            size,
+            &raw mut size,
+        )
+    };
+    // SAFETY: token was opened successfully above and is not used afterwards.
+    unsafe {
+        let _ = CloseHandle(token);
+    }
+    result?;
+
+    // SAFETY: GetTokenInformation filled the buffer with a TOKEN_USER whose SID points
+    // into the same buffer.
+    let sid = unsafe { (*buffer.as_ptr().cast::<TOKEN_USER>()).User.Sid };
+    let mut string = PWSTR::null();
+    // SAFETY: sid is valid while buffer lives; string is freed with LocalFree below.
+    unsafe { ConvertSidToStringSidW(sid, &raw mut string) }?;
+    // SAFETY: ConvertSidToStringSidW returned a null-terminated string.
+    let sid = unsafe { string.to_string() };
+    // SAFETY: string was allocated by ConvertSidToStringSidW and is not used afterwards.
+    unsafe {
+        let _ = LocalFree(Some(HLOCAL(string.0.cast())));
+    }
+    sid.map_err(|e| EasyHdrError::ConfigError(StringError::new(format!("Invalid user SID: {e}"))))
+}
+
+/// Task Scheduler only exists on Windows
+#[cfg(not(windows))]
+pub fn delete() -> Result<()> {
+    Ok(())
+}
+
+/// Run `schtasks.exe` without a console window, failing with its error output
+#[cfg(windows)]
+fn run_schtasks(args: impl IntoIterator<Item = impl AsRef<std::ffi::OsStr>>) -> Result<()> {
+    query_schtasks(args).map(drop)
+}
+
+/// Run `schtasks.exe` without a console window and return its output, failing with its
+/// error output
+#[cfg(windows)]
+fn query_schtasks(args: impl IntoIterator<Item = impl AsRef<std::ffi::OsStr>>) -> Result<String> {
+    use crate::error::{EasyHdrError, StringError};
+    use std::os::windows::process::CommandExt;
+
+    /// Keeps `schtasks.exe` from flashing a console window
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = std::process::Command::new("schtasks.exe")
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    Err(EasyHdrError::ConfigError(StringError::new(format!(
+        "Task Scheduler failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))))
+}
+
+/// Escape text for an XML element
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(elevated: bool, delay_secs: u32) -> LogonTask {
+        LogonTask {
+            user: r"DESKTOP\Gamer".to_string(),
+            command: r"C:\Games & Tools\EasyHDR\easyhdr.exe".to_string(),
+            arguments: "--agent".to_string(),
+            elevated,
+            delay_secs,
+        }
+    }
+
+    #[test]
+    fn test_xml_for_elevated_delayed_task() {
+        let xml = task(true, 30).to_xml();
+        assert!(xml.contains("<RunLevel>HighestAvailable</RunLevel>"));
+        assert!(xml.contains("<Delay>PT30S</Delay>"));
+        assert!(xml.contains(r"<UserId>DESKTOP\Gamer</UserId>"));
+        assert!(xml.contains(r"<Command>C:\Games &amp; Tools\EasyHDR\easyhdr.exe</Command>"));
+        assert!(xml.contains("<Arguments>--agent</Arguments>"));
+    }
+
+    #[test]
+    fn test_xml_without_delay_or_elevation() {
+        let xml = task(false, 0).to_xml();
+        assert!(xml.contains("<RunLevel>LeastPrivilege</RunLevel>"));
+        assert!(!xml.contains("<Delay>"));
+
+        let capped = task(false, 86_400).to_xml();
+        assert!(capped.contains(&format!("<Delay>PT{MAX_DELAY_SECS}S</Delay>")));
+    }
+
+    #[test]
+    fn test_task_name_includes_sid() {
+        let first = task_name("S-1-5-21-1-2-3-1001");
+        assert_eq!(first, "EasyHDR S-1-5-21-1-2-3-1001");
+        assert_ne!(first, task_name("S-1-5-21-1-2-3-1002"));
+    }
+
+    #[test]
+    fn test_runs_for_user() {
+        let xml = task(false, 0).to_xml();
+        assert!(runs_for_user(&xml, r"desktop\gamer", "S-1-5-21-1-2-3-1001"));
+        assert!(!runs_for_user(
+            &xml,
+            r"DESKTOP\Other",
+            "S-1-5-21-1-2-3-1002"
+        ));
+
+        let by_sid = xml.replace(r"DESKTOP\Gamer", "S-1-5-21-1-2-3-1001");
+        assert!(runs_for_user(
+            &by_sid,
+            r"DESKTOP\Other",
+            "S-1-5-21-1-2-3-1001"
+        ));
+    }
+}
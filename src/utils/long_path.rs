@@ -0,0 +1,195 @@
+//! Long path (`MAX_PATH`+) handling for executable paths
+//!
+//! Game stores nest installs deeply enough that executable paths regularly exceed the
+//! 260 character `MAX_PATH` limit. The Rust standard library already adds the `\\?\`
+//! prefix for its own file operations, but paths passed straight to Win32 APIs (icon
+//! extraction, version resources) need it added explicitly. The prefix is only an API
+//! detail: paths are stored in the configuration, compared and written to the registry
+//! in their plain form, so [`to_plain`] removes it again wherever paths enter `EasyHDR`.
+//!
+//! Process snapshots only report the executable file name, which is always shorter than
+//! `MAX_PATH`, so process matching is unaffected.
+//!
+//! Paths are handled as strings so the Windows rules also apply on other hosts.
+
+use std::path::{Path, PathBuf};
+
+/// Maximum length of a path accepted by Win32 APIs without the `\\?\` prefix,
+/// including the terminating NUL
+pub const MAX_PATH: usize = 260;
+
+/// Prefix for extended-length local paths
+const VERBATIM_PREFIX: &str = r"\\?\";
+
+/// Prefix for extended-length UNC paths
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// Remove the `\\?\` prefix from a path
+///
+/// `\\?\C:\Games\game.exe` becomes `C:\Games\game.exe` and
+/// `\\?\UNC\server\share\game.exe` becomes `\\server\share\game.exe`. Paths without a
+/// drive letter or UNC share after the prefix (volume GUID paths) are returned
+/// unchanged because they have no plain form.
+pub fn to_plain(path: &Path) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+
+    if let Some(rest) = strip_prefix_ignore_case(text, VERBATIM_UNC_PREFIX) {
+        return PathBuf::from(format!(r"\\{rest}"));
+    }
+
+    match text.strip_prefix(VERBATIM_PREFIX) {
+        Some(rest) if has_drive_letter(rest) => PathBuf::from(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Add the `\\?\` prefix to an absolute path that is too long for Win32 APIs
+///
+/// Extended-length paths are passed to the file system without normalization, so
+/// forward slashes are converted and `.` and `..` components resolved first. Short,
+/// relative and already prefixed paths are returned unchanged.
+pub fn to_extended(path: &Path) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+
+    if text.starts_with(VERBATIM_PREFIX) || text.encode_utf16().count() < MAX_PATH {
+        return path.to_path_buf();
+    }
+
+    let text = text.replace('/', "\\");
+    if let Some(rest) = text.strip_prefix(r"\\") {
+        // UNC path: \\server\share\...
+        let Some(rest) = normalize_components(rest, 2) else {
+            return path.to_path_buf();
+        };
+        PathBuf::from(format!("{VERBATIM_UNC_PREFIX}{rest}"))
+    } else if has_drive_letter(&text) {
+        let Some(rest) = normalize_components(&text, 1) else {
+            return path.to_path_buf();
+        };
+        PathBuf::from(format!("{VERBATIM_PREFIX}{rest}"))
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Convert a path to a NUL-terminated wide string for Win32 APIs, adding the `\\?\`
+/// prefix when the path is too long
+#[cfg(windows)]
+pub fn to_wide_extended(path: &Path) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+
+    to_extended(path)
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+/// Whether a path starts with an absolute drive root such as `C:\`
+fn has_drive_letter(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\'
+}
+
+/// Strip an ASCII prefix ignoring case (`\\?\unc\` is as valid as `\\?\UNC\`)
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+/// Join backslash-separated components, dropping empty and `.` components and
+/// resolving `..`
+///
+/// The first `root` components (drive or server and share) are never removed by `..`.
+/// Returns `None` if a root component is missing.
+fn normalize_components(text: &str, root: usize) -> Option<String> {
+    let mut components: Vec<&str> = Vec::new();
+    for component in text.split('\\') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                if components.len() > root {
+                    components.pop();
+                }
+            }
+            component => components.push(component),
+        }
+    }
+
+    (components.len() >= root).then(|| components.join("\\"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an absolute path longer than `MAX_PATH`
+    fn long_path(root: &str) -> String {
+        let nested = "Deeply Nested Install Folder\\".repeat(10);
+        format!("{root}{nested}Binaries\\Win64\\Game-Win64-Shipping.exe")
+    }
+
+    #[test]
+    fn test_to_extended_prefixes_long_paths_only() {
+        let short = Path::new(r"C:\Games\game.exe");
+        assert_eq!(to_extended(short), short);
+
+        let long = long_path(r"C:\Games\");
+        assert!(long.len() >= MAX_PATH);
+        assert_eq!(
+            to_extended(Path::new(&long)),
+            PathBuf::from(format!(r"\\?\{long}"))
+        );
+
+        // Already prefixed and relative paths are left alone
+        let prefixed = format!(r"\\?\{long}");
+        assert_eq!(to_extended(Path::new(&prefixed)), Path::new(&prefixed));
+        let relative = long_path(r"Games\");
+        assert_eq!(to_extended(Path::new(&relative)), Path::new(&relative));
+    }
+
+    #[test]
+    fn test_to_extended_normalizes_components() {
+        let long = long_path(r"D:/Games/./Store/../");
+        let expected = long_path(r"\\?\D:\Games\");
+        assert_eq!(to_extended(Path::new(&long)), PathBuf::from(expected));
+
+        let unc = long_path(r"\\nas\games\");
+        let expected = long_path(r"\\?\UNC\nas\games\");
+        assert_eq!(to_extended(Path::new(&unc)), PathBuf::from(expected));
+    }
+
+    #[test]
+    fn test_to_plain() {
+        assert_eq!(
+            to_plain(Path::new(r"\\?\C:\Games\game.exe")),
+            PathBuf::from(r"C:\Games\game.exe")
+        );
+        assert_eq!(
+            to_plain(Path::new(r"\\?\UNC\nas\games\game.exe")),
+            PathBuf::from(r"\\nas\games\game.exe")
+        );
+        assert_eq!(
+            to_plain(Path::new(r"C:\Games\game.exe")),
+            PathBuf::from(r"C:\Games\game.exe")
+        );
+
+        // Volume GUID paths have no plain form
+        let volume = r"\\?\Volume{0b9c5f6e-1234-4d2b-9c1d-8f0e1a2b3c4d}\game.exe";
+        assert_eq!(to_plain(Path::new(volume)), Path::new(volume));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let long = long_path(r"E:\SteamLibrary\steamapps\common\");
+        assert_eq!(
+            to_plain(&to_extended(Path::new(&long))),
+            PathBuf::from(&long)
+        );
+    }
+}
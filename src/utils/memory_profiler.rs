@@ -197,6 +197,36 @@ pub fn get_profiler() -> &'static MemoryProfiler {
     &MEMORY_PROFILER
 }
 
+/// Return unused pages of the working set to the system
+///
+/// Called when the window is hidden to the tray: the renderer and the released icons
+/// leave pages behind that an idle tray app does not touch again until the window is
+/// shown. Windows pages them back in on demand.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for EmptyWorkingSet on the current process"
+)]
+pub fn trim_working_set() {
+    use windows::Win32::System::ProcessStatus::EmptyWorkingSet;
+    use windows::Win32::System::Threading::GetCurrentProcess;
+
+    let before = MemoryProfiler::get_process_memory();
+    // SAFETY: the pseudo handle of the current process is always valid
+    match unsafe { EmptyWorkingSet(GetCurrentProcess()) } {
+        Ok(()) => debug!(
+            "Trimmed working set from {} KB to {} KB",
+            before / 1024,
+            MemoryProfiler::get_process_memory() / 1024
+        ),
+        Err(e) => tracing::warn!("Failed to trim working set: {}", e),
+    }
+}
+
+/// Working set trimming is only available on Windows
+#[cfg(not(windows))]
+pub fn trim_working_set() {}
+
 /// Records cached icon size in bytes (Windows only, no-op on other platforms)
 #[inline]
 pub fn record_icon_cached_safe(size: usize) {
@@ -0,0 +1,270 @@
+//! Local toggle metrics
+//!
+//! Opt-in counters for the current run: HDR toggles, failed toggles, activations per
+//! application and the latency from detecting a monitored application to HDR being on.
+//! Nothing leaves the machine; when enabled the counters are written to `metrics.json`
+//! next to the configuration and summarized in the settings dialog.
+
+use crate::config::ConfigManager;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Upper bounds of the latency histogram buckets in milliseconds
+///
+/// A last bucket without a bound collects everything slower.
+pub const LATENCY_BUCKETS_MS: [u64; 7] = [50, 100, 250, 500, 1000, 2500, 5000];
+
+/// One bucket of the detection-to-toggle latency histogram
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyBucket {
+    /// Upper bound in milliseconds, `None` for the overflow bucket
+    pub le_ms: Option<u64>,
+    /// Toggles that took at most `le_ms` and more than the previous bound
+    pub count: u64,
+}
+
+/// Counters collected since metrics were enabled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// When collection started (Unix timestamp in seconds)
+    pub since: u64,
+    /// How often HDR was turned on
+    pub hdr_enabled_count: u64,
+    /// How often HDR was turned off
+    pub hdr_disabled_count: u64,
+    /// HDR toggles that failed on every display
+    pub toggle_failures: u64,
+    /// How often each application turned HDR on, by display name
+    pub activations: BTreeMap<String, u64>,
+    /// Detection-to-toggle latency histogram
+    pub latency_buckets: Vec<LatencyBucket>,
+    /// Number of measured latencies
+    pub latency_count: u64,
+    /// Sum of measured latencies in milliseconds
+    pub latency_sum_ms: u64,
+}
+
+impl MetricsSnapshot {
+    fn new() -> Self {
+        let since = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Self {
+            since,
+            hdr_enabled_count: 0,
+            hdr_disabled_count: 0,
+            toggle_failures: 0,
+            activations: BTreeMap::new(),
+            latency_buckets: LATENCY_BUCKETS_MS
+                .iter()
+                .map(|&bound| Some(bound))
+                .chain(std::iter::once(None))
+                .map(|le_ms| LatencyBucket { le_ms, count: 0 })
+                .collect(),
+            latency_count: 0,
+            latency_sum_ms: 0,
+        }
+    }
+
+    /// Average detection-to-toggle latency, `None` before the first measurement
+    pub fn average_latency(&self) -> Option<Duration> {
+        (self.latency_count > 0)
+            .then(|| Duration::from_millis(self.latency_sum_ms / self.latency_count))
+    }
+
+    /// One-line summary for the settings dialog
+    pub fn summary(&self) -> String {
+        use std::fmt::Write;
+
+        let toggles = self.hdr_enabled_count + self.hdr_disabled_count;
+        let mut summary = format!("{toggles} HDR toggles");
+        if self.toggle_failures > 0 {
+            let _ = write!(summary, ", {} failed", self.toggle_failures);
+        }
+        if let Some(average) = self.average_latency() {
+            let _ = write!(
+                summary,
+                " · {}ms average from detection to HDR on",
+                average.as_millis()
+            );
+        }
+        if let Some((name, count)) = self.activations.iter().max_by_key(|(_, count)| **count) {
+            let _ = write!(summary, " · most often {name} ({count}×)");
+        }
+        summary
+    }
+}
+
+/// Collector for the local metrics
+pub struct Metrics {
+    /// Whether metrics are collected, set from the preferences
+    enabled: AtomicBool,
+    /// Counters, reset when collection is turned on
+    snapshot: parking_lot::Mutex<MetricsSnapshot>,
+    /// Whether the counters changed since the last save
+    dirty: AtomicBool,
+}
+
+impl Metrics {
+    /// Create a disabled collector
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            snapshot: parking_lot::Mutex::new(MetricsSnapshot::new()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Turn collection on or off; turning it on starts from zero
+    pub fn set_enabled(&self, enabled: bool) {
+        use tracing::info;
+
+        if self.enabled.swap(enabled, Ordering::Relaxed) == enabled {
+            return;
+        }
+        if enabled {
+            *self.snapshot.lock() = MetricsSnapshot::new();
+        }
+        info!(
+            "Local metrics {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+    }
+
+    /// Check if metrics are collected
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Record a successful HDR toggle
+    pub fn record_toggle(&self, enable: bool) {
+        self.update(|snapshot| {
+            if enable {
+                snapshot.hdr_enabled_count += 1;
+            } else {
+                snapshot.hdr_disabled_count += 1;
+            }
+        });
+    }
+
+    /// Record an HDR toggle that failed on every display
+    pub fn record_failure(&self) {
+        self.update(|snapshot| snapshot.toggle_failures += 1);
+    }
+
+    /// Record that `app_name` turned HDR on `latency` after it was detected
+    pub fn record_activation(&self, app_name: &str, latency: Duration) {
+        let latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        self.update(|snapshot| {
+            *snapshot
+                .activations
+                .entry(app_name.to_string())
+                .or_default() += 1;
+            let bucket = LATENCY_BUCKETS_MS
+                .iter()
+                .position(|&bound| latency_ms <= bound)
+                .unwrap_or(LATENCY_BUCKETS_MS.len());
+            snapshot.latency_buckets[bucket].count += 1;
+            snapshot.latency_count += 1;
+            snapshot.latency_sum_ms = snapshot.latency_sum_ms.saturating_add(latency_ms);
+        });
+    }
+
+    /// Get a copy of the counters
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.snapshot.lock().clone()
+    }
+
+    /// Write the counters to `metrics.json` if they changed since the last save
+    pub fn save_if_changed(&self) -> Result<()> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(&self.snapshot())?;
+        std::fs::write(get_metrics_path(), json)?;
+        Ok(())
+    }
+
+    fn update(&self, record: impl FnOnce(&mut MetricsSnapshot)) {
+        if !self.is_enabled() {
+            return;
+        }
+        record(&mut self.snapshot.lock());
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the metrics file path (`metrics.json` next to `config.json`)
+pub fn get_metrics_path() -> PathBuf {
+    ConfigManager::get_config_path().with_file_name("metrics.json")
+}
+
+/// Global metrics instance
+static METRICS: std::sync::LazyLock<Metrics> = std::sync::LazyLock::new(Metrics::new);
+
+/// Get the global metrics instance
+pub fn get_metrics() -> &'static Metrics {
+    &METRICS
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_metrics_record_nothing() {
+        let metrics = Metrics::new();
+        metrics.record_toggle(true);
+        metrics.record_failure();
+        metrics.record_activation("Game", Duration::from_millis(120));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.hdr_enabled_count, 0);
+        assert_eq!(snapshot.toggle_failures, 0);
+        assert!(snapshot.activations.is_empty());
+    }
+
+    #[test]
+    fn test_latency_histogram_buckets() {
+        let metrics = Metrics::new();
+        metrics.set_enabled(true);
+        metrics.record_activation("Game", Duration::from_millis(50));
+        metrics.record_activation("Game", Duration::from_millis(300));
+        metrics.record_activation("Editor", Duration::from_secs(10));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.activations["Game"], 2);
+        assert_eq!(snapshot.activations["Editor"], 1);
+        assert_eq!(snapshot.latency_buckets[0].count, 1);
+        assert_eq!(snapshot.latency_buckets[3].count, 1);
+        assert_eq!(snapshot.latency_buckets.last().unwrap().le_ms, None);
+        assert_eq!(snapshot.latency_buckets.last().unwrap().count, 1);
+        assert_eq!(snapshot.latency_count, 3);
+    }
+
+    #[test]
+    fn test_summary() {
+        let metrics = Metrics::new();
+        metrics.set_enabled(true);
+        metrics.record_toggle(true);
+        metrics.record_toggle(false);
+        metrics.record_failure();
+        metrics.record_activation("Game", Duration::from_millis(200));
+
+        assert_eq!(
+            metrics.snapshot().summary(),
+            "2 HDR toggles, 1 failed · 200ms average from detection to HDR on · most often Game (1×)"
+        );
+    }
+}
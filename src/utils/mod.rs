@@ -1,20 +1,50 @@
 //! Utility modules
 //!
-//! Provides auto-start management, icon extraction, icon caching, logging,
-//! memory and startup profiling, single instance enforcement, and update checking.
+//! Provides auto-start management (registry and Task Scheduler), canonical path matching,
+//! COM initialization, data directory selection (including portable mode), elevation
+//! detection, the error center, Windows Event Log reporting, translations, icon
+//! extraction, icon caching, letter-tile fallback icons, long path handling, logging,
+//! local toggle metrics, memory and startup profiling, power source and battery charge,
+//! shortcut resolution, single instance enforcement, Start Menu scanning, Unicode name
+//! normalization, update checking and self-updating, volume availability checks, and the
+//! watchdog that restarts background components after a panic.
 
 pub mod autostart;
+pub mod canonical_path;
+#[cfg(windows)]
+pub mod com;
+pub mod data_dir;
+pub mod elevation;
+pub mod error_center;
+pub mod event_log;
+pub mod i18n;
 pub mod icon_cache;
 pub mod icon_extractor;
+pub mod letter_tile;
 pub mod logging;
+pub mod logon_task;
+pub mod long_path;
 pub mod memory_profiler;
+pub mod metrics;
+pub mod power;
+pub mod self_update;
+pub mod shortcut_resolver;
 pub mod single_instance;
+pub mod start_menu;
 pub mod startup_profiler;
+pub mod unicode;
 pub mod update_checker;
+pub mod volume;
+pub mod watchdog;
 
 pub use autostart::AutoStartManager;
-pub use icon_cache::{CacheStats, IconCache};
+pub use elevation::is_elevated;
+pub use i18n::{tr, tr_args};
+pub use icon_cache::{CacheStats, CacheVerification, IconCache};
 pub use icon_extractor::{extract_display_name_from_exe, extract_icon_from_exe};
-pub use logging::init_logging;
+pub use letter_tile::generate_letter_tile;
+pub use logging::{LogFormat, LogLevel, init_logging, set_log_format, set_log_level};
+pub use power::PowerStatus;
 pub use single_instance::SingleInstanceGuard;
-pub use update_checker::{UpdateCheckResult, UpdateChecker};
+pub use unicode::{clean_display_name, normalize_process_name};
+pub use update_checker::{UpdateCheckCache, UpdateCheckResult, UpdateChecker};
@@ -0,0 +1,55 @@
+//! Power source and battery charge
+//!
+//! HDR drains a laptop battery noticeably faster, so the controller can keep it off on
+//! battery power. The HDR state monitor reports changes of the power source and charge
+//! as they happen; [`PowerStatus::current`] reads the state once at startup.
+
+/// Power source and battery charge of the system
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PowerStatus {
+    /// Whether the system runs on battery power
+    pub on_battery: bool,
+    /// Remaining battery charge in percent, `None` without a battery or if unknown
+    pub battery_percent: Option<u8>,
+}
+
+impl PowerStatus {
+    /// Read the current power status
+    ///
+    /// Reports AC power when the status cannot be read.
+    #[cfg(windows)]
+    #[expect(
+        unsafe_code,
+        reason = "Windows FFI for reading the power status via GetSystemPowerStatus"
+    )]
+    pub fn current() -> Self {
+        use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+        /// `ACLineStatus` while running on battery
+        const AC_LINE_OFFLINE: u8 = 0;
+        /// `BatteryFlag` bit set on systems without a battery
+        const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+        /// `BatteryLifePercent` when the charge is unknown
+        const BATTERY_PERCENT_UNKNOWN: u8 = 255;
+
+        let mut status = SYSTEM_POWER_STATUS::default();
+        // SAFETY: `status` is a valid, writable SYSTEM_POWER_STATUS on the stack
+        if let Err(e) = unsafe { GetSystemPowerStatus(&raw mut status) } {
+            tracing::debug!("Failed to read the power status: {}", e);
+            return Self::default();
+        }
+
+        let has_battery = status.BatteryFlag & BATTERY_FLAG_NO_BATTERY == 0;
+        Self {
+            on_battery: has_battery && status.ACLineStatus == AC_LINE_OFFLINE,
+            battery_percent: (has_battery && status.BatteryLifePercent != BATTERY_PERCENT_UNKNOWN)
+                .then_some(status.BatteryLifePercent.min(100)),
+        }
+    }
+
+    /// Power status is only read on Windows
+    #[cfg(not(windows))]
+    pub fn current() -> Self {
+        Self::default()
+    }
+}
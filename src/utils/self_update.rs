@@ -0,0 +1,599 @@
+//! Self-update
+//!
+//! Installs the release the update checker found instead of only pointing at the release
+//! page. The [`EXECUTABLE_ASSET`] of the release is downloaded into the [`UPDATES_DIR`]
+//! folder of the data directory and checked against the SHA-256 checksum published with
+//! it in one of the [`CHECKSUM_ASSETS`], which catches a broken download. What vouches
+//! for the executable is its Ed25519 signature in [`SIGNATURE_ASSET`], checked against
+//! the public key built into `EasyHDR` from the `EASYHDR_UPDATE_PUBLIC_KEY` environment
+//! variable (32 bytes in hex). Builds without that key, such as local ones, and releases
+//! without a signature are not offered for installation.
+//!
+//! A verified download is staged as `easyhdr-<version>.exe`, with its signature next to
+//! it in `easyhdr-<version>.exe.sig`, and installed the next time `EasyHDR` starts. The
+//! staging folder is writable by any program of the user, so the staged file is never
+//! installed in place: it is copied next to the running executable as
+//! `easyhdr.exe.new`, the copy is checked against the signature, and only that copy is
+//! renamed over the executable. The running executable is renamed to
+//! `easyhdr.exe.old` first, which Windows allows while it runs, and `EasyHDR` restarts
+//! with [`RESTART_FLAG`]. Nothing is installed while `EasyHDR` runs elevated, such as
+//! from an auto-start task with highest privileges. When the folder `EasyHDR` runs from
+//! is not writable, such as under `Program Files`, the installed executable is started
+//! elevated with [`INSTALL_UPDATE_FLAG`] and does the copy, check and swap itself, so the
+//! checked copy lies in a folder the user can't write to.
+
+use crate::error::{EasyHdrError, Result, StringError};
+use crate::utils::data_dir;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Folder in the data directory holding downloaded updates
+pub const UPDATES_DIR: &str = "updates";
+
+/// Release asset holding the executable
+pub const EXECUTABLE_ASSET: &str = "easyhdr.exe";
+
+/// Release assets that can hold the checksum of [`EXECUTABLE_ASSET`]
+pub const CHECKSUM_ASSETS: [&str; 3] = ["easyhdr.exe.sha256", "SHA256SUMS", "SHA256SUMS.txt"];
+
+/// Release asset holding the Ed25519 signature of [`EXECUTABLE_ASSET`], 64 raw bytes
+pub const SIGNATURE_ASSET: &str = "easyhdr.exe.sig";
+
+/// Command-line flag of the elevated helper, followed by the staged executable
+pub const INSTALL_UPDATE_FLAG: &str = "--install-update";
+
+/// Command-line flag making a restarted `EasyHDR` wait for the previous process to exit
+pub const RESTART_FLAG: &str = "--restart";
+
+/// Public key releases are signed with, in hex, when the build was given one
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("EASYHDR_UPDATE_PUBLIC_KEY");
+
+/// Timeout for downloading the executable
+const DOWNLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_mins(10);
+
+/// Release assets needed to install an update
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UpdateDownload {
+    /// Download URL of [`EXECUTABLE_ASSET`]
+    pub url: String,
+    /// Download URL of the checksum asset
+    pub checksum_url: String,
+    /// Download URL of [`SIGNATURE_ASSET`], empty in update checks cached by older
+    /// versions
+    #[serde(default)]
+    pub signature_url: String,
+    /// Size of the executable in bytes, as reported by GitHub
+    pub size: u64,
+}
+
+/// Public key updates must be signed with, `None` when this build installs no updates
+pub fn update_public_key() -> Option<[u8; 32]> {
+    UPDATE_PUBLIC_KEY.and_then(|key| parse_hex(key.trim()))
+}
+
+/// Location of [`UPDATES_DIR`] in the data directory
+pub fn updates_dir() -> PathBuf {
+    data_dir::get_data_dir().join(UPDATES_DIR)
+}
+
+/// Download, verify and stage an update
+///
+/// `progress` is called with the bytes downloaded so far and the total size. Returns
+/// the staged executable, which [`install_staged`] installs at the next start.
+pub fn download(
+    download: &UpdateDownload,
+    version: &Version,
+    mut progress: impl FnMut(u64, u64),
+) -> Result<PathBuf> {
+    use tracing::info;
+
+    let public_key = update_public_key().ok_or_else(no_public_key)?;
+    if download.signature_url.is_empty() {
+        return Err(EasyHdrError::ConfigError(StringError::new(format!(
+            "The release has no {SIGNATURE_ASSET}"
+        ))));
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(format!("EasyHDR/{}", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| EasyHdrError::ConfigError(Box::new(e)))?;
+
+    let checksums = client
+        .get(&download.checksum_url)
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .and_then(reqwest::blocking::Response::text)
+        .map_err(|e| EasyHdrError::ConfigError(Box::new(e)))?;
+    let expected = parse_checksum(&checksums, EXECUTABLE_ASSET).ok_or_else(|| {
+        EasyHdrError::ConfigError(StringError::new(format!(
+            "The release checksum does not list {EXECUTABLE_ASSET}"
+        )))
+    })?;
+
+    let signature = client
+        .get(&download.signature_url)
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .and_then(reqwest::blocking::Response::bytes)
+        .map_err(|e| EasyHdrError::ConfigError(Box::new(e)))?;
+
+    info!("Downloading EasyHDR {} from {}", version, download.url);
+    let response = client
+        .get(&download.url)
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .map_err(|e| EasyHdrError::ConfigError(Box::new(e)))?;
+    let total = response.content_length().unwrap_or(download.size);
+
+    let dir = updates_dir();
+    std::fs::create_dir_all(&dir)?;
+    let staged = dir.join(staged_file_name(version));
+    let partial = staged.with_extension("exe.part");
+    let verified = copy_verified(response, &partial, &expected, total, &mut progress)
+        .and_then(|()| verify_signature(&std::fs::read(&partial)?, &signature, &public_key));
+    if let Err(e) = verified {
+        let _ = std::fs::remove_file(&partial);
+        return Err(e);
+    }
+    std::fs::write(signature_path(&staged), &signature)?;
+    std::fs::rename(&partial, &staged)?;
+    info!(
+        "Staged EasyHDR {} for installation at the next start",
+        version
+    );
+    Ok(staged)
+}
+
+/// Write `reader` to `path`, failing unless its SHA-256 digest is `expected`
+fn copy_verified(
+    mut reader: impl Read,
+    path: &Path,
+    expected: &[u8; 32],
+    total: u64,
+    progress: &mut impl FnMut(u64, u64),
+) -> Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    let mut downloaded = 0;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        hasher.update(&buffer[..read]);
+        file.write_all(&buffer[..read])?;
+        downloaded += read as u64;
+        progress(downloaded, total.max(downloaded));
+    }
+    file.sync_all()?;
+
+    if hasher.finalize().as_slice() != expected {
+        return Err(EasyHdrError::ConfigError(StringError::new(
+            "The downloaded update does not match its published checksum",
+        )));
+    }
+    Ok(())
+}
+
+/// SHA-256 digest of `file_name` in a checksum file
+///
+/// Accepts a file holding just the digest as well as `sha256sum` output listing several
+/// files, where `file_name` is matched without regard to case.
+pub fn parse_checksum(contents: &str, file_name: &str) -> Option<[u8; 32]> {
+    let lines: Vec<_> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let line = match lines.as_slice() {
+        [line] => *line,
+        _ => lines.iter().copied().find(|line| {
+            line.split_once(char::is_whitespace)
+                .is_some_and(|(_, name)| {
+                    name.trim()
+                        .trim_start_matches('*')
+                        .eq_ignore_ascii_case(file_name)
+                })
+        })?,
+    };
+    parse_hex(line.split_whitespace().next()?)
+}
+
+/// Bytes written as exactly `N * 2` hex digits
+fn parse_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Check that `signature` is the Ed25519 signature of `contents` made with the private
+/// key belonging to `public_key`
+pub fn verify_signature(contents: &[u8], signature: &[u8], public_key: &[u8]) -> Result<()> {
+    use ring::signature::{ED25519, UnparsedPublicKey};
+
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(contents, signature)
+        .map_err(|_| {
+            EasyHdrError::ConfigError(StringError::new(
+                "The update is not signed with the EasyHDR release key",
+            ))
+        })
+}
+
+/// Error for builds without [`update_public_key`]
+fn no_public_key() -> EasyHdrError {
+    EasyHdrError::ConfigError(StringError::new(
+        "This build of EasyHDR has no update signing key, install updates from the release page",
+    ))
+}
+
+/// File name of the staged executable of a version
+fn staged_file_name(version: &Version) -> String {
+    format!("easyhdr-{version}.exe")
+}
+
+/// File next to a staged executable holding its signature
+fn signature_path(staged: &Path) -> PathBuf {
+    staged.with_extension("exe.sig")
+}
+
+/// Newest staged executable in `dir` and its version
+fn newest_staged(dir: &Path) -> Option<(Version, PathBuf)> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(std::result::Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let version = name
+                .to_str()?
+                .strip_prefix("easyhdr-")?
+                .strip_suffix(".exe")?;
+            Some((Version::parse(version).ok()?, entry.path()))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+}
+
+/// Install the staged update, if one newer than `current_version` is waiting
+///
+/// Returns the installed version; the caller should then [`restart`]. The staged file
+/// is only installed if it is signed with [`update_public_key`]. While `EasyHDR` runs
+/// elevated nothing is installed and the update waits for a start without elevation.
+/// Otherwise staged files are removed whether or not the installation succeeds, so a
+/// failing update is not retried at every start.
+pub fn install_staged(current_version: &Version) -> Result<Option<Version>> {
+    use tracing::info;
+
+    let dir = updates_dir();
+    let Some((version, staged)) = newest_staged(&dir) else {
+        return Ok(None);
+    };
+    if version <= *current_version {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Ok(None);
+    }
+    if crate::utils::is_elevated() {
+        info!(
+            "Not installing EasyHDR {} while running elevated, it is installed at the next start without elevation",
+            version
+        );
+        return Ok(None);
+    }
+
+    info!("Installing EasyHDR {}", version);
+    let result = update_public_key()
+        .ok_or_else(no_public_key)
+        .and_then(|public_key| install(&staged, &public_key));
+    let _ = std::fs::remove_dir_all(&dir);
+    result.map(|()| Some(version))
+}
+
+/// Install a staged executable over the running one, elevated if its folder is not
+/// writable
+fn install(staged: &Path, public_key: &[u8]) -> Result<()> {
+    use tracing::info;
+
+    let target = std::env::current_exe()?;
+    match install_verified(staged, &target, public_key) {
+        Err(EasyHdrError::IoError(e)) if e.kind() == io::ErrorKind::PermissionDenied => {
+            info!("No write access to {:?}, installing elevated", target);
+            install_elevated(&target, staged)
+        }
+        result => result,
+    }
+}
+
+/// Replace `target` with the signed executable `staged`, keeping the old executable as
+/// a backup
+///
+/// `staged` is copied next to `target` first and only that copy is checked and renamed
+/// into place, so the installed bytes are the checked ones even if `staged` lies in a
+/// folder other programs can write to. The backup can't be deleted while the old
+/// executable runs; [`remove_backup`] cleans it up after the restart.
+pub fn install_verified(staged: &Path, target: &Path, public_key: &[u8]) -> Result<()> {
+    let copy = sibling_path(target, "new");
+    std::fs::copy(staged, &copy)?;
+    let verified = std::fs::read(signature_path(staged))
+        .map_err(Into::into)
+        .and_then(|signature| verify_signature(&std::fs::read(&copy)?, &signature, public_key))
+        .and_then(|()| replace_executable(&copy, target).map_err(Into::into));
+    if verified.is_err() {
+        let _ = std::fs::remove_file(&copy);
+    }
+    verified
+}
+
+/// Rename `copy` over `target`, keeping the old executable as a backup
+fn replace_executable(copy: &Path, target: &Path) -> io::Result<()> {
+    let backup = sibling_path(target, "old");
+    match std::fs::remove_file(&backup) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    std::fs::rename(target, &backup)?;
+    if let Err(e) = std::fs::rename(copy, target) {
+        let _ = std::fs::rename(&backup, target);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// `target` with `.<extension>` appended, e.g. the backup `easyhdr.exe.old`
+fn sibling_path(target: &Path, extension: &str) -> PathBuf {
+    let mut name = target.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// Remove the executable an earlier update replaced
+pub fn remove_backup() {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::fs::remove_file(sibling_path(&exe, "old"));
+    }
+}
+
+/// Start `EasyHDR` again with the same arguments
+///
+/// The new process waits for this one to exit, see [`RESTART_FLAG`].
+pub fn restart() -> io::Result<()> {
+    std::process::Command::new(std::env::current_exe()?)
+        .arg(RESTART_FLAG)
+        .args(
+            std::env::args_os()
+                .skip(1)
+                .filter(|arg| arg != RESTART_FLAG),
+        )
+        .spawn()
+        .map(drop)
+}
+
+/// Staged executable the elevated helper should install, when started with
+/// [`INSTALL_UPDATE_FLAG`]
+pub fn helper_args(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    args.find(|arg| arg == INSTALL_UPDATE_FLAG)?;
+    args.next().map(PathBuf::from)
+}
+
+/// Replace the running executable with the staged one, as the elevated helper
+///
+/// The unelevated process that started the helper is not trusted: the staged file is
+/// copied into the protected install folder and that copy is checked against the
+/// public key built into this executable before it is installed.
+pub fn run_helper(staged: &Path) -> Result<()> {
+    let public_key = update_public_key().ok_or_else(no_public_key)?;
+    install_verified(staged, &std::env::current_exe()?, &public_key)
+}
+
+/// Start the installed executable `target` elevated to install `staged`, and wait for it
+///
+/// The helper is the executable in the protected install folder, not the staged one,
+/// so only code that was already installed runs elevated.
+#[cfg(windows)]
+fn install_elevated(target: &Path, staged: &Path) -> Result<()> {
+    use windows::Win32::Foundation::{CloseHandle, WAIT_OBJECT_0};
+    use windows::Win32::System::Threading::{GetExitCodeProcess, WaitForSingleObject};
+    use windows::Win32::UI::Shell::{SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW, ShellExecuteExW};
+    use windows::Win32::UI::WindowsAndMessaging::SW_HIDE;
+    use windows::core::{HSTRING, PCWSTR, w};
+
+    /// How long the helper may take, including the elevation prompt
+    const HELPER_TIMEOUT_MS: u32 = 5 * 60 * 1000;
+
+    let file = HSTRING::from(target.as_os_str());
+    let parameters = HSTRING::from(format!("{INSTALL_UPDATE_FLAG} \"{}\"", staged.display()));
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "size_of::<SHELLEXECUTEINFOW>() is a compile-time constant that fits in u32"
+    )]
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS,
+        lpVerb: w!("runas"),
+        lpFile: PCWSTR(file.as_ptr()),
+        lpParameters: PCWSTR(parameters.as_ptr()),
+        nShow: SW_HIDE.0,
+        ..Default::default()
+    };
+
+    // SAFETY: `info` is initialized with its size and points to strings that outlive
+    // the call. Declining the elevation prompt fails the call.
+    unsafe { ShellExecuteExW(&raw mut info) }?;
+
+    let mut exit_code = 1;
+    // SAFETY: SEE_MASK_NOCLOSEPROCESS makes `hProcess` a process handle owned by us,
+    // closed once below
+    unsafe {
+        let finished = WaitForSingleObject(info.hProcess, HELPER_TIMEOUT_MS) == WAIT_OBJECT_0;
+        if finished {
+            GetExitCodeProcess(info.hProcess, &raw mut exit_code)?;
+        }
+        let _ = CloseHandle(info.hProcess);
+    }
+    if exit_code != 0 {
+        return Err(EasyHdrError::ConfigError(StringError::new(
+            "The elevated update helper failed",
+        )));
+    }
+    Ok(())
+}
+
+/// Elevation only exists on Windows
+#[cfg(not(windows))]
+fn install_elevated(_target: &Path, _staged: &Path) -> Result<()> {
+    Err(EasyHdrError::ConfigError(StringError::new(
+        "No write access to the EasyHDR executable",
+    )))
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_dir;
+
+    /// SHA-256 of `abc`
+    const ABC_DIGEST: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn test_parse_checksum_formats() {
+        let digest = parse_checksum(ABC_DIGEST, EXECUTABLE_ASSET).unwrap();
+        assert_eq!(digest[0], 0xba);
+        assert_eq!(digest[31], 0xad);
+
+        let sums = format!(
+            "{} *EasyHDR-setup.msi\n{ABC_DIGEST}  EasyHDR.exe\n",
+            "0".repeat(64)
+        );
+        assert_eq!(parse_checksum(&sums, EXECUTABLE_ASSET), Some(digest));
+
+        assert_eq!(
+            parse_checksum(
+                &format!("{ABC_DIGEST}  other.exe\n{ABC_DIGEST}  x.exe"),
+                "y.exe"
+            ),
+            None
+        );
+        assert_eq!(parse_checksum("not a digest", EXECUTABLE_ASSET), None);
+    }
+
+    #[test]
+    fn test_copy_verified_checks_digest() {
+        let dir = create_test_dir();
+        let path = dir.path().join("easyhdr.exe.part");
+        let expected = parse_checksum(ABC_DIGEST, EXECUTABLE_ASSET).unwrap();
+
+        let mut reported = Vec::new();
+        copy_verified(&b"abc"[..], &path, &expected, 3, &mut |done, total| {
+            reported.push((done, total));
+        })
+        .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"abc");
+        assert_eq!(reported, [(3, 3)]);
+
+        assert!(copy_verified(&b"abd"[..], &path, &expected, 3, &mut |_, _| {}).is_err());
+    }
+
+    #[test]
+    fn test_newest_staged_version() {
+        let dir = create_test_dir();
+        assert!(newest_staged(dir.path()).is_none());
+
+        for name in [
+            "easyhdr-1.2.0.exe",
+            "easyhdr-1.10.0.exe",
+            "easyhdr-2.0.0.exe.part",
+        ] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        let (version, path) = newest_staged(dir.path()).unwrap();
+        assert_eq!(version, Version::new(1, 10, 0));
+        assert_eq!(path, dir.path().join("easyhdr-1.10.0.exe"));
+    }
+
+    /// Fresh Ed25519 key pair standing in for the release key
+    fn release_key() -> ring::signature::Ed25519KeyPair {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = ring::signature::Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        ring::signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        use ring::signature::KeyPair;
+        use std::fmt::Write as _;
+
+        let key = release_key();
+        let public_key = key.public_key().as_ref();
+        let signature = key.sign(b"abc");
+
+        verify_signature(b"abc", signature.as_ref(), public_key).unwrap();
+        assert!(verify_signature(b"evil", signature.as_ref(), public_key).is_err());
+        assert!(verify_signature(b"abc", &signature.as_ref()[1..], public_key).is_err());
+        let other = release_key();
+        assert!(verify_signature(b"abc", signature.as_ref(), other.public_key().as_ref()).is_err());
+
+        let hex = public_key.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        });
+        assert_eq!(parse_hex::<32>(&hex).unwrap(), public_key);
+        assert_eq!(parse_hex::<32>(&hex[2..]), None);
+    }
+
+    #[test]
+    fn test_install_verified_installs_checked_copy() {
+        use ring::signature::KeyPair;
+
+        let key = release_key();
+        let public_key = key.public_key().as_ref();
+        let dir = create_test_dir();
+        let staged = dir.path().join("easyhdr-2.0.0.exe");
+        let target = dir.path().join("easyhdr.exe");
+        assert_eq!(
+            signature_path(&staged),
+            dir.path().join("easyhdr-2.0.0.exe.sig")
+        );
+        std::fs::write(&target, b"old").unwrap();
+        std::fs::write(sibling_path(&target, "old"), b"older").unwrap();
+
+        // A staged file swapped after it was signed is not installed
+        std::fs::write(&staged, b"evil").unwrap();
+        std::fs::write(signature_path(&staged), key.sign(b"new")).unwrap();
+        assert!(install_verified(&staged, &target, public_key).is_err());
+        assert_eq!(std::fs::read(&target).unwrap(), b"old");
+        assert!(!sibling_path(&target, "new").exists());
+
+        std::fs::write(&staged, b"new").unwrap();
+        install_verified(&staged, &target, public_key).unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"new");
+        assert_eq!(std::fs::read(sibling_path(&target, "old")).unwrap(), b"old");
+        assert!(!sibling_path(&target, "new").exists());
+    }
+
+    #[test]
+    fn test_helper_args() {
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let staged = r"C:\Users\me\AppData\Roaming\EasyHDR\updates\easyhdr-2.0.0.exe";
+        assert_eq!(
+            helper_args(args(&[INSTALL_UPDATE_FLAG, staged]).into_iter()),
+            Some(PathBuf::from(staged))
+        );
+        assert_eq!(helper_args(args(&["--portable"]).into_iter()), None);
+        assert_eq!(helper_args(args(&[INSTALL_UPDATE_FLAG]).into_iter()), None);
+    }
+}
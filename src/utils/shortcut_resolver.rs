@@ -0,0 +1,141 @@
+//! Shortcut (`.lnk`) resolution for adding applications
+//!
+//! Games are usually reachable through Start menu or desktop shortcuts rather than their
+//! executables, so applications can be added by dropping either onto the main window.
+//! [`executable_for`] accepts an `.exe` as is and reads the target of a shell link with
+//! `IShellLinkW`. Launcher URL shortcuts (`.url`, e.g. `steam://rungameid/...`) have no
+//! executable target and are rejected.
+
+use crate::error::{EasyHdrError, Result, StringError};
+use std::path::{Path, PathBuf};
+
+/// Whether the path names a shell link, by extension (ignoring case)
+pub fn is_shortcut(path: &Path) -> bool {
+    has_extension(path, "lnk")
+}
+
+/// Executable to add for a dropped or picked file
+///
+/// Executables are returned unchanged and shortcuts resolved to their target.
+///
+/// # Errors
+///
+/// Returns error if the file is neither an executable nor a shortcut to one, or the
+/// shortcut cannot be read.
+pub fn executable_for(path: &Path) -> Result<PathBuf> {
+    let target = if is_shortcut(path) {
+        resolve_shortcut(path)?
+    } else {
+        path.to_path_buf()
+    };
+
+    if has_extension(&target, "exe") {
+        Ok(target)
+    } else if is_shortcut(path) {
+        Err(EasyHdrError::ConfigError(StringError::new(format!(
+            "Shortcut does not point to an executable: {}",
+            target.display()
+        ))))
+    } else {
+        Err(EasyHdrError::ConfigError(StringError::new(format!(
+            "Not an executable or a shortcut to one: {}",
+            path.display()
+        ))))
+    }
+}
+
+/// Read the target path of a shell link
+///
+/// Environment variables in the target are expanded. The link is not searched for when
+/// its target moved; a missing target is reported when the application is added.
+///
+/// # Errors
+///
+/// Returns error if the shortcut cannot be loaded or has no file system target, such as a
+/// shortcut to a Store app or a control panel item.
+///
+/// # Safety
+///
+/// COM is initialized for the calling thread and uninitialized again by
+/// [`ComGuard`](crate::utils::com::ComGuard) when it was initialized here. The path passed
+/// to `IPersistFile::Load` is NUL-terminated and outlives the call; `GetPath` writes at
+/// most the length of the buffer it is given.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows COM FFI for reading shortcut targets via IShellLinkW"
+)]
+pub fn resolve_shortcut(path: &Path) -> Result<PathBuf> {
+    use crate::utils::com::ComGuard;
+    use std::ffi::OsString;
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    use windows::Win32::System::Com::{
+        CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance, IPersistFile, STGM_READ,
+    };
+    use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+    use windows::core::{Interface, PCWSTR};
+
+    /// Longest extended-length path, in UTF-16 code units
+    const MAX_TARGET_LEN: usize = 32_768;
+
+    // The GUI thread is already a single-threaded apartment
+    let _com = ComGuard::init(COINIT_APARTMENTTHREADED);
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut target = vec![0u16; MAX_TARGET_LEN];
+    unsafe {
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        link.cast::<IPersistFile>()?
+            .Load(PCWSTR(wide.as_ptr()), STGM_READ)?;
+        link.GetPath(&mut target, std::ptr::null_mut(), 0)?;
+    }
+
+    let len = target.iter().position(|&c| c == 0).unwrap_or(target.len());
+    if len == 0 {
+        return Err(EasyHdrError::ConfigError(StringError::new(format!(
+            "Shortcut has no file target: {}",
+            path.display()
+        ))));
+    }
+    Ok(PathBuf::from(OsString::from_wide(&target[..len])))
+}
+
+/// Shortcuts are only read on Windows
+#[cfg(not(windows))]
+pub fn resolve_shortcut(path: &Path) -> Result<PathBuf> {
+    Err(EasyHdrError::ConfigError(StringError::new(format!(
+        "Shortcuts can only be read on Windows: {}",
+        path.display()
+    ))))
+}
+
+/// Whether the path has the given extension, ignoring case
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_executables_are_kept() {
+        let path = Path::new(r"C:\Games\Game.EXE");
+        assert!(!is_shortcut(path));
+        assert_eq!(executable_for(path).unwrap(), path);
+    }
+
+    #[test]
+    fn test_other_files_are_rejected() {
+        assert!(is_shortcut(Path::new(r"C:\Users\me\Desktop\Game.LNK")));
+        assert!(executable_for(Path::new(r"C:\Games\readme.txt")).is_err());
+        assert!(executable_for(Path::new(r"C:\Users\me\Desktop\Game.url")).is_err());
+        assert!(executable_for(Path::new(r"C:\Games")).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Start Menu entries offered when adding an application
+//!
+//! Most installers put a shortcut into the Start Menu, named the way users know the
+//! application, so the Start Menu is a quicker way to find an executable than the file
+//! picker. [`scan`] lists the shortcuts in the current user's and the common Start Menu
+//! folders whose target is an executable. Uninstallers, Windows' own tools and shortcuts
+//! to missing executables are left out, and an executable reached through several
+//! shortcuts is listed once.
+
+use crate::utils::canonical_path;
+use crate::utils::shortcut_resolver;
+use crate::utils::unicode::fold_case;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Deepest folder nesting searched below a Start Menu folder
+const MAX_SEARCH_DEPTH: usize = 4;
+
+/// An application found in the Start Menu
+#[derive(Debug, Clone)]
+pub struct StartMenuEntry {
+    /// Name of the shortcut
+    pub display_name: String,
+    /// Executable the shortcut starts
+    pub exe_path: PathBuf,
+    /// 32x32 RGBA icon of the executable, if one could be extracted
+    pub icon_data: Option<Vec<u8>>,
+}
+
+/// Programs folders of the current user's and the common Start Menu
+pub fn start_menu_dirs() -> Vec<PathBuf> {
+    ["APPDATA", "ProgramData"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .map(|base| {
+            PathBuf::from(base)
+                .join("Microsoft")
+                .join("Windows")
+                .join("Start Menu")
+                .join("Programs")
+        })
+        .collect()
+}
+
+/// List the applications in the Start Menu, sorted by name
+pub fn scan() -> Vec<StartMenuEntry> {
+    use tracing::{debug, info};
+
+    let mut shortcuts = Vec::new();
+    for dir in start_menu_dirs() {
+        collect_shortcuts(&dir, 0, &mut shortcuts);
+    }
+    let windows_dir =
+        std::env::var_os("SystemRoot").map_or_else(|| PathBuf::from(r"C:\Windows"), PathBuf::from);
+
+    let targets = shortcuts.into_iter().filter_map(|shortcut| {
+        shortcut_resolver::resolve_shortcut(&shortcut)
+            .map_err(|e| debug!("Skipping Start Menu shortcut {:?}: {}", shortcut, e))
+            .ok()
+            .map(|target| (shortcut, target))
+    });
+
+    let mut entries: Vec<StartMenuEntry> = select_entries(targets, &[windows_dir])
+        .into_iter()
+        .filter(|(_, exe_path)| exe_path.is_file())
+        .map(|(display_name, exe_path)| StartMenuEntry {
+            icon_data: crate::utils::extract_icon_from_exe(&exe_path)
+                .ok()
+                .filter(|data| !data.is_empty()),
+            display_name,
+            exe_path,
+        })
+        .collect();
+    entries.sort_by_cached_key(|entry| fold_case(&entry.display_name));
+    info!("Found {} application(s) in the Start Menu", entries.len());
+    entries
+}
+
+/// Reduce `(shortcut, target)` pairs to one `(name, executable)` per application
+///
+/// Targets that are not executables, that lie inside `excluded_dirs` (the Windows
+/// directory) or that look like uninstallers are skipped. The first shortcut to an
+/// executable names it.
+pub fn select_entries(
+    shortcuts: impl IntoIterator<Item = (PathBuf, PathBuf)>,
+    excluded_dirs: &[PathBuf],
+) -> Vec<(String, PathBuf)> {
+    let excluded_keys: Vec<_> = excluded_dirs
+        .iter()
+        .map(|dir| canonical_path::lexical_key(dir))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+
+    for (shortcut, target) in shortcuts {
+        if !target
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+        {
+            continue;
+        }
+        let target_key = canonical_path::lexical_key(&target);
+        if excluded_keys
+            .iter()
+            .any(|dir_key| canonical_path::key_is_inside(&target_key, dir_key))
+        {
+            continue;
+        }
+
+        let display_name = file_stem(&shortcut);
+        if is_uninstaller(&display_name) || is_uninstaller(&file_stem(&target)) {
+            continue;
+        }
+
+        if seen.insert(canonical_path::path_key(&target)) {
+            entries.push((display_name, target));
+        }
+    }
+
+    entries
+}
+
+/// Recursively collect `.lnk` files
+fn collect_shortcuts(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            if depth < MAX_SEARCH_DEPTH {
+                collect_shortcuts(&path, depth + 1, out);
+            }
+        } else if shortcut_resolver::is_shortcut(&path) {
+            out.push(path);
+        }
+    }
+}
+
+/// File name without extension
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Whether a shortcut or executable name looks like an uninstaller, such as
+/// "Uninstall Game" or Inno Setup's `unins000.exe`
+fn is_uninstaller(name: &str) -> bool {
+    let name = fold_case(name);
+    name.contains("uninstall") || name.starts_with("unins")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(shortcut: &str, target: &str) -> (PathBuf, PathBuf) {
+        (PathBuf::from(shortcut), PathBuf::from(target))
+    }
+
+    #[test]
+    fn test_select_entries() {
+        let entries = select_entries(
+            [
+                pair(r"C:\Start\Game.lnk", r"C:\Games\Game\game.exe"),
+                pair(r"C:\Start\Game (copy).lnk", r"C:\GAMES\Game\Game.exe"),
+                pair(r"C:\Start\Uninstall Game.lnk", r"C:\Games\Game\setup.exe"),
+                pair(r"C:\Start\Remove.lnk", r"C:\Games\Other\unins000.exe"),
+                pair(r"C:\Start\Manual.lnk", r"C:\Games\Game\manual.pdf"),
+                pair(r"C:\Start\Notepad.lnk", r"C:\Windows\System32\notepad.exe"),
+                pair(r"C:\Start\Tool.lnk", r"D:\Tools\tool.exe"),
+            ],
+            &[PathBuf::from(r"C:\Windows")],
+        );
+
+        assert_eq!(
+            entries,
+            [
+                ("Game".to_string(), PathBuf::from(r"C:\Games\Game\game.exe")),
+                ("Tool".to_string(), PathBuf::from(r"D:\Tools\tool.exe")),
+            ]
+        );
+    }
+}
@@ -31,6 +31,8 @@ pub enum StartupPhase {
     GuiDisplay,
     /// Application fully initialized
     AppReady,
+    /// Icons loaded in the background, after the window is shown
+    IconLoad,
 }
 
 impl StartupPhase {
@@ -48,6 +50,7 @@ impl StartupPhase {
             StartupPhase::GuiControllerInit => "GUI Controller Init",
             StartupPhase::GuiDisplay => "GUI Display",
             StartupPhase::AppReady => "Application Ready",
+            StartupPhase::IconLoad => "Background Icon Loading",
         }
     }
 }
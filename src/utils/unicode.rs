@@ -0,0 +1,159 @@
+//! Unicode-safe name normalization
+//!
+//! Process names and display names come from several Windows APIs (Toolhelp snapshots,
+//! version resources, package manifests) and from user-picked paths. Names of games
+//! with non-ASCII titles (e.g. `原神.exe`) must normalize identically on every path, so
+//! all of them go through the helpers here instead of ad-hoc `to_ascii_lowercase` or
+//! byte slicing.
+//!
+//! Case-insensitive comparison follows Windows ordinal ignore-case semantics (as used by
+//! NTFS and `CompareStringOrdinal`): every character is compared through its simple,
+//! one-to-one case mapping. Characters whose case mapping expands to several characters
+//! (`ß` → `SS`, `İ` → `i̇`) only match themselves, exactly as Windows treats them.
+
+/// Check whether a character is invisible padding that Windows APIs leave in names
+///
+/// Covers control characters (including the NUL terminators of fixed-size buffers),
+/// the byte order mark and zero-width spaces. Joiners (U+200C, U+200D) are kept
+/// because they change how some scripts and emoji render.
+fn is_invisible(c: char) -> bool {
+    c.is_control() || matches!(c, '\u{FEFF}' | '\u{200B}' | '\u{2060}')
+}
+
+/// Fold a character to its case-insensitive form
+///
+/// Maps through the uppercase and back to lowercase so characters such as `ſ` and `s`
+/// or `ς` and `σ` fold together. Mappings that are not one-to-one leave the character
+/// unchanged.
+fn fold_char(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    let (Some(upper), None) = (upper.next(), upper.next()) else {
+        return c;
+    };
+
+    let mut lower = upper.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => upper,
+    }
+}
+
+/// Iterate over the case-folded characters of a name
+///
+/// Allocation-free counterpart of [`fold_case`], used for hashing.
+pub fn fold_chars(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.chars().map(fold_char)
+}
+
+/// Fold a name for case-insensitive comparison
+pub fn fold_case(name: &str) -> String {
+    fold_chars(name).collect()
+}
+
+/// Compare two names case-insensitively without allocating
+pub fn eq_ignore_case(a: &str, b: &str) -> bool {
+    fold_chars(a).eq(fold_chars(b))
+}
+
+/// Normalize a process name for matching
+///
+/// Trims surrounding whitespace, drops invisible characters and case-folds the rest, so
+/// `Überspiel` and `ÜBERSPIEL` normalize to `überspiel` and CJK names pass through
+/// unchanged.
+pub fn normalize_process_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .filter(|&c| !is_invisible(c))
+        .map(fold_char)
+        .collect()
+}
+
+/// Clean a display name for storage and rendering
+///
+/// Drops invisible characters and trims surrounding whitespace while keeping the
+/// original case and script.
+pub fn clean_display_name(name: &str) -> String {
+    name.chars()
+        .filter(|&c| !is_invisible(c))
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_process_name_keeps_cjk() {
+        assert_eq!(normalize_process_name("原神"), "原神");
+        assert_eq!(
+            normalize_process_name("ファイナルファンタジー"),
+            "ファイナルファンタジー"
+        );
+        assert_eq!(normalize_process_name("검은사막"), "검은사막");
+    }
+
+    #[test]
+    fn test_normalize_process_name_lowercases_non_ascii() {
+        assert_eq!(normalize_process_name("ÉLDEN Ring"), "élden ring");
+        assert_eq!(normalize_process_name("ΔΟΚΙΜΗ"), "δοκιμη");
+        assert_eq!(normalize_process_name("ＧＡＭＥ"), "ｇａｍｅ");
+    }
+
+    #[test]
+    fn test_fold_case_matches_windows_semantics() {
+        assert!(eq_ignore_case("ÜBERSPIEL", "überspiel"));
+        assert!(eq_ignore_case("ΟΔΟΣ", "οδος"));
+        assert!(eq_ignore_case("οδος", "οδοσ"));
+        assert!(eq_ignore_case("ſpiel", "Spiel"));
+        assert!(eq_ignore_case("Ｇａｍｅ", "ＧＡＭＥ"));
+        assert!(eq_ignore_case("原神", "原神"));
+
+        // Expanding case mappings only match themselves
+        assert!(!eq_ignore_case("straße", "STRASSE"));
+        assert!(eq_ignore_case("STRAßE", "straße"));
+        assert!(!eq_ignore_case("İstanbul", "istanbul"));
+
+        assert!(!eq_ignore_case("game", "games"));
+        assert_eq!(
+            fold_case("Pearl.BlackDesert_8WEKYB3D8BBWE"),
+            "pearl.blackdesert_8wekyb3d8bbwe"
+        );
+    }
+
+    #[test]
+    fn test_normalize_process_name_strips_padding() {
+        assert_eq!(normalize_process_name("  \u{FEFF}原神\0\0"), "原神");
+        assert_eq!(normalize_process_name("Game\u{200B}"), "game");
+    }
+
+    #[test]
+    fn test_clean_display_name() {
+        assert_eq!(
+            clean_display_name(" \u{FEFF}ゼルダの伝説\0"),
+            "ゼルダの伝説"
+        );
+        assert_eq!(clean_display_name("Cyberpunk 2077"), "Cyberpunk 2077");
+        // Zero-width joiners are part of the name
+        assert_eq!(clean_display_name("👨\u{200D}💻"), "👨\u{200D}💻");
+    }
+
+    #[test]
+    fn test_normalization_is_idempotent() {
+        for name in [
+            "原神",
+            "ÉLDEN Ring",
+            " Game\0",
+            "ＧＡＭＥ",
+            "ΟΔΟΣ",
+            "straße",
+        ] {
+            let once = normalize_process_name(name);
+            assert_eq!(normalize_process_name(&once), once);
+
+            let cleaned = clean_display_name(name);
+            assert_eq!(clean_display_name(&cleaned), cleaned);
+        }
+    }
+}
@@ -2,13 +2,46 @@
 //!
 //! This module provides functionality to check for application updates from GitHub releases.
 //! It implements rate limiting, caching, and graceful error handling.
+//!
+//! Requests are conditional: the `ETag` and `Last-Modified` validators of the last response
+//! are sent back, and an unchanged release costs GitHub nothing against the hourly limit of
+//! unauthenticated requests. That limit is shared by everyone behind the same IP address, so
+//! when GitHub still answers with a rate limit error, checks back off exponentially (or until
+//! the reset time GitHub names) with some jitter.
+//!
+//! The stable channel only looks at the latest full release. The beta channel looks at
+//! the most recent releases, prereleases included, and picks the highest version.
 
+use crate::config::UpdateChannel;
 use crate::error::{EasyHdrError, Result};
+use crate::utils::self_update::{
+    self, CHECKSUM_ASSETS, EXECUTABLE_ASSET, SIGNATURE_ASSET, UpdateDownload,
+};
+use reqwest::StatusCode;
+use reqwest::header::{
+    ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER,
+};
 use semver::Version;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
+/// Minimum time between automatic checks at startup
+pub const AUTOMATIC_CHECK_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+/// Longest random delay added to the automatic check at startup, so machines that boot
+/// together don't all ask GitHub at the same moment
+pub const STARTUP_CHECK_JITTER_SECS: u64 = 30;
+
+/// First backoff after GitHub rate limited a check, doubled for each further one
+const BASE_BACKOFF_SECS: u64 = 60;
+
+/// Longest backoff after repeated rate limit errors
+const MAX_BACKOFF_SECS: u64 = 24 * 60 * 60;
+
+/// Recent releases the beta channel picks the highest version from
+const BETA_RELEASES_PER_PAGE: u32 = 10;
+
 /// GitHub API response for a release
 #[derive(Debug, Deserialize)]
 struct GitHubRelease {
@@ -19,6 +52,141 @@ struct GitHubRelease {
     name: String,
     /// Whether this is a prerelease
     prerelease: bool,
+    /// Files attached to the release
+    #[serde(default)]
+    assets: Vec<GitHubAsset>,
+}
+
+/// GitHub API response for a file attached to a release
+#[derive(Debug, Deserialize)]
+struct GitHubAsset {
+    /// File name
+    name: String,
+    /// Download URL
+    browser_download_url: String,
+    /// Size in bytes
+    size: u64,
+}
+
+/// Assets needed to install a release, when it has them
+fn select_download(assets: &[GitHubAsset]) -> Option<UpdateDownload> {
+    let find = |names: &[&str]| {
+        assets.iter().find(|asset| {
+            names
+                .iter()
+                .any(|name| asset.name.eq_ignore_ascii_case(name))
+        })
+    };
+    let executable = find(&[EXECUTABLE_ASSET])?;
+    let checksum = find(&CHECKSUM_ASSETS)?;
+    let signature = find(&[SIGNATURE_ASSET])?;
+    Some(UpdateDownload {
+        url: executable.browser_download_url.clone(),
+        checksum_url: checksum.browser_download_url.clone(),
+        signature_url: signature.browser_download_url.clone(),
+        size: executable.size,
+    })
+}
+
+/// Version of a release from its tag name (a leading `v` is allowed)
+fn release_version(tag_name: &str) -> Option<Version> {
+    Version::parse(tag_name.trim_start_matches('v')).ok()
+}
+
+/// Release with the highest version, prereleases included
+fn newest_release(releases: Vec<GitHubRelease>) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter_map(|release| Some((release_version(&release.tag_name)?, release)))
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, release)| release)
+}
+
+/// Conditional request validators and rate limit state, persisted between checks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UpdateCheckCache {
+    /// `ETag` of the last successful response
+    #[serde(default)]
+    pub etag: String,
+    /// `Last-Modified` of the last successful response
+    #[serde(default)]
+    pub last_modified: String,
+    /// Release tag the validators belong to, reused when GitHub answers 304 Not Modified
+    #[serde(default)]
+    pub tag_name: String,
+    /// Assets for installing that release, reused like `tag_name`
+    #[serde(default)]
+    pub download: Option<UpdateDownload>,
+    /// Update channel the validators belong to
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    /// No request is made before this time (Unix timestamp in seconds, 0 for none)
+    #[serde(default)]
+    pub backoff_until: u64,
+    /// Rate limit errors in a row, for the exponential backoff
+    #[serde(default)]
+    pub rate_limit_strikes: u32,
+}
+
+impl UpdateCheckCache {
+    /// Seconds left before checks may resume, `None` when not backing off
+    pub fn backoff_remaining(&self, now: u64) -> Option<u64> {
+        (self.backoff_until > now).then(|| self.backoff_until - now)
+    }
+
+    /// Back off after a rate limit error and return the number of seconds to wait
+    ///
+    /// Waits at least as long as GitHub asked (`server_wait`) and at least the
+    /// exponential backoff, plus `jitter`.
+    pub fn record_rate_limit(&mut self, now: u64, server_wait: Option<u64>, jitter: u64) -> u64 {
+        let backoff = BASE_BACKOFF_SECS
+            .checked_shl(self.rate_limit_strikes)
+            .unwrap_or(MAX_BACKOFF_SECS)
+            .min(MAX_BACKOFF_SECS);
+        let wait = server_wait.unwrap_or(0).max(backoff) + jitter;
+        self.rate_limit_strikes = self.rate_limit_strikes.saturating_add(1);
+        self.backoff_until = now + wait;
+        wait
+    }
+
+    /// Forget the backoff after a check that was not rate limited
+    fn clear_backoff(&mut self) {
+        self.backoff_until = 0;
+        self.rate_limit_strikes = 0;
+    }
+}
+
+/// Seconds GitHub asks to wait according to the rate limit headers of a response
+///
+/// `Retry-After` is used for secondary rate limits; primary limits report
+/// `x-ratelimit-remaining: 0` and the reset time in `x-ratelimit-reset`.
+fn rate_limit_wait(headers: &HeaderMap, now: u64) -> Option<u64> {
+    let number = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+
+    if let Some(retry_after) = number(RETRY_AFTER.as_str()) {
+        return Some(retry_after);
+    }
+    if number("x-ratelimit-remaining") == Some(0) {
+        return number("x-ratelimit-reset").map(|reset| reset.saturating_sub(now));
+    }
+    None
+}
+
+/// Random number of seconds in `0..=max_secs`
+///
+/// Uses the randomly keyed standard hasher, which is plenty for spreading out requests.
+pub fn jitter_secs(max_secs: u64) -> u64 {
+    use std::hash::{BuildHasher, RandomState};
+
+    if max_secs == 0 {
+        return 0;
+    }
+    RandomState::new().hash_one(SystemTime::now()) % (max_secs + 1)
 }
 
 /// Result of an update check
@@ -32,6 +200,9 @@ pub struct UpdateCheckResult {
     pub update_available: bool,
     /// URL to the releases page
     pub releases_url: String,
+    /// Assets for installing the latest version, `None` when it can only be installed
+    /// by hand
+    pub download: Option<UpdateDownload>,
 }
 
 /// Update checker for `EasyHDR`
@@ -44,6 +215,8 @@ pub struct UpdateChecker {
     current_version: Version,
     /// Minimum time between checks in seconds (rate limiting)
     min_check_interval_secs: u64,
+    /// Releases considered
+    channel: UpdateChannel,
 }
 
 impl UpdateChecker {
@@ -66,9 +239,17 @@ impl UpdateChecker {
             repo_name: repo_name.into(),
             current_version,
             min_check_interval_secs,
+            channel: UpdateChannel::default(),
         }
     }
 
+    /// Consider the releases of the given channel instead of only stable ones
+    #[must_use]
+    pub fn with_channel(mut self, channel: UpdateChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
     /// Check if enough time has passed since the last check (rate limiting)
     ///
     /// # Arguments
@@ -103,23 +284,44 @@ impl UpdateChecker {
     /// Check for updates from GitHub releases
     ///
     /// This method:
-    /// - Fetches the latest release from GitHub API
+    /// - Skips the request while `cache` says to back off from rate limiting
+    /// - Fetches the latest release from GitHub API, conditionally on the validators in `cache`
     /// - Compares versions using semantic versioning
     /// - Returns an `UpdateCheckResult` if successful
     /// - Fails silently on network errors (returns `Err`)
     ///
+    /// `cache` is updated with the new validators or backoff and should be persisted.
+    ///
     /// # Returns
     ///
     /// - `Ok(UpdateCheckResult)` if the check succeeded
     /// - `Err(EasyHdrError)` if the check failed (network error, parse error, etc.)
-    pub fn check_for_updates(&self) -> Result<UpdateCheckResult> {
+    #[expect(
+        clippy::too_many_lines,
+        reason = "Cache handling, the request and release parsing are one sequential check"
+    )]
+    pub fn check_for_updates(&self, cache: &mut UpdateCheckCache) -> Result<UpdateCheckResult> {
+        let now = Self::current_timestamp();
+        if let Some(remaining) = cache.backoff_remaining(now) {
+            info!("Skipping update check, rate limited for another {remaining}s");
+            return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                format!("GitHub rate limit reached, retrying in {remaining}s"),
+            )));
+        }
+
         info!("Checking for updates from GitHub");
 
         // Build GitHub API URL
-        let api_url = format!(
-            "https://api.github.com/repos/{}/{}/releases/latest",
-            self.repo_owner, self.repo_name
-        );
+        let api_url = match self.channel {
+            UpdateChannel::Stable => format!(
+                "https://api.github.com/repos/{}/{}/releases/latest",
+                self.repo_owner, self.repo_name
+            ),
+            UpdateChannel::Beta => format!(
+                "https://api.github.com/repos/{}/{}/releases?per_page={BETA_RELEASES_PER_PAGE}",
+                self.repo_owner, self.repo_name
+            ),
+        };
 
         debug!("Fetching latest release from: {}", api_url);
 
@@ -134,40 +336,98 @@ impl UpdateChecker {
                 EasyHdrError::ConfigError(Box::new(e))
             })?;
 
-        // Fetch the latest release
-        let response = client.get(&api_url).send().map_err(|e| {
+        // Fetch the latest release, unless it is unchanged since the last check
+        let mut request = client.get(&api_url);
+        if !cache.tag_name.is_empty() && cache.channel == self.channel {
+            if !cache.etag.is_empty() {
+                request = request.header(IF_NONE_MATCH, &cache.etag);
+            }
+            if !cache.last_modified.is_empty() {
+                request = request.header(IF_MODIFIED_SINCE, &cache.last_modified);
+            }
+        }
+        let response = request.send().map_err(|e| {
             warn!("Failed to fetch latest release: {}", e);
             // Preserve error chain by wrapping the source error
             EasyHdrError::ConfigError(Box::new(e))
         })?;
 
         // Check HTTP status
-        if !response.status().is_success() {
-            warn!("GitHub API returned error status: {}", response.status());
+        let status = response.status();
+        let server_wait = rate_limit_wait(response.headers(), now);
+        if status == StatusCode::TOO_MANY_REQUESTS
+            || (status == StatusCode::FORBIDDEN && server_wait.is_some())
+        {
+            let wait = cache.record_rate_limit(now, server_wait, jitter_secs(BASE_BACKOFF_SECS));
+            warn!("GitHub API rate limit reached, backing off for {}s", wait);
             return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
-                format!("GitHub API returned error status: {}", response.status()),
+                format!("GitHub rate limit reached, retrying in {wait}s"),
             )));
         }
-
-        // Parse JSON response
-        let release: GitHubRelease = response.json().map_err(|e| {
-            warn!("Failed to parse GitHub API response: {}", e);
-            // Preserve error chain by wrapping the source error
-            EasyHdrError::ConfigError(Box::new(e))
-        })?;
-
-        debug!("Fetched release: {:?}", release);
-
-        // Skip prereleases
-        if release.prerelease {
-            info!("Latest release is a prerelease, skipping");
+        cache.clear_backoff();
+
+        let (tag_name, download) = if status == StatusCode::NOT_MODIFIED {
+            debug!("Latest release unchanged since the last check");
+            (cache.tag_name.clone(), cache.download.clone())
+        } else if status.is_success() {
+            let header = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let etag = header(ETAG);
+            let last_modified = header(LAST_MODIFIED);
+
+            // Parse JSON response
+            let parse_error = |e: reqwest::Error| {
+                warn!("Failed to parse GitHub API response: {}", e);
+                // Preserve error chain by wrapping the source error
+                EasyHdrError::ConfigError(Box::new(e))
+            };
+            let release = match self.channel {
+                UpdateChannel::Stable => {
+                    let release: GitHubRelease = response.json().map_err(parse_error)?;
+
+                    // Skip prereleases
+                    if release.prerelease {
+                        info!("Latest release is a prerelease, skipping");
+                        return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
+                            "Latest release is a prerelease",
+                        )));
+                    }
+                    release
+                }
+                UpdateChannel::Beta => {
+                    let releases: Vec<GitHubRelease> = response.json().map_err(parse_error)?;
+                    newest_release(releases).ok_or_else(|| {
+                        EasyHdrError::ConfigError(crate::error::StringError::new(
+                            "No release with a version tag found",
+                        ))
+                    })?
+                }
+            };
+
+            debug!("Fetched release: {:?}", release);
+
+            let download = select_download(&release.assets);
+            cache.etag = etag;
+            cache.last_modified = last_modified;
+            cache.tag_name.clone_from(&release.tag_name);
+            cache.download.clone_from(&download);
+            cache.channel = self.channel;
+            (release.tag_name, download)
+        } else {
+            warn!("GitHub API returned error status: {}", status);
             return Err(EasyHdrError::ConfigError(crate::error::StringError::new(
-                "Latest release is a prerelease",
+                format!("GitHub API returned error status: {status}"),
             )));
-        }
+        };
 
         // Parse version from tag name (strip leading 'v' if present)
-        let tag_name = release.tag_name.trim_start_matches('v');
+        let tag_name = tag_name.trim_start_matches('v');
         let latest_version = Version::parse(tag_name).map_err(|e| {
             warn!("Failed to parse version from tag '{}': {}", tag_name, e);
             // Preserve error chain by wrapping the source error
@@ -199,6 +459,8 @@ impl UpdateChecker {
                 "https://github.com/{}/{}/releases",
                 self.repo_owner, self.repo_name
             ),
+            // Builds without the release key can't check what they would install
+            download: download.filter(|_| self_update::update_public_key().is_some()),
         })
     }
 }
@@ -250,4 +512,105 @@ mod tests {
 
         assert_eq!(version_with_v, version_without_v);
     }
+
+    #[test]
+    fn test_rate_limit_backoff_doubles_and_is_capped() {
+        let mut cache = UpdateCheckCache::default();
+
+        assert_eq!(cache.record_rate_limit(1000, None, 0), 60);
+        assert_eq!(cache.record_rate_limit(1000, None, 0), 120);
+        assert_eq!(cache.record_rate_limit(1000, None, 5), 245);
+        assert_eq!(cache.backoff_remaining(1000), Some(245));
+        assert_eq!(cache.backoff_remaining(1245), None);
+
+        cache.rate_limit_strikes = 40;
+        assert_eq!(cache.record_rate_limit(0, None, 0), MAX_BACKOFF_SECS);
+    }
+
+    #[test]
+    fn test_rate_limit_backoff_respects_server_wait() {
+        let mut cache = UpdateCheckCache::default();
+        assert_eq!(cache.record_rate_limit(1000, Some(900), 0), 900);
+
+        cache.clear_backoff();
+        assert_eq!(cache.backoff_remaining(1000), None);
+        assert_eq!(cache.rate_limit_strikes, 0);
+    }
+
+    #[test]
+    fn test_rate_limit_wait_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(rate_limit_wait(&headers, 1000), None);
+
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1600".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers, 1000), Some(600));
+
+        headers.insert(RETRY_AFTER, "30".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers, 1000), Some(30));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "12".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1600".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers, 1000), None);
+    }
+
+    fn release(tag_name: &str, prerelease: bool, assets: &[&str]) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag_name.to_string(),
+            name: tag_name.to_string(),
+            prerelease,
+            assets: assets
+                .iter()
+                .map(|name| GitHubAsset {
+                    name: (*name).to_string(),
+                    browser_download_url: format!("https://example.com/{name}"),
+                    size: 42,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_newest_release_includes_prereleases() {
+        let releases = vec![
+            release("v1.2.0", false, &[]),
+            release("v1.3.0-beta.2", true, &[]),
+            release("nightly", true, &[]),
+            release("v1.3.0-beta.1", true, &[]),
+        ];
+        assert_eq!(newest_release(releases).unwrap().tag_name, "v1.3.0-beta.2");
+        assert!(newest_release(vec![release("nightly", true, &[])]).is_none());
+    }
+
+    #[test]
+    fn test_select_download_needs_checksum_and_signature() {
+        let full = release(
+            "v1.3.0",
+            false,
+            &["EasyHDR.exe", "SHA256SUMS", "easyhdr.exe.sig", "source.zip"],
+        );
+        assert_eq!(
+            select_download(&full.assets),
+            Some(UpdateDownload {
+                url: "https://example.com/EasyHDR.exe".to_string(),
+                checksum_url: "https://example.com/SHA256SUMS".to_string(),
+                signature_url: "https://example.com/easyhdr.exe.sig".to_string(),
+                size: 42,
+            })
+        );
+
+        let unverifiable = release("v1.3.0", false, &["easyhdr.exe"]);
+        assert_eq!(select_download(&unverifiable.assets), None);
+        let unsigned = release("v1.3.0", false, &["easyhdr.exe", "SHA256SUMS"]);
+        assert_eq!(select_download(&unsigned.assets), None);
+    }
+
+    #[test]
+    fn test_jitter_is_bounded() {
+        assert_eq!(jitter_secs(0), 0);
+        for _ in 0..100 {
+            assert!(jitter_secs(10) <= 10);
+        }
+    }
 }
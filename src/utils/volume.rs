@@ -0,0 +1,266 @@
+//! Availability of the volumes that monitored executables live on
+//!
+//! Games installed on a NAS, a mapped network drive or an external disk are not always
+//! reachable: the share may be disconnected or the disk unplugged. File system calls on
+//! such paths can block for many seconds while Windows waits for the network redirector,
+//! so they must never run on the startup path or while the controller lock is held.
+//!
+//! [`OfflineVolumes::check`] probes each network or removable volume root once, on its
+//! own thread, and gives up after a timeout. Volumes that do not answer in time are
+//! reported offline just like volumes that are gone. Fixed local volumes are assumed to
+//! be available and never probed.
+
+use crate::utils::unicode::fold_case;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long to wait for a volume to answer before reporting it offline
+pub const AVAILABILITY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Kind of volume a path lives on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeKind {
+    /// Internal disk or RAM disk
+    Fixed,
+    /// USB disk, SD card or optical drive
+    Removable,
+    /// UNC path or mapped network drive
+    Network,
+    /// Relative path or a drive Windows does not know about
+    Unknown,
+}
+
+impl VolumeKind {
+    /// Whether paths on this volume may be unavailable or slow to access
+    pub const fn may_be_offline(self) -> bool {
+        matches!(self, Self::Removable | Self::Network)
+    }
+}
+
+/// Root of the volume a path lives on, such as `D:\` or `\\nas\games\`
+///
+/// Returns `None` for relative paths and incomplete UNC paths.
+pub fn volume_root(path: &Path) -> Option<PathBuf> {
+    let text = path.to_str()?.replace('/', "\\");
+    let text = crate::utils::long_path::to_plain(Path::new(&text));
+    let text = text.to_str()?;
+
+    if let Some(rest) = text.strip_prefix(r"\\") {
+        let mut components = rest.split('\\').filter(|component| !component.is_empty());
+        let server = components.next()?;
+        let share = components.next()?;
+        return Some(PathBuf::from(format!(r"\\{server}\{share}\")));
+    }
+
+    let bytes = text.as_bytes();
+    (bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\')
+        .then(|| PathBuf::from(&text[..3]))
+}
+
+/// Determine the kind of volume a path lives on
+///
+/// Only looks at the path and the drive type, so it never blocks on the volume itself.
+pub fn volume_kind(path: &Path) -> VolumeKind {
+    let Some(root) = volume_root(path) else {
+        return VolumeKind::Unknown;
+    };
+
+    if root.to_string_lossy().starts_with(r"\\") {
+        return VolumeKind::Network;
+    }
+
+    drive_kind(&root)
+}
+
+/// Kind of a drive letter root
+#[cfg(windows)]
+#[expect(unsafe_code, reason = "Windows FFI for GetDriveTypeW")]
+fn drive_kind(root: &Path) -> VolumeKind {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+    use windows::Win32::System::WindowsProgramming::{
+        DRIVE_CDROM, DRIVE_FIXED, DRIVE_RAMDISK, DRIVE_REMOTE, DRIVE_REMOVABLE,
+    };
+    use windows::core::PCWSTR;
+
+    let wide: Vec<u16> = root
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    // SAFETY: `wide` is a NUL-terminated root path that outlives the call
+    match unsafe { GetDriveTypeW(PCWSTR(wide.as_ptr())) } {
+        DRIVE_FIXED | DRIVE_RAMDISK => VolumeKind::Fixed,
+        DRIVE_REMOVABLE | DRIVE_CDROM => VolumeKind::Removable,
+        DRIVE_REMOTE => VolumeKind::Network,
+        _ => VolumeKind::Unknown,
+    }
+}
+
+/// Kind of a drive letter root; drive types are only known on Windows
+#[cfg(not(windows))]
+fn drive_kind(_root: &Path) -> VolumeKind {
+    VolumeKind::Fixed
+}
+
+/// Volumes found unavailable by the last availability check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OfflineVolumes {
+    /// Case-folded volume roots
+    roots: HashSet<String>,
+}
+
+impl OfflineVolumes {
+    /// Probe the network and removable volumes of the given paths
+    ///
+    /// Each volume is probed once on its own thread; volumes that do not answer within
+    /// `timeout` are reported offline. Blocks for at most `timeout`.
+    pub fn check<'a>(paths: impl IntoIterator<Item = &'a Path>, timeout: Duration) -> Self {
+        let roots = paths
+            .into_iter()
+            .filter(|path| volume_kind(path).may_be_offline())
+            .filter_map(volume_root);
+        Self::check_roots(roots, timeout, Path::exists)
+    }
+
+    /// Probe volume roots with the given probe function
+    fn check_roots(
+        roots: impl IntoIterator<Item = PathBuf>,
+        timeout: Duration,
+        probe: impl Fn(&Path) -> bool + Clone + Send + 'static,
+    ) -> Self {
+        use tracing::{debug, warn};
+
+        let mut pending = HashSet::new();
+        let (sender, receiver) = mpsc::channel();
+        for root in roots {
+            if !pending.insert(root_key(&root)) {
+                continue;
+            }
+
+            let sender = sender.clone();
+            let probe = probe.clone();
+            let spawned = std::thread::Builder::new()
+                .name("volume-probe".to_string())
+                .spawn(move || {
+                    let available = probe(&root);
+                    // The receiver is gone if the check already timed out
+                    let _ = sender.send((root_key(&root), available));
+                });
+            if let Err(e) = spawned {
+                warn!("Failed to spawn volume probe thread: {}", e);
+            }
+        }
+        drop(sender);
+
+        let mut offline = HashSet::new();
+        let deadline = Instant::now() + timeout;
+        while !pending.is_empty() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Ok((key, available)) = receiver.recv_timeout(remaining) else {
+                break;
+            };
+            pending.remove(&key);
+            if !available {
+                debug!("Volume {} is offline", key);
+                offline.insert(key);
+            }
+        }
+
+        // Volumes still pending did not answer in time (or their probe never started)
+        for key in pending {
+            debug!("Volume {} did not answer within {:?}", key, timeout);
+            offline.insert(key);
+        }
+
+        Self { roots: offline }
+    }
+
+    /// Whether the volume of a path was found offline
+    pub fn contains(&self, path: &Path) -> bool {
+        volume_root(path).is_some_and(|root| self.roots.contains(&root_key(&root)))
+    }
+
+    /// Whether all checked volumes were available
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Number of offline volumes
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+}
+
+/// Case-insensitive key of a volume root
+fn root_key(root: &Path) -> String {
+    fold_case(&root.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_root() {
+        assert_eq!(
+            volume_root(Path::new(r"D:\Games\game.exe")),
+            Some(PathBuf::from(r"D:\"))
+        );
+        assert_eq!(
+            volume_root(Path::new("//nas/games/Steam/game.exe")),
+            Some(PathBuf::from(r"\\nas\games\"))
+        );
+        assert_eq!(
+            volume_root(Path::new(r"\\?\UNC\nas\games\game.exe")),
+            Some(PathBuf::from(r"\\nas\games\"))
+        );
+        assert_eq!(volume_root(Path::new(r"\\nas")), None);
+        assert_eq!(volume_root(Path::new(r"Games\game.exe")), None);
+    }
+
+    #[test]
+    fn test_volume_kind() {
+        assert_eq!(
+            volume_kind(Path::new(r"\\nas\games\game.exe")),
+            VolumeKind::Network
+        );
+        assert_eq!(volume_kind(Path::new("game.exe")), VolumeKind::Unknown);
+        assert!(VolumeKind::Network.may_be_offline());
+        assert!(VolumeKind::Removable.may_be_offline());
+        assert!(!VolumeKind::Fixed.may_be_offline());
+        assert!(!VolumeKind::Unknown.may_be_offline());
+    }
+
+    #[test]
+    fn test_check_reports_missing_and_slow_volumes() {
+        let roots = [
+            PathBuf::from(r"\\nas\games\"),
+            PathBuf::from(r"\\NAS\Games\"),
+            PathBuf::from(r"\\slow\games\"),
+            PathBuf::from(r"\\online\games\"),
+        ];
+        let offline = OfflineVolumes::check_roots(roots, Duration::from_millis(200), |root| {
+            let root = root.to_string_lossy();
+            if root.contains("slow") {
+                std::thread::sleep(Duration::from_secs(5));
+            }
+            root.contains("online")
+        });
+
+        assert_eq!(offline.len(), 2);
+        assert!(offline.contains(Path::new(r"\\nas\games\Game\game.exe")));
+        assert!(offline.contains(Path::new(r"\\slow\games\game.exe")));
+        assert!(!offline.contains(Path::new(r"\\online\games\game.exe")));
+        assert!(!offline.contains(Path::new(r"C:\Games\game.exe")));
+    }
+
+    #[test]
+    fn test_check_skips_fixed_volumes() {
+        let paths = [Path::new("relative.exe"), Path::new("/usr/bin/game")];
+        assert!(OfflineVolumes::check(paths, Duration::from_millis(200)).is_empty());
+    }
+}
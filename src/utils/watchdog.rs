@@ -0,0 +1,178 @@
+//! Restarting background components after a panic
+//!
+//! Process monitoring, HDR state monitoring and the application controller each run in a
+//! thread that calls [`supervise`]. A panic in one of them no longer ends `EasyHDR`: the
+//! panic hook of the crash guard logs it, the watchdog records it in the
+//! [error center](crate::utils::error_center) and starts the component again after a
+//! short delay. A component that panics more than [`MAX_RESTARTS`] times within
+//! [`RESTART_WINDOW`] is given up on, and the crash guard turns HDR back off, tells the
+//! user and exits.
+
+use crate::hdr::crash_guard;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Restarts allowed within [`RESTART_WINDOW`] before a component is given up on
+pub const MAX_RESTARTS: usize = 3;
+
+/// Period in which at most [`MAX_RESTARTS`] restarts are allowed
+pub const RESTART_WINDOW: Duration = Duration::from_mins(10);
+
+/// Pause before a component is started again
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+thread_local! {
+    /// Whether the current thread runs a component under [`supervise`]
+    static SUPERVISED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the current thread runs a component the watchdog restarts after a panic
+pub fn is_supervised() -> bool {
+    SUPERVISED.get()
+}
+
+/// Run `component` on the current thread, restarting it after a panic
+///
+/// Returns once `component` returns normally. `thread` names the component for the
+/// error center and the message shown when it is given up on; it should be the name of
+/// the current thread. Panics only unwind in builds with `panic = "unwind"`, which is
+/// what the release profile uses.
+pub fn supervise(thread: &'static str, component: impl FnMut()) {
+    supervise_with(thread, RESTART_DELAY, component, |details| {
+        crash_guard::exit_after_panic(thread, details)
+    });
+}
+
+/// [`supervise`] with the restart delay and the action taken when giving up as parameters
+fn supervise_with(
+    thread: &'static str,
+    delay: Duration,
+    mut component: impl FnMut(),
+    give_up: impl Fn(&str),
+) {
+    use tracing::{error, warn};
+
+    SUPERVISED.set(true);
+    let mut budget = RestartBudget::default();
+    loop {
+        let Err(payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(&mut component))
+        else {
+            SUPERVISED.set(false);
+            return;
+        };
+
+        let details = panic_details(payload.as_ref());
+        let name = crash_guard::component_name(thread);
+        if !budget.allow(Instant::now()) {
+            error!(
+                "Giving up on {} after {} restarts within {:?}",
+                name, MAX_RESTARTS, RESTART_WINDOW
+            );
+            crate::utils::error_center::report(name, &format!("stopped: {details}"));
+            SUPERVISED.set(false);
+            give_up(&format!(
+                "It kept failing after {MAX_RESTARTS} restarts.\n\n{details}"
+            ));
+            return;
+        }
+
+        warn!("Restarting {} after a panic: {}", name, details);
+        crate::utils::error_center::report(name, &format!("restarted after an error: {details}"));
+        std::thread::sleep(delay);
+    }
+}
+
+/// Message of a panic payload, for panics raised with a string
+fn panic_details(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Restarts of a component within the last [`RESTART_WINDOW`]
+#[derive(Debug, Default)]
+struct RestartBudget {
+    /// When the component was restarted, oldest first
+    restarts: VecDeque<Instant>,
+}
+
+impl RestartBudget {
+    /// Whether another restart at `now` is allowed, recording it if so
+    fn allow(&mut self, now: Instant) -> bool {
+        while self
+            .restarts
+            .front()
+            .is_some_and(|&restart| now.saturating_duration_since(restart) >= RESTART_WINDOW)
+        {
+            self.restarts.pop_front();
+        }
+        if self.restarts.len() >= MAX_RESTARTS {
+            return false;
+        }
+        self.restarts.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_budget_allows_restarts_per_window() {
+        let start = Instant::now();
+        let mut budget = RestartBudget::default();
+
+        for _ in 0..MAX_RESTARTS {
+            assert!(budget.allow(start));
+        }
+        assert!(!budget.allow(start + Duration::from_secs(1)));
+
+        // Restarts older than the window no longer count
+        assert!(budget.allow(start + RESTART_WINDOW));
+    }
+
+    #[test]
+    fn test_supervise_restarts_panicking_component() {
+        let mut runs = 0;
+        let gave_up = Cell::new(false);
+        supervise_with(
+            "process-monitor",
+            Duration::ZERO,
+            || {
+                runs += 1;
+                assert!(is_supervised());
+                assert!(runs > 2, "simulated failure");
+            },
+            |_| gave_up.set(true),
+        );
+
+        assert_eq!(runs, 3);
+        assert!(!gave_up.get());
+        assert!(!is_supervised());
+    }
+
+    #[test]
+    fn test_supervise_gives_up_on_repeated_panics() {
+        let mut runs = 0;
+        let reason = std::cell::RefCell::new(String::new());
+        supervise_with(
+            "hdr-state-monitor",
+            Duration::ZERO,
+            || {
+                runs += 1;
+                panic!("always fails");
+            },
+            |details| *reason.borrow_mut() = details.to_string(),
+        );
+
+        assert_eq!(runs, MAX_RESTARTS + 1);
+        assert!(reason.borrow().ends_with("always fails"));
+        assert!(!is_supervised());
+    }
+}
@@ -0,0 +1,388 @@
+//! Cached UWP package enumeration
+//!
+//! Enumerating installed UWP packages through `PackageManager` takes several
+//! seconds on machines with many Store apps, which made the "Add UWP app" picker
+//! feel sluggish every time it was opened. This module keeps the last enumeration
+//! result in memory and refreshes it on a background thread.
+//!
+//! # Invalidation
+//!
+//! The cache subscribes to `PackageCatalog` events for the current user. Whenever a
+//! package finishes installing, updating, or uninstalling (or its status changes),
+//! the cached list is marked stale and a background refresh is scheduled. Callers
+//! keep receiving the previous snapshot until the refresh completes, so the picker
+//! never blocks on enumeration after the first successful run.
+//!
+//! # Scope
+//!
+//! The cache holds packages for a single [`PackageEnumerationScope`]. Switching the
+//! scope discards the cached list so the next request enumerates synchronously for
+//! the new scope.
+
+use crate::Result;
+use crate::uwp::enumerator::{
+    PackageEnumerationScope, UwpPackageInfo, enumerate_packages_for_scope,
+};
+use parking_lot::{Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+
+/// Function used to produce a fresh package list (replaceable in tests)
+type EnumerateFn = fn(PackageEnumerationScope) -> Result<Vec<UwpPackageInfo>>;
+
+/// In-memory cache of enumerated UWP packages with background refresh
+pub struct UwpPackageCache {
+    /// Last successfully enumerated package list
+    packages: RwLock<Option<Arc<Vec<UwpPackageInfo>>>>,
+    /// Scope the cached list was (or will be) enumerated for
+    scope: RwLock<PackageEnumerationScope>,
+    /// Set when the cached list no longer reflects installed packages
+    stale: AtomicBool,
+    /// Guards against spawning more than one refresh thread at a time
+    refreshing: AtomicBool,
+    /// Set once the package catalog change events are subscribed
+    watching: Mutex<bool>,
+    /// Enumeration backend
+    enumerate: EnumerateFn,
+}
+
+impl UwpPackageCache {
+    /// Create an empty cache backed by [`enumerate_packages_for_scope`]
+    pub fn new() -> Self {
+        Self::with_enumerator(enumerate_packages_for_scope)
+    }
+
+    /// Create an empty cache backed by a custom enumeration function
+    #[doc(hidden)]
+    pub fn with_enumerator(enumerate: EnumerateFn) -> Self {
+        Self {
+            packages: RwLock::new(None),
+            scope: RwLock::new(PackageEnumerationScope::CurrentUser),
+            stale: AtomicBool::new(true),
+            refreshing: AtomicBool::new(false),
+            watching: Mutex::new(false),
+            enumerate,
+        }
+    }
+
+    /// Get the cached package list, enumerating synchronously on first use
+    ///
+    /// If a previous result exists it is returned immediately, even when stale; a
+    /// background refresh is scheduled in that case so the next call sees the update.
+    pub fn packages(self: &Arc<Self>) -> Result<Arc<Vec<UwpPackageInfo>>> {
+        if let Some(cached) = self.snapshot() {
+            if self.is_stale() {
+                self.refresh_in_background();
+            }
+            return Ok(cached);
+        }
+
+        self.refresh()
+    }
+
+    /// Get the cached package list without triggering enumeration
+    pub fn snapshot(&self) -> Option<Arc<Vec<UwpPackageInfo>>> {
+        self.packages.read().clone()
+    }
+
+    /// Scope used for enumeration
+    pub fn scope(&self) -> PackageEnumerationScope {
+        *self.scope.read()
+    }
+
+    /// Change the enumeration scope, discarding the cached list if it differs
+    pub fn set_scope(&self, scope: PackageEnumerationScope) {
+        use tracing::debug;
+
+        let mut current = self.scope.write();
+        if *current == scope {
+            return;
+        }
+
+        debug!("UWP package cache scope changed to {:?}", scope);
+        *current = scope;
+        *self.packages.write() = None;
+        self.stale.store(true, Ordering::Release);
+    }
+
+    /// Whether the cached list is missing or has been invalidated
+    pub fn is_stale(&self) -> bool {
+        self.stale.load(Ordering::Acquire)
+    }
+
+    /// Mark the cached list as stale and schedule a background refresh
+    pub fn invalidate(self: &Arc<Self>) {
+        use tracing::debug;
+
+        debug!("UWP package cache invalidated");
+        self.stale.store(true, Ordering::Release);
+        self.refresh_in_background();
+    }
+
+    /// Enumerate packages synchronously and replace the cached list
+    pub fn refresh(&self) -> Result<Arc<Vec<UwpPackageInfo>>> {
+        use tracing::debug;
+
+        // Clear the flag before enumerating so invalidations that arrive mid-refresh
+        // are not lost
+        self.stale.store(false, Ordering::Release);
+
+        let scope = self.scope();
+        let packages = match (self.enumerate)(scope) {
+            Ok(packages) => Arc::new(packages),
+            Err(e) => {
+                self.stale.store(true, Ordering::Release);
+                return Err(e);
+            }
+        };
+
+        debug!(
+            "UWP package cache refreshed ({} packages, scope {:?})",
+            packages.len(),
+            scope
+        );
+
+        // Drop the result if the scope changed while enumerating
+        let current_scope = self.scope.read();
+        if *current_scope == scope {
+            *self.packages.write() = Some(Arc::clone(&packages));
+        }
+        Ok(packages)
+    }
+
+    /// Refresh the cached list on a background thread
+    ///
+    /// Does nothing if a refresh is already running. The running refresh loops until
+    /// no further invalidations arrived while it was enumerating.
+    pub fn refresh_in_background(self: &Arc<Self>) {
+        use tracing::warn;
+
+        if self.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let cache = Arc::clone(self);
+        let spawn_result = std::thread::Builder::new()
+            .name("uwp-package-cache".to_string())
+            .spawn(move || {
+                while cache.is_stale() {
+                    if let Err(e) = cache.refresh() {
+                        warn!("Background UWP package refresh failed: {}", e);
+                        break;
+                    }
+                }
+                cache.refreshing.store(false, Ordering::Release);
+            });
+
+        if let Err(e) = spawn_result {
+            warn!("Failed to spawn UWP package refresh thread: {}", e);
+            self.refreshing.store(false, Ordering::Release);
+        }
+    }
+
+    /// Subscribe to package catalog changes for the current user
+    ///
+    /// Safe to call more than once; subsequent calls are no-ops while a subscription
+    /// is active.
+    pub fn watch_package_changes(self: &Arc<Self>) -> Result<()> {
+        use crate::EasyHdrError;
+        use tracing::info;
+        use windows::ApplicationModel::{
+            PackageCatalog, PackageInstallingEventArgs, PackageStatusChangedEventArgs,
+            PackageUninstallingEventArgs, PackageUpdatingEventArgs,
+        };
+        use windows::Foundation::TypedEventHandler;
+
+        let mut watching = self.watching.lock();
+        if *watching {
+            return Ok(());
+        }
+
+        let catalog = PackageCatalog::OpenForCurrentUser()
+            .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
+
+        // Installing/updating/uninstalling events fire repeatedly with progress
+        // updates; only the completion event changes what enumeration returns
+        let cache = Arc::clone(self);
+        catalog
+            .PackageInstalling(&TypedEventHandler::<
+                PackageCatalog,
+                PackageInstallingEventArgs,
+            >::new(move |_, args| {
+                if args.ok().and_then(|a| a.IsComplete()).unwrap_or(true) {
+                    cache.invalidate();
+                }
+                Ok(())
+            }))
+            .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
+
+        let cache = Arc::clone(self);
+        catalog
+            .PackageUpdating(
+                &TypedEventHandler::<PackageCatalog, PackageUpdatingEventArgs>::new(
+                    move |_, args| {
+                        if args.ok().and_then(|a| a.IsComplete()).unwrap_or(true) {
+                            cache.invalidate();
+                        }
+                        Ok(())
+                    },
+                ),
+            )
+            .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
+
+        let cache = Arc::clone(self);
+        catalog
+            .PackageUninstalling(&TypedEventHandler::<
+                PackageCatalog,
+                PackageUninstallingEventArgs,
+            >::new(move |_, args| {
+                if args.ok().and_then(|a| a.IsComplete()).unwrap_or(true) {
+                    cache.invalidate();
+                }
+                Ok(())
+            }))
+            .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
+
+        let cache = Arc::clone(self);
+        catalog
+            .PackageStatusChanged(&TypedEventHandler::<
+                PackageCatalog,
+                PackageStatusChangedEventArgs,
+            >::new(move |_, _| {
+                cache.invalidate();
+                Ok(())
+            }))
+            .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
+
+        info!("Watching package catalog for UWP package changes");
+        // The catalog is not `Send`, so it cannot live in this shared cache. The
+        // subscription lasts for the rest of the process, so keep the catalog alive by
+        // leaking its reference instead
+        std::mem::forget(catalog);
+        *watching = true;
+        Ok(())
+    }
+}
+
+impl Default for UwpPackageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global UWP package cache instance
+static UWP_PACKAGE_CACHE: LazyLock<Arc<UwpPackageCache>> =
+    LazyLock::new(|| Arc::new(UwpPackageCache::new()));
+
+/// Get the global UWP package cache
+pub fn get_package_cache() -> &'static Arc<UwpPackageCache> {
+    &UWP_PACKAGE_CACHE
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::uwp::enumerator::PackageKind;
+    use std::sync::atomic::AtomicUsize;
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counting_enumerator(scope: PackageEnumerationScope) -> Result<Vec<UwpPackageInfo>> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        let mut packages = vec![UwpPackageInfo {
+            display_name: "Calculator".to_string(),
+            package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
+            app_id: "App".to_string(),
+            publisher_display_name: "Microsoft Corporation".to_string(),
+            installed_for_current_user: true,
+            multi_app_package: false,
+            kind: PackageKind::App,
+            logo_stream: None,
+        }];
+        if scope == PackageEnumerationScope::AllUsers {
+            packages.push(UwpPackageInfo {
+                display_name: "Other User Game".to_string(),
+                package_family_name: "Contoso.Game_abcdef123".to_string(),
+                app_id: "App".to_string(),
+                publisher_display_name: "Contoso".to_string(),
+                installed_for_current_user: false,
+                multi_app_package: false,
+                kind: PackageKind::Game,
+                logo_stream: None,
+            });
+        }
+        Ok(packages)
+    }
+
+    fn failing_enumerator(_scope: PackageEnumerationScope) -> Result<Vec<UwpPackageInfo>> {
+        Err(crate::EasyHdrError::UwpEnumerationError(
+            crate::error::StringError::new("enumeration failed"),
+        ))
+    }
+
+    #[test]
+    fn test_cache_enumerates_once_and_reuses_result() {
+        let cache = Arc::new(UwpPackageCache::with_enumerator(counting_enumerator));
+        let before = CALLS.load(Ordering::SeqCst);
+
+        let first = cache.packages().unwrap();
+        let second = cache.packages().unwrap();
+
+        assert_eq!(CALLS.load(Ordering::SeqCst) - before, 1);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!cache.is_stale());
+    }
+
+    #[test]
+    fn test_invalidate_keeps_snapshot_and_refreshes_in_background() {
+        static INVALIDATE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        fn enumerator(scope: PackageEnumerationScope) -> Result<Vec<UwpPackageInfo>> {
+            INVALIDATE_CALLS.fetch_add(1, Ordering::SeqCst);
+            counting_enumerator(scope)
+        }
+
+        let cache = Arc::new(UwpPackageCache::with_enumerator(enumerator));
+        let first = cache.refresh().unwrap();
+        assert_eq!(INVALIDATE_CALLS.load(Ordering::SeqCst), 1);
+
+        cache.invalidate();
+        // A stale cache still serves the previous snapshot immediately
+        assert!(cache.snapshot().is_some());
+        assert_eq!(cache.packages().unwrap().len(), 1);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while (cache.is_stale() || cache.refreshing.load(Ordering::Acquire))
+            && std::time::Instant::now() < deadline
+        {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        assert!(!cache.is_stale());
+        assert_eq!(INVALIDATE_CALLS.load(Ordering::SeqCst), 2);
+        assert!(!Arc::ptr_eq(&first, &cache.snapshot().unwrap()));
+    }
+
+    #[test]
+    fn test_scope_change_discards_cached_list() {
+        let cache = Arc::new(UwpPackageCache::with_enumerator(counting_enumerator));
+        assert_eq!(cache.packages().unwrap().len(), 1);
+
+        cache.set_scope(PackageEnumerationScope::AllUsers);
+        assert!(cache.snapshot().is_none());
+
+        let packages = cache.packages().unwrap();
+        assert_eq!(packages.len(), 2);
+        assert!(!packages[1].installed_for_current_user);
+    }
+
+    #[test]
+    fn test_failed_refresh_leaves_cache_stale() {
+        let cache = Arc::new(UwpPackageCache::with_enumerator(failing_enumerator));
+
+        assert!(cache.packages().is_err());
+        assert!(cache.is_stale());
+        assert!(cache.snapshot().is_none());
+    }
+}
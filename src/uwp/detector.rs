@@ -21,8 +21,34 @@
 //!   - Example: `Microsoft.WindowsCalculator_8wekyb3d8bbwe`
 //!
 //! The family name is stable across version updates and is used to identify monitored apps.
+//!
+//! # Application-Level Detection
+//!
+//! Packages can contain several applications. `GetApplicationUserModelId` returns the
+//! process's Application User Model ID (`PackageFamilyName!AppId`), which identifies the
+//! specific application. Not every packaged process has one (e.g., background tasks or
+//! helper processes), so callers fall back to package-level matching when it is absent.
+//!
+//! # Running App Notifications
+//!
+//! On Windows 10 1809 and later, [`watch_running_apps`] subscribes to an
+//! `AppDiagnosticInfoWatcher`, which reports each app as it starts and stops without
+//! looking at individual processes. The reports are recorded in
+//! [`RunningUwpApps`](crate::monitor::RunningUwpApps) and forwarded as [`ProcessEvent`]s.
+//! On older builds, or when diagnostic access is denied, the process monitor keeps
+//! identifying UWP apps from process handles.
 
 use crate::Result;
+#[cfg(windows)]
+use crate::monitor::{ProcessEvent, SharedWatchState};
+#[cfg(windows)]
+use parking_lot::Mutex;
+#[cfg(windows)]
+use std::sync::{Arc, mpsc};
+
+/// Running app watcher, kept alive so its events keep firing
+#[cfg(windows)]
+static APP_WATCHER: Mutex<Option<windows::System::AppDiagnosticInfoWatcher>> = Mutex::new(None);
 
 /// Check if a process is a UWP application and return its package family name
 ///
@@ -112,6 +138,218 @@ pub unsafe fn detect_uwp_process(
     Ok(Some(family_name))
 }
 
+/// Get the Application User Model ID (AUMID) of a packaged process
+///
+/// Returns `PackageFamilyName!AppId`, or `None` if the process is not packaged, has no
+/// application identity, or cannot be queried.
+///
+/// # Safety
+///
+/// Caller must ensure `h_process` is a valid, open process handle with
+/// `PROCESS_QUERY_LIMITED_INFORMATION` rights that remains valid for the call duration.
+///
+/// # Errors
+///
+/// Returns error if the Windows API call fails unexpectedly or UTF-16 conversion fails.
+#[cfg(windows)]
+#[expect(
+    unsafe_code,
+    reason = "Windows FFI for UWP application detection via GetApplicationUserModelId"
+)]
+pub unsafe fn detect_uwp_application_user_model_id(
+    h_process: windows::Win32::Foundation::HANDLE,
+) -> Result<Option<String>> {
+    use windows::Win32::Foundation::WIN32_ERROR;
+    use windows::Win32::Storage::Packaging::Appx::GetApplicationUserModelId;
+    use windows::core::PWSTR;
+
+    const APPMODEL_ERROR_NO_PACKAGE: WIN32_ERROR = WIN32_ERROR(15700);
+    const APPMODEL_ERROR_NO_APPLICATION: WIN32_ERROR = WIN32_ERROR(15703);
+    const ERROR_INSUFFICIENT_BUFFER: WIN32_ERROR = WIN32_ERROR(122);
+    const ERROR_ACCESS_DENIED: WIN32_ERROR = WIN32_ERROR(5);
+
+    // First call to get required buffer length
+    let mut length: u32 = 0;
+    let result = unsafe { GetApplicationUserModelId(h_process, &raw mut length, None) };
+
+    if result == APPMODEL_ERROR_NO_PACKAGE
+        || result == APPMODEL_ERROR_NO_APPLICATION
+        || result == ERROR_ACCESS_DENIED
+    {
+        // Not packaged, packaged without application identity, or not queryable
+        return Ok(None);
+    }
+
+    if result != ERROR_INSUFFICIENT_BUFFER {
+        return Err(crate::EasyHdrError::UwpProcessDetectionError(
+            crate::error::StringError::new(format!(
+                "GetApplicationUserModelId failed with error code {result:?}"
+            )),
+        ));
+    }
+
+    // length includes the null terminator
+    let mut buffer = vec![0u16; length as usize];
+
+    let result = unsafe {
+        GetApplicationUserModelId(h_process, &raw mut length, Some(PWSTR(buffer.as_mut_ptr())))
+    };
+
+    if result != WIN32_ERROR(0) {
+        return Err(crate::EasyHdrError::UwpProcessDetectionError(
+            crate::error::StringError::new(format!(
+                "GetApplicationUserModelId (second call) failed with error code {result:?}"
+            )),
+        ));
+    }
+
+    let null_pos = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+
+    let aumid = String::from_utf16(&buffer[..null_pos]).map_err(|e| {
+        crate::EasyHdrError::UwpProcessDetectionError(crate::error::StringError::new(format!(
+            "Failed to convert application user model ID from UTF-16: {e}"
+        )))
+    })?;
+
+    Ok(Some(aumid))
+}
+
+/// Subscribe to UWP app start and stop notifications
+///
+/// Added and removed apps are recorded in the watch state's
+/// [`RunningUwpApps`](crate::monitor::RunningUwpApps), and changes to watched apps are
+/// sent to `event_sender`. Safe to call more than once; subsequent calls are no-ops
+/// while the watcher runs.
+///
+/// # Errors
+///
+/// Returns error if the watcher is not available on this Windows build or diagnostic
+/// access to other apps is denied. UWP apps are then detected from process handles.
+#[cfg(windows)]
+pub fn watch_running_apps(
+    watch_state: &Arc<SharedWatchState>,
+    event_sender: &mpsc::SyncSender<ProcessEvent>,
+) -> Result<()> {
+    use crate::EasyHdrError;
+    use crate::error::StringError;
+    use tracing::{info, warn};
+    use windows::Foundation::TypedEventHandler;
+    use windows::System::{
+        AppDiagnosticInfo, AppDiagnosticInfoWatcher, AppDiagnosticInfoWatcherEventArgs,
+        DiagnosticAccessStatus,
+    };
+    use windows::core::IInspectable;
+
+    let mut watcher_slot = APP_WATCHER.lock();
+    if watcher_slot.is_some() {
+        return Ok(());
+    }
+
+    // Without full access the watcher only reports EasyHDR itself
+    let access = AppDiagnosticInfo::RequestAccessAsync()
+        .and_then(|operation| operation.join())
+        .map_err(|e| EasyHdrError::UwpProcessDetectionError(Box::new(e)))?;
+    if access != DiagnosticAccessStatus::Allowed {
+        return Err(EasyHdrError::UwpProcessDetectionError(StringError::new(
+            format!("App diagnostic access is {access:?}"),
+        )));
+    }
+
+    // Not available before Windows 10 1809
+    let watcher = AppDiagnosticInfo::CreateWatcher()
+        .map_err(|e| EasyHdrError::UwpProcessDetectionError(Box::new(e)))?;
+
+    let state = Arc::clone(watch_state);
+    let sender = event_sender.clone();
+    watcher
+        .Added(&TypedEventHandler::<
+            AppDiagnosticInfoWatcher,
+            AppDiagnosticInfoWatcherEventArgs,
+        >::new(move |_, args| {
+            let aumid = args
+                .ok()?
+                .AppDiagnosticInfo()?
+                .AppInfo()?
+                .AppUserModelId()?;
+            state.load().uwp_apps.app_added(&aumid.to_string());
+            forward_running_app_changes(&state, &sender);
+            Ok(())
+        }))
+        .map_err(|e| EasyHdrError::UwpProcessDetectionError(Box::new(e)))?;
+
+    let state = Arc::clone(watch_state);
+    let sender = event_sender.clone();
+    watcher
+        .Removed(&TypedEventHandler::<
+            AppDiagnosticInfoWatcher,
+            AppDiagnosticInfoWatcherEventArgs,
+        >::new(move |_, args| {
+            let aumid = args
+                .ok()?
+                .AppDiagnosticInfo()?
+                .AppInfo()?
+                .AppUserModelId()?;
+            state.load().uwp_apps.app_removed(&aumid.to_string());
+            forward_running_app_changes(&state, &sender);
+            Ok(())
+        }))
+        .map_err(|e| EasyHdrError::UwpProcessDetectionError(Box::new(e)))?;
+
+    // The watcher stops on its own if the diagnostics service goes away; hand detection
+    // back to the process monitor so apps are not lost
+    let state = Arc::clone(watch_state);
+    let sender = event_sender.clone();
+    watcher
+        .Stopped(
+            &TypedEventHandler::<AppDiagnosticInfoWatcher, IInspectable>::new(move |_, _| {
+                warn!("UWP app watcher stopped, detecting UWP apps from processes");
+                let events = state.load().uwp_apps.deactivate();
+                send_events(&sender, events);
+                Ok(())
+            }),
+        )
+        .map_err(|e| EasyHdrError::UwpProcessDetectionError(Box::new(e)))?;
+
+    // Activate first so apps reported during the initial enumeration are kept
+    let uwp_apps = Arc::clone(&watch_state.load().uwp_apps);
+    uwp_apps.activate();
+    if let Err(e) = watcher.Start() {
+        uwp_apps.deactivate();
+        return Err(EasyHdrError::UwpProcessDetectionError(Box::new(e)));
+    }
+
+    info!("Watching running UWP apps through app diagnostics");
+    *watcher_slot = Some(watcher);
+    Ok(())
+}
+
+/// Send the events for watched UWP apps that started or stopped
+#[cfg(windows)]
+fn forward_running_app_changes(
+    watch_state: &SharedWatchState,
+    event_sender: &mpsc::SyncSender<ProcessEvent>,
+) {
+    // Events are sent after dropping the snapshot, like the process monitor does
+    let events = {
+        let state = watch_state.load();
+        state.uwp_apps.update(&state)
+    };
+    send_events(event_sender, events);
+}
+
+/// Send process events, logging failures
+#[cfg(windows)]
+fn send_events(event_sender: &mpsc::SyncSender<ProcessEvent>, events: Vec<ProcessEvent>) {
+    use tracing::{error, info};
+
+    for event in events {
+        info!("UWP app watcher reported: {:?}", event);
+        if let Err(e) = event_sender.send(event) {
+            error!("Failed to send UWP app watcher event: {}", e);
+        }
+    }
+}
+
 /// Extract package family name from package full name
 ///
 /// Extracts `Name_PublisherId` from `Name_Version_Architecture_ResourceId_PublisherId`.
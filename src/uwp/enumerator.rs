@@ -13,6 +13,9 @@
 //!    - `Package.Id.FamilyName` - Stable identifier
 //!    - `Package.DisplayName` - User-visible name
 //!    - `Package.PublisherDisplayName` - Publisher/vendor
+//! 4. Emit one entry per application (`AppListEntry`) in the package:
+//!    - `AppListEntry.AppUserModelId` - Application ID (`FamilyName!AppId`)
+//!    - `AppListEntry.DisplayInfo.DisplayName` - Per-application name
 //!    - `AppListEntry.DisplayInfo.GetLogo()` - Icon stream reference
 //!
 //! # Icon Loading Strategy
@@ -31,19 +34,50 @@
 //! **No administrator privileges are required** for enumeration. Using an empty string
 //! as the user security ID parameter (`""`) retrieves packages for the current user only.
 //!
+//! On shared machines, [`PackageEnumerationScope::AllUsers`] additionally lists packages
+//! installed for other accounts via `PackageManager.FindPackages()`. That API requires an
+//! elevated process; entries that are not registered for the current user are marked
+//! with `installed_for_current_user = false` because they may not be launchable.
+//!
 //! # Filtering
 //!
 //! Framework packages and system packages are excluded from results to show only
 //! user-installable applications.
+//!
+//! Each package is also classified as a game or an application from its
+//! `AppxManifest.xml`: a Game Explorer extension, an Xbox Live protocol (`ms-xbl-<title
+//! ID>`) or a `MicrosoftGame.config` next to the manifest (Xbox Game Pass / GDK titles)
+//! marks a game. [`enumerate_packages_filtered`] returns only one kind.
 
 use crate::Result;
 
 #[cfg(windows)]
 use windows::Storage::Streams::RandomAccessStreamReference;
 
-/// Metadata for an installed UWP package
+/// Which user accounts to enumerate packages for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PackageEnumerationScope {
+    /// Packages registered for the current user (no elevation required)
+    #[default]
+    CurrentUser,
+    /// Packages installed for any user on the machine (requires elevation)
+    AllUsers,
+}
+
+/// Kind of application a package contains, as declared by its manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PackageKind {
+    /// Regular application, or a package whose manifest could not be read
+    #[default]
+    App,
+    /// Game registered with Game Explorer, Xbox Live or the Microsoft Game Core
+    Game,
+}
+
+/// Metadata for an application within an installed UWP package
 ///
-/// Contains information needed to display and monitor a UWP application.
+/// Contains information needed to display and monitor a UWP application. Packages with
+/// several applications produce one `UwpPackageInfo` per application.
 /// The `package_family_name` serves as the stable identifier for process detection.
 ///
 /// # Icon Handling
@@ -59,12 +93,24 @@ pub struct UwpPackageInfo {
     /// Stable package identifier (e.g., "`Microsoft.WindowsCalculator_8wekyb3d8bbwe`")
     pub package_family_name: String,
 
-    /// Application ID within the package (typically "App" for main executable)
+    /// Application ID within the package (from the AUMID; "App" if unavailable)
     pub app_id: String,
 
     /// Publisher display name (e.g., "Microsoft Corporation")
     pub publisher_display_name: String,
 
+    /// Whether the package is registered for the current user
+    ///
+    /// Packages found only through all-users enumeration may not be launchable
+    /// (and therefore never detected) under the current account.
+    pub installed_for_current_user: bool,
+
+    /// Whether the package contains more than one application
+    pub multi_app_package: bool,
+
+    /// Whether the package is a game, from its manifest
+    pub kind: PackageKind,
+
     /// Optional stream reference to logo/icon (Windows Runtime API)
     #[cfg(windows)]
     pub logo_stream: Option<RandomAccessStreamReference>,
@@ -113,10 +159,34 @@ pub struct UwpPackageInfo {
 /// ```
 #[cfg(windows)]
 pub fn enumerate_packages() -> Result<Vec<UwpPackageInfo>> {
+    enumerate_packages_for_scope(PackageEnumerationScope::CurrentUser)
+}
+
+/// Enumerate installed UWP packages for the given scope
+///
+/// With [`PackageEnumerationScope::CurrentUser`] this behaves like [`enumerate_packages`].
+/// With [`PackageEnumerationScope::AllUsers`] the current user's packages are listed
+/// first, followed by packages only installed for other accounts (marked with
+/// `installed_for_current_user = false`). Duplicate applications (same AUMID) are collapsed.
+///
+/// # Errors
+///
+/// Returns [`EasyHdrError::UwpElevationRequired`](crate::EasyHdrError::UwpElevationRequired)
+/// if all-users enumeration is requested from a non-elevated process, and
+/// `UwpEnumerationError` if `PackageManager` fails.
+#[cfg(windows)]
+pub fn enumerate_packages_for_scope(scope: PackageEnumerationScope) -> Result<Vec<UwpPackageInfo>> {
     use crate::EasyHdrError;
+    use std::collections::HashSet;
     use windows::Management::Deployment::PackageManager;
     use windows::core::HSTRING;
 
+    // Check elevation up front so the caller gets a clear error instead of an
+    // opaque access-denied HRESULT from FindPackages
+    if scope == PackageEnumerationScope::AllUsers && !crate::utils::is_elevated() {
+        return Err(EasyHdrError::UwpElevationRequired);
+    }
+
     // Create PackageManager instance
     let package_manager =
         PackageManager::new().map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
@@ -129,14 +199,106 @@ pub fn enumerate_packages() -> Result<Vec<UwpPackageInfo>> {
         .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
 
     let mut result = Vec::new();
+    let mut seen_aumids = HashSet::new();
+
+    collect_package_infos(packages, true, &mut seen_aumids, &mut result);
+
+    if scope == PackageEnumerationScope::AllUsers {
+        let all_packages = package_manager
+            .FindPackages()
+            .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
+
+        collect_package_infos(all_packages, false, &mut seen_aumids, &mut result);
+    }
+
+    Ok(result)
+}
+
+/// Enumerate the current user's installed UWP packages of one kind
+///
+/// Like [`enumerate_packages`], but only returns applications whose package is of the
+/// given [`PackageKind`], e.g. `PackageKind::Game` for a clean list of installed games.
+///
+/// # Errors
+///
+/// Returns error if `PackageManager` fails, like [`enumerate_packages`].
+#[cfg(windows)]
+pub fn enumerate_packages_filtered(kind: PackageKind) -> Result<Vec<UwpPackageInfo>> {
+    let mut packages = enumerate_packages()?;
+    packages.retain(|pkg| pkg.kind == kind);
+    Ok(packages)
+}
+
+/// Classify a package from the text of its `AppxManifest.xml`
+///
+/// Looks for a `windows.gameExplorer` extension or an Xbox Live `ms-xbl-` protocol.
+/// Matching is textual and case-insensitive, so namespace prefixes don't matter.
+pub fn classify_manifest(manifest: &str) -> PackageKind {
+    let manifest = manifest.to_ascii_lowercase();
+    let game_explorer = ["\"windows.gameexplorer\"", "'windows.gameexplorer'"]
+        .iter()
+        .any(|category| manifest.contains(category));
+    let xbox_live = manifest.contains("\"ms-xbl-") || manifest.contains("'ms-xbl-");
+
+    if game_explorer || xbox_live {
+        PackageKind::Game
+    } else {
+        PackageKind::App
+    }
+}
+
+/// Classify an installed package from the files in its install directory
+///
+/// Packages whose manifest can't be read (e.g. other users' packages) count as
+/// applications.
+#[cfg(windows)]
+fn package_kind(package: &windows::ApplicationModel::Package) -> PackageKind {
+    use std::path::PathBuf;
+    use tracing::debug;
+
+    let Ok(install_path) = package.InstalledPath() else {
+        return PackageKind::App;
+    };
+    let install_path = PathBuf::from(install_path.to_string());
 
+    // GDK titles (Xbox Game Pass for PC) ship their game configuration next to the manifest
+    if install_path.join("MicrosoftGame.config").is_file() {
+        return PackageKind::Game;
+    }
+
+    match std::fs::read_to_string(install_path.join("AppxManifest.xml")) {
+        Ok(manifest) => classify_manifest(&manifest),
+        Err(e) => {
+            debug!(
+                "Failed to read manifest in '{}': {}",
+                install_path.display(),
+                e
+            );
+            PackageKind::App
+        }
+    }
+}
+
+/// Extract metadata from each package, skipping filtered packages and applications already seen
+#[cfg(windows)]
+fn collect_package_infos(
+    packages: impl IntoIterator<Item = windows::ApplicationModel::Package>,
+    installed_for_current_user: bool,
+    seen_aumids: &mut std::collections::HashSet<String>,
+    result: &mut Vec<UwpPackageInfo>,
+) {
     // Iterate through packages
     for package in packages {
         // Extract package metadata, skip on error (log and continue)
-        match extract_package_info(&package) {
-            Ok(Some(info)) => result.push(info),
-            Ok(None) => {
-                // Package was filtered (framework or system package)
+        match extract_package_info(&package, installed_for_current_user) {
+            Ok(infos) => {
+                // Empty when the package was filtered (framework or system package)
+                for info in infos {
+                    let aumid = format!("{}!{}", info.package_family_name, info.app_id);
+                    if seen_aumids.insert(aumid) {
+                        result.push(info);
+                    }
+                }
             }
             Err(e) => {
                 // Log error but continue processing other packages
@@ -144,18 +306,20 @@ pub fn enumerate_packages() -> Result<Vec<UwpPackageInfo>> {
             }
         }
     }
-
-    Ok(result)
 }
 
-/// Extract package information from a `WinRT` Package object
+/// Extract per-application information from a `WinRT` Package object
 ///
-/// Returns `Ok(None)` if package should be filtered (e.g., framework package)
+/// Returns one entry per `AppListEntry` in the package, or an empty vector if the
+/// package should be filtered (e.g., framework package). Packages without app list
+/// entries yield a single package-level entry with the default "App" ID.
 #[cfg(windows)]
 fn extract_package_info(
     package: &windows::ApplicationModel::Package,
-) -> Result<Option<UwpPackageInfo>> {
+    installed_for_current_user: bool,
+) -> Result<Vec<UwpPackageInfo>> {
     use crate::EasyHdrError;
+    use crate::utils::clean_display_name;
 
     // Check if this is a framework package - skip if so
     let is_framework = package
@@ -163,7 +327,7 @@ fn extract_package_info(
         .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?;
 
     if is_framework {
-        return Ok(None);
+        return Ok(Vec::new());
     }
 
     // Get package ID
@@ -178,14 +342,16 @@ fn extract_package_info(
         .to_string();
 
     // Get display name
-    let display_name = package
-        .DisplayName()
-        .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?
-        .to_string();
+    let package_display_name = clean_display_name(
+        &package
+            .DisplayName()
+            .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?
+            .to_string(),
+    );
 
     // Skip packages with empty display names (typically system packages)
-    if display_name.is_empty() {
-        return Ok(None);
+    if package_display_name.is_empty() {
+        return Ok(Vec::new());
     }
 
     // Get publisher display name
@@ -194,115 +360,145 @@ fn extract_package_info(
         .map_err(|e| EasyHdrError::UwpEnumerationError(Box::new(e)))?
         .to_string();
 
-    // Get logo stream reference using AppListEntry API
-    // This is the recommended approach that automatically handles scale variants
-    let logo_stream = get_app_logo_stream(package);
-
-    // App ID is typically "App" for the main application
-    // This could be extracted from the package manifest, but "App" is the standard
-    let app_id = String::from("App");
-
-    Ok(Some(UwpPackageInfo {
-        display_name,
-        package_family_name,
-        app_id,
-        publisher_display_name,
-        logo_stream,
-    }))
+    let entries = get_app_list_entries(package, &package_display_name);
+    let kind = package_kind(package);
+
+    if entries.is_empty() {
+        // No app list entries; fall back to the standard "App" ID at package level
+        return Ok(vec![UwpPackageInfo {
+            display_name: package_display_name,
+            package_family_name,
+            app_id: String::from("App"),
+            publisher_display_name,
+            installed_for_current_user,
+            multi_app_package: false,
+            kind,
+            logo_stream: None,
+        }]);
+    }
+
+    let multi_app_package = entries.len() > 1;
+
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            let app_id = get_entry_app_id(entry).unwrap_or_else(|| String::from("App"));
+
+            // Prefer the per-application name; multi-app packages often share one package name
+            let display_name = entry
+                .DisplayInfo()
+                .and_then(|info| info.DisplayName())
+                .map(|name| clean_display_name(&name.to_string()))
+                .ok()
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| package_display_name.clone());
+
+            // Get logo stream reference using AppListEntry API
+            // This is the recommended approach that automatically handles scale variants
+            let logo_stream = get_app_logo_stream(entry, &display_name);
+
+            UwpPackageInfo {
+                display_name,
+                package_family_name: package_family_name.clone(),
+                app_id,
+                publisher_display_name: publisher_display_name.clone(),
+                installed_for_current_user,
+                multi_app_package,
+                kind,
+                logo_stream,
+            }
+        })
+        .collect())
 }
 
-/// Get app logo stream using Windows Runtime `AppListEntry` API
-///
-/// Uses the recommended `AppListEntry.DisplayInfo.GetLogo()` API to retrieve
-/// a logo stream reference. This approach automatically handles:
-/// - Scale variant selection (100%, 125%, 150%, 200%, etc.) based on system DPI
-/// - Missing icon files (returns None instead of failing)
-/// - Permission issues (gracefully degrades to None)
-///
-/// # Arguments
-///
-/// * `package` - Reference to the Windows Runtime Package object
-///
-/// # Returns
+/// Get all `AppListEntry` objects (one per application) for a package
 ///
-/// `Option<RandomAccessStreamReference>` - Stream reference if logo is available
-///
-/// # Implementation Note
-///
-/// Uses blocking `.get()` on async operations rather than requiring an async runtime.
-/// This is acceptable because icon loading happens during enumeration, not in
-/// performance-critical paths.
+/// Uses blocking `.join()` on the async operation rather than requiring an async runtime.
+/// This is acceptable because enumeration runs on a background thread and is cached.
 #[cfg(windows)]
-fn get_app_logo_stream(
+fn get_app_list_entries(
     package: &windows::ApplicationModel::Package,
-) -> Option<RandomAccessStreamReference> {
-    use tracing::{debug, warn};
-    use windows::Foundation::Size;
+    package_display_name: &str,
+) -> Vec<windows::ApplicationModel::Core::AppListEntry> {
+    use tracing::debug;
 
-    // Try to get the first AppListEntry for this package
     let entries_async = match package.GetAppListEntriesAsync() {
         Ok(async_op) => async_op,
         Err(e) => {
             debug!(
                 "Failed to call GetAppListEntriesAsync for package '{}': {}",
-                package.DisplayName().unwrap_or_default().to_string_lossy(),
-                e
+                package_display_name, e
             );
-            return None;
+            return Vec::new();
         }
     };
 
     // Block on the async operation using .join()
-    let entries = match entries_async.join() {
-        Ok(entries) => entries,
+    match entries_async.join() {
+        Ok(entries) => {
+            let entries: Vec<_> = entries.into_iter().collect();
+            if entries.is_empty() {
+                debug!("Package '{}' has no app list entries", package_display_name);
+            }
+            entries
+        }
         Err(e) => {
             debug!(
                 "Failed to await GetAppListEntriesAsync for package '{}': {}",
-                package.DisplayName().unwrap_or_default().to_string_lossy(),
-                e
+                package_display_name, e
             );
-            return None;
+            Vec::new()
         }
-    };
+    }
+}
 
-    // Check if we have at least one entry
-    let entry_count = match entries.Size() {
-        Ok(count) => count,
-        Err(e) => {
-            debug!("Failed to get entry count: {}", e);
-            return None;
-        }
-    };
+/// Extract the application ID (the part after `!`) from an entry's AUMID
+#[cfg(windows)]
+fn get_entry_app_id(entry: &windows::ApplicationModel::Core::AppListEntry) -> Option<String> {
+    let aumid = entry.AppUserModelId().ok()?.to_string();
+    split_aumid(&aumid).map(|(_, app_id)| app_id.to_string())
+}
 
-    if entry_count == 0 {
-        debug!(
-            "Package '{}' has no app list entries",
-            package.DisplayName().unwrap_or_default().to_string_lossy()
-        );
-        return None;
-    }
+/// Split an AUMID (`PackageFamilyName!AppId`) into its family name and application ID
+///
+/// Returns `None` if the string is not a well-formed AUMID.
+pub fn split_aumid(aumid: &str) -> Option<(&str, &str)> {
+    aumid
+        .split_once('!')
+        .filter(|(family_name, app_id)| !family_name.is_empty() && !app_id.is_empty())
+}
 
-    // Get the first entry
-    let entry = match entries.GetAt(0) {
-        Ok(entry) => entry,
-        Err(e) => {
-            warn!(
-                "Failed to get first entry for package '{}': {}",
-                package.DisplayName().unwrap_or_default().to_string_lossy(),
-                e
-            );
-            return None;
-        }
-    };
+/// Get app logo stream using Windows Runtime `AppListEntry` API
+///
+/// Uses the recommended `AppListEntry.DisplayInfo.GetLogo()` API to retrieve
+/// a logo stream reference. This approach automatically handles:
+/// - Scale variant selection (100%, 125%, 150%, 200%, etc.) based on system DPI
+/// - Missing icon files (returns None instead of failing)
+/// - Permission issues (gracefully degrades to None)
+///
+/// # Arguments
+///
+/// * `entry` - The application's `AppListEntry`
+/// * `display_name` - Application name used in log messages
+///
+/// # Returns
+///
+/// `Option<RandomAccessStreamReference>` - Stream reference if logo is available
+#[cfg(windows)]
+fn get_app_logo_stream(
+    entry: &windows::ApplicationModel::Core::AppListEntry,
+    display_name: &str,
+) -> Option<RandomAccessStreamReference> {
+    use tracing::debug;
+    use windows::Foundation::Size;
 
     // Get DisplayInfo
     let display_info = match entry.DisplayInfo() {
         Ok(info) => info,
         Err(e) => {
             debug!(
-                "Failed to get DisplayInfo for package '{}': {}",
-                package.DisplayName().unwrap_or_default().to_string_lossy(),
-                e
+                "Failed to get DisplayInfo for application '{}': {}",
+                display_name, e
             );
             return None;
         }
@@ -321,16 +517,15 @@ fn get_app_logo_stream(
     match display_info.GetLogo(size) {
         Ok(logo_stream) => {
             debug!(
-                "Successfully retrieved logo stream for package '{}'",
-                package.DisplayName().unwrap_or_default().to_string_lossy()
+                "Successfully retrieved logo stream for application '{}'",
+                display_name
             );
             Some(logo_stream)
         }
         Err(e) => {
             debug!(
-                "Failed to get logo for package '{}': {}. This is normal for some system packages.",
-                package.DisplayName().unwrap_or_default().to_string_lossy(),
-                e
+                "Failed to get logo for application '{}': {}. This is normal for some system packages.",
+                display_name, e
             );
             None
         }
@@ -342,6 +537,18 @@ pub fn enumerate_packages() -> Result<Vec<UwpPackageInfo>> {
     Ok(Vec::new())
 }
 
+#[cfg(not(windows))]
+pub fn enumerate_packages_for_scope(
+    _scope: PackageEnumerationScope,
+) -> Result<Vec<UwpPackageInfo>> {
+    Ok(Vec::new())
+}
+
+#[cfg(not(windows))]
+pub fn enumerate_packages_filtered(_kind: PackageKind) -> Result<Vec<UwpPackageInfo>> {
+    Ok(Vec::new())
+}
+
 #[cfg(test)]
 #[expect(clippy::unwrap_used)]
 mod tests {
@@ -398,7 +605,7 @@ mod tests {
                 "Package family name should not be empty"
             );
 
-            // App ID should not be empty (we set it to "App")
+            // App ID should not be empty (from the AUMID, or "App" as fallback)
             assert!(!pkg.app_id.is_empty(), "App ID should not be empty");
 
             // Publisher display name may or may not be empty, so we don't assert on it
@@ -446,6 +653,125 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(windows)]
+    fn test_current_user_packages_are_marked_launchable() {
+        let packages = enumerate_packages_for_scope(PackageEnumerationScope::CurrentUser).unwrap();
+
+        assert!(packages.iter().all(|pkg| pkg.installed_for_current_user));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_all_users_scope_respects_elevation() {
+        let result = enumerate_packages_for_scope(PackageEnumerationScope::AllUsers);
+
+        if crate::utils::is_elevated() {
+            let packages = result.unwrap();
+            let mut family_names: Vec<_> = packages
+                .iter()
+                .map(|pkg| &pkg.package_family_name)
+                .collect();
+            let total = family_names.len();
+            family_names.sort();
+            family_names.dedup();
+            assert_eq!(family_names.len(), total, "Family names should be unique");
+        } else {
+            assert!(matches!(
+                result,
+                Err(crate::EasyHdrError::UwpElevationRequired)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_split_aumid() {
+        assert_eq!(
+            split_aumid("Microsoft.WindowsCalculator_8wekyb3d8bbwe!App"),
+            Some(("Microsoft.WindowsCalculator_8wekyb3d8bbwe", "App"))
+        );
+        assert_eq!(
+            split_aumid("Microsoft.WindowsCalculator_8wekyb3d8bbwe"),
+            None
+        );
+        assert_eq!(split_aumid("!App"), None);
+        assert_eq!(split_aumid("Family_abc!"), None);
+    }
+
+    #[test]
+    fn test_classify_manifest() {
+        let game_explorer = r#"<Package>
+  <Applications>
+    <Application Id="App" Executable="Game.exe">
+      <Extensions>
+        <uap:Extension Category="windows.gameExplorer">
+          <uap:GameExplorer GameDefinitionFile="Game.gdf" />
+        </uap:Extension>
+      </Extensions>
+    </Application>
+  </Applications>
+</Package>"#;
+        assert_eq!(classify_manifest(game_explorer), PackageKind::Game);
+
+        let xbox_live = r#"<Package>
+  <Applications>
+    <Application Id="Game">
+      <Extensions>
+        <uap:Extension Category="windows.protocol">
+          <uap:Protocol Name="ms-xbl-6c1e8e4b" />
+        </uap:Extension>
+      </Extensions>
+    </Application>
+  </Applications>
+</Package>"#;
+        assert_eq!(classify_manifest(xbox_live), PackageKind::Game);
+
+        // Mentioning Xbox in a name or another protocol is not enough
+        let app = r#"<Package>
+  <Properties><DisplayName>Xbox Accessories</DisplayName></Properties>
+  <Applications>
+    <Application Id="App">
+      <Extensions>
+        <uap:Extension Category="windows.protocol">
+          <uap:Protocol Name="ms-calculator" />
+        </uap:Extension>
+      </Extensions>
+    </Application>
+  </Applications>
+</Package>"#;
+        assert_eq!(classify_manifest(app), PackageKind::App);
+        assert_eq!(classify_manifest(""), PackageKind::App);
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_filtered_enumeration_returns_only_that_kind() {
+        let games = enumerate_packages_filtered(PackageKind::Game).unwrap();
+        assert!(games.iter().all(|pkg| pkg.kind == PackageKind::Game));
+
+        let all = enumerate_packages().unwrap();
+        let apps = enumerate_packages_filtered(PackageKind::App).unwrap();
+        assert_eq!(games.len() + apps.len(), all.len());
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_multi_app_packages_have_distinct_app_ids() {
+        let packages = enumerate_packages().unwrap();
+
+        for pkg in packages.iter().filter(|pkg| pkg.multi_app_package) {
+            let siblings = packages
+                .iter()
+                .filter(|other| other.package_family_name == pkg.package_family_name)
+                .count();
+            assert!(
+                siblings > 1,
+                "Multi-app package '{}' should list several applications",
+                pkg.package_family_name
+            );
+        }
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn test_enumerate_packages_non_windows() {
@@ -5,6 +5,9 @@
 //! Uses package family names for stable identification across version updates.
 
 // Submodule declarations (Windows-only)
+#[cfg(windows)]
+pub mod cache;
+
 #[cfg(windows)]
 pub mod detector;
 
@@ -16,10 +19,19 @@ pub mod icon;
 
 // Public API re-exports
 #[cfg(windows)]
-pub use detector::{detect_uwp_process, extract_package_family_name};
+pub use cache::{UwpPackageCache, get_package_cache};
+
+#[cfg(windows)]
+pub use detector::{
+    detect_uwp_application_user_model_id, detect_uwp_process, extract_package_family_name,
+    watch_running_apps,
+};
 
 #[cfg(windows)]
-pub use enumerator::{UwpPackageInfo, enumerate_packages};
+pub use enumerator::{
+    PackageEnumerationScope, PackageKind, UwpPackageInfo, classify_manifest, enumerate_packages,
+    enumerate_packages_filtered, enumerate_packages_for_scope, split_aumid,
+};
 
 #[cfg(windows)]
 pub use icon::{extract_icon, extract_icon_from_stream};
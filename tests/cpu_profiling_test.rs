@@ -22,10 +22,10 @@
 
 #![cfg(windows)]
 
-use easyhdr::config::{AppConfig, MonitoredApp, UserPreferences, Win32App, WindowState};
+use easyhdr::config::{AppConfig, AppSource, MonitoredApp, UserPreferences, Win32App, WindowState};
 use easyhdr::controller::AppController;
-use easyhdr::monitor::{ProcessMonitor, WatchState};
-use parking_lot::RwLock;
+use easyhdr::hdr::AutoHdrMode;
+use easyhdr::monitor::{ProcessMonitor, SharedWatchState};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::mpsc;
@@ -119,8 +119,11 @@ fn create_profiling_config() -> AppConfig {
 
     AppConfig {
         monitored_apps: create_monitored_apps(),
+        excluded_processes: Vec::new(),
         preferences,
         window_state: WindowState::default(),
+        known_displays: Vec::new(),
+        display_groups: Vec::new(),
     }
 }
 
@@ -135,6 +138,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe"),
             process_name: "chrome".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -143,6 +156,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\Mozilla Firefox\\firefox.exe"),
             process_name: "firefox".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -151,6 +174,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\obs-studio\\bin\\64bit\\obs64.exe"),
             process_name: "obs64".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -159,6 +192,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\Microsoft VS Code\\Code.exe"),
             process_name: "code".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -167,6 +210,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Windows\\System32\\notepad.exe"),
             process_name: "notepad".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
     ]
@@ -209,7 +262,7 @@ fn profile_handle_process_event_throughput() {
     let (state_tx, state_rx) = mpsc::sync_channel(32);
     // Use double-Arc pattern to match production implementation
     let _apps = create_monitored_apps();
-    let watch_state = Arc::new(RwLock::new(WatchState::new()));
+    let watch_state = Arc::new(SharedWatchState::default());
 
     let mut controller =
         AppController::new(config, process_rx, hdr_state_rx, state_tx, watch_state)
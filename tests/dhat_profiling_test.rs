@@ -29,8 +29,13 @@
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
-use easyhdr::config::models::Win32App;
-use easyhdr::config::{AppConfig, MonitoredApp, UserPreferences, WindowState};
+use easyhdr::config::models::{AppSource, Win32App};
+use easyhdr::config::{
+    AppConfig, AutoStartMethod, BatteryHdrPolicy, MonitoredApp, ShutdownPolicy, UpdateChannel,
+    UserPreferences, WindowState,
+};
+use easyhdr::hdr::AutoHdrMode;
+use easyhdr::utils::{LogFormat, LogLevel, UpdateCheckCache};
 use std::path::PathBuf;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -53,17 +58,50 @@ use std::time::{Duration, Instant};
 fn create_realistic_config(num_apps: usize) -> AppConfig {
     let mut config = AppConfig {
         monitored_apps: Vec::with_capacity(num_apps),
+        excluded_processes: Vec::new(),
         preferences: UserPreferences {
             auto_start: false,
+            autostart_method: AutoStartMethod::Registry,
+            autostart_elevated: false,
+            autostart_delay_secs: 0,
             monitoring_interval_ms: 1000,
             show_tray_notifications: false,
             show_update_notifications: false,
             auto_open_release_page: false,
+            update_channel: UpdateChannel::Stable,
             minimize_to_tray_on_minimize: false,
             minimize_to_tray_on_close: false,
             start_minimized_to_tray: false,
             last_update_check_time: 0,
             cached_latest_version: String::new(),
+            update_check_cache: UpdateCheckCache::default(),
+            uwp_enumerate_all_users: false,
+            track_child_processes: true,
+            wait_for_window: false,
+            hdr_disable_debounce_ms: 500,
+            weekly_summary_notification: false,
+            last_weekly_summary_time: 0,
+            log_level: LogLevel::Info,
+            log_format: LogFormat::Text,
+            shutdown_policy: ShutdownPolicy::LeaveAsIs,
+            max_hdr_session_minutes: 0,
+            external_change_hold_minutes: 0,
+            battery_hdr_policy: BatteryHdrPolicy::Allow,
+            battery_hdr_threshold_percent: 20,
+            icon_cache_max_mb: 16,
+            adaptive_polling: false,
+            max_idle_polling_interval_ms: 5000,
+            suspend_night_light: false,
+            onboarding_completed: true,
+            language: String::new(),
+            allow_unsupported_windows: false,
+            dry_run: false,
+            write_event_log: false,
+            metrics_enabled: false,
+            trace_hdr_api: false,
+            discord_presence: false,
+            discord_client_id: String::new(),
+            run_in_background: false,
         },
         window_state: WindowState {
             x: 100,
@@ -71,6 +109,8 @@ fn create_realistic_config(num_apps: usize) -> AppConfig {
             width: 800,
             height: 600,
         },
+        known_displays: Vec::new(),
+        display_groups: Vec::new(),
     };
 
     // Realistic application paths that might be monitored
@@ -134,6 +174,16 @@ fn create_realistic_config(num_apps: usize) -> AppConfig {
             exe_path: PathBuf::from(path),
             process_name: process_name.to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
     }
@@ -444,8 +494,11 @@ fn create_profiling_config() -> AppConfig {
 
     AppConfig {
         monitored_apps: create_monitored_apps(),
+        excluded_processes: Vec::new(),
         preferences,
         window_state: WindowState::default(),
+        known_displays: Vec::new(),
+        display_groups: Vec::new(),
     }
 }
 
@@ -461,6 +514,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe"),
             process_name: "chrome".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -469,6 +532,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\Mozilla Firefox\\firefox.exe"),
             process_name: "firefox".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -477,6 +550,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\obs-studio\\bin\\64bit\\obs64.exe"),
             process_name: "obs64".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -485,6 +568,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Program Files\\Microsoft VS Code\\Code.exe"),
             process_name: "code".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
         MonitoredApp::Win32(Win32App {
@@ -493,6 +586,16 @@ fn create_monitored_apps() -> Vec<MonitoredApp> {
             exe_path: PathBuf::from("C:\\Windows\\System32\\notepad.exe"),
             process_name: "notepad".to_string(),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }),
     ]
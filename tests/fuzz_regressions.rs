@@ -0,0 +1,55 @@
+#![expect(clippy::unwrap_used)]
+//! Replays inputs found by the fuzz targets
+//!
+//! Inputs the fuzzer produced are saved in `fuzz/regressions/<target>/`: minimized
+//! crashers (`cargo +nightly fuzz tmin <target> <crash>`) and a minimized corpus
+//! (`cargo +nightly fuzz cmin <target>`). They run here through the checks the targets
+//! share (`fuzz/checks.rs`), so a fixed crash stays fixed without nightly Rust or
+//! cargo-fuzz.
+
+#[path = "../fuzz/checks.rs"]
+mod checks;
+
+use std::panic::{self, RefUnwindSafe};
+use std::path::PathBuf;
+
+/// Inputs saved for a fuzz target
+fn regressions(target: &str) -> Vec<(PathBuf, Vec<u8>)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz")
+        .join("regressions")
+        .join(target);
+    let mut inputs: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let data = std::fs::read(&path).unwrap();
+            (path, data)
+        })
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+/// Run `check` on every input saved for a fuzz target, naming the input that fails
+fn replay(target: &str, check: impl Fn(&[u8]) + RefUnwindSafe) {
+    for (path, data) in regressions(target) {
+        let result = panic::catch_unwind(|| check(&data));
+        assert!(result.is_ok(), "{} input {} failed", target, path.display());
+    }
+}
+
+#[test]
+fn test_fuzz_config_json_regressions() {
+    replay("fuzz_config_json", checks::config_json);
+}
+
+#[test]
+fn test_fuzz_monitored_app_json_regressions() {
+    replay("fuzz_monitored_app_json", checks::monitored_app_json);
+}
+
+#[test]
+fn test_fuzz_agent_protocol_regressions() {
+    replay("fuzz_agent_protocol", checks::agent_protocol);
+}
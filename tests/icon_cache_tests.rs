@@ -25,8 +25,9 @@
 use easyhdr::{
     config::{
         AppConfig,
-        models::{MonitoredApp, Win32App},
+        models::{AppSource, MonitoredApp, Win32App},
     },
+    hdr::AutoHdrMode,
     utils::IconCache,
 };
 use std::fs::File;
@@ -88,6 +89,16 @@ fn test_icons_persist_across_restarts() {
         exe_path: exe_path_1.clone(),
         process_name: "app1".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: Some(test_icon_data_1.clone()),
     };
 
@@ -97,6 +108,16 @@ fn test_icons_persist_across_restarts() {
         exe_path: exe_path_2.clone(),
         process_name: "app2".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: Some(test_icon_data_2.clone()),
     };
 
@@ -134,6 +155,11 @@ fn test_icons_persist_across_restarts() {
                     uwp.icon_data = Some(icon_data);
                 }
             }
+            MonitoredApp::Folder(folder) => {
+                if let Ok(Some(icon_data)) = cache.load_icon(folder.id, None) {
+                    folder.icon_data = Some(icon_data);
+                }
+            }
         }
     }
 
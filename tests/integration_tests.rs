@@ -5,14 +5,13 @@
 //! and error handling for the full application lifecycle.
 
 use easyhdr::{
-    config::models::Win32App,
+    config::models::{AppSource, Win32App},
     config::{AppConfig, ConfigManager, MonitoredApp},
     controller::AppController,
     error::{EasyHdrError, get_user_friendly_error},
-    hdr::HdrController,
-    monitor::{ProcessEvent, ProcessMonitor, WatchState},
+    hdr::{AutoHdrMode, HdrController},
+    monitor::{ProcessEvent, ProcessMonitor, SharedWatchState},
 };
-use parking_lot::RwLock;
 use std::path::PathBuf;
 use std::sync::{Arc, mpsc};
 use std::thread;
@@ -35,6 +34,16 @@ fn test_config_persistence_integration() {
         exe_path: PathBuf::from("C:\\Games\\test.exe"),
         process_name: "test".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: None,
     }));
 
@@ -76,6 +85,16 @@ fn test_process_monitor_integration() {
         exe_path: PathBuf::from("C:\\Windows\\notepad.exe"),
         process_name: "notepad".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: None,
     })]);
 
@@ -117,10 +136,20 @@ fn test_app_controller_hdr_logic_integration() {
         exe_path: PathBuf::from("C:\\Games\\test.exe"),
         process_name: "testgame".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: None,
     }));
 
-    let watch_state = Arc::new(RwLock::new(WatchState::new()));
+    let watch_state = Arc::new(SharedWatchState::default());
 
     // Create the controller
     let controller = AppController::new(config, event_rx, hdr_state_rx, state_tx, watch_state);
@@ -192,6 +221,16 @@ fn test_multiple_apps_integration() {
         exe_path: PathBuf::from("C:\\Games\\game1.exe"),
         process_name: "game1".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: None,
     }));
     config.monitored_apps.push(MonitoredApp::Win32(Win32App {
@@ -200,10 +239,20 @@ fn test_multiple_apps_integration() {
         exe_path: PathBuf::from("C:\\Games\\game2.exe"),
         process_name: "game2".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: None,
     }));
 
-    let watch_state = Arc::new(RwLock::new(WatchState::new()));
+    let watch_state = Arc::new(SharedWatchState::default());
 
     let controller = AppController::new(config, event_rx, hdr_state_rx, state_tx, watch_state);
 
@@ -227,10 +276,20 @@ fn test_disabled_apps_ignored() {
         exe_path: PathBuf::from("C:\\Games\\disabled.exe"),
         process_name: "disabled".to_string(),
         enabled: false, // Disabled
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: None,
     }));
 
-    let watch_state = Arc::new(RwLock::new(WatchState::new()));
+    let watch_state = Arc::new(SharedWatchState::default());
 
     let controller = AppController::new(config, event_rx, hdr_state_rx, state_tx, watch_state);
 
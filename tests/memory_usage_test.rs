@@ -3,7 +3,8 @@
 //!
 //! This test module verifies that the application uses less than 50MB RAM during monitoring.
 
-use easyhdr::config::models::{AppConfig, MonitoredApp, UwpApp, Win32App};
+use easyhdr::config::models::{AppConfig, AppSource, MonitoredApp, UwpApp, Win32App};
+use easyhdr::hdr::AutoHdrMode;
 use easyhdr::utils::memory_profiler;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -69,6 +70,16 @@ fn test_config_memory_estimation() {
             exe_path: PathBuf::from(format!("C:\\Apps\\app{i}.exe")),
             process_name: format!("app{i}"),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None, // No icons for this test
         }));
     }
@@ -92,6 +103,16 @@ fn test_monitored_app_release_icon() {
         exe_path: PathBuf::from("C:\\test.exe"),
         process_name: "test".to_string(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: Some(vec![0u8; 4096]), // 4 KB icon
     });
 
@@ -269,6 +290,14 @@ fn test_uwp_app_memory_size() {
         package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
         app_id: "App".to_string(),
         enabled: true,
+        match_app_id: false,
+        source: AppSource::Manual,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
         icon_data: None,
     };
 
@@ -299,6 +328,14 @@ fn test_uwp_app_icon_memory() {
         package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
         app_id: "App".to_string(),
         enabled: true,
+        match_app_id: false,
+        source: AppSource::Manual,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
         icon_data: Some(vec![0u8; 4096]), // 4 KB icon (32x32 RGBA)
     };
 
@@ -319,6 +356,14 @@ fn test_uwp_config_memory_estimation() {
             package_family_name: format!("Publisher.AppName{i}_8wekyb3d8bbwe"),
             app_id: "App".to_string(),
             enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: None,
         }));
     }
@@ -351,6 +396,16 @@ fn test_mixed_app_types_config_memory() {
             exe_path: PathBuf::from(format!("C:\\Apps\\app{i}.exe")),
             process_name: format!("app{i}"),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: None,
         }));
     }
@@ -363,6 +418,14 @@ fn test_mixed_app_types_config_memory() {
             package_family_name: format!("Publisher.AppName{i}_8wekyb3d8bbwe"),
             app_id: "App".to_string(),
             enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: None,
         }));
     }
@@ -394,6 +457,14 @@ fn test_uwp_metadata_memory_overhead() {
         package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
         app_id: "App".to_string(),
         enabled: true,
+        match_app_id: false,
+        source: AppSource::Manual,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
         icon_data: None,
     };
 
@@ -455,6 +526,16 @@ fn test_large_mixed_app_list_memory() {
             exe_path: PathBuf::from(format!("C:\\Apps\\app{i}.exe")),
             process_name: format!("app{i}"),
             enabled: true,
+            source: AppSource::Manual,
+            auto_hdr: AutoHdrMode::Default,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
+            match_rule: None,
+            aliases: Vec::new(),
             icon_data: Some(vec![0u8; 4096]), // 4 KB icon
         }));
         profiler.record_icon_cached(4096);
@@ -468,6 +549,14 @@ fn test_large_mixed_app_list_memory() {
             package_family_name: format!("Publisher.AppName{i}_8wekyb3d8bbwe"),
             app_id: "App".to_string(),
             enabled: true,
+            match_app_id: false,
+            source: AppSource::Manual,
+            display_mode: None,
+            hdr_disable_debounce_ms: None,
+            max_hdr_session_minutes: None,
+            color_mode: None,
+            capture_tone_mapping: None,
+            display_group: None,
             icon_data: Some(vec![0u8; 4096]), // 4 KB icon
         }));
         profiler.record_icon_cached(4096);
@@ -510,6 +599,14 @@ fn test_monitored_app_uwp_release_icon() {
         package_family_name: "Microsoft.WindowsCalculator_8wekyb3d8bbwe".to_string(),
         app_id: "App".to_string(),
         enabled: true,
+        match_app_id: false,
+        source: AppSource::Manual,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
         icon_data: Some(vec![0u8; 4096]), // 4 KB icon
     });
 
@@ -18,7 +18,8 @@
 
 #[cfg(windows)]
 use easyhdr::{
-    config::{MonitoredApp, UwpApp, Win32App},
+    config::{AppSource, MonitoredApp, UwpApp, Win32App},
+    hdr::AutoHdrMode,
     monitor::{AppIdentifier, ProcessEvent, ProcessMonitor},
 };
 
@@ -42,6 +43,16 @@ fn create_test_win32_app(process_name: &str, display_name: &str) -> MonitoredApp
         exe_path: PathBuf::from(format!("C:\\Windows\\{process_name}.exe")),
         process_name: process_name.to_lowercase(),
         enabled: true,
+        source: AppSource::Manual,
+        auto_hdr: AutoHdrMode::Default,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
+        match_rule: None,
+        aliases: Vec::new(),
         icon_data: None,
     })
 }
@@ -59,6 +70,14 @@ fn create_test_uwp_app(
         package_family_name: package_family_name.to_string(),
         app_id: app_id.to_string(),
         enabled: true,
+        match_app_id: false,
+        source: AppSource::Manual,
+        display_mode: None,
+        hdr_disable_debounce_ms: None,
+        max_hdr_session_minutes: None,
+        color_mode: None,
+        capture_tone_mapping: None,
+        display_group: None,
         icon_data: None,
     })
 }
@@ -429,10 +448,16 @@ fn test_app_identifier_discrimination() {
                 | ProcessEvent::Stopped(AppIdentifier::Win32(name)) => {
                     win32_apps.insert(name);
                 }
-                ProcessEvent::Started(AppIdentifier::Uwp(family_name))
-                | ProcessEvent::Stopped(AppIdentifier::Uwp(family_name)) => {
+                ProcessEvent::Started(
+                    AppIdentifier::Uwp(family_name) | AppIdentifier::UwpApplication(family_name),
+                )
+                | ProcessEvent::Stopped(
+                    AppIdentifier::Uwp(family_name) | AppIdentifier::UwpApplication(family_name),
+                ) => {
                     uwp_apps.insert(family_name);
                 }
+                ProcessEvent::Started(AppIdentifier::Folder(_))
+                | ProcessEvent::Stopped(AppIdentifier::Folder(_)) => {}
             }
         }
     }